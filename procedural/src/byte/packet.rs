@@ -52,6 +52,9 @@ pub fn derive_packet_struct(
 
             fn try_from_bytes(byte_stream: &mut crate::loaders::ByteStream) -> Result<Self, String> {
 
+                #[cfg(feature = "debug_network")]
+                let (start_offset, start_time) = (byte_stream.get_offset(), std::time::Instant::now());
+
                 let result = match byte_stream.match_signature(Self::header()) {
                     true => {
                         #(#from_bytes_implementations)*
@@ -62,7 +65,8 @@ pub fn derive_packet_struct(
 
                 #[cfg(feature = "debug_network")]
                 if let Ok(packet) = &result {
-                    byte_stream.incoming_packet(packet, Self::PACKET_NAME, Self::IS_PING);
+                    let size = byte_stream.get_offset() - start_offset;
+                    byte_stream.incoming_packet(packet, size, start_time.elapsed());
                 }
 
                 result
@@ -0,0 +1,385 @@
+mod settings;
+
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{BufReader, Cursor};
+use std::path::PathBuf;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use cgmath::{InnerSpace, Vector2, Vector3};
+use rodio::source::{Buffered, ChannelVolume};
+use rodio::{Decoder, OutputStream, OutputStreamHandle, Sink, Source};
+
+pub use self::settings::AudioSettings;
+#[cfg(feature = "debug")]
+use crate::debug::*;
+use crate::loaders::{FileData, GameFileLoader};
+use crate::world::SoundSource;
+
+/// Time in seconds it takes for the music of one map to fade into the music
+/// of the next one.
+const MUSIC_CROSSFADE_TIME: f32 = 2.0;
+/// How often the panning of a playing ambient sound is updated.
+const PANNING_UPDATE_PERIOD: Duration = Duration::from_millis(5);
+
+type SoundData = Buffered<Decoder<Cursor<FileData>>>;
+
+/// A music track that is currently playing or fading out.
+struct MusicTrack {
+    path: String,
+    sink: Sink,
+    /// Volume of the track relative to the music volume, from 0 to 1.
+    fade: f32,
+}
+
+/// A sound source of the map that is close enough to be heard.
+struct AmbientSound {
+    sink: Sink,
+    next_play: Instant,
+    /// Volume of the left and right channel, picked up by the sound while it
+    /// is playing.
+    panning: Arc<Mutex<[f32; 2]>>,
+}
+
+/// Volume of the left and right channel for a sound coming from `direction`,
+/// keeping the overall loudness the same. Both are given on the ground with
+/// north being positive y.
+fn panning(direction: Vector2<f32>, listener_direction: Vector2<f32>) -> [f32; 2] {
+    let right = Vector2::new(listener_direction.y, -listener_direction.x);
+    // From 0 for hard left to 1 for hard right.
+    let balance = (direction.dot(right).clamp(-1.0, 1.0) + 1.0) / 2.0;
+    let angle = balance * std::f32::consts::FRAC_PI_2;
+
+    [angle.cos(), angle.sin()]
+}
+
+pub struct AudioEngine {
+    /// The stream has to be kept alive for anything to be played. If there
+    /// is no output device, all sounds are silently ignored.
+    output: Option<(OutputStream, OutputStreamHandle)>,
+    settings: AudioSettings,
+    /// Sounds that finished loading, or `None` if they failed to.
+    cache: HashMap<String, Option<SoundData>>,
+    /// Sounds that are being loaded and decoded on another thread.
+    loading: HashSet<String>,
+    loaded_sender: Sender<(String, Option<SoundData>)>,
+    loaded_receiver: Receiver<(String, Option<SoundData>)>,
+    /// Sound effects waiting for their sound to finish loading.
+    queued_effects: Vec<String>,
+    ambient_sounds: HashMap<usize, AmbientSound>,
+    /// Directory that the music paths are relative to.
+    data_directory: PathBuf,
+    /// Music track of every map, loaded from the mp3 name table.
+    music_table: HashMap<String, String>,
+    music: Option<MusicTrack>,
+    fading_music: Vec<MusicTrack>,
+}
+
+impl AudioEngine {
+    pub fn new(game_file_loader: &mut GameFileLoader, data_directory: PathBuf) -> Self {
+        let output = OutputStream::try_default().ok();
+
+        #[cfg(feature = "debug")]
+        if output.is_none() {
+            print_debug!("{}failed to open audio output device{}", RED, NONE);
+        }
+
+        let music_table = game_file_loader
+            .get("data\\mp3nametable.txt")
+            .map(|bytes| Self::parse_music_table(&String::from_utf8_lossy(&bytes)))
+            .unwrap_or_default();
+
+        let (loaded_sender, loaded_receiver) = channel();
+
+        Self {
+            output,
+            settings: AudioSettings::new(),
+            cache: HashMap::new(),
+            loading: HashSet::new(),
+            loaded_sender,
+            loaded_receiver,
+            queued_effects: Vec::new(),
+            ambient_sounds: HashMap::new(),
+            data_directory,
+            music_table,
+            music: None,
+            fading_music: Vec::new(),
+        }
+    }
+
+    /// Every line of the table has the form `prontera.rsw#bgm\\08.mp3#`.
+    fn parse_music_table(data: &str) -> HashMap<String, String> {
+        data.lines()
+            .filter(|line| !line.starts_with("//"))
+            .filter_map(|line| {
+                let mut parts = line.split('#');
+                let map_name = parts.next()?.trim().strip_suffix(".rsw")?;
+                let path = parts.next()?.trim().replace("\\\\", "\\");
+
+                Some((map_name.to_lowercase(), path))
+            })
+            .collect()
+    }
+
+    pub fn get_settings(&self) -> &AudioSettings {
+        &self.settings
+    }
+
+    /// Returns the sound if it finished loading. Otherwise it is loaded and
+    /// decoded on another thread, so the game doesn't stutter, and `None` is
+    /// returned until it is done.
+    fn load(&mut self, game_file_loader: &GameFileLoader, path: &str) -> Option<SoundData> {
+        if let Some(sound) = self.cache.get(path) {
+            return sound.clone();
+        }
+
+        if self.loading.insert(path.to_owned()) {
+            let mut game_file_loader = game_file_loader.clone();
+            let loaded_sender = self.loaded_sender.clone();
+            let path = path.to_owned();
+
+            std::thread::spawn(move || {
+                let sound = game_file_loader
+                    .get(&format!("data\\wav\\{}", path))
+                    .ok()
+                    .and_then(|bytes| Decoder::new(Cursor::new(bytes)).ok())
+                    .map(Source::buffered);
+
+                // Going through the sound once decodes all of it, and the decoded samples are
+                // shared with every clone.
+                if let Some(sound) = &sound {
+                    sound.clone().for_each(drop);
+                }
+
+                // The engine might be gone already.
+                let _ = loaded_sender.send((path, sound));
+            });
+        }
+
+        None
+    }
+
+    /// Moves the sounds that finished loading into the cache.
+    fn receive_loaded_sounds(&mut self) {
+        while let Ok((path, sound)) = self.loaded_receiver.try_recv() {
+            #[cfg(feature = "debug")]
+            if sound.is_none() {
+                print_debug!("failed to decode sound {}{}{}", MAGENTA, path, NONE);
+            }
+
+            self.loading.remove(&path);
+            self.cache.insert(path, sound);
+        }
+    }
+
+    /// Play a sound once at full volume, independent of any position. Sounds
+    /// that are still loading are played once they are ready.
+    pub fn play_sound_effect(&mut self, game_file_loader: &GameFileLoader, path: &str) {
+        let Some(sound) = self.load(game_file_loader, path) else {
+            if self.loading.contains(path) {
+                self.queued_effects.push(path.to_owned());
+            }
+
+            return;
+        };
+
+        if let Some((_, output_handle)) = &self.output
+            && let Ok(sink) = Sink::try_new(output_handle)
+        {
+            sink.set_volume(self.settings.volume(*self.settings.effect_volume));
+            sink.append(sound);
+            sink.detach();
+        }
+    }
+
+    fn play_queued_effects(&mut self, game_file_loader: &GameFileLoader) {
+        let (ready, waiting): (Vec<String>, Vec<String>) = self.queued_effects.drain(..).partition(|path| !self.loading.contains(path));
+        self.queued_effects = waiting;
+
+        for path in &ready {
+            self.play_sound_effect(game_file_loader, path);
+        }
+    }
+
+    /// Stop all sounds of the current map, for example when changing maps.
+    pub fn clear_ambient_sounds(&mut self) {
+        self.ambient_sounds.clear();
+    }
+
+    /// Open the music track for streaming. Tracks are usually stored next to
+    /// the game archives, but they can also be packed into them.
+    fn open_music(&self, game_file_loader: &mut GameFileLoader, path: &str) -> Option<Box<dyn Source<Item = i16> + Send>> {
+        let file_path = self.data_directory.join(path.replace('\\', "/"));
+
+        if let Ok(file) = File::open(&file_path) {
+            return Decoder::new(BufReader::new(file))
+                .ok()
+                .map(|decoder| Box::new(decoder) as Box<dyn Source<Item = i16> + Send>);
+        }
+
+        let bytes = game_file_loader.get(&format!("data\\{}", path)).ok()?;
+
+        Decoder::new(Cursor::new(bytes))
+            .ok()
+            .map(|decoder| Box::new(decoder) as Box<dyn Source<Item = i16> + Send>)
+    }
+
+    /// Start playing the music of the given map. The music of the previous
+    /// map fades out while the new one fades in.
+    pub fn play_map_music(&mut self, game_file_loader: &mut GameFileLoader, map_name: &str) {
+        let path = self.music_table.get(&map_name.to_lowercase()).cloned();
+
+        if self.music.as_ref().map(|music| &music.path) == path.as_ref() {
+            return;
+        }
+
+        if let Some(music) = self.music.take() {
+            self.fading_music.push(music);
+        }
+
+        let Some(path) = path else {
+            return;
+        };
+
+        let Some((_, output_handle)) = &self.output else {
+            return;
+        };
+
+        let Ok(sink) = Sink::try_new(output_handle) else {
+            return;
+        };
+
+        match self.open_music(game_file_loader, &path) {
+            Some(source) => sink.append(source),
+            None => {
+                #[cfg(feature = "debug")]
+                print_debug!("failed to open music {}{}{}", MAGENTA, path, NONE);
+
+                return;
+            }
+        }
+
+        sink.set_volume(0.0);
+        self.music = Some(MusicTrack { path, sink, fade: 0.0 });
+    }
+
+    fn update_music(&mut self, game_file_loader: &mut GameFileLoader, delta_time: f32) {
+        let music_volume = self.settings.volume(*self.settings.music_volume);
+        let fade_step = delta_time / MUSIC_CROSSFADE_TIME;
+
+        self.fading_music.retain_mut(|music| {
+            music.fade -= fade_step;
+            music.sink.set_volume(music_volume * music.fade.max(0.0));
+            music.fade > 0.0
+        });
+
+        let Some(music) = &mut self.music else {
+            return;
+        };
+
+        music.fade = (music.fade + fade_step).min(1.0);
+        music.sink.set_volume(music_volume * music.fade);
+
+        // Music is streamed, so it is started again from the beginning instead of
+        // being kept in memory to repeat it.
+        if music.sink.empty() {
+            let path = music.path.clone();
+
+            if let Some(source) = self.open_music(game_file_loader, &path)
+                && let Some(music) = &self.music
+            {
+                music.sink.append(source);
+            }
+        }
+    }
+
+    /// Update the music and start, stop and adjust the volume of the sound
+    /// sources of the map based on their distance to the listener. Sources
+    /// are panned to the side they are on, relative to the direction the
+    /// listener is facing on the ground.
+    pub fn update(
+        &mut self,
+        game_file_loader: &mut GameFileLoader,
+        delta_time: f32,
+        sound_sources: &[SoundSource],
+        listener_position: Vector3<f32>,
+        listener_direction: Vector2<f32>,
+    ) {
+        self.receive_loaded_sounds();
+        self.play_queued_effects(game_file_loader);
+        self.update_music(game_file_loader, delta_time);
+
+        let now = Instant::now();
+        let ambient_volume = self.settings.volume(*self.settings.ambient_volume);
+
+        for (index, sound_source) in sound_sources.iter().enumerate() {
+            let distance = (sound_source.position - listener_position).magnitude();
+            let attenuation = match sound_source.range > 0.0 {
+                true => (1.0 - distance / sound_source.range).max(0.0),
+                false => 0.0,
+            };
+
+            if attenuation == 0.0 {
+                // Let the sound finish, but don't start it again until the listener is back in
+                // range.
+                if self.ambient_sounds.get(&index).map_or(false, |ambient_sound| ambient_sound.sink.empty()) {
+                    self.ambient_sounds.remove(&index);
+                }
+
+                continue;
+            }
+
+            if !self.ambient_sounds.contains_key(&index) {
+                let Some((_, output_handle)) = &self.output else {
+                    return;
+                };
+
+                let Ok(sink) = Sink::try_new(output_handle) else {
+                    continue;
+                };
+
+                let ambient_sound = AmbientSound {
+                    sink,
+                    next_play: now,
+                    panning: Arc::new(Mutex::new([1.0; 2])),
+                };
+
+                self.ambient_sounds.insert(index, ambient_sound);
+            }
+
+            let Some(sound) = self.load(game_file_loader, &sound_source.sound_file) else {
+                continue;
+            };
+
+            // Sources right where the listener is are heard from the front.
+            let offset = Vector2::new(
+                sound_source.position.x - listener_position.x,
+                sound_source.position.z - listener_position.z,
+            );
+            let direction = match offset.magnitude2() > f32::EPSILON {
+                true => offset.normalize(),
+                false => listener_direction,
+            };
+
+            let ambient_sound = self.ambient_sounds.get_mut(&index).unwrap();
+            ambient_sound.sink.set_volume(sound_source.volume * attenuation * ambient_volume);
+            *ambient_sound.panning.lock().unwrap() = panning(direction, listener_direction);
+
+            // The cycle is the time between two starts of the sound, but a sound never
+            // overlaps with itself.
+            if now >= ambient_sound.next_play && ambient_sound.sink.empty() {
+                let panning = ambient_sound.panning.clone();
+                let sound = ChannelVolume::new(sound, vec![1.0; 2]).periodic_access(PANNING_UPDATE_PERIOD, move |source| {
+                    let [left, right] = *panning.lock().unwrap();
+                    source.set_volume(0, left);
+                    source.set_volume(1, right);
+                });
+
+                ambient_sound.sink.append(sound);
+                ambient_sound.next_play = now + Duration::from_secs_f32(sound_source.cycle.max(0.0));
+            }
+        }
+    }
+}
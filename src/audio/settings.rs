@@ -0,0 +1,75 @@
+use procedural::*;
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "debug")]
+use crate::debug::*;
+use crate::interface::{MutableRange, TrackedState, NO_EVENT};
+use crate::system::profile_file;
+
+#[derive(Serialize, Deserialize, PrototypeElement)]
+#[serde(default)]
+pub struct AudioSettings {
+    pub master_volume: MutableRange<f32, NO_EVENT>,
+    pub music_volume: MutableRange<f32, NO_EVENT>,
+    pub ambient_volume: MutableRange<f32, NO_EVENT>,
+    pub effect_volume: MutableRange<f32, NO_EVENT>,
+    /// Toggled from the audio settings window, so it is not shown as part of
+    /// the element.
+    #[hidden_element]
+    pub muted: TrackedState<bool>,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        Self {
+            master_volume: MutableRange::new(1.0, 0.0, 1.0),
+            music_volume: MutableRange::new(0.5, 0.0, 1.0),
+            ambient_volume: MutableRange::new(1.0, 0.0, 1.0),
+            effect_volume: MutableRange::new(1.0, 0.0, 1.0),
+            muted: TrackedState::new(false),
+        }
+    }
+}
+
+impl AudioSettings {
+    pub fn new() -> Self {
+        Self::load().unwrap_or_else(|| {
+            #[cfg(feature = "debug")]
+            print_debug!("failed to load audio settings from {}filename{}", MAGENTA, NONE);
+
+            Default::default()
+        })
+    }
+
+    /// Final volume of a channel after applying the master volume.
+    pub fn volume(&self, channel_volume: f32) -> f32 {
+        match *self.muted.borrow() {
+            true => 0.0,
+            false => *self.master_volume * channel_volume,
+        }
+    }
+
+    pub fn load() -> Option<Self> {
+        #[cfg(feature = "debug")]
+        print_debug!("loading audio settings from {}filename{}", MAGENTA, NONE);
+
+        std::fs::read_to_string(profile_file("audio_settings.ron"))
+            .ok()
+            .and_then(|data| ron::from_str(&data).ok())
+    }
+
+    pub fn save(&self) {
+        #[cfg(feature = "debug")]
+        print_debug!("saving audio settings to {}filename{}", MAGENTA, NONE);
+
+        let data = ron::ser::to_string_pretty(self, PrettyConfig::new()).unwrap();
+        std::fs::write(profile_file("audio_settings.ron"), data).expect("unable to write file");
+    }
+}
+
+impl Drop for AudioSettings {
+    fn drop(&mut self) {
+        self.save();
+    }
+}
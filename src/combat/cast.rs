@@ -0,0 +1,135 @@
+use std::time::{Duration, Instant};
+
+use crate::graphics::Texture;
+
+/// How long the cast bar keeps flashing after a cast was interrupted.
+const INTERRUPT_FLASH_DURATION: Duration = Duration::from_millis(800);
+/// Number of cooldowns shown at the same time. Older ones are dropped first.
+const MAXIMUM_COOLDOWNS: usize = 8;
+
+/// Who or what a skill can be used on, derived from the skill type sent by
+/// the server.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SkillTarget {
+    Passive,
+    Enemy,
+    Ground,
+    Caster,
+    Support,
+}
+
+impl SkillTarget {
+    pub fn from_skill_type(skill_type: u32) -> Self {
+        match skill_type {
+            skill_type if skill_type & 0x01 != 0 => Self::Enemy,
+            skill_type if skill_type & 0x02 != 0 => Self::Ground,
+            skill_type if skill_type & 0x04 != 0 => Self::Caster,
+            skill_type if skill_type & 0x10 != 0 => Self::Support,
+            _ => Self::Passive,
+        }
+    }
+}
+
+struct Cast {
+    skill_name: String,
+    start: Instant,
+    duration: Duration,
+    interrupted: Option<Instant>,
+}
+
+/// The skill the player is currently casting.
+#[derive(Default)]
+pub struct CastState {
+    cast: Option<Cast>,
+}
+
+impl CastState {
+    pub fn start(&mut self, skill_name: String, duration: Duration) {
+        self.cast = Some(Cast {
+            skill_name,
+            start: Instant::now(),
+            duration,
+            interrupted: None,
+        });
+    }
+
+    pub fn interrupt(&mut self) {
+        if let Some(cast) = &mut self.cast && cast.interrupted.is_none() {
+            cast.interrupted = Some(Instant::now());
+        }
+    }
+
+    /// Name of the skill and progress from 0 to 1, as long as the cast bar
+    /// should be shown.
+    pub fn progress(&self) -> Option<(&str, f32)> {
+        let cast = self.cast.as_ref()?;
+
+        match cast.interrupted {
+            Some(interrupted) => (interrupted.elapsed() < INTERRUPT_FLASH_DURATION).then(|| {
+                let progress = (interrupted - cast.start).as_secs_f32() / cast.duration.as_secs_f32();
+                (cast.skill_name.as_str(), progress.min(1.0))
+            }),
+            None => (cast.start.elapsed() < cast.duration).then(|| {
+                let progress = cast.start.elapsed().as_secs_f32() / cast.duration.as_secs_f32();
+                (cast.skill_name.as_str(), progress)
+            }),
+        }
+    }
+
+    /// Whether the cast bar should currently be flashing.
+    pub fn is_flashing(&self) -> bool {
+        self.cast
+            .as_ref()
+            .and_then(|cast| cast.interrupted)
+            .map(|interrupted| (interrupted.elapsed().as_millis() / 100) % 2 == 0)
+            .unwrap_or(false)
+    }
+}
+
+pub struct Cooldown {
+    pub skill_id: u16,
+    pub skill_name: String,
+    pub icon: Option<Texture>,
+    start: Instant,
+    duration: Duration,
+}
+
+impl Cooldown {
+    /// Time left until the skill can be used again.
+    pub fn remaining(&self) -> Duration {
+        self.duration.saturating_sub(self.start.elapsed())
+    }
+
+    /// Part of the cooldown that is still left, from 1 to 0.
+    pub fn remaining_fraction(&self) -> f32 {
+        self.remaining().as_secs_f32() / self.duration.as_secs_f32()
+    }
+}
+
+/// Cooldowns of the skills the player used recently.
+#[derive(Default)]
+pub struct SkillCooldowns {
+    cooldowns: Vec<Cooldown>,
+}
+
+impl SkillCooldowns {
+    pub fn add(&mut self, skill_id: u16, skill_name: String, icon: Option<Texture>, duration: Duration) {
+        self.cooldowns.retain(|cooldown| cooldown.skill_id != skill_id);
+        self.cooldowns.push(Cooldown {
+            skill_id,
+            skill_name,
+            icon,
+            start: Instant::now(),
+            duration,
+        });
+
+        if self.cooldowns.len() > MAXIMUM_COOLDOWNS {
+            self.cooldowns.remove(0);
+        }
+    }
+
+    /// Cooldowns that did not run out yet.
+    pub fn running(&self) -> impl Iterator<Item = &Cooldown> {
+        self.cooldowns.iter().filter(|cooldown| !cooldown.remaining().is_zero())
+    }
+}
@@ -0,0 +1,149 @@
+mod cast;
+mod placement;
+mod pvp;
+mod target;
+
+use procedural::*;
+
+use crate::loaders::{ElementType, MonsterInfo};
+use crate::network::SkillInformation;
+
+pub use self::cast::{CastState, SkillCooldowns, SkillTarget};
+pub use self::placement::SkillPlacement;
+pub use self::pvp::PvpMode;
+pub use self::target::{next_target, TargetStatus};
+
+/// Damage modifiers in percent against a level 1 defending element. Rows are
+/// the attacking element, columns the defending element, both in the order of
+/// [`ElementType`]. Higher element levels are not taken into account yet.
+#[rustfmt::skip]
+const ELEMENT_TABLE: [[isize; 10]; 10] = [
+    [100, 100, 100, 100, 100, 100, 100, 100,  25, 100],
+    [100,  25, 100, 150,  50, 100,  75, 100, 100, 100],
+    [100, 100,  25,  50, 150, 100,  75, 100, 100, 100],
+    [100,  50, 150,  25, 100, 100,  75, 100, 100, 125],
+    [100, 175,  50, 100,  25, 100,  75, 100, 100, 100],
+    [100, 100, 125, 125, 125,   0,  75,  50, 100, -25],
+    [100, 100, 100, 100, 100, 100,   0, 125, 100, 150],
+    [100, 100, 100, 100, 100,  50, 125,   0, 100, -25],
+    [ 25, 100, 100, 100, 100, 100,  75,  75, 125, 100],
+    [100, 100, 100, 100, 100,  50, 100,   0, 100,   0],
+];
+
+/// Variance of the weapon attack in percent.
+const WEAPON_VARIANCE: usize = 5;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DamageType {
+    Physical,
+    Magical,
+}
+
+/// The part of the player status that is needed to estimate damage.
+#[derive(Clone, Copy, Debug, Default, PrototypeElement)]
+pub struct AttackStats {
+    pub attack: usize,
+    pub weapon_attack: usize,
+    pub magic_attack: usize,
+    pub weapon_magic_attack: usize,
+}
+
+struct SkillDamage {
+    damage_type: DamageType,
+    element: ElementType,
+    /// Damage in percent of a regular attack at the given skill level.
+    multiplier: fn(usize) -> usize,
+    hits: usize,
+}
+
+fn skill_damage(skill_id: u16) -> Option<SkillDamage> {
+    let (damage_type, element, multiplier, hits): (_, _, fn(usize) -> usize, _) = match skill_id {
+        // Bash
+        5 => (DamageType::Physical, ElementType::Neutral, |level| 100 + 30 * level, 1),
+        // Magnum Break
+        7 => (DamageType::Physical, ElementType::Fire, |level| 100 + 20 * level, 1),
+        // Napalm Beat
+        11 => (DamageType::Magical, ElementType::Ghost, |level| 70 + 10 * level, 1),
+        // Soul Strike
+        13 => (DamageType::Magical, ElementType::Ghost, |_| 100, 1),
+        // Cold Bolt
+        14 => (DamageType::Magical, ElementType::Water, |level| 100 * level, 1),
+        // Fire Ball
+        17 => (DamageType::Magical, ElementType::Fire, |level| 70 + 10 * level, 1),
+        // Fire Bolt
+        19 => (DamageType::Magical, ElementType::Fire, |level| 100 * level, 1),
+        // Lightning Bolt
+        20 => (DamageType::Magical, ElementType::Wind, |level| 100 * level, 1),
+        // Double Strafe
+        46 => (DamageType::Physical, ElementType::Neutral, |level| 100 + 10 * level, 2),
+        // Arrow Shower
+        47 => (DamageType::Physical, ElementType::Neutral, |level| 75 + 5 * level, 1),
+        _ => return None,
+    };
+
+    Some(SkillDamage {
+        damage_type,
+        element,
+        multiplier,
+        hits,
+    })
+}
+
+/// Skills with effects big enough to shake the camera when they hit something
+/// close to the player.
+pub fn is_large_skill(skill_id: u16) -> bool {
+    matches!(
+        skill_id,
+        // Magnus Exorcismus, Meteor Storm, Lord of Vermilion, Storm Gust, Heaven's Drive
+        79 | 83 | 85 | 89 | 91
+        // Grand Cross, Asura Strike
+        | 254 | 271
+    )
+}
+
+pub fn element_modifier(attack_element: ElementType, defense_element: ElementType) -> isize {
+    ELEMENT_TABLE[attack_element as usize][defense_element as usize]
+}
+
+/// Estimated minimum and maximum damage of a skill against a monster. Returns
+/// `None` for skills that don't deal damage or are not known to the client.
+///
+/// This ignores the weapon size modifier, race and element cards, and soft
+/// defense, so it's only meant to give the player a rough idea.
+pub fn estimate_skill_damage(stats: &AttackStats, skill_id: u16, skill_level: usize, monster: &MonsterInfo) -> Option<(usize, usize)> {
+    let skill = skill_damage(skill_id)?;
+
+    let (base, weapon, defense, defense_factor) = match skill.damage_type {
+        DamageType::Physical => (stats.attack, stats.weapon_attack, monster.defense, 4000),
+        DamageType::Magical => (stats.magic_attack, stats.weapon_magic_attack, monster.magic_defense, 1000),
+    };
+
+    let element_modifier = element_modifier(skill.element, monster.element).max(0) as usize;
+
+    let damage = |weapon_percent: usize| {
+        let raw = base + weapon * weapon_percent / 100;
+        let raw = raw * (skill.multiplier)(skill_level) / 100 * element_modifier / 100;
+        let reduced = raw * (defense_factor + defense) / (defense_factor + defense * 10);
+        reduced * skill.hits
+    };
+
+    Some((damage(100 - WEAPON_VARIANCE), damage(100 + WEAPON_VARIANCE)))
+}
+
+/// Lines shown in the tooltip when hovering a skill, including the estimated
+/// damage against the current target.
+pub fn skill_info_lines(skill: &SkillInformation, stats: &AttackStats, target: Option<&MonsterInfo>) -> Vec<String> {
+    let mut lines = vec![
+        skill.skill_name.clone(),
+        format!("level {}", skill.skill_level),
+        format!("{} sp", skill.spell_point_cost),
+    ];
+
+    if let Some(monster) = target
+        && let Some((minimum, maximum)) = estimate_skill_damage(stats, skill.skill_id, skill.skill_level as usize, monster)
+    {
+        lines.push(format!("against {}: {} - {}", monster.name, minimum, maximum));
+    }
+
+    lines
+}
@@ -0,0 +1,143 @@
+use cgmath::Vector2;
+
+use crate::graphics::{Camera, Color, DeferredRenderer, Renderer};
+use crate::network::SkillInformation;
+use crate::world::Map;
+
+/// Size in pixels of the markers drawn on the cells.
+const MARKER_SIZE: f32 = 6.0;
+const RANGE_COLOR: Color = Color::rgba(255, 255, 255, 120);
+const AREA_COLOR: Color = Color::rgba(90, 170, 255, 200);
+const INVALID_COLOR: Color = Color::rgba(120, 120, 120, 200);
+
+/// Number of cells the area of a ground skill extends from the target cell.
+fn area_radius(skill_id: u16) -> usize {
+    match skill_id {
+        // Thunderstorm, Heaven's Drive, Quagmire, Sanctuary, Arrow Shower
+        21 | 91 | 92 | 70 | 47 => 2,
+        // Pneuma
+        25 => 1,
+        // Meteor Storm, Magnus Exorcismus
+        83 | 79 => 3,
+        // Storm Gust, Lord of Vermilion
+        89 | 85 => 4,
+        _ => 0,
+    }
+}
+
+fn cell_distance(first: Vector2<usize>, second: Vector2<usize>) -> usize {
+    first.x.abs_diff(second.x).max(first.y.abs_diff(second.y))
+}
+
+/// A ground skill that was selected and is waiting for the player to pick the
+/// cell it should be used on.
+pub struct SkillPlacement {
+    pub skill_id: u16,
+    pub skill_level: u16,
+    range: usize,
+    area: usize,
+}
+
+impl SkillPlacement {
+    pub fn new(skill: &SkillInformation) -> Self {
+        Self {
+            skill_id: skill.skill_id,
+            skill_level: skill.skill_level,
+            range: skill.attack_range as usize,
+            area: area_radius(skill.skill_id),
+        }
+    }
+
+    pub fn is_valid_cell(&self, map: &Map, caster: Vector2<usize>, cell: Vector2<usize>) -> bool {
+        cell_distance(caster, cell) <= self.range && map.get_tile_checked(cell).map_or(false, |tile| tile.is_walkable())
+    }
+
+    fn render_cell(
+        &self,
+        render_target: &mut <DeferredRenderer as Renderer>::Target,
+        renderer: &DeferredRenderer,
+        camera: &dyn Camera,
+        window_size: Vector2<f32>,
+        map: &Map,
+        cell: Vector2<usize>,
+        color: Color,
+    ) {
+        if map.get_tile_checked(cell).is_none() {
+            return;
+        }
+
+        let (view_matrix, projection_matrix) = camera.view_projection_matrices();
+        let clip_space_position = (projection_matrix * view_matrix) * map.get_world_position(cell).extend(1.0);
+
+        // Cells behind the camera would be mirrored onto the screen.
+        if clip_space_position.w <= 0.0 {
+            return;
+        }
+
+        let screen_position = Vector2::new(
+            clip_space_position.x / clip_space_position.w + 1.0,
+            clip_space_position.y / clip_space_position.w + 1.0,
+        ) / 2.0;
+        let final_position = Vector2::new(screen_position.x * window_size.x, screen_position.y * window_size.y);
+        let offset = Vector2::new(MARKER_SIZE, MARKER_SIZE) / 2.0;
+
+        renderer.render_rectangle(render_target, final_position - offset, Vector2::new(MARKER_SIZE, MARKER_SIZE), color);
+    }
+
+    /// Draws the maximum range around the caster and the area of the skill
+    /// around the hovered cell. Cells the skill can not be used on are grayed
+    /// out.
+    pub fn render(
+        &self,
+        render_target: &mut <DeferredRenderer as Renderer>::Target,
+        renderer: &DeferredRenderer,
+        camera: &dyn Camera,
+        window_size: Vector2<f32>,
+        map: &Map,
+        caster: Vector2<usize>,
+        hovered_cell: Option<Vector2<usize>>,
+    ) {
+        let range = self.range as isize;
+
+        for offset_y in -range..=range {
+            for offset_x in -range..=range {
+                // Only the outline of the range is drawn.
+                if offset_x.abs() != range && offset_y.abs() != range {
+                    continue;
+                }
+
+                let cell = caster.cast::<isize>().unwrap() + Vector2::new(offset_x, offset_y);
+
+                if let Some(cell) = cell.cast::<usize>() {
+                    self.render_cell(render_target, renderer, camera, window_size, map, cell, RANGE_COLOR);
+                }
+            }
+        }
+
+        let Some(hovered_cell) = hovered_cell else {
+            return;
+        };
+
+        let color = match self.is_valid_cell(map, caster, hovered_cell) {
+            true => AREA_COLOR,
+            false => INVALID_COLOR,
+        };
+        let area = self.area as isize;
+
+        for offset_y in -area..=area {
+            for offset_x in -area..=area {
+                let cell = hovered_cell.cast::<isize>().unwrap() + Vector2::new(offset_x, offset_y);
+
+                if let Some(cell) = cell.cast::<usize>() {
+                    let walkable = map.get_tile_checked(cell).map_or(false, |tile| tile.is_walkable());
+                    let color = match walkable {
+                        true => color,
+                        false => INVALID_COLOR,
+                    };
+
+                    self.render_cell(render_target, renderer, camera, window_size, map, cell, color);
+                }
+            }
+        }
+    }
+}
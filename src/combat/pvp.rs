@@ -0,0 +1,40 @@
+/// Whether and how players can fight each other on the current map, derived
+/// from the map type sent by the server.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PvpMode {
+    #[default]
+    None,
+    Pvp,
+    Gvg,
+    Battleground,
+}
+
+impl PvpMode {
+    pub fn from_map_type(map_type: u16, flags: u32) -> Self {
+        match map_type {
+            _ if flags & 0x80 != 0 => Self::Battleground,
+            1 | 2 | 4 | 5 => Self::Pvp,
+            3 => Self::Gvg,
+            _ => Self::None,
+        }
+    }
+
+    /// Whether other players can be attacked on the map.
+    pub fn allows_player_attacks(self) -> bool {
+        self != Self::None
+    }
+
+    /// Whether the map keeps track of the kills of each player.
+    pub fn shows_ranking(self) -> bool {
+        self == Self::Pvp
+    }
+
+    pub fn display_name(self) -> &'static str {
+        match self {
+            Self::None => "peaceful",
+            Self::Pvp => "PvP",
+            Self::Gvg => "GvG",
+            Self::Battleground => "battleground",
+        }
+    }
+}
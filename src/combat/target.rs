@@ -0,0 +1,59 @@
+use crate::network::EntityId;
+use crate::world::{Entity, EntityType};
+
+/// Maximum distance in cells of the monsters that can be selected by tab
+/// targeting.
+const TARGET_RANGE: usize = 15;
+
+/// What the target frame shows about the current target.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TargetStatus {
+    pub entity_id: EntityId,
+    pub name: String,
+    pub health_points: usize,
+    pub maximum_health_points: usize,
+}
+
+impl TargetStatus {
+    pub fn from_entity(entity: &Entity) -> Self {
+        let name = entity
+            .get_details()
+            .map(|details| details.split('#').next().unwrap().to_owned())
+            .unwrap_or_default();
+        let (health_points, maximum_health_points) = entity.get_health_points();
+
+        Self {
+            entity_id: entity.get_entity_id(),
+            name,
+            health_points,
+            maximum_health_points,
+        }
+    }
+}
+
+/// The next monster around the player, ordered by distance. Wraps around to
+/// the closest monster after the furthest one.
+pub fn next_target(entities: &[Entity], current_target: Option<EntityId>) -> Option<EntityId> {
+    let player_position = entities.first()?.get_grid_position();
+
+    let mut monsters: Vec<(usize, EntityId)> = entities
+        .iter()
+        .skip(1)
+        .filter(|entity| entity.get_entity_type() == EntityType::Monster)
+        .map(|entity| {
+            let position = entity.get_grid_position();
+            let distance = player_position.x.abs_diff(position.x).max(player_position.y.abs_diff(position.y));
+            (distance, entity.get_entity_id())
+        })
+        .filter(|(distance, _)| *distance <= TARGET_RANGE)
+        .collect();
+
+    monsters.sort_by_key(|(distance, _)| *distance);
+
+    let next_index = current_target
+        .and_then(|entity_id| monsters.iter().position(|(_, monster_id)| *monster_id == entity_id))
+        .map(|index| (index + 1) % monsters.len())
+        .unwrap_or(0);
+
+    monsters.get(next_index).map(|(_, entity_id)| *entity_id)
+}
@@ -1,9 +1,11 @@
 #[macro_use]
 mod logging;
 mod profiling;
+mod snapshot;
 
 pub use self::logging::*;
 pub use self::profiling::*;
+pub use self::snapshot::{ClientSnapshot, SnapshotEntity};
 
 #[cfg(test)]
 mod debug_condition {
@@ -1 +1,54 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
 
+use lazy_static::lazy_static;
+
+const MAXIMUM_LOAD_TIMINGS: usize = 32;
+
+static ALLOCATION_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+/// Counts heap allocations, so allocator churn shows up in the profiler.
+struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATION_COUNT.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, pointer: *mut u8, layout: Layout) {
+        System.dealloc(pointer, layout)
+    }
+
+    unsafe fn realloc(&self, pointer: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATION_COUNT.fetch_add(1, Ordering::Relaxed);
+        System.realloc(pointer, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+lazy_static! {
+    static ref LOAD_TIMINGS: Mutex<Vec<(String, Duration)>> = Mutex::new(Vec::new());
+}
+
+pub fn record_load_timing(name: String, duration: Duration) {
+    let mut load_timings = LOAD_TIMINGS.lock().unwrap();
+
+    if load_timings.len() == MAXIMUM_LOAD_TIMINGS {
+        load_timings.remove(0);
+    }
+
+    load_timings.push((name, duration));
+}
+
+pub fn load_timings() -> Vec<(String, Duration)> {
+    LOAD_TIMINGS.lock().unwrap().clone()
+}
+
+pub fn allocation_count() -> usize {
+    ALLOCATION_COUNT.load(Ordering::Relaxed)
+}
@@ -0,0 +1,123 @@
+use cgmath::Vector2;
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+
+use super::logging::*;
+use crate::graphics::{CameraPreset, DebugCameraState};
+use crate::input::UserEvent;
+use crate::interface::*;
+use crate::network::{EntityData, EntityId, Sex};
+use crate::system::profile_file;
+
+const SNAPSHOT_FILE: &str = "snapshot.ron";
+/// Restored entities get ids that the server is very unlikely to hand out.
+const ENTITY_ID_OFFSET: u32 = 0xFFFF_0000;
+
+/// An entity that is placed on the map again when restoring.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SnapshotEntity {
+    pub job: usize,
+    pub position: Vector2<usize>,
+    pub head_direction: usize,
+}
+
+impl SnapshotEntity {
+    fn to_entity_data(&self, index: usize) -> EntityData {
+        EntityData {
+            entity_id: EntityId(ENTITY_ID_OFFSET + index as u32),
+            movement_speed: 150,
+            job: self.job as u16,
+            position: self.position,
+            destination: None,
+            health_points: 1,
+            maximum_health_points: 1,
+            level: 1,
+            head_direction: self.head_direction,
+            body_state: 0,
+            health_state: 0,
+            effect_state: 0,
+            sex: Sex::Male,
+            weapon: 0,
+            shield: 0,
+            head: 0,
+            head_palette: 0,
+            body_palette: 0,
+            head_top: 0,
+            head_middle: 0,
+            head_bottom: 0,
+            robe: 0,
+        }
+    }
+}
+
+/// Everything needed to get back to the scene that was being debugged.
+/// Written with the save snapshot button in the menu and read on launch with
+/// `--restore-snapshot`.
+#[derive(Serialize, Deserialize)]
+pub struct ClientSnapshot {
+    pub map_name: String,
+    pub use_debug_camera: bool,
+    pub debug_camera: DebugCameraState,
+    pub player_camera: CameraPreset,
+    /// Classes of the windows that were open, from the bottom to the top.
+    pub open_windows: Vec<String>,
+    pub entities: Vec<SnapshotEntity>,
+}
+
+impl ClientSnapshot {
+    pub fn load() -> Option<Self> {
+        print_debug!("loading snapshot from {}{}{}", MAGENTA, SNAPSHOT_FILE, NONE);
+
+        let snapshot = std::fs::read_to_string(profile_file(SNAPSHOT_FILE))
+            .ok()
+            .and_then(|data| ron::from_str(&data).ok());
+
+        if snapshot.is_none() {
+            print_debug!("failed to load snapshot from {}{}{}", MAGENTA, SNAPSHOT_FILE, NONE);
+        }
+
+        snapshot
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        print_debug!("saving snapshot to {}{}{}", MAGENTA, SNAPSHOT_FILE, NONE);
+
+        let data =
+            ron::ser::to_string_pretty(self, PrettyConfig::new()).map_err(|error| format!("failed to serialize snapshot ({})", error))?;
+        std::fs::write(profile_file(SNAPSHOT_FILE), data).map_err(|error| format!("failed to write snapshot ({})", error))
+    }
+
+    /// Data to spawn the entities of the snapshot with.
+    pub fn entity_data(&self) -> impl Iterator<Item = EntityData> + '_ {
+        self.entities.iter().enumerate().map(|(index, entity)| entity.to_entity_data(index))
+    }
+
+    /// Events that open the windows of the snapshot again. Windows that need
+    /// the player to be logged in are skipped.
+    pub fn window_events(&self) -> Vec<UserEvent> {
+        self.open_windows
+            .iter()
+            .filter_map(|window_class| match window_class.as_str() {
+                MenuWindow::WINDOW_CLASS => Some(UserEvent::OpenMenuWindow),
+                GraphicsSettingsWindow::WINDOW_CLASS => Some(UserEvent::OpenGraphicsSettingsWindow),
+                AudioSettingsWindow::WINDOW_CLASS => Some(UserEvent::OpenAudioSettingsWindow),
+                ControlsWindow::WINDOW_CLASS => Some(UserEvent::OpenControlsWindow),
+                ClockWindow::WINDOW_CLASS => Some(UserEvent::OpenClockWindow),
+                ClockSettingsWindow::WINDOW_CLASS => Some(UserEvent::OpenClockSettingsWindow),
+                MinimapWindow::WINDOW_CLASS => Some(UserEvent::OpenMinimapWindow),
+                WorldMapWindow::WINDOW_CLASS => Some(UserEvent::OpenWorldMapWindow),
+                RenderSettingsWindow::WINDOW_CLASS => Some(UserEvent::OpenRenderSettingsWindow),
+                MapsWindow::WINDOW_CLASS => Some(UserEvent::OpenMapsWindow),
+                TimeWindow::WINDOW_CLASS => Some(UserEvent::OpenTimeWindow),
+                ProfilerWindow::WINDOW_CLASS => Some(UserEvent::OpenProfilerWindow),
+                RenderStatisticsWindow::WINDOW_CLASS => Some(UserEvent::OpenRenderStatisticsWindow),
+                InspectorWindow::WINDOW_CLASS => Some(UserEvent::OpenInspectorWindow),
+                #[cfg(feature = "debug_network")]
+                PacketWindow::WINDOW_CLASS => Some(UserEvent::OpenPacketWindow),
+                #[cfg(feature = "debug_network")]
+                NetworkStatisticsWindow::WINDOW_CLASS => Some(UserEvent::OpenNetworkStatisticsWindow),
+                _ => None,
+            })
+            .collect()
+    }
+}
@@ -2,12 +2,25 @@ use std::f32::consts::FRAC_PI_4;
 
 use cgmath::{Array, EuclideanSpace, InnerSpace, Matrix4, MetricSpace, Point3, Rad, SquareMatrix, Vector2, Vector3, Vector4};
 
+use serde::{Deserialize, Serialize};
+
 use super::Camera;
 use crate::graphics::Transform;
 
 const LOOK_AROUND_SPEED: f32 = 0.005;
 const FLY_SPEED_FAST: f32 = 1000.0;
 const FLY_SPEED_SLOW: f32 = 100.0;
+/// Distance above the ground that the camera is placed at when teleporting.
+const TELEPORT_HEIGHT: f32 = 50.0;
+
+/// Where the debug camera is and where it looks, without anything that is
+/// derived from it.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct DebugCameraState {
+    pub position: Point3<f32>,
+    pub pitch: f32,
+    pub yaw: f32,
+}
 
 pub struct DebugCamera {
     camera_position: Point3<f32>,
@@ -65,6 +78,26 @@ impl DebugCamera {
         self.camera_position += Vector3::new(0.0, 1.0, 0.0) * self.fly_speed * delta_time;
     }
 
+    /// Place the camera above the given position, keeping the direction it
+    /// looks in.
+    pub fn teleport(&mut self, position: Vector3<f32>) {
+        self.camera_position = Point3::new(position.x, position.y + TELEPORT_HEIGHT, position.z);
+    }
+
+    pub fn get_state(&self) -> DebugCameraState {
+        DebugCameraState {
+            position: self.camera_position,
+            pitch: self.pitch.0,
+            yaw: self.yaw.0,
+        }
+    }
+
+    pub fn restore_state(&mut self, state: DebugCameraState) {
+        self.camera_position = state.position;
+        self.pitch = Rad(state.pitch);
+        self.yaw = Rad(state.yaw);
+    }
+
     pub fn accelerate(&mut self) {
         self.fly_speed = FLY_SPEED_FAST;
     }
@@ -0,0 +1,111 @@
+use std::f32::consts::TAU;
+
+use cgmath::Vector3;
+
+#[derive(Clone, Copy, Debug)]
+enum CameraEffectKind {
+    /// Moves the view back and forth in all directions.
+    Shake,
+    /// Pushes the camera towards the player once and lets it ease back.
+    Punch,
+    /// Moves the camera towards and away from the player.
+    ZoomPulse,
+}
+
+/// A short movement of the camera on top of where it would normally be. All
+/// effects fade out linearly over their duration.
+#[derive(Clone, Copy, Debug)]
+pub struct CameraEffect {
+    kind: CameraEffectKind,
+    amplitude: f32,
+    frequency: f32,
+    duration: f32,
+    elapsed: f32,
+}
+
+impl CameraEffect {
+    fn new(kind: CameraEffectKind, amplitude: f32, frequency: f32, duration: f32) -> Self {
+        Self {
+            kind,
+            amplitude,
+            frequency,
+            duration,
+            elapsed: 0.0,
+        }
+    }
+
+    /// Amplitude in world units, frequency in oscillations per second and
+    /// duration in seconds.
+    pub fn shake(amplitude: f32, frequency: f32, duration: f32) -> Self {
+        Self::new(CameraEffectKind::Shake, amplitude, frequency, duration)
+    }
+
+    /// Amplitude in parts of the camera distance.
+    pub fn punch(amplitude: f32, duration: f32) -> Self {
+        Self::new(CameraEffectKind::Punch, amplitude, 0.0, duration)
+    }
+
+    /// Amplitude in parts of the camera distance.
+    pub fn zoom_pulse(amplitude: f32, frequency: f32, duration: f32) -> Self {
+        Self::new(CameraEffectKind::ZoomPulse, amplitude, frequency, duration)
+    }
+
+    fn strength(&self) -> f32 {
+        (1.0 - self.elapsed / self.duration).max(0.0)
+    }
+
+    fn phase(&self) -> f32 {
+        self.elapsed * self.frequency * TAU
+    }
+
+    fn is_finished(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+}
+
+#[derive(Default)]
+pub struct CameraEffects {
+    effects: Vec<CameraEffect>,
+}
+
+impl CameraEffects {
+    pub fn add(&mut self, effect: CameraEffect) {
+        self.effects.push(effect);
+    }
+
+    pub fn update(&mut self, delta_time: f32) {
+        self.effects.iter_mut().for_each(|effect| effect.elapsed += delta_time);
+        self.effects.retain(|effect| !effect.is_finished());
+    }
+
+    /// Offset of the whole view in world units.
+    pub fn view_offset(&self) -> Vector3<f32> {
+        self.effects
+            .iter()
+            .filter(|effect| matches!(effect.kind, CameraEffectKind::Shake))
+            .map(|effect| {
+                let phase = effect.phase();
+                // Slightly different frequencies per axis, so the shake doesn't look like
+                // a straight line.
+                let direction = Vector3::new(phase.sin(), (phase * 1.3 + 1.7).sin(), (phase * 0.7 + 0.5).cos());
+                direction * effect.amplitude * effect.strength()
+            })
+            .fold(Vector3::new(0.0, 0.0, 0.0), |offset, effect_offset| offset + effect_offset)
+    }
+
+    /// Factor that the distance between the camera and the player is
+    /// multiplied with.
+    pub fn distance_factor(&self) -> f32 {
+        let offset: f32 = self
+            .effects
+            .iter()
+            .map(|effect| match effect.kind {
+                CameraEffectKind::Shake => 0.0,
+                CameraEffectKind::Punch => -effect.amplitude * effect.strength().powi(2),
+                CameraEffectKind::ZoomPulse => effect.amplitude * effect.strength() * effect.phase().sin(),
+            })
+            .sum();
+
+        (1.0 + offset).max(0.1)
+    }
+}
@@ -1,14 +1,20 @@
 #[cfg(feature = "debug")]
 mod debug;
+mod effect;
 mod player;
+mod presets;
+mod preview;
 mod shadow;
 mod start;
 
 use cgmath::{InnerSpace, Matrix4, Vector2, Vector3, Vector4};
 
 #[cfg(feature = "debug")]
-pub use self::debug::DebugCamera;
-pub use self::player::PlayerCamera;
+pub use self::debug::{DebugCamera, DebugCameraState};
+pub use self::effect::{CameraEffect, CameraEffects};
+pub use self::player::{CameraMode, PlayerCamera};
+pub use self::presets::{CameraPreset, CameraPresets};
+pub use self::preview::PreviewCamera;
 pub use self::shadow::ShadowCamera;
 pub use self::start::StartCamera;
 use crate::graphics::{SmoothedValue, Transform};
@@ -1,8 +1,8 @@
-use std::f32::consts::FRAC_PI_2;
+use std::f32::consts::{FRAC_PI_2, FRAC_PI_4, PI, SQRT_2, TAU};
 
 use cgmath::{Array, EuclideanSpace, InnerSpace, Matrix4, MetricSpace, Point3, Rad, SquareMatrix, Vector2, Vector3, Vector4};
 
-use super::{Camera, SmoothedValue};
+use super::{Camera, CameraEffect, CameraEffects, CameraPreset, SmoothedValue};
 use crate::graphics::Transform;
 
 const ZOOM_SPEED: f32 = 2.0;
@@ -10,6 +10,25 @@ const ROTATION_SPEED: f32 = 0.02;
 const MINIMUM_ZOOM: f32 = 150.0;
 const MAXIMUM_ZOOM: f32 = 600.0;
 const DEFAULT_ZOOM: f32 = 400.0;
+const TILT_SPEED: f32 = 0.005;
+const MINIMUM_TILT: f32 = 0.35;
+const MAXIMUM_TILT: f32 = 1.35;
+const FREE_MINIMUM_ZOOM: f32 = 30.0;
+const FREE_MINIMUM_TILT: f32 = 0.05;
+const FREE_MAXIMUM_TILT: f32 = 1.5;
+const FREE_DEFAULT_ZOOM: f32 = 120.0;
+const FREE_DEFAULT_TILT: f32 = 0.3;
+/// How fast the camera moves back out after terrain stopped blocking it, in
+/// parts of the full distance per second.
+const COLLISION_RELEASE_SPEED: f32 = 1.5;
+
+/// The fixed mode keeps the camera high above the player like the original
+/// client, the free mode allows looking at the player from almost any angle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CameraMode {
+    Fixed,
+    Free,
+}
 
 pub struct PlayerCamera {
     focus_position: Point3<f32>,
@@ -20,6 +39,15 @@ pub struct PlayerCamera {
     screen_to_world_matrix: Matrix4<f32>,
     view_angle: SmoothedValue,
     zoom: SmoothedValue,
+    tilt: SmoothedValue,
+    mode: CameraMode,
+    /// Zoom and tilt of the mode that is not active, restored when switching
+    /// back to it.
+    inactive_view: (f32, f32),
+    /// Part of the distance to the orbit position that is not blocked by the
+    /// terrain.
+    collision_fraction: f32,
+    effects: CameraEffects,
     aspect_ratio: f32,
 }
 
@@ -34,6 +62,11 @@ impl PlayerCamera {
             screen_to_world_matrix: Matrix4::from_value(0.0),
             view_angle: SmoothedValue::new(FRAC_PI_2, 0.01, 15.0),
             zoom: SmoothedValue::new(DEFAULT_ZOOM, 0.01, 5.0),
+            tilt: SmoothedValue::new(FRAC_PI_4, 0.001, 5.0),
+            mode: CameraMode::Fixed,
+            inactive_view: (FREE_DEFAULT_ZOOM, FREE_DEFAULT_TILT),
+            collision_fraction: 1.0,
+            effects: CameraEffects::default(),
             aspect_ratio: 0.0,
         }
     }
@@ -42,26 +75,139 @@ impl PlayerCamera {
         self.focus_position = Point3::new(position.x, position.y, position.z);
     }
 
+    pub fn focus_point(&self) -> Vector3<f32> {
+        self.focus_position.to_vec()
+    }
+
+    fn zoom_range(&self) -> (f32, f32) {
+        match self.mode {
+            CameraMode::Fixed => (MINIMUM_ZOOM, MAXIMUM_ZOOM),
+            CameraMode::Free => (FREE_MINIMUM_ZOOM, MAXIMUM_ZOOM),
+        }
+    }
+
+    fn tilt_range(&self) -> (f32, f32) {
+        match self.mode {
+            CameraMode::Fixed => (MINIMUM_TILT, MAXIMUM_TILT),
+            CameraMode::Free => (FREE_MINIMUM_TILT, FREE_MAXIMUM_TILT),
+        }
+    }
+
+    pub fn get_mode(&self) -> CameraMode {
+        self.mode
+    }
+
+    /// Switch between the fixed and the free mode. The camera moves smoothly to
+    /// the zoom and tilt that were last used in the other mode.
+    pub fn toggle_mode(&mut self) {
+        let current_view = (self.zoom.get_desired(), self.tilt.get_desired());
+        let (zoom, tilt) = std::mem::replace(&mut self.inactive_view, current_view);
+
+        self.mode = match self.mode {
+            CameraMode::Fixed => CameraMode::Free,
+            CameraMode::Free => CameraMode::Fixed,
+        };
+
+        let (minimum_zoom, maximum_zoom) = self.zoom_range();
+        let (minimum_tilt, maximum_tilt) = self.tilt_range();
+        self.zoom.set_desired(zoom.clamp(minimum_zoom, maximum_zoom));
+        self.tilt.set_desired(tilt.clamp(minimum_tilt, maximum_tilt));
+    }
+
     pub fn soft_zoom(&mut self, zoom_factor: f32) {
-        self.zoom.move_desired_clamp(zoom_factor * ZOOM_SPEED, MINIMUM_ZOOM, MAXIMUM_ZOOM);
+        let (minimum_zoom, maximum_zoom) = self.zoom_range();
+        self.zoom.move_desired_clamp(zoom_factor * ZOOM_SPEED, minimum_zoom, maximum_zoom);
     }
 
     pub fn soft_rotate(&mut self, rotation: f32) {
         self.view_angle.move_desired(rotation * ROTATION_SPEED);
     }
 
+    pub fn soft_tilt(&mut self, tilt: f32) {
+        let (minimum_tilt, maximum_tilt) = self.tilt_range();
+        self.tilt.move_desired_clamp(tilt * TILT_SPEED, minimum_tilt, maximum_tilt);
+    }
+
+    /// Tilt that comes from rotating the camera with the mouse, which is only
+    /// used in the free mode.
+    pub fn soft_pitch(&mut self, pitch: f32) {
+        if self.mode == CameraMode::Free {
+            self.soft_tilt(pitch);
+        }
+    }
+
+    /// Direction the camera is looking in along the ground, with x pointing east
+    /// and y pointing north like tile positions.
+    pub fn ground_view_direction(&self) -> Vector2<f32> {
+        let view_angle = self.view_angle.get_current();
+        Vector2::new(-view_angle.cos(), view_angle.sin())
+    }
+
+    /// The view the camera is moving towards, which might not be reached yet.
+    pub fn get_preset(&self) -> CameraPreset {
+        CameraPreset {
+            zoom: self.zoom.get_desired(),
+            rotation: self.view_angle.get_desired().rem_euclid(TAU),
+            tilt: self.tilt.get_desired(),
+        }
+    }
+
+    pub fn apply_preset(&mut self, preset: &CameraPreset) {
+        // The view angle is never wrapped, so take the shortest way to the rotation
+        // of the preset instead of spinning back through every turn made since.
+        let current_rotation = self.view_angle.get_desired();
+        let offset = (preset.rotation - current_rotation + PI).rem_euclid(TAU) - PI;
+
+        let (minimum_zoom, maximum_zoom) = self.zoom_range();
+        let (minimum_tilt, maximum_tilt) = self.tilt_range();
+
+        self.view_angle.set_desired(current_rotation + offset);
+        self.zoom.set_desired(preset.zoom.clamp(minimum_zoom, maximum_zoom));
+        self.tilt.set_desired(preset.tilt.clamp(minimum_tilt, maximum_tilt));
+    }
+
+    /// Play an effect like a screen shake on top of the current view.
+    pub fn add_effect(&mut self, effect: CameraEffect) {
+        self.effects.add(effect);
+    }
+
     pub fn update(&mut self, delta_time: f64) {
         self.zoom.update(delta_time);
         self.view_angle.update(delta_time);
+        self.tilt.update(delta_time);
+        self.effects.update(delta_time as f32);
     }
 
-    fn camera_position(&self) -> Point3<f32> {
-        let zoom = self.zoom.get_current();
+    /// Limit the distance of the camera to a part of the orbit, so that the
+    /// terrain doesn't end up between the camera and the player.
+    pub fn set_collision_fraction(&mut self, fraction: f32, delta_time: f64) {
+        // Move in right away, so the terrain never covers the player, but move back out
+        // smoothly.
+        self.collision_fraction = match fraction < self.collision_fraction {
+            true => fraction,
+            false => (self.collision_fraction + COLLISION_RELEASE_SPEED * delta_time as f32).min(fraction),
+        };
+    }
+
+    pub fn camera_position(&self) -> Point3<f32> {
+        let focus_position = self.focus_position.to_vec();
+        let distance_factor = self.collision_fraction * self.effects.distance_factor();
+        Point3::from_vec(focus_position + (self.orbit_position() - focus_position) * distance_factor)
+    }
+
+    /// Position of the camera if nothing is in the way.
+    pub fn orbit_position(&self) -> Vector3<f32> {
+        // Scaled so that the zoom is still the height of the camera at the default
+        // tilt.
+        let distance = self.zoom.get_current() * SQRT_2;
         let view_angle = self.view_angle.get_current();
-        Point3::new(
-            self.focus_position.x + zoom * view_angle.cos(),
-            self.focus_position.y + zoom,
-            self.focus_position.z + -zoom * view_angle.sin(),
+        let tilt = self.tilt.get_current();
+        let horizontal_distance = distance * tilt.cos();
+
+        Vector3::new(
+            self.focus_position.x + horizontal_distance * view_angle.cos(),
+            self.focus_position.y + distance * tilt.sin(),
+            self.focus_position.z + -horizontal_distance * view_angle.sin(),
         )
     }
 
@@ -92,8 +238,12 @@ impl Camera for PlayerCamera {
         self.aspect_ratio = window_size.x as f32 / window_size.y as f32;
         self.projection_matrix = cgmath::perspective(Rad(0.2617), self.aspect_ratio, 1.0, 2000.0);
 
-        let camera_position = self.camera_position();
-        self.view_matrix = Matrix4::look_at_rh(camera_position, self.focus_position, self.look_up_vector);
+        // Shaking moves the camera and the point it looks at together, so the player
+        // moves on the screen instead of the view rotating.
+        let view_offset = self.effects.view_offset();
+        let camera_position = self.camera_position() + view_offset;
+        let focus_position = self.focus_position + view_offset;
+        self.view_matrix = Matrix4::look_at_rh(camera_position, focus_position, self.look_up_vector);
 
         self.world_to_screen_matrix = self.projection_matrix * self.view_matrix;
         self.screen_to_world_matrix = self.world_to_screen_matrix.invert().unwrap();
@@ -0,0 +1,66 @@
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "debug")]
+use crate::debug::*;
+use crate::system::profile_file;
+
+/// Number of presets, one for each of the number keys from 1 to 4.
+const CAMERA_PRESET_COUNT: usize = 4;
+
+/// A saved view of the player camera.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct CameraPreset {
+    pub zoom: f32,
+    pub rotation: f32,
+    pub tilt: f32,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+pub struct CameraPresets {
+    presets: [Option<CameraPreset>; CAMERA_PRESET_COUNT],
+}
+
+impl CameraPresets {
+    pub fn new() -> Self {
+        Self::load().unwrap_or_else(|| {
+            #[cfg(feature = "debug")]
+            print_debug!("failed to load camera presets from {}filename{}", MAGENTA, NONE);
+
+            Default::default()
+        })
+    }
+
+    pub fn load() -> Option<Self> {
+        #[cfg(feature = "debug")]
+        print_debug!("loading camera presets from {}filename{}", MAGENTA, NONE);
+
+        std::fs::read_to_string(profile_file("camera_presets.ron"))
+            .ok()
+            .and_then(|data| ron::from_str(&data).ok())
+    }
+
+    pub fn save(&self) {
+        #[cfg(feature = "debug")]
+        print_debug!("saving camera presets to {}filename{}", MAGENTA, NONE);
+
+        let data = ron::ser::to_string_pretty(self, PrettyConfig::new()).unwrap();
+        std::fs::write(profile_file("camera_presets.ron"), data).expect("unable to write file");
+    }
+
+    pub fn get(&self, slot: usize) -> Option<&CameraPreset> {
+        self.presets.get(slot)?.as_ref()
+    }
+
+    pub fn set(&mut self, slot: usize, preset: CameraPreset) {
+        if let Some(entry) = self.presets.get_mut(slot) {
+            *entry = Some(preset);
+        }
+    }
+}
+
+impl Drop for CameraPresets {
+    fn drop(&mut self) {
+        self.save();
+    }
+}
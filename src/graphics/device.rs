@@ -0,0 +1,79 @@
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+use vulkano::device::physical::PhysicalDevice;
+
+#[cfg(feature = "debug")]
+use crate::debug::*;
+use crate::system::profile_file;
+
+/// Vendor and device id of a graphics device. Unlike the name, they don't
+/// change with the driver version.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeviceId {
+    pub vendor_id: u32,
+    pub device_id: u32,
+}
+
+impl DeviceId {
+    pub fn from_device(physical_device: &PhysicalDevice) -> Self {
+        let properties = physical_device.properties();
+
+        Self {
+            vendor_id: properties.vendor_id,
+            device_id: properties.device_id,
+        }
+    }
+
+    /// Parses ids written as `vendor:device` in hexadecimal, for example
+    /// `10de:2684`.
+    pub fn parse(text: &str) -> Option<Self> {
+        let (vendor_id, device_id) = text.split_once(':')?;
+        let vendor_id = u32::from_str_radix(vendor_id, 16).ok()?;
+        let device_id = u32::from_str_radix(device_id, 16).ok()?;
+
+        Some(Self { vendor_id, device_id })
+    }
+}
+
+/// The graphics device picked by the player. Since the device is created once
+/// on startup, changes only apply after restarting the client.
+#[derive(Default, Serialize, Deserialize)]
+pub struct DeviceSettings {
+    /// Id of the preferred device. `None` picks the best device
+    /// automatically.
+    pub preferred_device: Option<DeviceId>,
+}
+
+impl DeviceSettings {
+    pub fn new() -> Self {
+        Self::load().unwrap_or_else(|| {
+            #[cfg(feature = "debug")]
+            print_debug!("failed to load device settings from {}filename{}", MAGENTA, NONE);
+
+            Default::default()
+        })
+    }
+
+    pub fn load() -> Option<Self> {
+        #[cfg(feature = "debug")]
+        print_debug!("loading device settings from {}filename{}", MAGENTA, NONE);
+
+        std::fs::read_to_string(profile_file("device_settings.ron"))
+            .ok()
+            .and_then(|data| ron::from_str(&data).ok())
+    }
+
+    pub fn save(&self) {
+        #[cfg(feature = "debug")]
+        print_debug!("saving device settings to {}filename{}", MAGENTA, NONE);
+
+        let data = ron::ser::to_string_pretty(self, PrettyConfig::new()).unwrap();
+        std::fs::write(profile_file("device_settings.ron"), data).expect("unable to write file");
+    }
+}
+
+impl Drop for DeviceSettings {
+    fn drop(&mut self) {
+        self.save();
+    }
+}
@@ -1,18 +1,72 @@
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
+use ash::vk;
 use vulkano::command_buffer::allocator::{CommandBufferAllocator, StandardCommandBufferAllocator};
 use vulkano::descriptor_set::allocator::{DescriptorSetAllocator, StandardDescriptorSetAlloc, StandardDescriptorSetAllocator};
 use vulkano::descriptor_set::layout::DescriptorSetLayout;
+use vulkano::device::physical::PhysicalDevice;
 use vulkano::device::{Device, DeviceOwned};
 use vulkano::memory::allocator::StandardMemoryAllocator;
-use vulkano::OomError;
+use vulkano::{OomError, Version, VulkanObject};
+
+// Start evicting cached resources once this much of the budget is used.
+const BUDGET_THRESHOLD: f64 = 0.9;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MemoryCategory {
+    MapGeometry,
+    Textures,
+    Sprites,
+    Interface,
+}
+
+impl MemoryCategory {
+    pub const ALL: [MemoryCategory; 4] = [
+        MemoryCategory::MapGeometry,
+        MemoryCategory::Textures,
+        MemoryCategory::Sprites,
+        MemoryCategory::Interface,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            MemoryCategory::MapGeometry => "map geometry",
+            MemoryCategory::Textures => "textures",
+            MemoryCategory::Sprites => "sprites",
+            MemoryCategory::Interface => "interface",
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct MemoryBudget {
+    pub usage: u64,
+    pub budget: u64,
+}
+
+/// Counts the size of a buffer or image towards its category for as long as it
+/// is kept next to it.
+pub struct TrackedAllocation {
+    memory_allocator: Arc<MemoryAllocator>,
+    category: MemoryCategory,
+    size: u64,
+}
+
+impl Drop for TrackedAllocation {
+    fn drop(&mut self) {
+        self.memory_allocator.category_usage[self.category as usize].fetch_sub(self.size, Ordering::Relaxed);
+    }
+}
 
 pub struct MemoryAllocator {
     device: Arc<Device>,
     memory_allocator: StandardMemoryAllocator,
     descriptor_set_allocator: StandardDescriptorSetAllocator,
     command_buffer_allocator: StandardCommandBufferAllocator,
+    category_usage: [AtomicU64; 4],
 }
+
 impl MemoryAllocator {
     pub fn new(device: Arc<Device>) -> Self {
         let memory_allocator = StandardMemoryAllocator::new_default(device.clone());
@@ -24,7 +78,83 @@ impl MemoryAllocator {
             memory_allocator,
             descriptor_set_allocator,
             command_buffer_allocator,
+            category_usage: Default::default(),
+        }
+    }
+
+    /// The allocation is counted until the returned value is dropped, so it
+    /// should be stored together with the buffer or image.
+    #[must_use]
+    pub fn track_allocation(self: &Arc<Self>, category: MemoryCategory, size: u64) -> TrackedAllocation {
+        self.category_usage[category as usize].fetch_add(size, Ordering::Relaxed);
+
+        TrackedAllocation {
+            memory_allocator: self.clone(),
+            category,
+            size,
+        }
+    }
+
+    pub fn category_usage(&self, category: MemoryCategory) -> u64 {
+        self.category_usage[category as usize].load(Ordering::Relaxed)
+    }
+
+    /// `VK_EXT_memory_budget` is queried through
+    /// `vkGetPhysicalDeviceMemoryProperties2`, which needs either Vulkan 1.1 or
+    /// `VK_KHR_get_physical_device_properties2`.
+    pub fn supports_memory_budget(physical_device: &PhysicalDevice) -> bool {
+        let instance = physical_device.instance();
+        let has_memory_properties2 =
+            physical_device.api_version() >= Version::V1_1 || instance.enabled_extensions().khr_get_physical_device_properties2;
+
+        physical_device.supported_extensions().ext_memory_budget && has_memory_properties2
+    }
+
+    /// Usage and budget of all device local heaps as reported by the driver.
+    /// Returns `None` if `VK_EXT_memory_budget` is not enabled.
+    pub fn memory_budget(&self) -> Option<MemoryBudget> {
+        if !self.device.enabled_extensions().ext_memory_budget {
+            return None;
+        }
+
+        let physical_device = self.device.physical_device();
+        let mut budget_properties = vk::PhysicalDeviceMemoryBudgetPropertiesEXT::default();
+        let mut memory_properties = vk::PhysicalDeviceMemoryProperties2 {
+            p_next: &mut budget_properties as *mut _ as *mut _,
+            ..Default::default()
+        };
+
+        unsafe {
+            let fns = physical_device.instance().fns();
+
+            match physical_device.api_version() >= Version::V1_1 {
+                true => (fns.v1_1.get_physical_device_memory_properties2)(physical_device.handle(), &mut memory_properties),
+                false => (fns.khr_get_physical_device_properties2.get_physical_device_memory_properties2_khr)(
+                    physical_device.handle(),
+                    &mut memory_properties,
+                ),
+            }
         }
+
+        let heaps = &memory_properties.memory_properties.memory_heaps[..memory_properties.memory_properties.memory_heap_count as usize];
+        let (usage, budget) = heaps
+            .iter()
+            .enumerate()
+            .filter(|(_, heap)| heap.flags.contains(vk::MemoryHeapFlags::DEVICE_LOCAL))
+            .fold((0, 0), |(usage, budget), (index, _)| {
+                (
+                    usage + budget_properties.heap_usage[index],
+                    budget + budget_properties.heap_budget[index],
+                )
+            });
+
+        Some(MemoryBudget { usage, budget })
+    }
+
+    pub fn is_near_budget(&self) -> bool {
+        self.memory_budget()
+            .map(|MemoryBudget { usage, budget }| usage as f64 > budget as f64 * BUDGET_THRESHOLD)
+            .unwrap_or(false)
     }
 }
 
@@ -1,5 +1,6 @@
 mod cameras;
 mod color;
+mod device;
 mod memory;
 mod particles;
 mod renderers;
@@ -19,10 +20,11 @@ use vulkano::image::ImmutableImage;
 
 pub use self::cameras::*;
 pub use self::color::*;
-pub use self::memory::MemoryAllocator;
+pub use self::device::{DeviceId, DeviceSettings};
+pub use self::memory::{MemoryAllocator, MemoryBudget, MemoryCategory, TrackedAllocation};
 pub use self::particles::*;
 pub use self::renderers::*;
-pub use self::settings::GraphicsSettings;
+pub use self::settings::{EffectBudget, EffectQuality, EntityShadowMode, GraphicsSettings};
 pub use self::smoothed::SmoothedValue;
 pub use self::transform::Transform;
 pub use self::vertices::*;
@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 use cgmath::{Array, Vector2, Vector3};
 use derive_new::new;
-use rand::{thread_rng, Rng};
+use rand::Rng;
 
 use crate::graphics::*;
 use crate::loaders::{GameFileLoader, TextureLoader};
@@ -12,6 +12,11 @@ use crate::world::*;
 pub trait Particle {
     fn update(&mut self, delta_time: f32) -> bool;
 
+    /// Cosmetic particles are skipped entirely on the lowest effect quality.
+    fn is_cosmetic(&self) -> bool {
+        false
+    }
+
     fn render(
         &self,
         render_target: &mut <DeferredRenderer as Renderer>::Target,
@@ -21,20 +26,30 @@ pub trait Particle {
     );
 }
 
-#[derive(new)]
 pub struct DamageNumber {
     position: Vector3<f32>,
     damage_amount: String,
-    #[new(value = "50.0")]
     velocity_y: f32,
-    #[new(value = "thread_rng().gen_range(-20.0..20.0)")]
     velocity_x: f32,
-    #[new(value = "thread_rng().gen_range(-20.0..20.0)")]
     velocity_z: f32,
-    #[new(value = "0.6")]
     timer: f32,
 }
 
+impl DamageNumber {
+    /// The number jumps up in a random direction, so hits in quick succession
+    /// don't cover each other.
+    pub fn new(position: Vector3<f32>, damage_amount: String, random: &mut impl Rng) -> Self {
+        Self {
+            position,
+            damage_amount,
+            velocity_y: 50.0,
+            velocity_x: random.gen_range(-20.0..20.0),
+            velocity_z: random.gen_range(-20.0..20.0),
+            timer: 0.6,
+        }
+    }
+}
+
 impl Particle for DamageNumber {
     fn update(&mut self, delta_time: f32) -> bool {
         self.velocity_y -= 200.0 * delta_time;
@@ -67,6 +82,77 @@ impl Particle for DamageNumber {
     }
 }
 
+/// How long the light pillar of a teleport stays visible, in seconds.
+const TELEPORT_EFFECT_DURATION: f32 = 0.5;
+/// Height of the light pillar of a teleport in world units.
+const TELEPORT_EFFECT_HEIGHT: f32 = 40.0;
+
+/// Pillar of light shown where an entity vanishes or appears when
+/// teleporting.
+#[derive(new)]
+pub struct TeleportEffect {
+    position: Vector3<f32>,
+    appearing: bool,
+    #[new(value = "TELEPORT_EFFECT_DURATION")]
+    timer: f32,
+}
+
+impl Particle for TeleportEffect {
+    fn update(&mut self, delta_time: f32) -> bool {
+        self.timer -= delta_time;
+        self.timer > 0.0
+    }
+
+    fn is_cosmetic(&self) -> bool {
+        true
+    }
+
+    fn render(
+        &self,
+        render_target: &mut <DeferredRenderer as Renderer>::Target,
+        renderer: &DeferredRenderer,
+        camera: &dyn Camera,
+        window_size: Vector2<f32>,
+    ) {
+        let remaining = self.timer / TELEPORT_EFFECT_DURATION;
+
+        // The pillar shoots up when vanishing and comes down when appearing.
+        let (bottom, top) = match self.appearing {
+            true => (0.0, TELEPORT_EFFECT_HEIGHT * (0.5 + remaining)),
+            false => (TELEPORT_EFFECT_HEIGHT * (1.0 - remaining), TELEPORT_EFFECT_HEIGHT * (2.0 - remaining)),
+        };
+
+        let (view_matrix, projection_matrix) = camera.view_projection_matrices();
+        let to_screen = |height: f32| {
+            let clip_space_position = (projection_matrix * view_matrix) * (self.position + Vector3::new(0.0, height, 0.0)).extend(1.0);
+            let screen_position = Vector2::new(
+                clip_space_position.x / clip_space_position.w + 1.0,
+                clip_space_position.y / clip_space_position.w + 1.0,
+            );
+            let screen_position = screen_position / 2.0;
+            Vector2::new(screen_position.x * window_size.x, screen_position.y * window_size.y)
+        };
+
+        let bottom_position = to_screen(bottom);
+        let top_position = to_screen(top);
+        let width = 30.0 * remaining;
+        let alpha = (200.0 * remaining) as u8;
+
+        renderer.render_rectangle(
+            render_target,
+            Vector2::new(top_position.x - width / 2.0, top_position.y),
+            Vector2::new(width, bottom_position.y - top_position.y),
+            Color::rgba(180, 220, 255, alpha),
+        );
+        renderer.render_rectangle(
+            render_target,
+            Vector2::new(top_position.x - width / 6.0, top_position.y),
+            Vector2::new(width / 3.0, bottom_position.y - top_position.y),
+            Color::rgba(255, 255, 255, alpha),
+        );
+    }
+}
+
 pub struct QuestIcon {
     position: Vector3<f32>,
     texture: Texture,
@@ -124,15 +210,44 @@ impl QuestIcon {
     }
 }
 
-#[derive(Default)]
 pub struct ParticleHolder {
     particles: Vec<Box<dyn Particle + Send + Sync>>,
     quest_icons: HashMap<EntityId, QuestIcon>,
+    budget: EffectBudget,
 }
 
 impl ParticleHolder {
+    pub fn new(budget: EffectBudget) -> Self {
+        Self {
+            particles: Vec::new(),
+            quest_icons: HashMap::new(),
+            budget,
+        }
+    }
+
+    pub fn set_budget(&mut self, budget: EffectBudget) {
+        self.budget = budget;
+        self.enforce_budget();
+    }
+
     pub fn spawn_particle(&mut self, particle: Box<dyn Particle + Send + Sync>) {
+        if particle.is_cosmetic() && !self.budget.cosmetic_particles {
+            return;
+        }
+
         self.particles.push(particle);
+        self.enforce_budget();
+    }
+
+    /// Particles are stored in the order they were spawned, so the oldest
+    /// ones are removed first.
+    fn enforce_budget(&mut self) {
+        if !self.budget.cosmetic_particles {
+            self.particles.retain(|particle| !particle.is_cosmetic());
+        }
+
+        let excess = self.particles.len().saturating_sub(self.budget.maximum_particles);
+        self.particles.drain(..excess);
     }
 
     pub fn add_quest_icon(
@@ -0,0 +1,18 @@
+#version 450
+
+layout(location = 0) in vec2 offset;
+layout(location = 1) in float opacity;
+
+layout(location = 0) out vec4 fragment_color;
+layout(location = 1) out vec4 fragment_normal;
+layout(location = 2) out vec4 fragment_water;
+
+void main() {
+    float shadow = (1.0 - smoothstep(0.2, 1.0, length(offset))) * opacity;
+
+    // The alpha is the amount that the existing color is darkened by, so a zero
+    // alpha leaves the normals and the water untouched.
+    fragment_color = vec4(0.0, 0.0, 0.0, shadow);
+    fragment_normal = vec4(0.0);
+    fragment_water = vec4(0.0);
+}
@@ -0,0 +1,166 @@
+// TODO: remove once no longer needed
+#[allow(clippy::needless_question_mark)]
+mod vertex_shader {
+    vulkano_shaders::shader! {
+        ty: "vertex",
+        path: "src/graphics/renderers/deferred/blob_shadow/vertex_shader.glsl"
+    }
+}
+
+// TODO: remove once no longer needed
+#[allow(clippy::needless_question_mark)]
+mod fragment_shader {
+    vulkano_shaders::shader! {
+        ty: "fragment",
+        path: "src/graphics/renderers/deferred/blob_shadow/fragment_shader.glsl"
+    }
+}
+
+use std::iter;
+use std::sync::Arc;
+
+use cgmath::{Matrix4, Quaternion, Vector3};
+use vulkano::buffer::BufferUsage;
+use vulkano::descriptor_set::{PersistentDescriptorSet, WriteDescriptorSet};
+use vulkano::device::{Device, DeviceOwned};
+use vulkano::memory::allocator::MemoryUsage;
+use vulkano::pipeline::graphics::color_blend::ColorBlendState;
+use vulkano::pipeline::graphics::depth_stencil::{CompareOp, DepthState, DepthStencilState};
+use vulkano::pipeline::graphics::input_assembly::InputAssemblyState;
+use vulkano::pipeline::graphics::multisample::MultisampleState;
+use vulkano::pipeline::graphics::viewport::{Viewport, ViewportState};
+use vulkano::pipeline::{GraphicsPipeline, Pipeline, PipelineBindPoint, StateMode};
+use vulkano::render_pass::Subpass;
+use vulkano::shader::ShaderModule;
+
+use self::vertex_shader::ty::{Constants, Matrices};
+use crate::graphics::*;
+
+unsafe impl bytemuck::Zeroable for Constants {}
+unsafe impl bytemuck::Pod for Constants {}
+
+unsafe impl bytemuck::Zeroable for Matrices {}
+unsafe impl bytemuck::Pod for Matrices {}
+
+/// Darkens the diffuse buffer in a soft circle on the ground.
+pub struct BlobShadowRenderer {
+    memory_allocator: Arc<MemoryAllocator>,
+    pipeline: Arc<GraphicsPipeline>,
+    vertex_shader: Arc<ShaderModule>,
+    fragment_shader: Arc<ShaderModule>,
+    matrices_buffer: CpuBufferPool<Matrices, MemoryAllocator>,
+}
+
+impl BlobShadowRenderer {
+    pub fn new(memory_allocator: Arc<MemoryAllocator>, subpass: Subpass, viewport: Viewport) -> Self {
+        let device = memory_allocator.device().clone();
+        let vertex_shader = vertex_shader::load(device.clone()).unwrap();
+        let fragment_shader = fragment_shader::load(device.clone()).unwrap();
+        let pipeline = Self::create_pipeline(device, subpass, viewport, &vertex_shader, &fragment_shader);
+        let matrices_buffer = CpuBufferPool::new(
+            memory_allocator.clone(),
+            BufferUsage {
+                uniform_buffer: true,
+                ..Default::default()
+            },
+            MemoryUsage::Upload,
+        );
+
+        Self {
+            memory_allocator,
+            pipeline,
+            vertex_shader,
+            fragment_shader,
+            matrices_buffer,
+        }
+    }
+
+    pub fn recreate_pipeline(&mut self, device: Arc<Device>, subpass: Subpass, viewport: Viewport) {
+        self.pipeline = Self::create_pipeline(device, subpass, viewport, &self.vertex_shader, &self.fragment_shader);
+    }
+
+    fn create_pipeline(
+        device: Arc<Device>,
+        subpass: Subpass,
+        viewport: Viewport,
+        vertex_shader: &ShaderModule,
+        fragment_shader: &ShaderModule,
+    ) -> Arc<GraphicsPipeline> {
+        // Shadows are hidden by anything in front of them but don't hide anything
+        // themselves.
+        let depth_stencil_state = DepthStencilState {
+            depth: Some(DepthState {
+                enable_dynamic: false,
+                compare_op: StateMode::Fixed(CompareOp::Less),
+                write_enable: StateMode::Fixed(false),
+            }),
+            depth_bounds: Default::default(),
+            stencil: Default::default(),
+        };
+
+        GraphicsPipeline::start()
+            .vertex_shader(vertex_shader.entry_point("main").unwrap(), ())
+            .input_assembly_state(InputAssemblyState::new())
+            .viewport_state(ViewportState::viewport_fixed_scissor_irrelevant(iter::once(viewport)))
+            .fragment_shader(fragment_shader.entry_point("main").unwrap(), ())
+            .depth_stencil_state(depth_stencil_state)
+            .color_blend_state(ColorBlendState::new(subpass.num_color_attachments()).blend(SHADOW_ATTACHMENT_BLEND))
+            .multisample_state(MultisampleState {
+                rasterization_samples: vulkano::image::SampleCount::Sample4,
+                ..Default::default()
+            })
+            .render_pass(subpass)
+            .build(device)
+            .unwrap()
+    }
+
+    pub fn bind_pipeline(&self, render_target: &mut <DeferredRenderer as Renderer>::Target, camera: &dyn Camera) {
+        let layout = self.pipeline.layout().clone();
+        let descriptor_layout = layout.set_layouts().get(0).unwrap().clone();
+
+        let (view_matrix, projection_matrix) = camera.view_projection_matrices();
+        let matrices = Matrices {
+            view: view_matrix.into(),
+            projection: projection_matrix.into(),
+        };
+
+        let matrices_subbuffer = Arc::new(self.matrices_buffer.from_data(matrices).unwrap());
+        let set = PersistentDescriptorSet::new(&*self.memory_allocator, descriptor_layout, [WriteDescriptorSet::buffer(
+            0,
+            matrices_subbuffer,
+        )])
+        .unwrap();
+
+        render_target
+            .state
+            .get_builder()
+            .bind_pipeline_graphics(self.pipeline.clone())
+            .bind_descriptor_sets(PipelineBindPoint::Graphics, layout, 0, set);
+    }
+
+    pub fn render(
+        &self,
+        render_target: &mut <DeferredRenderer as Renderer>::Target,
+        position: Vector3<f32>,
+        normal: Vector3<f32>,
+        radius: f32,
+        opacity: f32,
+    ) {
+        let layout = self.pipeline.layout().clone();
+        // The shadow is a flat square facing up, so it is tilted to lie on the slope.
+        let rotation = Quaternion::from_arc(Vector3::unit_y(), normal, None);
+        let world_matrix = Matrix4::from_translation(position) * Matrix4::from(rotation) * Matrix4::from_scale(radius);
+
+        let constants = Constants {
+            world: world_matrix.into(),
+            opacity,
+        };
+
+        render_target
+            .state
+            .get_builder()
+            .push_constants(layout, 0, constants)
+            .draw(6, 1, 0, 0)
+            .unwrap();
+    }
+}
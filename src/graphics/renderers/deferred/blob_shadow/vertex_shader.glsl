@@ -0,0 +1,31 @@
+#version 450
+
+layout(location = 0) out vec2 offset_out;
+layout(location = 1) out float opacity_out;
+
+layout(set = 0, binding = 0) uniform Matrices {
+    mat4 view;
+    mat4 projection;
+} matrices;
+
+layout(push_constant) uniform Constants {
+    mat4 world;
+    float opacity;
+} constants;
+
+const vec2 data[6] = vec2[]
+(
+    vec2(-1, -1),
+    vec2(-1, 1),
+    vec2(1, -1),
+    vec2(1, -1),
+    vec2(-1, 1),
+    vec2(1, 1)
+);
+
+void main() {
+    vec2 offset = data[gl_VertexIndex];
+    gl_Position = matrices.projection * matrices.view * constants.world * vec4(offset.x, 0.0, offset.y, 1.0);
+    offset_out = offset;
+    opacity_out = constants.opacity;
+}
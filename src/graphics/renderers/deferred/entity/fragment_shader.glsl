@@ -7,10 +7,32 @@ layout(location = 0) out vec4 fragment_color;
 layout(location = 1) out vec3 fragment_normal;
 
 layout (set = 1, binding = 0) uniform sampler2D sampled_texture;
+layout (set = 1, binding = 1) uniform sampler2D palette_texture;
+
+layout(push_constant) uniform Constants {
+    mat4 world;
+    vec2 texture_position;
+    vec2 texture_size;
+    bool mirror;
+    bool use_palette;
+} constants;
 
 void main() {
 
-    vec4 diffuse_color = texture(sampled_texture, texture_coordinates);
+    vec4 diffuse_color;
+
+    if (constants.use_palette) {
+        // The palette index is stored in the red channel. Filtering would mix
+        // indices of unrelated colors, so the texels are fetched directly.
+        ivec2 texture_size = textureSize(sampled_texture, 0);
+        ivec2 texel = clamp(ivec2(texture_coordinates * texture_size), ivec2(0), texture_size - 1);
+        vec4 index_color = texelFetch(sampled_texture, texel, 0);
+
+        diffuse_color = texelFetch(palette_texture, ivec2(int(index_color.r * 255.0 + 0.5), 0), 0);
+        diffuse_color.a = index_color.a;
+    } else {
+        diffuse_color = texture(sampled_texture, texture_coordinates);
+    }
 
     if (diffuse_color.a != 1.0) {
         discard;
@@ -21,9 +21,10 @@ use std::sync::Arc;
 
 use cgmath::{Vector2, Vector3};
 use vulkano::buffer::BufferUsage;
+use vulkano::command_buffer::{AutoCommandBufferBuilder, PrimaryAutoCommandBuffer};
 use vulkano::descriptor_set::{PersistentDescriptorSet, WriteDescriptorSet};
 use vulkano::device::{Device, DeviceOwned};
-use vulkano::image::ImageAccess;
+use vulkano::image::{ImageAccess, SampleCount};
 use vulkano::memory::allocator::MemoryUsage;
 use vulkano::pipeline::graphics::depth_stencil::DepthStencilState;
 use vulkano::pipeline::graphics::input_assembly::InputAssemblyState;
@@ -97,7 +98,7 @@ impl EntityRenderer {
             .fragment_shader(fragment_shader.entry_point("main").unwrap(), ())
             .depth_stencil_state(DepthStencilState::simple_depth_test())
             .multisample_state(MultisampleState {
-                rasterization_samples: vulkano::image::SampleCount::Sample4,
+                rasterization_samples: subpass.num_samples().unwrap_or(SampleCount::Sample1),
                 ..Default::default()
             })
             .render_pass(subpass)
@@ -105,7 +106,9 @@ impl EntityRenderer {
             .unwrap()
     }
 
-    pub fn bind_pipeline(&self, render_target: &mut <DeferredRenderer as Renderer>::Target, camera: &dyn Camera) {
+    /// Takes the command buffer builder instead of a render target, so the
+    /// pipeline can also be used by the [`PreviewRenderer`].
+    pub fn bind_pipeline(&self, builder: &mut AutoCommandBufferBuilder<PrimaryAutoCommandBuffer, MemoryAllocator>, camera: &dyn Camera) {
         let layout = self.pipeline.layout().clone();
         let descriptor_layout = layout.set_layouts().get(0).unwrap().clone();
 
@@ -122,18 +125,17 @@ impl EntityRenderer {
         )])
         .unwrap();
 
-        render_target
-            .state
-            .get_builder()
+        builder
             .bind_pipeline_graphics(self.pipeline.clone())
             .bind_descriptor_sets(PipelineBindPoint::Graphics, layout, 0, set);
     }
 
     pub fn render(
         &self,
-        render_target: &mut <DeferredRenderer as Renderer>::Target,
+        builder: &mut AutoCommandBufferBuilder<PrimaryAutoCommandBuffer, MemoryAllocator>,
         camera: &dyn Camera,
         texture: Texture,
+        palette: Option<Texture>,
         position: Vector3<f32>,
         origin: Vector3<f32>,
         scale: Vector2<f32>,
@@ -150,8 +152,14 @@ impl EntityRenderer {
         let layout = self.pipeline.layout().clone();
         let descriptor_layout = layout.set_layouts().get(1).unwrap().clone();
 
+        // The palette binding can't be left empty, so the texture itself is bound if
+        // there is no palette.
+        let use_palette = palette.is_some();
+        let palette = palette.unwrap_or_else(|| texture.clone());
+
         let set = PersistentDescriptorSet::new(&*self.memory_allocator, descriptor_layout, [
             WriteDescriptorSet::image_view_sampler(0, texture, self.nearest_sampler.clone()),
+            WriteDescriptorSet::image_view_sampler(1, palette, self.nearest_sampler.clone()),
         ])
         .unwrap();
 
@@ -164,11 +172,10 @@ impl EntityRenderer {
             texture_position: [texture_position.x, texture_position.y],
             texture_size: [texture_size.x, texture_size.y],
             mirror: mirror as u32,
+            use_palette: use_palette as u32,
         };
 
-        render_target
-            .state
-            .get_builder()
+        builder
             .bind_descriptor_sets(PipelineBindPoint::Graphics, layout.clone(), 1, set)
             .push_constants(layout, 0, constants)
             .draw(6, 1, 0, 0)
@@ -13,6 +13,7 @@ layout(push_constant) uniform Constants {
     vec2 texture_position;
     vec2 texture_size;
     bool mirror;
+    bool use_palette;
 } constants;
 
 struct Vertex {
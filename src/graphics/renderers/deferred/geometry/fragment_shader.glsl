@@ -5,6 +5,7 @@ const int TEXTURE_COUNT = 30;
 layout(location = 0) in vec3 normal;
 layout(location = 1) in vec2 texture_coordinates;
 layout(location = 2) flat in int texture_index;
+layout(location = 3) flat in float opacity;
 
 layout(location = 0) out vec4 fragment_color;
 layout(location = 1) out vec3 fragment_normal;
@@ -15,8 +16,22 @@ layout (set = 1, binding = 2) uniform texture2D textures[TEXTURE_COUNT];
 
 layout (constant_id = 0) const float additional_color = 0.0;
 
+// Thresholds of a 4x4 ordered dither, used to fade out objects without having to
+// sort and blend them.
+const float DITHER_THRESHOLDS[16] = float[](
+    0.0 / 16.0, 8.0 / 16.0, 2.0 / 16.0, 10.0 / 16.0,
+    12.0 / 16.0, 4.0 / 16.0, 14.0 / 16.0, 6.0 / 16.0,
+    3.0 / 16.0, 11.0 / 16.0, 1.0 / 16.0, 9.0 / 16.0,
+    15.0 / 16.0, 7.0 / 16.0, 13.0 / 16.0, 5.0 / 16.0
+);
+
 void main() {
 
+    ivec2 dither_position = ivec2(gl_FragCoord.xy) % 4;
+    if (opacity <= DITHER_THRESHOLDS[dither_position.y * 4 + dither_position.x]) {
+        discard;
+    }
+
     vec4 diffuse_color;
     float alpha_channel;
 
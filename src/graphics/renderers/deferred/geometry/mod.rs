@@ -202,6 +202,7 @@ impl GeometryRenderer {
         vertex_buffer: ModelVertexBuffer,
         textures: &[Texture],
         world_matrix: Matrix4<f32>,
+        opacity: f32,
     ) {
         if textures.is_empty() {
             return;
@@ -233,6 +234,7 @@ impl GeometryRenderer {
         let vertex_count = vertex_buffer.size() as usize / std::mem::size_of::<ModelVertex>();
         let constants = Constants {
             world: world_matrix.into(),
+            opacity,
         };
 
         render_target
@@ -9,6 +9,7 @@ layout(location = 4) in float wind_affinity;
 layout(location = 0) out vec3 normal_out;
 layout(location = 1) out vec2 texture_coordinates_out;
 layout(location = 2) out int texture_index_out;
+layout(location = 3) out float opacity_out;
 
 layout(set = 0, binding = 0) uniform Matrices {
     mat4 view_projection;
@@ -17,6 +18,7 @@ layout(set = 0, binding = 0) uniform Matrices {
 
 layout(push_constant) uniform Constants {
     mat4 world;
+    float opacity;
 } constants;
 
 void main() {
@@ -29,4 +31,5 @@ void main() {
     normal_out = transpose(inverse(mat3(constants.world))) * normal;
     texture_coordinates_out = texture_coordinates;
     texture_index_out = texture_index;
+    opacity_out = constants.opacity;
 }
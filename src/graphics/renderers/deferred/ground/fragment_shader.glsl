@@ -0,0 +1,33 @@
+#version 450
+
+layout(location = 0) in vec3 normal;
+layout(location = 1) in vec2 texture_coordinates;
+layout(location = 2) flat in int texture_index;
+
+layout(location = 0) out vec4 fragment_color;
+layout(location = 1) out vec3 fragment_normal;
+
+layout (set = 1, binding = 0) uniform sampler nearest_sampler;
+layout (set = 1, binding = 1) uniform sampler linear_sampler;
+layout (set = 1, binding = 2) uniform texture2DArray textures;
+
+layout (constant_id = 0) const float additional_color = 0.0;
+
+void main() {
+
+    vec3 array_coordinates = vec3(texture_coordinates, texture_index);
+    vec4 diffuse_color = texture(sampler2DArray(textures, linear_sampler), array_coordinates);
+    float alpha_channel = texture(sampler2DArray(textures, nearest_sampler), array_coordinates).a;
+
+    if (alpha_channel + additional_color < 1.0) {
+        discard;
+    }
+
+    fragment_color = diffuse_color + vec4(additional_color);
+
+    fragment_color.r = pow(fragment_color.r, 1.0 / 1.8);
+    fragment_color.g = pow(fragment_color.g, 1.0 / 1.8);
+    fragment_color.b = pow(fragment_color.b, 1.0 / 1.8);
+
+    fragment_normal = normal;
+}
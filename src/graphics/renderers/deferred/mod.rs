@@ -1,4 +1,5 @@
 mod ambient;
+mod blob_shadow;
 #[cfg(feature = "debug")]
 mod r#box;
 #[cfg(feature = "debug")]
@@ -6,6 +7,7 @@ mod buffer;
 mod directional;
 mod entity;
 mod geometry;
+mod ground;
 mod overlay;
 mod point;
 mod rectangle;
@@ -23,25 +25,29 @@ use vulkano::format::Format;
 #[cfg(feature = "debug")]
 use vulkano::image::StorageImage;
 use vulkano::image::SwapchainImage;
-use vulkano::ordered_passes_renderpass;
 use vulkano::pipeline::graphics::viewport::Viewport;
 use vulkano::render_pass::{RenderPass, Subpass};
+use vulkano::{ordered_passes_renderpass, single_pass_renderpass};
 
 use self::ambient::AmbientLightRenderer;
+use self::blob_shadow::BlobShadowRenderer;
 #[cfg(feature = "debug")]
 use self::r#box::BoxRenderer;
 #[cfg(feature = "debug")]
 use self::buffer::BufferRenderer;
 use self::directional::DirectionalLightRenderer;
-use self::entity::EntityRenderer;
+pub(super) use self::entity::EntityRenderer;
 use self::geometry::GeometryRenderer;
+use self::ground::GroundRenderer;
 use self::overlay::OverlayRenderer;
 use self::point::PointLightRenderer;
 use self::rectangle::RectangleRenderer;
 use self::sprite::SpriteRenderer;
 use self::water::WaterRenderer;
 use self::water_light::WaterLightRenderer;
-use crate::graphics::{EntityRenderer as EntityRendererTrait, GeometryRenderer as GeometryRendererTrait, *};
+use crate::graphics::{
+    EntityRenderer as EntityRendererTrait, GeometryRenderer as GeometryRendererTrait, GroundRenderer as GroundRendererTrait, *,
+};
 use crate::loaders::{GameFileLoader, TextureLoader};
 use crate::network::EntityId;
 #[cfg(feature = "debug")]
@@ -50,7 +56,9 @@ use crate::world::{BoundingBox, MarkerIdentifier};
 #[derive(PartialEq, Eq)]
 pub enum DeferredSubrenderer {
     Geometry,
+    Ground,
     Entity,
+    BlobShadow,
     PointLight,
     #[cfg(feature = "debug")]
     BoundingBox,
@@ -60,8 +68,11 @@ pub struct DeferredRenderer {
     memory_allocator: Arc<MemoryAllocator>,
     queue: Arc<Queue>,
     render_pass: Arc<RenderPass>,
+    interface_render_pass: Arc<RenderPass>,
     geometry_renderer: GeometryRenderer,
+    ground_renderer: GroundRenderer,
     entity_renderer: EntityRenderer,
+    blob_shadow_renderer: BlobShadowRenderer,
     water_renderer: WaterRenderer,
     ambient_light_renderer: AmbientLightRenderer,
     directional_light_renderer: DirectionalLightRenderer,
@@ -78,6 +89,19 @@ pub struct DeferredRenderer {
     tile_textures: [Texture; 7],
     font_map: Texture,
     dimensions: [u32; 2],
+    scene_dimensions: [u32; 2],
+}
+
+fn scaled_dimensions(dimensions: [u32; 2], render_scale: f32) -> [u32; 2] {
+    dimensions.map(|dimension| ((dimension as f32 * render_scale) as u32).max(1))
+}
+
+fn scaled_viewport(dimensions: [u32; 2]) -> Viewport {
+    Viewport {
+        origin: [0.0, 0.0],
+        dimensions: dimensions.map(|dimension| dimension as f32),
+        depth_range: 0.0..1.0,
+    }
 }
 
 impl DeferredRenderer {
@@ -87,11 +111,12 @@ impl DeferredRenderer {
         swapchain_format: Format,
         viewport: Viewport,
         dimensions: [u32; 2],
+        render_scale: f32,
         game_file_loader: &mut GameFileLoader,
         texture_loader: &mut TextureLoader,
     ) -> Self {
         let device = memory_allocator.device().clone();
-        let render_pass = ordered_passes_renderpass!(device,
+        let render_pass = ordered_passes_renderpass!(device.clone(),
             attachments: {
                 output: {
                     load: Clear,
@@ -139,32 +164,55 @@ impl DeferredRenderer {
         )
         .unwrap();
 
+        // The interface is drawn in a separate pass at the full resolution of the
+        // swapchain, after the scene has been scaled up to it.
+        let interface_render_pass = single_pass_renderpass!(device,
+            attachments: {
+                output: {
+                    load: Load,
+                    store: Store,
+                    format: swapchain_format,
+                    samples: 1,
+                }
+            },
+            pass: {
+                color: [output],
+                depth_stencil: {}
+            }
+        )
+        .unwrap();
+
         let geometry_subpass = Subpass::from(render_pass.clone(), 0).unwrap();
         let lighting_subpass = Subpass::from(render_pass.clone(), 1).unwrap();
-
-        let geometry_renderer = GeometryRenderer::new(memory_allocator.clone(), geometry_subpass.clone(), viewport.clone());
-        let entity_renderer = EntityRenderer::new(memory_allocator.clone(), geometry_subpass.clone(), viewport.clone());
-        let water_renderer = WaterRenderer::new(memory_allocator.clone(), geometry_subpass, viewport.clone());
-        let ambient_light_renderer = AmbientLightRenderer::new(memory_allocator.clone(), lighting_subpass.clone(), viewport.clone());
+        let interface_subpass = interface_render_pass.clone().first_subpass();
+        let scene_dimensions = scaled_dimensions(dimensions, render_scale);
+        let scene_viewport = scaled_viewport(scene_dimensions);
+
+        let geometry_renderer = GeometryRenderer::new(memory_allocator.clone(), geometry_subpass.clone(), scene_viewport.clone());
+        let ground_renderer = GroundRenderer::new(memory_allocator.clone(), geometry_subpass.clone(), scene_viewport.clone());
+        let entity_renderer = EntityRenderer::new(memory_allocator.clone(), geometry_subpass.clone(), scene_viewport.clone());
+        let blob_shadow_renderer = BlobShadowRenderer::new(memory_allocator.clone(), geometry_subpass.clone(), scene_viewport.clone());
+        let water_renderer = WaterRenderer::new(memory_allocator.clone(), geometry_subpass, scene_viewport.clone());
+        let ambient_light_renderer = AmbientLightRenderer::new(memory_allocator.clone(), lighting_subpass.clone(), scene_viewport.clone());
         let directional_light_renderer =
-            DirectionalLightRenderer::new(memory_allocator.clone(), lighting_subpass.clone(), viewport.clone());
-        let point_light_renderer = PointLightRenderer::new(memory_allocator.clone(), lighting_subpass.clone(), viewport.clone());
-        let water_light_renderer = WaterLightRenderer::new(memory_allocator.clone(), lighting_subpass.clone(), viewport.clone());
-        let overlay_renderer = OverlayRenderer::new(memory_allocator.clone(), lighting_subpass.clone(), viewport.clone());
-        let rectangle_renderer = RectangleRenderer::new(memory_allocator.clone(), lighting_subpass.clone(), viewport.clone());
+            DirectionalLightRenderer::new(memory_allocator.clone(), lighting_subpass.clone(), scene_viewport.clone());
+        let point_light_renderer = PointLightRenderer::new(memory_allocator.clone(), lighting_subpass.clone(), scene_viewport.clone());
+        let water_light_renderer = WaterLightRenderer::new(memory_allocator.clone(), lighting_subpass.clone(), scene_viewport.clone());
+        let overlay_renderer = OverlayRenderer::new(memory_allocator.clone(), interface_subpass.clone(), viewport.clone());
+        let rectangle_renderer = RectangleRenderer::new(memory_allocator.clone(), interface_subpass.clone(), viewport.clone());
         let sprite_renderer = SpriteRenderer::new(
             memory_allocator.clone(),
-            lighting_subpass.clone(),
-            viewport.clone(),
+            interface_subpass,
+            viewport,
             #[cfg(feature = "debug")]
             game_file_loader,
             #[cfg(feature = "debug")]
             texture_loader,
         );
         #[cfg(feature = "debug")]
-        let buffer_renderer = BufferRenderer::new(memory_allocator.clone(), lighting_subpass.clone(), viewport.clone());
+        let buffer_renderer = BufferRenderer::new(memory_allocator.clone(), lighting_subpass.clone(), scene_viewport.clone());
         #[cfg(feature = "debug")]
-        let box_renderer = BoxRenderer::new(memory_allocator.clone(), lighting_subpass, viewport);
+        let box_renderer = BoxRenderer::new(memory_allocator.clone(), lighting_subpass, scene_viewport);
 
         let font_map = texture_loader.get("font.png", game_file_loader).unwrap();
 
@@ -183,8 +231,11 @@ impl DeferredRenderer {
             memory_allocator,
             queue,
             render_pass,
+            interface_render_pass,
             geometry_renderer,
+            ground_renderer,
             entity_renderer,
+            blob_shadow_renderer,
             water_renderer,
             ambient_light_renderer,
             directional_light_renderer,
@@ -201,45 +252,64 @@ impl DeferredRenderer {
             tile_textures,
             font_map,
             dimensions,
+            scene_dimensions,
         }
     }
 
-    pub fn recreate_pipeline(&mut self, viewport: Viewport, dimensions: [u32; 2], #[cfg(feature = "debug")] wireframe: bool) {
+    pub fn recreate_pipeline(
+        &mut self,
+        viewport: Viewport,
+        dimensions: [u32; 2],
+        render_scale: f32,
+        #[cfg(feature = "debug")] wireframe: bool,
+    ) {
         let device = self.memory_allocator.device().clone();
         let geometry_subpass = Subpass::from(self.render_pass.clone(), 0).unwrap();
         let lighting_subpass = Subpass::from(self.render_pass.clone(), 1).unwrap();
+        let interface_subpass = self.interface_render_pass.clone().first_subpass();
+        let scene_dimensions = scaled_dimensions(dimensions, render_scale);
+        let scene_viewport = scaled_viewport(scene_dimensions);
 
         self.geometry_renderer.recreate_pipeline(
             device.clone(),
             geometry_subpass.clone(),
-            viewport.clone(),
+            scene_viewport.clone(),
+            #[cfg(feature = "debug")]
+            wireframe,
+        );
+        self.ground_renderer.recreate_pipeline(
+            device.clone(),
+            geometry_subpass.clone(),
+            scene_viewport.clone(),
             #[cfg(feature = "debug")]
             wireframe,
         );
         self.entity_renderer
-            .recreate_pipeline(device.clone(), geometry_subpass.clone(), viewport.clone());
+            .recreate_pipeline(device.clone(), geometry_subpass.clone(), scene_viewport.clone());
+        self.blob_shadow_renderer
+            .recreate_pipeline(device.clone(), geometry_subpass.clone(), scene_viewport.clone());
         self.water_renderer
-            .recreate_pipeline(device.clone(), geometry_subpass, viewport.clone());
+            .recreate_pipeline(device.clone(), geometry_subpass, scene_viewport.clone());
         self.ambient_light_renderer
-            .recreate_pipeline(device.clone(), lighting_subpass.clone(), viewport.clone());
+            .recreate_pipeline(device.clone(), lighting_subpass.clone(), scene_viewport.clone());
         self.directional_light_renderer
-            .recreate_pipeline(device.clone(), lighting_subpass.clone(), viewport.clone());
+            .recreate_pipeline(device.clone(), lighting_subpass.clone(), scene_viewport.clone());
         self.point_light_renderer
-            .recreate_pipeline(device.clone(), lighting_subpass.clone(), viewport.clone());
+            .recreate_pipeline(device.clone(), lighting_subpass.clone(), scene_viewport.clone());
         self.water_light_renderer
-            .recreate_pipeline(device.clone(), lighting_subpass.clone(), viewport.clone());
+            .recreate_pipeline(device.clone(), lighting_subpass.clone(), scene_viewport.clone());
         self.overlay_renderer
-            .recreate_pipeline(device.clone(), lighting_subpass.clone(), viewport.clone());
+            .recreate_pipeline(device.clone(), interface_subpass.clone(), viewport.clone());
         self.rectangle_renderer
-            .recreate_pipeline(device.clone(), lighting_subpass.clone(), viewport.clone());
-        self.sprite_renderer
-            .recreate_pipeline(device.clone(), lighting_subpass.clone(), viewport.clone());
+            .recreate_pipeline(device.clone(), interface_subpass.clone(), viewport.clone());
+        self.sprite_renderer.recreate_pipeline(device.clone(), interface_subpass, viewport);
         #[cfg(feature = "debug")]
         self.buffer_renderer
-            .recreate_pipeline(device.clone(), lighting_subpass.clone(), viewport.clone());
+            .recreate_pipeline(device.clone(), lighting_subpass.clone(), scene_viewport.clone());
         #[cfg(feature = "debug")]
-        self.box_renderer.recreate_pipeline(device, lighting_subpass, viewport);
+        self.box_renderer.recreate_pipeline(device, lighting_subpass, scene_viewport);
         self.dimensions = dimensions;
+        self.scene_dimensions = scene_dimensions;
     }
 
     pub fn create_render_target(&self, swapchain_image: Arc<SwapchainImage>) -> <Self as Renderer>::Target {
@@ -247,8 +317,9 @@ impl DeferredRenderer {
             self.memory_allocator.clone(),
             self.queue.clone(),
             self.render_pass.clone(),
+            self.interface_render_pass.clone(),
             swapchain_image,
-            self.dimensions,
+            self.scene_dimensions,
         )
     }
 
@@ -263,6 +334,24 @@ impl DeferredRenderer {
         self.water_renderer.render(render_target, camera, vertex_buffer, day_timer);
     }
 
+    /// Soft round shadow on the ground, centered on `position` and facing the
+    /// direction of `normal`.
+    pub fn render_blob_shadow(
+        &self,
+        render_target: &mut <Self as Renderer>::Target,
+        camera: &dyn Camera,
+        position: Vector3<f32>,
+        normal: Vector3<f32>,
+        radius: f32,
+        opacity: f32,
+    ) {
+        if render_target.bind_subrenderer(DeferredSubrenderer::BlobShadow) {
+            self.blob_shadow_renderer.bind_pipeline(render_target, camera);
+        }
+
+        self.blob_shadow_renderer.render(render_target, position, normal, radius, opacity);
+    }
+
     pub fn ambient_light(&self, render_target: &mut <Self as Renderer>::Target, color: Color) {
         render_target.unbind_subrenderer();
         self.ambient_light_renderer.render(render_target, color);
@@ -402,6 +491,7 @@ impl DeferredRenderer {
             vertex_buffer,
             &self.tile_textures,
             Matrix4::identity(),
+            1.0,
             0.0,
         );
     }
@@ -449,6 +539,7 @@ impl GeometryRendererTrait for DeferredRenderer {
         vertex_buffer: ModelVertexBuffer,
         textures: &[Texture],
         world_matrix: Matrix4<f32>,
+        opacity: f32,
         time: f32,
     ) where
         Self: Renderer,
@@ -458,7 +549,26 @@ impl GeometryRendererTrait for DeferredRenderer {
         }
 
         self.geometry_renderer
-            .render(render_target, camera, vertex_buffer, textures, world_matrix);
+            .render(render_target, camera, vertex_buffer, textures, world_matrix, opacity);
+    }
+}
+
+impl GroundRendererTrait for DeferredRenderer {
+    fn render_ground(
+        &self,
+        render_target: &mut <Self as Renderer>::Target,
+        camera: &dyn Camera,
+        vertex_buffer: ModelVertexBuffer,
+        texture_array: Texture,
+        time: f32,
+    ) where
+        Self: Renderer,
+    {
+        if render_target.bind_subrenderer(DeferredSubrenderer::Ground) {
+            self.ground_renderer.bind_pipeline(render_target, camera, time);
+        }
+
+        self.ground_renderer.render(render_target, camera, vertex_buffer, texture_array);
     }
 }
 
@@ -468,6 +578,7 @@ impl EntityRendererTrait for DeferredRenderer {
         render_target: &mut <Self as Renderer>::Target,
         camera: &dyn Camera,
         texture: Texture,
+        palette: Option<Texture>,
         position: Vector3<f32>,
         origin: Vector3<f32>,
         scale: Vector2<f32>,
@@ -479,13 +590,14 @@ impl EntityRendererTrait for DeferredRenderer {
         Self: Renderer,
     {
         if render_target.bind_subrenderer(DeferredSubrenderer::Entity) {
-            self.entity_renderer.bind_pipeline(render_target, camera);
+            self.entity_renderer.bind_pipeline(render_target.state.get_builder(), camera);
         }
 
         self.entity_renderer.render(
-            render_target,
+            render_target.state.get_builder(),
             camera,
             texture,
+            palette,
             position,
             origin,
             scale,
@@ -0,0 +1,18 @@
+float rounded_box_distance(vec2 point, vec4 bounds, vec4 corner_radius) {
+    vec2 half_size = (bounds.zw - bounds.xy) / 2.0;
+    vec2 offset = point - (bounds.xy + half_size);
+
+    // corner_radius is ordered top-left, top-right, bottom-right, bottom-left
+    float radius = offset.x < 0.0 ? (offset.y < 0.0 ? corner_radius.x : corner_radius.w) : (offset.y < 0.0 ? corner_radius.y : corner_radius.z);
+    radius = min(radius, min(half_size.x, half_size.y));
+    vec2 distance = abs(offset) - half_size + radius;
+
+    return min(max(distance.x, distance.y), 0.0) + length(max(distance, 0.0)) - radius;
+}
+
+// The rectangular part of the clip is handled by the scissor.
+void clip(vec4 clip_bounds, vec4 clip_corner_radius) {
+    if (rounded_box_distance(gl_FragCoord.xy, clip_bounds, clip_corner_radius) > 0.0) {
+        discard;
+    }
+}
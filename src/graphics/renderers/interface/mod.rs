@@ -9,20 +9,38 @@ use std::sync::Arc;
 use cgmath::{Vector2, Vector4};
 use vulkano::device::{DeviceOwned, Queue};
 use vulkano::format::{ClearColorValue, Format};
-use vulkano::image::{ImageUsage, SampleCount};
-use vulkano::pipeline::graphics::viewport::Viewport;
+use vulkano::image::view::ImageView;
+use vulkano::image::{AttachmentImage, ImageAccess, ImageUsage, SampleCount};
+use vulkano::pipeline::graphics::viewport::{Scissor, Viewport};
 use vulkano::render_pass::RenderPass;
 
 use self::rectangle::RectangleRenderer;
 use self::sprite::SpriteRenderer;
 use self::text::TextRenderer;
-use crate::graphics::{Color, MemoryAllocator, Renderer, SingleRenderTarget, Texture};
+use crate::graphics::{Color, ImageBuffer, MemoryAllocator, Renderer, SingleRenderTarget, Texture};
+use crate::interface::{Background, ClipSize, NineSlice};
 use crate::loaders::{FontLoader, GameFileLoader, TextureLoader};
 
+/// The rectangular part of the clip is applied with a dynamic scissor, so the
+/// fragment shaders only need to handle rounded corners.
+fn clip_scissor(clip_size: &ClipSize) -> Scissor {
+    let left = clip_size.bounds.x.max(0.0);
+    let top = clip_size.bounds.y.max(0.0);
+
+    Scissor {
+        origin: [left as u32, top as u32],
+        dimensions: [
+            (clip_size.bounds.z - left).max(0.0).ceil() as u32,
+            (clip_size.bounds.w - top).max(0.0).ceil() as u32,
+        ],
+    }
+}
+
 pub struct InterfaceRenderer {
     memory_allocator: Arc<MemoryAllocator>,
     queue: Arc<Queue>,
     render_pass: Arc<RenderPass>,
+    resume_render_pass: Arc<RenderPass>,
     rectangle_renderer: RectangleRenderer,
     sprite_renderer: SpriteRenderer,
     text_renderer: TextRenderer,
@@ -61,6 +79,25 @@ impl InterfaceRenderer {
         )
         .unwrap();
 
+        // Only differs in the load operation, so the pipelines can be used with both
+        // render passes.
+        let resume_render_pass = vulkano::single_pass_renderpass!(
+            device,
+            attachments: {
+                interface: {
+                    load: Load,
+                    store: Store,
+                    format: Format::R8G8B8A8_SRGB,
+                    samples: 4,
+                }
+            },
+            pass: {
+                color: [interface],
+                depth_stencil: {}
+            }
+        )
+        .unwrap();
+
         let subpass = render_pass.clone().first_subpass();
         let rectangle_renderer = RectangleRenderer::new(memory_allocator.clone(), subpass.clone(), viewport.clone());
         let sprite_renderer = SpriteRenderer::new(memory_allocator.clone(), subpass.clone(), viewport.clone());
@@ -75,6 +112,7 @@ impl InterfaceRenderer {
             memory_allocator,
             queue,
             render_pass,
+            resume_render_pass,
             rectangle_renderer,
             sprite_renderer,
             text_renderer: font_renderer,
@@ -101,6 +139,7 @@ impl InterfaceRenderer {
     pub fn create_render_target(&self) -> <Self as Renderer>::Target {
         let image_usage = ImageUsage {
             sampled: true,
+            transfer_src: true,
             transfer_dst: true,
             color_attachment: true,
             input_attachment: true,
@@ -116,6 +155,27 @@ impl InterfaceRenderer {
             image_usage,
             ClearColorValue::Float([0.0, 0.0, 0.0, 0.0]),
         )
+        .with_resume_render_pass(self.resume_render_pass.clone())
+    }
+
+    /// Image that the rendered output of a window can be copied to.
+    pub fn create_window_cache(&self, size: Vector2<f32>) -> ImageBuffer {
+        let image_usage = ImageUsage {
+            sampled: true,
+            transfer_dst: true,
+            ..ImageUsage::empty()
+        };
+
+        ImageView::new_default(Arc::new(
+            AttachmentImage::with_usage(
+                &*self.memory_allocator,
+                [size.x as u32, size.y as u32],
+                Format::R8G8B8A8_SRGB,
+                image_usage,
+            )
+            .unwrap(),
+        ))
+        .unwrap()
     }
 
     pub fn render_sprite(
@@ -124,7 +184,7 @@ impl InterfaceRenderer {
         texture: Texture,
         position: Vector2<f32>,
         size: Vector2<f32>,
-        clip_size: Vector4<f32>,
+        clip_size: ClipSize,
         color: Color,
         smooth: bool,
     ) {
@@ -133,18 +193,121 @@ impl InterfaceRenderer {
             .render(render_target, texture, window_size, position, size, clip_size, color, smooth);
     }
 
+    /// Show an image that was rendered by another renderer, like the preview of
+    /// the player.
+    pub fn render_image(
+        &self,
+        render_target: &mut <InterfaceRenderer as Renderer>::Target,
+        image: ImageBuffer,
+        position: Vector2<f32>,
+        size: Vector2<f32>,
+        clip_size: ClipSize,
+    ) {
+        let window_size = Vector2::new(self.dimensions[0] as usize, self.dimensions[1] as usize);
+        self.sprite_renderer
+            .render(render_target, image, window_size, position, size, clip_size, Color::monochrome(255), true);
+    }
+
     pub fn render_rectangle(
         &self,
         render_target: &mut <InterfaceRenderer as Renderer>::Target,
         position: Vector2<f32>,
         size: Vector2<f32>,
-        clip_size: Vector4<f32>,
+        clip_size: ClipSize,
         corner_radius: Vector4<f32>,
         color: Color,
     ) {
         let window_size = Vector2::new(self.dimensions[0] as usize, self.dimensions[1] as usize);
-        self.rectangle_renderer
-            .render(render_target, window_size, position, size, clip_size, corner_radius, color);
+        self.rectangle_renderer.render(
+            render_target,
+            window_size,
+            position,
+            size,
+            clip_size,
+            corner_radius,
+            color,
+            None,
+        );
+    }
+
+    pub fn render_background(
+        &self,
+        render_target: &mut <InterfaceRenderer as Renderer>::Target,
+        background: &Background,
+        position: Vector2<f32>,
+        size: Vector2<f32>,
+        clip_size: ClipSize,
+        corner_radius: Vector4<f32>,
+        color: Color,
+        scaling: f32,
+    ) {
+        let window_size = Vector2::new(self.dimensions[0] as usize, self.dimensions[1] as usize);
+
+        match background {
+            Background::Gradient(gradient) => {
+                self.rectangle_renderer.render(
+                    render_target,
+                    window_size,
+                    position,
+                    size,
+                    clip_size,
+                    corner_radius,
+                    color,
+                    Some(gradient),
+                );
+            }
+            Background::NineSlice(NineSlice {
+                borders,
+                loaded_texture: Some(texture),
+                ..
+            }) => {
+                self.render_nine_slice(render_target, texture, *borders, position, size, clip_size, color, scaling);
+            }
+            _ => self.render_rectangle(render_target, position, size, clip_size, corner_radius, color),
+        }
+    }
+
+    fn render_nine_slice(
+        &self,
+        render_target: &mut <InterfaceRenderer as Renderer>::Target,
+        texture: &Texture,
+        borders: Vector4<f32>,
+        position: Vector2<f32>,
+        size: Vector2<f32>,
+        clip_size: ClipSize,
+        color: Color,
+        scaling: f32,
+    ) {
+        let window_size = Vector2::new(self.dimensions[0] as usize, self.dimensions[1] as usize);
+        let texture_size = texture.image().dimensions().width_height().map(|dimension| dimension as f32);
+
+        // The image keeps its own colors, only the transparency of the background is
+        // applied.
+        let color = Color::rgba(255, 255, 255, color.alpha);
+
+        // Offsets of the four edges of each column and row, both on screen and in
+        // texture coordinates.
+        let screen_x = [0.0, borders.x * scaling, size.x - borders.z * scaling, size.x];
+        let screen_y = [0.0, borders.y * scaling, size.y - borders.w * scaling, size.y];
+        let texture_x = [0.0, borders.x / texture_size[0], 1.0 - borders.z / texture_size[0], 1.0];
+        let texture_y = [0.0, borders.y / texture_size[1], 1.0 - borders.w / texture_size[1], 1.0];
+
+        for row in 0..3 {
+            for column in 0..3 {
+                self.sprite_renderer.render_region(
+                    render_target,
+                    texture.clone(),
+                    window_size,
+                    position + Vector2::new(screen_x[column], screen_y[row]),
+                    Vector2::new(screen_x[column + 1] - screen_x[column], screen_y[row + 1] - screen_y[row]),
+                    clip_size,
+                    Vector2::new(texture_x[column], texture_y[row]),
+                    Vector2::new(texture_x[column + 1] - texture_x[column], texture_y[row + 1] - texture_y[row]),
+                    color,
+                    true,
+                );
+            }
+        }
     }
 
     pub fn render_checkbox(
@@ -152,7 +315,7 @@ impl InterfaceRenderer {
         render_target: &mut <InterfaceRenderer as Renderer>::Target,
         position: Vector2<f32>,
         size: Vector2<f32>,
-        clip_size: Vector4<f32>,
+        clip_size: ClipSize,
         color: Color,
         checked: bool,
     ) {
@@ -169,7 +332,7 @@ impl InterfaceRenderer {
         render_target: &mut <InterfaceRenderer as Renderer>::Target,
         position: Vector2<f32>,
         size: Vector2<f32>,
-        clip_size: Vector4<f32>,
+        clip_size: ClipSize,
         color: Color,
         expanded: bool,
     ) {
@@ -186,7 +349,7 @@ impl InterfaceRenderer {
         render_target: &mut <InterfaceRenderer as Renderer>::Target,
         text: &str,
         position: Vector2<f32>,
-        clip_size: Vector4<f32>,
+        clip_size: ClipSize,
         color: Color,
         font_size: f32,
     ) -> f32 {
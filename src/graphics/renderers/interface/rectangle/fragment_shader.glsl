@@ -7,42 +7,41 @@ layout(location = 0) out vec4 fragment_color;
 layout(push_constant) uniform Constants {
     vec2 screen_position;
     vec2 screen_size;
-    vec4 clip_size;
+    vec4 clip_bounds;
+    vec4 clip_corner_radius;
+    vec4 bounds;
     vec4 corner_radius;
     vec4 color;
+    vec4 gradient_color;
+    // x is the kind of gradient (0 = none, 1 = linear, 2 = radial), y is the angle
+    vec2 gradient;
 } constants;
 
-void main() {
+#include <clip.glsl>
 
-    vec2 coords = fragment_position * constants.screen_size;
+void main() {
 
-    // top-left
-    if (length(coords - constants.corner_radius.x) > constants.corner_radius.x && coords.x < constants.corner_radius.x &&
-        coords.y < constants.corner_radius.x) {
+    if (rounded_box_distance(gl_FragCoord.xy, constants.bounds, constants.corner_radius) > 0.0) {
         discard;
     }
 
-    // top-right
-    if (length(coords - vec2(constants.screen_size.x - constants.corner_radius.y, constants.corner_radius.y)) > constants.corner_radius.y &&
-        constants.screen_size.x - coords.x < constants.corner_radius.y && coords.y < constants.corner_radius.y) {
-        discard;
-    }
+    clip(constants.clip_bounds, constants.clip_corner_radius);
 
-    // bottom-right
-    if (length(coords - constants.screen_size + constants.corner_radius.z) > constants.corner_radius.z &&
-        constants.screen_size.x - coords.x < constants.corner_radius.z && constants.screen_size.y - coords.y < constants.corner_radius.z) {
-        discard;
+    if (constants.gradient.x < 0.5) {
+        fragment_color = constants.color;
+        return;
     }
 
-    // bottom_left
-    if (length(coords - vec2(constants.corner_radius.w, constants.screen_size.y - constants.corner_radius.w)) > constants.corner_radius.w &&
-        coords.x < constants.corner_radius.w && constants.screen_size.y - coords.y < constants.corner_radius.w) {
-        discard;
-    }
+    vec2 size = constants.bounds.zw - constants.bounds.xy;
+    vec2 offset = (gl_FragCoord.xy - constants.bounds.xy) / size - 0.5;
+    float factor;
 
-    if (gl_FragCoord.x < constants.clip_size.x || gl_FragCoord.y < constants.clip_size.y || gl_FragCoord.x > constants.clip_size.z || gl_FragCoord.y > constants.clip_size.w) {
-        discard;
+    if (constants.gradient.x < 1.5) {
+        vec2 direction = vec2(cos(constants.gradient.y), sin(constants.gradient.y));
+        factor = dot(offset, direction) + 0.5;
+    } else {
+        factor = length(offset) * 2.0;
     }
 
-    fragment_color = constants.color;
+    fragment_color = mix(constants.color, constants.gradient_color, clamp(factor, 0.0, 1.0));
 }
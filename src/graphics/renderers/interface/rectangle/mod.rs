@@ -12,11 +12,11 @@ mod vertex_shader {
 mod fragment_shader {
     vulkano_shaders::shader! {
         ty: "fragment",
-        path: "src/graphics/renderers/interface/rectangle/fragment_shader.glsl"
+        path: "src/graphics/renderers/interface/rectangle/fragment_shader.glsl",
+        include: ["src/graphics/renderers/interface"]
     }
 }
 
-use std::iter;
 use std::sync::Arc;
 
 use cgmath::{Vector2, Vector4};
@@ -30,7 +30,9 @@ use vulkano::render_pass::Subpass;
 use vulkano::shader::ShaderModule;
 
 use self::vertex_shader::ty::Constants;
+use super::clip_scissor;
 use crate::graphics::*;
+use crate::interface::{ClipSize, Gradient, GradientKind};
 
 unsafe impl bytemuck::Zeroable for Constants {}
 unsafe impl bytemuck::Pod for Constants {}
@@ -70,7 +72,10 @@ impl RectangleRenderer {
         GraphicsPipeline::start()
             .vertex_shader(vertex_shader.entry_point("main").unwrap(), ())
             .input_assembly_state(InputAssemblyState::new())
-            .viewport_state(ViewportState::viewport_fixed_scissor_irrelevant(iter::once(viewport)))
+            .viewport_state(ViewportState::FixedViewport {
+                viewports: vec![viewport],
+                scissor_count_dynamic: false,
+            })
             .fragment_shader(fragment_shader.entry_point("main").unwrap(), ())
             .color_blend_state(ColorBlendState::new(1).blend(INTERFACE_ATTACHMENT_BLEND))
             .multisample_state(MultisampleState {
@@ -88,36 +93,58 @@ impl RectangleRenderer {
         window_size: Vector2<usize>,
         screen_position: Vector2<f32>,
         screen_size: Vector2<f32>,
-        clip_size: Vector4<f32>,
+        clip_size: ClipSize,
         corner_radius: Vector4<f32>,
         color: Color,
+        gradient: Option<&Gradient>,
     ) {
         let layout = self.pipeline.layout().clone();
 
+        let bounds = Vector4::new(
+            screen_position.x,
+            screen_position.y,
+            screen_position.x + screen_size.x,
+            screen_position.y + screen_size.y,
+        );
+
         let half_screen = Vector2::new(window_size.x as f32 / 2.0, window_size.y as f32 / 2.0);
         let screen_position = Vector2::new(screen_position.x / half_screen.x, screen_position.y / half_screen.y);
         let screen_size = Vector2::new(screen_size.x / half_screen.x, screen_size.y / half_screen.y);
 
-        let pixel_size = 1.0 / window_size.y as f32;
-        let corner_radius = Vector4::new(
-            corner_radius.x * pixel_size,
-            corner_radius.y * pixel_size,
-            corner_radius.z * pixel_size,
-            corner_radius.w * pixel_size,
-        );
+        let (gradient_color, gradient) = match gradient {
+            Some(gradient) => {
+                let kind = match gradient.kind {
+                    GradientKind::Linear => 1.0,
+                    GradientKind::Radial => 2.0,
+                };
+
+                (gradient.end_color, [kind, gradient.angle.to_radians()])
+            }
+            None => (color, [0.0, 0.0]),
+        };
 
         let constants = Constants {
             screen_position: screen_position.into(),
             screen_size: screen_size.into(),
-            clip_size: clip_size.into(),
+            clip_bounds: clip_size.rounded_bounds.into(),
+            clip_corner_radius: clip_size.corner_radius.into(),
+            bounds: bounds.into(),
             corner_radius: corner_radius.into(),
             color: [color.red_f32(), color.green_f32(), color.blue_f32(), color.alpha_f32()],
+            gradient_color: [
+                gradient_color.red_f32(),
+                gradient_color.green_f32(),
+                gradient_color.blue_f32(),
+                gradient_color.alpha_f32(),
+            ],
+            gradient,
         };
 
         render_target
             .state
             .get_builder()
             .bind_pipeline_graphics(self.pipeline.clone())
+            .set_scissor(0, [clip_scissor(&clip_size)])
             .push_constants(layout, 0, constants)
             .draw(6, 1, 0, 0)
             .unwrap();
@@ -5,9 +5,14 @@ layout(location = 0) out vec2 position_out;
 layout(push_constant) uniform Constants {
     vec2 screen_position;
     vec2 screen_size;
-    vec4 clip_size;
+    vec4 clip_bounds;
+    vec4 clip_corner_radius;
+    vec4 bounds;
     vec4 corner_radius;
     vec4 color;
+    vec4 gradient_color;
+    // x is the kind of gradient (0 = none, 1 = linear, 2 = radial), y is the angle
+    vec2 gradient;
 } constants;
 
 const vec2 data[6] = vec2[]
@@ -12,16 +12,17 @@ mod vertex_shader {
 mod fragment_shader {
     vulkano_shaders::shader! {
         ty: "fragment",
-        path: "src/graphics/renderers/interface/sprite/fragment_shader.glsl"
+        path: "src/graphics/renderers/interface/sprite/fragment_shader.glsl",
+        include: ["src/graphics/renderers/interface"]
     }
 }
 
-use std::iter;
 use std::sync::Arc;
 
-use cgmath::{Vector2, Vector4};
+use cgmath::Vector2;
 use vulkano::descriptor_set::{PersistentDescriptorSet, WriteDescriptorSet};
 use vulkano::device::{Device, DeviceOwned};
+use vulkano::image::ImageViewAbstract;
 use vulkano::pipeline::graphics::color_blend::ColorBlendState;
 use vulkano::pipeline::graphics::input_assembly::InputAssemblyState;
 use vulkano::pipeline::graphics::multisample::MultisampleState;
@@ -32,7 +33,9 @@ use vulkano::sampler::{Filter, Sampler, SamplerCreateInfo};
 use vulkano::shader::ShaderModule;
 
 use self::vertex_shader::ty::Constants;
+use super::clip_scissor;
 use crate::graphics::*;
+use crate::interface::ClipSize;
 
 unsafe impl bytemuck::Zeroable for Constants {}
 unsafe impl bytemuck::Pod for Constants {}
@@ -86,7 +89,10 @@ impl SpriteRenderer {
         GraphicsPipeline::start()
             .vertex_shader(vertex_shader.entry_point("main").unwrap(), ())
             .input_assembly_state(InputAssemblyState::new())
-            .viewport_state(ViewportState::viewport_fixed_scissor_irrelevant(iter::once(viewport)))
+            .viewport_state(ViewportState::FixedViewport {
+                viewports: vec![viewport],
+                scissor_count_dynamic: false,
+            })
             .fragment_shader(fragment_shader.entry_point("main").unwrap(), ())
             .color_blend_state(ColorBlendState::new(1).blend(INTERFACE_ATTACHMENT_BLEND))
             .multisample_state(MultisampleState {
@@ -101,10 +107,10 @@ impl SpriteRenderer {
     fn build(
         &self,
         render_target: &mut <InterfaceRenderer as Renderer>::Target,
-        texture: Texture,
+        texture: Arc<dyn ImageViewAbstract>,
         screen_position: Vector2<f32>,
         screen_size: Vector2<f32>,
-        clip_size: Vector4<f32>,
+        clip_size: ClipSize,
         texture_position: Vector2<f32>,
         texture_size: Vector2<f32>,
         color: Color,
@@ -126,7 +132,8 @@ impl SpriteRenderer {
         let constants = Constants {
             screen_position: screen_position.into(),
             screen_size: screen_size.into(),
-            clip_size: clip_size.into(),
+            clip_bounds: clip_size.rounded_bounds.into(),
+            clip_corner_radius: clip_size.corner_radius.into(),
             texture_position: texture_position.into(),
             texture_size: texture_size.into(),
             color: [color.red_f32(), color.green_f32(), color.blue_f32(), color.alpha_f32()],
@@ -136,6 +143,7 @@ impl SpriteRenderer {
             .state
             .get_builder()
             .bind_pipeline_graphics(self.pipeline.clone())
+            .set_scissor(0, [clip_scissor(&clip_size)])
             .bind_descriptor_sets(PipelineBindPoint::Graphics, layout.clone(), 0, set)
             .push_constants(layout, 0, constants)
             .draw(6, 1, 0, 0)
@@ -145,11 +153,38 @@ impl SpriteRenderer {
     pub fn render(
         &self,
         render_target: &mut <InterfaceRenderer as Renderer>::Target,
-        texture: Texture,
+        texture: Arc<dyn ImageViewAbstract>,
         window_size: Vector2<usize>,
         screen_position: Vector2<f32>,
         screen_size: Vector2<f32>,
-        clip_size: Vector4<f32>,
+        clip_size: ClipSize,
+        color: Color,
+        smooth: bool,
+    ) {
+        self.render_region(
+            render_target,
+            texture,
+            window_size,
+            screen_position,
+            screen_size,
+            clip_size,
+            Vector2::new(0.0, 0.0),
+            Vector2::new(1.0, 1.0),
+            color,
+            smooth,
+        );
+    }
+
+    pub fn render_region(
+        &self,
+        render_target: &mut <InterfaceRenderer as Renderer>::Target,
+        texture: Arc<dyn ImageViewAbstract>,
+        window_size: Vector2<usize>,
+        screen_position: Vector2<f32>,
+        screen_size: Vector2<f32>,
+        clip_size: ClipSize,
+        texture_position: Vector2<f32>,
+        texture_size: Vector2<f32>,
         color: Color,
         smooth: bool,
     ) {
@@ -164,8 +199,8 @@ impl SpriteRenderer {
             screen_position,
             screen_size,
             clip_size,
-            Vector2::new(0.0, 0.0),
-            Vector2::new(1.0, 1.0),
+            texture_position,
+            texture_size,
             color,
             smooth,
         );
@@ -5,7 +5,8 @@ layout(location = 0) out vec2 texture_coordinates;
 layout(push_constant) uniform Constants {
     vec2 screen_position;
     vec2 screen_size;
-    vec4 clip_size;
+    vec4 clip_bounds;
+    vec4 clip_corner_radius;
     vec2 texture_position;
     vec2 texture_size;
     vec4 color;
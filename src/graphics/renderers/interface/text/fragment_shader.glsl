@@ -9,17 +9,18 @@ layout (set = 0, binding = 0) uniform sampler2D sprite_texture;
 layout(push_constant) uniform Constants {
     vec2 screen_position;
     vec2 screen_size;
-    vec4 clip_size;
+    vec4 clip_bounds;
+    vec4 clip_corner_radius;
     vec2 texture_position;
     vec2 texture_size;
     vec4 color;
 } constants;
 
+#include <clip.glsl>
+
 void main() {
 
-    if (gl_FragCoord.x < constants.clip_size.x || gl_FragCoord.y < constants.clip_size.y || gl_FragCoord.x > constants.clip_size.z || gl_FragCoord.y > constants.clip_size.w) {
-        discard;
-    }
+    clip(constants.clip_bounds, constants.clip_corner_radius);
 
     fragment_color = vec4(constants.color.rgb, texture(sprite_texture, texture_coordinates).r * constants.color.a);
 }
@@ -12,16 +12,16 @@ mod vertex_shader {
 mod fragment_shader {
     vulkano_shaders::shader! {
         ty: "fragment",
-        path: "src/graphics/renderers/interface/text/fragment_shader.glsl"
+        path: "src/graphics/renderers/interface/text/fragment_shader.glsl",
+        include: ["src/graphics/renderers/interface"]
     }
 }
 
 use std::cell::RefCell;
-use std::iter;
 use std::rc::Rc;
 use std::sync::Arc;
 
-use cgmath::{Vector2, Vector4};
+use cgmath::Vector2;
 use vulkano::descriptor_set::{PersistentDescriptorSet, WriteDescriptorSet};
 use vulkano::device::{Device, DeviceOwned};
 use vulkano::pipeline::graphics::color_blend::ColorBlendState;
@@ -34,7 +34,9 @@ use vulkano::sampler::{Filter, Sampler, SamplerCreateInfo};
 use vulkano::shader::ShaderModule;
 
 use self::vertex_shader::ty::Constants;
+use super::clip_scissor;
 use crate::graphics::*;
+use crate::interface::ClipSize;
 use crate::loaders::FontLoader;
 
 unsafe impl bytemuck::Zeroable for Constants {}
@@ -87,7 +89,10 @@ impl TextRenderer {
         GraphicsPipeline::start()
             .vertex_shader(vertex_shader.entry_point("main").unwrap(), ())
             .input_assembly_state(InputAssemblyState::new())
-            .viewport_state(ViewportState::viewport_fixed_scissor_irrelevant(iter::once(viewport)))
+            .viewport_state(ViewportState::FixedViewport {
+                viewports: vec![viewport],
+                scissor_count_dynamic: false,
+            })
             .fragment_shader(fragment_shader.entry_point("main").unwrap(), ())
             .color_blend_state(ColorBlendState::new(1).blend(INTERFACE_ATTACHMENT_BLEND))
             .multisample_state(MultisampleState {
@@ -105,7 +110,7 @@ impl TextRenderer {
         text: &str,
         window_size: Vector2<usize>,
         screen_position: Vector2<f32>,
-        clip_size: Vector4<f32>,
+        clip_size: ClipSize,
         color: Color,
         font_size: f32,
     ) -> f32 {
@@ -114,7 +119,7 @@ impl TextRenderer {
 
         let mut font_loader = self.font_loader.borrow_mut();
         let texture = font_loader.get_font_atlas();
-        let (character_layout, heigth) = font_loader.get(text, color, font_size, clip_size.z - screen_position.x);
+        let (character_layout, heigth) = font_loader.get(text, color, font_size, clip_size.bounds.z - screen_position.x);
 
         let half_screen = Vector2::new(window_size.x as f32 / 2.0, window_size.y as f32 / 2.0);
 
@@ -127,6 +132,7 @@ impl TextRenderer {
             .state
             .get_builder()
             .bind_pipeline_graphics(self.pipeline.clone())
+            .set_scissor(0, [clip_scissor(&clip_size)])
             .bind_descriptor_sets(PipelineBindPoint::Graphics, layout.clone(), 0, set);
 
         character_layout.iter().for_each(|(texture_coordinates, position, color)| {
@@ -146,7 +152,8 @@ impl TextRenderer {
             let constants = Constants {
                 screen_position: screen_position.into(),
                 screen_size: screen_size.into(),
-                clip_size: clip_size.into(),
+                clip_bounds: clip_size.rounded_bounds.into(),
+                clip_corner_radius: clip_size.corner_radius.into(),
                 texture_position: [texture_position.x, texture_position.y],
                 texture_size: [texture_size.x, texture_size.y],
                 color: [color.red_f32(), color.green_f32(), color.blue_f32(), color.alpha_f32()],
@@ -1,6 +1,7 @@
 mod deferred;
 mod interface;
 mod picker;
+mod preview;
 #[cfg(feature = "debug")]
 mod settings;
 mod shadow;
@@ -10,21 +11,22 @@ use std::sync::Arc;
 use cgmath::{Matrix4, Vector2, Vector3};
 use vulkano::buffer::{BufferUsage, CpuAccessibleBuffer};
 use vulkano::command_buffer::{
-    AutoCommandBufferBuilder, ClearAttachment, ClearRect, CommandBufferUsage, CopyImageToBufferInfo, PrimaryAutoCommandBuffer,
-    PrimaryCommandBufferAbstract, RenderPassBeginInfo, SubpassContents,
+    AutoCommandBufferBuilder, BlitImageInfo, ClearAttachment, ClearRect, CommandBufferUsage, CopyImageToBufferInfo, ImageResolve,
+    PrimaryAutoCommandBuffer, PrimaryCommandBufferAbstract, RenderPassBeginInfo, ResolveImageInfo, SubpassContents,
 };
-use vulkano::device::physical::PhysicalDevice;
-use vulkano::device::{Device, Queue};
+use vulkano::device::{Device, DeviceOwned, Queue};
 use vulkano::format::{ClearColorValue, ClearValue, Format};
 use vulkano::image::view::ImageView;
-use vulkano::image::{AttachmentImage, ImageUsage, SampleCount, SwapchainImage};
+use vulkano::image::{AttachmentImage, ImageAccess, ImageUsage, SampleCount, SwapchainImage};
 use vulkano::pipeline::graphics::color_blend::{AttachmentBlend, BlendFactor, BlendOp};
 use vulkano::pipeline::graphics::viewport::Viewport;
 use vulkano::render_pass::{Framebuffer, FramebufferCreateInfo, RenderPass};
+use vulkano::sampler::Filter;
 use vulkano::swapchain::{
-    acquire_next_image, AcquireError, ColorSpace, PresentMode, Surface, SurfaceInfo, Swapchain, SwapchainCreateInfo, SwapchainPresentInfo,
+    acquire_next_image, AcquireError, ColorSpace, PresentMode, Surface, SurfaceInfo, Swapchain, SwapchainCreateInfo, SwapchainCreationError,
+    SwapchainPresentInfo,
 };
-use vulkano::sync::{FenceSignalFuture, GpuFuture, SemaphoreSignalFuture};
+use vulkano::sync::{now, FenceSignalFuture, GpuFuture, SemaphoreSignalFuture};
 use winit::window::Window;
 
 pub use self::deferred::DeferredRenderer;
@@ -32,11 +34,14 @@ use self::deferred::DeferredSubrenderer;
 pub use self::interface::InterfaceRenderer;
 pub use self::picker::PickerRenderer;
 use self::picker::PickerSubrenderer;
+pub use self::preview::PreviewRenderer;
+use self::preview::PreviewSubrenderer;
 #[cfg(feature = "debug")]
 pub use self::settings::RenderSettings;
 pub use self::shadow::ShadowRenderer;
 use super::MemoryAllocator;
 use crate::graphics::{Camera, ImageBuffer, ModelVertexBuffer, Texture};
+use crate::interface::{Remote, TrackedState};
 use crate::network::EntityId;
 #[cfg(feature = "debug")]
 use crate::world::MarkerIdentifier;
@@ -59,6 +64,16 @@ pub const WATER_ATTACHMENT_BLEND: AttachmentBlend = AttachmentBlend {
     alpha_destination: BlendFactor::One,
 };
 
+/// Darkens the destination by the alpha of the source and keeps its alpha.
+pub const SHADOW_ATTACHMENT_BLEND: AttachmentBlend = AttachmentBlend {
+    color_op: BlendOp::Add,
+    color_source: BlendFactor::Zero,
+    color_destination: BlendFactor::OneMinusSrcAlpha,
+    alpha_op: BlendOp::Add,
+    alpha_source: BlendFactor::Zero,
+    alpha_destination: BlendFactor::One,
+};
+
 pub const INTERFACE_ATTACHMENT_BLEND: AttachmentBlend = AttachmentBlend {
     color_op: BlendOp::Add,
     color_source: BlendFactor::SrcAlpha,
@@ -80,6 +95,19 @@ pub trait GeometryRenderer {
         vertex_buffer: ModelVertexBuffer,
         textures: &[Texture],
         world_matrix: Matrix4<f32>,
+        opacity: f32,
+        time: f32,
+    ) where
+        Self: Renderer;
+}
+
+pub trait GroundRenderer {
+    fn render_ground(
+        &self,
+        render_target: &mut <Self as Renderer>::Target,
+        camera: &dyn Camera,
+        vertex_buffer: ModelVertexBuffer,
+        texture_array: Texture,
         time: f32,
     ) where
         Self: Renderer;
@@ -91,6 +119,7 @@ pub trait EntityRenderer {
         render_target: &mut <Self as Renderer>::Target,
         camera: &dyn Camera,
         texture: Texture,
+        palette: Option<Texture>,
         position: Vector3<f32>,
         origin: Vector3<f32>,
         scale: Vector2<f32>,
@@ -238,7 +267,9 @@ impl RenderTargetState {
     }
 
     pub fn try_take_fence(&mut self) -> Option<FenceSignalFuture<Box<dyn GpuFuture>>> {
-        if let RenderTargetState::Ready = self {
+        // Presenting to an out of date swapchain doesn't leave anything to wait for.
+        if let RenderTargetState::Ready | RenderTargetState::OutOfDate = self {
+            *self = RenderTargetState::Ready;
             return None;
         }
 
@@ -254,6 +285,9 @@ pub struct DeferredRenderTarget {
     memory_allocator: Arc<MemoryAllocator>,
     queue: Arc<Queue>,
     framebuffer: Arc<Framebuffer>,
+    interface_framebuffer: Arc<Framebuffer>,
+    swapchain_image: Arc<SwapchainImage>,
+    scene_image: ImageBuffer,
     diffuse_image: ImageBuffer,
     normal_image: ImageBuffer,
     water_image: ImageBuffer,
@@ -267,6 +301,7 @@ impl DeferredRenderTarget {
         memory_allocator: Arc<MemoryAllocator>,
         queue: Arc<Queue>,
         render_pass: Arc<RenderPass>,
+        interface_render_pass: Arc<RenderPass>,
         swapchain_image: Arc<SwapchainImage>,
         dimensions: [u32; 2],
     ) -> Self {
@@ -284,6 +319,19 @@ impl DeferredRenderTarget {
             ..ImageUsage::empty()
         };
 
+        let scene_image_usage = ImageUsage {
+            color_attachment: true,
+            transfer_src: true,
+            ..ImageUsage::empty()
+        };
+
+        // The scene is rendered at the (possibly lower) render resolution and
+        // scaled up to the swapchain image before the interface is drawn on top.
+        let scene_image = ImageView::new_default(Arc::new(
+            AttachmentImage::with_usage(&*memory_allocator, dimensions, swapchain_image.format(), scene_image_usage).unwrap(),
+        ))
+        .unwrap();
+
         let diffuse_image = ImageView::new_default(Arc::new(
             AttachmentImage::multisampled_with_usage(
                 &*memory_allocator,
@@ -334,7 +382,7 @@ impl DeferredRenderTarget {
 
         let framebuffer_create_info = FramebufferCreateInfo {
             attachments: vec![
-                ImageView::new_default(swapchain_image).unwrap(),
+                scene_image.clone(),
                 diffuse_image.clone(),
                 normal_image.clone(),
                 water_image.clone(),
@@ -344,6 +392,13 @@ impl DeferredRenderTarget {
         };
 
         let framebuffer = Framebuffer::new(render_pass, framebuffer_create_info).unwrap();
+
+        let interface_framebuffer_create_info = FramebufferCreateInfo {
+            attachments: vec![ImageView::new_default(swapchain_image.clone()).unwrap()],
+            ..Default::default()
+        };
+
+        let interface_framebuffer = Framebuffer::new(interface_render_pass, interface_framebuffer_create_info).unwrap();
         let state = RenderTargetState::Ready;
         let bound_subrenderer = None;
 
@@ -351,6 +406,9 @@ impl DeferredRenderTarget {
             memory_allocator,
             queue,
             framebuffer,
+            interface_framebuffer,
+            swapchain_image,
+            scene_image,
             diffuse_image,
             normal_image,
             water_image,
@@ -398,6 +456,28 @@ impl DeferredRenderTarget {
         self.state.get_builder().next_subpass(SubpassContents::Inline).unwrap();
     }
 
+    pub fn interface_pass(&mut self) {
+        let builder = self.state.get_builder();
+
+        builder.end_render_pass().unwrap();
+
+        let blit_image_info = BlitImageInfo {
+            filter: Filter::Linear,
+            ..BlitImageInfo::images(self.scene_image.image().clone(), self.swapchain_image.clone())
+        };
+
+        builder.blit_image(blit_image_info).unwrap();
+
+        let render_pass_begin_info = RenderPassBeginInfo {
+            clear_values: vec![None],
+            ..RenderPassBeginInfo::framebuffer(self.interface_framebuffer.clone())
+        };
+
+        builder.begin_render_pass(render_pass_begin_info, SubpassContents::Inline).unwrap();
+
+        self.bound_subrenderer = None;
+    }
+
     pub fn finish(&mut self, swapchain: Arc<Swapchain>, semaphore: Box<dyn GpuFuture>, image_number: usize) {
         let mut builder = self.state.take_builder();
 
@@ -540,10 +620,151 @@ impl PickerRenderTarget {
     }
 }
 
+pub struct PreviewRenderTarget {
+    memory_allocator: Arc<MemoryAllocator>,
+    queue: Arc<Queue>,
+    render_pass: Arc<RenderPass>,
+    dimensions: [u32; 2],
+    /// One framebuffer and image per swapchain image, so the preview of the
+    /// current frame never writes to an image that a previous frame still
+    /// reads.
+    frames: Vec<(Arc<Framebuffer>, ImageBuffer)>,
+    current_frame: usize,
+    image: TrackedState<ImageBuffer>,
+    pub state: RenderTargetState,
+    bound_subrenderer: Option<PreviewSubrenderer>,
+}
+
+impl PreviewRenderTarget {
+    pub fn new(memory_allocator: Arc<MemoryAllocator>, queue: Arc<Queue>, render_pass: Arc<RenderPass>, dimensions: [u32; 2]) -> Self {
+        let first_frame = Self::create_frame(&memory_allocator, render_pass.clone(), dimensions);
+        let image = TrackedState::new(first_frame.1.clone());
+        let state = RenderTargetState::Ready;
+        let bound_subrenderer = None;
+
+        Self {
+            memory_allocator,
+            queue,
+            render_pass,
+            dimensions,
+            frames: vec![first_frame],
+            current_frame: 0,
+            image,
+            state,
+            bound_subrenderer,
+        }
+    }
+
+    fn create_frame(
+        memory_allocator: &MemoryAllocator,
+        render_pass: Arc<RenderPass>,
+        dimensions: [u32; 2],
+    ) -> (Arc<Framebuffer>, ImageBuffer) {
+        let image_usage = ImageUsage {
+            sampled: true,
+            color_attachment: true,
+            ..ImageUsage::empty()
+        };
+
+        let depth_image_usage = ImageUsage {
+            depth_stencil_attachment: true,
+            ..ImageUsage::empty()
+        };
+
+        let image = ImageView::new_default(Arc::new(
+            AttachmentImage::with_usage(memory_allocator, dimensions, Format::R8G8B8A8_SRGB, image_usage).unwrap(),
+        ))
+        .unwrap();
+
+        let normal_buffer =
+            ImageView::new_default(AttachmentImage::transient(memory_allocator, dimensions, Format::R16G16B16A16_SFLOAT).unwrap()).unwrap();
+
+        let depth_buffer = ImageView::new_default(Arc::new(
+            AttachmentImage::with_usage(memory_allocator, dimensions, Format::D16_UNORM, depth_image_usage).unwrap(),
+        ))
+        .unwrap();
+
+        let framebuffer_create_info = FramebufferCreateInfo {
+            attachments: vec![image.clone(), normal_buffer, depth_buffer],
+            ..Default::default()
+        };
+
+        let framebuffer = Framebuffer::new(render_pass, framebuffer_create_info).unwrap();
+
+        (framebuffer, image)
+    }
+
+    pub fn get_size(&self) -> Vector2<usize> {
+        Vector2::new(self.dimensions[0] as usize, self.dimensions[1] as usize)
+    }
+
+    /// The image that the preview was last rendered to.
+    pub fn get_image(&self) -> Remote<ImageBuffer> {
+        self.image.new_remote()
+    }
+
+    pub fn start(&mut self, image_number: usize) {
+        while self.frames.len() <= image_number {
+            let frame = Self::create_frame(&self.memory_allocator, self.render_pass.clone(), self.dimensions);
+            self.frames.push(frame);
+        }
+
+        self.current_frame = image_number;
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &*self.memory_allocator,
+            self.queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        let render_pass_begin_info = RenderPassBeginInfo {
+            clear_values: vec![
+                Some(ClearValue::Float([0.0; 4])),
+                Some(ClearValue::Float([0.0; 4])),
+                Some(ClearValue::Depth(1.0)),
+            ],
+            ..RenderPassBeginInfo::framebuffer(self.frames[image_number].0.clone())
+        };
+
+        builder.begin_render_pass(render_pass_begin_info, SubpassContents::Inline).unwrap();
+
+        self.state = RenderTargetState::Rendering(builder);
+    }
+
+    pub fn bind_subrenderer(&mut self, subrenderer: PreviewSubrenderer) -> bool {
+        let already_bound = self.bound_subrenderer.contains(&subrenderer);
+        self.bound_subrenderer = Some(subrenderer);
+        !already_bound
+    }
+
+    pub fn finish(&mut self) {
+        let mut builder = self.state.take_builder();
+
+        builder.end_render_pass().unwrap();
+
+        let command_buffer = builder.build().unwrap();
+        let fence = command_buffer
+            .execute(self.queue.clone())
+            .unwrap()
+            .boxed()
+            .then_signal_fence_and_flush()
+            .unwrap();
+
+        self.state = RenderTargetState::Fence(fence);
+        self.image.set(self.frames[self.current_frame].1.clone());
+        self.bound_subrenderer = None;
+    }
+}
+
 pub struct SingleRenderTarget<const F: Format, S: PartialEq, C> {
     memory_allocator: Arc<MemoryAllocator>,
     queue: Arc<Queue>,
     framebuffer: Arc<Framebuffer>,
+    /// Framebuffer of a render pass that loads the contents of the image, used
+    /// to continue rendering after leaving the render pass in the middle of a
+    /// frame.
+    resume_framebuffer: Option<Arc<Framebuffer>>,
     pub image: ImageBuffer,
     pub state: RenderTargetState,
     clear_value: C,
@@ -579,6 +800,7 @@ impl<const F: Format, S: PartialEq, C> SingleRenderTarget<F, S, C> {
             memory_allocator,
             queue,
             framebuffer,
+            resume_framebuffer: None,
             image,
             state,
             clear_value,
@@ -586,6 +808,18 @@ impl<const F: Format, S: PartialEq, C> SingleRenderTarget<F, S, C> {
         }
     }
 
+    /// The render pass has to be compatible with the one the target was
+    /// created with.
+    pub fn with_resume_render_pass(mut self, render_pass: Arc<RenderPass>) -> Self {
+        let framebuffer_create_info = FramebufferCreateInfo {
+            attachments: vec![self.image.clone()],
+            ..Default::default()
+        };
+
+        self.resume_framebuffer = Some(Framebuffer::new(render_pass, framebuffer_create_info).unwrap());
+        self
+    }
+
     pub fn bind_subrenderer(&mut self, subrenderer: S) -> bool {
         let already_bound = self.bound_subrenderer.contains(&subrenderer);
         self.bound_subrenderer = Some(subrenderer);
@@ -674,7 +908,46 @@ impl<const F: Format, S: PartialEq> SingleRenderTarget<F, S, ClearColorValue> {
         self.state = RenderTargetState::Rendering(builder);
     }
 
-    pub fn finish(&mut self, font_future: Option<FenceSignalFuture<Box<dyn GpuFuture>>>) {
+    /// Copies part of the target into a single sampled image, so it can be
+    /// drawn again later without rendering its contents. Copies can't happen
+    /// inside of a render pass, so a render pass that loads the contents of the
+    /// target is started afterwards.
+    pub fn copy_region(&mut self, offset: [u32; 2], image: ImageBuffer) {
+        let [width, height] = image.image().dimensions().width_height();
+        let builder = self.state.get_builder();
+
+        builder.end_render_pass().unwrap();
+
+        let resolve_image_info = ResolveImageInfo {
+            regions: [ImageResolve {
+                src_subresource: self.image.image().subresource_layers(),
+                src_offset: [offset[0], offset[1], 0],
+                dst_subresource: image.image().subresource_layers(),
+                extent: [width, height, 1],
+                ..Default::default()
+            }]
+            .into(),
+            ..ResolveImageInfo::images(self.image.image().clone(), image.image().clone())
+        };
+
+        builder.resolve_image(resolve_image_info).unwrap();
+
+        let framebuffer = self
+            .resume_framebuffer
+            .clone()
+            .expect("render target has no render pass to resume rendering");
+        let render_pass_begin_info = RenderPassBeginInfo {
+            clear_values: vec![None],
+            ..RenderPassBeginInfo::framebuffer(framebuffer)
+        };
+
+        builder.begin_render_pass(render_pass_begin_info, SubpassContents::Inline).unwrap();
+        self.bound_subrenderer = None;
+    }
+
+    /// The wait future is used for images that the interface samples but
+    /// that are rendered on the GPU, such as entity previews.
+    pub fn finish(&mut self, font_future: Option<FenceSignalFuture<Box<dyn GpuFuture>>>, wait_future: Option<Box<dyn GpuFuture>>) {
         if let Some(mut future) = font_future {
             future.wait(None).unwrap();
             future.cleanup_finished();
@@ -684,8 +957,9 @@ impl<const F: Format, S: PartialEq> SingleRenderTarget<F, S, ClearColorValue> {
         builder.end_render_pass().unwrap();
 
         let command_buffer = builder.build().unwrap();
-        let semaphore = command_buffer
-            .execute(self.queue.clone())
+        let semaphore = wait_future
+            .unwrap_or_else(|| now(self.queue.device().clone()).boxed())
+            .then_execute(self.queue.clone(), command_buffer)
             .unwrap()
             .boxed()
             .then_signal_semaphore_and_flush()
@@ -703,37 +977,53 @@ pub struct SwapchainHolder {
     window_size: [u32; 2],
     image_number: usize,
     recreate: bool,
+    /// The surface can be lost when the driver is updated or the GPU is reset,
+    /// after which a new surface has to be created for the window.
+    surface_lost: bool,
     acquire_future: Option<Box<dyn GpuFuture>>,
 }
 
+fn create_swapchain(
+    device: Arc<Device>,
+    surface: Arc<Surface>,
+    present_mode: PresentMode,
+    window_size: [u32; 2],
+) -> (Arc<Swapchain>, Vec<Arc<SwapchainImage>>) {
+    let physical_device = device.physical_device();
+    let capabilities = physical_device
+        .surface_capabilities(&surface, SurfaceInfo::default())
+        .expect("failed to get surface capabilities");
+    let composite_alpha = capabilities.supported_composite_alpha.iter().next().unwrap();
+    let image_format = physical_device.surface_formats(&surface, SurfaceInfo::default()).unwrap()[0].0;
+
+    let swapchain_create_info = SwapchainCreateInfo {
+        min_image_count: capabilities.min_image_count,
+        image_format: Some(image_format),
+        image_extent: window_size,
+        image_usage: ImageUsage {
+            color_attachment: true,
+            transfer_dst: true,
+            ..Default::default()
+        },
+        composite_alpha,
+        image_color_space: ColorSpace::SrgbNonLinear, // Is this really needed?
+        present_mode,
+        ..Default::default()
+    };
+
+    Swapchain::new(device, surface, swapchain_create_info).expect("failed to create swapchain")
+}
+
 impl SwapchainHolder {
-    pub fn new(physical_device: &PhysicalDevice, device: Arc<Device>, _queue: Arc<Queue>, surface: Arc<Surface>) -> Self {
+    pub fn new(device: Arc<Device>, _queue: Arc<Queue>, surface: Arc<Surface>) -> Self {
         let window_size: [u32; 2] = surface.object().unwrap().downcast_ref::<Window>().unwrap().inner_size().into();
-        let capabilities = physical_device
-            .surface_capabilities(&surface, SurfaceInfo::default())
-            .expect("failed to get surface capabilities");
-        let composite_alpha = capabilities.supported_composite_alpha.iter().next().unwrap();
-        let image_format = physical_device.surface_formats(&surface, SurfaceInfo::default()).unwrap()[0].0;
         let present_mode = PresentMode::Fifo;
         let image_number = 0;
         let recreate = false;
+        let surface_lost = false;
         let acquire_future = None;
 
-        let swapchain_create_info = SwapchainCreateInfo {
-            min_image_count: capabilities.min_image_count,
-            image_format: Some(image_format),
-            image_extent: window_size,
-            image_usage: ImageUsage {
-                color_attachment: true,
-                ..Default::default()
-            },
-            composite_alpha,
-            image_color_space: ColorSpace::SrgbNonLinear, // Is this really needed?
-            present_mode,
-            ..Default::default()
-        };
-
-        let (swapchain, swapchain_images) = Swapchain::new(device, surface, swapchain_create_info).expect("failed to create swapchain");
+        let (swapchain, swapchain_images) = create_swapchain(device, surface, present_mode, window_size);
 
         Self {
             swapchain,
@@ -742,6 +1032,7 @@ impl SwapchainHolder {
             window_size,
             image_number,
             recreate,
+            surface_lost,
             acquire_future,
         }
     }
@@ -749,10 +1040,14 @@ impl SwapchainHolder {
     pub fn acquire_next_image(&mut self) -> Result<(), ()> {
         let (image_number, suboptimal, acquire_future) = match acquire_next_image(self.swapchain.clone(), None) {
             Ok(r) => r,
-            Err(AcquireError::OutOfDate) => {
+            Err(AcquireError::OutOfDate | AcquireError::FullScreenExclusiveModeLost) => {
                 self.recreate = true;
                 return Err(());
             }
+            Err(AcquireError::SurfaceLost) => {
+                self.surface_lost = true;
+                return Err(());
+            }
             Err(e) => panic!("Failed to acquire next image: {:?}", e),
         };
 
@@ -774,7 +1069,31 @@ impl SwapchainHolder {
         self.recreate
     }
 
-    pub fn recreate_swapchain(&mut self) -> Viewport {
+    pub fn is_surface_lost(&self) -> bool {
+        self.surface_lost
+    }
+
+    /// The window has no area to render to, for example while it is minimized.
+    pub fn is_minimized(&self) -> bool {
+        self.window_size.contains(&0)
+    }
+
+    /// Create a new swapchain for a surface that replaces the lost one. The
+    /// swapchain images change, so everything depending on them has to be
+    /// recreated afterwards.
+    pub fn recreate_surface(&mut self, surface: Arc<Surface>) {
+        let (swapchain, swapchain_images) = create_swapchain(self.swapchain.device().clone(), surface, self.present_mode, self.window_size);
+
+        self.swapchain = swapchain;
+        self.swapchain_images = swapchain_images;
+        self.surface_lost = false;
+        self.recreate = true;
+    }
+
+    /// Returns `None` if the swapchain can't be recreated for the current
+    /// window size yet. It stays invalid in that case and is recreated on a
+    /// later frame.
+    pub fn recreate_swapchain(&mut self) -> Option<Viewport> {
         let swapchain_create_info = SwapchainCreateInfo {
             image_extent: self.window_size,
             present_mode: self.present_mode,
@@ -785,14 +1104,22 @@ impl SwapchainHolder {
 
         let (swapchain, swapchain_images) = match swapchain_result {
             Ok(r) => r,
-            //Err(SwapchainCreationError::UnsupportedDimensions) => return,
+            // The window size can change between the resize event and recreating the
+            // swapchain.
+            Err(SwapchainCreationError::ImageExtentNotSupported { .. } | SwapchainCreationError::ImageExtentZeroLengthDimensions) => {
+                return None;
+            }
+            Err(SwapchainCreationError::SurfaceLost) => {
+                self.surface_lost = true;
+                return None;
+            }
             Err(e) => panic!("Failed to recreate swapchain: {:?}", e),
         };
 
         self.swapchain = swapchain;
         self.swapchain_images = swapchain_images;
         self.recreate = false;
-        self.viewport()
+        self.viewport().into()
     }
 
     pub fn get_swapchain(&self) -> Arc<Swapchain> {
@@ -129,6 +129,7 @@ impl GeometryRendererTrait for PickerRenderer {
         vertex_buffer: ModelVertexBuffer,
         textures: &[Texture],
         world_matrix: Matrix4<f32>,
+        _opacity: f32,
         _time: f32,
     ) where
         Self: Renderer,
@@ -148,6 +149,7 @@ impl EntityRendererTrait for PickerRenderer {
         render_target: &mut <Self as Renderer>::Target,
         camera: &dyn Camera,
         texture: Texture,
+        _palette: Option<Texture>,
         position: Vector3<f32>,
         origin: Vector3<f32>,
         scale: Vector2<f32>,
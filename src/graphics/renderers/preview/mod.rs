@@ -0,0 +1,128 @@
+use std::sync::Arc;
+
+use cgmath::{Vector2, Vector3};
+use vulkano::device::{DeviceOwned, Queue};
+use vulkano::format::Format;
+use vulkano::pipeline::graphics::viewport::Viewport;
+use vulkano::render_pass::RenderPass;
+
+use super::deferred::EntityRenderer;
+use crate::graphics::{EntityRenderer as EntityRendererTrait, *};
+use crate::network::EntityId;
+
+/// Size of the preview image in pixels. The preview is scaled to the size of
+/// the element it is shown in.
+const PREVIEW_SIZE: [u32; 2] = [160, 200];
+
+#[derive(PartialEq, Eq)]
+pub enum PreviewSubrenderer {
+    Entity,
+}
+
+/// Renders entities without any lighting into an image that can be shown
+/// inside of the interface. Uses the same entity pipeline as the
+/// [`DeferredRenderer`], so the normals are written to a transient attachment.
+pub struct PreviewRenderer {
+    memory_allocator: Arc<MemoryAllocator>,
+    queue: Arc<Queue>,
+    render_pass: Arc<RenderPass>,
+    entity_renderer: EntityRenderer,
+}
+
+impl PreviewRenderer {
+    pub fn new(memory_allocator: Arc<MemoryAllocator>, queue: Arc<Queue>) -> Self {
+        let device = memory_allocator.device().clone();
+        let render_pass = vulkano::single_pass_renderpass!(
+            device,
+            attachments: {
+                color: {
+                    load: Clear,
+                    store: Store,
+                    format: Format::R8G8B8A8_SRGB,
+                    samples: 1,
+                },
+                normal: {
+                    load: Clear,
+                    store: DontCare,
+                    format: Format::R16G16B16A16_SFLOAT,
+                    samples: 1,
+                },
+                depth: {
+                    load: Clear,
+                    store: DontCare,
+                    format: Format::D16_UNORM,
+                    samples: 1,
+                }
+            },
+            pass: {
+                color: [color, normal],
+                depth_stencil: {depth}
+            }
+        )
+        .unwrap();
+
+        let subpass = render_pass.clone().first_subpass();
+        let viewport = Viewport {
+            origin: [0.0, 0.0],
+            dimensions: PREVIEW_SIZE.map(|component| component as f32),
+            depth_range: 0.0..1.0,
+        };
+        let entity_renderer = EntityRenderer::new(memory_allocator.clone(), subpass, viewport);
+
+        Self {
+            memory_allocator,
+            queue,
+            render_pass,
+            entity_renderer,
+        }
+    }
+
+    pub fn create_render_target(&self) -> <Self as Renderer>::Target {
+        <Self as Renderer>::Target::new(
+            self.memory_allocator.clone(),
+            self.queue.clone(),
+            self.render_pass.clone(),
+            PREVIEW_SIZE,
+        )
+    }
+}
+
+impl Renderer for PreviewRenderer {
+    type Target = PreviewRenderTarget;
+}
+
+impl EntityRendererTrait for PreviewRenderer {
+    fn render_entity(
+        &self,
+        render_target: &mut <Self as Renderer>::Target,
+        camera: &dyn Camera,
+        texture: Texture,
+        palette: Option<Texture>,
+        position: Vector3<f32>,
+        origin: Vector3<f32>,
+        scale: Vector2<f32>,
+        cell_count: Vector2<usize>,
+        cell_position: Vector2<usize>,
+        mirror: bool,
+        _entity_id: EntityId,
+    ) where
+        Self: Renderer,
+    {
+        if render_target.bind_subrenderer(PreviewSubrenderer::Entity) {
+            self.entity_renderer.bind_pipeline(render_target.state.get_builder(), camera);
+        }
+
+        self.entity_renderer.render(
+            render_target.state.get_builder(),
+            camera,
+            texture,
+            palette,
+            position,
+            origin,
+            scale,
+            cell_count,
+            cell_position,
+            mirror,
+        );
+    }
+}
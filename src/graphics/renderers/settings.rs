@@ -17,6 +17,9 @@ pub struct RenderSettings {
     pub show_bounding_boxes: bool,
     #[toggle]
     #[new(value = "true")]
+    pub cache_windows: bool,
+    #[toggle]
+    #[new(value = "true")]
     pub show_map: bool,
     #[toggle]
     #[new(value = "true")]
@@ -0,0 +1,15 @@
+#version 450
+
+layout(location = 0) in vec2 texture_coordinates;
+layout(location = 1) flat in int texture_index;
+
+layout (set = 1, binding = 0) uniform sampler2DArray textures;
+
+void main() {
+
+    vec4 diffuse_color = texture(textures, vec3(texture_coordinates, texture_index));
+
+    if (diffuse_color.a != 1.0) {
+        discard;
+    }
+}
@@ -1,5 +1,6 @@
 mod entity;
 mod geometry;
+mod ground;
 
 use std::sync::Arc;
 
@@ -11,12 +12,16 @@ use vulkano::render_pass::RenderPass;
 
 use self::entity::EntityRenderer;
 use self::geometry::GeometryRenderer;
-use crate::graphics::{EntityRenderer as EntityRendererTrait, GeometryRenderer as GeometryRendererTrait, *};
+use self::ground::GroundRenderer;
+use crate::graphics::{
+    EntityRenderer as EntityRendererTrait, GeometryRenderer as GeometryRendererTrait, GroundRenderer as GroundRendererTrait, *,
+};
 use crate::network::EntityId;
 
 #[derive(PartialEq, Eq)]
 pub enum ShadowSubrenderer {
     Geometry,
+    Ground,
     Entity,
 }
 
@@ -25,6 +30,7 @@ pub struct ShadowRenderer {
     queue: Arc<Queue>,
     render_pass: Arc<RenderPass>,
     geometry_renderer: GeometryRenderer,
+    ground_renderer: GroundRenderer,
     entity_renderer: EntityRenderer,
 }
 
@@ -50,6 +56,7 @@ impl ShadowRenderer {
 
         let subpass = render_pass.clone().first_subpass();
         let geometry_renderer = GeometryRenderer::new(memory_allocator.clone(), subpass.clone());
+        let ground_renderer = GroundRenderer::new(memory_allocator.clone(), subpass.clone());
         let entity_renderer = EntityRenderer::new(memory_allocator.clone(), subpass);
 
         Self {
@@ -57,6 +64,7 @@ impl ShadowRenderer {
             queue,
             render_pass,
             geometry_renderer,
+            ground_renderer,
             entity_renderer,
         }
     }
@@ -92,6 +100,7 @@ impl GeometryRendererTrait for ShadowRenderer {
         vertex_buffer: ModelVertexBuffer,
         textures: &[Texture],
         world_matrix: Matrix4<f32>,
+        _opacity: f32,
         time: f32,
     ) where
         Self: Renderer,
@@ -105,12 +114,32 @@ impl GeometryRendererTrait for ShadowRenderer {
     }
 }
 
+impl GroundRendererTrait for ShadowRenderer {
+    fn render_ground(
+        &self,
+        render_target: &mut <Self as Renderer>::Target,
+        camera: &dyn Camera,
+        vertex_buffer: ModelVertexBuffer,
+        texture_array: Texture,
+        time: f32,
+    ) where
+        Self: Renderer,
+    {
+        if render_target.bind_subrenderer(ShadowSubrenderer::Ground) {
+            self.ground_renderer.bind_pipeline(render_target, camera, time);
+        }
+
+        self.ground_renderer.render(render_target, camera, vertex_buffer, texture_array);
+    }
+}
+
 impl EntityRendererTrait for ShadowRenderer {
     fn render_entity(
         &self,
         render_target: &mut <Self as Renderer>::Target,
         camera: &dyn Camera,
         texture: Texture,
+        _palette: Option<Texture>,
         position: Vector3<f32>,
         origin: Vector3<f32>,
         scale: Vector2<f32>,
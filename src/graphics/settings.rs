@@ -1,6 +1,53 @@
 use derive_new::new;
 use procedural::toggle;
 
+/// How entities cast shadows on the ground.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EntityShadowMode {
+    None,
+    /// A soft circle below every entity.
+    Blob,
+    /// The silhouette of the sprite, rendered into the shadow map of the sun.
+    Sprite,
+}
+
+/// Overall amount of purely visual effects.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EffectQuality {
+    Low,
+    Medium,
+    High,
+    Ultra,
+}
+
+impl EffectQuality {
+    pub fn budget(self) -> EffectBudget {
+        let (maximum_particles, cosmetic_particles) = match self {
+            EffectQuality::Low => (32, false),
+            EffectQuality::Medium => (64, true),
+            EffectQuality::High => (128, true),
+            EffectQuality::Ultra => (512, true),
+        };
+
+        EffectBudget {
+            maximum_particles,
+            cosmetic_particles,
+        }
+    }
+}
+
+/// Limits that the particle system has to stay within, derived from the
+/// effect quality.
+#[derive(Clone, Copy, Debug)]
+pub struct EffectBudget {
+    /// Most particles alive at the same time. Spawning more removes the
+    /// oldest ones.
+    pub maximum_particles: usize,
+    /// Whether particles that don't carry any information, like teleport
+    /// pillars, are spawned at all.
+    pub cosmetic_particles: bool,
+}
+
 #[derive(toggle, new)]
 pub struct GraphicsSettings {
     #[toggle]
@@ -9,4 +56,19 @@ pub struct GraphicsSettings {
     #[toggle]
     #[new(value = "true")]
     pub show_interface: bool,
+    #[new(value = "1.0")]
+    pub render_scale: f32,
+    #[toggle]
+    #[new(value = "true")]
+    pub show_entity_info: bool,
+    /// Opacity of objects that hide the player. At 1.0 objects never fade.
+    #[new(value = "1.0")]
+    pub object_fade_opacity: f32,
+    #[toggle]
+    #[new(value = "false")]
+    pub hide_roofs: bool,
+    #[new(value = "EntityShadowMode::Blob")]
+    pub entity_shadows: EntityShadowMode,
+    #[new(value = "EffectQuality::High")]
+    pub effect_quality: EffectQuality,
 }
@@ -35,6 +35,20 @@ impl SmoothedValue {
         self.desired = (self.desired + offset).clamp(minimum, maximum);
     }
 
+    /// Jump to a value without smoothing.
+    pub fn set(&mut self, value: f32) {
+        self.current = value;
+        self.desired = value;
+    }
+
+    pub fn set_desired(&mut self, value: f32) {
+        self.desired = value;
+    }
+
+    pub fn get_desired(&self) -> f32 {
+        self.desired
+    }
+
     pub fn get_current(&self) -> f32 {
         self.current
     }
@@ -3,6 +3,8 @@ use derive_new::new;
 
 use crate::graphics::ModelVertex;
 
+const DEGENERATE_THRESHOLD: f32 = 1e-8;
+
 #[derive(new)]
 pub struct NativeModelVertex {
     pub position: Vector3<f32>,
@@ -43,6 +45,29 @@ impl NativeModelVertex {
         vertices
     }
 
+    /// Removes triangles without any area. They can't be seen but still cost
+    /// vertex throughput, and the ground produces a lot of them for walls
+    /// between tiles of the same height.
+    pub fn remove_degenerate_triangles(native_vertices: Vec<NativeModelVertex>) -> Vec<NativeModelVertex> {
+        let mut retained_vertices = Vec::with_capacity(native_vertices.len());
+        let mut vertex_iterator = native_vertices.into_iter();
+
+        while let Some(first_partial) = vertex_iterator.next() {
+            let second_partial = vertex_iterator.next().unwrap();
+            let third_partial = vertex_iterator.next().unwrap();
+
+            if !Self::is_degenerate(first_partial.position, second_partial.position, third_partial.position) {
+                retained_vertices.extend([first_partial, second_partial, third_partial]);
+            }
+        }
+
+        retained_vertices
+    }
+
+    pub fn is_degenerate(first_position: Vector3<f32>, second_position: Vector3<f32>, third_position: Vector3<f32>) -> bool {
+        Self::calculate_normal(first_position, second_position, third_position).magnitude2() < DEGENERATE_THRESHOLD
+    }
+
     pub fn calculate_normal(first_position: Vector3<f32>, second_position: Vector3<f32>, third_position: Vector3<f32>) -> Vector3<f32> {
         let delta_position_1 = second_position - first_position;
         let delta_position_2 = third_position - first_position;
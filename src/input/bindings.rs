@@ -0,0 +1,227 @@
+use std::collections::HashMap;
+
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+use winit::event::VirtualKeyCode;
+
+use super::UserEvent;
+#[cfg(feature = "debug")]
+use crate::debug::*;
+use crate::system::profile_file;
+
+/// Everything that can be triggered with a configurable key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum KeyAction {
+    TargetNextMonster,
+    OpenInventory,
+    OpenEquipment,
+    OpenSkillTree,
+    ToggleShowInterface,
+    ToggleCameraMode,
+    RecallCameraPreset(usize),
+    SaveCameraPreset(usize),
+    HotbarSlot(usize),
+}
+
+impl KeyAction {
+    /// All actions in the order they are shown in the controls window.
+    pub const ALL: [KeyAction; 23] = [
+        KeyAction::TargetNextMonster,
+        KeyAction::OpenInventory,
+        KeyAction::OpenEquipment,
+        KeyAction::OpenSkillTree,
+        KeyAction::ToggleShowInterface,
+        KeyAction::ToggleCameraMode,
+        KeyAction::RecallCameraPreset(0),
+        KeyAction::RecallCameraPreset(1),
+        KeyAction::RecallCameraPreset(2),
+        KeyAction::RecallCameraPreset(3),
+        KeyAction::SaveCameraPreset(0),
+        KeyAction::SaveCameraPreset(1),
+        KeyAction::SaveCameraPreset(2),
+        KeyAction::SaveCameraPreset(3),
+        KeyAction::HotbarSlot(0),
+        KeyAction::HotbarSlot(1),
+        KeyAction::HotbarSlot(2),
+        KeyAction::HotbarSlot(3),
+        KeyAction::HotbarSlot(4),
+        KeyAction::HotbarSlot(5),
+        KeyAction::HotbarSlot(6),
+        KeyAction::HotbarSlot(7),
+        KeyAction::HotbarSlot(8),
+    ];
+
+    pub fn display_name(&self) -> String {
+        match self {
+            KeyAction::TargetNextMonster => "next target".to_string(),
+            KeyAction::OpenInventory => "inventory".to_string(),
+            KeyAction::OpenEquipment => "equipment".to_string(),
+            KeyAction::OpenSkillTree => "skill tree".to_string(),
+            KeyAction::ToggleShowInterface => "toggle interface".to_string(),
+            KeyAction::ToggleCameraMode => "free camera".to_string(),
+            KeyAction::RecallCameraPreset(slot) => format!("camera preset {}", slot + 1),
+            KeyAction::SaveCameraPreset(slot) => format!("save camera preset {}", slot + 1),
+            KeyAction::HotbarSlot(slot) => format!("hotbar slot {}", slot + 1),
+        }
+    }
+
+    pub fn user_event(&self) -> UserEvent {
+        match *self {
+            KeyAction::TargetNextMonster => UserEvent::TargetNextMonster,
+            KeyAction::OpenInventory => UserEvent::OpenInventoryWindow,
+            KeyAction::OpenEquipment => UserEvent::OpenEquipmentWindow,
+            KeyAction::OpenSkillTree => UserEvent::OpenSkillTreeWindow,
+            KeyAction::ToggleShowInterface => UserEvent::ToggleShowInterface,
+            KeyAction::ToggleCameraMode => UserEvent::ToggleCameraMode,
+            KeyAction::RecallCameraPreset(slot) => UserEvent::RecallCameraPreset(slot),
+            KeyAction::SaveCameraPreset(slot) => UserEvent::SaveCameraPreset(slot),
+            KeyAction::HotbarSlot(slot) => UserEvent::HotbarSlot(slot),
+        }
+    }
+
+    fn default_binding(&self) -> KeyBinding {
+        const PRESET_KEYS: [VirtualKeyCode; 4] = [
+            VirtualKeyCode::Key1,
+            VirtualKeyCode::Key2,
+            VirtualKeyCode::Key3,
+            VirtualKeyCode::Key4,
+        ];
+        const HOTBAR_KEYS: [VirtualKeyCode; 9] = [
+            VirtualKeyCode::F1,
+            VirtualKeyCode::F2,
+            VirtualKeyCode::F3,
+            VirtualKeyCode::F4,
+            VirtualKeyCode::F5,
+            VirtualKeyCode::F6,
+            VirtualKeyCode::F7,
+            VirtualKeyCode::F8,
+            VirtualKeyCode::F9,
+        ];
+
+        match *self {
+            KeyAction::TargetNextMonster => KeyBinding::new(VirtualKeyCode::Tab, false),
+            KeyAction::OpenInventory => KeyBinding::new(VirtualKeyCode::I, false),
+            KeyAction::OpenEquipment => KeyBinding::new(VirtualKeyCode::Q, false),
+            KeyAction::OpenSkillTree => KeyBinding::new(VirtualKeyCode::K, false),
+            KeyAction::ToggleShowInterface => KeyBinding::new(VirtualKeyCode::H, true),
+            KeyAction::ToggleCameraMode => KeyBinding::new(VirtualKeyCode::V, false),
+            KeyAction::RecallCameraPreset(slot) => KeyBinding::new(PRESET_KEYS[slot % PRESET_KEYS.len()], false),
+            KeyAction::SaveCameraPreset(slot) => KeyBinding::new(PRESET_KEYS[slot % PRESET_KEYS.len()], true),
+            KeyAction::HotbarSlot(slot) => KeyBinding::new(HOTBAR_KEYS[slot % HOTBAR_KEYS.len()], false),
+        }
+    }
+}
+
+/// A key together with the state of the shift modifier.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyBinding {
+    pub key: VirtualKeyCode,
+    pub shift: bool,
+}
+
+impl KeyBinding {
+    pub fn new(key: VirtualKeyCode, shift: bool) -> Self {
+        Self { key, shift }
+    }
+}
+
+impl std::fmt::Display for KeyBinding {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.shift {
+            true => write!(formatter, "shift + {:?}", self.key),
+            false => write!(formatter, "{:?}", self.key),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct KeyBindings {
+    /// Actions without a binding lost their key to another action.
+    bindings: HashMap<KeyAction, Option<KeyBinding>>,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        let bindings = KeyAction::ALL
+            .into_iter()
+            .map(|action| (action, Some(action.default_binding())))
+            .collect();
+
+        Self { bindings }
+    }
+}
+
+impl KeyBindings {
+    pub fn new() -> Self {
+        let mut key_bindings = Self::load().unwrap_or_else(|| {
+            #[cfg(feature = "debug")]
+            print_debug!("failed to load key bindings from {}filename{}", MAGENTA, NONE);
+
+            Default::default()
+        });
+
+        // Actions added after the file was saved get their default key, unless another
+        // action already uses it, so that updating never steals a key the player
+        // bound themselves.
+        for action in KeyAction::ALL {
+            if !key_bindings.bindings.contains_key(&action) {
+                key_bindings.bind_default(action);
+            }
+        }
+
+        key_bindings
+    }
+
+    pub fn load() -> Option<Self> {
+        #[cfg(feature = "debug")]
+        print_debug!("loading key bindings from {}filename{}", MAGENTA, NONE);
+
+        std::fs::read_to_string(profile_file("key_bindings.ron"))
+            .ok()
+            .and_then(|data| ron::from_str(&data).ok())
+    }
+
+    pub fn save(&self) {
+        #[cfg(feature = "debug")]
+        print_debug!("saving key bindings to {}filename{}", MAGENTA, NONE);
+
+        let data = ron::ser::to_string_pretty(self, PrettyConfig::new()).unwrap();
+        std::fs::write(profile_file("key_bindings.ron"), data).expect("unable to write file");
+    }
+
+    pub fn get(&self, action: KeyAction) -> Option<KeyBinding> {
+        self.bindings.get(&action).copied().flatten()
+    }
+
+    /// Bind a key to an action. Any other action that used the same key loses
+    /// its binding, so that one key never triggers two actions.
+    pub fn rebind(&mut self, action: KeyAction, binding: KeyBinding) {
+        self.bindings
+            .values_mut()
+            .filter(|other_binding| **other_binding == Some(binding))
+            .for_each(|other_binding| *other_binding = None);
+
+        self.bindings.insert(action, Some(binding));
+    }
+
+    /// Bind the default key to an action if no other action uses it yet.
+    /// Otherwise the action stays unbound.
+    fn bind_default(&mut self, action: KeyAction) {
+        let binding = action.default_binding();
+        let is_used = self.bindings.values().any(|other_binding| *other_binding == Some(binding));
+
+        self.bindings.insert(action, (!is_used).then_some(binding));
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (KeyAction, KeyBinding)> + '_ {
+        self.bindings
+            .iter()
+            .filter_map(|(action, binding)| binding.map(|binding| (*action, binding)))
+    }
+}
+
+impl Drop for KeyBindings {
+    fn drop(&mut self) {
+        self.save();
+    }
+}
@@ -1,7 +1,12 @@
 use cgmath::Vector2;
 
-use crate::interface::ItemMove;
-use crate::network::{CharacterId, EntityId};
+use super::KeyAction;
+use crate::graphics::{DeviceId, EffectQuality, EntityShadowMode};
+use crate::interface::{ItemMove, ItemSearch};
+use crate::inventory::HotbarEntry;
+use crate::loaders::{CompressionQuality, TextureCompression};
+use crate::network::{CharacterId, ChatLink, EntityId, ItemId, ItemIndex, PasswordEncryption, PetCommand};
+use crate::system::Alarm;
 #[cfg(feature = "debug")]
 use crate::world::MarkerIdentifier;
 
@@ -12,17 +17,48 @@ pub enum UserEvent {
     Exit,
     ToggleRemeberUsername,
     ToggleRemeberPassword,
+    SetPasswordEncryption(PasswordEncryption),
+    SelectCharacterServer(usize),
+    CancelServerSelection,
     CameraZoom(f32),
     CameraRotate(f32),
+    CameraTilt(f32),
+    CameraPitch(f32),
+    ToggleCameraMode,
+    SaveCameraPreset(usize),
+    RecallCameraPreset(usize),
+    OpenControlsWindow,
+    StartRebinding(KeyAction),
+    KeyBindingsChanged,
     ToggleFrameLimit,
     ToggleShowInterface,
+    ToggleShowEntityInfo,
+    SetRenderScale(f32),
+    SetObjectFadeOpacity(f32),
+    ToggleHideRoofs,
+    SetEntityShadowMode(EntityShadowMode),
+    SetEffectQuality(EffectQuality),
+    SetPreferredDevice(Option<DeviceId>),
+    SetTextureCompression(TextureCompression),
+    SetCompressionQuality(CompressionQuality),
+    ToggleCustomDecorations,
+    DragWindow,
+    MinimizeWindow,
+    ToggleMaximizeWindow,
+    SetWindowOpacity(String, f32),
+    ToggleWindowClickThrough(String),
+    MoveWindowsOnScreen,
     OpenMenuWindow,
     OpenInventoryWindow,
     OpenEquipmentWindow,
     OpenGraphicsSettingsWindow,
     OpenAudioSettingsWindow,
+    OpenChatSettingsWindow,
+    OpenChatLogDirectory,
+    OpenChatTabWindow,
     ReloadTheme,
     SaveTheme,
+    ExportTheme,
     SelectCharacter(usize),
     OpenCharacterCreationWindow(usize),
     CreateCharacter(usize, String),
@@ -32,8 +68,50 @@ pub enum UserEvent {
     SwitchCharacterSlot(usize),
     RequestPlayerMove(Vector2<usize>),
     RequestPlayerInteract(EntityId),
+    OpenEntityContextMenu(EntityId),
+    RequestTrade(EntityId),
+    RespondToTradeRequest(bool),
+    LockTrade,
+    CancelTrade,
+    CommitTrade,
     RequestWarpToMap(String, Vector2<usize>),
     SendMessage(String),
+    SendWhisper(String, String),
+    EnterChatRoom(u32, String),
+    LeaveChatRoom,
+    FollowChatLink(ChatLink),
+    SearchMonsters(String),
+    OpenMonsterInfo(usize),
+    SearchItems(ItemSearch),
+    SelectRefineItem(ItemIndex),
+    RefineItem(ItemIndex, ItemId),
+    CloseRefineWindow,
+    CraftItem(ItemId, [ItemId; 3]),
+    SelectCard(ItemIndex),
+    SelectCardTarget(ItemIndex, ItemIndex),
+    InsertCard(ItemIndex, ItemIndex),
+    UseItem(ItemIndex),
+    DropItem(ItemIndex, u16),
+    SelectPetEgg(ItemIndex),
+    OpenPetStatusWindow,
+    OpenSessionStatsWindow,
+    ResetSessionStatistics,
+    ExportSessionStatistics,
+    OpenClockWindow,
+    OpenMinimapWindow,
+    OpenWorldMapWindow,
+    OpenClockSettingsWindow,
+    ChangeServerTimeOffset(i32),
+    AddClockAlarm(Alarm),
+    RemoveClockAlarm(usize),
+    SendPetCommand(PetCommand),
+    OpenSkillTreeWindow,
+    UseSkill(u16, u16),
+    HotbarSlot(usize),
+    SetHotbarSlot(usize, Option<HotbarEntry>),
+    TargetNextMonster,
+    ClearTarget,
+    SetPvpAggressive(bool),
     NextDialog(EntityId),
     CloseDialog(EntityId),
     ChooseDialogOption(EntityId, i8),
@@ -43,6 +121,8 @@ pub enum UserEvent {
     #[cfg(feature = "debug")]
     ToggleShowBoundingBoxes,
     #[cfg(feature = "debug")]
+    ToggleCacheWindows,
+    #[cfg(feature = "debug")]
     OpenMarkerDetails(MarkerIdentifier),
     #[cfg(feature = "debug")]
     OpenRenderSettingsWindow,
@@ -50,6 +130,10 @@ pub enum UserEvent {
     OpenMapDataWindow,
     #[cfg(feature = "debug")]
     OpenMapsWindow,
+    /// Move the debug camera to a spot on the current map, given from 0 to 1
+    /// with north facing up.
+    #[cfg(feature = "debug")]
+    TeleportDebugCamera(Vector2<f32>),
     #[cfg(feature = "debug")]
     OpenTimeWindow,
     #[cfg(feature = "debug")]
@@ -61,11 +145,27 @@ pub enum UserEvent {
     #[cfg(feature = "debug")]
     SetMidnight,
     #[cfg(feature = "debug")]
-    OpenThemeViewerWindow,
+    OpenThemeEditorWindow,
     #[cfg(feature = "debug")]
     OpenProfilerWindow,
+    #[cfg(feature = "debug")]
+    OpenRenderStatisticsWindow,
     #[cfg(feature = "debug_network")]
     OpenPacketWindow,
+    #[cfg(feature = "debug_network")]
+    OpenNetworkStatisticsWindow,
+    #[cfg(feature = "debug")]
+    OpenInspectorWindow,
+    /// The next click selects the element to inspect instead of clicking it.
+    #[cfg(feature = "debug")]
+    PickInspectedElement,
+    /// Make the inspected element bigger or smaller by the given amount.
+    #[cfg(feature = "debug")]
+    TweakInspectedElement(Vector2<f32>),
+    #[cfg(feature = "debug")]
+    ResetInspectedElement,
+    #[cfg(feature = "debug")]
+    SaveSnapshot,
     #[cfg(feature = "debug")]
     ToggleUseDebugCamera,
     #[cfg(feature = "debug")]
@@ -1,3 +1,4 @@
+mod bindings;
 mod event;
 mod key;
 mod mode;
@@ -5,22 +6,35 @@ mod mode;
 use std::mem::variant_count;
 use std::rc::{Rc, Weak};
 
+use arboard::Clipboard;
 use cgmath::Vector2;
 use winit::dpi::PhysicalPosition;
-use winit::event::{ElementState, MouseButton, MouseScrollDelta, VirtualKeyCode};
+use winit::event::{ElementState, Ime, MouseButton, MouseScrollDelta, VirtualKeyCode};
 
+pub use self::bindings::{KeyAction, KeyBinding, KeyBindings};
 pub use self::event::UserEvent;
 pub use self::key::Key;
 pub use self::mode::MouseInputMode;
 #[cfg(feature = "debug")]
 use crate::graphics::RenderSettings;
 use crate::graphics::{PickerRenderTarget, PickerTarget};
-use crate::interface::{ClickAction, ElementCell, Focus, Interface, MouseCursorState, WeakElementCell};
+use crate::interface::{ClickAction, ElementCell, Focus, Interface, MouseCursorState, TextEdit, WeakElementCell};
 use crate::network::ClientTick;
 
 const MOUSE_SCOLL_MULTIPLIER: f32 = 30.0;
 const KEY_COUNT: usize = variant_count::<VirtualKeyCode>();
 
+/// Input for the focused element, buffered in the order it was received.
+enum TextInput {
+    Character(char),
+    Edit(TextEdit, bool),
+    Copy,
+    Cut,
+    Paste,
+    Preedit(Option<String>),
+    Commit(String),
+}
+
 #[derive(Default)]
 pub struct FocusState {
     focused_element: Option<(WeakElementCell, usize)>,
@@ -93,9 +107,16 @@ pub struct InputSystem {
     scroll_delta: f32,
     left_mouse_button: Key,
     right_mouse_button: Key,
+    middle_mouse_button: Key,
     keys: [Key; KEY_COUNT],
     mouse_input_mode: MouseInputMode,
-    input_buffer: Vec<char>,
+    input_buffer: Vec<TextInput>,
+    /// `None` if the system clipboard is not available.
+    clipboard: Option<Clipboard>,
+    key_bindings: KeyBindings,
+    /// Action that will be bound to the next key that is pressed.
+    rebinding: Option<KeyAction>,
+    captured_key: Option<VirtualKeyCode>,
 }
 
 impl InputSystem {
@@ -110,10 +131,13 @@ impl InputSystem {
 
         let left_mouse_button = Key::default();
         let right_mouse_button = Key::default();
+        let middle_mouse_button = Key::default();
         let keys = [Key::default(); KEY_COUNT];
 
         let mouse_input_mode = MouseInputMode::None;
         let input_buffer = Vec::new();
+        let clipboard = Clipboard::new().ok();
+        let key_bindings = KeyBindings::new();
 
         Self {
             previous_mouse_position,
@@ -124,15 +148,21 @@ impl InputSystem {
             scroll_delta,
             left_mouse_button,
             right_mouse_button,
+            middle_mouse_button,
             keys,
             mouse_input_mode,
             input_buffer,
+            clipboard,
+            key_bindings,
+            rebinding: None,
+            captured_key: None,
         }
     }
 
     pub fn reset(&mut self) {
         self.left_mouse_button.reset();
         self.right_mouse_button.reset();
+        self.middle_mouse_button.reset();
         self.keys.iter_mut().for_each(|key| key.reset());
         self.mouse_input_mode = MouseInputMode::None;
     }
@@ -147,6 +177,7 @@ impl InputSystem {
         match button {
             MouseButton::Left => self.left_mouse_button.set_down(pressed),
             MouseButton::Right => self.right_mouse_button.set_down(pressed),
+            MouseButton::Middle => self.middle_mouse_button.set_down(pressed),
             _ignored => {}
         }
     }
@@ -160,11 +191,83 @@ impl InputSystem {
 
     pub fn update_keyboard(&mut self, virtual_code: VirtualKeyCode, state: ElementState) {
         let pressed = matches!(state, ElementState::Pressed);
+        let modifier = matches!(virtual_code, VirtualKeyCode::LShift | VirtualKeyCode::RShift);
+
+        // While rebinding, the key is only captured so it doesn't trigger anything else.
+        if self.rebinding.is_some() && pressed && !modifier {
+            self.captured_key = Some(virtual_code);
+            return;
+        }
+
         self.keys[virtual_code as usize].set_down(pressed);
+
+        // Editing keys repeat while they are held, so they are buffered with every press.
+        if pressed && let Some(text_input) = self.text_input(virtual_code) {
+            self.input_buffer.push(text_input);
+        }
+    }
+
+    fn text_input(&self, virtual_code: VirtualKeyCode) -> Option<TextInput> {
+        let shift_down = self.get_key(VirtualKeyCode::LShift).down() || self.get_key(VirtualKeyCode::RShift).down();
+
+        #[cfg(target_os = "macos")]
+        let command_down = self.get_key(VirtualKeyCode::LWin).down() || self.get_key(VirtualKeyCode::RWin).down();
+
+        #[cfg(not(target_os = "macos"))]
+        let command_down = self.get_key(VirtualKeyCode::LControl).down() || self.get_key(VirtualKeyCode::RControl).down();
+
+        match (virtual_code, command_down) {
+            (VirtualKeyCode::Left, _) => Some(TextInput::Edit(TextEdit::Left, shift_down)),
+            (VirtualKeyCode::Right, _) => Some(TextInput::Edit(TextEdit::Right, shift_down)),
+            (VirtualKeyCode::Home, _) => Some(TextInput::Edit(TextEdit::Home, shift_down)),
+            (VirtualKeyCode::End, _) => Some(TextInput::Edit(TextEdit::End, shift_down)),
+            (VirtualKeyCode::Delete, _) => Some(TextInput::Edit(TextEdit::Delete, false)),
+            (VirtualKeyCode::A, true) => Some(TextInput::Edit(TextEdit::SelectAll, false)),
+            (VirtualKeyCode::C, true) => Some(TextInput::Copy),
+            (VirtualKeyCode::X, true) => Some(TextInput::Cut),
+            (VirtualKeyCode::V, true) => Some(TextInput::Paste),
+            _ => None,
+        }
+    }
+
+    pub fn get_key_bindings(&self) -> &KeyBindings {
+        &self.key_bindings
+    }
+
+    pub fn get_rebinding(&self) -> Option<KeyAction> {
+        self.rebinding
+    }
+
+    /// Bind the next key that is pressed to the action. Escape cancels.
+    pub fn start_rebinding(&mut self, action: KeyAction) {
+        self.rebinding = Some(action);
+        self.captured_key = None;
     }
 
     pub fn buffer_character(&mut self, character: char) {
-        self.input_buffer.push(character);
+        self.input_buffer.push(TextInput::Character(character));
+    }
+
+    pub fn buffer_ime(&mut self, ime: Ime) {
+        match ime {
+            Ime::Enabled => {}
+            Ime::Preedit(text, _cursor) => self.input_buffer.push(TextInput::Preedit((!text.is_empty()).then_some(text))),
+            Ime::Commit(text) => {
+                self.input_buffer.push(TextInput::Preedit(None));
+                self.input_buffer.push(TextInput::Commit(text));
+            }
+            Ime::Disabled => self.input_buffer.push(TextInput::Preedit(None)),
+        }
+    }
+
+    /// Copy the selected text of the element to the clipboard. Returns `false`
+    /// if nothing was copied.
+    fn copy_selection(&mut self, element: &ElementCell) -> bool {
+        let Some(text) = element.borrow().selected_text() else {
+            return false;
+        };
+
+        self.clipboard.as_mut().is_some_and(|clipboard| clipboard.set_text(text).is_ok())
     }
 
     pub fn update_delta(&mut self) {
@@ -176,6 +279,7 @@ impl InputSystem {
 
         self.left_mouse_button.update();
         self.right_mouse_button.update();
+        self.middle_mouse_button.update();
         self.keys.iter_mut().for_each(|key| key.update());
     }
 
@@ -191,12 +295,24 @@ impl InputSystem {
         #[cfg(feature = "debug")] render_settings: &RenderSettings,
         window_size: Vector2<usize>,
         client_tick: ClientTick,
+        target_locked: bool,
     ) -> (Vec<UserEvent>, Option<ElementCell>, Option<ElementCell>, Option<PickerTarget>) {
         let mut events = Vec::new();
         let mut mouse_target = None;
-        let (hovered_element, mut window_index) = interface.hovered_element(self.new_mouse_position, &self.mouse_input_mode);
+        let (mut hovered_element, mut window_index) = interface.hovered_element(self.new_mouse_position, &self.mouse_input_mode);
 
-        let shift_down = self.get_key(VirtualKeyCode::LShift).down();
+        let shift_down = self.get_key(VirtualKeyCode::LShift).down() || self.get_key(VirtualKeyCode::RShift).down();
+
+        if let Some(key_code) = self.captured_key.take()
+            && let Some(action) = self.rebinding.take()
+        {
+            if key_code != VirtualKeyCode::Escape {
+                self.key_bindings.rebind(action, KeyBinding::new(key_code, shift_down));
+                self.key_bindings.save();
+            }
+
+            events.push(UserEvent::KeyBindingsChanged);
+        }
 
         #[cfg(feature = "debug")]
         let lock_actions = render_settings.use_debug_camera;
@@ -207,7 +323,43 @@ impl InputSystem {
             focus_state.remove_focus();
         }
 
-        if shift_down {
+        // Clicking anywhere outside of a popup closes it.
+        if (self.left_mouse_button.pressed() || self.right_mouse_button.pressed())
+            && !window_index.is_some_and(|window_index| interface.is_popup_window(window_index))
+            && interface.close_popup_windows(focus_state)
+        {
+            (hovered_element, window_index) = interface.hovered_element(self.new_mouse_position, &self.mouse_input_mode);
+        }
+
+        // Autoscrolling stops with the next click, which shouldn't click anything else.
+        if let MouseInputMode::Autoscroll(element, _) = &self.mouse_input_mode
+            && (self.left_mouse_button.pressed() || self.right_mouse_button.pressed() || self.middle_mouse_button.pressed())
+        {
+            if let Some(element) = element.upgrade() {
+                interface.autoscroll_element(&element, 0.0);
+            }
+
+            self.mouse_input_mode = match self.middle_mouse_button.pressed() {
+                true => MouseInputMode::None,
+                false => MouseInputMode::ClickInterface,
+            };
+        } else if self.middle_mouse_button.pressed()
+            && self.mouse_input_mode.is_none()
+            && window_index.is_some()
+            && let Some(hovered_element) = &hovered_element
+        {
+            self.mouse_input_mode = MouseInputMode::Autoscroll(Rc::downgrade(hovered_element), self.new_mouse_position);
+        }
+
+        // HUD windows are locked in place unless the HUD edit mode is active, in which case
+        // they can be moved and resized without holding shift.
+        let hud_window = window_index.map(|window_index| interface.is_hud_window(window_index)).unwrap_or(false);
+        let reposition_window = match hud_window {
+            true => interface.is_hud_edit_mode(),
+            false => shift_down,
+        };
+
+        if reposition_window {
             if let Some(window_index) = &mut window_index {
                 if self.left_mouse_button.pressed() {
                     *window_index = interface.move_window_to_top(*window_index);
@@ -221,7 +373,13 @@ impl InputSystem {
             }
         }
 
-        let condition = (self.left_mouse_button.pressed() || self.right_mouse_button.pressed()) && !shift_down;
+        // While the inspector is waiting for an element, the click only selects it.
+        #[cfg(feature = "debug")]
+        if self.left_mouse_button.pressed() && self.mouse_input_mode.is_none() && interface.pick_element(hovered_element.as_ref()) {
+            self.mouse_input_mode = MouseInputMode::ClickInterface;
+        }
+
+        let condition = (self.left_mouse_button.pressed() || self.right_mouse_button.pressed()) && !reposition_window;
         if let Some(window_index) = &mut window_index && self.mouse_input_mode.is_none() && condition {
 
             *window_index = interface.move_window_to_top(*window_index);
@@ -253,7 +411,14 @@ impl InputSystem {
                             focus_state.update_focused_element(new_focused_element, *window_index);
                         },
 
-                        ClickAction::Event(event) => events.push(event),
+                        ClickAction::Event(event) => {
+                            // Popups are done once one of their actions was chosen.
+                            if interface.is_popup_window(*window_index) {
+                                interface.close_popup_windows(focus_state);
+                            }
+
+                            events.push(event);
+                        }
 
                         ClickAction::MoveInterface => self.mouse_input_mode = MouseInputMode::MoveInterface(*window_index),
 
@@ -268,8 +433,12 @@ impl InputSystem {
                             interface.schedule_rerender();
                         },
 
+                        ClickAction::MoveSkill(skill) => self.mouse_input_mode = MouseInputMode::ClickSkill(skill),
+
                         ClickAction::OpenWindow(prototype_window) => interface.open_window(focus_state, prototype_window.as_ref()),
 
+                        ClickAction::OpenContextMenu(entries) => interface.open_context_menu(focus_state, self.new_mouse_position, entries),
+
                         ClickAction::CloseWindow => interface.close_window(focus_state, *window_index),
                     }
                 }
@@ -278,6 +447,8 @@ impl InputSystem {
 
         if self.left_mouse_button.released() {
             if let MouseInputMode::MoveInterface(identifier) = self.mouse_input_mode {
+                interface.end_window_move(identifier);
+
                 match self.right_mouse_button.down() && !self.right_mouse_button.released() {
                     true => self.mouse_input_mode = MouseInputMode::ResizeInterface(identifier),
                     false => self.mouse_input_mode = MouseInputMode::None,
@@ -294,10 +465,21 @@ impl InputSystem {
                             events.push(UserEvent::MoveItem(item_move));
                         }
                     }
+                } else if let MouseInputMode::ClickSkill(skill) = mouse_input_mode {
+                    events.push(UserEvent::UseSkill(skill.skill_id, skill.skill_level));
+                } else if let MouseInputMode::MoveSkill(skill) = mouse_input_mode {
+                    if let Some(hovered_element) = &hovered_element {
+                        if let Some(event) = hovered_element.borrow_mut().drop_skill(skill) {
+                            events.push(event);
+                        }
+                    }
                 }
             }
         }
 
+        // Releasing the right mouse button without rotating the camera counts as a click.
+        let right_clicked = self.right_mouse_button.released() && self.mouse_input_mode.is_none() && !lock_actions;
+
         if self.right_mouse_button.released() {
             if let MouseInputMode::ResizeInterface(identifier) = self.mouse_input_mode {
                 match self.left_mouse_button.down() && !self.left_mouse_button.released() {
@@ -337,9 +519,31 @@ impl InputSystem {
                 }
             }
             MouseInputMode::RotateCamera => {
-                events.push(UserEvent::CameraRotate(self.mouse_delta.x));
+                match shift_down {
+                    true => events.push(UserEvent::CameraTilt(self.mouse_delta.y)),
+                    false => {
+                        events.push(UserEvent::CameraRotate(self.mouse_delta.x));
+                        events.push(UserEvent::CameraPitch(self.mouse_delta.y));
+                    }
+                }
+
                 interface.set_mouse_cursor_state(MouseCursorState::RotateCamera, client_tick);
             }
+            MouseInputMode::Autoscroll(element, origin) => match element.upgrade() {
+                Some(element) => {
+                    if self.mouse_delta != Vector2::new(0.0, 0.0) {
+                        interface.autoscroll_element(&element, self.new_mouse_position.y - origin.y);
+                    }
+                    interface.set_mouse_cursor_state(MouseCursorState::Grab, client_tick);
+                }
+                None => self.mouse_input_mode = MouseInputMode::None,
+            },
+            MouseInputMode::ClickSkill(skill) => {
+                if self.mouse_delta != Vector2::new(0.0, 0.0) {
+                    self.mouse_input_mode = MouseInputMode::MoveSkill(skill.clone());
+                    interface.schedule_rerender();
+                }
+            }
             MouseInputMode::ClickInterface => interface.set_mouse_cursor_state(MouseCursorState::Click, client_tick),
             MouseInputMode::None => {}
             MouseInputMode::MoveItem(..) | MouseInputMode::MoveSkill(..) => {}
@@ -355,7 +559,7 @@ impl InputSystem {
             }
         }
 
-        let characters = self.input_buffer.drain(..).collect::<Vec<_>>();
+        let text_inputs = self.input_buffer.drain(..).collect::<Vec<_>>();
 
         if let Some((focused_element, focused_window)) = &focus_state.get_focused_element() {
             // this will currently not affect the following statements, which is a bit
@@ -378,7 +582,13 @@ impl InputSystem {
                 if let Some(action) = action {
                     // TODO: remove and replace with proper event
                     match action {
-                        ClickAction::Event(event) => events.push(event),
+                        ClickAction::Event(event) => {
+                            if interface.is_popup_window(*focused_window) {
+                                interface.close_popup_windows(focus_state);
+                            }
+
+                            events.push(event);
+                        }
                         ClickAction::OpenWindow(prototype_window) => interface.open_window(focus_state, prototype_window.as_ref()),
                         ClickAction::CloseWindow => interface.close_window(focus_state, *focused_window),
                         _ => {}
@@ -388,59 +598,70 @@ impl InputSystem {
         }
 
         if let Some((focused_element, focused_window)) = &focus_state.get_focused_element() {
-            for character in characters {
-                match character {
+            for text_input in text_inputs {
+                let action = match text_input {
                     // ignore since we need to handle tab knowing the state of shift
-                    '\t' => {}
-                    '\x1b' => {}
-                    valid => {
-                        if let Some(action) = interface.input_character_element(focused_element, *focused_window, valid) {
-                            match action {
-                                // is handled in the interface
-                                ClickAction::ChangeEvent(..) => {}
-                                ClickAction::FocusElement => {
-                                    let element_cell = focused_element.clone();
-                                    let new_focused_element = focused_element.borrow().focus_next(element_cell, None, Focus::downwards());
-
-                                    focus_state.set_focused_element(new_focused_element, *focused_window);
-                                }
-                                ClickAction::FocusNext(focus_mode) => {
-                                    let element_cell = focused_element.clone();
-                                    let new_focused_element =
-                                        focused_element.borrow().focus_next(element_cell, None, Focus::new(focus_mode));
-
-                                    focus_state.update_focused_element(new_focused_element, *focused_window);
-                                }
-                                ClickAction::Event(event) => events.push(event),
-                                ClickAction::MoveInterface => self.mouse_input_mode = MouseInputMode::MoveInterface(*focused_window),
-                                ClickAction::DragElement => {
-                                    self.mouse_input_mode = MouseInputMode::DragElement((focused_element.clone(), *focused_window))
-                                }
-                                // TODO: should just move immediately ?
-                                ClickAction::MoveItem(..) => {}
-                                ClickAction::OpenWindow(prototype_window) => interface.open_window(focus_state, prototype_window.as_ref()),
-                                ClickAction::CloseWindow => interface.close_window(focus_state, *focused_window),
-                            }
+                    TextInput::Character('\t' | '\x1b') => None,
+                    TextInput::Character(character) => interface.input_character_element(focused_element, *focused_window, character),
+                    TextInput::Edit(edit, select) => interface.edit_text_element(focused_element, *focused_window, edit, select),
+                    TextInput::Copy => {
+                        self.copy_selection(focused_element);
+                        None
+                    }
+                    TextInput::Cut => match self.copy_selection(focused_element) {
+                        true => interface.edit_text_element(focused_element, *focused_window, TextEdit::Delete, false),
+                        false => None,
+                    },
+                    TextInput::Paste => self
+                        .clipboard
+                        .as_mut()
+                        .and_then(|clipboard| clipboard.get_text().ok())
+                        .and_then(|text| interface.insert_text_element(focused_element, *focused_window, &text)),
+                    TextInput::Preedit(preedit) => interface.compose_text_element(focused_element, *focused_window, preedit),
+                    TextInput::Commit(text) => interface.insert_text_element(focused_element, *focused_window, &text),
+                };
+
+                if let Some(action) = action {
+                    match action {
+                        // is handled in the interface
+                        ClickAction::ChangeEvent(..) => {}
+                        ClickAction::FocusElement => {
+                            let element_cell = focused_element.clone();
+                            let new_focused_element = focused_element.borrow().focus_next(element_cell, None, Focus::downwards());
+
+                            focus_state.set_focused_element(new_focused_element, *focused_window);
+                        }
+                        ClickAction::FocusNext(focus_mode) => {
+                            let element_cell = focused_element.clone();
+                            let new_focused_element = focused_element.borrow().focus_next(element_cell, None, Focus::new(focus_mode));
+
+                            focus_state.update_focused_element(new_focused_element, *focused_window);
+                        }
+                        ClickAction::Event(event) => events.push(event),
+                        ClickAction::MoveInterface => self.mouse_input_mode = MouseInputMode::MoveInterface(*focused_window),
+                        ClickAction::DragElement => {
+                            self.mouse_input_mode = MouseInputMode::DragElement((focused_element.clone(), *focused_window))
                         }
+                        // TODO: should just move immediately ?
+                        ClickAction::MoveItem(..) | ClickAction::MoveSkill(..) | ClickAction::OpenContextMenu(..) => {}
+                        ClickAction::OpenWindow(prototype_window) => interface.open_window(focus_state, prototype_window.as_ref()),
+                        ClickAction::CloseWindow => interface.close_window(focus_state, *focused_window),
                     }
                 }
             }
         } else {
-            if self.get_key(VirtualKeyCode::Tab).pressed() {
-                interface.first_focused_element(focus_state);
-            }
-
-            if self.get_key(VirtualKeyCode::Escape).pressed() {
-                events.push(UserEvent::OpenMenuWindow);
-            }
-
-            if self.get_key(VirtualKeyCode::I).pressed() {
-                events.push(UserEvent::OpenInventoryWindow);
+            // A locked target is cleared before the menu is opened.
+            if self.get_key(VirtualKeyCode::Escape).pressed() && !interface.close_top_window(focus_state) {
+                match target_locked {
+                    true => events.push(UserEvent::ClearTarget),
+                    false => events.push(UserEvent::OpenMenuWindow),
+                }
             }
 
-            if self.get_key(VirtualKeyCode::H).pressed() && shift_down {
-                events.push(UserEvent::ToggleShowInterface);
-            }
+            self.key_bindings
+                .iter()
+                .filter(|(_, binding)| binding.shift == shift_down && self.get_key(binding.key).pressed())
+                .for_each(|(action, _)| events.push(action.user_event()));
 
             #[cfg(feature = "debug")]
             if self.get_key(VirtualKeyCode::M).pressed() {
@@ -536,6 +757,10 @@ impl InputSystem {
                         }
                     }
 
+                    if right_clicked && let PickerTarget::Entity(entity_id) = picker_target {
+                        events.push(UserEvent::OpenEntityContextMenu(entity_id));
+                    }
+
                     mouse_target = Some(picker_target);
                 }
             }
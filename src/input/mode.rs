@@ -1,14 +1,24 @@
+use cgmath::Vector2;
+
 use crate::graphics::Texture;
-use crate::interface::{ElementCell, ItemSource};
+use crate::interface::{ElementCell, ItemSource, WeakElementCell};
 use crate::inventory::Item;
+use crate::network::SkillInformation;
 
 #[derive(Default)]
 pub enum MouseInputMode {
     MoveItem(ItemSource, Item),
-    MoveSkill(usize),
+    /// A skill was pressed but the mouse did not move yet. Moving the mouse
+    /// starts dragging it, releasing the button uses it.
+    ClickSkill(SkillInformation),
+    MoveSkill(SkillInformation),
     MoveInterface(usize),
     ResizeInterface(usize),
     DragElement((ElementCell, usize)),
+    /// Scrolling the element with the middle mouse button, starting from the
+    /// given mouse position. The element is only held weakly, since its
+    /// window might close while scrolling.
+    Autoscroll(WeakElementCell, Vector2<f32>),
     ClickInterface,
     RotateCamera,
     #[default]
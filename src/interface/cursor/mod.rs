@@ -46,7 +46,7 @@ impl MouseCursor {
     }
 
     pub fn update(&mut self, client_tick: ClientTick) {
-        self.animation_state.update(client_tick);
+        self.animation_state.update(client_tick, None);
     }
 
     // TODO: this is just a workaround until i find a better solution to make the
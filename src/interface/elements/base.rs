@@ -3,10 +3,11 @@ use std::rc::{Rc, Weak};
 
 use cgmath::{Vector2, Vector4, Zero};
 
-use crate::graphics::{Color, InterfaceRenderer, Renderer, Texture};
-use crate::input::MouseInputMode;
+use crate::graphics::{Color, ImageBuffer, InterfaceRenderer, Renderer, Texture};
+use crate::input::{MouseInputMode, UserEvent};
 use crate::interface::*;
 use crate::inventory::Item;
+use crate::network::SkillInformation;
 
 pub type ElementCell = Rc<RefCell<dyn Element>>;
 pub type WeakElementCell = Weak<RefCell<dyn Element>>;
@@ -42,6 +43,19 @@ impl<'a> ElementRenderer<'a> {
         );
     }
 
+    pub fn render_themed_background(&mut self, background: &Background, border_radius: Vector4<f32>, color: Color) {
+        self.renderer.render_background(
+            self.render_target,
+            background,
+            self.position,
+            self.size,
+            self.clip_size,
+            border_radius * *self.interface_settings.scaling,
+            color,
+            *self.interface_settings.scaling,
+        );
+    }
+
     pub fn render_rectangle(&mut self, position: Position, size: Size, border_radius: Vector4<f32>, color: Color) {
         self.renderer.render_rectangle(
             self.render_target,
@@ -98,6 +112,16 @@ impl<'a> ElementRenderer<'a> {
         );
     }
 
+    pub fn render_image(&mut self, image: ImageBuffer, offset: Position, size: Size) {
+        self.renderer.render_image(
+            self.render_target,
+            image,
+            self.position + offset * *self.interface_settings.scaling,
+            size * *self.interface_settings.scaling,
+            self.clip_size,
+        );
+    }
+
     pub fn render_element(
         &mut self,
         element: &dyn Element,
@@ -129,6 +153,12 @@ pub struct ElementState {
     pub cached_size: Size,
     pub cached_position: Position,
     pub parent_element: Option<Weak<RefCell<dyn Element>>>,
+    /// Constraint that the element was last resolved with.
+    #[cfg(feature = "debug")]
+    pub resolved_constraint: Option<SizeConstraint>,
+    /// Set from the element inspector to try out a different constraint.
+    #[cfg(feature = "debug")]
+    pub constraint_override: Option<SizeConstraint>,
 }
 
 impl Default for ElementState {
@@ -137,6 +167,10 @@ impl Default for ElementState {
             cached_size: Size::zero(),
             cached_position: Position::zero(),
             parent_element: None,
+            #[cfg(feature = "debug")]
+            resolved_constraint: None,
+            #[cfg(feature = "debug")]
+            constraint_override: None,
         }
     }
 }
@@ -146,7 +180,18 @@ impl ElementState {
         self.parent_element = weak_parent;
     }
 
+    /// Remembers the constraint for the inspector and replaces it if the
+    /// inspector overrides it.
+    #[cfg(feature = "debug")]
+    pub fn apply_constraint_override(&mut self, size_constraint: &SizeConstraint) -> SizeConstraint {
+        self.resolved_constraint = Some(*size_constraint);
+        self.constraint_override.unwrap_or(*size_constraint)
+    }
+
     pub fn resolve(&mut self, placement_resolver: &mut PlacementResolver, size_constraint: &SizeConstraint) {
+        #[cfg(feature = "debug")]
+        let size_constraint = &self.apply_constraint_override(size_constraint);
+
         let (size, position) = placement_resolver.allocate(size_constraint);
         self.cached_size = size.finalize();
         self.cached_position = position;
@@ -177,12 +222,7 @@ impl ElementState {
         let position = parent_position + self.cached_position;
         let size = self.cached_size;
 
-        let clip_size = Vector4::new(
-            clip_size.x.max(position.x),
-            clip_size.y.max(position.y),
-            clip_size.z.min(position.x + self.cached_size.x),
-            clip_size.w.min(position.y + self.cached_size.y),
-        );
+        let clip_size = clip_size.intersect(position, size);
 
         ElementRenderer {
             render_target,
@@ -236,6 +276,12 @@ impl From<bool> for FocusMode {
 pub trait Element {
     fn get_state(&self) -> &ElementState;
 
+    /// Name of the type, shown by the element inspector.
+    #[cfg(feature = "debug")]
+    fn element_name(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
+
     fn get_state_mut(&mut self) -> &mut ElementState;
 
     fn link_back(&mut self, _weak_self: Weak<RefCell<dyn Element>>, weak_parent: Option<Weak<RefCell<dyn Element>>>) {
@@ -297,10 +343,53 @@ pub trait Element {
         None
     }
 
+    /// Move the cursor or delete text. `select` extends the selection instead
+    /// of collapsing it.
+    fn edit_text(&mut self, _edit: TextEdit, _select: bool) -> Option<ClickAction> {
+        None
+    }
+
+    /// Insert pasted or composed text at the cursor, replacing the selection.
+    fn insert_text(&mut self, _text: &str) -> Option<ClickAction> {
+        None
+    }
+
+    /// Text that is still being composed by an input method and not yet part
+    /// of the input. `None` once the composition ends.
+    fn compose_text(&mut self, _preedit: Option<String>) -> Option<ClickAction> {
+        None
+    }
+
+    /// The text that would be copied to the clipboard.
+    fn selected_text(&self) -> Option<String> {
+        None
+    }
+
+    /// Position of the text cursor relative to the element, used to place the
+    /// candidate window of input methods.
+    fn caret_offset(&self, _interface_settings: &InterfaceSettings, _theme: &Theme) -> Option<Position> {
+        None
+    }
+
+    /// Offset of the children from where they were placed, for example by
+    /// scrolling.
+    fn content_offset(&self) -> Position {
+        Position::zero()
+    }
+
     fn drop_item(&mut self, _item_source: ItemSource, _item: Item) -> Option<ItemMove> {
         None
     }
 
+    fn drop_skill(&mut self, _skill: SkillInformation) -> Option<UserEvent> {
+        None
+    }
+
+    /// Shown next to the mouse after the element was hovered for a moment.
+    fn tooltip(&self) -> Option<TooltipContent> {
+        None
+    }
+
     fn scroll(&mut self, delta: f32) -> Option<ChangeEvent> {
         self.get_state()
             .parent_element
@@ -309,6 +398,17 @@ pub trait Element {
             .and_then(|element| (*element).borrow_mut().scroll(delta))
     }
 
+    /// Keep scrolling while the middle mouse button autoscroll is active.
+    /// `distance` is how far the mouse moved vertically from where the
+    /// autoscroll started, so zero stops scrolling.
+    fn autoscroll(&mut self, distance: f32) -> Option<ChangeEvent> {
+        self.get_state()
+            .parent_element
+            .as_ref()
+            .and_then(|weak_pointer| weak_pointer.upgrade())
+            .and_then(|element| (*element).borrow_mut().autoscroll(distance))
+    }
+
     fn render(
         &self,
         render_target: &mut <InterfaceRenderer as Renderer>::Target,
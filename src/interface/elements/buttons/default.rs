@@ -140,7 +140,7 @@ impl Element for Button {
             false => *theme.button.background_color,
         };
 
-        renderer.render_background(*theme.button.border_radius, background_color);
+        renderer.render_themed_background(&theme.button.background, *theme.button.border_radius, background_color);
 
         if let Some(text) = &self.text {
             let foreground_color = if disabled {
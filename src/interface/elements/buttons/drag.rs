@@ -8,6 +8,7 @@ use crate::interface::{Element, *};
 pub struct DragButton {
     window_title: String,
     width_constraint: DimensionConstraint,
+    window_class: Option<String>,
     #[new(default)]
     state: ElementState,
 }
@@ -43,6 +44,12 @@ impl Element for DragButton {
         Some(ClickAction::MoveInterface)
     }
 
+    fn right_click(&mut self, _force_update: &mut bool) -> Option<ClickAction> {
+        self.window_class
+            .clone()
+            .map(|window_class| ClickAction::OpenWindow(Box::new(WindowOptionsWindow::new(window_class))))
+    }
+
     fn render(
         &self,
         render_target: &mut <InterfaceRenderer as Renderer>::Target,
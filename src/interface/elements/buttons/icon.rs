@@ -0,0 +1,79 @@
+use cgmath::{Array, Vector4, Zero};
+use derive_new::new;
+
+use crate::graphics::{InterfaceRenderer, Renderer, Texture};
+use crate::input::{MouseInputMode, UserEvent};
+use crate::interface::*;
+
+/// A square button that only shows a texture, used for item icons that are not
+/// part of the inventory.
+#[derive(new)]
+pub struct IconButton {
+    texture: Texture,
+    event: UserEvent,
+    #[new(default)]
+    state: ElementState,
+}
+
+impl IconButton {
+    pub fn wrap(self) -> ElementCell {
+        Rc::new(RefCell::new(self))
+    }
+}
+
+impl Element for IconButton {
+    fn get_state(&self) -> &ElementState {
+        &self.state
+    }
+
+    fn get_state_mut(&mut self) -> &mut ElementState {
+        &mut self.state
+    }
+
+    fn resolve(&mut self, placement_resolver: &mut PlacementResolver, _interface_settings: &InterfaceSettings, _theme: &Theme) {
+        self.state.resolve(placement_resolver, &constraint!(30, 30));
+    }
+
+    fn hovered_element(&self, mouse_position: Position, mouse_mode: &MouseInputMode) -> HoverInformation {
+        match mouse_mode {
+            MouseInputMode::None => self.state.hovered_element(mouse_position),
+            _ => HoverInformation::Missed,
+        }
+    }
+
+    fn left_click(&mut self, _force_update: &mut bool) -> Option<ClickAction> {
+        Some(ClickAction::Event(self.event.clone()))
+    }
+
+    fn render(
+        &self,
+        render_target: &mut <InterfaceRenderer as Renderer>::Target,
+        renderer: &InterfaceRenderer,
+        _state_provider: &StateProvider,
+        interface_settings: &InterfaceSettings,
+        theme: &Theme,
+        parent_position: Position,
+        clip_size: ClipSize,
+        hovered_element: Option<&dyn Element>,
+        focused_element: Option<&dyn Element>,
+        _mouse_mode: &MouseInputMode,
+        _second_theme: bool,
+    ) {
+        let mut renderer = self
+            .state
+            .element_renderer(render_target, renderer, interface_settings, parent_position, clip_size);
+
+        let background_color = match self.is_element_self(hovered_element) || self.is_element_self(focused_element) {
+            true => *theme.button.hovered_background_color,
+            false => *theme.button.background_color,
+        };
+
+        renderer.render_background(Vector4::from_value(5.0), background_color);
+        renderer.render_sprite(
+            self.texture.clone(),
+            Vector2::zero(),
+            Vector2::from_value(30.0),
+            Color::monochrome(255),
+        );
+    }
+}
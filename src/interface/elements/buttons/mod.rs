@@ -1,11 +1,13 @@
 mod close;
 mod default;
 mod drag;
+mod icon;
 mod state;
 
 pub use self::close::CloseButton;
 pub use self::default::{Button, ColorSelector, FontSizeSelector};
 pub use self::drag::DragButton;
+pub use self::icon::IconButton;
 pub use self::state::StateButton;
 use crate::input::UserEvent;
 use crate::interface::ClickAction;
@@ -118,7 +118,7 @@ impl Element for StateButton {
                 false => *theme.button.background_color,
             };
 
-            renderer.render_background(*theme.button.border_radius, background_color);
+            renderer.render_themed_background(&theme.button.background, *theme.button.border_radius, background_color);
         }
 
         let foreground_color = match self.transparent_background && highlighted {
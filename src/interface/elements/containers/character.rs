@@ -4,7 +4,7 @@ use std::rc::Weak;
 use cgmath::Array;
 use procedural::*;
 
-use crate::graphics::{Color, InterfaceRenderer, Renderer};
+use crate::graphics::{Color, ImageBuffer, InterfaceRenderer, Renderer};
 use crate::input::{MouseInputMode, UserEvent};
 use crate::interface::*;
 use crate::network::CharacterInformation;
@@ -13,12 +13,19 @@ use crate::network::CharacterInformation;
 pub struct CharacterPreview {
     characters: Remote<Vec<CharacterInformation>>,
     move_request: Remote<Option<usize>>,
+    /// Rendered sprites of the characters, by slot.
+    previews: Remote<Vec<(usize, Remote<ImageBuffer>)>>,
     slot: usize,
     state: ContainerState,
 }
 
 impl CharacterPreview {
-    fn get_elements(characters: &Remote<Vec<CharacterInformation>>, move_request: &Remote<Option<usize>>, slot: usize) -> Vec<ElementCell> {
+    fn get_elements(
+        characters: &Remote<Vec<CharacterInformation>>,
+        move_request: &Remote<Option<usize>>,
+        previews: &Remote<Vec<(usize, Remote<ImageBuffer>)>>,
+        slot: usize,
+    ) -> Vec<ElementCell> {
         if let Some(origin_slot) = *move_request.borrow() {
             let text = match origin_slot == slot {
                 true => "click to cancel",
@@ -37,7 +44,7 @@ impl CharacterPreview {
         let character_information = characters.iter().find(|character| character.character_number as usize == slot);
 
         if let Some(character_information) = character_information {
-            return vec![
+            let mut elements = vec![
                 Text::default()
                     .with_dynamic_text(character_information.name.clone())
                     .with_foreground_color(|_| Color::rgb(220, 210, 210))
@@ -57,6 +64,12 @@ impl CharacterPreview {
                     .with_width(dimension!(50%))
                     .wrap(),
             ];
+
+            if let Some((_, image)) = previews.borrow().iter().find(|(preview_slot, _)| *preview_slot == slot) {
+                elements.insert(0, EntityPreview::new(image.clone()).wrap());
+            }
+
+            return elements;
         }
 
         vec![
@@ -67,13 +80,19 @@ impl CharacterPreview {
         ]
     }
 
-    pub fn new(characters: Remote<Vec<CharacterInformation>>, move_request: Remote<Option<usize>>, slot: usize) -> Self {
-        let elements = Self::get_elements(&characters, &move_request, slot);
+    pub fn new(
+        characters: Remote<Vec<CharacterInformation>>,
+        move_request: Remote<Option<usize>>,
+        previews: Remote<Vec<(usize, Remote<ImageBuffer>)>>,
+        slot: usize,
+    ) -> Self {
+        let elements = Self::get_elements(&characters, &move_request, &previews, slot);
         let state = ContainerState::new(elements);
 
         Self {
             characters,
             move_request,
+            previews,
             slot,
             state,
         }
@@ -106,7 +125,7 @@ impl Element for CharacterPreview {
     }
 
     fn resolve(&mut self, placement_resolver: &mut PlacementResolver, interface_settings: &InterfaceSettings, theme: &Theme) {
-        let size_constraint = &constraint!(20%, 150);
+        let size_constraint = &constraint!(20%, 230);
         self.state.resolve(
             placement_resolver,
             interface_settings,
@@ -119,15 +138,21 @@ impl Element for CharacterPreview {
     fn update(&mut self) -> Option<ChangeEvent> {
         let characters_changed = self.characters.consume_changed();
         let move_request_changed = self.move_request.consume_changed();
+        let previews_changed = self.previews.consume_changed();
 
-        if characters_changed || move_request_changed {
+        if characters_changed || move_request_changed || previews_changed {
             let weak_parent = self.state.state.parent_element.clone();
             // Since the character container will always have at least one linked child
             // element, we can get a reference to self there instead of storing
             // it in self.
             let weak_self = self.state.elements[0].borrow().get_state().parent_element.clone().unwrap();
 
-            *self = Self::new(self.characters.clone(), self.move_request.clone(), self.slot);
+            *self = Self::new(
+                self.characters.clone(),
+                self.move_request.clone(),
+                self.previews.clone(),
+                self.slot,
+            );
 
             // important: link back after creating elements, otherwise focus navigation and
             // scrolling would break
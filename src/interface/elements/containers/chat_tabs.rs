@@ -0,0 +1,159 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use cgmath::Array;
+use procedural::*;
+
+use crate::graphics::{InterfaceRenderer, Renderer};
+use crate::input::{MouseInputMode, UserEvent};
+use crate::interface::*;
+use crate::network::ChatTab;
+
+pub struct ChatTabBar {
+    tabs: Remote<Vec<ChatTab>>,
+    selected_tab: TrackedState<usize>,
+    weak_self: Option<WeakElementCell>,
+    state: ContainerState,
+}
+
+impl ChatTabBar {
+    pub fn new(tabs: Remote<Vec<ChatTab>>, selected_tab: TrackedState<usize>) -> Self {
+        let mut elements: Vec<ElementCell> = tabs
+            .borrow()
+            .iter()
+            .enumerate()
+            .map(|(index, tab)| {
+                let selector = {
+                    let selected_tab = selected_tab.clone();
+                    move |_: &StateProvider| *selected_tab.borrow() == index
+                };
+
+                let closure = {
+                    let mut selected_tab = selected_tab.clone();
+                    move || selected_tab.set(index)
+                };
+
+                StateButton::default()
+                    .with_dynamic_text(tab.name.clone())
+                    .with_selector(selector)
+                    .with_closure(closure)
+                    .with_width(dimension!(80 > 25% < 120))
+                    .wrap()
+            })
+            .collect();
+
+        elements.push(
+            Button::default()
+                .with_static_text("edit")
+                .with_event(UserEvent::OpenChatTabWindow)
+                .with_width(dimension!(50))
+                .wrap(),
+        );
+
+        let weak_self = None;
+        let state = ContainerState::new(elements);
+
+        Self {
+            tabs,
+            selected_tab,
+            weak_self,
+            state,
+        }
+    }
+
+    pub fn wrap(self) -> ElementCell {
+        Rc::new(RefCell::new(self))
+    }
+}
+
+impl Element for ChatTabBar {
+    fn get_state(&self) -> &ElementState {
+        &self.state.state
+    }
+
+    fn get_state_mut(&mut self) -> &mut ElementState {
+        &mut self.state.state
+    }
+
+    fn link_back(&mut self, weak_self: WeakElementCell, weak_parent: Option<WeakElementCell>) {
+        self.weak_self = Some(weak_self.clone());
+        self.state.link_back(weak_self, weak_parent);
+    }
+
+    fn is_focusable(&self) -> bool {
+        self.state.is_focusable::<false>()
+    }
+
+    fn focus_next(&self, self_cell: ElementCell, caller_cell: Option<ElementCell>, focus: Focus) -> Option<ElementCell> {
+        self.state.focus_next::<false>(self_cell, caller_cell, focus)
+    }
+
+    fn restore_focus(&self, self_cell: ElementCell) -> Option<ElementCell> {
+        self.state.restore_focus(self_cell)
+    }
+
+    fn resolve(&mut self, placement_resolver: &mut PlacementResolver, interface_settings: &InterfaceSettings, theme: &Theme) {
+        let size_constraint = &constraint!(100%, ?);
+        self.state.resolve(
+            placement_resolver,
+            interface_settings,
+            theme,
+            size_constraint,
+            Vector2::from_value(0.0),
+        );
+    }
+
+    fn update(&mut self) -> Option<ChangeEvent> {
+        if self.tabs.consume_changed() {
+            let weak_parent = self.state.state.parent_element.take();
+            let weak_self = self.weak_self.take().unwrap();
+
+            *self = Self::new(self.tabs.clone(), self.selected_tab.clone());
+            // important: link back after creating elements, otherwise focus navigation and
+            // scrolling would break
+            self.link_back(weak_self, weak_parent);
+
+            return Some(ChangeEvent::Reresolve);
+        }
+
+        None
+    }
+
+    fn hovered_element(&self, mouse_position: Position, mouse_mode: &MouseInputMode) -> HoverInformation {
+        match mouse_mode {
+            MouseInputMode::None => self.state.hovered_element(mouse_position, mouse_mode, false),
+            _ => HoverInformation::Missed,
+        }
+    }
+
+    fn render(
+        &self,
+        render_target: &mut <InterfaceRenderer as Renderer>::Target,
+        renderer: &InterfaceRenderer,
+        state_provider: &StateProvider,
+        interface_settings: &InterfaceSettings,
+        theme: &Theme,
+        parent_position: Position,
+        clip_size: ClipSize,
+        hovered_element: Option<&dyn Element>,
+        focused_element: Option<&dyn Element>,
+        mouse_mode: &MouseInputMode,
+        second_theme: bool,
+    ) {
+        let mut renderer = self
+            .state
+            .state
+            .element_renderer(render_target, renderer, interface_settings, parent_position, clip_size);
+
+        self.state.render(
+            &mut renderer,
+            state_provider,
+            interface_settings,
+            theme,
+            hovered_element,
+            focused_element,
+            mouse_mode,
+            second_theme,
+        );
+    }
+}
@@ -0,0 +1,143 @@
+use std::rc::Weak;
+
+use cgmath::Zero;
+use procedural::*;
+
+use crate::graphics::{InterfaceRenderer, Renderer};
+use crate::input::{MouseInputMode, UserEvent};
+use crate::interface::{Element, *};
+
+/// A single row of a [ContextMenu].
+#[derive(Clone, Debug)]
+pub enum ContextMenuEntry {
+    Action {
+        label: String,
+        event: UserEvent,
+    },
+    /// Expands in place to show more entries.
+    Submenu {
+        label: String,
+        entries: Vec<ContextMenuEntry>,
+    },
+}
+
+impl ContextMenuEntry {
+    pub fn action(label: &str, event: UserEvent) -> Self {
+        Self::Action {
+            label: label.to_string(),
+            event,
+        }
+    }
+
+    pub fn submenu(label: &str, entries: Vec<ContextMenuEntry>) -> Self {
+        Self::Submenu {
+            label: label.to_string(),
+            entries,
+        }
+    }
+
+    fn to_element(&self) -> ElementCell {
+        match self {
+            Self::Action { label, event } => Button::default().with_dynamic_text(label.clone()).with_event(event.clone()).wrap(),
+            Self::Submenu { label, entries } => {
+                let elements = entries.iter().map(Self::to_element).collect();
+                cell!(Expandable::new(label.clone(), elements, false))
+            }
+        }
+    }
+}
+
+/// List of actions shown in a popup at the mouse, for example when right
+/// clicking an entity or an item.
+pub struct ContextMenu {
+    state: ContainerState,
+}
+
+impl ContextMenu {
+    pub fn new(entries: &[ContextMenuEntry]) -> Self {
+        let elements = entries.iter().map(ContextMenuEntry::to_element).collect();
+
+        Self {
+            state: ContainerState::new(elements),
+        }
+    }
+
+    pub fn wrap(self) -> ElementCell {
+        Rc::new(RefCell::new(self))
+    }
+}
+
+impl Element for ContextMenu {
+    fn get_state(&self) -> &ElementState {
+        &self.state.state
+    }
+
+    fn get_state_mut(&mut self) -> &mut ElementState {
+        &mut self.state.state
+    }
+
+    fn link_back(&mut self, weak_self: Weak<RefCell<dyn Element>>, weak_parent: Option<Weak<RefCell<dyn Element>>>) {
+        self.state.link_back(weak_self, weak_parent);
+    }
+
+    fn is_focusable(&self) -> bool {
+        self.state.is_focusable::<false>()
+    }
+
+    fn focus_next(&self, self_cell: ElementCell, caller_cell: Option<ElementCell>, focus: Focus) -> Option<ElementCell> {
+        self.state.focus_next::<false>(self_cell, caller_cell, focus)
+    }
+
+    fn restore_focus(&self, self_cell: ElementCell) -> Option<ElementCell> {
+        self.state.restore_focus(self_cell)
+    }
+
+    fn resolve(&mut self, placement_resolver: &mut PlacementResolver, interface_settings: &InterfaceSettings, theme: &Theme) {
+        self.state.resolve(
+            placement_resolver,
+            interface_settings,
+            theme,
+            &constraint!(100%, ?),
+            Vector2::zero(),
+        );
+    }
+
+    fn update(&mut self) -> Option<ChangeEvent> {
+        self.state.update()
+    }
+
+    fn hovered_element(&self, mouse_position: Position, mouse_mode: &MouseInputMode) -> HoverInformation {
+        self.state.hovered_element(mouse_position, mouse_mode, false)
+    }
+
+    fn render(
+        &self,
+        render_target: &mut <InterfaceRenderer as Renderer>::Target,
+        renderer: &InterfaceRenderer,
+        state_provider: &StateProvider,
+        interface_settings: &InterfaceSettings,
+        theme: &Theme,
+        parent_position: Position,
+        clip_size: ClipSize,
+        hovered_element: Option<&dyn Element>,
+        focused_element: Option<&dyn Element>,
+        mouse_mode: &MouseInputMode,
+        second_theme: bool,
+    ) {
+        let mut renderer = self
+            .state
+            .state
+            .element_renderer(render_target, renderer, interface_settings, parent_position, clip_size);
+
+        self.state.render(
+            &mut renderer,
+            state_provider,
+            interface_settings,
+            theme,
+            hovered_element,
+            focused_element,
+            mouse_mode,
+            second_theme,
+        );
+    }
+}
@@ -67,11 +67,16 @@ impl Element for Expandable {
             )
             .finalize();
 
-        let (mut size, position) = match self.expanded && !self.state.elements.is_empty() {
-            true => placement_resolver.allocate(&self.open_size_constraint),
-            false => placement_resolver.allocate(&self.closed_size_constraint),
+        let size_constraint = match self.expanded && !self.state.elements.is_empty() {
+            true => self.open_size_constraint,
+            false => self.closed_size_constraint,
         };
 
+        #[cfg(feature = "debug")]
+        let size_constraint = self.state.state.apply_constraint_override(&size_constraint);
+
+        let (mut size, position) = placement_resolver.allocate(&size_constraint);
+
         if self.expanded && !self.state.elements.is_empty() {
             let mut inner_placement_resolver = placement_resolver.derive(
                 size,
@@ -86,12 +91,12 @@ impl Element for Expandable {
                     .resolve(&mut inner_placement_resolver, interface_settings, theme)
             });
 
-            if self.open_size_constraint.height.is_flexible() {
+            if size_constraint.height.is_flexible() {
                 let final_height = inner_placement_resolver.final_height()
                     + closed_size.y
                     + theme.expandable.element_offset.y * *interface_settings.scaling
                     + theme.expandable.border_size.y * *interface_settings.scaling * 2.0;
-                let final_height = self.open_size_constraint.validated_height(
+                let final_height = size_constraint.validated_height(
                     final_height,
                     placement_resolver.get_available().y,
                     placement_resolver.get_available().y,
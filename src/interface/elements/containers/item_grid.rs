@@ -8,22 +8,48 @@ use crate::graphics::{InterfaceRenderer, Renderer};
 use crate::input::MouseInputMode;
 use crate::interface::*;
 use crate::inventory::Item;
+use crate::network::ItemId;
 
-pub struct InventoryContainer {
+/// Empty slots are shown up to this number, so there is always space to drop
+/// items into.
+const MINIMUM_SLOT_COUNT: usize = 40;
+
+/// A grid of item slots for any list of items, such as the inventory.
+pub struct ItemGrid {
     items: Remote<Vec<Item>>,
+    highlighted_items: Remote<Vec<ItemId>>,
+    source: ItemSource,
+    minimum_slot_count: usize,
     weak_self: Option<WeakElementCell>, // TODO: maybe remove?
     state: ContainerState,
 }
 
-impl InventoryContainer {
-    pub fn new(items: Remote<Vec<Item>>) -> Self {
+impl ItemGrid {
+    pub fn new(items: Remote<Vec<Item>>, highlighted_items: Remote<Vec<ItemId>>, source: ItemSource) -> Self {
+        Self::with_slot_count(items, highlighted_items, source, MINIMUM_SLOT_COUNT)
+    }
+
+    /// Shows at least the given number of slots instead of the default.
+    pub fn with_slot_count(
+        items: Remote<Vec<Item>>,
+        highlighted_items: Remote<Vec<ItemId>>,
+        source: ItemSource,
+        minimum_slot_count: usize,
+    ) -> Self {
         let elements = {
             let items = items.borrow();
+            let highlighted_item_ids = highlighted_items.borrow();
 
-            (0..40)
-                .into_iter()
+            (0..items.len().max(minimum_slot_count))
                 .map(|index| items.get(index).cloned())
-                .map(|item| ItemBox::new(item, ItemSource::Inventory, Box::new(|_| false)))
+                .map(|item| {
+                    let highlighted = item
+                        .as_ref()
+                        .map(|item| highlighted_item_ids.contains(&item.item_id))
+                        .unwrap_or(false);
+
+                    ItemBox::new(item, source, Box::new(move |_| highlighted))
+                })
                 .map(ItemBox::wrap)
                 .collect()
         };
@@ -31,7 +57,14 @@ impl InventoryContainer {
         let weak_self = None;
         let state = ContainerState::new(elements);
 
-        Self { items, weak_self, state }
+        Self {
+            items,
+            highlighted_items,
+            source,
+            minimum_slot_count,
+            weak_self,
+            state,
+        }
     }
 
     pub fn wrap(self) -> ElementCell {
@@ -39,7 +72,7 @@ impl InventoryContainer {
     }
 }
 
-impl Element for InventoryContainer {
+impl Element for ItemGrid {
     fn get_state(&self) -> &ElementState {
         &self.state.state
     }
@@ -77,11 +110,20 @@ impl Element for InventoryContainer {
     }
 
     fn update(&mut self) -> Option<ChangeEvent> {
-        if self.items.consume_changed() {
+        // Both remotes have to be consumed, so avoid short circuiting here.
+        let items_changed = self.items.consume_changed();
+        let highlights_changed = self.highlighted_items.consume_changed();
+
+        if items_changed || highlights_changed {
             let weak_parent = self.state.state.parent_element.take();
             let weak_self = self.weak_self.take().unwrap();
 
-            *self = Self::new(self.items.clone());
+            *self = Self::with_slot_count(
+                self.items.clone(),
+                self.highlighted_items.clone(),
+                self.source,
+                self.minimum_slot_count,
+            );
             // important: link back after creating elements, otherwise focus navigation and
             // scrolling would break
             self.link_back(weak_self, weak_parent);
@@ -103,7 +145,7 @@ impl Element for InventoryContainer {
     fn drop_item(&mut self, item_source: ItemSource, item: Item) -> Option<ItemMove> {
         Some(ItemMove {
             source: item_source,
-            destination: ItemSource::Inventory,
+            destination: self.source,
             item,
         })
     }
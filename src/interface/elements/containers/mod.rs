@@ -1,9 +1,11 @@
 mod character;
+mod chat_tabs;
+mod context_menu;
 mod default;
 mod dialog;
 mod equipment;
 mod expandable;
-mod inventory;
+mod item_grid;
 #[cfg(feature = "debug_network")]
 mod packet;
 mod scroll;
@@ -16,11 +18,13 @@ use cgmath::Zero;
 use derive_new::new;
 
 pub use self::character::CharacterPreview;
+pub use self::chat_tabs::ChatTabBar;
+pub use self::context_menu::{ContextMenu, ContextMenuEntry};
 pub use self::default::Container;
 pub use self::dialog::{DialogContainer, DialogElement};
 pub use self::equipment::EquipmentContainer;
 pub use self::expandable::Expandable;
-pub use self::inventory::InventoryContainer;
+pub use self::item_grid::ItemGrid;
 #[cfg(feature = "debug_network")]
 pub use self::packet::{PacketEntry, PacketView};
 pub use self::scroll::ScrollView;
@@ -52,7 +56,10 @@ impl ContainerState {
         theme: &Theme,
         size_constraint: &SizeConstraint,
         border: Vector2<f32>,
-    ) {
+    ) -> f32 {
+        #[cfg(feature = "debug")]
+        let size_constraint = &self.state.apply_constraint_override(size_constraint);
+
         let (mut size, position) = placement_resolver.allocate(size_constraint);
         let mut inner_placement_resolver = placement_resolver.derive(size, Position::zero(), border);
 
@@ -65,10 +72,11 @@ impl ContainerState {
                 .resolve(&mut inner_placement_resolver, interface_settings, theme)
         });
 
+        let content_height = inner_placement_resolver.final_height();
+
         if size_constraint.height.is_flexible() {
-            let final_height = inner_placement_resolver.final_height();
             let final_height = size_constraint.validated_height(
-                final_height,
+                content_height,
                 placement_resolver.get_available().y,
                 placement_resolver.get_available().y,
                 *interface_settings.scaling,
@@ -79,6 +87,8 @@ impl ContainerState {
 
         self.state.cached_size = size.finalize();
         self.state.cached_position = position;
+
+        content_height
     }
 
     fn get_next_element(&self, start_index: usize, focus_mode: FocusMode, wrapped_around: &mut bool) -> Option<ElementCell> {
@@ -22,27 +22,48 @@ impl Display for Direction {
     }
 }
 
+/// Number of bytes shown per line of the raw bytes.
+const BYTES_PER_LINE: usize = 8;
+
 pub struct PacketEntry {
     element: Box<dyn PrototypeElement>,
+    header: [u8; 2],
     name: &'static str,
+    bytes: Vec<u8>,
     is_ping: bool,
     direction: Direction,
 }
 
 impl PacketEntry {
-    pub fn new_incoming(element: &(impl PrototypeElement + Clone + 'static), name: &'static str, is_ping: bool) -> Self {
+    pub fn new_incoming(
+        element: &(impl PrototypeElement + Clone + 'static),
+        header: [u8; 2],
+        name: &'static str,
+        bytes: Vec<u8>,
+        is_ping: bool,
+    ) -> Self {
         Self {
             element: Box::new(element.clone()),
+            header,
             name,
+            bytes,
             is_ping,
             direction: Direction::Incoming,
         }
     }
 
-    pub fn new_outgoing(element: &(impl PrototypeElement + Clone + 'static), name: &'static str, is_ping: bool) -> Self {
+    pub fn new_outgoing(
+        element: &(impl PrototypeElement + Clone + 'static),
+        header: [u8; 2],
+        name: &'static str,
+        bytes: Vec<u8>,
+        is_ping: bool,
+    ) -> Self {
         Self {
             element: Box::new(element.clone()),
+            header,
             name,
+            bytes,
             is_ping,
             direction: Direction::Outgoing,
         }
@@ -52,8 +73,38 @@ impl PacketEntry {
         self.is_ping
     }
 
+    /// The packet id as it is usually written, so 0x0080 for the header
+    /// `[0x80, 0x00]`.
+    fn packet_id(&self) -> String {
+        format!("0x{:02x}{:02x}", self.header[1], self.header[0])
+    }
+
+    /// Checks if the name or the packet id contain the filter, ignoring case.
+    fn matches(&self, filter: &str) -> bool {
+        let filter = filter.to_lowercase();
+        self.name.to_lowercase().contains(&filter) || self.packet_id().contains(&filter)
+    }
+
     fn to_element(&self) -> ElementCell {
-        self.element.to_element(format!("{} {}", self.direction, self.name))
+        let raw_bytes = self
+            .bytes
+            .chunks(BYTES_PER_LINE)
+            .enumerate()
+            .map(|(index, chunk)| {
+                let bytes: Vec<String> = chunk.iter().map(|byte| format!("{:02x}", byte)).collect();
+                let line = format!("^888888{:04x}^000000 {}", index * BYTES_PER_LINE, bytes.join(" "));
+
+                Text::default().with_dynamic_text(line).wrap()
+            })
+            .collect();
+
+        let elements = vec![
+            self.element.to_element("fields".to_string()),
+            cell!(Expandable::new(format!("raw bytes ({})", self.bytes.len()), raw_bytes, false)),
+        ];
+
+        let display = format!("{} {} {}", self.direction, self.packet_id(), self.name);
+        cell!(Expandable::new(display, elements, false))
     }
 }
 
@@ -62,13 +113,22 @@ pub struct PacketView {
     cleared: Remote<()>,
     show_pings: Remote<bool>,
     update: Remote<bool>,
+    /// Only packets with a name or id containing this text are shown.
+    filter: Rc<RefCell<String>>,
+    cached_filter: String,
     weak_self: Option<WeakElementCell>,
     cached_packet_count: usize,
     state: ContainerState,
 }
 
 impl PacketView {
-    pub fn new(packets: TrackedState<Vec<PacketEntry>>, cleared: Remote<()>, show_pings: Remote<bool>, update: Remote<bool>) -> Self {
+    pub fn new(
+        packets: TrackedState<Vec<PacketEntry>>,
+        cleared: Remote<()>,
+        show_pings: Remote<bool>,
+        update: Remote<bool>,
+        filter: Rc<RefCell<String>>,
+    ) -> Self {
         let weak_self = None;
         let (elements, cached_packet_count) = {
             let packets = packets.borrow();
@@ -83,6 +143,8 @@ impl PacketView {
             cleared,
             show_pings,
             update,
+            filter,
+            cached_filter: String::new(),
             weak_self,
             cached_packet_count,
             state: ContainerState::new(elements),
@@ -144,9 +206,12 @@ impl Element for PacketView {
             reresolve = true;
         }
 
-        if self.show_pings.consume_changed() {
+        let filter_changed = *self.filter.borrow() != self.cached_filter;
+
+        if self.show_pings.consume_changed() || filter_changed {
             self.state.elements.clear();
             self.cached_packet_count = 0;
+            self.cached_filter = self.filter.borrow().clone();
             reresolve = true;
         }
 
@@ -155,6 +220,7 @@ impl Element for PacketView {
             let mut new_elements: Vec<ElementCell> = self.packets.borrow()[self.cached_packet_count..packet_count]
                 .iter()
                 .filter(|entry| show_pings || !entry.is_ping())
+                .filter(|entry| entry.matches(&self.cached_filter))
                 .map(PacketEntry::to_element)
                 .collect();
 
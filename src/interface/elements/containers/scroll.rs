@@ -1,30 +1,99 @@
+use std::cell::Cell;
 use std::rc::Weak;
+use std::time::Instant;
 
-use cgmath::Zero;
+use cgmath::{Array, Vector4, Zero};
 
-use crate::graphics::{InterfaceRenderer, Renderer};
+use crate::graphics::{InterfaceRenderer, Renderer, SmoothedValue};
 use crate::input::MouseInputMode;
 use crate::interface::{Element, *};
 
 const SCROLL_SPEED: f32 = 0.8;
+/// Speed that is added for every scrolled pixel when gliding is enabled, in
+/// pixels per second.
+const GLIDE_FACTOR: f32 = 1.5;
+/// Distance in pixels that the mouse can move away from where autoscrolling
+/// started without scrolling.
+const AUTOSCROLL_DEAD_ZONE: f32 = 8.0;
+/// Speed in pixels per second that is added for every pixel the mouse is moved
+/// past the dead zone.
+const AUTOSCROLL_SPEED: f32 = 6.0;
 
 pub struct ScrollView {
-    scroll: f32,
+    scroll: SmoothedValue,
+    /// Speed in pixels per second that the view keeps scrolling with after the
+    /// input stopped.
+    velocity: f32,
+    /// Cached from the interface settings when resolving.
+    scroll_glide: f32,
+    last_update: Instant,
+    /// Height of all elements inside the view, which can be larger than the
+    /// view itself.
+    content_height: f32,
+    /// Scaled width of the scroll bar, cached when resolving so that hovering
+    /// doesn't need the theme.
+    scroll_bar_width: f32,
+    scroll_bar_hovered: Cell<bool>,
+    /// Whether the current drag moves the scroll bar thumb or the content.
+    dragging_thumb: bool,
+    /// Speed in pixels per second while autoscrolling with the middle mouse
+    /// button.
+    autoscroll_speed: f32,
     state: ContainerState,
     size_constraint: SizeConstraint,
 }
 
 impl ScrollView {
     pub fn new(elements: Vec<ElementCell>, size_constraint: SizeConstraint) -> Self {
-        let scroll = 0.0;
+        let scroll = SmoothedValue::new(0.0, 0.1, 15.0);
+        let velocity = 0.0;
+        let scroll_glide = 0.0;
+        let last_update = Instant::now();
+        let content_height = 0.0;
+        let scroll_bar_width = 0.0;
+        let scroll_bar_hovered = Cell::new(false);
+        let dragging_thumb = false;
+        let autoscroll_speed = 0.0;
         let state = ContainerState::new(elements);
 
         Self {
             scroll,
+            velocity,
+            scroll_glide,
+            last_update,
+            content_height,
+            scroll_bar_width,
+            scroll_bar_hovered,
+            dragging_thumb,
+            autoscroll_speed,
             state,
             size_constraint,
         }
     }
+
+    fn maximum_scroll(&self) -> f32 {
+        (self.content_height - self.state.state.cached_size.y).max(0.0)
+    }
+
+    fn is_over_scroll_bar(&self, mouse_position: Position) -> bool {
+        let size = self.state.state.cached_size;
+        let relative_position = mouse_position - self.state.state.cached_position;
+
+        self.maximum_scroll() > 0.0
+            && relative_position.x >= size.x - self.scroll_bar_width
+            && relative_position.x <= size.x
+            && relative_position.y >= 0.0
+            && relative_position.y <= size.y
+    }
+
+    /// Height and offset from the top of the scroll bar thumb.
+    fn thumb_bounds(&self, minimum_height: f32) -> (f32, f32) {
+        let view_height = self.state.state.cached_size.y;
+        let thumb_height = (view_height * view_height / self.content_height).clamp(minimum_height.min(view_height), view_height);
+        let thumb_offset = self.scroll.get_current() / self.maximum_scroll() * (view_height - thumb_height);
+
+        (thumb_height, thumb_offset)
+    }
 }
 
 impl Element for ScrollView {
@@ -53,33 +122,127 @@ impl Element for ScrollView {
     }
 
     fn resolve(&mut self, placement_resolver: &mut PlacementResolver, interface_settings: &InterfaceSettings, theme: &Theme) {
-        self.state.resolve(
+        self.content_height = self.state.resolve(
             placement_resolver,
             interface_settings,
             theme,
             &self.size_constraint,
             Vector2::zero(),
         );
+
+        self.scroll_bar_width = *theme.scroll_view.thumb_width * *interface_settings.scaling;
+        self.scroll_glide = *interface_settings.scroll_glide;
+
+        // The content might have gotten smaller since the last resolve.
+        let maximum_scroll = self.maximum_scroll();
+        if self.scroll.get_current() > maximum_scroll {
+            self.scroll.set(maximum_scroll);
+        }
+        self.scroll.set_desired(self.scroll.get_desired().min(maximum_scroll));
     }
 
     fn update(&mut self) -> Option<ChangeEvent> {
-        self.state.update()
+        let delta_time = self.last_update.elapsed().as_secs_f32();
+        self.last_update = Instant::now();
+
+        if self.autoscroll_speed != 0.0 {
+            let desired = self.scroll.get_desired() + self.autoscroll_speed * delta_time;
+            self.scroll.set_desired(desired.clamp(0.0, self.maximum_scroll()));
+        }
+
+        if self.velocity != 0.0 {
+            let maximum_scroll = self.maximum_scroll();
+            let desired = self.scroll.get_desired() + self.velocity * delta_time;
+            self.scroll.set_desired(desired.clamp(0.0, maximum_scroll));
+
+            // Stop gliding at the edges and once the speed is too low to notice.
+            self.velocity *= self.scroll_glide.powf(delta_time);
+            if self.velocity.abs() < 1.0 || desired <= 0.0 || desired >= maximum_scroll {
+                self.velocity = 0.0;
+            }
+        }
+
+        let previous_scroll = self.scroll.get_current();
+        self.scroll.update(delta_time as f64);
+
+        let scroll_event = (self.scroll.get_current() != previous_scroll).then_some(ChangeEvent::RerenderWindow);
+        let elements_event = self.state.update();
+
+        elements_event.zip_with(scroll_event, ChangeEvent::combine).or(elements_event).or(scroll_event)
     }
 
     fn hovered_element(&self, mouse_position: Position, mouse_mode: &MouseInputMode) -> HoverInformation {
+        if let MouseInputMode::DragElement((element, _)) = mouse_mode
+            && self.is_element_self(Some(&*element.borrow()))
+        {
+            self.scroll_bar_hovered.set(self.dragging_thumb);
+            return HoverInformation::Hovered;
+        }
+
+        let scroll_bar_hovered = mouse_mode.is_none() && self.is_over_scroll_bar(mouse_position);
+        self.scroll_bar_hovered.set(scroll_bar_hovered);
+
+        if scroll_bar_hovered {
+            return HoverInformation::Hovered;
+        }
+
         self.state.hovered_element(
-            mouse_position + Vector2::new(0.0, self.scroll),
+            mouse_position + Vector2::new(0.0, self.scroll.get_current()),
             mouse_mode,
             mouse_mode.is_none(),
         )
     }
 
-    fn scroll(&mut self, delta: f32) -> Option<ChangeEvent> {
-        self.scroll -= delta * SCROLL_SPEED;
-        self.scroll = self.scroll.max(0.0);
+    fn left_click(&mut self, _force_update: &mut bool) -> Option<ClickAction> {
+        // Clicking the empty space between the elements drags the content itself.
+        self.dragging_thumb = self.scroll_bar_hovered.get();
+        (self.maximum_scroll() > 0.0).then_some(ClickAction::DragElement)
+    }
+
+    fn drag(&mut self, mouse_delta: Position) -> Option<ChangeEvent> {
+        let view_height = self.state.state.cached_size.y;
+
+        // The thumb follows the mouse, so one pixel of movement scrolls by the ratio of
+        // content to view. The content follows the mouse as well, so it moves the
+        // other way.
+        let scroll = match self.dragging_thumb {
+            true => self.scroll.get_desired() + mouse_delta.y * self.content_height / view_height,
+            false => self.scroll.get_desired() - mouse_delta.y,
+        };
+
+        // Dragging is direct, so there is no smoothing and no gliding.
+        self.scroll.set(scroll.clamp(0.0, self.maximum_scroll()));
+        self.velocity = 0.0;
         Some(ChangeEvent::RerenderWindow)
     }
 
+    fn scroll(&mut self, delta: f32) -> Option<ChangeEvent> {
+        let offset = -delta * SCROLL_SPEED;
+
+        // The view moves to the new position smoothly in update.
+        self.scroll.move_desired_clamp(offset, 0.0, self.maximum_scroll());
+
+        match self.scroll_glide > 0.0 {
+            true => self.velocity += offset * GLIDE_FACTOR,
+            false => self.velocity = 0.0,
+        }
+
+        None
+    }
+
+    fn autoscroll(&mut self, distance: f32) -> Option<ChangeEvent> {
+        let distance_past_dead_zone = (distance.abs() - AUTOSCROLL_DEAD_ZONE).max(0.0);
+        self.autoscroll_speed = distance_past_dead_zone.copysign(distance) * AUTOSCROLL_SPEED;
+        self.velocity = 0.0;
+
+        // The view moves in update.
+        None
+    }
+
+    fn content_offset(&self) -> Position {
+        Position::new(0.0, -self.scroll.get_current())
+    }
+
     fn render(
         &self,
         render_target: &mut <InterfaceRenderer as Renderer>::Target,
@@ -99,7 +262,7 @@ impl Element for ScrollView {
             .state
             .element_renderer(render_target, renderer, interface_settings, parent_position, clip_size);
 
-        renderer.set_scroll(self.scroll);
+        renderer.set_scroll(self.scroll.get_current());
 
         self.state.render(
             &mut renderer,
@@ -111,5 +274,23 @@ impl Element for ScrollView {
             mouse_mode,
             second_theme,
         );
+
+        if self.maximum_scroll() > 0.0 {
+            let minimum_height = *theme.scroll_view.minimum_thumb_height * *interface_settings.scaling;
+            let (thumb_height, thumb_offset) = self.thumb_bounds(minimum_height);
+            let thumb_color = match self.scroll_bar_hovered.get() && self.is_element_self(hovered_element) {
+                true => *theme.scroll_view.hovered_thumb_color,
+                false => *theme.scroll_view.thumb_color,
+            };
+
+            // The renderer is still scrolled, so the scroll is added back to keep the thumb
+            // in place.
+            renderer.render_rectangle(
+                Position::new(self.state.state.cached_size.x - self.scroll_bar_width, thumb_offset + self.scroll.get_current()),
+                Size::new(self.scroll_bar_width, thumb_height),
+                Vector4::from_value(*theme.scroll_view.thumb_width / 2.0),
+                thumb_color,
+            );
+        }
     }
 }
@@ -0,0 +1,102 @@
+use num::Zero;
+use procedural::dimension;
+
+use crate::debug::allocation_count;
+use crate::graphics::{InterfaceRenderer, Renderer};
+use crate::input::MouseInputMode;
+use crate::interface::{Element, *};
+
+const SAMPLE_FRAMES: usize = 60;
+
+/// Shows the average number of heap allocations per frame, sampled over a
+/// number of frames so the value stays readable.
+pub struct AllocationView {
+    sample_start: usize,
+    sampled_frames: usize,
+    display: String,
+    state: ElementState,
+}
+
+impl AllocationView {
+    pub fn wrap(self) -> ElementCell {
+        Rc::new(RefCell::new(self))
+    }
+}
+
+impl Default for AllocationView {
+    fn default() -> Self {
+        Self {
+            sample_start: allocation_count(),
+            sampled_frames: 0,
+            display: "allocations per frame ^FFBB00-".to_string(),
+            state: Default::default(),
+        }
+    }
+}
+
+impl Element for AllocationView {
+    fn get_state(&self) -> &ElementState {
+        &self.state
+    }
+
+    fn get_state_mut(&mut self) -> &mut ElementState {
+        &mut self.state
+    }
+
+    fn resolve(&mut self, placement_resolver: &mut PlacementResolver, _interface_settings: &InterfaceSettings, theme: &Theme) {
+        let size_constraint = dimension!(100%).add_height(DimensionConstraint {
+            size: Dimension::Absolute(*theme.button.font_size),
+            minimum_size: None,
+            maximum_size: None,
+        });
+
+        self.state.resolve(placement_resolver, &size_constraint);
+    }
+
+    fn is_focusable(&self) -> bool {
+        false
+    }
+
+    fn update(&mut self) -> Option<ChangeEvent> {
+        self.sampled_frames += 1;
+
+        if self.sampled_frames < SAMPLE_FRAMES {
+            return None;
+        }
+
+        let current_count = allocation_count();
+        let allocations = (current_count - self.sample_start) / self.sampled_frames;
+
+        self.sample_start = current_count;
+        self.sampled_frames = 0;
+        self.display = format!("allocations per frame ^FFBB00{}", allocations);
+
+        Some(ChangeEvent::RerenderWindow)
+    }
+
+    fn render(
+        &self,
+        render_target: &mut <InterfaceRenderer as Renderer>::Target,
+        renderer: &InterfaceRenderer,
+        _state_provider: &StateProvider,
+        interface_settings: &InterfaceSettings,
+        theme: &Theme,
+        parent_position: Position,
+        clip_size: ClipSize,
+        _hovered_element: Option<&dyn Element>,
+        _focused_element: Option<&dyn Element>,
+        _mouse_mode: &MouseInputMode,
+        _second_theme: bool,
+    ) {
+        let mut renderer = self
+            .state
+            .element_renderer(render_target, renderer, interface_settings, parent_position, clip_size);
+
+        renderer.render_text(
+            &self.display,
+            Vector2::zero(),
+            *theme.button.foreground_color,
+            *theme.button.font_size,
+        );
+    }
+}
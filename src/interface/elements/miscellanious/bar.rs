@@ -0,0 +1,124 @@
+use num::Zero;
+use procedural::dimension;
+
+use crate::graphics::{Color, InterfaceRenderer, Renderer};
+use crate::interface::{Element, *};
+
+/// Values that can be displayed by a [ValueBar].
+pub trait BarValue {
+    /// The current and the maximum value.
+    fn values(&self) -> (usize, usize);
+
+    /// Additional information shown when hovering the bar.
+    fn tooltip(&self) -> Option<Vec<String>> {
+        None
+    }
+}
+
+impl BarValue for (usize, usize) {
+    fn values(&self) -> (usize, usize) {
+        *self
+    }
+}
+
+/// Horizontal bar that shows how full a value is, used for things like health
+/// and spell points.
+pub struct ValueBar<T: BarValue + 'static> {
+    values: Remote<T>,
+    label: &'static str,
+    color: Color,
+    state: ElementState,
+}
+
+impl<T: BarValue + 'static> ValueBar<T> {
+    pub fn new(values: Remote<T>, label: &'static str, color: Color) -> Self {
+        Self {
+            values,
+            label,
+            color,
+            state: ElementState::default(),
+        }
+    }
+
+    pub fn wrap(self) -> ElementCell {
+        Rc::new(RefCell::new(self))
+    }
+}
+
+impl<T: BarValue + 'static> Element for ValueBar<T> {
+    fn get_state(&self) -> &ElementState {
+        &self.state
+    }
+
+    fn get_state_mut(&mut self) -> &mut ElementState {
+        &mut self.state
+    }
+
+    fn resolve(&mut self, placement_resolver: &mut PlacementResolver, _interface_settings: &InterfaceSettings, theme: &Theme) {
+        self.state
+            .resolve(placement_resolver, &dimension!(100%).add_height(theme.button.height_constraint));
+    }
+
+    fn is_focusable(&self) -> bool {
+        false
+    }
+
+    fn hovered_element(&self, mouse_position: Position, _mouse_mode: &MouseInputMode) -> HoverInformation {
+        self.state.hovered_element(mouse_position)
+    }
+
+    fn tooltip(&self) -> Option<TooltipContent> {
+        self.values.borrow().tooltip().map(TooltipContent::Text)
+    }
+
+    fn update(&mut self) -> Option<ChangeEvent> {
+        self.values.consume_changed().then_some(ChangeEvent::RerenderWindow)
+    }
+
+    fn render(
+        &self,
+        render_target: &mut <InterfaceRenderer as Renderer>::Target,
+        renderer: &InterfaceRenderer,
+        _state_provider: &StateProvider,
+        interface_settings: &InterfaceSettings,
+        theme: &Theme,
+        parent_position: Position,
+        clip_size: ClipSize,
+        _hovered_element: Option<&dyn Element>,
+        _focused_element: Option<&dyn Element>,
+        _mouse_mode: &MouseInputMode,
+        _second_theme: bool,
+    ) {
+        let mut renderer = self
+            .state
+            .element_renderer(render_target, renderer, interface_settings, parent_position, clip_size);
+
+        let (current, maximum) = self.values.borrow().values();
+        let fill = match maximum {
+            0 => 0.0,
+            maximum => (current as f32 / maximum as f32).min(1.0),
+        };
+
+        let size = self.state.cached_size;
+        let background_color = Color::rgba(self.color.red / 3, self.color.green / 3, self.color.blue / 3, self.color.alpha);
+
+        renderer.render_background(*theme.button.border_radius, background_color);
+
+        if fill > 0.0 {
+            renderer.render_rectangle(
+                Vector2::zero(),
+                Vector2::new(size.x * fill, size.y),
+                *theme.button.border_radius,
+                self.color,
+            );
+        }
+
+        let text = format!("{} {} / {}", self.label, current, maximum);
+        renderer.render_text(
+            &text,
+            *theme.button.text_offset,
+            *theme.button.foreground_color,
+            *theme.button.font_size,
+        );
+    }
+}
@@ -0,0 +1,103 @@
+use num::Zero;
+use procedural::dimension;
+
+use crate::combat::CastState;
+use crate::graphics::{Color, InterfaceRenderer, Renderer};
+use crate::interface::{Element, *};
+
+const CAST_COLOR: Color = Color::rgb(200, 170, 70);
+const INTERRUPTED_COLOR: Color = Color::rgb(200, 60, 60);
+
+/// Shows the progress of the skill the player is casting. Flashes when the
+/// cast is interrupted.
+pub struct CastBar {
+    cast_state: Remote<CastState>,
+    visible: bool,
+    state: ElementState,
+}
+
+impl CastBar {
+    pub fn new(cast_state: Remote<CastState>) -> Self {
+        Self {
+            cast_state,
+            visible: false,
+            state: ElementState::default(),
+        }
+    }
+
+    pub fn wrap(self) -> ElementCell {
+        Rc::new(RefCell::new(self))
+    }
+}
+
+impl Element for CastBar {
+    fn get_state(&self) -> &ElementState {
+        &self.state
+    }
+
+    fn get_state_mut(&mut self) -> &mut ElementState {
+        &mut self.state
+    }
+
+    fn resolve(&mut self, placement_resolver: &mut PlacementResolver, _interface_settings: &InterfaceSettings, theme: &Theme) {
+        self.state
+            .resolve(placement_resolver, &dimension!(100%).add_height(theme.button.height_constraint));
+    }
+
+    fn is_focusable(&self) -> bool {
+        false
+    }
+
+    fn update(&mut self) -> Option<ChangeEvent> {
+        let changed = self.cast_state.consume_changed();
+        let visible = self.cast_state.borrow().progress().is_some();
+        let was_visible = std::mem::replace(&mut self.visible, visible);
+
+        // The bar is animated, so it needs to be rendered every frame while it is visible.
+        (changed || visible || was_visible).then_some(ChangeEvent::RerenderWindow)
+    }
+
+    fn render(
+        &self,
+        render_target: &mut <InterfaceRenderer as Renderer>::Target,
+        renderer: &InterfaceRenderer,
+        _state_provider: &StateProvider,
+        interface_settings: &InterfaceSettings,
+        theme: &Theme,
+        parent_position: Position,
+        clip_size: ClipSize,
+        _hovered_element: Option<&dyn Element>,
+        _focused_element: Option<&dyn Element>,
+        _mouse_mode: &MouseInputMode,
+        _second_theme: bool,
+    ) {
+        let mut renderer = self
+            .state
+            .element_renderer(render_target, renderer, interface_settings, parent_position, clip_size);
+
+        let cast_state = self.cast_state.borrow();
+        let Some((skill_name, progress)) = cast_state.progress() else {
+            return;
+        };
+
+        let color = match cast_state.is_flashing() {
+            true => INTERRUPTED_COLOR,
+            false => CAST_COLOR,
+        };
+        let size = self.state.cached_size;
+
+        renderer.render_background(*theme.button.border_radius, *theme.button.background_color);
+        renderer.render_rectangle(
+            Vector2::zero(),
+            Vector2::new(size.x * progress, size.y),
+            *theme.button.border_radius,
+            color,
+        );
+        renderer.render_text(
+            skill_name,
+            *theme.button.text_offset,
+            *theme.button.foreground_color,
+            *theme.button.font_size,
+        );
+    }
+}
@@ -1,34 +1,129 @@
+use std::cell::Cell;
+
 use cgmath::Array;
 use procedural::*;
 
 use crate::graphics::{InterfaceRenderer, Renderer};
+use crate::input::{MouseInputMode, UserEvent};
 use crate::interface::{Element, *};
 use crate::loaders::FontLoader;
-use crate::network::ChatMessage;
+use crate::network::{ChatChannel, ChatLink, ChatMessage, ChatTab};
+
+/// Number of messages recalled when opening a whisper conversation.
+const WHISPER_HISTORY_LENGTH: usize = 50;
+
+enum ChatFilter {
+    Tab {
+        tabs: Remote<Vec<ChatTab>>,
+        selected_tab: Remote<usize>,
+    },
+    Whisper {
+        partner: String,
+    },
+    Channel(ChatChannel),
+}
 
 pub struct Chat {
     // TODO: make this Remote
     messages: Rc<RefCell<Vec<ChatMessage>>>,
     font_loader: Rc<RefCell<FontLoader>>,
-    // TODO: make this Remote
-    stamp: bool,
+    stamp: Remote<bool>,
+    filter: ChatFilter,
     cached_message_count: usize,
+    /// Top left and bottom right corner of every link, from the last time the
+    /// chat was resolved. Links spanning multiple lines have one area per line.
+    link_areas: Vec<(Position, Position, ChatLink)>,
+    hovered_link: Cell<Option<usize>>,
     state: ElementState,
 }
 
 impl Chat {
-    pub fn new(messages: Rc<RefCell<Vec<ChatMessage>>>, font_loader: Rc<RefCell<FontLoader>>) -> Self {
+    pub fn new(
+        messages: Rc<RefCell<Vec<ChatMessage>>>,
+        font_loader: Rc<RefCell<FontLoader>>,
+        stamp: Remote<bool>,
+        tabs: Remote<Vec<ChatTab>>,
+        selected_tab: Remote<usize>,
+    ) -> Self {
+        Self::with_filter(messages, font_loader, stamp, ChatFilter::Tab { tabs, selected_tab })
+    }
+
+    /// Only shows the most recent messages of a whisper conversation with
+    /// `partner`.
+    pub fn whisper(
+        messages: Rc<RefCell<Vec<ChatMessage>>>,
+        font_loader: Rc<RefCell<FontLoader>>,
+        stamp: Remote<bool>,
+        partner: String,
+    ) -> Self {
+        Self::with_filter(messages, font_loader, stamp, ChatFilter::Whisper { partner })
+    }
+
+    /// Only shows messages of a single channel.
+    pub fn channel(
+        messages: Rc<RefCell<Vec<ChatMessage>>>,
+        font_loader: Rc<RefCell<FontLoader>>,
+        stamp: Remote<bool>,
+        channel: ChatChannel,
+    ) -> Self {
+        Self::with_filter(messages, font_loader, stamp, ChatFilter::Channel(channel))
+    }
+
+    fn with_filter(
+        messages: Rc<RefCell<Vec<ChatMessage>>>,
+        font_loader: Rc<RefCell<FontLoader>>,
+        stamp: Remote<bool>,
+        filter: ChatFilter,
+    ) -> Self {
         let cached_message_count = messages.borrow().len();
+        let link_areas = Vec::new();
+        let hovered_link = Cell::new(None);
         let state = ElementState::default();
 
         Self {
             messages,
             font_loader,
-            stamp: true,
+            stamp,
+            filter,
             cached_message_count,
+            link_areas,
+            hovered_link,
             state,
         }
     }
+
+    fn visible_messages<'a>(&self, messages: &'a [ChatMessage]) -> Vec<&'a ChatMessage> {
+        match &self.filter {
+            ChatFilter::Tab { tabs, selected_tab } => {
+                let tabs = tabs.borrow();
+                let tab = tabs.get(*selected_tab.borrow());
+
+                messages
+                    .iter()
+                    .filter(|message| tab.map_or(true, |tab| tab.shows(message.channel)))
+                    .collect()
+            }
+            ChatFilter::Whisper { partner } => {
+                let mut messages: Vec<&ChatMessage> = messages
+                    .iter()
+                    .filter(|message| message.partner.as_ref() == Some(partner))
+                    .collect();
+
+                let skipped = messages.len().saturating_sub(WHISPER_HISTORY_LENGTH);
+                messages.drain(..skipped);
+                messages
+            }
+            ChatFilter::Channel(channel) => messages.iter().filter(|message| message.channel == *channel).collect(),
+        }
+    }
+
+    fn filter_changed(&mut self) -> bool {
+        match &mut self.filter {
+            // all of them need to be consumed, so no short circuiting here
+            ChatFilter::Tab { tabs, selected_tab } => tabs.consume_changed() | selected_tab.consume_changed(),
+            ChatFilter::Whisper { .. } | ChatFilter::Channel(_) => false,
+        }
+    }
 }
 
 impl Element for Chat {
@@ -48,21 +143,44 @@ impl Element for Chat {
         let mut size_constraint = constraint!(100%, 0);
         // Not sure why but 0.0 cuts off the lower part of the text, so add some
         // padding.
-        let mut height = 5.0 * *interface_settings.scaling;
-
-        for message in self.messages.borrow().iter() {
-            height += self.font_loader.borrow_mut().get_text_height(
-                message.stamped_text(self.stamp),
-                *theme.chat.font_size * *interface_settings.scaling,
-                placement_resolver.get_available().x,
-            );
+        let padding = 5.0 * *interface_settings.scaling;
+        let mut height = padding;
+        let mut link_areas = Vec::new();
+        let stamp = *self.stamp.borrow();
+        let font_size = *theme.chat.font_size * *interface_settings.scaling;
+        let available_width = placement_resolver.get_available().x;
+
+        for message in self.visible_messages(&self.messages.borrow()) {
+            let text = message.stamped_text(stamp);
+            let mut font_loader = self.font_loader.borrow_mut();
+            let message_height = font_loader.get_text_height(text, font_size, available_width);
+            let message_offset = Vector2::new(0.0, height - padding);
+
+            for (range, link) in message.stamped_links(stamp) {
+                let areas = font_loader.get_text_areas(text, font_size, available_width, range);
+                link_areas.extend(
+                    areas
+                        .into_iter()
+                        .map(|(start, end)| (start + message_offset, end + message_offset, link.clone())),
+                );
+            }
+
+            height += message_height;
         }
 
+        self.link_areas = link_areas;
+        self.hovered_link.set(None);
+
         size_constraint.height = Dimension::Absolute(height);
         self.state.resolve(placement_resolver, &size_constraint);
     }
 
     fn update(&mut self) -> Option<ChangeEvent> {
+        // all of them need to be consumed, so no short circuiting here
+        if self.stamp.consume_changed() | self.filter_changed() {
+            return Some(ChangeEvent::Reresolve);
+        }
+
         let messages = self.messages.borrow();
 
         if messages.len() != self.cached_message_count {
@@ -73,6 +191,32 @@ impl Element for Chat {
         None
     }
 
+    fn hovered_element(&self, mouse_position: Position, mouse_mode: &MouseInputMode) -> HoverInformation {
+        let hovered_link = match mouse_mode {
+            MouseInputMode::None => {
+                let position = mouse_position - self.state.cached_position;
+
+                self.link_areas
+                    .iter()
+                    .position(|(start, end, _)| position.x >= start.x && position.x < end.x && position.y >= start.y && position.y < end.y)
+            }
+            _ => None,
+        };
+
+        self.hovered_link.set(hovered_link);
+
+        match hovered_link {
+            Some(_) => self.state.hovered_element(mouse_position),
+            None => HoverInformation::Missed,
+        }
+    }
+
+    fn left_click(&mut self, _force_update: &mut bool) -> Option<ClickAction> {
+        self.hovered_link
+            .get()
+            .map(|index| ClickAction::Event(UserEvent::FollowChatLink(self.link_areas[index].2.clone())))
+    }
+
     fn render(
         &self,
         render_target: &mut <InterfaceRenderer as Renderer>::Target,
@@ -93,8 +237,10 @@ impl Element for Chat {
 
         let mut offset = 0.0;
 
-        for message in self.messages.borrow().iter() {
-            let text = message.stamped_text(self.stamp);
+        let stamp = *self.stamp.borrow();
+
+        for message in self.visible_messages(&self.messages.borrow()) {
+            let text = message.stamped_text(stamp);
 
             renderer.render_text(
                 text,
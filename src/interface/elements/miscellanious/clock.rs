@@ -0,0 +1,114 @@
+use chrono::{NaiveDateTime, Timelike};
+use procedural::dimension;
+
+use crate::graphics::{InterfaceRenderer, Renderer};
+use crate::interface::{Element, *};
+use crate::system::{format_countdown, format_offset, local_time, server_time, Alarm};
+
+const LINE_HEIGHT: f32 = 16.0;
+
+/// Server time, local time and the time left until each alarm. Rerendered
+/// once every second.
+pub struct ClockDisplay {
+    server_offset_minutes: Remote<i32>,
+    alarms: Remote<Vec<Alarm>>,
+    last_second: Option<u32>,
+    state: ElementState,
+}
+
+impl ClockDisplay {
+    pub fn new(server_offset_minutes: Remote<i32>, alarms: Remote<Vec<Alarm>>) -> Self {
+        Self {
+            server_offset_minutes,
+            alarms,
+            last_second: None,
+            state: ElementState::default(),
+        }
+    }
+
+    pub fn wrap(self) -> ElementCell {
+        Rc::new(RefCell::new(self))
+    }
+
+    fn lines(&self, server_time: NaiveDateTime) -> Vec<String> {
+        let offset = format_offset(*self.server_offset_minutes.borrow());
+        let mut lines = vec![
+            format!("server {} ({})", server_time.format("%H:%M:%S"), offset),
+            format!("local {}", local_time().format("%H:%M:%S")),
+        ];
+
+        lines.extend(self.alarms.borrow().iter().map(|alarm| {
+            let remaining = alarm.next_occurrence(server_time) - server_time;
+            format!("{} in {}", alarm.name, format_countdown(remaining))
+        }));
+
+        lines
+    }
+}
+
+impl Element for ClockDisplay {
+    fn get_state(&self) -> &ElementState {
+        &self.state
+    }
+
+    fn get_state_mut(&mut self) -> &mut ElementState {
+        &mut self.state
+    }
+
+    fn resolve(&mut self, placement_resolver: &mut PlacementResolver, _interface_settings: &InterfaceSettings, _theme: &Theme) {
+        let height_constraint = DimensionConstraint {
+            size: Dimension::Absolute((2 + self.alarms.borrow().len()) as f32 * LINE_HEIGHT),
+            minimum_size: None,
+            maximum_size: None,
+        };
+
+        self.state
+            .resolve(placement_resolver, &dimension!(100%).add_height(height_constraint));
+    }
+
+    fn is_focusable(&self) -> bool {
+        false
+    }
+
+    fn update(&mut self) -> Option<ChangeEvent> {
+        if self.alarms.consume_changed() {
+            return Some(ChangeEvent::Reresolve);
+        }
+
+        let offset_changed = self.server_offset_minutes.consume_changed();
+        let second = local_time().second();
+        let second_changed = self.last_second.replace(second) != Some(second);
+
+        (offset_changed || second_changed).then_some(ChangeEvent::RerenderWindow)
+    }
+
+    fn render(
+        &self,
+        render_target: &mut <InterfaceRenderer as Renderer>::Target,
+        renderer: &InterfaceRenderer,
+        _state_provider: &StateProvider,
+        interface_settings: &InterfaceSettings,
+        theme: &Theme,
+        parent_position: Position,
+        clip_size: ClipSize,
+        _hovered_element: Option<&dyn Element>,
+        _focused_element: Option<&dyn Element>,
+        _mouse_mode: &MouseInputMode,
+        _second_theme: bool,
+    ) {
+        let mut renderer = self
+            .state
+            .element_renderer(render_target, renderer, interface_settings, parent_position, clip_size);
+
+        let server_time = server_time(*self.server_offset_minutes.borrow());
+
+        for (index, line) in self.lines(server_time).iter().enumerate() {
+            renderer.render_text(
+                line,
+                Vector2::new(0.0, index as f32 * LINE_HEIGHT),
+                *theme.button.foreground_color,
+                *theme.button.font_size,
+            );
+        }
+    }
+}
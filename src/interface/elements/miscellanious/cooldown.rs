@@ -0,0 +1,120 @@
+use cgmath::{Array, Vector4, Zero};
+use procedural::dimension;
+
+use crate::combat::SkillCooldowns;
+use crate::graphics::{Color, InterfaceRenderer, Renderer};
+use crate::interface::{Element, *};
+
+const ICON_SIZE: f32 = 30.0;
+const ICON_GAP: f32 = 4.0;
+
+/// A row of icons for the skills that are on cooldown, darkened by the part
+/// of the cooldown that is left.
+pub struct CooldownRow {
+    cooldowns: Remote<SkillCooldowns>,
+    running: bool,
+    state: ElementState,
+}
+
+impl CooldownRow {
+    pub fn new(cooldowns: Remote<SkillCooldowns>) -> Self {
+        Self {
+            cooldowns,
+            running: false,
+            state: ElementState::default(),
+        }
+    }
+
+    pub fn wrap(self) -> ElementCell {
+        Rc::new(RefCell::new(self))
+    }
+}
+
+impl Element for CooldownRow {
+    fn get_state(&self) -> &ElementState {
+        &self.state
+    }
+
+    fn get_state_mut(&mut self) -> &mut ElementState {
+        &mut self.state
+    }
+
+    fn resolve(&mut self, placement_resolver: &mut PlacementResolver, _interface_settings: &InterfaceSettings, _theme: &Theme) {
+        let height_constraint = DimensionConstraint {
+            size: Dimension::Absolute(ICON_SIZE),
+            minimum_size: None,
+            maximum_size: None,
+        };
+
+        self.state
+            .resolve(placement_resolver, &dimension!(100%).add_height(height_constraint));
+    }
+
+    fn is_focusable(&self) -> bool {
+        false
+    }
+
+    fn update(&mut self) -> Option<ChangeEvent> {
+        let changed = self.cooldowns.consume_changed();
+        let running = self.cooldowns.borrow().running().next().is_some();
+        let was_running = std::mem::replace(&mut self.running, running);
+
+        (changed || running || was_running).then_some(ChangeEvent::RerenderWindow)
+    }
+
+    fn render(
+        &self,
+        render_target: &mut <InterfaceRenderer as Renderer>::Target,
+        renderer: &InterfaceRenderer,
+        _state_provider: &StateProvider,
+        interface_settings: &InterfaceSettings,
+        theme: &Theme,
+        parent_position: Position,
+        clip_size: ClipSize,
+        _hovered_element: Option<&dyn Element>,
+        _focused_element: Option<&dyn Element>,
+        _mouse_mode: &MouseInputMode,
+        _second_theme: bool,
+    ) {
+        let mut renderer = self
+            .state
+            .element_renderer(render_target, renderer, interface_settings, parent_position, clip_size);
+
+        let scaling = *interface_settings.scaling;
+        let border_radius = Vector4::from_value(5.0);
+
+        for (index, cooldown) in self.cooldowns.borrow().running().enumerate() {
+            let offset = Vector2::new(index as f32 * (ICON_SIZE + ICON_GAP), 0.0);
+
+            match &cooldown.icon {
+                Some(icon) => renderer.render_sprite(icon.clone(), offset, Vector2::from_value(ICON_SIZE), Color::monochrome(255)),
+                None => {
+                    renderer.render_rectangle(
+                        offset * scaling,
+                        Vector2::from_value(ICON_SIZE * scaling),
+                        border_radius,
+                        *theme.button.background_color,
+                    );
+                    let short_name: String = cooldown.skill_name.chars().take(3).collect();
+                    renderer.render_text(&short_name, offset + Vector2::new(2.0, 2.0), *theme.button.foreground_color, 10.0);
+                }
+            }
+
+            let overlay_height = ICON_SIZE * cooldown.remaining_fraction();
+            renderer.render_rectangle(
+                (offset + Vector2::new(0.0, ICON_SIZE - overlay_height)) * scaling,
+                Vector2::new(ICON_SIZE, overlay_height) * scaling,
+                Vector4::zero(),
+                Color::rgba(0, 0, 0, 150),
+            );
+
+            let seconds = format!("{:.0}", cooldown.remaining().as_secs_f32().ceil());
+            renderer.render_text(
+                &seconds,
+                offset + Vector2::new(8.0, 14.0),
+                Color::monochrome(255),
+                *theme.button.font_size,
+            );
+        }
+    }
+}
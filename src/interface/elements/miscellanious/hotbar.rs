@@ -0,0 +1,163 @@
+use cgmath::{Array, Vector4, Zero};
+use procedural::constraint;
+
+use crate::graphics::{Color, InterfaceRenderer, Renderer};
+use crate::input::{MouseInputMode, UserEvent};
+use crate::interface::{Element, *};
+use crate::inventory::{HotbarEntry, Item};
+use crate::network::{ItemId, SkillInformation};
+
+const SLOT_SIZE: f32 = 30.0;
+
+/// One slot of the hotbar. Skills and items can be dropped onto it, a left
+/// click activates it and a right click clears it.
+pub struct HotbarSlot {
+    slot: usize,
+    entries: Remote<Vec<Option<HotbarEntry>>>,
+    items: Remote<Vec<Item>>,
+    state: ElementState,
+}
+
+impl HotbarSlot {
+    pub fn new(slot: usize, entries: Remote<Vec<Option<HotbarEntry>>>, items: Remote<Vec<Item>>) -> Self {
+        Self {
+            slot,
+            entries,
+            items,
+            state: ElementState::default(),
+        }
+    }
+
+    pub fn wrap(self) -> ElementCell {
+        Rc::new(RefCell::new(self))
+    }
+
+    fn entry(&self) -> Option<HotbarEntry> {
+        self.entries.borrow().get(self.slot).cloned().flatten()
+    }
+}
+
+impl Element for HotbarSlot {
+    fn get_state(&self) -> &ElementState {
+        &self.state
+    }
+
+    fn get_state_mut(&mut self) -> &mut ElementState {
+        &mut self.state
+    }
+
+    fn is_focusable(&self) -> bool {
+        false
+    }
+
+    fn resolve(&mut self, placement_resolver: &mut PlacementResolver, _interface_settings: &InterfaceSettings, _theme: &Theme) {
+        self.state.resolve(placement_resolver, &constraint!(30, 30));
+    }
+
+    fn update(&mut self) -> Option<ChangeEvent> {
+        let entries_changed = self.entries.consume_changed();
+        let items_changed = self.items.consume_changed();
+
+        (entries_changed || items_changed).then_some(ChangeEvent::RerenderWindow)
+    }
+
+    fn hovered_element(&self, mouse_position: Position, mouse_mode: &MouseInputMode) -> HoverInformation {
+        match mouse_mode {
+            MouseInputMode::None | MouseInputMode::MoveItem(..) | MouseInputMode::MoveSkill(..) => {
+                self.state.hovered_element(mouse_position)
+            }
+            _ => HoverInformation::Missed,
+        }
+    }
+
+    fn left_click(&mut self, _force_update: &mut bool) -> Option<ClickAction> {
+        self.entry().map(|_| ClickAction::Event(UserEvent::HotbarSlot(self.slot)))
+    }
+
+    fn right_click(&mut self, _force_update: &mut bool) -> Option<ClickAction> {
+        self.entry().map(|_| ClickAction::Event(UserEvent::SetHotbarSlot(self.slot, None)))
+    }
+
+    fn tooltip(&self) -> Option<TooltipContent> {
+        match self.entry()? {
+            HotbarEntry::Skill {
+                skill_name, skill_level, ..
+            } => Some(TooltipContent::Text(vec![skill_name, format!("level {}", skill_level)])),
+            HotbarEntry::Item { item_id } => self
+                .items
+                .borrow()
+                .iter()
+                .find(|item| item.item_id == ItemId(item_id))
+                .cloned()
+                .map(TooltipContent::Item),
+        }
+    }
+
+    fn drop_item(&mut self, item_source: ItemSource, item: Item) -> Option<ItemMove> {
+        Some(ItemMove {
+            source: item_source,
+            destination: ItemSource::Hotbar { slot: self.slot },
+            item,
+        })
+    }
+
+    fn drop_skill(&mut self, skill: SkillInformation) -> Option<UserEvent> {
+        Some(UserEvent::SetHotbarSlot(self.slot, Some(HotbarEntry::from_skill(&skill))))
+    }
+
+    fn render(
+        &self,
+        render_target: &mut <InterfaceRenderer as Renderer>::Target,
+        renderer: &InterfaceRenderer,
+        _state_provider: &StateProvider,
+        interface_settings: &InterfaceSettings,
+        theme: &Theme,
+        parent_position: Position,
+        clip_size: ClipSize,
+        hovered_element: Option<&dyn Element>,
+        _focused_element: Option<&dyn Element>,
+        _mouse_mode: &MouseInputMode,
+        _second_theme: bool,
+    ) {
+        let mut renderer = self
+            .state
+            .element_renderer(render_target, renderer, interface_settings, parent_position, clip_size);
+
+        let background_color = match self.is_element_self(hovered_element) {
+            true => *theme.button.hovered_background_color,
+            false => *theme.button.background_color,
+        };
+
+        renderer.render_background(Vector4::from_value(5.0), background_color);
+
+        match self.entry() {
+            Some(HotbarEntry::Skill { skill_name, .. }) => {
+                let short_name: String = skill_name.chars().take(3).collect();
+                renderer.render_text(&short_name, Vector2::new(2.0, 10.0), *theme.button.foreground_color, 10.0);
+            }
+            Some(HotbarEntry::Item { item_id }) => {
+                let items = self.items.borrow();
+
+                // Items the player ran out of stay on the hotbar, so they are shown as empty.
+                match items.iter().find(|item| item.item_id == ItemId(item_id)) {
+                    Some(item) => {
+                        let size = Vector2::from_value(SLOT_SIZE);
+                        renderer.render_sprite(item.texture.clone(), Vector2::zero(), size, Color::monochrome(255));
+                        renderer.render_text(&item.amount.to_string(), Vector2::new(2.0, 18.0), Color::monochrome(255), 10.0);
+                    }
+                    None => {
+                        renderer.render_text("0", Vector2::new(2.0, 18.0), Color::rgb(255, 100, 100), 10.0);
+                    }
+                }
+            }
+            None => {}
+        }
+
+        renderer.render_text(
+            &(self.slot + 1).to_string(),
+            Vector2::new(22.0, 0.0),
+            *theme.button.foreground_color,
+            10.0,
+        );
+    }
+}
@@ -1,4 +1,6 @@
+use std::borrow::Cow;
 use std::cell::RefCell;
+use std::ops::Range;
 use std::rc::Rc;
 
 use cgmath::{Array, Vector2, Vector4};
@@ -15,30 +17,140 @@ pub struct InputField<const LENGTH: usize, const HIDDEN: bool = false> {
     ghost_text: &'static str,
     action: Box<dyn Fn() -> Option<ClickAction>>,
     width_constraint: DimensionConstraint,
+    /// Position of the cursor in characters.
+    #[new(default)]
+    cursor: usize,
+    /// Other end of the selection. The selection is empty while this is `None`
+    /// or at the cursor.
+    #[new(default)]
+    selection_anchor: Option<usize>,
+    #[new(default)]
+    composition: Option<String>,
     #[new(default)]
     state: ElementState,
 }
 
+fn byte_index(text: &str, character_index: usize) -> usize {
+    text.char_indices().nth(character_index).map(|(index, _)| index).unwrap_or(text.len())
+}
+
 impl<const LENGTH: usize, const HIDDEN: bool> InputField<LENGTH, HIDDEN> {
+    /// The display can be changed from outside, for example when a chat message
+    /// is sent, so the cursor is kept inside of it here.
+    fn cursor(&self, display: &str) -> usize {
+        self.cursor.min(display.chars().count())
+    }
+
+    fn selection(&self, display: &str) -> Option<Range<usize>> {
+        let cursor = self.cursor(display);
+        let anchor = self.selection_anchor?.min(display.chars().count());
+
+        (anchor != cursor).then(|| anchor.min(cursor)..anchor.max(cursor))
+    }
+
+    fn remove_range(&mut self, display: &mut String, range: Range<usize>) {
+        display.replace_range(byte_index(display, range.start)..byte_index(display, range.end), "");
+        self.cursor = range.start;
+        self.selection_anchor = None;
+    }
+
+    fn remove_selection(&mut self, display: &mut String) -> bool {
+        match self.selection(display) {
+            Some(selection) => {
+                self.remove_range(display, selection);
+                true
+            }
+            None => false,
+        }
+    }
+
     fn remove_character(&mut self) -> Option<ClickAction> {
-        let mut display = self.display.borrow_mut();
+        let display_cell = self.display.clone();
+        let mut display = display_cell.borrow_mut();
 
-        if display.is_empty() {
-            return None;
+        if !self.remove_selection(&mut display) {
+            let cursor = self.cursor(&display);
+
+            if cursor == 0 {
+                return None;
+            }
+
+            self.remove_range(&mut display, cursor - 1..cursor);
         }
 
-        display.pop();
         Some(ClickAction::ChangeEvent(ChangeEvent::RerenderWindow))
     }
 
-    fn add_character(&mut self, character: char) -> Option<ClickAction> {
-        let mut display = self.display.borrow_mut();
+    fn delete_character(&mut self) -> Option<ClickAction> {
+        let display_cell = self.display.clone();
+        let mut display = display_cell.borrow_mut();
 
-        if display.len() >= LENGTH {
-            return None;
+        if !self.remove_selection(&mut display) {
+            let cursor = self.cursor(&display);
+
+            if cursor == display.chars().count() {
+                return None;
+            }
+
+            self.remove_range(&mut display, cursor..cursor + 1);
         }
 
-        display.push(character);
+        Some(ClickAction::ChangeEvent(ChangeEvent::RerenderWindow))
+    }
+
+    fn add_text(&mut self, text: &str) -> Option<ClickAction> {
+        let display_cell = self.display.clone();
+        let mut display = display_cell.borrow_mut();
+        let removed = self.remove_selection(&mut display);
+
+        // The length is limited in bytes, so a multi-byte character is only added if
+        // it fits completely.
+        let mut available = LENGTH.saturating_sub(display.len());
+        let text: String = text
+            .chars()
+            .filter(|character| !character.is_control())
+            .take_while(|character| {
+                let fits = character.len_utf8() <= available;
+                available = available.saturating_sub(character.len_utf8());
+                fits
+            })
+            .collect();
+
+        if text.is_empty() {
+            return removed.then_some(ClickAction::ChangeEvent(ChangeEvent::RerenderWindow));
+        }
+
+        let cursor = self.cursor(&display);
+        display.insert_str(byte_index(&display, cursor), &text);
+        self.cursor = cursor + text.chars().count();
+
+        Some(ClickAction::ChangeEvent(ChangeEvent::RerenderWindow))
+    }
+
+    fn move_cursor(&mut self, edit: TextEdit, select: bool) -> Option<ClickAction> {
+        let display = self.display.borrow().clone();
+        let cursor = self.cursor(&display);
+        let length = display.chars().count();
+        let selection = self.selection(&display);
+
+        // Without shift, moving to the left or right collapses the selection to the
+        // respective side.
+        let new_cursor = match (edit, &selection, select) {
+            (TextEdit::Left, Some(selection), false) => selection.start,
+            (TextEdit::Right, Some(selection), false) => selection.end,
+            (TextEdit::Left, ..) => cursor.saturating_sub(1),
+            (TextEdit::Right, ..) => (cursor + 1).min(length),
+            (TextEdit::Home, ..) => 0,
+            (TextEdit::End, ..) => length,
+            _ => cursor,
+        };
+
+        self.selection_anchor = match select {
+            true => Some(self.selection_anchor.unwrap_or(cursor)),
+            false => None,
+        };
+        self.cursor = new_cursor;
+
         Some(ClickAction::ChangeEvent(ChangeEvent::RerenderWindow))
     }
 }
@@ -65,15 +177,72 @@ impl<const LENGTH: usize, const HIDDEN: bool> Element for InputField<LENGTH, HID
     }
 
     fn left_click(&mut self, _update: &mut bool) -> Option<ClickAction> {
+        self.cursor = self.display.borrow().chars().count();
+        self.selection_anchor = None;
         Some(ClickAction::FocusElement)
     }
 
     fn input_character(&mut self, character: char) -> Option<ClickAction> {
+        // Control characters are also sent for shortcuts like copy and paste, which are
+        // handled as key presses instead.
         match character {
-            '\u{8}' | '\u{7f}' => self.remove_character(),
+            '\u{8}' => self.remove_character(),
+            // The backspace key sends DEL on macOS. Other platforms send it for the delete
+            // key, which is already handled as a text edit.
+            #[cfg(target_os = "macos")]
+            '\u{7f}' => self.remove_character(),
             '\r' => (self.action)(),
-            character => self.add_character(character),
+            character if character.is_control() => None,
+            character => self.add_text(character.encode_utf8(&mut [0; 4])),
+        }
+    }
+
+    fn edit_text(&mut self, edit: TextEdit, select: bool) -> Option<ClickAction> {
+        match edit {
+            TextEdit::Delete => self.delete_character(),
+            TextEdit::SelectAll => {
+                self.selection_anchor = Some(0);
+                self.cursor = self.display.borrow().chars().count();
+                Some(ClickAction::ChangeEvent(ChangeEvent::RerenderWindow))
+            }
+            edit => self.move_cursor(edit, select),
+        }
+    }
+
+    fn insert_text(&mut self, text: &str) -> Option<ClickAction> {
+        self.add_text(text)
+    }
+
+    fn compose_text(&mut self, preedit: Option<String>) -> Option<ClickAction> {
+        if self.composition == preedit {
+            return None;
         }
+
+        self.composition = preedit;
+        Some(ClickAction::ChangeEvent(ChangeEvent::RerenderWindow))
+    }
+
+    fn selected_text(&self) -> Option<String> {
+        // Hidden input is never copied.
+        if HIDDEN {
+            return None;
+        }
+
+        let display = self.display.borrow();
+        let selection = self.selection(&display)?;
+
+        Some(display[byte_index(&display, selection.start)..byte_index(&display, selection.end)].to_owned())
+    }
+
+    fn caret_offset(&self, interface_settings: &InterfaceSettings, theme: &Theme) -> Option<Position> {
+        let cursor = self.cursor(&self.display.borrow());
+        let cursor_offset = *theme.input.cursor_offset * *interface_settings.scaling;
+        let character_width = *theme.input.font_size * *interface_settings.scaling * CHARACTER_WIDTH_RATIO;
+
+        Some(Vector2::new(
+            cursor_offset + cursor as f32 * character_width,
+            self.state.cached_size.y,
+        ))
     }
 
     fn render(
@@ -98,12 +267,26 @@ impl<const LENGTH: usize, const HIDDEN: bool> Element for InputField<LENGTH, HID
         let is_hovererd = self.is_element_self(hovered_element);
         let is_focused = self.is_element_self(focused_element);
 
-        let text = if display.is_empty() && !is_focused {
-            self.ghost_text.to_string()
+        let text: Cow<str> = if display.is_empty() && !is_focused {
+            Cow::Borrowed(self.ghost_text)
         } else if HIDDEN {
-            display.chars().map(|_| '*').collect()
+            Cow::Owned(display.chars().map(|_| '*').collect())
         } else {
-            display.clone()
+            Cow::Borrowed(display)
+        };
+
+        // Text that is being composed is shown at the cursor until it is committed.
+        let cursor = self.cursor(display);
+        let composition = self.composition.as_deref().filter(|_| is_focused).unwrap_or_default();
+        let composition_length = composition.chars().count();
+
+        let text = match composition.is_empty() {
+            true => text,
+            false => {
+                let mut text = text.into_owned();
+                text.insert_str(byte_index(&text, cursor), composition);
+                Cow::Owned(text)
+            }
         };
 
         let background_color = if is_hovererd {
@@ -124,16 +307,42 @@ impl<const LENGTH: usize, const HIDDEN: bool> Element for InputField<LENGTH, HID
 
         renderer.render_background(*theme.input.border_radius, background_color);
 
+        let cursor_offset = *theme.input.cursor_offset * *interface_settings.scaling;
+        let character_width = *theme.input.font_size * *interface_settings.scaling * CHARACTER_WIDTH_RATIO;
+        let character_offset = |index: usize| match index > cursor {
+            true => cursor_offset + (index + composition_length) as f32 * character_width,
+            false => cursor_offset + index as f32 * character_width,
+        };
+
+        if is_focused && let Some(selection) = self.selection(display) {
+            let start = character_offset(selection.start);
+
+            renderer.render_rectangle(
+                Vector2::new(start, 0.0),
+                Vector2::new(character_offset(selection.end) - start, self.state.cached_size.y),
+                Vector4::from_value(0.0),
+                *theme.input.selection_color,
+            );
+        }
+
         renderer.render_text(&text, Vector2::zero(), text_color, *theme.input.font_size);
 
         if is_focused {
-            let cursor_offset = *theme.input.cursor_offset * *interface_settings.scaling;
+            let composition_end = character_offset(cursor) + composition_length as f32 * character_width;
+
+            if composition_length > 0 {
+                let underline_height = *interface_settings.scaling;
+
+                renderer.render_rectangle(
+                    Vector2::new(character_offset(cursor), self.state.cached_size.y - underline_height),
+                    Vector2::new(composition_end - character_offset(cursor), underline_height),
+                    Vector4::from_value(0.0),
+                    *theme.input.focused_text_color,
+                );
+            }
 
             renderer.render_rectangle(
-                Vector2::new(
-                    cursor_offset + text.len() as f32 * *theme.input.font_size * *interface_settings.scaling * 0.5,
-                    0.0,
-                ),
+                Vector2::new(composition_end, 0.0),
                 Vector2::new(*theme.input.cursor_width, self.state.cached_size.y),
                 Vector4::from_value(0.0),
                 *theme.input.text_color,
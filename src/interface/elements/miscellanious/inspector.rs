@@ -0,0 +1,79 @@
+use cgmath::Vector2;
+use procedural::dimension;
+
+use crate::graphics::{InterfaceRenderer, Renderer};
+use crate::input::MouseInputMode;
+use crate::interface::{Element, *};
+
+/// Details about the element that is being inspected.
+pub struct InspectorView {
+    lines: Remote<Vec<String>>,
+    state: ElementState,
+}
+
+impl InspectorView {
+    pub fn new(lines: Remote<Vec<String>>) -> Self {
+        Self {
+            lines,
+            state: ElementState::default(),
+        }
+    }
+
+    pub fn wrap(self) -> ElementCell {
+        Rc::new(RefCell::new(self))
+    }
+}
+
+impl Element for InspectorView {
+    fn get_state(&self) -> &ElementState {
+        &self.state
+    }
+
+    fn get_state_mut(&mut self) -> &mut ElementState {
+        &mut self.state
+    }
+
+    fn resolve(&mut self, placement_resolver: &mut PlacementResolver, _interface_settings: &InterfaceSettings, theme: &Theme) {
+        let size_constraint = dimension!(100%).add_height(DimensionConstraint {
+            size: Dimension::Absolute(*theme.button.font_size * self.lines.borrow().len() as f32),
+            minimum_size: None,
+            maximum_size: None,
+        });
+
+        self.state.resolve(placement_resolver, &size_constraint);
+    }
+
+    fn is_focusable(&self) -> bool {
+        false
+    }
+
+    fn update(&mut self) -> Option<ChangeEvent> {
+        self.lines.consume_changed().then_some(ChangeEvent::Reresolve)
+    }
+
+    fn render(
+        &self,
+        render_target: &mut <InterfaceRenderer as Renderer>::Target,
+        renderer: &InterfaceRenderer,
+        _state_provider: &StateProvider,
+        interface_settings: &InterfaceSettings,
+        theme: &Theme,
+        parent_position: Position,
+        clip_size: ClipSize,
+        _hovered_element: Option<&dyn Element>,
+        _focused_element: Option<&dyn Element>,
+        _mouse_mode: &MouseInputMode,
+        _second_theme: bool,
+    ) {
+        let mut renderer = self
+            .state
+            .element_renderer(render_target, renderer, interface_settings, parent_position, clip_size);
+
+        let font_size = *theme.button.font_size;
+
+        for (index, line) in self.lines.borrow().iter().enumerate() {
+            let offset = Vector2::new(0.0, font_size * index as f32);
+            renderer.render_text(line, offset, *theme.button.foreground_color, font_size);
+        }
+    }
+}
@@ -3,7 +3,7 @@ use derive_new::new;
 use procedural::*;
 
 use crate::graphics::{InterfaceRenderer, Renderer};
-use crate::input::MouseInputMode;
+use crate::input::{MouseInputMode, UserEvent};
 use crate::interface::*;
 use crate::inventory::Item;
 
@@ -47,13 +47,64 @@ impl Element for ItemBox {
     }
 
     fn left_click(&mut self, _force_update: &mut bool) -> Option<ClickAction> {
-        if let Some(item) = &self.item {
+        // Offered items can't be taken back out of a trade.
+        if let Some(item) = &self.item
+            && !matches!(self.source, ItemSource::Trade | ItemSource::TradePartner)
+        {
             return Some(ClickAction::MoveItem(self.source, item.clone()));
         }
 
         None
     }
 
+    fn right_click(&mut self, _force_update: &mut bool) -> Option<ClickAction> {
+        let item = self.item.as_ref()?;
+
+        // Equipment is put on and taken off the same way as when it is dragged.
+        let move_item = |destination| {
+            UserEvent::MoveItem(ItemMove {
+                source: self.source,
+                destination,
+                item: item.clone(),
+            })
+        };
+
+        match self.source {
+            ItemSource::Equipment { .. } => Some(ClickAction::Event(move_item(ItemSource::Inventory))),
+            ItemSource::Inventory => {
+                let mut entries = Vec::new();
+
+                match ItemCategory::from_item_id(item.item_id) {
+                    // Using a card starts the card compounding flow.
+                    ItemCategory::Card => entries.push(ContextMenuEntry::action("insert card", UserEvent::SelectCard(item.index))),
+                    ItemCategory::Usable => entries.push(ContextMenuEntry::action("use", UserEvent::UseItem(item.index))),
+                    ItemCategory::Equipment => entries.push(ContextMenuEntry::action(
+                        "equip",
+                        move_item(ItemSource::Equipment {
+                            position: item.equip_position,
+                        }),
+                    )),
+                    _ => {}
+                }
+
+                entries.push(match item.amount > 1 {
+                    true => ContextMenuEntry::submenu("drop", vec![
+                        ContextMenuEntry::action("one", UserEvent::DropItem(item.index, 1)),
+                        ContextMenuEntry::action("all", UserEvent::DropItem(item.index, item.amount)),
+                    ]),
+                    false => ContextMenuEntry::action("drop", UserEvent::DropItem(item.index, 1)),
+                });
+
+                Some(ClickAction::OpenContextMenu(entries))
+            }
+            ItemSource::Hotbar { .. } | ItemSource::Trade | ItemSource::TradePartner => None,
+        }
+    }
+
+    fn tooltip(&self) -> Option<TooltipContent> {
+        self.item.clone().map(TooltipContent::Item)
+    }
+
     fn drop_item(&mut self, item_source: ItemSource, item: Item) -> Option<ItemMove> {
         Some(ItemMove {
             source: item_source,
@@ -98,13 +149,15 @@ impl Element for ItemBox {
                 Color::monochrome(255),
             );
 
-            renderer.render_text(
-                //&format!("{}", self.item.amount),
-                "1",
-                Vector2::zero(),
-                *theme.button.foreground_color,
-                8.0,
-            );
+            // Single items don't need a number, which also keeps it off of equipment.
+            if item.amount > 1 {
+                renderer.render_text(
+                    &item.amount.to_string(),
+                    Vector2::zero(),
+                    *theme.button.foreground_color,
+                    8.0,
+                );
+            }
         }
     }
 }
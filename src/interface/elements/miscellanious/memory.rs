@@ -0,0 +1,120 @@
+use std::sync::Arc;
+
+use procedural::dimension;
+
+use crate::graphics::{InterfaceRenderer, MemoryAllocator, MemoryBudget, MemoryCategory, Renderer};
+use crate::input::MouseInputMode;
+use crate::interface::{Element, *};
+
+fn format_size(bytes: u64) -> String {
+    format!("{:.2} MiB", bytes as f64 / (1024.0 * 1024.0))
+}
+
+type MemoryUsage = ([u64; MemoryCategory::ALL.len()], Option<MemoryBudget>);
+
+pub struct MemoryView {
+    memory_allocator: Arc<MemoryAllocator>,
+    usage: MemoryUsage,
+    lines: Vec<String>,
+    state: ElementState,
+}
+
+impl MemoryView {
+    pub fn new(memory_allocator: Arc<MemoryAllocator>) -> Self {
+        let usage = Self::get_usage(&memory_allocator);
+        let lines = Self::get_lines(&usage);
+
+        Self {
+            memory_allocator,
+            usage,
+            lines,
+            state: Default::default(),
+        }
+    }
+
+    pub fn wrap(self) -> ElementCell {
+        Rc::new(RefCell::new(self))
+    }
+
+    fn get_usage(memory_allocator: &MemoryAllocator) -> MemoryUsage {
+        let category_usage = MemoryCategory::ALL.map(|category| memory_allocator.category_usage(category));
+        (category_usage, memory_allocator.memory_budget())
+    }
+
+    fn get_lines((category_usage, memory_budget): &MemoryUsage) -> Vec<String> {
+        let budget_line = match *memory_budget {
+            Some(MemoryBudget { usage, budget }) => format!("budget ^FFBB00{}^000000 / {}", format_size(usage), format_size(budget)),
+            None => "budget ^FF5555unavailable".to_string(),
+        };
+
+        MemoryCategory::ALL
+            .iter()
+            .zip(category_usage)
+            .map(|(category, usage)| format!("{} ^FFBB00{}", category.name(), format_size(*usage)))
+            .chain(std::iter::once(budget_line))
+            .collect()
+    }
+}
+
+impl Element for MemoryView {
+    fn get_state(&self) -> &ElementState {
+        &self.state
+    }
+
+    fn get_state_mut(&mut self) -> &mut ElementState {
+        &mut self.state
+    }
+
+    fn resolve(&mut self, placement_resolver: &mut PlacementResolver, _interface_settings: &InterfaceSettings, theme: &Theme) {
+        let size_constraint = dimension!(100%).add_height(DimensionConstraint {
+            size: Dimension::Absolute(*theme.button.font_size * self.lines.len() as f32),
+            minimum_size: None,
+            maximum_size: None,
+        });
+
+        self.state.resolve(placement_resolver, &size_constraint);
+    }
+
+    fn is_focusable(&self) -> bool {
+        false
+    }
+
+    fn update(&mut self) -> Option<ChangeEvent> {
+        // only comparing the raw values keeps this from allocating every frame
+        let usage = Self::get_usage(&self.memory_allocator);
+
+        if usage == self.usage {
+            return None;
+        }
+
+        self.lines = Self::get_lines(&usage);
+        self.usage = usage;
+        Some(ChangeEvent::RerenderWindow)
+    }
+
+    fn render(
+        &self,
+        render_target: &mut <InterfaceRenderer as Renderer>::Target,
+        renderer: &InterfaceRenderer,
+        _state_provider: &StateProvider,
+        interface_settings: &InterfaceSettings,
+        theme: &Theme,
+        parent_position: Position,
+        clip_size: ClipSize,
+        _hovered_element: Option<&dyn Element>,
+        _focused_element: Option<&dyn Element>,
+        _mouse_mode: &MouseInputMode,
+        _second_theme: bool,
+    ) {
+        let mut renderer = self
+            .state
+            .element_renderer(render_target, renderer, interface_settings, parent_position, clip_size);
+
+        let font_size = *theme.button.font_size;
+
+        for (index, line) in self.lines.iter().enumerate() {
+            let offset = Vector2::new(0.0, font_size * index as f32);
+            renderer.render_text(line, offset, *theme.button.foreground_color, font_size);
+        }
+    }
+}
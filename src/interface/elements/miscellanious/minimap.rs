@@ -0,0 +1,243 @@
+use std::cell::Cell;
+
+use cgmath::{Array, Vector2, Vector4, Zero};
+use procedural::dimension;
+
+use crate::graphics::{Color, InterfaceRenderer, Renderer};
+use crate::interface::{Element, *};
+use crate::network::MapLink;
+use crate::world::{MinimapMarker, MinimapState};
+
+/// Factors by which the map is enlarged. The first one shows the whole map.
+const ZOOM_LEVELS: [f32; 4] = [1.0, 2.0, 4.0, 8.0];
+const MARKER_SIZE: f32 = 6.0;
+const PLAYER_COLOR: Color = Color::rgb(255, 255, 255);
+const SELECTED_COLOR: Color = Color::rgb(255, 80, 80);
+const WAYPOINT_COLOR: Color = Color::rgb(80, 255, 120);
+const BACKGROUND_COLOR: Color = Color::rgb(20, 20, 20);
+
+fn marker_color(marker: MinimapMarker) -> Color {
+    match marker {
+        MinimapMarker::PartyMember => Color::rgb(80, 200, 255),
+        MinimapMarker::Npc => Color::rgb(255, 200, 50),
+        MinimapMarker::Warp => Color::rgb(200, 80, 255),
+    }
+}
+
+/// Top-down view of the current map that follows the player when zoomed in.
+/// Scrolling changes the zoom level and clicking shows the coordinates of a
+/// tile. The waypoint is shown if it is on the current map.
+pub struct Minimap {
+    minimap_state: Remote<MinimapState>,
+    waypoint: Remote<Option<MapLink>>,
+    zoom_level: usize,
+    /// Mouse position relative to the minimap, updated while hovering it.
+    mouse_position: Cell<Position>,
+    selected_tile: Option<Vector2<usize>>,
+    state: ElementState,
+}
+
+impl Minimap {
+    pub fn new(minimap_state: Remote<MinimapState>, waypoint: Remote<Option<MapLink>>) -> Self {
+        Self {
+            minimap_state,
+            waypoint,
+            zoom_level: 0,
+            mouse_position: Cell::new(Position::zero()),
+            selected_tile: None,
+            state: ElementState::default(),
+        }
+    }
+
+    pub fn wrap(self) -> ElementCell {
+        Rc::new(RefCell::new(self))
+    }
+
+    /// Size of one tile in pixels and the part of the map in the top left
+    /// corner, in tiles from the north west corner of the map.
+    fn view(&self, minimap_state: &MinimapState) -> Option<(f32, Vector2<f32>)> {
+        if minimap_state.map_size.x == 0 || minimap_state.map_size.y == 0 {
+            return None;
+        }
+
+        let map_size = minimap_state.map_size.cast::<f32>().unwrap();
+        let size = self.state.cached_size;
+        let tile_size = (size.x / map_size.x).min(size.y / map_size.y) * ZOOM_LEVELS[self.zoom_level];
+        let visible_tiles = size / tile_size;
+        let player_position = Self::to_view(minimap_state, minimap_state.player_position);
+
+        // Follow the player but never show anything past the edge of the map. Maps
+        // that fit completely are centered instead.
+        let origin_axis = |player: f32, visible: f32, map: f32| match visible < map {
+            true => (player - visible / 2.0).clamp(0.0, map - visible),
+            false => (map - visible) / 2.0,
+        };
+
+        let origin = Vector2::new(
+            origin_axis(player_position.x, visible_tiles.x, map_size.x),
+            origin_axis(player_position.y, visible_tiles.y, map_size.y),
+        );
+
+        Some((tile_size, origin))
+    }
+
+    /// Tile positions have north pointing up, which is the opposite of the
+    /// image.
+    fn to_view(minimap_state: &MinimapState, position: Vector2<f32>) -> Vector2<f32> {
+        Vector2::new(position.x, minimap_state.map_size.y as f32 - position.y)
+    }
+
+    fn tile_center(tile: Vector2<usize>) -> Vector2<f32> {
+        Vector2::new(tile.x as f32 + 0.5, tile.y as f32 + 0.5)
+    }
+}
+
+impl Element for Minimap {
+    fn get_state(&self) -> &ElementState {
+        &self.state
+    }
+
+    fn get_state_mut(&mut self) -> &mut ElementState {
+        &mut self.state
+    }
+
+    fn resolve(&mut self, placement_resolver: &mut PlacementResolver, interface_settings: &InterfaceSettings, _theme: &Theme) {
+        // Always as high as it is wide.
+        let height_constraint = DimensionConstraint {
+            size: Dimension::Absolute(placement_resolver.get_available().x / *interface_settings.scaling),
+            minimum_size: None,
+            maximum_size: None,
+        };
+
+        self.state
+            .resolve(placement_resolver, &dimension!(100%).add_height(height_constraint));
+    }
+
+    fn update(&mut self) -> Option<ChangeEvent> {
+        // both of them need to be consumed, so no short circuiting here
+        (self.minimap_state.consume_changed() | self.waypoint.consume_changed()).then_some(ChangeEvent::RerenderWindow)
+    }
+
+    fn hovered_element(&self, mouse_position: Position, mouse_mode: &MouseInputMode) -> HoverInformation {
+        if !mouse_mode.is_none() {
+            return HoverInformation::Missed;
+        }
+
+        self.mouse_position.set(mouse_position - self.state.cached_position);
+        self.state.hovered_element(mouse_position)
+    }
+
+    fn left_click(&mut self, force_update: &mut bool) -> Option<ClickAction> {
+        let minimap_state = self.minimap_state.borrow();
+        let (tile_size, origin) = self.view(&minimap_state)?;
+        let position = origin + self.mouse_position.get() / tile_size;
+        let north = minimap_state.map_size.y as f32 - position.y;
+
+        self.selected_tile = (position.x >= 0.0 && north >= 0.0)
+            .then(|| Vector2::new(position.x as usize, north as usize))
+            .filter(|tile| tile.x < minimap_state.map_size.x && tile.y < minimap_state.map_size.y);
+
+        *force_update = true;
+        None
+    }
+
+    fn right_click(&mut self, force_update: &mut bool) -> Option<ClickAction> {
+        self.selected_tile = None;
+        *force_update = true;
+        None
+    }
+
+    fn scroll(&mut self, delta: f32) -> Option<ChangeEvent> {
+        let zoom_level = match delta > 0.0 {
+            true => (self.zoom_level + 1).min(ZOOM_LEVELS.len() - 1),
+            false => self.zoom_level.saturating_sub(1),
+        };
+
+        let changed = zoom_level != self.zoom_level;
+        self.zoom_level = zoom_level;
+        changed.then_some(ChangeEvent::RerenderWindow)
+    }
+
+    fn render(
+        &self,
+        render_target: &mut <InterfaceRenderer as Renderer>::Target,
+        renderer: &InterfaceRenderer,
+        _state_provider: &StateProvider,
+        interface_settings: &InterfaceSettings,
+        theme: &Theme,
+        parent_position: Position,
+        clip_size: ClipSize,
+        _hovered_element: Option<&dyn Element>,
+        _focused_element: Option<&dyn Element>,
+        _mouse_mode: &MouseInputMode,
+        _second_theme: bool,
+    ) {
+        let mut renderer = self
+            .state
+            .element_renderer(render_target, renderer, interface_settings, parent_position, clip_size);
+
+        renderer.render_background(Vector4::zero(), BACKGROUND_COLOR);
+
+        let minimap_state = self.minimap_state.borrow();
+        let (Some(texture), Some((tile_size, origin))) = (minimap_state.texture.clone(), self.view(&minimap_state)) else {
+            renderer.render_text(
+                "no map",
+                Vector2::new(4.0, 2.0),
+                *theme.button.foreground_color,
+                *theme.button.font_size,
+            );
+            return;
+        };
+
+        // Minimap images are square with the map in the center. Sprites are scaled by
+        // the renderer, everything else is already in pixels.
+        let scaling = *interface_settings.scaling;
+        let map_size = minimap_state.map_size.cast::<f32>().unwrap();
+        let side = map_size.x.max(map_size.y);
+        let image_offset = (map_size - Vector2::new(side, side)) / 2.0 - origin;
+        renderer.render_sprite(
+            texture,
+            image_offset * tile_size / scaling,
+            Vector2::new(side, side) * tile_size / scaling,
+            Color::monochrome(255),
+        );
+
+        let marker_size = MARKER_SIZE * scaling;
+        let mut render_marker = |position: Vector2<f32>, size: f32, color: Color| {
+            let offset = (Self::to_view(&minimap_state, position) - origin) * tile_size - Vector2::new(size, size) / 2.0;
+            renderer.render_rectangle(
+                offset,
+                Vector2::new(size, size),
+                Vector4::from_value(size / 2.0 / scaling),
+                color,
+            );
+        };
+
+        minimap_state
+            .markers
+            .iter()
+            .for_each(|(marker, position)| render_marker(Self::tile_center(*position), marker_size, marker_color(*marker)));
+
+        if let Some(tile) = self.selected_tile {
+            render_marker(Self::tile_center(tile), marker_size, SELECTED_COLOR);
+        }
+
+        if let Some(waypoint) = &*self.waypoint.borrow()
+            && waypoint.map_name == minimap_state.map_name
+        {
+            render_marker(Self::tile_center(waypoint.position), marker_size * 1.5, WAYPOINT_COLOR);
+        }
+
+        // A smaller dot in front of the player shows where the camera is looking.
+        let player_position = minimap_state.player_position;
+        let view_offset = minimap_state.view_direction * marker_size * 1.2 / tile_size;
+        render_marker(player_position, marker_size * 1.5, PLAYER_COLOR);
+        render_marker(player_position + view_offset, marker_size * 0.75, PLAYER_COLOR);
+
+        if let Some(tile) = self.selected_tile {
+            let text = format!("{}, {}", tile.x, tile.y);
+            let text_offset = Vector2::new(4.0, self.state.cached_size.y / scaling - *theme.button.font_size - 4.0);
+            renderer.render_text(&text, text_offset, *theme.button.foreground_color, *theme.button.font_size);
+        }
+    }
+}
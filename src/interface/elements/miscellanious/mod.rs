@@ -1,15 +1,53 @@
+#[cfg(feature = "debug")]
+mod allocations;
+mod bar;
+mod cast;
 mod chat;
+mod clock;
+mod cooldown;
 mod headline;
+mod hotbar;
 mod input;
+#[cfg(feature = "debug")]
+mod inspector;
 mod item;
+#[cfg(feature = "debug")]
+mod memory;
+mod minimap;
+#[cfg(feature = "debug_network")]
+mod network;
+mod preview;
+mod reveal;
+mod skill;
 mod slider;
 mod static_label;
+mod target;
 mod text;
+mod world_map;
 
+#[cfg(feature = "debug")]
+pub use self::allocations::AllocationView;
+pub use self::bar::{BarValue, ValueBar};
+pub use self::cast::CastBar;
 pub use self::chat::Chat;
+pub use self::clock::ClockDisplay;
+pub use self::cooldown::CooldownRow;
 pub use self::headline::Headline;
+pub use self::hotbar::HotbarSlot;
 pub use self::input::InputField;
+#[cfg(feature = "debug")]
+pub use self::inspector::InspectorView;
 pub use self::item::ItemBox;
+#[cfg(feature = "debug")]
+pub use self::memory::MemoryView;
+pub use self::minimap::Minimap;
+#[cfg(feature = "debug_network")]
+pub use self::network::{PacketTotalsView, TrafficGraph, TrafficMetric};
+pub use self::preview::EntityPreview;
+pub use self::reveal::RevealText;
+pub use self::skill::SkillBox;
 pub use self::slider::Slider;
 pub use self::static_label::StaticLabel;
+pub use self::target::TargetFrame;
 pub use self::text::Text;
+pub use self::world_map::WorldMap;
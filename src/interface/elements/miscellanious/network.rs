@@ -0,0 +1,268 @@
+use cgmath::{Vector2, Vector4, Zero};
+use procedural::{constraint, dimension};
+
+use crate::graphics::{Color, InterfaceRenderer, Renderer};
+use crate::input::MouseInputMode;
+use crate::interface::{Element, *};
+use crate::network::{NetworkStatistics, PacketDirection, TrafficCounter, TrafficSample};
+
+const INCOMING_COLOR: Color = Color::rgb(102, 255, 68);
+const OUTGOING_COLOR: Color = Color::rgb(255, 119, 68);
+const BACKGROUND_COLOR: Color = Color::rgb(20, 20, 20);
+const TOP_PACKET_COUNT: usize = 10;
+
+fn format_bytes(bytes: f32) -> String {
+    match bytes < 1024.0 {
+        true => format!("{} B", bytes as usize),
+        false => format!("{:.1} KiB", bytes / 1024.0),
+    }
+}
+
+/// What a [TrafficGraph] shows per second.
+#[derive(Clone, Copy)]
+pub enum TrafficMetric {
+    Bytes,
+    Packets,
+    SerializationTime,
+}
+
+impl TrafficMetric {
+    fn value(self, counter: &TrafficCounter) -> f32 {
+        match self {
+            TrafficMetric::Bytes => counter.bytes as f32,
+            TrafficMetric::Packets => counter.packets as f32,
+            TrafficMetric::SerializationTime => counter.serialization_time.as_secs_f32() * 1000.0,
+        }
+    }
+
+    fn format(self, value: f32) -> String {
+        match self {
+            TrafficMetric::Bytes => format_bytes(value),
+            TrafficMetric::Packets => format!("{}", value as usize),
+            TrafficMetric::SerializationTime => format!("{:.3} ms", value),
+        }
+    }
+}
+
+/// Bar graph of the last minute of traffic, with incoming and outgoing
+/// traffic next to each other. Every bar is one second.
+pub struct TrafficGraph {
+    statistics: Remote<NetworkStatistics>,
+    metric: TrafficMetric,
+    state: ElementState,
+}
+
+impl TrafficGraph {
+    pub fn new(statistics: Remote<NetworkStatistics>, metric: TrafficMetric) -> Self {
+        Self {
+            statistics,
+            metric,
+            state: ElementState::default(),
+        }
+    }
+
+    pub fn wrap(self) -> ElementCell {
+        Rc::new(RefCell::new(self))
+    }
+
+    fn latest_line(&self, sample: Option<&TrafficSample>) -> String {
+        let value = |direction| sample.map(|sample| self.metric.value(sample.get(direction))).unwrap_or_default();
+
+        format!(
+            "^66FF44in^000000 {}  ^FF7744out^000000 {}",
+            self.metric.format(value(PacketDirection::Incoming)),
+            self.metric.format(value(PacketDirection::Outgoing))
+        )
+    }
+}
+
+impl Element for TrafficGraph {
+    fn get_state(&self) -> &ElementState {
+        &self.state
+    }
+
+    fn get_state_mut(&mut self) -> &mut ElementState {
+        &mut self.state
+    }
+
+    fn resolve(&mut self, placement_resolver: &mut PlacementResolver, _interface_settings: &InterfaceSettings, _theme: &Theme) {
+        self.state.resolve(placement_resolver, &constraint!(100%, 70));
+    }
+
+    fn is_focusable(&self) -> bool {
+        false
+    }
+
+    fn update(&mut self) -> Option<ChangeEvent> {
+        self.statistics.consume_changed().then_some(ChangeEvent::RerenderWindow)
+    }
+
+    fn render(
+        &self,
+        render_target: &mut <InterfaceRenderer as Renderer>::Target,
+        renderer: &InterfaceRenderer,
+        _state_provider: &StateProvider,
+        interface_settings: &InterfaceSettings,
+        theme: &Theme,
+        parent_position: Position,
+        clip_size: ClipSize,
+        _hovered_element: Option<&dyn Element>,
+        _focused_element: Option<&dyn Element>,
+        _mouse_mode: &MouseInputMode,
+        _second_theme: bool,
+    ) {
+        let mut renderer = self
+            .state
+            .element_renderer(render_target, renderer, interface_settings, parent_position, clip_size);
+
+        renderer.render_background(Vector4::zero(), BACKGROUND_COLOR);
+
+        let statistics = self.statistics.borrow();
+        let samples = statistics.get_samples();
+
+        // Both directions share one scale so they can be compared at a glance.
+        let maximum = samples
+            .iter()
+            .flat_map(|sample| [&sample.incoming, &sample.outgoing])
+            .map(|counter| self.metric.value(counter))
+            .fold(0.0, f32::max);
+
+        let size = self.state.cached_size;
+        let bar_width = size.x / samples.len().max(1) as f32 / 2.0;
+
+        if maximum > 0.0 {
+            for (index, sample) in samples.iter().enumerate() {
+                for (side, direction, color) in [
+                    (0.0, PacketDirection::Incoming, INCOMING_COLOR),
+                    (1.0, PacketDirection::Outgoing, OUTGOING_COLOR),
+                ] {
+                    let height = self.metric.value(sample.get(direction)) / maximum * size.y;
+                    let offset = Vector2::new((index as f32 * 2.0 + side) * bar_width, size.y - height);
+                    renderer.render_rectangle(offset, Vector2::new(bar_width, height), Vector4::zero(), color);
+                }
+            }
+        }
+
+        let font_size = *theme.button.font_size * 0.8;
+        let foreground_color = *theme.button.foreground_color;
+        renderer.render_text(
+            &self.latest_line(samples.back()),
+            Vector2::new(2.0, 2.0),
+            foreground_color,
+            font_size,
+        );
+
+        let peak_line = format!("peak {}", self.metric.format(maximum));
+        renderer.render_text(&peak_line, Vector2::new(2.0, 2.0 + font_size), foreground_color, font_size);
+    }
+}
+
+/// Packets that used up the most bandwidth since the client started.
+pub struct PacketTotalsView {
+    statistics: Remote<NetworkStatistics>,
+    lines: Vec<String>,
+    state: ElementState,
+}
+
+impl PacketTotalsView {
+    pub fn new(statistics: Remote<NetworkStatistics>) -> Self {
+        let lines = Self::get_lines(&statistics.borrow());
+
+        Self {
+            statistics,
+            lines,
+            state: ElementState::default(),
+        }
+    }
+
+    pub fn wrap(self) -> ElementCell {
+        Rc::new(RefCell::new(self))
+    }
+
+    fn get_lines(statistics: &NetworkStatistics) -> Vec<String> {
+        let lines: Vec<String> = statistics
+            .top_packets(TOP_PACKET_COUNT)
+            .iter()
+            .map(|total| {
+                let direction = match total.direction {
+                    PacketDirection::Incoming => "^66FF44in ",
+                    PacketDirection::Outgoing => "^FF7744out",
+                };
+
+                format!(
+                    "{}^000000 0x{:02x}{:02x} {} ^FFBB00{}^000000 ({})",
+                    direction,
+                    total.header[1],
+                    total.header[0],
+                    total.name,
+                    format_bytes(total.counter.bytes as f32),
+                    total.counter.packets
+                )
+            })
+            .collect();
+
+        match lines.is_empty() {
+            true => vec!["no packets yet".to_string()],
+            false => lines,
+        }
+    }
+}
+
+impl Element for PacketTotalsView {
+    fn get_state(&self) -> &ElementState {
+        &self.state
+    }
+
+    fn get_state_mut(&mut self) -> &mut ElementState {
+        &mut self.state
+    }
+
+    fn resolve(&mut self, placement_resolver: &mut PlacementResolver, _interface_settings: &InterfaceSettings, theme: &Theme) {
+        let size_constraint = dimension!(100%).add_height(DimensionConstraint {
+            size: Dimension::Absolute(*theme.button.font_size * self.lines.len() as f32),
+            minimum_size: None,
+            maximum_size: None,
+        });
+
+        self.state.resolve(placement_resolver, &size_constraint);
+    }
+
+    fn is_focusable(&self) -> bool {
+        false
+    }
+
+    fn update(&mut self) -> Option<ChangeEvent> {
+        if !self.statistics.consume_changed() {
+            return None;
+        }
+
+        self.lines = Self::get_lines(&self.statistics.borrow());
+        Some(ChangeEvent::Reresolve)
+    }
+
+    fn render(
+        &self,
+        render_target: &mut <InterfaceRenderer as Renderer>::Target,
+        renderer: &InterfaceRenderer,
+        _state_provider: &StateProvider,
+        interface_settings: &InterfaceSettings,
+        theme: &Theme,
+        parent_position: Position,
+        clip_size: ClipSize,
+        _hovered_element: Option<&dyn Element>,
+        _focused_element: Option<&dyn Element>,
+        _mouse_mode: &MouseInputMode,
+        _second_theme: bool,
+    ) {
+        let mut renderer = self
+            .state
+            .element_renderer(render_target, renderer, interface_settings, parent_position, clip_size);
+
+        let font_size = *theme.button.font_size;
+
+        for (index, line) in self.lines.iter().enumerate() {
+            let offset = Vector2::new(0.0, font_size * index as f32);
+            renderer.render_text(line, offset, *theme.button.foreground_color, font_size);
+        }
+    }
+}
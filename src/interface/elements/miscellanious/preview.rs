@@ -0,0 +1,75 @@
+use cgmath::Vector2;
+use procedural::constraint;
+use vulkano::image::ImageAccess;
+
+use crate::graphics::{ImageBuffer, InterfaceRenderer, Renderer};
+use crate::interface::{Element, *};
+
+/// Shows the player as rendered by the
+/// [`PreviewRenderer`](crate::graphics::PreviewRenderer).
+pub struct EntityPreview {
+    image: Remote<ImageBuffer>,
+    state: ElementState,
+}
+
+impl EntityPreview {
+    pub fn new(image: Remote<ImageBuffer>) -> Self {
+        Self {
+            image,
+            state: ElementState::default(),
+        }
+    }
+
+    pub fn wrap(self) -> ElementCell {
+        Rc::new(RefCell::new(self))
+    }
+}
+
+impl Element for EntityPreview {
+    fn get_state(&self) -> &ElementState {
+        &self.state
+    }
+
+    fn get_state_mut(&mut self) -> &mut ElementState {
+        &mut self.state
+    }
+
+    fn resolve(&mut self, placement_resolver: &mut PlacementResolver, _interface_settings: &InterfaceSettings, _theme: &Theme) {
+        self.state.resolve(placement_resolver, &constraint!(100%, 150));
+    }
+
+    fn is_focusable(&self) -> bool {
+        false
+    }
+
+    fn update(&mut self) -> Option<ChangeEvent> {
+        self.image.consume_changed().then_some(ChangeEvent::RerenderWindow)
+    }
+
+    fn render(
+        &self,
+        render_target: &mut <InterfaceRenderer as Renderer>::Target,
+        renderer: &InterfaceRenderer,
+        _state_provider: &StateProvider,
+        interface_settings: &InterfaceSettings,
+        _theme: &Theme,
+        parent_position: Position,
+        clip_size: ClipSize,
+        _hovered_element: Option<&dyn Element>,
+        _focused_element: Option<&dyn Element>,
+        _mouse_mode: &MouseInputMode,
+        _second_theme: bool,
+    ) {
+        let mut renderer = self
+            .state
+            .element_renderer(render_target, renderer, interface_settings, parent_position, clip_size);
+
+        // Keep the aspect ratio of the image and center it horizontally.
+        let image = self.image.borrow().clone();
+        let image_size = image.image().dimensions().width_height();
+        let size = self.state.cached_size;
+        let width = size.y * image_size[0] as f32 / image_size[1] as f32;
+
+        renderer.render_image(image, Vector2::new((size.x - width) / 2.0, 0.0), Vector2::new(width, size.y));
+    }
+}
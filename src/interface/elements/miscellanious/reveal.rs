@@ -0,0 +1,97 @@
+use std::time::Instant;
+
+use num::Zero;
+use procedural::dimension;
+
+use crate::graphics::{Color, InterfaceRenderer, Renderer};
+use crate::interface::{Element, *};
+
+/// Number of characters revealed per second.
+const REVEAL_SPEED: f32 = 20.0;
+
+/// Text that is revealed one character at a time after the element is created,
+/// used to build up some suspense when showing results.
+pub struct RevealText {
+    text: String,
+    color: Color,
+    font_size: f32,
+    start: Instant,
+    revealed: usize,
+    state: ElementState,
+}
+
+impl RevealText {
+    pub fn new(text: String, color: Color, font_size: f32) -> Self {
+        Self {
+            text,
+            color,
+            font_size,
+            start: Instant::now(),
+            revealed: 0,
+            state: ElementState::default(),
+        }
+    }
+
+    pub fn wrap(self) -> ElementCell {
+        Rc::new(RefCell::new(self))
+    }
+}
+
+impl Element for RevealText {
+    fn get_state(&self) -> &ElementState {
+        &self.state
+    }
+
+    fn get_state_mut(&mut self) -> &mut ElementState {
+        &mut self.state
+    }
+
+    fn resolve(&mut self, placement_resolver: &mut PlacementResolver, _interface_settings: &InterfaceSettings, _theme: &Theme) {
+        let height_constraint = DimensionConstraint {
+            size: Dimension::Absolute(self.font_size),
+            minimum_size: None,
+            maximum_size: None,
+        };
+
+        self.state
+            .resolve(placement_resolver, &dimension!(100%).add_height(height_constraint));
+    }
+
+    fn is_focusable(&self) -> bool {
+        false
+    }
+
+    fn update(&mut self) -> Option<ChangeEvent> {
+        let character_count = self.text.chars().count();
+        let revealed = ((self.start.elapsed().as_secs_f32() * REVEAL_SPEED) as usize).min(character_count);
+
+        if revealed != self.revealed {
+            self.revealed = revealed;
+            return Some(ChangeEvent::RerenderWindow);
+        }
+
+        None
+    }
+
+    fn render(
+        &self,
+        render_target: &mut <InterfaceRenderer as Renderer>::Target,
+        renderer: &InterfaceRenderer,
+        _state_provider: &StateProvider,
+        interface_settings: &InterfaceSettings,
+        _theme: &Theme,
+        parent_position: Position,
+        clip_size: ClipSize,
+        _hovered_element: Option<&dyn Element>,
+        _focused_element: Option<&dyn Element>,
+        _mouse_mode: &MouseInputMode,
+        _second_theme: bool,
+    ) {
+        let mut renderer = self
+            .state
+            .element_renderer(render_target, renderer, interface_settings, parent_position, clip_size);
+
+        let text: String = self.text.chars().take(self.revealed).collect();
+        renderer.render_text(&text, Vector2::zero(), self.color, self.font_size);
+    }
+}
@@ -0,0 +1,93 @@
+use derive_new::new;
+use procedural::dimension;
+
+use crate::combat::SkillTarget;
+use crate::graphics::{InterfaceRenderer, Renderer};
+use crate::input::MouseInputMode;
+use crate::interface::*;
+use crate::network::SkillInformation;
+
+#[derive(new)]
+pub struct SkillBox {
+    skill: SkillInformation,
+    #[new(default)]
+    state: ElementState,
+}
+
+impl SkillBox {
+    pub fn wrap(self) -> ElementCell {
+        Rc::new(RefCell::new(self))
+    }
+}
+
+impl Element for SkillBox {
+    fn get_state(&self) -> &ElementState {
+        &self.state
+    }
+
+    fn get_state_mut(&mut self) -> &mut ElementState {
+        &mut self.state
+    }
+
+    fn is_focusable(&self) -> bool {
+        false
+    }
+
+    fn resolve(&mut self, placement_resolver: &mut PlacementResolver, _interface_settings: &InterfaceSettings, theme: &Theme) {
+        let size_constraint = dimension!(100%).add_height(theme.button.height_constraint);
+        self.state.resolve(placement_resolver, &size_constraint);
+    }
+
+    fn hovered_element(&self, mouse_position: Position, mouse_mode: &MouseInputMode) -> HoverInformation {
+        match mouse_mode {
+            MouseInputMode::None => self.state.hovered_element(mouse_position),
+            _ => HoverInformation::Missed,
+        }
+    }
+
+    fn left_click(&mut self, _force_update: &mut bool) -> Option<ClickAction> {
+        if SkillTarget::from_skill_type(self.skill.skill_type) == SkillTarget::Passive {
+            return None;
+        }
+
+        Some(ClickAction::MoveSkill(self.skill.clone()))
+    }
+
+    fn tooltip(&self) -> Option<TooltipContent> {
+        Some(TooltipContent::Skill(self.skill.clone()))
+    }
+
+    fn render(
+        &self,
+        render_target: &mut <InterfaceRenderer as Renderer>::Target,
+        renderer: &InterfaceRenderer,
+        _state_provider: &StateProvider,
+        interface_settings: &InterfaceSettings,
+        theme: &Theme,
+        parent_position: Position,
+        clip_size: ClipSize,
+        hovered_element: Option<&dyn Element>,
+        _focused_element: Option<&dyn Element>,
+        _mouse_mode: &MouseInputMode,
+        _second_theme: bool,
+    ) {
+        let mut renderer = self
+            .state
+            .element_renderer(render_target, renderer, interface_settings, parent_position, clip_size);
+
+        let background_color = match self.is_element_self(hovered_element) {
+            true => *theme.button.hovered_background_color,
+            false => *theme.button.background_color,
+        };
+
+        let text = format!("{} {}", self.skill.skill_name, self.skill.skill_level);
+
+        renderer.render_themed_background(&theme.button.background, *theme.button.border_radius, background_color);
+        renderer.render_text(
+            &text,
+            *theme.button.text_offset,
+            *theme.button.foreground_color,
+            *theme.button.font_size,
+        );
+    }
+}
@@ -6,6 +6,9 @@ use crate::interface::{Element, *};
 #[derive(new)]
 pub struct StaticLabel {
     label: String,
+    /// Labels that don't fit into their row show the full text as a tooltip.
+    #[new(default)]
+    truncated: bool,
     #[new(default)]
     state: ElementState,
 }
@@ -25,6 +28,18 @@ impl Element for StaticLabel {
         size_constraint.width = Dimension::Absolute(width);
 
         self.state.resolve(placement_resolver, &size_constraint);
+        self.truncated = width * *interface_settings.scaling > self.state.cached_size.x + 1.0;
+    }
+
+    fn hovered_element(&self, mouse_position: Position, _mouse_mode: &MouseInputMode) -> HoverInformation {
+        match self.truncated {
+            true => self.state.hovered_element(mouse_position),
+            false => HoverInformation::Missed,
+        }
+    }
+
+    fn tooltip(&self) -> Option<TooltipContent> {
+        self.truncated.then(|| TooltipContent::Text(vec![self.label.clone()]))
     }
 
     fn render(
@@ -0,0 +1,117 @@
+use num::Zero;
+use procedural::dimension;
+
+use crate::combat::TargetStatus;
+use crate::graphics::{Color, InterfaceRenderer, Renderer};
+use crate::interface::{Element, *};
+
+const HEALTH_COLOR: Color = Color::rgb(163, 67, 67);
+
+/// Name and health of the current target.
+pub struct TargetFrame {
+    target: Remote<Option<TargetStatus>>,
+    state: ElementState,
+}
+
+impl TargetFrame {
+    pub fn new(target: Remote<Option<TargetStatus>>) -> Self {
+        Self {
+            target,
+            state: ElementState::default(),
+        }
+    }
+
+    pub fn wrap(self) -> ElementCell {
+        Rc::new(RefCell::new(self))
+    }
+}
+
+impl Element for TargetFrame {
+    fn get_state(&self) -> &ElementState {
+        &self.state
+    }
+
+    fn get_state_mut(&mut self) -> &mut ElementState {
+        &mut self.state
+    }
+
+    fn resolve(&mut self, placement_resolver: &mut PlacementResolver, _interface_settings: &InterfaceSettings, theme: &Theme) {
+        let height_constraint = DimensionConstraint {
+            size: Dimension::Absolute(*theme.button.font_size * 2.0 + 8.0),
+            minimum_size: None,
+            maximum_size: None,
+        };
+
+        self.state
+            .resolve(placement_resolver, &dimension!(100%).add_height(height_constraint));
+    }
+
+    fn is_focusable(&self) -> bool {
+        false
+    }
+
+    fn update(&mut self) -> Option<ChangeEvent> {
+        self.target.consume_changed().then_some(ChangeEvent::RerenderWindow)
+    }
+
+    fn render(
+        &self,
+        render_target: &mut <InterfaceRenderer as Renderer>::Target,
+        renderer: &InterfaceRenderer,
+        _state_provider: &StateProvider,
+        interface_settings: &InterfaceSettings,
+        theme: &Theme,
+        parent_position: Position,
+        clip_size: ClipSize,
+        _hovered_element: Option<&dyn Element>,
+        _focused_element: Option<&dyn Element>,
+        _mouse_mode: &MouseInputMode,
+        _second_theme: bool,
+    ) {
+        let mut renderer = self
+            .state
+            .element_renderer(render_target, renderer, interface_settings, parent_position, clip_size);
+
+        let target = self.target.borrow();
+        let Some(target) = target.as_ref() else {
+            renderer.render_text("no target", Vector2::zero(), *theme.button.foreground_color, *theme.button.font_size);
+            return;
+        };
+
+        renderer.render_text(&target.name, Vector2::zero(), *theme.button.foreground_color, *theme.button.font_size);
+
+        let scaling = *interface_settings.scaling;
+        let bar_offset = Vector2::new(0.0, *theme.button.font_size + 4.0);
+        let bar_size = Vector2::new(self.state.cached_size.x, *theme.button.font_size * scaling + 4.0 * scaling);
+
+        renderer.render_rectangle(
+            bar_offset * scaling,
+            bar_size,
+            *theme.button.border_radius,
+            *theme.button.background_color,
+        );
+
+        // The server only sends the health of monsters that were damaged, and only if
+        // it is configured to do so.
+        let text = match target.maximum_health_points {
+            0 => "health unknown".to_string(),
+            maximum_health_points => {
+                let fill = (target.health_points as f32 / maximum_health_points as f32).min(1.0);
+                renderer.render_rectangle(
+                    bar_offset * scaling,
+                    Vector2::new(bar_size.x * fill, bar_size.y),
+                    *theme.button.border_radius,
+                    HEALTH_COLOR,
+                );
+                format!("{} / {}", target.health_points, maximum_health_points)
+            }
+        };
+
+        renderer.render_text(
+            &text,
+            bar_offset + Vector2::new(4.0, 2.0),
+            *theme.button.foreground_color,
+            *theme.button.font_size,
+        );
+    }
+}
@@ -0,0 +1,208 @@
+#[cfg(feature = "debug")]
+use std::cell::Cell;
+
+use cgmath::{Array, Vector2, Vector4, Zero};
+use procedural::dimension;
+
+use crate::graphics::{Color, InterfaceRenderer, Renderer};
+#[cfg(feature = "debug")]
+use crate::input::UserEvent;
+use crate::interface::{Element, *};
+use crate::loaders::WorldMapEntry;
+use crate::network::MapLink;
+
+/// Part of a cell that is taken up by the map, the rest is left for the
+/// connections.
+const MAP_FRACTION: f32 = 0.8;
+const CONNECTION_WIDTH: f32 = 2.0;
+/// Width of the frame around the map that the waypoint is on.
+const WAYPOINT_FRAME_WIDTH: f32 = 2.0;
+const MAP_COLOR: Color = Color::rgb(70, 90, 70);
+const CURRENT_MAP_COLOR: Color = Color::rgb(200, 160, 60);
+const CONNECTION_COLOR: Color = Color::rgb(120, 120, 120);
+const WAYPOINT_COLOR: Color = Color::rgb(80, 255, 120);
+const BACKGROUND_COLOR: Color = Color::rgb(20, 20, 20);
+
+/// Overview of the continent, highlighting the map that the player is on
+/// and framing the one with the waypoint. With the debug feature, clicking another map requests a warp there and
+/// clicking the current one moves the debug camera to that spot.
+pub struct WorldMap {
+    maps: Vec<WorldMapEntry>,
+    current_map: Remote<String>,
+    waypoint: Remote<Option<MapLink>>,
+    layout_size: Vector2<usize>,
+    /// Mouse position relative to the world map, updated while hovering it.
+    #[cfg(feature = "debug")]
+    mouse_position: Cell<Position>,
+    state: ElementState,
+}
+
+impl WorldMap {
+    pub fn new(maps: Vec<WorldMapEntry>, current_map: Remote<String>, waypoint: Remote<Option<MapLink>>) -> Self {
+        let layout_size = maps.iter().fold(Vector2::new(1, 1), |size, map| {
+            Vector2::new(size.x.max(map.position.x + 1), size.y.max(map.position.y + 1))
+        });
+
+        Self {
+            maps,
+            current_map,
+            waypoint,
+            layout_size,
+            #[cfg(feature = "debug")]
+            mouse_position: Cell::new(Position::zero()),
+            state: ElementState::default(),
+        }
+    }
+
+    pub fn wrap(self) -> ElementCell {
+        Rc::new(RefCell::new(self))
+    }
+
+    /// Size of a single cell of the layout in pixels.
+    fn cell_size(&self) -> f32 {
+        self.state.cached_size.x / self.layout_size.x as f32
+    }
+
+    fn cell_center(&self, position: Vector2<usize>) -> Vector2<f32> {
+        (position.cast::<f32>().unwrap() + Vector2::from_value(0.5)) * self.cell_size()
+    }
+
+    /// Returns the map below the mouse and where on it the mouse is, from 0 to
+    /// 1 with north facing up.
+    #[cfg(feature = "debug")]
+    fn map_at(&self, position: Position) -> Option<(&WorldMapEntry, Vector2<f32>)> {
+        let map_size = self.cell_size() * MAP_FRACTION;
+
+        self.maps.iter().find_map(|map| {
+            let offset = position - (self.cell_center(map.position) - Vector2::from_value(map_size / 2.0));
+            let fraction = offset / map_size;
+
+            (fraction.x >= 0.0 && fraction.x <= 1.0 && fraction.y >= 0.0 && fraction.y <= 1.0)
+                .then(|| (map, Vector2::new(fraction.x, 1.0 - fraction.y)))
+        })
+    }
+}
+
+impl Element for WorldMap {
+    fn get_state(&self) -> &ElementState {
+        &self.state
+    }
+
+    fn get_state_mut(&mut self) -> &mut ElementState {
+        &mut self.state
+    }
+
+    fn resolve(&mut self, placement_resolver: &mut PlacementResolver, interface_settings: &InterfaceSettings, _theme: &Theme) {
+        // Cells are square, so the height follows from the width.
+        let cell_size = placement_resolver.get_available().x / *interface_settings.scaling / self.layout_size.x as f32;
+        let height_constraint = DimensionConstraint {
+            size: Dimension::Absolute(cell_size * self.layout_size.y as f32),
+            minimum_size: None,
+            maximum_size: None,
+        };
+
+        self.state
+            .resolve(placement_resolver, &dimension!(100%).add_height(height_constraint));
+    }
+
+    fn update(&mut self) -> Option<ChangeEvent> {
+        // both of them need to be consumed, so no short circuiting here
+        (self.current_map.consume_changed() | self.waypoint.consume_changed()).then_some(ChangeEvent::RerenderWindow)
+    }
+
+    #[cfg(feature = "debug")]
+    fn hovered_element(&self, mouse_position: Position, mouse_mode: &MouseInputMode) -> HoverInformation {
+        if !mouse_mode.is_none() {
+            return HoverInformation::Missed;
+        }
+
+        self.mouse_position.set(mouse_position - self.state.cached_position);
+        self.state.hovered_element(mouse_position)
+    }
+
+    #[cfg(feature = "debug")]
+    fn left_click(&mut self, _force_update: &mut bool) -> Option<ClickAction> {
+        let (map, fraction) = self.map_at(self.mouse_position.get())?;
+
+        let event = match map.name == *self.current_map.borrow() {
+            true => UserEvent::TeleportDebugCamera(fraction),
+            false => UserEvent::RequestWarpToMap(format!("{}.gat", map.name), map.warp_position.unwrap_or_else(Vector2::zero)),
+        };
+
+        Some(ClickAction::Event(event))
+    }
+
+    fn render(
+        &self,
+        render_target: &mut <InterfaceRenderer as Renderer>::Target,
+        renderer: &InterfaceRenderer,
+        _state_provider: &StateProvider,
+        interface_settings: &InterfaceSettings,
+        theme: &Theme,
+        parent_position: Position,
+        clip_size: ClipSize,
+        _hovered_element: Option<&dyn Element>,
+        _focused_element: Option<&dyn Element>,
+        _mouse_mode: &MouseInputMode,
+        _second_theme: bool,
+    ) {
+        let mut renderer = self
+            .state
+            .element_renderer(render_target, renderer, interface_settings, parent_position, clip_size);
+
+        renderer.render_background(Vector4::zero(), BACKGROUND_COLOR);
+
+        let scaling = *interface_settings.scaling;
+        let connection_width = CONNECTION_WIDTH * scaling;
+
+        // Connections are drawn as a horizontal and a vertical line, so maps that
+        // are not in the same row or column still get connected.
+        for map in &self.maps {
+            let from = self.cell_center(map.position);
+
+            for neighbour in map
+                .neighbours
+                .iter()
+                .filter_map(|name| self.maps.iter().find(|other| other.name == *name))
+            {
+                let to = self.cell_center(neighbour.position);
+                let corner = Vector2::new(to.x, from.y);
+
+                let horizontal_offset = Vector2::new(from.x.min(corner.x), from.y) - Vector2::from_value(connection_width / 2.0);
+                let horizontal_size = Vector2::new((from.x - corner.x).abs() + connection_width, connection_width);
+                renderer.render_rectangle(horizontal_offset, horizontal_size, Vector4::zero(), CONNECTION_COLOR);
+
+                let vertical_offset = Vector2::new(corner.x, corner.y.min(to.y)) - Vector2::from_value(connection_width / 2.0);
+                let vertical_size = Vector2::new(connection_width, (corner.y - to.y).abs() + connection_width);
+                renderer.render_rectangle(vertical_offset, vertical_size, Vector4::zero(), CONNECTION_COLOR);
+            }
+        }
+
+        let current_map = self.current_map.borrow();
+        let waypoint = self.waypoint.borrow();
+        let map_size = self.cell_size() * MAP_FRACTION;
+        let font_size = *theme.button.font_size * 0.8;
+        let frame_width = WAYPOINT_FRAME_WIDTH * scaling;
+
+        for map in &self.maps {
+            let offset = self.cell_center(map.position) - Vector2::from_value(map_size / 2.0);
+
+            if waypoint.as_ref().is_some_and(|waypoint| waypoint.map_name == map.name) {
+                renderer.render_rectangle(
+                    offset - Vector2::from_value(frame_width),
+                    Vector2::from_value(map_size + frame_width * 2.0),
+                    *theme.button.border_radius,
+                    WAYPOINT_COLOR,
+                );
+            }
+
+            let (color, text_color) = match map.name == *current_map {
+                true => (CURRENT_MAP_COLOR, Color::monochrome(0)),
+                false => (MAP_COLOR, *theme.button.foreground_color),
+            };
+
+            renderer.render_rectangle(offset, Vector2::from_value(map_size), *theme.button.border_radius, color);
+            renderer.render_text(&map.name, offset / scaling + Vector2::new(2.0, 2.0), text_color, font_size);
+        }
+    }
+}
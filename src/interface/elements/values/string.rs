@@ -6,6 +6,9 @@ use crate::interface::{Element, *};
 #[derive(new)]
 pub struct StringValue {
     value: String,
+    /// Values that don't fit into their row show the full text as a tooltip.
+    #[new(default)]
+    truncated: bool,
     #[new(default)]
     state: ElementState,
 }
@@ -19,8 +22,24 @@ impl Element for StringValue {
         &mut self.state
     }
 
-    fn resolve(&mut self, placement_resolver: &mut PlacementResolver, _interface_settings: &InterfaceSettings, theme: &Theme) {
+    fn resolve(&mut self, placement_resolver: &mut PlacementResolver, interface_settings: &InterfaceSettings, theme: &Theme) {
         self.state.resolve(placement_resolver, &theme.value.size_constraint);
+
+        let character_width = *theme.value.font_size * CHARACTER_WIDTH_RATIO;
+        let text_width = self.value.chars().count() as f32 * character_width + theme.value.text_offset.x * 2.0;
+        let text_width = text_width * *interface_settings.scaling;
+        self.truncated = text_width > self.state.cached_size.x;
+    }
+
+    fn hovered_element(&self, mouse_position: Position, _mouse_mode: &MouseInputMode) -> HoverInformation {
+        match self.truncated {
+            true => self.state.hovered_element(mouse_position),
+            false => HoverInformation::Missed,
+        }
+    }
+
+    fn tooltip(&self) -> Option<TooltipContent> {
+        self.truncated.then(|| TooltipContent::Text(vec![self.value.clone()]))
     }
 
     fn render(
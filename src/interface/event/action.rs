@@ -1,7 +1,8 @@
 use super::ItemSource;
 use crate::input::UserEvent;
-use crate::interface::{ChangeEvent, FocusMode, PrototypeWindow};
+use crate::interface::{ChangeEvent, ContextMenuEntry, FocusMode, PrototypeWindow};
 use crate::inventory::Item;
+use crate::network::SkillInformation;
 
 pub enum ClickAction {
     FocusElement,
@@ -10,7 +11,10 @@ pub enum ClickAction {
     Event(UserEvent),
     DragElement,
     MoveItem(ItemSource, Item),
+    MoveSkill(SkillInformation),
     MoveInterface,
     OpenWindow(Box<dyn PrototypeWindow>),
+    /// Opens a context menu at the mouse.
+    OpenContextMenu(Vec<ContextMenuEntry>),
     CloseWindow,
 }
@@ -5,6 +5,9 @@ use crate::network::EquipPosition;
 pub enum ItemSource {
     Inventory,
     Equipment { position: EquipPosition },
+    Hotbar { slot: usize },
+    Trade,
+    TradePartner,
 }
 
 #[derive(Debug, Clone)]
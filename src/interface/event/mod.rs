@@ -2,8 +2,12 @@ mod action;
 mod change;
 mod hover;
 mod item;
+mod text;
+mod tooltip;
 
 pub use self::action::ClickAction;
 pub use self::change::*;
 pub use self::hover::HoverInformation;
 pub use self::item::{ItemMove, ItemSource};
+pub use self::text::TextEdit;
+pub use self::tooltip::TooltipContent;
@@ -0,0 +1,10 @@
+/// Editing operations on text input that don't insert characters.
+#[derive(Clone, Copy, Debug)]
+pub enum TextEdit {
+    Left,
+    Right,
+    Home,
+    End,
+    Delete,
+    SelectAll,
+}
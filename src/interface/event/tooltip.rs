@@ -0,0 +1,12 @@
+use crate::inventory::Item;
+use crate::network::SkillInformation;
+
+/// What an element shows in its tooltip. Items and skills are turned into
+/// text when the tooltip is rendered, since that needs game data the
+/// interface doesn't have.
+#[derive(Clone, Debug)]
+pub enum TooltipContent {
+    Text(Vec<String>),
+    Item(Item),
+    Skill(SkillInformation),
+}
@@ -0,0 +1,153 @@
+use crate::interface::*;
+
+fn format_dimension(dimension: Dimension) -> String {
+    match dimension {
+        Dimension::Relative(percentage) => format!("{}%", percentage),
+        Dimension::Absolute(value) => format!("{}", value),
+        Dimension::Remaining => "!".to_string(),
+        Dimension::Flexible => "?".to_string(),
+    }
+}
+
+fn format_dimension_constraint(size: Dimension, minimum: Option<Dimension>, maximum: Option<Dimension>) -> String {
+    let mut text = String::new();
+
+    if let Some(minimum) = minimum {
+        text += &format!("{} > ", format_dimension(minimum));
+    }
+
+    text += &format_dimension(size);
+
+    if let Some(maximum) = maximum {
+        text += &format!(" < {}", format_dimension(maximum));
+    }
+
+    text
+}
+
+/// Same notation as the constraint macro.
+fn format_constraint(constraint: &SizeConstraint) -> String {
+    format!(
+        "{}, {}",
+        format_dimension_constraint(constraint.width, constraint.minimum_width, constraint.maximum_width),
+        format_dimension_constraint(constraint.height, constraint.minimum_height, constraint.maximum_height)
+    )
+}
+
+/// Removes the module path, so `korangar::interface::Button` becomes
+/// `Button`.
+fn short_name(element: &dyn Element) -> &'static str {
+    let name = element.element_name();
+    let generics_start = name.find('<').unwrap_or(name.len());
+
+    name[..generics_start].rsplit("::").next().unwrap_or(name)
+}
+
+/// State of the element inspector. While picking, the element below the
+/// mouse is highlighted and the next click selects it instead of clicking it.
+#[derive(Default)]
+pub struct ElementInspector {
+    picking: bool,
+    inspected_element: Option<WeakElementCell>,
+    lines: TrackedState<Vec<String>>,
+}
+
+impl ElementInspector {
+    pub fn is_picking(&self) -> bool {
+        self.picking
+    }
+
+    pub fn start_picking(&mut self) {
+        self.picking = true;
+    }
+
+    pub fn pick(&mut self, element: Option<&ElementCell>) {
+        self.picking = false;
+
+        if let Some(element) = element {
+            self.inspected_element = Some(Rc::downgrade(element));
+        }
+    }
+
+    pub fn get_inspected_element(&self) -> Option<ElementCell> {
+        self.inspected_element.as_ref().and_then(Weak::upgrade)
+    }
+
+    pub fn get_lines(&self) -> Remote<Vec<String>> {
+        self.lines.new_remote()
+    }
+
+    /// Returns the top level element of the window and the names of all
+    /// elements from there down to the element.
+    pub fn parent_chain(element: &ElementCell) -> (ElementCell, Vec<&'static str>) {
+        let mut root = element.clone();
+        let mut names = vec![short_name(&*element.borrow())];
+
+        loop {
+            let parent = root.borrow().get_state().parent_element.as_ref().and_then(Weak::upgrade);
+
+            let Some(parent) = parent else {
+                break;
+            };
+
+            names.push(short_name(&*parent.borrow()));
+            root = parent;
+        }
+
+        names.reverse();
+        (root, names)
+    }
+
+    /// Position of the element relative to the window it is in, including the
+    /// scroll of any scroll views it is in.
+    pub fn offset_in_window(element: &ElementCell) -> Position {
+        let mut offset = Position::zero();
+        let mut current = Some(element.clone());
+
+        while let Some(element) = current {
+            let element = element.borrow();
+            offset += element.get_state().cached_position;
+            current = element.get_state().parent_element.as_ref().and_then(Weak::upgrade);
+
+            if let Some(parent) = &current {
+                offset += parent.borrow().content_offset();
+            }
+        }
+
+        offset
+    }
+
+    /// Updates the lines shown by the inspector window, if anything changed.
+    /// The position is where the inspected element is on the screen.
+    pub fn update(&mut self, position: Option<Position>) {
+        let lines = match (self.get_inspected_element(), position) {
+            (Some(element), Some(position)) => {
+                let (_, names) = Self::parent_chain(&element);
+                let element = element.borrow();
+                let state = element.get_state();
+
+                let mut lines = vec![
+                    format!("type ^FFBB00{}", names.last().unwrap()),
+                    format!("parents ^FFBB00{}", names[..names.len() - 1].join(" > ")),
+                    format!("position ^FFBB00{:.0}, {:.0}", position.x, position.y),
+                    format!("size ^FFBB00{:.0} x {:.0}", state.cached_size.x, state.cached_size.y),
+                ];
+
+                if let Some(constraint) = &state.resolved_constraint {
+                    lines.push(format!("constraint ^FFBB00{}", format_constraint(constraint)));
+                }
+
+                if let Some(constraint) = &state.constraint_override {
+                    lines.push(format!("override ^FF5555{}", format_constraint(constraint)));
+                }
+
+                lines
+            }
+            _ => vec!["no element selected".to_string()],
+        };
+
+        if *self.lines.borrow() != lines {
+            self.lines.set(lines);
+        }
+    }
+}
@@ -2,9 +2,42 @@ use cgmath::{Vector2, Vector4};
 use derive_new::new;
 
 pub type Size = Vector2<f32>;
-pub type ClipSize = Vector4<f32>;
 pub type Position = Vector2<f32>;
 
+/// Area that an element is allowed to draw in. `bounds` is the intersection
+/// of all parent rectangles, while `rounded_bounds` and `corner_radius`
+/// describe the closest parent with rounded corners. All values are in pixels
+/// and ordered `(left, top, right, bottom)`.
+#[derive(Copy, Clone, Debug)]
+pub struct ClipSize {
+    pub bounds: Vector4<f32>,
+    pub rounded_bounds: Vector4<f32>,
+    pub corner_radius: Vector4<f32>,
+}
+
+impl ClipSize {
+    pub fn new(position: Position, size: Size, corner_radius: Vector4<f32>) -> Self {
+        let bounds = Vector4::new(position.x, position.y, position.x + size.x, position.y + size.y);
+
+        Self {
+            bounds,
+            rounded_bounds: bounds,
+            corner_radius,
+        }
+    }
+
+    pub fn intersect(&self, position: Position, size: Size) -> Self {
+        let bounds = Vector4::new(
+            self.bounds.x.max(position.x),
+            self.bounds.y.max(position.y),
+            self.bounds.z.min(position.x + size.x),
+            self.bounds.w.min(position.y + size.y),
+        );
+
+        Self { bounds, ..*self }
+    }
+}
+
 #[derive(Copy, Clone, new)]
 pub struct PartialSize {
     pub x: f32,
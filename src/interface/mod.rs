@@ -7,28 +7,52 @@ mod theme;
 #[macro_use]
 mod elements;
 mod cursor;
+#[cfg(feature = "debug")]
+mod inspector;
 mod windows;
 
 use std::cell::RefCell;
-use std::rc::Rc;
+use std::rc::{Rc, Weak};
+use std::time::{Duration, Instant, SystemTime};
 
-use cgmath::Vector2;
+use cgmath::{Array, InnerSpace, Vector2, Vector4, Zero};
 use derive_new::new;
 
 pub use self::cursor::*;
 pub use self::elements::*;
 pub use self::event::*;
+#[cfg(feature = "debug")]
+use self::inspector::ElementInspector;
 pub use self::layout::*;
 pub use self::provider::StateProvider;
 pub use self::settings::InterfaceSettings;
 pub use self::state::{Remote, TrackedState};
-pub use self::theme::Theme;
+pub use self::theme::{Background, Gradient, GradientKind, NineSlice, Theme};
 pub use self::windows::*;
 use crate::graphics::{Color, DeferredRenderer, InterfaceRenderer, Renderer, Texture};
 use crate::input::{FocusState, MouseInputMode};
-use crate::loaders::{ActionLoader, GameFileLoader, SpriteLoader};
+use crate::loaders::{ActionLoader, GameFileLoader, SpriteLoader, TextureLoader};
 use crate::network::{ClientTick, EntityId};
 
+/// Distance between the positions that are considered when placing a new
+/// window.
+const PLACEMENT_STEP: f32 = 20.0;
+/// Distance at which a moved window snaps to the edge of the screen or of
+/// another window. Scaled with the interface.
+const WINDOW_SNAP_DISTANCE: f32 = 10.0;
+/// Time between checks if the theme file was changed on disk.
+const THEME_WATCH_INTERVAL: Duration = Duration::from_secs(1);
+/// Time in seconds that the mouse has to rest on an element before its
+/// tooltip is shown.
+const TOOLTIP_DELAY: f64 = 0.4;
+/// The fonts are monospaced and every character is half as wide as it is
+/// tall, which is used to measure text without laying it out.
+pub const CHARACTER_WIDTH_RATIO: f32 = 0.5;
+const INFO_FONT_SIZE: f32 = 12.0;
+const INFO_PADDING: f32 = 5.0;
+#[cfg(feature = "debug")]
+const INSPECTOR_HIGHLIGHT_COLOR: Color = Color::rgba(255, 187, 0, 80);
+
 #[derive(new)]
 struct DialogHandle {
     elements: TrackedState<Vec<DialogElement>>,
@@ -40,12 +64,26 @@ pub struct Interface {
     window_cache: WindowCache,
     interface_settings: InterfaceSettings,
     available_space: Size,
+    /// Area that the HUD windows were last placed in.
+    hud_area: (Position, Size),
     theme: Theme,
+    /// Modification time of the theme file when it was last loaded or saved.
+    theme_modified: Option<SystemTime>,
+    last_theme_check: Instant,
     dialog_handle: Option<DialogHandle>,
     mouse_cursor: MouseCursor,
     mouse_cursor_hidden: bool,
+    hud_edit_mode: TrackedState<bool>,
+    hud_edit_mode_remote: Remote<bool>,
+    /// The element the mouse is resting on and for how long.
+    tooltip_hover: Option<(WeakElementCell, f64)>,
     reresolve: bool,
     rerender: bool,
+    /// Set when something changed that every window might depend on, like the
+    /// theme or the mouse mode, so no window can be drawn from its cache.
+    invalidate_render_caches: bool,
+    #[cfg(feature = "debug")]
+    element_inspector: ElementInspector,
 }
 
 impl Interface {
@@ -53,46 +91,99 @@ impl Interface {
         game_file_loader: &mut GameFileLoader,
         sprite_loader: &mut SpriteLoader,
         action_loader: &mut ActionLoader,
+        texture_loader: &mut TextureLoader,
         available_space: Size,
     ) -> Self {
         let window_cache = WindowCache::new();
         let interface_settings = InterfaceSettings::new();
-        let theme = Theme::new(&interface_settings.theme_file);
+        let hud_area = hud_area(
+            available_space,
+            *interface_settings.hud_aspect_ratio,
+            *interface_settings.hud_inset,
+        );
+        let theme = Theme::new(&interface_settings.theme_file, game_file_loader, texture_loader);
+        let theme_modified = Theme::modified(&interface_settings.theme_file);
+        let last_theme_check = Instant::now();
         let dialog_handle = None;
         let mouse_cursor = MouseCursor::new(game_file_loader, sprite_loader, action_loader);
         let mouse_cursor_hidden = false;
+        let hud_edit_mode = TrackedState::new(false);
+        let hud_edit_mode_remote = hud_edit_mode.new_remote();
 
         Self {
             windows: Vec::new(),
             window_cache,
             interface_settings,
             available_space,
+            hud_area,
             theme,
+            theme_modified,
+            last_theme_check,
             dialog_handle,
             mouse_cursor,
             mouse_cursor_hidden,
+            hud_edit_mode,
+            hud_edit_mode_remote,
+            tooltip_hover: None,
             reresolve: false,
             rerender: true, // set to true initially to clear the interface buffer
+            invalidate_render_caches: false,
+            #[cfg(feature = "debug")]
+            element_inspector: ElementInspector::default(),
         }
     }
 
-    pub fn reload_theme(&mut self) {
-        if self.theme.reload(&self.interface_settings.theme_file) {
+    pub fn reload_theme(&mut self, game_file_loader: &mut GameFileLoader, texture_loader: &mut TextureLoader) {
+        self.theme_modified = Theme::modified(&self.interface_settings.theme_file);
+
+        if self
+            .theme
+            .reload(&self.interface_settings.theme_file, game_file_loader, texture_loader)
+        {
             self.reresolve = true;
         }
     }
 
-    pub fn save_theme(&self) {
+    /// Reloads the theme if the theme file was changed by another program, so
+    /// that themes can be edited without restarting the client.
+    pub fn reload_theme_if_changed(&mut self, game_file_loader: &mut GameFileLoader, texture_loader: &mut TextureLoader) {
+        if self.last_theme_check.elapsed() < THEME_WATCH_INTERVAL {
+            return;
+        }
+
+        self.last_theme_check = Instant::now();
+
+        if Theme::modified(&self.interface_settings.theme_file) != self.theme_modified {
+            self.reload_theme(game_file_loader, texture_loader);
+        }
+    }
+
+    pub fn save_theme(&mut self) {
         self.theme.save(&self.interface_settings.theme_file);
+        // Saving changes the file but shouldn't cause a reload.
+        self.theme_modified = Theme::modified(&self.interface_settings.theme_file);
+    }
+
+    /// Saves a copy of the theme next to the theme file. Returns the name of
+    /// the new file.
+    pub fn export_theme(&self) -> String {
+        let timestamp = chrono::Local::now().format("%Y-%m-%d_%H-%M-%S");
+        let theme_file = self.interface_settings.theme_file.trim_end_matches(".ron");
+        let export_file = format!("{}_{}.ron", theme_file, timestamp);
+
+        self.theme.save(&export_file);
+        export_file
     }
 
     pub fn schedule_rerender(&mut self) {
         self.rerender = true;
+        self.invalidate_render_caches = true;
     }
 
     pub fn schedule_rerender_window(&mut self, window_index: usize) {
         if window_index < self.windows.len() {
             let (window, _reresolve, rerender) = &mut self.windows[window_index];
+            window.invalidate_render_cache();
 
             match window.has_transparency(&self.theme) {
                 true => self.rerender = true,
@@ -108,27 +199,44 @@ impl Interface {
     }
 
     pub fn update(&mut self, focus_state: &mut FocusState, client_tick: ClientTick) -> (bool, bool) {
+        self.update_hud_area();
+
         self.mouse_cursor.update(client_tick);
 
+        // HUD windows are highlighted while editing, so everything needs to be rendered again.
+        self.rerender |= self.hud_edit_mode_remote.consume_changed();
+
         for (window, _reresolve, rerender) in &mut self.windows {
             if let Some(change_event) = window.update() {
                 match change_event {
                     ChangeEvent::Reresolve => self.reresolve = true,
-                    ChangeEvent::Rerender => self.rerender = true,
-                    ChangeEvent::RerenderWindow => match window.has_transparency(&self.theme) {
-                        true => self.rerender = true,
-                        false => *rerender = true,
-                    },
+                    ChangeEvent::Rerender => {
+                        self.rerender = true;
+                        self.invalidate_render_caches = true;
+                    }
+                    ChangeEvent::RerenderWindow => {
+                        window.invalidate_render_cache();
+
+                        match window.has_transparency(&self.theme) {
+                            true => self.rerender = true,
+                            false => *rerender = true,
+                        }
+                    }
                 }
             }
         }
 
+        if std::mem::take(&mut self.invalidate_render_caches) {
+            self.windows.iter_mut().for_each(|(window, ..)| window.invalidate_render_cache());
+        }
+
         let mut restore_focus = false;
 
         for (window_index, (window, reresolve, rerender)) in self.windows.iter_mut().enumerate() {
             if self.reresolve || *reresolve {
                 let (_position, previous_size) = window.get_area();
                 let (window_class, new_position, new_size) = window.resolve(&self.interface_settings, &self.theme, self.available_space);
+                window.invalidate_render_cache();
 
                 // should only ever be the last window
                 if let Some(focused_index) = focus_state.focused_window() && focused_index == window_index {
@@ -149,6 +257,25 @@ impl Interface {
             }
         }
 
+        // new windows are placed once they are resolved, since the final size might
+        // not be known before
+        for window_index in 0..self.windows.len() {
+            if !self.windows[window_index].0.needs_placement() {
+                continue;
+            }
+
+            let (_position, size) = self.windows[window_index].0.get_area();
+            let position = self.find_window_position(window_index, size);
+            let (window, ..) = &mut self.windows[window_index];
+
+            if let Some((window_class, position)) = window.place(self.available_space, position) {
+                self.window_cache.update_position(window_class, position);
+            }
+
+            self.update_hud_anchor(window_index);
+            self.rerender = true;
+        }
+
         if restore_focus {
             self.restore_focus(focus_state);
         }
@@ -156,6 +283,10 @@ impl Interface {
         self.rerender |= self.reresolve;
         self.reresolve = false;
 
+        #[cfg(feature = "debug")]
+        self.element_inspector
+            .update(self.inspected_element_area().map(|(position, _size)| position));
+
         if !self.rerender {
             self.flag_rerender_windows(0, None);
         }
@@ -167,10 +298,94 @@ impl Interface {
     }
 
     pub fn update_window_size(&mut self, screen_size: Size) {
-        self.available_space = screen_size;
+        let previous_space = std::mem::replace(&mut self.available_space, screen_size);
+
+        // Minimizing shrinks the screen to nothing, which should not move any windows.
+        let has_area = |space: Size| space.x > 0.0 && space.y > 0.0;
+
+        if previous_space != screen_size && has_area(previous_space) && has_area(screen_size) {
+            self.window_cache.reanchor_windows(previous_space, screen_size);
+            // HUD windows are moved along with the HUD area instead.
+            self.windows
+                .iter_mut()
+                .filter(|(window, ..)| !window.is_hud())
+                .for_each(|(window, ..)| window.reanchor(previous_space, screen_size));
+        }
+
+        self.reresolve = true;
+    }
+
+    /// Moves the HUD windows along with the HUD area, which changes with the
+    /// screen size, the HUD aspect ratio and the inset.
+    fn update_hud_area(&mut self) {
+        let new_area = hud_area(
+            self.available_space,
+            *self.interface_settings.hud_aspect_ratio,
+            *self.interface_settings.hud_inset,
+        );
+        let (_position, new_size) = new_area;
+
+        if new_area == self.hud_area || new_size.x <= 0.0 || new_size.y <= 0.0 {
+            return;
+        }
+
+        let previous_area = std::mem::replace(&mut self.hud_area, new_area);
+
+        for (window, ..) in self.windows.iter_mut().filter(|(window, ..)| window.is_hud()) {
+            let (position, size) = window.get_area();
+            let cached_anchor = window
+                .get_window_class()
+                .and_then(|window_class| self.window_cache.get_hud_anchor(window_class));
+
+            // Windows that were never moved since anchors were introduced use the
+            // closest one.
+            let hud_anchor = cached_anchor.unwrap_or_else(|| {
+                let hud_anchor = HudAnchor::closest(position, size, previous_area);
+
+                if let Some(window_class) = window.get_window_class() {
+                    self.window_cache.update_hud_anchor(window_class, hud_anchor);
+                }
+
+                hud_anchor
+            });
+
+            window.reanchor_hud(hud_anchor, previous_area, new_area);
+        }
+
+        self.window_cache.reanchor_hud_windows(previous_area, new_area);
         self.reresolve = true;
     }
 
+    /// HUD windows are attached to the closest anchor of the HUD area whenever
+    /// they are moved, resized or placed.
+    fn update_hud_anchor(&mut self, window_index: usize) {
+        let window = &self.windows[window_index].0;
+
+        if !window.is_hud() {
+            return;
+        }
+
+        if let Some(window_class) = window.get_window_class() {
+            let (position, size) = window.get_area();
+            let hud_anchor = HudAnchor::closest(position, size, self.hud_area);
+            self.window_cache.update_hud_anchor(window_class, hud_anchor);
+        }
+    }
+
+    /// Pulls every window back on the screen, including the ones that are not
+    /// open right now.
+    pub fn move_windows_on_screen(&mut self) {
+        self.window_cache.move_windows_on_screen(self.available_space);
+
+        for (window, ..) in &mut self.windows {
+            if let Some((window_class, position)) = window.move_on_screen(self.available_space) {
+                self.window_cache.update_position(window_class, position);
+            }
+        }
+
+        self.rerender = true;
+    }
+
     pub fn hovered_element(&self, mouse_position: Position, mouse_mode: &MouseInputMode) -> (Option<ElementCell>, Option<usize>) {
         for (window_index, (window, _reresolve, _rerender)) in self.windows.iter().enumerate().rev() {
             match window.hovered_element(mouse_position, mouse_mode) {
@@ -183,12 +398,25 @@ impl Interface {
         (None, None)
     }
 
+    /// Modal windows are always at the end of the window stack, so any other
+    /// window can only be moved up to the first modal one.
+    fn top_window_index(&self, modal: bool) -> usize {
+        match modal {
+            true => self.windows.len(),
+            false => self
+                .windows
+                .iter()
+                .position(|(window, ..)| window.is_modal())
+                .unwrap_or(self.windows.len()),
+        }
+    }
+
     pub fn move_window_to_top(&mut self, window_index: usize) -> usize {
         let (window, reresolve, _rerender) = self.windows.remove(window_index);
-        let new_window_index = self.windows.len();
+        let new_window_index = self.top_window_index(window.is_modal());
         let has_transparency = window.has_transparency(&self.theme);
 
-        self.windows.push((window, reresolve, !has_transparency));
+        self.windows.insert(new_window_index, (window, reresolve, !has_transparency));
         self.rerender |= has_transparency;
 
         new_window_index
@@ -206,43 +434,108 @@ impl Interface {
         // for input character ?
     }
 
-    pub fn drag_element(&mut self, element: &ElementCell, _window_index: usize, mouse_delta: Position) {
-        //let (_window, _reresolve, _rerender) = &mut self.windows[window_index];
+    pub fn drag_element(&mut self, element: &ElementCell, window_index: usize, mouse_delta: Position) {
+        let (window, _, rerender) = &mut self.windows[window_index];
 
         if let Some(change_event) = element.borrow_mut().drag(mouse_delta) {
             match change_event {
                 ChangeEvent::Reresolve => self.reresolve = true,
-                ChangeEvent::Rerender => self.rerender = true,
-                ChangeEvent::RerenderWindow => panic!(),
+                ChangeEvent::Rerender => {
+                    self.rerender = true;
+                    self.invalidate_render_caches = true;
+                }
+                ChangeEvent::RerenderWindow => {
+                    window.invalidate_render_cache();
+                    *rerender = true;
+                }
             }
         }
     }
 
     pub fn scroll_element(&mut self, element: &ElementCell, window_index: usize, scroll_delta: f32) {
-        let (_, _, rerender) = &mut self.windows[window_index];
+        let (window, _, rerender) = &mut self.windows[window_index];
 
         if let Some(change_event) = element.borrow_mut().scroll(scroll_delta) {
             match change_event {
                 ChangeEvent::Reresolve => self.reresolve = true,
-                ChangeEvent::Rerender => self.rerender = true,
-                ChangeEvent::RerenderWindow => *rerender = true,
+                ChangeEvent::Rerender => {
+                    self.rerender = true;
+                    self.invalidate_render_caches = true;
+                }
+                ChangeEvent::RerenderWindow => {
+                    window.invalidate_render_cache();
+                    *rerender = true;
+                }
+            }
+        }
+    }
+
+    /// Looks up the window of the element, since windows can be reordered or
+    /// closed while autoscrolling.
+    pub fn autoscroll_element(&mut self, element: &ElementCell, distance: f32) {
+        let Some(window_index) = self.window_index_of(element) else {
+            return;
+        };
+
+        let (window, _, rerender) = &mut self.windows[window_index];
+
+        if let Some(change_event) = element.borrow_mut().autoscroll(distance) {
+            match change_event {
+                ChangeEvent::Reresolve => self.reresolve = true,
+                ChangeEvent::Rerender => {
+                    self.rerender = true;
+                    self.invalidate_render_caches = true;
+                }
+                ChangeEvent::RerenderWindow => {
+                    window.invalidate_render_cache();
+                    *rerender = true;
+                }
             }
         }
     }
 
     pub fn input_character_element(&mut self, element: &ElementCell, window_index: usize, character: char) -> Option<ClickAction> {
+        let click_action = element.borrow_mut().input_character(character);
+        self.text_input_action(window_index, click_action)
+    }
+
+    pub fn edit_text_element(&mut self, element: &ElementCell, window_index: usize, edit: TextEdit, select: bool) -> Option<ClickAction> {
+        let click_action = element.borrow_mut().edit_text(edit, select);
+        self.text_input_action(window_index, click_action)
+    }
+
+    pub fn insert_text_element(&mut self, element: &ElementCell, window_index: usize, text: &str) -> Option<ClickAction> {
+        let click_action = element.borrow_mut().insert_text(text);
+        self.text_input_action(window_index, click_action)
+    }
+
+    pub fn compose_text_element(&mut self, element: &ElementCell, window_index: usize, preedit: Option<String>) -> Option<ClickAction> {
+        let click_action = element.borrow_mut().compose_text(preedit);
+        self.text_input_action(window_index, click_action)
+    }
+
+    /// Apply change events of text input right away and return all other
+    /// actions.
+    fn text_input_action(&mut self, window_index: usize, click_action: Option<ClickAction>) -> Option<ClickAction> {
         let (window, _reresolve, rerender) = &mut self.windows[window_index];
         let has_transparency = window.has_transparency(&self.theme);
 
-        if let Some(click_event) = element.borrow_mut().input_character(character) {
+        if let Some(click_event) = click_action {
             match click_event {
                 ClickAction::ChangeEvent(change_event) => match change_event {
                     ChangeEvent::Reresolve => self.reresolve = true,
-                    ChangeEvent::Rerender => self.rerender = true,
-                    ChangeEvent::RerenderWindow => match has_transparency {
-                        true => self.rerender = true,
-                        false => *rerender = true,
-                    },
+                    ChangeEvent::Rerender => {
+                        self.rerender = true;
+                        self.invalidate_render_caches = true;
+                    }
+                    ChangeEvent::RerenderWindow => {
+                        window.invalidate_render_cache();
+
+                        match has_transparency {
+                            true => self.rerender = true,
+                            false => *rerender = true,
+                        }
+                    }
                 },
                 other => return Some(other),
             }
@@ -251,19 +544,149 @@ impl Interface {
         None
     }
 
+    pub fn get_hud_edit_mode_state(&self) -> TrackedState<bool> {
+        self.hud_edit_mode.clone()
+    }
+
+    pub fn is_hud_window(&self, window_index: usize) -> bool {
+        self.windows[window_index].0.is_hud()
+    }
+
+    pub fn is_popup_window(&self, window_index: usize) -> bool {
+        self.windows[window_index].0.is_popup()
+    }
+
+    pub fn is_hud_edit_mode(&self) -> bool {
+        *self.hud_edit_mode.borrow()
+    }
+
+    /// HUD windows snap to the grid while they are being edited.
+    fn grid_size(&self, window_index: usize) -> Option<f32> {
+        (self.is_hud_edit_mode() && self.is_hud_window(window_index))
+            .then(|| *self.interface_settings.hud_grid_size * *self.interface_settings.scaling)
+    }
+
     pub fn move_window(&mut self, window_index: usize, offset: Position) {
-        if let Some((window_class, position)) = self.windows[window_index].0.offset(self.available_space, offset) {
-            self.window_cache.update_position(window_class, position);
+        let (previous_position, _size) = self.windows[window_index].0.get_area();
+        let docked_windows = self.docked_windows(window_index);
+
+        match self.grid_size(window_index) {
+            Some(grid_size) => {
+                if let Some((window_class, position)) = self.windows[window_index]
+                    .0
+                    .offset(self.available_space, offset, Some(grid_size))
+                {
+                    self.window_cache.update_position(window_class, position);
+                }
+            }
+            None => self.move_window_snapped(window_index, offset, &docked_windows),
+        }
+
+        // Windows docked to the moved window follow it.
+        let (new_position, _size) = self.windows[window_index].0.get_area();
+        let difference = new_position - previous_position;
+
+        for docked_index in docked_windows {
+            if let Some((window_class, position)) = self.windows[docked_index].0.offset(self.available_space, difference, None) {
+                self.window_cache.update_position(window_class, position);
+            }
+
+            self.update_hud_anchor(docked_index);
         }
 
+        self.update_hud_anchor(window_index);
         self.rerender = true;
     }
 
+    pub fn end_window_move(&mut self, window_index: usize) {
+        if let Some((window, ..)) = self.windows.get_mut(window_index) {
+            window.end_move();
+        }
+    }
+
+    fn move_window_snapped(&mut self, window_index: usize, offset: Position, docked_windows: &[usize]) {
+        // Windows docked to the moved window are moved with it, so they can't be
+        // snapped to.
+        let (area_indices, areas): (Vec<usize>, Vec<(Position, Size)>) = self
+            .windows
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| *index != window_index && !docked_windows.contains(index))
+            .map(|(index, (window, ..))| (index, window.get_area()))
+            .unzip();
+
+        let snap_distance = WINDOW_SNAP_DISTANCE * *self.interface_settings.scaling;
+        let (position, anchor) = self.windows[window_index]
+            .0
+            .offset_snapped(self.available_space, offset, &areas, snap_distance);
+
+        let Some(window_class) = self.windows[window_index].0.get_window_class() else {
+            return;
+        };
+
+        let docked_to = anchor.and_then(|anchor| {
+            let (anchor_window, ..) = &self.windows[area_indices[anchor]];
+            let (anchor_position, _size) = anchor_window.get_area();
+
+            anchor_window.get_window_class().map(|anchor_class| WindowDock {
+                window_class: anchor_class.to_string(),
+                offset: position - anchor_position,
+            })
+        });
+
+        self.window_cache.update_position(window_class, position);
+        self.window_cache.update_dock(window_class, docked_to);
+    }
+
+    /// Indices of all open windows that are docked to the given window.
+    fn docked_windows(&self, window_index: usize) -> Vec<usize> {
+        let Some(anchor_class) = self.windows[window_index].0.get_window_class() else {
+            return Vec::new();
+        };
+
+        self.windows
+            .iter()
+            .enumerate()
+            .filter(|(index, (window, ..))| {
+                *index != window_index
+                    && window
+                        .get_window_class()
+                        .and_then(|window_class| self.window_cache.get_dock(window_class))
+                        .is_some_and(|dock| dock.window_class == anchor_class)
+            })
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    pub fn set_window_opacity(&mut self, window_class: &str, opacity: f32) {
+        self.window_cache.update_opacity(window_class, opacity);
+
+        if let Some((window, ..)) = self.find_window_mut(window_class) {
+            window.set_opacity(opacity);
+            self.rerender = true;
+        }
+    }
+
+    pub fn toggle_window_click_through(&mut self, window_class: &str) {
+        if let Some((window, ..)) = self.find_window_mut(window_class) {
+            let click_through = !window.is_click_through();
+            window.set_click_through(click_through);
+            self.window_cache.update_click_through(window_class, click_through);
+        }
+    }
+
+    fn find_window_mut(&mut self, window_class: &str) -> Option<&mut (Window, bool, bool)> {
+        self.windows
+            .iter_mut()
+            .find(|(window, ..)| window.get_window_class() == Some(window_class))
+    }
+
     pub fn resize_window(&mut self, window_index: usize, growth: Size) {
+        let grid_size = self.grid_size(window_index);
         let (window, reresolve, _rerender) = &mut self.windows[window_index];
 
         let (_position, previous_size) = window.get_area();
-        let (window_class, new_size) = window.resize(&self.interface_settings, &self.theme, self.available_space, growth);
+        let (window_class, new_size) = window.resize(&self.interface_settings, &self.theme, self.available_space, growth, grid_size);
 
         if previous_size != new_size {
             if let Some(window_class) = window_class {
@@ -272,6 +695,7 @@ impl Interface {
 
             *reresolve = true;
             self.rerender |= previous_size.x > new_size.x || previous_size.y > new_size.y;
+            self.update_hud_anchor(window_index);
         }
     }
 
@@ -306,12 +730,60 @@ impl Interface {
         hovered_element: Option<ElementCell>,
         focused_element: Option<ElementCell>,
         mouse_mode: &MouseInputMode,
+        #[cfg(feature = "debug")] cache_windows: bool,
     ) {
         let hovered_element = hovered_element.map(|element| unsafe { &*element.as_ptr() });
         let focused_element = focused_element.map(|element| unsafe { &*element.as_ptr() });
+        let hud_edit_mode = self.is_hud_edit_mode();
+
+        // Shade the parts of the screen outside of the HUD area while editing the
+        // layout, so it is clear where HUD windows will be anchored.
+        if self.rerender && hud_edit_mode {
+            let (hud_position, hud_size) = self.hud_area;
+            let side_areas = [
+                (Position::zero(), Size::new(hud_position.x, self.available_space.y)),
+                (
+                    Position::new(hud_position.x + hud_size.x, 0.0),
+                    Size::new(self.available_space.x - hud_position.x - hud_size.x, self.available_space.y),
+                ),
+            ];
+
+            for (position, size) in side_areas.into_iter().filter(|(_, size)| size.x > 0.0) {
+                let clip_size = ClipSize::new(Position::zero(), self.available_space, Vector4::zero());
+                renderer.render_rectangle(
+                    render_target,
+                    position,
+                    size,
+                    clip_size,
+                    Vector4::zero(),
+                    Color::rgba(255, 200, 50, 20),
+                );
+            }
+        }
 
         for (window, _reresolve, rerender) in &mut self.windows {
             if self.rerender || *rerender {
+                // Transparent windows show what is behind them, so only opaque ones can
+                // be cached.
+                let cacheable = !hud_edit_mode && !window.has_transparency(&self.theme);
+                // Turning the cache off in debug builds allows comparing frame times with
+                // and without it.
+                #[cfg(feature = "debug")]
+                let cacheable = cacheable && cache_windows;
+
+                if cacheable
+                    && window.render_cached(
+                        render_target,
+                        renderer,
+                        &self.interface_settings,
+                        &self.theme,
+                        self.available_space,
+                    )
+                {
+                    *rerender = false;
+                    continue;
+                }
+
                 window.render(
                     render_target,
                     renderer,
@@ -321,7 +793,15 @@ impl Interface {
                     hovered_element,
                     focused_element,
                     mouse_mode,
+                    hud_edit_mode,
                 );
+
+                // Caches are only created when the whole interface is rendered again,
+                // so windows that change every frame don't pay for the copy.
+                if cacheable && self.rerender {
+                    window.update_render_cache(render_target, renderer, self.available_space);
+                }
+
                 *rerender = false;
             }
         }
@@ -347,6 +827,99 @@ impl Interface {
         renderer.render_text(render_target, text, mouse_position + offset, Color::monochrome(255), 12.0); // move variables into theme
     }
 
+    /// Tracks how long the hovered element has been hovered and returns its
+    /// tooltip once the mouse rested on it long enough.
+    pub fn update_tooltip(&mut self, hovered_element: Option<&ElementCell>, delta_time: f64) -> Option<TooltipContent> {
+        let Some(hovered_element) = hovered_element else {
+            self.tooltip_hover = None;
+            return None;
+        };
+
+        let hover_time = match &self.tooltip_hover {
+            Some((element, hover_time)) if Weak::ptr_eq(element, &Rc::downgrade(hovered_element)) => hover_time + delta_time,
+            _ => 0.0,
+        };
+
+        self.tooltip_hover = Some((Rc::downgrade(hovered_element), hover_time));
+
+        match hover_time >= TOOLTIP_DELAY {
+            true => hovered_element.borrow().tooltip(),
+            false => None,
+        }
+    }
+
+    fn info_card_size(lines: &[String]) -> Size {
+        let longest_line = lines.iter().map(|line| line.chars().count()).max().unwrap_or_default();
+
+        Vector2::new(
+            longest_line as f32 * INFO_FONT_SIZE * CHARACTER_WIDTH_RATIO + INFO_PADDING * 2.0,
+            lines.len() as f32 * INFO_FONT_SIZE + INFO_PADDING * 2.0,
+        )
+    }
+
+    fn render_info_card(
+        &self,
+        render_target: &mut <DeferredRenderer as Renderer>::Target,
+        renderer: &DeferredRenderer,
+        lines: &[String],
+        position: Position,
+    ) {
+        let size = Self::info_card_size(lines);
+
+        renderer.render_rectangle(render_target, position, size, Color::rgba(20, 20, 20, 200)); // move variables into theme
+
+        for (index, line) in lines.iter().enumerate() {
+            let line_position = position + Vector2::new(INFO_PADDING, INFO_PADDING + index as f32 * INFO_FONT_SIZE);
+            renderer.render_text(render_target, line, line_position, Color::monochrome(230), INFO_FONT_SIZE); // move variables into theme
+        }
+    }
+
+    pub fn render_entity_info(
+        &self,
+        render_target: &mut <DeferredRenderer as Renderer>::Target,
+        renderer: &DeferredRenderer,
+        lines: &[String],
+        mouse_position: Position,
+    ) {
+        if lines.is_empty() {
+            return;
+        }
+
+        // keep the card out from under the cursor and the hover text
+        let position = mouse_position + Vector2::new(20.0, 35.0);
+        self.render_info_card(render_target, renderer, lines, position);
+    }
+
+    /// Renders a tooltip next to the mouse. It is flipped to the other side of
+    /// the cursor where it would leave the screen.
+    pub fn render_tooltip(
+        &self,
+        render_target: &mut <DeferredRenderer as Renderer>::Target,
+        renderer: &DeferredRenderer,
+        lines: &[String],
+        mouse_position: Position,
+    ) {
+        const CURSOR_OFFSET: f32 = 20.0;
+
+        if lines.is_empty() {
+            return;
+        }
+
+        let size = Self::info_card_size(lines);
+        let mut position = mouse_position + Vector2::from_value(CURSOR_OFFSET);
+
+        if position.x + size.x > self.available_space.x {
+            position.x = mouse_position.x - CURSOR_OFFSET - size.x;
+        }
+
+        if position.y + size.y > self.available_space.y {
+            position.y = mouse_position.y - CURSOR_OFFSET - size.y;
+        }
+
+        let position = Vector2::new(position.x.max(0.0), position.y.max(0.0));
+        self.render_info_card(render_target, renderer, lines, position);
+    }
+
     #[cfg(feature = "debug")]
     pub fn render_frames_per_second(
         &self,
@@ -382,7 +955,7 @@ impl Interface {
         }
     }
 
-    fn window_exists(&self, window_class: Option<&str>) -> bool {
+    pub fn window_exists(&self, window_class: Option<&str>) -> bool {
         match window_class {
             Some(window_class) => self.windows.iter().any(|window| {
                 window
@@ -394,8 +967,194 @@ impl Interface {
         }
     }
 
-    fn open_new_window(&mut self, focus_state: &mut FocusState, window: Window) {
-        self.windows.push((window, true, true));
+    /// Classes of all open windows, from the bottom to the top.
+    #[cfg(feature = "debug")]
+    pub fn get_window_classes(&self) -> Vec<String> {
+        self.windows
+            .iter()
+            .filter_map(|window| window.0.get_window_class())
+            .map(str::to_owned)
+            .collect()
+    }
+
+    #[cfg(feature = "debug")]
+    pub fn get_inspector_lines(&self) -> Remote<Vec<String>> {
+        self.element_inspector.get_lines()
+    }
+
+    #[cfg(feature = "debug")]
+    pub fn start_picking_element(&mut self) {
+        self.element_inspector.start_picking();
+    }
+
+    /// Selects the element for the inspector if it is waiting for one. Returns
+    /// true if the click was used for that, so it can be ignored otherwise.
+    #[cfg(feature = "debug")]
+    pub fn pick_element(&mut self, hovered_element: Option<&ElementCell>) -> bool {
+        if !self.element_inspector.is_picking() {
+            return false;
+        }
+
+        self.element_inspector.pick(hovered_element);
+        true
+    }
+
+    /// Overrides the constraint of the inspected element with a fixed size
+    /// that is `growth` bigger than its current one. Like the constraints,
+    /// `growth` is not scaled.
+    #[cfg(feature = "debug")]
+    pub fn tweak_inspected_element(&mut self, growth: Size) {
+        let Some(element) = self.element_inspector.get_inspected_element() else {
+            return;
+        };
+
+        let scaling = *self.interface_settings.scaling;
+        let mut element = element.borrow_mut();
+        let state = element.get_state_mut();
+        let size = state.cached_size / scaling + growth;
+
+        // Minimum and maximum are left out, since they could undo the change.
+        state.constraint_override = Some(SizeConstraint::new(
+            Dimension::Absolute(size.x.max(0.0)),
+            None,
+            None,
+            Dimension::Absolute(size.y.max(0.0)),
+            None,
+            None,
+        ));
+        self.reresolve = true;
+    }
+
+    #[cfg(feature = "debug")]
+    pub fn reset_inspected_element(&mut self) {
+        if let Some(element) = self.element_inspector.get_inspected_element() {
+            element.borrow_mut().get_state_mut().constraint_override = None;
+            self.reresolve = true;
+        }
+    }
+
+    /// Area of the inspected element on the screen.
+    #[cfg(feature = "debug")]
+    fn inspected_element_area(&self) -> Option<(Position, Size)> {
+        let element = self.element_inspector.get_inspected_element()?;
+        self.element_area(&element)
+    }
+
+    /// Index of the window that the element is in, if that window is still
+    /// open.
+    fn window_index_of(&self, element: &ElementCell) -> Option<usize> {
+        let (root, _) = ElementInspector::parent_chain(element);
+        self.windows.iter().position(|(window, ..)| window.has_element(&root))
+    }
+
+    /// Area of any element on the screen.
+    fn element_area(&self, element: &ElementCell) -> Option<(Position, Size)> {
+        let window_index = self.window_index_of(element)?;
+        let (window, ..) = &self.windows[window_index];
+        let (window_position, _size) = window.get_area();
+        let position = window_position + ElementInspector::offset_in_window(element);
+
+        Some((position, element.borrow().get_state().cached_size))
+    }
+
+    /// Position of the text cursor of the element on the screen, if it has
+    /// one.
+    pub fn caret_position(&self, element: &ElementCell) -> Option<Position> {
+        let caret_offset = element.borrow().caret_offset(&self.interface_settings, &self.theme)?;
+        let (position, _size) = self.element_area(element)?;
+
+        Some(position + caret_offset)
+    }
+
+    /// Highlights the element below the mouse while picking, and the
+    /// inspected element otherwise.
+    #[cfg(feature = "debug")]
+    pub fn render_inspector_highlight(
+        &self,
+        render_target: &mut <DeferredRenderer as Renderer>::Target,
+        renderer: &DeferredRenderer,
+        mouse_position: Position,
+        mouse_mode: &MouseInputMode,
+    ) {
+        let area = match self.element_inspector.is_picking() {
+            true => self
+                .hovered_element(mouse_position, mouse_mode)
+                .0
+                .and_then(|element| self.element_area(&element)),
+            false => self.inspected_element_area(),
+        };
+
+        if let Some((position, size)) = area {
+            renderer.render_rectangle(render_target, position, size, INSPECTOR_HIGHLIGHT_COLOR);
+        }
+    }
+
+    /// Find a position for a new window that covers as little as possible of
+    /// the player (who is always in the center of the screen) and the other
+    /// windows, preferring positions close to the center.
+    fn find_window_position(&self, window_index: usize, size: Size) -> Position {
+        let player_size = Vector2::new(160.0, 240.0) * *self.interface_settings.scaling;
+        let player_area = ((self.available_space - player_size) / 2.0, player_size);
+        let centered = (self.available_space - size) / 2.0;
+
+        let overlap = |position: Position, (other_position, other_size): (Position, Size)| {
+            let width = (position.x + size.x).min(other_position.x + other_size.x) - position.x.max(other_position.x);
+            let height = (position.y + size.y).min(other_position.y + other_size.y) - position.y.max(other_position.y);
+            width.max(0.0) * height.max(0.0)
+        };
+
+        let columns = ((self.available_space.x - size.x) / PLACEMENT_STEP).max(0.0) as usize;
+        let rows = ((self.available_space.y - size.y) / PLACEMENT_STEP).max(0.0) as usize;
+
+        let grid_positions =
+            (0..=columns).flat_map(|column| (0..=rows).map(move |row| Vector2::new(column as f32, row as f32) * PLACEMENT_STEP));
+
+        std::iter::once(centered)
+            .chain(grid_positions)
+            .map(|position| {
+                let covered_area = overlap(position, player_area)
+                    + self
+                        .windows
+                        .iter()
+                        .enumerate()
+                        .filter(|(index, _)| *index != window_index)
+                        .map(|(_, (window, ..))| overlap(position, window.get_area()))
+                        .sum::<f32>();
+                let distance = (position - centered).magnitude2();
+
+                (position, covered_area, distance)
+            })
+            .min_by(|(_, area, distance), (_, other_area, other_distance)| {
+                area.total_cmp(other_area).then(distance.total_cmp(other_distance))
+            })
+            .map(|(position, ..)| position)
+            .unwrap_or(centered)
+    }
+
+    fn open_new_window(&mut self, focus_state: &mut FocusState, mut window: Window) {
+        // Windows that were docked to another window are placed next to it again.
+        let docked_position = window
+            .get_window_class()
+            .and_then(|window_class| self.window_cache.get_dock(window_class))
+            .and_then(|dock| {
+                self.windows
+                    .iter()
+                    .find(|(other_window, ..)| other_window.get_window_class() == Some(dock.window_class.as_str()))
+                    .map(|(other_window, ..)| other_window.get_area().0 + dock.offset)
+            });
+
+        if let Some(position) = docked_position
+            && let Some((window_class, position)) = window.place(self.available_space, position)
+        {
+            self.window_cache.update_position(window_class, position);
+        }
+
+        let window_index = self.top_window_index(window.is_modal());
+
+        // windows opened below a modal window might be covering other windows, so
+        // everything needs to be rendered again
+        self.rerender |= window_index != self.windows.len();
+        self.windows.insert(window_index, (window, true, true));
         focus_state.remove_focus();
     }
 
@@ -406,6 +1165,32 @@ impl Interface {
         }
     }
 
+    /// Replace an open window of the same class with a new one, or just open
+    /// the window if there is none.
+    pub fn reopen_window(&mut self, focus_state: &mut FocusState, prototype_window: &dyn PrototypeWindow) {
+        let index = prototype_window.window_class().and_then(|window_class| {
+            self.windows
+                .iter()
+                .position(|(window, ..)| window.get_window_class() == Some(window_class))
+        });
+
+        if let Some(index) = index {
+            self.close_window(focus_state, index);
+        }
+
+        self.open_window(focus_state, prototype_window);
+    }
+
+    /// Opens a context menu at the given position, replacing any other popup.
+    pub fn open_context_menu(&mut self, focus_state: &mut FocusState, position: Position, entries: Vec<ContextMenuEntry>) {
+        if entries.is_empty() {
+            return;
+        }
+
+        self.close_popup_windows(focus_state);
+        self.open_window(focus_state, &ContextMenuWindow::new(position, entries));
+    }
+
     pub fn open_dialog_window(&mut self, focus_state: &mut FocusState, text: String, npc_id: EntityId) {
         if let Some(dialog_handle) = &mut self.dialog_handle {
             dialog_handle.elements.with_mut(|elements, changed| {
@@ -464,7 +1249,7 @@ impl Interface {
     }
 
     #[cfg(feature = "debug")]
-    pub fn open_theme_viewer_window(&mut self, focus_state: &mut FocusState) {
+    pub fn open_theme_editor_window(&mut self, focus_state: &mut FocusState) {
         if !self.window_exists(self.theme.window_class()) {
             let window = self
                 .theme
@@ -486,6 +1271,35 @@ impl Interface {
         self.restore_focus(focus_state);
     }
 
+    /// Closes the top most window that can be closed by the user. Returns
+    /// `false` if there is no such window.
+    pub fn close_top_window(&mut self, focus_state: &mut FocusState) -> bool {
+        let Some(window_index) = self
+            .windows
+            .iter()
+            .rposition(|(window, ..)| window.is_closable() || window.is_popup())
+        else {
+            return false;
+        };
+
+        self.close_window(focus_state, window_index);
+        true
+    }
+
+    /// Closes all popups. Returns `false` if there were none.
+    pub fn close_popup_windows(&mut self, focus_state: &mut FocusState) -> bool {
+        let window_count = self.windows.len();
+        self.windows.retain(|(window, ..)| !window.is_popup());
+
+        if self.windows.len() == window_count {
+            return false;
+        }
+
+        self.rerender = true;
+        self.restore_focus(focus_state);
+        true
+    }
+
     pub fn close_window_with_class(&mut self, focus_state: &mut FocusState, window_class: &str) {
         let index = self
             .windows
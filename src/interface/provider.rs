@@ -4,6 +4,7 @@ use crate::graphics::GraphicsSettings;
 #[cfg(feature = "debug")]
 use crate::graphics::RenderSettings;
 use crate::network::LoginSettings;
+use crate::system::WindowSettings;
 
 #[derive(new)]
 pub struct StateProvider<'t> {
@@ -11,4 +12,5 @@ pub struct StateProvider<'t> {
     #[cfg(feature = "debug")]
     pub render_settings: &'t RenderSettings,
     pub login_settings: &'t LoginSettings,
+    pub window_settings: &'t WindowSettings,
 }
@@ -5,19 +5,63 @@ use serde::{Deserialize, Serialize};
 #[cfg(feature = "debug")]
 use crate::debug::*;
 use crate::interface::*;
+use crate::system::profile_file;
 
 #[derive(Serialize, Deserialize, PrototypeElement)]
 pub struct InterfaceSettings {
     pub scaling: MutableRange<f32, RERESOLVE>,
+    #[serde(default = "default_hud_grid_size")]
+    pub hud_grid_size: MutableRange<f32, NO_EVENT>,
+    /// Widest aspect ratio of the area that HUD windows are placed in. Wider
+    /// screens keep the HUD in the center while the world uses the full width,
+    /// e.g. 1.78 for 16:9. The maximum uses the full width of any screen.
+    #[serde(default = "default_hud_aspect_ratio")]
+    pub hud_aspect_ratio: MutableRange<f32, RERESOLVE>,
+    /// Distance between the HUD area and the edges of the screen, for
+    /// displays that cut off or curve at the edges.
+    #[serde(default = "default_hud_inset")]
+    pub hud_inset: MutableRange<f32, RERESOLVE>,
+    /// Part of the scroll speed that is kept after one second, so that lists
+    /// keep gliding after scrolling. At 0 they stop right away.
+    #[serde(default = "default_scroll_glide")]
+    pub scroll_glide: MutableRange<f32, RERESOLVE>,
     #[hidden_element]
     pub theme_file: String,
 }
 
+fn default_hud_grid_size() -> MutableRange<f32, NO_EVENT> {
+    MutableRange::new(10.0, 1.0, 50.0)
+}
+
+fn default_hud_aspect_ratio() -> MutableRange<f32, RERESOLVE> {
+    MutableRange::new(4.0, 1.25, 4.0)
+}
+
+fn default_hud_inset() -> MutableRange<f32, RERESOLVE> {
+    MutableRange::new(0.0, 0.0, 100.0)
+}
+
+fn default_scroll_glide() -> MutableRange<f32, RERESOLVE> {
+    MutableRange::new(0.05, 0.0, 0.5)
+}
+
 impl Default for InterfaceSettings {
     fn default() -> Self {
         let scaling = MutableRange::new(1.0, 0.7, 1.7);
+        let hud_grid_size = default_hud_grid_size();
+        let hud_aspect_ratio = default_hud_aspect_ratio();
+        let hud_inset = default_hud_inset();
+        let scroll_glide = default_scroll_glide();
         let theme_file = "client/themes/theme.ron".to_string();
-        Self { scaling, theme_file }
+
+        Self {
+            scaling,
+            hud_grid_size,
+            hud_aspect_ratio,
+            hud_inset,
+            scroll_glide,
+            theme_file,
+        }
     }
 }
 
@@ -35,7 +79,7 @@ impl InterfaceSettings {
         #[cfg(feature = "debug")]
         print_debug!("loading interface settings from {}filename{}", MAGENTA, NONE);
 
-        std::fs::read_to_string("client/interface_settings.ron")
+        std::fs::read_to_string(profile_file("interface_settings.ron"))
             .ok()
             .and_then(|data| ron::from_str(&data).ok())
     }
@@ -45,7 +89,7 @@ impl InterfaceSettings {
         print_debug!("saving interface settings to {}filename{}", MAGENTA, NONE);
 
         let data = ron::ser::to_string_pretty(self, PrettyConfig::new()).unwrap();
-        std::fs::write("client/interface_settings.ron", data).expect("unable to write file");
+        std::fs::write(profile_file("interface_settings.ron"), data).expect("unable to write file");
     }
 }
 
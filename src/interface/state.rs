@@ -2,6 +2,8 @@ use std::cell::{Ref, RefCell};
 use std::ops::Not;
 use std::rc::Rc;
 
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
 use super::StateProvider;
 
 #[derive(Default)]
@@ -128,6 +130,18 @@ impl<T> Clone for TrackedState<T> {
     }
 }
 
+impl<T: Serialize> Serialize for TrackedState<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.borrow().serialize(serializer)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for TrackedState<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize(deserializer).map(TrackedState::new)
+    }
+}
+
 pub struct Remote<T> {
     tracked_state: TrackedState<T>,
     version: usize,
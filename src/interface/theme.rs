@@ -1,3 +1,5 @@
+use std::time::SystemTime;
+
 use cgmath::{Array, Vector2, Vector4, Zero};
 use procedural::*;
 use ron::ser::PrettyConfig;
@@ -5,8 +7,63 @@ use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "debug")]
 use crate::debug::*;
-use crate::graphics::Color;
+use crate::graphics::{Color, Texture};
 use crate::interface::*;
+use crate::loaders::{GameFileLoader, TextureLoader};
+
+#[derive(Copy, Clone, Serialize, Deserialize)]
+pub enum GradientKind {
+    Linear,
+    Radial,
+}
+
+/// Blends from the regular background color to `end_color`. The angle (in
+/// degrees) is only used by linear gradients.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Gradient {
+    pub kind: GradientKind,
+    pub end_color: Color,
+    pub angle: f32,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct NineSlice {
+    pub texture: String,
+    /// Size of the left, top, right and bottom border in texels.
+    pub borders: Vector4<f32>,
+    #[serde(skip)]
+    pub loaded_texture: Option<Texture>,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub enum Background {
+    #[default]
+    Flat,
+    Gradient(Gradient),
+    NineSlice(NineSlice),
+}
+
+impl Background {
+    fn load_textures(&mut self, game_file_loader: &mut GameFileLoader, texture_loader: &mut TextureLoader) {
+        let Background::NineSlice(nine_slice) = self else {
+            return;
+        };
+
+        nine_slice.loaded_texture = texture_loader
+            .get(&nine_slice.texture, game_file_loader)
+            .map_err(|_error| {
+                #[cfg(feature = "debug")]
+                print_debug!(
+                    "failed to load nine-slice texture {}{}{}: {}",
+                    MAGENTA,
+                    nine_slice.texture,
+                    NONE,
+                    _error
+                );
+            })
+            .ok();
+    }
+}
 
 #[derive(Serialize, Deserialize, PrototypeElement)]
 pub struct ButtonTheme {
@@ -17,6 +74,9 @@ pub struct ButtonTheme {
     pub hovered_foreground_color: Mutable<Color, RERENDER>,
     pub disabled_foreground_color: Mutable<Color, RERENDER>,
     pub debug_foreground_color: Mutable<Color, RERENDER>,
+    #[serde(default)]
+    #[hidden_element]
+    pub background: Background,
     pub border_radius: MutableRange<Vector4<f32>, RERENDER>,
     pub icon_offset: MutableRange<Vector2<f32>, RERENDER>,
     pub icon_size: MutableRange<Vector2<f32>, RERENDER>,
@@ -36,6 +96,7 @@ impl Default for ButtonTheme {
             hovered_foreground_color: Mutable::new(Color::rgb(220, 170, 215)),
             disabled_foreground_color: Mutable::new(Color::monochrome(140)),
             debug_foreground_color: Mutable::new(Color::rgb(230, 140, 230)),
+            background: Background::default(),
             border_radius: MutableRange::new(Vector4::from_value(6.0), Vector4::from_value(0.0), Vector4::from_value(30.0)),
             icon_offset: MutableRange::new(Vector2::new(7.0, 2.0), Vector2::zero(), Vector2::new(20.0, 20.0)),
             icon_size: MutableRange::new(Vector2::new(10.0, 10.0), Vector2::zero(), Vector2::new(20.0, 20.0)),
@@ -52,6 +113,9 @@ pub struct WindowTheme {
     pub background_color: Mutable<Color, RERENDER>,
     pub title_background_color: Mutable<Color, RERENDER>,
     pub foreground_color: Mutable<Color, RERENDER>,
+    #[serde(default)]
+    #[hidden_element]
+    pub background: Background,
     pub border_radius: MutableRange<Vector4<f32>, RERENDER>,
     pub title_border_radius: MutableRange<Vector4<f32>, RERENDER>,
     pub border_size: MutableRange<Vector2<f32>, RERESOLVE>,
@@ -67,6 +131,7 @@ impl Default for WindowTheme {
             background_color: Mutable::new(Color::monochrome(40)),
             title_background_color: Mutable::new(Color::rgb(70, 60, 70)),
             foreground_color: Mutable::new(Color::monochrome(160)),
+            background: Background::default(),
             border_radius: MutableRange::new(Vector4::from_value(4.0), Vector4::from_value(0.0), Vector4::from_value(30.0)),
             title_border_radius: MutableRange::new(Vector4::from_value(6.0), Vector4::from_value(0.0), Vector4::from_value(30.0)),
             border_size: MutableRange::new(Vector2::new(12.0, 6.0), Vector2::zero(), Vector2::new(30.0, 30.0)),
@@ -222,6 +287,25 @@ impl Default for SliderTheme {
     }
 }
 
+#[derive(Serialize, Deserialize, PrototypeElement)]
+pub struct ScrollViewTheme {
+    pub thumb_color: Mutable<Color, RERENDER>,
+    pub hovered_thumb_color: Mutable<Color, RERENDER>,
+    pub thumb_width: MutableRange<f32, RERESOLVE>,
+    pub minimum_thumb_height: MutableRange<f32, RERENDER>,
+}
+
+impl Default for ScrollViewTheme {
+    fn default() -> Self {
+        Self {
+            thumb_color: Mutable::new(Color::rgba(150, 130, 150, 160)),
+            hovered_thumb_color: Mutable::new(Color::rgb(100, 180, 180)),
+            thumb_width: MutableRange::new(6.0, 2.0, 20.0),
+            minimum_thumb_height: MutableRange::new(16.0, 4.0, 60.0),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, PrototypeElement)]
 pub struct InputTheme {
     pub background_color: Mutable<Color, RERENDER>,
@@ -230,6 +314,8 @@ pub struct InputTheme {
     pub text_color: Mutable<Color, RERENDER>,
     pub ghost_text_color: Mutable<Color, RERENDER>,
     pub focused_text_color: Mutable<Color, RERENDER>,
+    #[serde(default = "default_selection_color")]
+    pub selection_color: Mutable<Color, RERENDER>,
     pub border_radius: MutableRange<Vector4<f32>, RERENDER>,
     pub font_size: MutableRange<f32, RERENDER>,
     pub cursor_offset: MutableRange<f32, RERENDER>,
@@ -237,6 +323,10 @@ pub struct InputTheme {
     pub height_constraint: DimensionConstraint,
 }
 
+fn default_selection_color() -> Mutable<Color, RERENDER> {
+    Mutable::new(Color::rgba(100, 140, 200, 160))
+}
+
 impl Default for InputTheme {
     fn default() -> Self {
         Self {
@@ -246,6 +336,7 @@ impl Default for InputTheme {
             text_color: Mutable::new(Color::monochrome(200)),
             ghost_text_color: Mutable::new(Color::monochrome(100)),
             focused_text_color: Mutable::new(Color::monochrome(200)),
+            selection_color: default_selection_color(),
             border_radius: MutableRange::new(Vector4::from_value(6.0), Vector4::from_value(0.0), Vector4::from_value(30.0)),
             font_size: MutableRange::new(14.0, 6.0, 50.0),
             cursor_offset: MutableRange::new(4.0, 2.0, 10.0),
@@ -284,11 +375,12 @@ impl Default for CursorTheme {
 }
 
 #[derive(Serialize, Deserialize, Default, PrototypeWindow)]
-#[window_title("Theme Viewer")]
-#[window_class("theme_viewer")]
+#[window_title("Theme Editor")]
+#[window_class("theme_editor")]
 pub struct Theme {
     #[event_button("reload theme", ReloadTheme)]
     #[event_button("save theme", SaveTheme)]
+    #[event_button("export theme", ExportTheme)]
     pub button: ButtonTheme,
     pub window: WindowTheme,
     pub expandable: ExpandableTheme,
@@ -297,19 +389,29 @@ pub struct Theme {
     pub close_button: CloseButtonTheme,
     pub overlay: OverlayTheme,
     pub slider: SliderTheme,
+    #[serde(default)]
+    pub scroll_view: ScrollViewTheme,
     pub input: InputTheme,
     pub chat: ChatTheme,
     pub cursor: CursorTheme,
 }
 
 impl Theme {
-    pub fn new(theme_file: &str) -> Self {
-        Self::load(theme_file).unwrap_or_else(|| {
+    pub fn new(theme_file: &str, game_file_loader: &mut GameFileLoader, texture_loader: &mut TextureLoader) -> Self {
+        let mut theme = Self::load(theme_file).unwrap_or_else(|| {
             #[cfg(feature = "debug")]
             print_debug!("failed to load theme from file {}{}{}", MAGENTA, theme_file, NONE);
 
             Default::default()
-        })
+        });
+
+        theme.load_textures(game_file_loader, texture_loader);
+        theme
+    }
+
+    fn load_textures(&mut self, game_file_loader: &mut GameFileLoader, texture_loader: &mut TextureLoader) {
+        self.button.background.load_textures(game_file_loader, texture_loader);
+        self.window.background.load_textures(game_file_loader, texture_loader);
     }
 
     fn load(theme_file: &str) -> Option<Self> {
@@ -319,8 +421,8 @@ impl Theme {
         std::fs::read_to_string(theme_file).ok().and_then(|data| ron::from_str(&data).ok())
     }
 
-    pub fn reload(&mut self, theme_file: &str) -> bool {
-        let Some(theme) = Self::load(theme_file) else {
+    pub fn reload(&mut self, theme_file: &str, game_file_loader: &mut GameFileLoader, texture_loader: &mut TextureLoader) -> bool {
+        let Some(mut theme) = Self::load(theme_file) else {
 
             #[cfg(feature = "debug")]
             print_debug!("failed to load theme from file {}{}{}", MAGENTA, theme_file, NONE);
@@ -328,10 +430,15 @@ impl Theme {
             return false;
         };
 
+        theme.load_textures(game_file_loader, texture_loader);
         *self = theme;
         true
     }
 
+    pub fn modified(theme_file: &str) -> Option<SystemTime> {
+        std::fs::metadata(theme_file).and_then(|metadata| metadata.modified()).ok()
+    }
+
     pub fn save(&self, theme_file: &str) {
         #[cfg(feature = "debug")]
         print_debug!("saving theme to {}{}{}", MAGENTA, theme_file, NONE);
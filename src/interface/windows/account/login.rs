@@ -7,7 +7,7 @@ use procedural::*;
 
 use crate::input::UserEvent;
 use crate::interface::*;
-use crate::network::LoginSettings;
+use crate::network::{LoginSettings, PasswordEncryption};
 
 #[derive(new)]
 pub struct LoginWindow {
@@ -89,6 +89,28 @@ impl PrototypeWindow for LoginWindow {
                 .with_event(UserEvent::ToggleRemeberPassword)
                 .with_transparent_background()
                 .wrap(),
+            cell!(Headline::new("password encryption".to_string(), Headline::DEFAULT_SIZE)),
+            StateButton::default()
+                .with_static_text("plain")
+                .with_selector(|state_provider| state_provider.login_settings.password_encryption == PasswordEncryption::Plain)
+                .with_event(UserEvent::SetPasswordEncryption(PasswordEncryption::Plain))
+                .with_transparent_background()
+                .with_width(dimension!(33.33%))
+                .wrap(),
+            StateButton::default()
+                .with_static_text("key + password")
+                .with_selector(|state_provider| state_provider.login_settings.password_encryption == PasswordEncryption::Md5KeyFirst)
+                .with_event(UserEvent::SetPasswordEncryption(PasswordEncryption::Md5KeyFirst))
+                .with_transparent_background()
+                .with_width(dimension!(33.33%))
+                .wrap(),
+            StateButton::default()
+                .with_static_text("password + key")
+                .with_selector(|state_provider| state_provider.login_settings.password_encryption == PasswordEncryption::Md5KeyLast)
+                .with_event(UserEvent::SetPasswordEncryption(PasswordEncryption::Md5KeyLast))
+                .with_transparent_background()
+                .with_width(dimension!(!))
+                .wrap(),
             Button::default()
                 .with_static_text("log in")
                 .with_disabled_selector(selector)
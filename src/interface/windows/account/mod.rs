@@ -1,3 +1,5 @@
 mod login;
+mod server;
 
 pub use self::login::LoginWindow;
+pub use self::server::ServerSelectionWindow;
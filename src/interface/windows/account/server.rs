@@ -0,0 +1,50 @@
+use derive_new::new;
+use procedural::*;
+
+use crate::input::UserEvent;
+use crate::interface::*;
+
+/// Lists the character servers that the login server sent, with the number
+/// of players on each.
+#[derive(new)]
+pub struct ServerSelectionWindow {
+    servers: Vec<(String, usize)>,
+}
+
+impl ServerSelectionWindow {
+    pub const WINDOW_CLASS: &'static str = "server_selection";
+}
+
+impl PrototypeWindow for ServerSelectionWindow {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: Size) -> Window {
+        let mut elements: Vec<ElementCell> = self
+            .servers
+            .iter()
+            .enumerate()
+            .map(|(index, (name, user_count))| {
+                Button::default()
+                    .with_dynamic_text(format!("{} ({} players)", name, user_count))
+                    .with_event(UserEvent::SelectCharacterServer(index))
+                    .wrap()
+            })
+            .collect();
+
+        elements.push(
+            Button::default()
+                .with_static_text("back")
+                .with_event(UserEvent::CancelServerSelection)
+                .wrap(),
+        );
+
+        WindowBuilder::default()
+            .with_title("Select Server".to_string())
+            .with_class(Self::WINDOW_CLASS.to_string())
+            .with_size(constraint!(200 > 250 < 300, ? < 80%))
+            .with_elements(elements)
+            .build(window_cache, interface_settings, available_space)
+    }
+}
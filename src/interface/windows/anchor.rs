@@ -0,0 +1,89 @@
+use cgmath::{Array, ElementWise, Vector2};
+use serde::{Deserialize, Serialize};
+
+use crate::interface::{Position, Size};
+
+/// Point of the HUD area that a HUD window is attached to. The window keeps
+/// its offset to that point when the HUD area changes, so windows in a corner
+/// stay in that corner and centered windows stay centered.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub enum HudAnchor {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+impl HudAnchor {
+    const ALL: [Self; 9] = [
+        Self::TopLeft,
+        Self::Top,
+        Self::TopRight,
+        Self::Left,
+        Self::Center,
+        Self::Right,
+        Self::BottomLeft,
+        Self::Bottom,
+        Self::BottomRight,
+    ];
+
+    /// Anchor of the third of the HUD area that the center of the window is
+    /// in.
+    pub fn closest(position: Position, size: Size, (area_position, area_size): (Position, Size)) -> Self {
+        let third = |position: f32, size: f32, area_position: f32, area_size: f32| {
+            ((position + size / 2.0 - area_position) / area_size * 3.0).clamp(0.0, 2.0) as usize
+        };
+
+        let column = third(position.x, size.x, area_position.x, area_size.x);
+        let row = third(position.y, size.y, area_position.y, area_size.y);
+
+        Self::ALL[row * 3 + column]
+    }
+
+    /// Where the anchor is relative to the size of the area, and at the same
+    /// time which point of the window is attached to it.
+    fn factors(self) -> Vector2<f32> {
+        let index = self as usize;
+        Vector2::new((index % 3) as f32 / 2.0, (index / 3) as f32 / 2.0)
+    }
+
+    fn offset(self, position: Position, size: Size, (area_position, area_size): (Position, Size)) -> Position {
+        let factors = self.factors();
+        position + size.mul_element_wise(factors) - area_position - area_size.mul_element_wise(factors)
+    }
+
+    fn position(self, offset: Position, size: Size, (area_position, area_size): (Position, Size)) -> Position {
+        let factors = self.factors();
+        area_position + area_size.mul_element_wise(factors) + offset - size.mul_element_wise(factors)
+    }
+
+    /// Moves a window from one HUD area to another, keeping its offset to the
+    /// anchor.
+    pub fn reanchored_position(
+        self,
+        position: Position,
+        size: Size,
+        previous_area: (Position, Size),
+        new_area: (Position, Size),
+    ) -> Position {
+        self.position(self.offset(position, size, previous_area), size, new_area)
+    }
+}
+
+/// Part of the screen that HUD windows are placed in. Screens wider than the
+/// maximum aspect ratio use a centered area of that aspect ratio, so the HUD
+/// stays close to the center on ultrawide displays. The inset keeps the area
+/// away from all edges of the screen.
+pub fn hud_area(available_space: Size, maximum_aspect_ratio: f32, inset: f32) -> (Position, Size) {
+    let safe_space = available_space - Size::from_value(inset * 2.0);
+    let width = safe_space.x.min(safe_space.y * maximum_aspect_ratio);
+    (
+        Position::new((available_space.x - width) / 2.0, inset),
+        Size::new(width, safe_space.y),
+    )
+}
@@ -1,3 +1,5 @@
+use cgmath::Vector2;
+use num::Zero;
 use procedural::dimension;
 
 use crate::interface::*;
@@ -9,6 +11,10 @@ pub struct WindowBuilder {
     size_constraint: SizeConstraint,
     elements: Vec<ElementCell>,
     closable: bool,
+    modal: bool,
+    hud: bool,
+    popup: bool,
+    default_position: Option<Position>,
     background_color: Option<ColorSelector>,
 }
 
@@ -46,6 +52,33 @@ impl WindowBuilder {
         self
     }
 
+    /// Modal windows are always kept above all other windows.
+    pub fn modal(mut self) -> Self {
+        self.modal = true;
+        self
+    }
+
+    /// HUD windows can only be moved and resized while the HUD edit mode is
+    /// active, where they will also snap to the grid.
+    pub fn hud(mut self) -> Self {
+        self.hud = true;
+        self
+    }
+
+    /// Popups close when anything outside of them is clicked, or once one of
+    /// their events was triggered.
+    pub fn popup(mut self) -> Self {
+        self.popup = true;
+        self
+    }
+
+    /// Position used instead of the automatic placement, as long as the window
+    /// was never moved.
+    pub fn with_default_position(mut self, position: Position) -> Self {
+        self.default_position = Some(position);
+        self
+    }
+
     pub fn build(self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: Size) -> Window {
         let WindowBuilder {
             window_title,
@@ -53,12 +86,19 @@ impl WindowBuilder {
             size_constraint,
             mut elements,
             closable,
+            modal,
+            hud,
+            popup,
+            default_position,
             background_color,
         } = self;
 
+        let mut title_bar = Vec::new();
+
         if closable {
             assert!(window_title.is_some(), "closable window must also have a title");
-            let close_button = cell!(CloseButton::default());
+            let close_button: ElementCell = cell!(CloseButton::default());
+            title_bar.push(close_button.clone());
             elements.insert(0, close_button);
         }
 
@@ -68,7 +108,8 @@ impl WindowBuilder {
         };
 
         if let Some(title) = window_title {
-            let drag_button = cell!(DragButton::new(title, width_constraint));
+            let drag_button: ElementCell = cell!(DragButton::new(title, width_constraint, window_class.clone()));
+            title_bar.push(drag_button.clone());
             elements.insert(0, drag_button);
         }
 
@@ -94,10 +135,17 @@ impl WindowBuilder {
                     .finalize_or(0.0)
             });
 
+        let cached_position = cached_position.or(default_position);
+        let needs_placement = cached_position.is_none();
         let position = cached_position
             .map(|position| size_constraint.validated_position(position, size, available_space))
             .unwrap_or((available_space - size) / 2.0);
 
+        let (opacity, click_through) = window_class
+            .as_ref()
+            .and_then(|window_class| window_cache.get_window_options(window_class))
+            .unwrap_or((1.0, false));
+
         Window {
             window_class,
             position,
@@ -105,7 +153,19 @@ impl WindowBuilder {
             size,
             elements,
             closable,
+            modal,
+            hud,
+            popup,
             background_color,
+            title_bar,
+            opacity,
+            click_through,
+            needs_placement,
+            position_remainder: Vector2::zero(),
+            size_remainder: Vector2::zero(),
+            snap_remainder: Vector2::zero(),
+            render_cache: None,
+            render_cache_valid: false,
         }
     }
 }
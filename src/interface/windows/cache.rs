@@ -5,14 +5,42 @@ use derive_new::new;
 use ron::ser::PrettyConfig;
 use serde::{Deserialize, Serialize};
 
+use super::{on_screen_position, reanchored_position, HudAnchor};
 #[cfg(feature = "debug")]
 use crate::debug::*;
 use crate::interface::{Position, Size};
+use crate::system::profile_file;
+
+fn default_opacity() -> f32 {
+    1.0
+}
+
+/// A window that was placed next to another window. The offset is relative to
+/// the position of that window, so both can be moved together and the layout
+/// is restored when the docked window is opened again.
+#[derive(Serialize, Deserialize)]
+pub struct WindowDock {
+    pub window_class: String,
+    pub offset: Position,
+}
 
 #[derive(Serialize, Deserialize, new)]
 pub struct WindowState {
     pub position: Position,
     pub size: Size,
+    #[new(value = "1.0")]
+    #[serde(default = "default_opacity")]
+    pub opacity: f32,
+    #[new(default)]
+    #[serde(default)]
+    pub click_through: bool,
+    #[new(default)]
+    #[serde(default)]
+    pub docked_to: Option<WindowDock>,
+    /// Only set for HUD windows, which are placed relative to the HUD area.
+    #[new(default)]
+    #[serde(default)]
+    pub hud_anchor: Option<HudAnchor>,
 }
 
 #[derive(Default, Serialize, Deserialize)]
@@ -38,7 +66,7 @@ impl WindowCache {
         #[cfg(feature = "debug")]
         print_debug!("loading window cache from {}filename{}", MAGENTA, NONE);
 
-        std::fs::read_to_string("client/window_cache.ron")
+        std::fs::read_to_string(profile_file("window_cache.ron"))
             .ok()
             .and_then(|data| ron::from_str(&data).ok())
             .map(|entries| Self { entries })
@@ -49,7 +77,7 @@ impl WindowCache {
         print_debug!("saving window cache to {}filename{}", MAGENTA, NONE);
 
         let data = ron::ser::to_string_pretty(&self.entries, PrettyConfig::new()).unwrap();
-        std::fs::write("client/window_cache.ron", data).expect("unable to write file");
+        std::fs::write(profile_file("window_cache.ron"), data).expect("unable to write file");
     }
 
     pub fn register_window(&mut self, identifier: &str, position: Position, size: Size) {
@@ -74,6 +102,67 @@ impl WindowCache {
         }
     }
 
+    pub fn update_opacity(&mut self, identifier: &str, opacity: f32) {
+        if let Some(entry) = self.entries.get_mut(identifier) {
+            entry.opacity = opacity;
+        }
+    }
+
+    pub fn update_click_through(&mut self, identifier: &str, click_through: bool) {
+        if let Some(entry) = self.entries.get_mut(identifier) {
+            entry.click_through = click_through;
+        }
+    }
+
+    pub fn update_dock(&mut self, identifier: &str, docked_to: Option<WindowDock>) {
+        if let Some(entry) = self.entries.get_mut(identifier) {
+            entry.docked_to = docked_to;
+        }
+    }
+
+    pub fn update_hud_anchor(&mut self, identifier: &str, hud_anchor: HudAnchor) {
+        if let Some(entry) = self.entries.get_mut(identifier) {
+            entry.hud_anchor = Some(hud_anchor);
+        }
+    }
+
+    /// Applies a change of the screen size to all windows that are not
+    /// anchored to the HUD area, including the ones that are not open right
+    /// now.
+    pub fn reanchor_windows(&mut self, previous_space: Size, new_space: Size) {
+        self.entries
+            .values_mut()
+            .filter(|entry| entry.hud_anchor.is_none())
+            .for_each(|entry| entry.position = reanchored_position(entry.position, entry.size, previous_space, new_space));
+    }
+
+    /// Applies a change of the HUD area to all windows anchored to it.
+    pub fn reanchor_hud_windows(&mut self, previous_area: (Position, Size), new_area: (Position, Size)) {
+        self.entries.values_mut().for_each(|entry| {
+            if let Some(hud_anchor) = entry.hud_anchor {
+                entry.position = hud_anchor.reanchored_position(entry.position, entry.size, previous_area, new_area);
+            }
+        });
+    }
+
+    pub fn move_windows_on_screen(&mut self, available_space: Size) {
+        self.entries
+            .values_mut()
+            .for_each(|entry| entry.position = on_screen_position(entry.position, entry.size, available_space));
+    }
+
+    pub fn get_dock(&self, identifier: &str) -> Option<&WindowDock> {
+        self.entries.get(identifier).and_then(|entry| entry.docked_to.as_ref())
+    }
+
+    pub fn get_hud_anchor(&self, identifier: &str) -> Option<HudAnchor> {
+        self.entries.get(identifier).and_then(|entry| entry.hud_anchor)
+    }
+
+    pub fn get_window_options(&self, identifier: &str) -> Option<(f32, bool)> {
+        self.entries.get(identifier).map(|entry| (entry.opacity, entry.click_through))
+    }
+
     pub fn get_window_state(&self, identifier: &str) -> Option<(Position, Size)> {
         self.entries.get(identifier).map(|entry| (entry.position, entry.size))
     }
@@ -1,11 +1,13 @@
 use procedural::*;
 
+use crate::graphics::ImageBuffer;
 use crate::interface::*;
 use crate::inventory::Item;
 
 #[derive(new)]
 pub struct EquipmentWindow {
     items: TrackedState<Vec<Item>>,
+    preview_image: Remote<ImageBuffer>,
 }
 
 impl EquipmentWindow {
@@ -18,7 +20,10 @@ impl PrototypeWindow for EquipmentWindow {
     }
 
     fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: Size) -> Window {
-        let elements = vec![EquipmentContainer::new(self.items.new_remote()).wrap()];
+        let elements = vec![
+            EntityPreview::new(self.preview_image.clone()).wrap(),
+            EquipmentContainer::new(self.items.new_remote()).wrap(),
+        ];
 
         WindowBuilder::default()
             .with_title("Equipment".to_string())
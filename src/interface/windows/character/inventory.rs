@@ -2,10 +2,12 @@ use procedural::*;
 
 use crate::interface::*;
 use crate::inventory::Item;
+use crate::network::ItemId;
 
 #[derive(new)]
 pub struct InventoryWindow {
     items: TrackedState<Vec<Item>>,
+    highlighted_items: TrackedState<Vec<ItemId>>,
 }
 
 impl InventoryWindow {
@@ -18,7 +20,7 @@ impl PrototypeWindow for InventoryWindow {
     }
 
     fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: Size) -> Window {
-        let elements = vec![InventoryContainer::new(self.items.new_remote()).wrap()];
+        let elements = vec![ItemGrid::new(self.items.new_remote(), self.highlighted_items.new_remote(), ItemSource::Inventory).wrap()];
 
         WindowBuilder::default()
             .with_title("Inventory".to_string())
@@ -3,9 +3,13 @@ mod equipment;
 mod inventory;
 mod overview;
 mod selection;
+mod session;
+mod skills;
 
 pub use self::creation::CharacterCreationWindow;
 pub use self::equipment::EquipmentWindow;
 pub use self::inventory::InventoryWindow;
 pub use self::overview::CharacterOverviewWindow;
 pub use self::selection::CharacterSelectionWindow;
+pub use self::session::SessionStatsWindow;
+pub use self::skills::SkillTreeWindow;
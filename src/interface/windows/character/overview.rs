@@ -32,6 +32,10 @@ impl PrototypeWindow for CharacterOverviewWindow {
                 .with_static_text("equipment")
                 .with_event(UserEvent::OpenEquipmentWindow)
                 .wrap(),
+            Button::default()
+                .with_static_text("skills")
+                .with_event(UserEvent::OpenSkillTreeWindow)
+                .wrap(),
             Button::default()
                 .with_static_text("menu")
                 .with_event(UserEvent::OpenMenuWindow)
@@ -1,6 +1,7 @@
 use derive_new::new;
 use procedural::*;
 
+use crate::graphics::ImageBuffer;
 use crate::interface::*;
 use crate::network::CharacterInformation;
 
@@ -8,6 +9,7 @@ use crate::network::CharacterInformation;
 pub struct CharacterSelectionWindow {
     characters: TrackedState<Vec<CharacterInformation>>,
     move_request: TrackedState<Option<usize>>,
+    previews: TrackedState<Vec<(usize, Remote<ImageBuffer>)>>,
     slot_count: usize,
 }
 
@@ -27,6 +29,7 @@ impl PrototypeWindow for CharacterSelectionWindow {
                 cell!(CharacterPreview::new(
                     self.characters.new_remote(),
                     self.move_request.new_remote(),
+                    self.previews.new_remote(),
                     slot
                 )) as ElementCell
             })
@@ -0,0 +1,53 @@
+use procedural::*;
+
+use crate::input::UserEvent;
+use crate::interface::*;
+use crate::system::SessionStatistics;
+
+/// Shows what the player achieved during the current session.
+pub struct SessionStatsWindow {
+    lines: Vec<String>,
+}
+
+impl SessionStatsWindow {
+    pub const WINDOW_CLASS: &'static str = "session_statistics";
+
+    pub fn new(statistics: &SessionStatistics) -> Self {
+        Self {
+            lines: statistics.lines(),
+        }
+    }
+}
+
+impl PrototypeWindow for SessionStatsWindow {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: Size) -> Window {
+        let mut elements: Vec<ElementCell> = self
+            .lines
+            .iter()
+            .map(|line| Text::default().with_dynamic_text(line.clone()).wrap())
+            .collect();
+
+        elements.extend([
+            Button::default()
+                .with_static_text("reset")
+                .with_event(UserEvent::ResetSessionStatistics)
+                .wrap(),
+            Button::default()
+                .with_static_text("export to csv")
+                .with_event(UserEvent::ExportSessionStatistics)
+                .wrap(),
+        ]);
+
+        WindowBuilder::default()
+            .with_title("Session Statistics".to_string())
+            .with_class(Self::WINDOW_CLASS.to_string())
+            .with_size(constraint!(200 > 250 < 300, ?))
+            .with_elements(elements)
+            .closable()
+            .build(window_cache, interface_settings, available_space)
+    }
+}
@@ -0,0 +1,39 @@
+use procedural::*;
+
+use crate::interface::*;
+use crate::network::SkillInformation;
+
+/// Lists the skills of the player. Hovering a skill shows its details and the
+/// estimated damage against the current target.
+pub struct SkillTreeWindow {
+    skills: Vec<SkillInformation>,
+}
+
+impl SkillTreeWindow {
+    pub const WINDOW_CLASS: &'static str = "skill_tree";
+
+    pub fn new(skills: Vec<SkillInformation>) -> Self {
+        Self { skills }
+    }
+}
+
+impl PrototypeWindow for SkillTreeWindow {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: Size) -> Window {
+        let elements = match self.skills.is_empty() {
+            true => vec![Text::default().with_static_text("no skills learned").wrap()],
+            false => self.skills.iter().cloned().map(|skill| SkillBox::new(skill).wrap()).collect(),
+        };
+
+        WindowBuilder::default()
+            .with_title("Skills".to_string())
+            .with_class(Self::WINDOW_CLASS.to_string())
+            .with_size(constraint!(200 > 250 < 300, ? < 80%))
+            .with_elements(elements)
+            .closable()
+            .build(window_cache, interface_settings, available_space)
+    }
+}
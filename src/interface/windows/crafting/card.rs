@@ -0,0 +1,122 @@
+use procedural::*;
+
+use crate::graphics::{Color, Texture};
+use crate::input::UserEvent;
+use crate::interface::*;
+use crate::inventory::Item;
+use crate::loaders::ScriptLoader;
+use crate::network::{ItemId, ItemIndex};
+
+struct CardTarget {
+    index: ItemIndex,
+    texture: Texture,
+    free_slots: usize,
+}
+
+/// Lets the player pick a piece of equipment to insert a card into. Selecting
+/// an item reopens the window with a confirmation, since cards can't be
+/// removed again.
+pub struct CardCompoundWindow {
+    card_index: ItemIndex,
+    card_name: String,
+    targets: Vec<CardTarget>,
+    selected: Option<(ItemIndex, String)>,
+}
+
+impl CardCompoundWindow {
+    pub const WINDOW_CLASS: &'static str = "card_compound";
+
+    pub fn new(
+        items: &[Item],
+        script_loader: &ScriptLoader,
+        card_index: ItemIndex,
+        target_indices: Vec<ItemIndex>,
+        selected: Option<ItemIndex>,
+    ) -> Self {
+        let card_name = items
+            .iter()
+            .find(|item| item.index == card_index)
+            .map(|card| script_loader.get_item_name_from_id(card.item_id))
+            .unwrap_or_default();
+
+        let targets = target_indices
+            .into_iter()
+            .filter_map(|index| items.iter().find(|item| item.index == index))
+            .map(|item| {
+                let slot_count = script_loader.get_item_slot_count(item.item_id).min(item.cards.len());
+                let free_slots = item.cards[..slot_count].iter().filter(|card_id| **card_id == ItemId(0)).count();
+
+                CardTarget {
+                    index: item.index,
+                    texture: item.texture.clone(),
+                    free_slots,
+                }
+            })
+            .collect();
+
+        let selected = selected.and_then(|index| {
+            let item = items.iter().find(|item| item.index == index)?;
+            Some((index, script_loader.get_item_name_from_id(item.item_id)))
+        });
+
+        Self {
+            card_index,
+            card_name,
+            targets,
+            selected,
+        }
+    }
+}
+
+impl PrototypeWindow for CardCompoundWindow {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: Size) -> Window {
+        let mut elements = vec![cell!(Headline::new(self.card_name.clone(), Headline::DEFAULT_SIZE))];
+
+        match self.targets.is_empty() {
+            true => elements.push(Text::default().with_static_text("no equipment can hold this card").wrap()),
+            false => elements.extend(self.targets.iter().map(|target| {
+                IconButton::new(
+                    target.texture.clone(),
+                    UserEvent::SelectCardTarget(self.card_index, target.index),
+                )
+                .wrap()
+            })),
+        }
+
+        if let Some((equipment_index, equipment_name)) = &self.selected {
+            let free_slots = self
+                .targets
+                .iter()
+                .find(|target| target.index == *equipment_index)
+                .map(|target| target.free_slots)
+                .unwrap_or_default();
+
+            elements.push(Text::default().with_dynamic_text(format!("insert into {}", equipment_name)).wrap());
+            elements.push(Text::default().with_dynamic_text(format!("free slots: {}", free_slots)).wrap());
+            elements.push(
+                Text::default()
+                    .with_static_text("the card can not be removed again")
+                    .with_foreground_color(|_| Color::rgb(255, 100, 100))
+                    .wrap(),
+            );
+            elements.push(
+                Button::default()
+                    .with_static_text("insert card")
+                    .with_event(UserEvent::InsertCard(self.card_index, *equipment_index))
+                    .wrap(),
+            );
+        }
+
+        WindowBuilder::default()
+            .with_title("Insert Card".to_string())
+            .with_class(Self::WINDOW_CLASS.to_string())
+            .with_size(constraint!(200 > 250 < 350, ?))
+            .with_elements(elements)
+            .closable()
+            .build(window_cache, interface_settings, available_space)
+    }
+}
@@ -0,0 +1,70 @@
+use procedural::*;
+
+use crate::input::UserEvent;
+use crate::interface::*;
+use crate::loaders::ScriptLoader;
+use crate::network::{CraftableItem, ItemId};
+
+/// Lists the items that the player can forge with the materials in their
+/// inventory.
+pub struct CraftingWindow {
+    items: Vec<(CraftableItem, String, Vec<String>)>,
+}
+
+impl CraftingWindow {
+    pub const WINDOW_CLASS: &'static str = "crafting";
+
+    pub fn new(items: Vec<CraftableItem>, script_loader: &ScriptLoader) -> Self {
+        let items = items
+            .into_iter()
+            .map(|item| {
+                let name = script_loader.get_item_name_from_id(item.item_id);
+                let material_names = item
+                    .material_ids
+                    .iter()
+                    .filter(|material_id| **material_id != ItemId(0))
+                    .map(|material_id| script_loader.get_item_name_from_id(*material_id))
+                    .collect();
+
+                (item, name, material_names)
+            })
+            .collect();
+
+        Self { items }
+    }
+}
+
+impl PrototypeWindow for CraftingWindow {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: Size) -> Window {
+        let elements = self
+            .items
+            .iter()
+            .flat_map(|(item, name, material_names)| {
+                let materials = match material_names.is_empty() {
+                    true => "no materials".to_string(),
+                    false => material_names.join(", "),
+                };
+
+                [
+                    Button::default()
+                        .with_dynamic_text(name.clone())
+                        .with_event(UserEvent::CraftItem(item.item_id, item.material_ids))
+                        .wrap(),
+                    Text::default().with_dynamic_text(materials).wrap(),
+                ]
+            })
+            .collect();
+
+        WindowBuilder::default()
+            .with_title("Crafting".to_string())
+            .with_class(Self::WINDOW_CLASS.to_string())
+            .with_size(constraint!(200 > 300 < 400, ?))
+            .with_elements(elements)
+            .closable()
+            .build(window_cache, interface_settings, available_space)
+    }
+}
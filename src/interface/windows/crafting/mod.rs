@@ -0,0 +1,9 @@
+mod card;
+mod craft;
+mod refine;
+mod result;
+
+pub use self::card::CardCompoundWindow;
+pub use self::craft::CraftingWindow;
+pub use self::refine::RefineWindow;
+pub use self::result::CraftingResultWindow;
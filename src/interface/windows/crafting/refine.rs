@@ -0,0 +1,122 @@
+use procedural::*;
+
+use crate::graphics::{Color, Texture};
+use crate::input::UserEvent;
+use crate::interface::*;
+use crate::inventory::Item;
+use crate::loaders::ScriptLoader;
+use crate::network::{EquipPosition, ItemIndex, RefineMaterial};
+
+struct RefineSelection {
+    index: ItemIndex,
+    name: String,
+    materials: Vec<(RefineMaterial, String)>,
+}
+
+/// Opened by the map server when talking to a refine NPC. The player first
+/// picks a piece of equipment and then one of the materials the server offers
+/// for it.
+pub struct RefineWindow {
+    equipment: Vec<(ItemIndex, Texture)>,
+    selection: Option<RefineSelection>,
+}
+
+impl RefineWindow {
+    pub const WINDOW_CLASS: &'static str = "refine";
+
+    pub fn new(items: &[Item], script_loader: &ScriptLoader, selection: Option<(ItemIndex, Vec<RefineMaterial>)>) -> Self {
+        let equipment = items
+            .iter()
+            .filter(|item| item.equip_position != EquipPosition::None)
+            .map(|item| (item.index, item.texture.clone()))
+            .collect();
+
+        let selection = selection.and_then(|(index, materials)| {
+            let item = items.iter().find(|item| item.index == index)?;
+            let materials = materials
+                .into_iter()
+                .map(|material| {
+                    let name = script_loader.get_item_name_from_id(material.item_id);
+                    (material, name)
+                })
+                .collect();
+
+            Some(RefineSelection {
+                index,
+                name: script_loader.get_item_name_from_id(item.item_id),
+                materials,
+            })
+        });
+
+        Self { equipment, selection }
+    }
+
+    fn selection_elements(selection: &RefineSelection) -> Vec<ElementCell> {
+        let accepted_risk = TrackedState::new(false);
+        let risky = selection.materials.iter().any(|(material, _)| material.chance < 100);
+
+        let mut elements = vec![cell!(Headline::new(selection.name.clone(), Headline::DEFAULT_SIZE))];
+
+        if risky {
+            elements.push(
+                Text::default()
+                    .with_static_text("a failed refine may break the item")
+                    .with_foreground_color(|_| Color::rgb(255, 100, 100))
+                    .wrap(),
+            );
+            elements.push(
+                StateButton::default()
+                    .with_static_text("accept the risk")
+                    .with_selector(accepted_risk.selector())
+                    .with_closure(accepted_risk.toggle_action())
+                    .wrap(),
+            );
+        }
+
+        elements.extend(selection.materials.iter().map(|(material, name)| {
+            let accepted_risk = accepted_risk.clone();
+            let guaranteed = material.chance >= 100;
+
+            Button::default()
+                .with_dynamic_text(format!("{} ({}%, {} zeny)", name, material.chance, material.zeny))
+                .with_event(UserEvent::RefineItem(selection.index, material.item_id))
+                .with_disabled_selector(move || guaranteed || *accepted_risk.borrow())
+                .wrap()
+        }));
+
+        elements
+    }
+}
+
+impl PrototypeWindow for RefineWindow {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: Size) -> Window {
+        let mut elements: Vec<ElementCell> = self
+            .equipment
+            .iter()
+            .map(|(index, texture)| IconButton::new(texture.clone(), UserEvent::SelectRefineItem(*index)).wrap())
+            .collect();
+
+        match &self.selection {
+            Some(selection) => elements.extend(Self::selection_elements(selection)),
+            None => elements.push(Text::default().with_static_text("select an item to refine").wrap()),
+        }
+
+        elements.push(
+            Button::default()
+                .with_static_text("close")
+                .with_event(UserEvent::CloseRefineWindow)
+                .wrap(),
+        );
+
+        WindowBuilder::default()
+            .with_title("Refine".to_string())
+            .with_class(Self::WINDOW_CLASS.to_string())
+            .with_size(constraint!(200 > 300 < 400, ?))
+            .with_elements(elements)
+            .build(window_cache, interface_settings, available_space)
+    }
+}
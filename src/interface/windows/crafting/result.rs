@@ -0,0 +1,39 @@
+use derive_new::new;
+use procedural::*;
+
+use crate::graphics::Color;
+use crate::interface::*;
+
+/// Shows the outcome of a refine or crafting attempt.
+#[derive(new)]
+pub struct CraftingResultWindow {
+    text: String,
+    success: bool,
+}
+
+impl CraftingResultWindow {
+    pub const WINDOW_CLASS: &'static str = "crafting_result";
+}
+
+impl PrototypeWindow for CraftingResultWindow {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: Size) -> Window {
+        let color = match self.success {
+            true => Color::rgb(100, 255, 100),
+            false => Color::rgb(255, 100, 100),
+        };
+
+        let elements = vec![RevealText::new(self.text.clone(), color, 18.0).wrap()];
+
+        WindowBuilder::default()
+            .with_title("Result".to_string())
+            .with_class(Self::WINDOW_CLASS.to_string())
+            .with_size(constraint!(200 > 250 < 300, ?))
+            .with_elements(elements)
+            .closable()
+            .build(window_cache, interface_settings, available_space)
+    }
+}
@@ -0,0 +1,221 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use procedural::*;
+
+use crate::graphics::Texture;
+use crate::input::UserEvent;
+use crate::interface::*;
+use crate::loaders::{GameFileLoader, MonsterDatabase, ScriptLoader, TextureLoader};
+use crate::network::ItemId;
+
+/// Maximum number of search results shown in the icon grid.
+const MAXIMUM_RESULTS: usize = 40;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ItemCategory {
+    All,
+    Usable,
+    Equipment,
+    Card,
+    Etc,
+}
+
+impl ItemCategory {
+    pub const ALL: [ItemCategory; 5] = [Self::All, Self::Usable, Self::Equipment, Self::Card, Self::Etc];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::All => "all",
+            Self::Usable => "usable",
+            Self::Equipment => "equipment",
+            Self::Card => "card",
+            Self::Etc => "etc",
+        }
+    }
+
+    /// The item info tables don't carry a type, so the category is derived
+    /// from the id ranges used by the official item database.
+    pub fn from_item_id(item_id: ItemId) -> Self {
+        match item_id.0 {
+            500..=699 | 11500..=12999 | 14500..=14999 => Self::Usable,
+            1100..=2999 | 5000..=5999 | 13000..=13499 | 15000..=15999 | 18100..=20999 => Self::Equipment,
+            4000..=4999 => Self::Card,
+            _ => Self::Etc,
+        }
+    }
+
+    pub fn contains(self, item_id: ItemId) -> bool {
+        self == Self::All || self == Self::from_item_id(item_id)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ItemSearch {
+    pub query: String,
+    pub category: ItemCategory,
+    pub selected: Option<ItemId>,
+}
+
+impl Default for ItemSearch {
+    fn default() -> Self {
+        Self {
+            query: String::new(),
+            category: ItemCategory::All,
+            selected: None,
+        }
+    }
+}
+
+struct ItemDetails {
+    item_id: ItemId,
+    name: String,
+    description: Vec<String>,
+    drop_sources: Vec<String>,
+}
+
+/// Searchable view of the item info tables. Like the monster database, every
+/// search or selection reopens the window with the new contents.
+pub struct ItemSearchWindow {
+    search: ItemSearch,
+    results: Vec<(ItemId, Texture)>,
+    details: Option<ItemDetails>,
+}
+
+impl ItemSearchWindow {
+    pub const WINDOW_CLASS: &'static str = "item_search";
+
+    pub fn new(
+        search: ItemSearch,
+        script_loader: &ScriptLoader,
+        texture_loader: &mut TextureLoader,
+        game_file_loader: &mut GameFileLoader,
+        monster_database: &MonsterDatabase,
+    ) -> Self {
+        let query = search.query.trim().to_lowercase();
+        let query_id = query.parse::<u32>().ok();
+
+        let results = script_loader
+            .get_item_list()
+            .into_iter()
+            .filter(|(item_id, _)| search.category.contains(*item_id))
+            .filter(|(item_id, name)| Some(item_id.0) == query_id || name.to_lowercase().contains(&query))
+            .take(MAXIMUM_RESULTS)
+            .filter_map(|(item_id, _)| {
+                let resource_name = script_loader.get_item_resource_from_id(item_id);
+                let full_path = format!("À¯ÀúÀÎÅÍÆäÀÌ½º\\item\\{}.bmp", resource_name);
+                texture_loader
+                    .get(&full_path, game_file_loader)
+                    .ok()
+                    .map(|texture| (item_id, texture))
+            })
+            .collect();
+
+        let details = search.selected.map(|item_id| ItemDetails {
+            item_id,
+            name: script_loader.get_item_name_from_id(item_id),
+            description: script_loader.get_item_description_from_id(item_id),
+            drop_sources: monster_database
+                .dropped_by(item_id.0)
+                .into_iter()
+                .map(|(monster, rate)| format!("  {} {}.{:02}%", monster.name, rate / 100, rate % 100))
+                .collect(),
+        });
+
+        Self { search, results, details }
+    }
+
+    fn details(details: &ItemDetails) -> Vec<ElementCell> {
+        let mut lines = vec![
+            format!("id: {}", details.item_id.0),
+            format!("category: {}", ItemCategory::from_item_id(details.item_id).name()),
+        ];
+
+        lines.extend(details.description.iter().cloned());
+
+        lines.push("dropped by:".to_string());
+        match details.drop_sources.is_empty() {
+            true => lines.push("  unknown".to_string()),
+            false => lines.extend(details.drop_sources.iter().cloned()),
+        }
+
+        let mut elements = vec![cell!(Headline::new(details.name.clone(), Headline::DEFAULT_SIZE))];
+        elements.extend(lines.into_iter().map(|line| Text::default().with_dynamic_text(line).wrap()));
+        elements
+    }
+}
+
+impl PrototypeWindow for ItemSearchWindow {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: Size) -> Window {
+        let query = Rc::new(RefCell::new(self.search.query.clone()));
+
+        let search_action = {
+            let query = query.clone();
+            let category = self.search.category;
+            move || {
+                Some(ClickAction::Event(UserEvent::SearchItems(ItemSearch {
+                    query: query.borrow().clone(),
+                    category,
+                    selected: None,
+                })))
+            }
+        };
+
+        let mut elements: Vec<ElementCell> = vec![
+            cell!(InputField::<24>::new(
+                query.clone(),
+                "name or id",
+                Box::new(search_action.clone()),
+                dimension!(75%)
+            )),
+            Button::default()
+                .with_static_text("search")
+                .with_action_closure(search_action)
+                .with_width(dimension!(!))
+                .wrap(),
+        ];
+
+        elements.extend(ItemCategory::ALL.into_iter().map(|category| {
+            let query = query.clone();
+            let selected = self.search.category == category;
+
+            StateButton::default()
+                .with_static_text(category.name())
+                .with_selector(move |_| selected)
+                .with_action_closure(move || {
+                    Some(ClickAction::Event(UserEvent::SearchItems(ItemSearch {
+                        query: query.borrow().clone(),
+                        category,
+                        selected: None,
+                    })))
+                })
+                .with_width(dimension!(20%))
+                .wrap()
+        }));
+
+        elements.extend(self.results.iter().map(|(item_id, texture)| {
+            let search = ItemSearch {
+                selected: Some(*item_id),
+                ..self.search.clone()
+            };
+
+            IconButton::new(texture.clone(), UserEvent::SearchItems(search)).wrap()
+        }));
+
+        if let Some(details) = &self.details {
+            elements.extend(Self::details(details));
+        }
+
+        WindowBuilder::default()
+            .with_title("Item Database".to_string())
+            .with_class(Self::WINDOW_CLASS.to_string())
+            .with_size(constraint!(200 > 300 < 400, ?))
+            .with_elements(elements)
+            .closable()
+            .build(window_cache, interface_settings, available_space)
+    }
+}
@@ -0,0 +1,5 @@
+mod item;
+mod monster;
+
+pub use self::item::{ItemCategory, ItemSearch, ItemSearchWindow};
+pub use self::monster::MonsterInfoWindow;
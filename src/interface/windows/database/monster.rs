@@ -0,0 +1,126 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use procedural::*;
+
+use crate::input::UserEvent;
+use crate::interface::*;
+use crate::loaders::{MonsterDatabase, MonsterInfo, ScriptLoader};
+use crate::network::ItemId;
+
+/// Maximum number of search results listed at once.
+const MAXIMUM_RESULTS: usize = 20;
+
+/// Searchable view of the monster database. Searching or selecting a monster
+/// reopens the window with the new contents.
+pub struct MonsterInfoWindow {
+    query: String,
+    results: Vec<(usize, String)>,
+    monster: Option<MonsterInfo>,
+    drop_names: Vec<String>,
+}
+
+impl MonsterInfoWindow {
+    pub const WINDOW_CLASS: &'static str = "monster_info";
+
+    pub fn new(monster_database: &MonsterDatabase, script_loader: &ScriptLoader, query: String, monster_id: Option<usize>) -> Self {
+        let results = monster_database
+            .search(&query)
+            .into_iter()
+            .take(MAXIMUM_RESULTS)
+            .map(|monster| (monster.id, monster.name.clone()))
+            .collect();
+
+        let monster = monster_id.and_then(|monster_id| monster_database.get(monster_id)).cloned();
+        let drop_names = monster
+            .iter()
+            .flat_map(|monster| monster.drops.iter())
+            .map(|drop| script_loader.get_item_name_from_id(ItemId(drop.item_id)))
+            .collect();
+
+        Self {
+            query,
+            results,
+            monster,
+            drop_names,
+        }
+    }
+
+    fn details(&self, monster: &MonsterInfo) -> Vec<ElementCell> {
+        let mut lines = vec![
+            format!("id: {}", monster.id),
+            format!("level: {}", monster.level),
+            format!("health: {}", monster.health_points),
+            format!("experience: {} / {}", monster.base_experience, monster.job_experience),
+            format!("attack: {} - {}", monster.minimum_attack, monster.maximum_attack),
+            format!("defense: {} / {}", monster.defense, monster.magic_defense),
+            format!("element: {:?} {}", monster.element, monster.element_level),
+            format!("race: {:?}", monster.race),
+            format!("size: {:?}", monster.size),
+        ];
+
+        lines.push("drops:".to_string());
+        lines.extend(
+            monster
+                .drops
+                .iter()
+                .zip(self.drop_names.iter())
+                .map(|(drop, name)| format!("  {} {}.{:02}%", name, drop.rate / 100, drop.rate % 100)),
+        );
+
+        lines.push("spawns:".to_string());
+        lines.extend(monster.spawn_maps.iter().map(|map_name| format!("  {}", map_name)));
+
+        let mut elements = vec![cell!(Headline::new(monster.name.clone(), Headline::DEFAULT_SIZE))];
+        elements.extend(lines.into_iter().map(|line| Text::default().with_dynamic_text(line).wrap()));
+        elements
+    }
+}
+
+impl PrototypeWindow for MonsterInfoWindow {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: Size) -> Window {
+        let query = Rc::new(RefCell::new(self.query.clone()));
+
+        let search_action = {
+            let query = query.clone();
+            move || Some(ClickAction::Event(UserEvent::SearchMonsters(query.borrow().clone())))
+        };
+
+        let mut elements: Vec<ElementCell> = vec![
+            cell!(InputField::<24>::new(
+                query,
+                "name or id",
+                Box::new(search_action.clone()),
+                dimension!(75%)
+            )),
+            Button::default()
+                .with_static_text("search")
+                .with_action_closure(search_action)
+                .with_width(dimension!(!))
+                .wrap(),
+        ];
+
+        elements.extend(self.results.iter().map(|(monster_id, name)| {
+            Button::default()
+                .with_dynamic_text(name.clone())
+                .with_event(UserEvent::OpenMonsterInfo(*monster_id))
+                .wrap()
+        }));
+
+        if let Some(monster) = &self.monster {
+            elements.extend(self.details(monster));
+        }
+
+        WindowBuilder::default()
+            .with_title("Monster Database".to_string())
+            .with_class(Self::WINDOW_CLASS.to_string())
+            .with_size(constraint!(200 > 300 < 400, ?))
+            .with_elements(elements)
+            .closable()
+            .build(window_cache, interface_settings, available_space)
+    }
+}
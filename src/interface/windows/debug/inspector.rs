@@ -0,0 +1,60 @@
+use cgmath::Vector2;
+use procedural::*;
+
+use crate::input::UserEvent;
+use crate::interface::*;
+
+/// Amount that the inspected element grows or shrinks per click.
+const TWEAK_STEP: f32 = 10.0;
+
+pub struct InspectorWindow {
+    lines: Remote<Vec<String>>,
+}
+
+impl InspectorWindow {
+    pub const WINDOW_CLASS: &'static str = "inspector";
+
+    pub fn new(lines: Remote<Vec<String>>) -> Self {
+        Self { lines }
+    }
+}
+
+impl PrototypeWindow for InspectorWindow {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: Size) -> Window {
+        let tweak_button = |text: &'static str, growth: Vector2<f32>| {
+            Button::default()
+                .with_static_text(text)
+                .with_event(UserEvent::TweakInspectedElement(growth))
+                .with_width(dimension!(25%))
+                .wrap()
+        };
+
+        let elements: Vec<ElementCell> = vec![
+            Button::default()
+                .with_static_text("pick element")
+                .with_event(UserEvent::PickInspectedElement)
+                .wrap(),
+            InspectorView::new(self.lines.clone()).wrap(),
+            tweak_button("wider", Vector2::new(TWEAK_STEP, 0.0)),
+            tweak_button("narrower", Vector2::new(-TWEAK_STEP, 0.0)),
+            tweak_button("taller", Vector2::new(0.0, TWEAK_STEP)),
+            tweak_button("shorter", Vector2::new(0.0, -TWEAK_STEP)),
+            Button::default()
+                .with_static_text("reset size")
+                .with_event(UserEvent::ResetInspectedElement)
+                .wrap(),
+        ];
+
+        WindowBuilder::default()
+            .with_title("Inspector".to_string())
+            .with_class(Self::WINDOW_CLASS.to_string())
+            .with_size(constraint!(300 > 400 < 600, ?))
+            .with_elements(elements)
+            .closable()
+            .build(window_cache, interface_settings, available_space)
+    }
+}
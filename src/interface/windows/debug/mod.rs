@@ -1,11 +1,19 @@
+mod inspector;
 mod maps;
 #[cfg(feature = "debug_network")]
+mod network;
+#[cfg(feature = "debug_network")]
 mod packet;
 mod profiler;
+mod statistics;
 mod time;
 
+pub use self::inspector::InspectorWindow;
 pub use self::maps::MapsWindow;
 #[cfg(feature = "debug_network")]
+pub use self::network::NetworkStatisticsWindow;
+#[cfg(feature = "debug_network")]
 pub use self::packet::PacketWindow;
 pub use self::profiler::ProfilerWindow;
+pub use self::statistics::RenderStatisticsWindow;
 pub use self::time::TimeWindow;
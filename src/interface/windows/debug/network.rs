@@ -0,0 +1,43 @@
+use procedural::*;
+
+use crate::interface::*;
+use crate::network::NetworkStatistics;
+
+pub struct NetworkStatisticsWindow {
+    statistics: Remote<NetworkStatistics>,
+}
+
+impl NetworkStatisticsWindow {
+    pub const WINDOW_CLASS: &'static str = "network_statistics";
+
+    pub fn new(statistics: Remote<NetworkStatistics>) -> Self {
+        Self { statistics }
+    }
+}
+
+impl PrototypeWindow for NetworkStatisticsWindow {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: Size) -> Window {
+        let elements: Vec<ElementCell> = vec![
+            cell!(Headline::new("bytes per second".to_string(), Headline::DEFAULT_SIZE)),
+            TrafficGraph::new(self.statistics.clone(), TrafficMetric::Bytes).wrap(),
+            cell!(Headline::new("packets per second".to_string(), Headline::DEFAULT_SIZE)),
+            TrafficGraph::new(self.statistics.clone(), TrafficMetric::Packets).wrap(),
+            cell!(Headline::new("serialization time".to_string(), Headline::DEFAULT_SIZE)),
+            TrafficGraph::new(self.statistics.clone(), TrafficMetric::SerializationTime).wrap(),
+            cell!(Headline::new("top packets".to_string(), Headline::DEFAULT_SIZE)),
+            PacketTotalsView::new(self.statistics.clone()).wrap(),
+        ];
+
+        WindowBuilder::default()
+            .with_title("Network statistics".to_string())
+            .with_class(Self::WINDOW_CLASS.to_string())
+            .with_size(constraint!(300 > 400 < 600, ?))
+            .with_elements(elements)
+            .closable()
+            .build(window_cache, interface_settings, available_space)
+    }
+}
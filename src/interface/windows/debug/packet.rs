@@ -1,3 +1,6 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
 use procedural::*;
 
 use crate::interface::*;
@@ -32,12 +35,15 @@ impl PrototypeWindow for PacketWindow {
     }
 
     fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: Size) -> Window {
+        let filter = Rc::new(RefCell::new(String::new()));
+
         let elements: Vec<ElementCell> = vec![
             PacketView::new(
                 self.packets.clone(),
                 self.cleared.new_remote(),
                 self.show_pings.new_remote(),
                 self.update.new_remote(),
+                filter.clone(),
             )
             .wrap(),
         ];
@@ -76,6 +82,12 @@ impl PrototypeWindow for PacketWindow {
                 .with_closure(self.update.toggle_action())
                 .with_width(dimension!(!))
                 .wrap(),
+            cell!(InputField::<32>::new(
+                filter,
+                "filter by name or id",
+                Box::new(|| None),
+                dimension!(100%)
+            )),
             cell!(ScrollView::new(elements, constraint!(100%, ?))),
         ];
 
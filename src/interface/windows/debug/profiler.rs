@@ -1,6 +1,7 @@
 use procedural::*;
 
-use crate::interface::{InterfaceSettings, PrototypeWindow, Size, Window, WindowBuilder, WindowCache};
+use crate::debug::load_timings;
+use crate::interface::*;
 
 #[derive(Default)]
 pub struct ProfilerWindow {}
@@ -15,10 +16,23 @@ impl PrototypeWindow for ProfilerWindow {
     }
 
     fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: Size) -> Window {
+        let mut elements: Vec<ElementCell> = vec![
+            cell!(Headline::new("allocations".to_string(), Headline::DEFAULT_SIZE)),
+            AllocationView::default().wrap(),
+            cell!(Headline::new("load times".to_string(), Headline::DEFAULT_SIZE)),
+        ];
+
+        elements.extend(load_timings().into_iter().map(|(name, duration)| {
+            Text::default()
+                .with_dynamic_text(format!("{} ^FFBB00{:.2}ms", name, duration.as_secs_f64() * 1000.0))
+                .wrap()
+        }));
+
         WindowBuilder::default()
             .with_title("Profiler".to_string())
             .with_class(Self::WINDOW_CLASS.to_string())
-            .with_size(constraint!(200 > 250 < 300, ?))
+            .with_size(constraint!(200 > 300 < 400, ?))
+            .with_elements(elements)
             .closable()
             .build(window_cache, interface_settings, available_space)
     }
@@ -0,0 +1,39 @@
+use std::sync::Arc;
+
+use procedural::*;
+
+use crate::graphics::MemoryAllocator;
+use crate::interface::*;
+
+pub struct RenderStatisticsWindow {
+    memory_allocator: Arc<MemoryAllocator>,
+}
+
+impl RenderStatisticsWindow {
+    pub const WINDOW_CLASS: &'static str = "render_statistics";
+
+    pub fn new(memory_allocator: Arc<MemoryAllocator>) -> Self {
+        Self { memory_allocator }
+    }
+}
+
+impl PrototypeWindow for RenderStatisticsWindow {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: Size) -> Window {
+        let elements: Vec<ElementCell> = vec![
+            cell!(Headline::new("memory".to_string(), Headline::DEFAULT_SIZE)),
+            MemoryView::new(self.memory_allocator.clone()).wrap(),
+        ];
+
+        WindowBuilder::default()
+            .with_title("Render statistics".to_string())
+            .with_class(Self::WINDOW_CLASS.to_string())
+            .with_size(constraint!(200 > 300 < 400, ?))
+            .with_elements(elements)
+            .closable()
+            .build(window_cache, interface_settings, available_space)
+    }
+}
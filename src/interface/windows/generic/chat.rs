@@ -8,12 +8,15 @@ use procedural::*;
 use crate::input::UserEvent;
 use crate::interface::*;
 use crate::loaders::FontLoader;
-use crate::network::ChatMessage;
+use crate::network::{ChatMessage, ChatTab};
 
 #[derive(new)]
 pub struct ChatWindow {
     messages: Rc<RefCell<Vec<ChatMessage>>>,
     font_loader: Rc<RefCell<FontLoader>>,
+    show_timestamps: TrackedState<bool>,
+    tabs: TrackedState<Vec<ChatTab>>,
+    selected_tab: TrackedState<usize>,
 }
 
 impl ChatWindow {
@@ -55,6 +58,7 @@ impl PrototypeWindow for ChatWindow {
         };
 
         let elements: Vec<ElementCell> = vec![
+            ChatTabBar::new(self.tabs.new_remote(), self.selected_tab.clone()).wrap(),
             cell!(InputField::<30>::new(
                 input_text,
                 "write message or command",
@@ -68,7 +72,13 @@ impl PrototypeWindow for ChatWindow {
                 .with_width(dimension!(25%))
                 .wrap(),
             cell!(ScrollView::new(
-                vec![cell!(Chat::new(self.messages.clone(), self.font_loader.clone()))],
+                vec![cell!(Chat::new(
+                    self.messages.clone(),
+                    self.font_loader.clone(),
+                    self.show_timestamps.new_remote(),
+                    self.tabs.new_remote(),
+                    self.selected_tab.new_remote()
+                ))],
                 constraint!(100%, ?)
             )),
         ];
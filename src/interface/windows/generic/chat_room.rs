@@ -0,0 +1,164 @@
+use std::cell::RefCell;
+use std::ops::Not;
+use std::rc::Rc;
+
+use procedural::*;
+
+use crate::input::UserEvent;
+use crate::interface::*;
+use crate::loaders::FontLoader;
+use crate::network::{ChatChannel, ChatMessage, ChatRoom};
+
+/// Opened when clicking the owner of a chat room. Private rooms ask for the
+/// password before entering.
+pub struct ChatRoomJoinWindow {
+    chat_room: ChatRoom,
+}
+
+impl ChatRoomJoinWindow {
+    pub const WINDOW_CLASS: &'static str = "chat_room_join";
+
+    pub fn new(chat_room: ChatRoom) -> Self {
+        Self { chat_room }
+    }
+}
+
+impl PrototypeWindow for ChatRoomJoinWindow {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: Size) -> Window {
+        let password = Rc::new(RefCell::new(String::new()));
+        let room_id = self.chat_room.room_id;
+        let private = self.chat_room.private;
+
+        let selector = {
+            let password = password.clone();
+            move || !private || !password.borrow().is_empty()
+        };
+
+        let action = {
+            let password = password.clone();
+            move || Some(ClickAction::Event(UserEvent::EnterChatRoom(room_id, password.borrow().clone())))
+        };
+
+        let password_action = {
+            let password = password.clone();
+
+            Box::new(move || {
+                password
+                    .borrow()
+                    .is_empty()
+                    .not()
+                    .then(|| ClickAction::Event(UserEvent::EnterChatRoom(room_id, password.borrow().clone())))
+            })
+        };
+
+        let mut elements: Vec<ElementCell> = vec![
+            Text::default().with_dynamic_text(self.chat_room.title.clone()).wrap(),
+            Text::default()
+                .with_dynamic_text(format!("{} of {} members", self.chat_room.users, self.chat_room.limit))
+                .wrap(),
+        ];
+
+        if private {
+            elements.push(cell!(InputField::<8, true>::new(
+                password,
+                "password",
+                password_action,
+                dimension!(100%)
+            )));
+        }
+
+        elements.push(
+            Button::default()
+                .with_static_text("enter")
+                .with_disabled_selector(selector)
+                .with_action_closure(action)
+                .wrap(),
+        );
+
+        WindowBuilder::default()
+            .with_title("Chat Room".to_string())
+            .with_class(Self::WINDOW_CLASS.to_string())
+            .with_size(constraint!(200 > 250 < 300, ?))
+            .with_elements(elements)
+            .closable()
+            .build(window_cache, interface_settings, available_space)
+    }
+}
+
+/// The conversation inside of the chat room the player is in. Messages sent
+/// from here or from the main chat go to the room until the player leaves it.
+pub struct ChatRoomWindow {
+    title: String,
+    messages: Rc<RefCell<Vec<ChatMessage>>>,
+    font_loader: Rc<RefCell<FontLoader>>,
+    show_timestamps: TrackedState<bool>,
+}
+
+impl ChatRoomWindow {
+    pub const WINDOW_CLASS: &'static str = "chat_room";
+
+    pub fn new(
+        title: String,
+        messages: Rc<RefCell<Vec<ChatMessage>>>,
+        font_loader: Rc<RefCell<FontLoader>>,
+        show_timestamps: TrackedState<bool>,
+    ) -> Self {
+        Self {
+            title,
+            messages,
+            font_loader,
+            show_timestamps,
+        }
+    }
+}
+
+impl PrototypeWindow for ChatRoomWindow {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: Size) -> Window {
+        let input_text = Rc::new(RefCell::new(String::new()));
+
+        let input_action = {
+            let input_text = input_text.clone();
+
+            Box::new(move || {
+                let message: String = input_text.borrow_mut().drain(..).collect();
+                message.is_empty().not().then(|| ClickAction::Event(UserEvent::SendMessage(message)))
+            })
+        };
+
+        let elements: Vec<ElementCell> = vec![
+            cell!(InputField::<30>::new(input_text, "write message", input_action, dimension!(75%))) as _,
+            Button::default()
+                .with_static_text("leave")
+                .with_event(UserEvent::LeaveChatRoom)
+                .with_width(dimension!(25%))
+                .wrap(),
+            cell!(ScrollView::new(
+                vec![cell!(Chat::channel(
+                    self.messages.clone(),
+                    self.font_loader.clone(),
+                    self.show_timestamps.new_remote(),
+                    ChatChannel::Room
+                ))],
+                constraint!(100%, ?)
+            )),
+        ];
+
+        // Not closable, since the player has to leave the room for the window to go
+        // away.
+        WindowBuilder::default()
+            .with_title(self.title.clone())
+            .with_class(Self::WINDOW_CLASS.to_string())
+            .with_size(constraint!(200 > 300 < 500, 100 > 200 < 400))
+            .with_background_color(Box::new(|theme| *theme.chat.background_color))
+            .with_elements(elements)
+            .build(window_cache, interface_settings, available_space)
+    }
+}
@@ -0,0 +1,200 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use derive_new::new;
+use procedural::*;
+
+use crate::interface::*;
+use crate::network::{ChatChannel, ChatTab};
+
+/// Edits whichever chat tab is currently selected, so the window stays useful
+/// while switching between tabs.
+#[derive(new)]
+pub struct ChatTabWindow {
+    tabs: TrackedState<Vec<ChatTab>>,
+    selected_tab: TrackedState<usize>,
+}
+
+impl ChatTabWindow {
+    pub const WINDOW_CLASS: &'static str = "chat_tab";
+}
+
+impl PrototypeWindow for ChatTabWindow {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: Size) -> Window {
+        let current_name = self
+            .tabs
+            .borrow()
+            .get(*self.selected_tab.borrow())
+            .map(|tab| tab.name.clone())
+            .unwrap_or_default();
+        let name_text = Rc::new(RefCell::new(current_name));
+
+        let rename_action = {
+            let name_text = name_text.clone();
+            let tabs = self.tabs.clone();
+            let selected_tab = self.selected_tab.clone();
+
+            move || {
+                let name = name_text.borrow().clone();
+                let index = *selected_tab.borrow();
+
+                if !name.is_empty() {
+                    tabs.clone().with_mut(|tabs, changed| {
+                        if let Some(tab) = tabs.get_mut(index) {
+                            tab.name = name;
+                            changed();
+                        }
+                    });
+                }
+
+                None
+            }
+        };
+
+        let mut elements: Vec<ElementCell> = vec![
+            cell!(Headline::new("name".to_string(), Headline::DEFAULT_SIZE)),
+            cell!(InputField::<20>::new(
+                name_text,
+                "tab name",
+                Box::new(rename_action.clone()),
+                dimension!(75%)
+            )),
+            Button::default()
+                .with_static_text("rename")
+                .with_action_closure(rename_action)
+                .with_width(dimension!(!))
+                .wrap(),
+            cell!(Headline::new("channels".to_string(), Headline::DEFAULT_SIZE)),
+        ];
+
+        elements.extend(ChatChannel::ALL.into_iter().map(|channel| {
+            let selector = {
+                let tabs = self.tabs.clone();
+                let selected_tab = self.selected_tab.clone();
+
+                move |_: &StateProvider| tabs.borrow().get(*selected_tab.borrow()).map_or(false, |tab| tab.shows(channel))
+            };
+
+            let closure = {
+                let mut tabs = self.tabs.clone();
+                let selected_tab = self.selected_tab.clone();
+
+                move || {
+                    let index = *selected_tab.borrow();
+
+                    tabs.with_mut(|tabs, changed| {
+                        if let Some(tab) = tabs.get_mut(index) {
+                            tab.toggle_channel(channel);
+                            changed();
+                        }
+                    });
+                }
+            };
+
+            StateButton::default()
+                .with_static_text(channel.name())
+                .with_selector(selector)
+                .with_closure(closure)
+                .wrap()
+        }));
+
+        let move_left = {
+            let mut tabs = self.tabs.clone();
+            let mut selected_tab = self.selected_tab.clone();
+
+            move || {
+                let index = *selected_tab.borrow();
+
+                if index > 0 {
+                    tabs.with_mut(|tabs, changed| {
+                        tabs.swap(index, index - 1);
+                        changed();
+                    });
+                    selected_tab.set(index - 1);
+                }
+            }
+        };
+
+        let move_right = {
+            let mut tabs = self.tabs.clone();
+            let mut selected_tab = self.selected_tab.clone();
+
+            move || {
+                let index = *selected_tab.borrow();
+
+                if index + 1 < tabs.len() {
+                    tabs.with_mut(|tabs, changed| {
+                        tabs.swap(index, index + 1);
+                        changed();
+                    });
+                    selected_tab.set(index + 1);
+                }
+            }
+        };
+
+        let add_tab = {
+            let mut tabs = self.tabs.clone();
+            let mut selected_tab = self.selected_tab.clone();
+
+            move || {
+                let index = tabs.len();
+
+                tabs.push(ChatTab::new(format!("tab {}", index + 1)));
+                selected_tab.set(index);
+            }
+        };
+
+        let remove_tab = {
+            let mut tabs = self.tabs.clone();
+            let mut selected_tab = self.selected_tab.clone();
+
+            move || {
+                let index = *selected_tab.borrow();
+
+                // there always has to be at least one tab
+                if tabs.len() > 1 && index < tabs.len() {
+                    tabs.with_mut(|tabs, changed| {
+                        tabs.remove(index);
+                        changed();
+                    });
+                    selected_tab.set(index.saturating_sub(1));
+                }
+            }
+        };
+
+        elements.extend([
+            Button::default()
+                .with_static_text("move left")
+                .with_closure(move_left)
+                .with_width(dimension!(50%))
+                .wrap(),
+            Button::default()
+                .with_static_text("move right")
+                .with_closure(move_right)
+                .with_width(dimension!(!))
+                .wrap(),
+            Button::default()
+                .with_static_text("new tab")
+                .with_closure(add_tab)
+                .with_width(dimension!(50%))
+                .wrap(),
+            Button::default()
+                .with_static_text("remove tab")
+                .with_closure(remove_tab)
+                .with_width(dimension!(!))
+                .wrap(),
+        ]);
+
+        WindowBuilder::default()
+            .with_title("Chat Tab".to_string())
+            .with_class(Self::WINDOW_CLASS.to_string())
+            .with_size(constraint!(200 > 250 < 300, ?))
+            .with_elements(elements)
+            .closable()
+            .build(window_cache, interface_settings, available_space)
+    }
+}
@@ -0,0 +1,40 @@
+use derive_new::new;
+use procedural::*;
+
+use crate::input::UserEvent;
+use crate::interface::*;
+use crate::system::Alarm;
+
+#[derive(new)]
+pub struct ClockWindow {
+    server_offset_minutes: Remote<i32>,
+    alarms: Remote<Vec<Alarm>>,
+}
+
+impl ClockWindow {
+    pub const WINDOW_CLASS: &'static str = "clock";
+}
+
+impl PrototypeWindow for ClockWindow {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: Size) -> Window {
+        let elements = vec![
+            ClockDisplay::new(self.server_offset_minutes.clone(), self.alarms.clone()).wrap(),
+            Button::default()
+                .with_static_text("alarms")
+                .with_event(UserEvent::OpenClockSettingsWindow)
+                .wrap(),
+        ];
+
+        WindowBuilder::default()
+            .with_title("Clock".to_string())
+            .with_class(Self::WINDOW_CLASS.to_string())
+            .with_size(constraint!(150 > 200 < 300, ?))
+            .with_elements(elements)
+            .closable()
+            .build(window_cache, interface_settings, available_space)
+    }
+}
@@ -0,0 +1,24 @@
+use derive_new::new;
+use procedural::*;
+
+use crate::interface::*;
+
+/// Popup with a [ContextMenu], opened at the mouse.
+#[derive(new)]
+pub struct ContextMenuWindow {
+    position: Position,
+    entries: Vec<ContextMenuEntry>,
+}
+
+impl PrototypeWindow for ContextMenuWindow {
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: Size) -> Window {
+        let elements: Vec<ElementCell> = vec![ContextMenu::new(&self.entries).wrap()];
+
+        WindowBuilder::default()
+            .with_size(constraint!(150 > 180 < 250, ?))
+            .with_elements(elements)
+            .with_default_position(self.position)
+            .popup()
+            .build(window_cache, interface_settings, available_space)
+    }
+}
@@ -25,6 +25,46 @@ impl PrototypeWindow for MenuWindow {
                 .with_static_text("audio settings")
                 .with_event(UserEvent::OpenAudioSettingsWindow)
                 .wrap(),
+            Button::default()
+                .with_static_text("chat settings")
+                .with_event(UserEvent::OpenChatSettingsWindow)
+                .wrap(),
+            Button::default()
+                .with_static_text("controls")
+                .with_event(UserEvent::OpenControlsWindow)
+                .wrap(),
+            Button::default()
+                .with_static_text("pet")
+                .with_event(UserEvent::OpenPetStatusWindow)
+                .wrap(),
+            Button::default()
+                .with_static_text("clock")
+                .with_event(UserEvent::OpenClockWindow)
+                .wrap(),
+            Button::default()
+                .with_static_text("minimap")
+                .with_event(UserEvent::OpenMinimapWindow)
+                .wrap(),
+            Button::default()
+                .with_static_text("world map")
+                .with_event(UserEvent::OpenWorldMapWindow)
+                .wrap(),
+            Button::default()
+                .with_static_text("session statistics")
+                .with_event(UserEvent::OpenSessionStatsWindow)
+                .wrap(),
+            Button::default()
+                .with_static_text("monster database")
+                .with_event(UserEvent::SearchMonsters(String::new()))
+                .wrap(),
+            Button::default()
+                .with_static_text("item database")
+                .with_event(UserEvent::SearchItems(ItemSearch::default()))
+                .wrap(),
+            Button::default()
+                .with_static_text("recover windows")
+                .with_event(UserEvent::MoveWindowsOnScreen)
+                .wrap(),
             #[cfg(feature = "debug")]
             Button::default()
                 .with_static_text("render settings")
@@ -51,8 +91,8 @@ impl PrototypeWindow for MenuWindow {
                 .wrap(),
             #[cfg(feature = "debug")]
             Button::default()
-                .with_static_text("theme viewer")
-                .with_event(UserEvent::OpenThemeViewerWindow)
+                .with_static_text("theme editor")
+                .with_event(UserEvent::OpenThemeEditorWindow)
                 .with_foreground_color(|theme| *theme.button.debug_foreground_color)
                 .wrap(),
             #[cfg(feature = "debug")]
@@ -61,12 +101,36 @@ impl PrototypeWindow for MenuWindow {
                 .with_event(UserEvent::OpenProfilerWindow)
                 .with_foreground_color(|theme| *theme.button.debug_foreground_color)
                 .wrap(),
+            #[cfg(feature = "debug")]
+            Button::default()
+                .with_static_text("render statistics")
+                .with_event(UserEvent::OpenRenderStatisticsWindow)
+                .with_foreground_color(|theme| *theme.button.debug_foreground_color)
+                .wrap(),
             #[cfg(feature = "debug_network")]
             Button::default()
                 .with_static_text("packets")
                 .with_event(UserEvent::OpenPacketWindow)
                 .with_foreground_color(|theme| *theme.button.debug_foreground_color)
                 .wrap(),
+            #[cfg(feature = "debug_network")]
+            Button::default()
+                .with_static_text("network statistics")
+                .with_event(UserEvent::OpenNetworkStatisticsWindow)
+                .with_foreground_color(|theme| *theme.button.debug_foreground_color)
+                .wrap(),
+            #[cfg(feature = "debug")]
+            Button::default()
+                .with_static_text("inspector")
+                .with_event(UserEvent::OpenInspectorWindow)
+                .with_foreground_color(|theme| *theme.button.debug_foreground_color)
+                .wrap(),
+            #[cfg(feature = "debug")]
+            Button::default()
+                .with_static_text("save snapshot")
+                .with_event(UserEvent::SaveSnapshot)
+                .with_foreground_color(|theme| *theme.button.debug_foreground_color)
+                .wrap(),
             Button::default().with_static_text("log out").with_event(UserEvent::LogOut).wrap(),
             Button::default().with_static_text("exit").with_event(UserEvent::Exit).wrap(),
         ];
@@ -0,0 +1,34 @@
+use derive_new::new;
+use procedural::*;
+
+use crate::interface::*;
+use crate::network::MapLink;
+use crate::world::MinimapState;
+
+#[derive(new)]
+pub struct MinimapWindow {
+    minimap_state: Remote<MinimapState>,
+    waypoint: Remote<Option<MapLink>>,
+}
+
+impl MinimapWindow {
+    pub const WINDOW_CLASS: &'static str = "minimap";
+}
+
+impl PrototypeWindow for MinimapWindow {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: Size) -> Window {
+        let elements = vec![Minimap::new(self.minimap_state.clone(), self.waypoint.clone()).wrap()];
+
+        WindowBuilder::default()
+            .with_title("Minimap".to_string())
+            .with_class(Self::WINDOW_CLASS.to_string())
+            .with_size(constraint!(150 > 200 < 400, ?))
+            .with_elements(elements)
+            .closable()
+            .build(window_cache, interface_settings, available_space)
+    }
+}
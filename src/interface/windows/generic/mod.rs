@@ -1,9 +1,29 @@
 mod chat;
+mod chat_room;
+mod chat_tab;
+mod clock;
+mod context_menu;
 mod dialog;
 mod error;
 mod menu;
+mod minimap;
+mod options;
+mod pvp;
+mod trade;
+mod whisper;
+mod world_map;
 
 pub use self::chat::ChatWindow;
+pub use self::chat_room::{ChatRoomJoinWindow, ChatRoomWindow};
+pub use self::chat_tab::ChatTabWindow;
+pub use self::clock::ClockWindow;
+pub use self::context_menu::ContextMenuWindow;
 pub use self::dialog::DialogWindow;
 pub use self::error::ErrorWindow;
 pub use self::menu::MenuWindow;
+pub use self::minimap::MinimapWindow;
+pub use self::options::WindowOptionsWindow;
+pub use self::pvp::PvpConfirmationWindow;
+pub use self::trade::{TradeRequestWindow, TradeWindow};
+pub use self::whisper::WhisperWindow;
+pub use self::world_map::WorldMapWindow;
@@ -0,0 +1,49 @@
+use derive_new::new;
+use procedural::*;
+
+use crate::input::UserEvent;
+use crate::interface::*;
+
+#[derive(new)]
+pub struct WindowOptionsWindow {
+    window_class: String,
+}
+
+impl WindowOptionsWindow {
+    pub const WINDOW_CLASS: &'static str = "window_options";
+}
+
+impl PrototypeWindow for WindowOptionsWindow {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: Size) -> Window {
+        let opacity_button = |text: &'static str, opacity: f32, width_constraint: DimensionConstraint| {
+            Button::default()
+                .with_static_text(text)
+                .with_event(UserEvent::SetWindowOpacity(self.window_class.clone(), opacity))
+                .with_width(width_constraint)
+                .wrap()
+        };
+
+        let elements: Vec<ElementCell> = vec![
+            cell!(Headline::new("background opacity".to_string(), Headline::DEFAULT_SIZE)),
+            opacity_button("100%", 1.0, dimension!(33.33%)),
+            opacity_button("75%", 0.75, dimension!(33.33%)),
+            opacity_button("50%", 0.5, dimension!(!)),
+            Button::default()
+                .with_static_text("toggle click-through")
+                .with_event(UserEvent::ToggleWindowClickThrough(self.window_class.clone()))
+                .wrap(),
+        ];
+
+        WindowBuilder::default()
+            .with_title(format!("Options ({})", self.window_class))
+            .with_class(Self::WINDOW_CLASS.to_string())
+            .with_size(constraint!(200 > 250 < 300, ?))
+            .with_elements(elements)
+            .closable()
+            .build(window_cache, interface_settings, available_space)
+    }
+}
@@ -0,0 +1,55 @@
+use procedural::*;
+
+use crate::combat::PvpMode;
+use crate::graphics::Color;
+use crate::input::UserEvent;
+use crate::interface::*;
+
+/// Opened when entering a map where players can fight each other. Lets the
+/// player decide if clicking other players should attack them.
+pub struct PvpConfirmationWindow {
+    pvp_mode: PvpMode,
+}
+
+impl PvpConfirmationWindow {
+    pub const WINDOW_CLASS: &'static str = "pvp_confirmation";
+
+    pub fn new(pvp_mode: PvpMode) -> Self {
+        Self { pvp_mode }
+    }
+}
+
+impl PrototypeWindow for PvpConfirmationWindow {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: Size) -> Window {
+        let message = format!(
+            "You entered a {} map. Other players can attack you here.",
+            self.pvp_mode.display_name()
+        );
+
+        let elements: Vec<ElementCell> = vec![
+            Text::default()
+                .with_dynamic_text(message)
+                .with_foreground_color(|_| Color::rgb(255, 200, 50))
+                .wrap(),
+            Button::default()
+                .with_static_text("attack players on click")
+                .with_event(UserEvent::SetPvpAggressive(true))
+                .wrap(),
+            Button::default()
+                .with_static_text("stay passive")
+                .with_event(UserEvent::SetPvpAggressive(false))
+                .wrap(),
+        ];
+
+        WindowBuilder::default()
+            .with_title("Player versus Player".to_string())
+            .with_class(Self::WINDOW_CLASS.to_string())
+            .with_size(constraint!(250 > 300 < 400, ?))
+            .with_elements(elements)
+            .build(window_cache, interface_settings, available_space)
+    }
+}
@@ -0,0 +1,145 @@
+use procedural::*;
+
+use crate::input::UserEvent;
+use crate::interface::*;
+use crate::inventory::{Item, Trade, TRADE_SLOT_COUNT};
+
+/// Opened when another player asks the player to trade.
+pub struct TradeRequestWindow {
+    name: String,
+}
+
+impl TradeRequestWindow {
+    pub const WINDOW_CLASS: &'static str = "trade_request";
+
+    pub fn new(name: String) -> Self {
+        Self { name }
+    }
+}
+
+impl PrototypeWindow for TradeRequestWindow {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: Size) -> Window {
+        let elements = vec![
+            Text::default().with_dynamic_text(format!("{} wants to trade", self.name)).wrap(),
+            Button::default()
+                .with_static_text("accept")
+                .with_event(UserEvent::RespondToTradeRequest(true))
+                .with_width(dimension!(50%))
+                .wrap(),
+            Button::default()
+                .with_static_text("decline")
+                .with_event(UserEvent::RespondToTradeRequest(false))
+                .with_width(dimension!(50%))
+                .wrap(),
+        ];
+
+        WindowBuilder::default()
+            .with_title("Trade Request".to_string())
+            .with_class(Self::WINDOW_CLASS.to_string())
+            .with_size(constraint!(200 > 250 < 300, ?))
+            .with_elements(elements)
+            .build(window_cache, interface_settings, available_space)
+    }
+}
+
+/// The offers of both players in the current trade. Items are offered by
+/// dropping them onto the lower grid. The window is opened again whenever
+/// one of the players locks their offer.
+pub struct TradeWindow {
+    partner_name: String,
+    items: TrackedState<Vec<Item>>,
+    partner_items: TrackedState<Vec<Item>>,
+    partner_zeny: u32,
+    locked: bool,
+    partner_locked: bool,
+}
+
+impl TradeWindow {
+    pub const WINDOW_CLASS: &'static str = "trade";
+
+    pub fn new(trade: &Trade) -> Self {
+        Self {
+            partner_name: trade.get_partner_name().to_owned(),
+            items: trade.get_item_state(),
+            partner_items: trade.get_partner_item_state(),
+            partner_zeny: trade.get_partner_zeny(),
+            locked: trade.is_locked(),
+            partner_locked: trade.is_partner_locked(),
+        }
+    }
+}
+
+impl PrototypeWindow for TradeWindow {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: Size) -> Window {
+        let locked = self.locked;
+        let both_locked = self.locked && self.partner_locked;
+        let lock_state = |locked: bool| match locked {
+            true => " (locked)",
+            false => "",
+        };
+
+        let mut elements = vec![
+            Text::default()
+                .with_dynamic_text(format!("{}{}", self.partner_name, lock_state(self.partner_locked)))
+                .wrap(),
+            ItemGrid::with_slot_count(
+                self.partner_items.new_remote(),
+                TrackedState::default().new_remote(),
+                ItemSource::TradePartner,
+                TRADE_SLOT_COUNT,
+            )
+            .wrap(),
+        ];
+
+        if self.partner_zeny > 0 {
+            elements.push(Text::default().with_dynamic_text(format!("{} zeny", self.partner_zeny)).wrap());
+        }
+
+        elements.extend([
+            Text::default()
+                .with_dynamic_text(format!("your offer{}", lock_state(self.locked)))
+                .wrap(),
+            ItemGrid::with_slot_count(
+                self.items.new_remote(),
+                TrackedState::default().new_remote(),
+                ItemSource::Trade,
+                TRADE_SLOT_COUNT,
+            )
+            .wrap(),
+            Button::default()
+                .with_static_text("lock")
+                .with_event(UserEvent::LockTrade)
+                .with_disabled_selector(move || !locked)
+                .with_width(dimension!(33%))
+                .wrap(),
+            Button::default()
+                .with_static_text("trade")
+                .with_event(UserEvent::CommitTrade)
+                .with_disabled_selector(move || both_locked)
+                .with_width(dimension!(33%))
+                .wrap(),
+            Button::default()
+                .with_static_text("cancel")
+                .with_event(UserEvent::CancelTrade)
+                .with_width(dimension!(!))
+                .wrap(),
+        ]);
+
+        // Not closable, since the trade has to be cancelled for the window to go
+        // away.
+        WindowBuilder::default()
+            .with_title("Trade".to_string())
+            .with_class(Self::WINDOW_CLASS.to_string())
+            .with_size(constraint!(200 > 250 < 350, ?))
+            .with_elements(elements)
+            .build(window_cache, interface_settings, available_space)
+    }
+}
@@ -0,0 +1,108 @@
+use std::cell::RefCell;
+use std::ops::Not;
+use std::rc::Rc;
+
+use procedural::*;
+
+use crate::input::UserEvent;
+use crate::interface::*;
+use crate::loaders::FontLoader;
+use crate::network::ChatMessage;
+
+/// A conversation with a single player. Every partner gets their own window
+/// class, so multiple conversations can be open at the same time.
+pub struct WhisperWindow {
+    partner: String,
+    window_class: String,
+    messages: Rc<RefCell<Vec<ChatMessage>>>,
+    font_loader: Rc<RefCell<FontLoader>>,
+    show_timestamps: TrackedState<bool>,
+}
+
+impl WhisperWindow {
+    pub const WINDOW_CLASS_PREFIX: &'static str = "whisper_";
+
+    pub fn new(
+        partner: String,
+        messages: Rc<RefCell<Vec<ChatMessage>>>,
+        font_loader: Rc<RefCell<FontLoader>>,
+        show_timestamps: TrackedState<bool>,
+    ) -> Self {
+        let window_class = format!("{}{}", Self::WINDOW_CLASS_PREFIX, partner);
+
+        Self {
+            partner,
+            window_class,
+            messages,
+            font_loader,
+            show_timestamps,
+        }
+    }
+}
+
+impl PrototypeWindow for WhisperWindow {
+    fn window_class(&self) -> Option<&str> {
+        Some(&self.window_class)
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: Size) -> Window {
+        let input_text = Rc::new(RefCell::new(String::new()));
+
+        let button_selector = {
+            let input_text = input_text.clone();
+
+            move || !input_text.borrow().is_empty()
+        };
+
+        let button_action = {
+            let input_text = input_text.clone();
+            let partner = self.partner.clone();
+
+            move || {
+                let message: String = input_text.borrow_mut().drain(..).collect();
+                Some(ClickAction::Event(UserEvent::SendWhisper(partner.clone(), message)))
+            }
+        };
+
+        let input_action = {
+            let input_text = input_text.clone();
+            let partner = self.partner.clone();
+
+            Box::new(move || {
+                let message: String = input_text.borrow_mut().drain(..).collect();
+                message
+                    .is_empty()
+                    .not()
+                    .then(|| ClickAction::Event(UserEvent::SendWhisper(partner.clone(), message)))
+            })
+        };
+
+        let elements: Vec<ElementCell> = vec![
+            cell!(InputField::<30>::new(input_text, "write message", input_action, dimension!(75%))) as _,
+            Button::default()
+                .with_static_text("send")
+                .with_disabled_selector(button_selector)
+                .with_action_closure(button_action)
+                .with_width(dimension!(25%))
+                .wrap(),
+            cell!(ScrollView::new(
+                vec![cell!(Chat::whisper(
+                    self.messages.clone(),
+                    self.font_loader.clone(),
+                    self.show_timestamps.new_remote(),
+                    self.partner.clone()
+                ))],
+                constraint!(100%, ?)
+            )),
+        ];
+
+        WindowBuilder::default()
+            .with_title(self.partner.clone())
+            .with_class(self.window_class.clone())
+            .with_size(constraint!(200 > 300 < 500, 100 > 150 < 400))
+            .with_background_color(Box::new(|theme| *theme.chat.background_color))
+            .with_elements(elements)
+            .closable()
+            .build(window_cache, interface_settings, available_space)
+    }
+}
@@ -0,0 +1,42 @@
+use procedural::*;
+
+use crate::interface::*;
+use crate::loaders::{WorldMapDatabase, WorldMapEntry};
+use crate::network::MapLink;
+
+pub struct WorldMapWindow {
+    maps: Vec<WorldMapEntry>,
+    current_map: Remote<String>,
+    waypoint: Remote<Option<MapLink>>,
+}
+
+impl WorldMapWindow {
+    pub const WINDOW_CLASS: &'static str = "world_map";
+
+    pub fn new(world_map_database: &WorldMapDatabase, current_map: Remote<String>, waypoint: Remote<Option<MapLink>>) -> Self {
+        let maps = world_map_database.get_maps().to_vec();
+        Self {
+            maps,
+            current_map,
+            waypoint,
+        }
+    }
+}
+
+impl PrototypeWindow for WorldMapWindow {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: Size) -> Window {
+        let elements = vec![WorldMap::new(self.maps.clone(), self.current_map.clone(), self.waypoint.clone()).wrap()];
+
+        WindowBuilder::default()
+            .with_title("World Map".to_string())
+            .with_class(Self::WINDOW_CLASS.to_string())
+            .with_size(constraint!(250 > 400 < 700, ?))
+            .with_elements(elements)
+            .closable()
+            .build(window_cache, interface_settings, available_space)
+    }
+}
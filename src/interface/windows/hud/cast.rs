@@ -0,0 +1,31 @@
+use derive_new::new;
+use procedural::*;
+
+use crate::combat::CastState;
+use crate::interface::*;
+
+#[derive(new)]
+pub struct CastBarWindow {
+    cast_state: Remote<CastState>,
+}
+
+impl CastBarWindow {
+    pub const WINDOW_CLASS: &'static str = "hud_cast";
+}
+
+impl PrototypeWindow for CastBarWindow {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: Size) -> Window {
+        let elements = vec![CastBar::new(self.cast_state.clone()).wrap()];
+
+        WindowBuilder::default()
+            .with_class(Self::WINDOW_CLASS.to_string())
+            .with_size(constraint!(100 > 200 < 500, ?))
+            .with_elements(elements)
+            .hud()
+            .build(window_cache, interface_settings, available_space)
+    }
+}
@@ -0,0 +1,31 @@
+use derive_new::new;
+use procedural::*;
+
+use crate::combat::SkillCooldowns;
+use crate::interface::*;
+
+#[derive(new)]
+pub struct CooldownWindow {
+    cooldowns: Remote<SkillCooldowns>,
+}
+
+impl CooldownWindow {
+    pub const WINDOW_CLASS: &'static str = "hud_cooldowns";
+}
+
+impl PrototypeWindow for CooldownWindow {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: Size) -> Window {
+        let elements = vec![CooldownRow::new(self.cooldowns.clone()).wrap()];
+
+        WindowBuilder::default()
+            .with_class(Self::WINDOW_CLASS.to_string())
+            .with_size(constraint!(40 > 280 < 400, ?))
+            .with_elements(elements)
+            .hud()
+            .build(window_cache, interface_settings, available_space)
+    }
+}
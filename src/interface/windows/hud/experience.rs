@@ -0,0 +1,36 @@
+use derive_new::new;
+use procedural::*;
+
+use crate::graphics::Color;
+use crate::interface::*;
+use crate::system::ExperienceTracker;
+
+#[derive(new)]
+pub struct ExperienceBarWindow {
+    base_experience: Remote<ExperienceTracker>,
+    job_experience: Remote<ExperienceTracker>,
+}
+
+impl ExperienceBarWindow {
+    pub const WINDOW_CLASS: &'static str = "hud_experience";
+}
+
+impl PrototypeWindow for ExperienceBarWindow {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: Size) -> Window {
+        let elements = vec![
+            ValueBar::new(self.base_experience.clone(), "base", Color::rgb(163, 140, 67)).wrap(),
+            ValueBar::new(self.job_experience.clone(), "job", Color::rgb(130, 67, 163)).wrap(),
+        ];
+
+        WindowBuilder::default()
+            .with_class(Self::WINDOW_CLASS.to_string())
+            .with_size(constraint!(150 > 300 < 800, ?))
+            .with_elements(elements)
+            .hud()
+            .build(window_cache, interface_settings, available_space)
+    }
+}
@@ -0,0 +1,34 @@
+use derive_new::new;
+use procedural::*;
+
+use crate::interface::*;
+use crate::inventory::{HotbarEntry, Item, HOTBAR_SLOT_COUNT};
+
+#[derive(new)]
+pub struct HotbarWindow {
+    entries: Remote<Vec<Option<HotbarEntry>>>,
+    items: Remote<Vec<Item>>,
+}
+
+impl HotbarWindow {
+    pub const WINDOW_CLASS: &'static str = "hud_hotbar";
+}
+
+impl PrototypeWindow for HotbarWindow {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: Size) -> Window {
+        let elements = (0..HOTBAR_SLOT_COUNT)
+            .map(|slot| HotbarSlot::new(slot, self.entries.clone(), self.items.clone()).wrap())
+            .collect();
+
+        WindowBuilder::default()
+            .with_class(Self::WINDOW_CLASS.to_string())
+            .with_size(constraint!(200 > 320 < 400, ?))
+            .with_elements(elements)
+            .hud()
+            .build(window_cache, interface_settings, available_space)
+    }
+}
@@ -0,0 +1,15 @@
+mod cast;
+mod cooldown;
+mod experience;
+mod hotbar;
+mod status;
+mod target;
+mod title;
+
+pub use self::cast::CastBarWindow;
+pub use self::cooldown::CooldownWindow;
+pub use self::experience::ExperienceBarWindow;
+pub use self::hotbar::HotbarWindow;
+pub use self::status::StatusBarWindow;
+pub use self::target::TargetFrameWindow;
+pub use self::title::TitleBarWindow;
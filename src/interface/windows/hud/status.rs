@@ -0,0 +1,35 @@
+use derive_new::new;
+use procedural::*;
+
+use crate::graphics::Color;
+use crate::interface::*;
+
+#[derive(new)]
+pub struct StatusBarWindow {
+    health_points: Remote<(usize, usize)>,
+    spell_points: Remote<(usize, usize)>,
+}
+
+impl StatusBarWindow {
+    pub const WINDOW_CLASS: &'static str = "hud_status";
+}
+
+impl PrototypeWindow for StatusBarWindow {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: Size) -> Window {
+        let elements = vec![
+            ValueBar::new(self.health_points.clone(), "hp", Color::rgb(67, 163, 83)).wrap(),
+            ValueBar::new(self.spell_points.clone(), "sp", Color::rgb(67, 129, 163)).wrap(),
+        ];
+
+        WindowBuilder::default()
+            .with_class(Self::WINDOW_CLASS.to_string())
+            .with_size(constraint!(150 > 250 < 500, ?))
+            .with_elements(elements)
+            .hud()
+            .build(window_cache, interface_settings, available_space)
+    }
+}
@@ -0,0 +1,35 @@
+use derive_new::new;
+use procedural::*;
+
+use crate::combat::{CastState, TargetStatus};
+use crate::interface::*;
+
+#[derive(new)]
+pub struct TargetFrameWindow {
+    target: Remote<Option<TargetStatus>>,
+    target_cast: Remote<CastState>,
+}
+
+impl TargetFrameWindow {
+    pub const WINDOW_CLASS: &'static str = "hud_target";
+}
+
+impl PrototypeWindow for TargetFrameWindow {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: Size) -> Window {
+        let elements = vec![
+            TargetFrame::new(self.target.clone()).wrap(),
+            CastBar::new(self.target_cast.clone()).wrap(),
+        ];
+
+        WindowBuilder::default()
+            .with_class(Self::WINDOW_CLASS.to_string())
+            .with_size(constraint!(150 > 220 < 500, ?))
+            .with_elements(elements)
+            .hud()
+            .build(window_cache, interface_settings, available_space)
+    }
+}
@@ -0,0 +1,53 @@
+use procedural::*;
+
+use crate::input::UserEvent;
+use crate::interface::*;
+
+/// Replaces the title bar of the operating system when its decorations are
+/// disabled. Dragging the title moves the game window, including snapping to
+/// screen edges where the platform supports it.
+#[derive(Default)]
+pub struct TitleBarWindow {}
+
+impl TitleBarWindow {
+    pub const WINDOW_CLASS: &'static str = "hud_title_bar";
+}
+
+impl PrototypeWindow for TitleBarWindow {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: Size) -> Window {
+        let elements = vec![
+            Button::default()
+                .with_static_text("Korangar")
+                .with_event(UserEvent::DragWindow)
+                .with_width(dimension!(85%))
+                .wrap(),
+            Button::default()
+                .with_static_text("_")
+                .with_event(UserEvent::MinimizeWindow)
+                .with_width(dimension!(5%))
+                .wrap(),
+            Button::default()
+                .with_static_text("[]")
+                .with_event(UserEvent::ToggleMaximizeWindow)
+                .with_width(dimension!(5%))
+                .wrap(),
+            Button::default()
+                .with_static_text("x")
+                .with_event(UserEvent::Exit)
+                .with_width(dimension!(!))
+                .wrap(),
+        ];
+
+        WindowBuilder::default()
+            .with_class(Self::WINDOW_CLASS.to_string())
+            .with_size(constraint!(100%, ?))
+            .with_default_position(Position::new(0.0, 0.0))
+            .with_elements(elements)
+            .hud()
+            .build(window_cache, interface_settings, available_space)
+    }
+}
@@ -1,27 +1,37 @@
 mod account;
+mod anchor;
 mod builder;
 mod cache;
 mod character;
+mod crafting;
+mod database;
 #[cfg(feature = "debug")]
 mod debug;
 mod generic;
+mod hud;
 mod mutable;
+mod pet;
 mod prototype;
 mod settings;
 
-use cgmath::{Vector2, Vector4};
+use cgmath::Vector2;
 
 pub use self::account::*;
+pub use self::anchor::{hud_area, HudAnchor};
 pub use self::builder::WindowBuilder;
 pub use self::cache::*;
 pub use self::character::*;
+pub use self::crafting::*;
+pub use self::database::*;
 #[cfg(feature = "debug")]
 pub use self::debug::*;
 pub use self::generic::*;
+pub use self::hud::*;
 pub use self::mutable::*;
+pub use self::pet::*;
 pub use self::prototype::PrototypeWindow;
 pub use self::settings::*;
-use crate::graphics::{InterfaceRenderer, Renderer};
+use crate::graphics::{ImageBuffer, InterfaceRenderer, Renderer};
 use crate::input::MouseInputMode;
 use crate::interface::*;
 
@@ -32,7 +42,119 @@ pub struct Window {
     size: Vector2<f32>,
     elements: Vec<ElementCell>,
     closable: bool,
+    modal: bool,
+    hud: bool,
+    popup: bool,
     background_color: Option<ColorSelector>,
+    title_bar: Vec<ElementCell>,
+    opacity: f32,
+    click_through: bool,
+    needs_placement: bool,
+    position_remainder: Vector2<f32>,
+    size_remainder: Vector2<f32>,
+    snap_remainder: Vector2<f32>,
+    /// Output of the last time the window was rendered, together with the
+    /// part of the window it covers. It can be drawn again instead of all
+    /// elements until something inside of the window changes.
+    render_cache: Option<(ImageBuffer, Position, Size)>,
+    /// The image of an outdated cache is kept, so it can be reused if the
+    /// size of the window doesn't change.
+    render_cache_valid: bool,
+}
+
+/// Distance in pixels from a screen edge at which a window counts as attached
+/// to it.
+const EDGE_ATTACH_DISTANCE: f32 = 2.0;
+
+/// Position of a window after the screen size changed. Windows in the outer
+/// thirds of the screen keep their distance to the closest edge, the ones in
+/// between keep their relative position.
+fn reanchored_position(position: Position, size: Size, previous_space: Size, new_space: Size) -> Position {
+    let reanchor_axis = |position: f32, size: f32, previous: f32, new: f32| {
+        let center = (position + size / 2.0) / previous;
+
+        match center {
+            center if center < 1.0 / 3.0 => position,
+            center if center > 2.0 / 3.0 => position + new - previous,
+            center => center * new - size / 2.0,
+        }
+    };
+
+    Position::new(
+        reanchor_axis(position.x, size.x, previous_space.x, new_space.x),
+        reanchor_axis(position.y, size.y, previous_space.y, new_space.y),
+    )
+}
+
+/// Moves a window so that it is fully visible, as long as it fits on the
+/// screen.
+fn on_screen_position(position: Position, size: Size, available_space: Size) -> Position {
+    Position::new(
+        position.x.min(available_space.x - size.x).max(0.0),
+        position.y.min(available_space.y - size.y).max(0.0),
+    )
+}
+
+/// Snaps a position or size to the grid. The part that was cut off is kept in
+/// the remainder, so that slow mouse movements still add up to a full grid
+/// step.
+fn snap_to_grid(value: Vector2<f32>, remainder: &mut Vector2<f32>, grid_size: f32) -> Vector2<f32> {
+    let target = value + *remainder;
+    let snapped = target.map(|component| (component / grid_size).round() * grid_size);
+    *remainder = target - snapped;
+    snapped
+}
+
+/// Snaps a window to the edges of the screen and the edges of other windows
+/// that are within the snap distance. Each axis is snapped on its own. Returns
+/// the index of the area the window was placed next to, if any.
+fn snap_to_areas(
+    position: Position,
+    size: Size,
+    available_space: Size,
+    areas: &[(Position, Size)],
+    snap_distance: f32,
+) -> (Position, Option<usize>) {
+    let mut x_candidates = vec![(0.0, None), (available_space.x - size.x, None)];
+    let mut y_candidates = vec![(0.0, None), (available_space.y - size.y, None)];
+
+    for (index, &(area_position, area_size)) in areas.iter().enumerate() {
+        let area_end = area_position + area_size;
+        let overlaps_x = position.x < area_end.x + snap_distance && position.x + size.x > area_position.x - snap_distance;
+        let overlaps_y = position.y < area_end.y + snap_distance && position.y + size.y > area_position.y - snap_distance;
+
+        if overlaps_y {
+            x_candidates.extend([(area_end.x, Some(index)), (area_position.x - size.x, Some(index))]);
+        }
+
+        if overlaps_x {
+            y_candidates.extend([(area_end.y, Some(index)), (area_position.y - size.y, Some(index))]);
+        }
+
+        // Windows that are next to each other also line up their edges.
+        if overlaps_x && overlaps_y {
+            x_candidates.extend([(area_position.x, None), (area_end.x - size.x, None)]);
+            y_candidates.extend([(area_position.y, None), (area_end.y - size.y, None)]);
+        }
+    }
+
+    let closest = |value: f32, candidates: Vec<(f32, Option<usize>)>| {
+        candidates
+            .into_iter()
+            .filter(|(candidate, _)| (candidate - value).abs() <= snap_distance)
+            .min_by(|(first, _), (second, _)| (first - value).abs().total_cmp(&(second - value).abs()))
+    };
+
+    let snapped_x = closest(position.x, x_candidates);
+    let snapped_y = closest(position.y, y_candidates);
+
+    let snapped = Vector2::new(
+        snapped_x.map(|(x, _)| x).unwrap_or(position.x),
+        snapped_y.map(|(y, _)| y).unwrap_or(position.y),
+    );
+    let anchor = snapped_x.and_then(|(_, anchor)| anchor).or(snapped_y.and_then(|(_, anchor)| anchor));
+
+    (snapped, anchor)
 }
 
 impl Window {
@@ -41,10 +163,36 @@ impl Window {
     }
 
     fn get_background_color(&self, theme: &Theme) -> Color {
-        self.background_color
+        let mut color = self
+            .background_color
             .as_ref()
             .map(|closure| closure(theme))
-            .unwrap_or(*theme.window.background_color)
+            .unwrap_or(*theme.window.background_color);
+
+        color.alpha = (color.alpha as f32 * self.opacity) as u8;
+        color
+    }
+
+    pub fn set_opacity(&mut self, opacity: f32) {
+        self.opacity = opacity;
+    }
+
+    pub fn is_click_through(&self) -> bool {
+        self.click_through
+    }
+
+    pub fn set_click_through(&mut self, click_through: bool) {
+        self.click_through = click_through;
+    }
+
+    /// Checks if the element is one of the top level elements of this window,
+    /// including the ones in the title bar.
+    pub fn has_element(&self, element: &ElementCell) -> bool {
+        self.elements.iter().any(|other| Rc::ptr_eq(other, element)) || self.is_title_bar_element(element)
+    }
+
+    fn is_title_bar_element(&self, element: &ElementCell) -> bool {
+        self.title_bar.iter().any(|title_element| Rc::ptr_eq(title_element, element))
     }
 
     pub fn has_transparency(&self, theme: &Theme) -> bool {
@@ -55,12 +203,30 @@ impl Window {
         self.closable
     }
 
+    pub fn is_modal(&self) -> bool {
+        self.modal
+    }
+
+    pub fn is_hud(&self) -> bool {
+        self.hud
+    }
+
+    pub fn is_popup(&self) -> bool {
+        self.popup
+    }
+
     pub fn resolve(
         &mut self,
         interface_settings: &InterfaceSettings,
         theme: &Theme,
         available_space: Size,
     ) -> (Option<&str>, Vector2<f32>, Size) {
+        // Windows attached to the right or bottom edge of the screen stay attached when
+        // their size changes, for example because the interface scaling changed.
+        let window_end = self.position + self.size;
+        let attached_right = window_end.x >= available_space.x - EDGE_ATTACH_DISTANCE;
+        let attached_bottom = window_end.y >= available_space.y - EDGE_ATTACH_DISTANCE;
+
         let height = match self.size_constraint.height.is_flexible() {
             true => None,
             false => Some(self.size.y),
@@ -90,6 +256,14 @@ impl Window {
             self.validate_size(interface_settings, available_space);
         }
 
+        if !self.needs_placement && attached_right {
+            self.position.x = available_space.x - self.size.x;
+        }
+
+        if !self.needs_placement && attached_bottom {
+            self.position.y = available_space.y - self.size.y;
+        }
+
         self.validate_position(available_space);
 
         (self.window_class.as_deref(), self.position, self.size)
@@ -125,11 +299,18 @@ impl Window {
                 match element.borrow().hovered_element(absolute_position, mouse_mode) {
                     HoverInformation::Hovered => return HoverInformation::Element(element.clone()),
                     HoverInformation::Missed => {}
+                    // Click-through windows only keep their title bar interactive, so they can still
+                    // be moved, closed and configured.
+                    HoverInformation::Element(element) if self.click_through && !self.is_title_bar_element(&element) => {
+                        return HoverInformation::Missed;
+                    }
                     hover_information => return hover_information,
                 }
             }
 
-            return HoverInformation::Hovered;
+            if !self.click_through {
+                return HoverInformation::Hovered;
+            }
         }
 
         HoverInformation::Missed
@@ -149,14 +330,71 @@ impl Window {
             && self.position.y < area_combined.y
     }
 
-    pub fn offset(&mut self, available_space: Size, offset: Position) -> Option<(&str, Position)> {
-        self.position += offset;
+    pub fn offset(&mut self, available_space: Size, offset: Position, grid_size: Option<f32>) -> Option<(&str, Position)> {
+        self.position = match grid_size {
+            Some(grid_size) => snap_to_grid(self.position + offset, &mut self.position_remainder, grid_size),
+            None => self.position + offset,
+        };
         self.validate_position(available_space);
         self.window_class
             .as_ref()
             .map(|window_class| (window_class.as_str(), self.position))
     }
 
+    /// Same as [`offset`](Self::offset), but snaps the window to the screen
+    /// edges and the given areas instead of a grid. Returns the index of the
+    /// area that the window is docked to after the move.
+    pub fn offset_snapped(
+        &mut self,
+        available_space: Size,
+        offset: Position,
+        areas: &[(Position, Size)],
+        snap_distance: f32,
+    ) -> (Position, Option<usize>) {
+        let target = self.position + offset + self.snap_remainder;
+        let (snapped, anchor) = snap_to_areas(target, self.size, available_space, areas, snap_distance);
+
+        self.snap_remainder = target - snapped;
+        self.position = snapped;
+        self.validate_position(available_space);
+
+        (self.position, anchor)
+    }
+
+    /// Forgets how far the window was pulled away from where it snapped, so
+    /// the next drag doesn't start with an offset.
+    pub fn end_move(&mut self) {
+        self.snap_remainder = Vector2::new(0.0, 0.0);
+    }
+
+    pub fn needs_placement(&self) -> bool {
+        self.needs_placement
+    }
+
+    pub fn place(&mut self, available_space: Size, position: Position) -> Option<(&str, Position)> {
+        self.position = position;
+        self.needs_placement = false;
+        self.validate_position(available_space);
+        self.window_class
+            .as_ref()
+            .map(|window_class| (window_class.as_str(), self.position))
+    }
+
+    pub fn reanchor(&mut self, previous_space: Size, new_space: Size) {
+        self.position = reanchored_position(self.position, self.size, previous_space, new_space);
+    }
+
+    pub fn reanchor_hud(&mut self, hud_anchor: HudAnchor, previous_area: (Position, Size), new_area: (Position, Size)) {
+        self.position = hud_anchor.reanchored_position(self.position, self.size, previous_area, new_area);
+    }
+
+    pub fn move_on_screen(&mut self, available_space: Size) -> Option<(&str, Position)> {
+        self.position = on_screen_position(self.position, self.size, available_space);
+        self.window_class
+            .as_ref()
+            .map(|window_class| (window_class.as_str(), self.position))
+    }
+
     fn validate_position(&mut self, available_space: Size) {
         self.position = self.size_constraint.validated_position(self.position, self.size, available_space);
     }
@@ -167,8 +405,12 @@ impl Window {
         _theme: &Theme,
         available_space: Size,
         growth: Size,
+        grid_size: Option<f32>,
     ) -> (Option<&str>, Size) {
-        self.size += growth;
+        self.size = match grid_size {
+            Some(grid_size) => snap_to_grid(self.size + growth, &mut self.size_remainder, grid_size),
+            None => self.size + growth,
+        };
         self.validate_size(interface_settings, available_space);
         (self.window_class.as_deref(), self.size)
     }
@@ -189,21 +431,20 @@ impl Window {
         hovered_element: Option<&dyn Element>,
         focused_element: Option<&dyn Element>,
         mouse_mode: &MouseInputMode,
+        hud_edit_mode: bool,
     ) {
-        let clip_size = Vector4::new(
-            self.position.x,
-            self.position.y,
-            self.position.x + self.size.x,
-            self.position.y + self.size.y,
-        );
+        let border_radius = *theme.window.border_radius * *interface_settings.scaling;
+        let clip_size = ClipSize::new(self.position, self.size, border_radius);
 
-        renderer.render_rectangle(
+        renderer.render_background(
             render_target,
+            &theme.window.background,
             self.position,
             self.size,
             clip_size,
-            *theme.window.border_radius,
+            border_radius,
             self.get_background_color(theme),
+            *interface_settings.scaling,
         );
 
         self.elements.iter().for_each(|element| {
@@ -221,6 +462,85 @@ impl Window {
                 false,
             )
         });
+
+        // Highlight HUD windows so they are easy to find while editing the layout.
+        if self.hud && hud_edit_mode {
+            renderer.render_rectangle(
+                render_target,
+                self.position,
+                self.size,
+                clip_size,
+                border_radius,
+                Color::rgba(255, 200, 50, 60),
+            );
+        }
+    }
+
+    /// Part of the window that is on screen, rounded to whole pixels and
+    /// relative to the window.
+    fn visible_region(&self, available_space: Size) -> Option<(Position, Size)> {
+        let start = Position::new(self.position.x.floor().max(0.0), self.position.y.floor().max(0.0));
+        let end = Position::new(
+            (self.position.x + self.size.x).ceil().min(available_space.x.floor()),
+            (self.position.y + self.size.y).ceil().min(available_space.y.floor()),
+        );
+        let size = end - start;
+
+        (size.x > 0.0 && size.y > 0.0).then_some((start - self.position, size))
+    }
+
+    /// Draws the window from its render cache. Returns false if there is no
+    /// usable cache, in which case the window needs to be rendered normally.
+    pub fn render_cached(
+        &self,
+        render_target: &mut <InterfaceRenderer as Renderer>::Target,
+        renderer: &InterfaceRenderer,
+        interface_settings: &InterfaceSettings,
+        theme: &Theme,
+        available_space: Size,
+    ) -> bool {
+        let Some((image, offset, size)) = self.render_cache.as_ref().filter(|_| self.render_cache_valid) else {
+            return false;
+        };
+
+        // Moving the window partially off screen changes the visible part.
+        if self.visible_region(available_space).map(|(_, visible_size)| visible_size) != Some(*size) {
+            return false;
+        }
+
+        // The corners of the copied region contain whatever was behind the window,
+        // so they are cut off again.
+        let border_radius = *theme.window.border_radius * *interface_settings.scaling;
+        let clip_size = ClipSize::new(self.position, self.size, border_radius);
+
+        renderer.render_image(render_target, image.clone(), self.position + *offset, *size, clip_size);
+        true
+    }
+
+    /// Copies what was just rendered for this window into its render cache.
+    pub fn update_render_cache(
+        &mut self,
+        render_target: &mut <InterfaceRenderer as Renderer>::Target,
+        renderer: &InterfaceRenderer,
+        available_space: Size,
+    ) {
+        let Some((offset, size)) = self.visible_region(available_space) else {
+            return;
+        };
+
+        let image = match &self.render_cache {
+            Some((image, _, cached_size)) if *cached_size == size => image.clone(),
+            _ => renderer.create_window_cache(size),
+        };
+        let position = self.position + offset;
+
+        render_target.copy_region([position.x.round() as u32, position.y.round() as u32], image.clone());
+        self.render_cache = Some((image, offset, size));
+        self.render_cache_valid = true;
+    }
+
+    pub fn invalidate_render_cache(&mut self) {
+        self.render_cache_valid = false;
     }
 }
 
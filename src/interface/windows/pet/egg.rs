@@ -0,0 +1,56 @@
+use procedural::*;
+
+use crate::input::UserEvent;
+use crate::interface::*;
+use crate::inventory::Item;
+use crate::loaders::ScriptLoader;
+use crate::network::ItemIndex;
+
+/// Opened after using a pet incubator. Lists all eggs the player can hatch.
+pub struct PetEggWindow {
+    eggs: Vec<(ItemIndex, String)>,
+}
+
+impl PetEggWindow {
+    pub const WINDOW_CLASS: &'static str = "pet_eggs";
+
+    pub fn new(items: &[Item], script_loader: &ScriptLoader, egg_indices: Vec<ItemIndex>) -> Self {
+        let eggs = egg_indices
+            .into_iter()
+            .filter_map(|index| items.iter().find(|item| item.index == index))
+            .map(|item| (item.index, script_loader.get_item_name_from_id(item.item_id)))
+            .collect();
+
+        Self { eggs }
+    }
+}
+
+impl PrototypeWindow for PetEggWindow {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: Size) -> Window {
+        let elements = match self.eggs.is_empty() {
+            true => vec![Text::default().with_static_text("you have no pet eggs").wrap()],
+            false => self
+                .eggs
+                .iter()
+                .map(|(index, name)| {
+                    Button::default()
+                        .with_dynamic_text(name.clone())
+                        .with_event(UserEvent::SelectPetEgg(*index))
+                        .wrap()
+                })
+                .collect(),
+        };
+
+        WindowBuilder::default()
+            .with_title("Pet Eggs".to_string())
+            .with_class(Self::WINDOW_CLASS.to_string())
+            .with_size(constraint!(200 > 250 < 300, ?))
+            .with_elements(elements)
+            .closable()
+            .build(window_cache, interface_settings, available_space)
+    }
+}
@@ -0,0 +1,5 @@
+mod egg;
+mod status;
+
+pub use self::egg::PetEggWindow;
+pub use self::status::PetStatusWindow;
@@ -0,0 +1,68 @@
+use procedural::*;
+
+use crate::input::UserEvent;
+use crate::interface::*;
+use crate::loaders::ScriptLoader;
+use crate::network::{PetCommand, PetStatus};
+
+pub struct PetStatusWindow {
+    status: PetStatus,
+    accessory_name: Option<String>,
+}
+
+impl PetStatusWindow {
+    pub const WINDOW_CLASS: &'static str = "pet_status";
+
+    pub fn new(status: PetStatus, script_loader: &ScriptLoader) -> Self {
+        let accessory_name = status.accessory.map(|item_id| script_loader.get_item_name_from_id(item_id));
+        Self { status, accessory_name }
+    }
+}
+
+impl PrototypeWindow for PetStatusWindow {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: Size) -> Window {
+        let status = &self.status;
+        let has_accessory = self.accessory_name.is_some();
+
+        let lines = [
+            format!("level: {}", status.level),
+            format!("hunger: {} ({})", status.hunger, status.hunger_description()),
+            format!("intimacy: {} ({})", status.intimacy, status.intimacy_description()),
+            format!("accessory: {}", self.accessory_name.as_deref().unwrap_or("none")),
+        ];
+
+        let mut elements = vec![cell!(Headline::new(status.name.clone(), Headline::DEFAULT_SIZE))];
+        elements.extend(lines.into_iter().map(|line| Text::default().with_dynamic_text(line).wrap()));
+        elements.extend([
+            Button::default()
+                .with_static_text("feed")
+                .with_event(UserEvent::SendPetCommand(PetCommand::Feed))
+                .wrap(),
+            Button::default()
+                .with_static_text("perform")
+                .with_event(UserEvent::SendPetCommand(PetCommand::Perform))
+                .wrap(),
+            Button::default()
+                .with_static_text("unequip accessory")
+                .with_event(UserEvent::SendPetCommand(PetCommand::UnequipAccessory))
+                .with_disabled_selector(move || has_accessory)
+                .wrap(),
+            Button::default()
+                .with_static_text("return to egg")
+                .with_event(UserEvent::SendPetCommand(PetCommand::ReturnToEgg))
+                .wrap(),
+        ]);
+
+        WindowBuilder::default()
+            .with_title("Pet".to_string())
+            .with_class(Self::WINDOW_CLASS.to_string())
+            .with_size(constraint!(200 > 250 < 300, ?))
+            .with_elements(elements)
+            .closable()
+            .build(window_cache, interface_settings, available_space)
+    }
+}
@@ -1,21 +1,32 @@
+use derive_new::new;
 use procedural::*;
 
-use crate::interface::{InterfaceSettings, PrototypeWindow, Size, Window, WindowBuilder, WindowCache};
+use crate::audio::AudioSettings;
+use crate::interface::*;
 
-#[derive(Default)]
-pub struct AudioSettingsWindow {}
+#[derive(new)]
+pub struct AudioSettingsWindow<'a> {
+    audio_settings: &'a AudioSettings,
+}
 
-impl AudioSettingsWindow {
+impl<'a> AudioSettingsWindow<'a> {
     pub const WINDOW_CLASS: &'static str = "audio_settings";
 }
 
-impl PrototypeWindow for AudioSettingsWindow {
+impl<'a> PrototypeWindow for AudioSettingsWindow<'a> {
     fn window_class(&self) -> Option<&str> {
         Self::WINDOW_CLASS.into()
     }
 
     fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: Size) -> Window {
-        let elements = vec![];
+        let elements: Vec<ElementCell> = vec![
+            StateButton::default()
+                .with_static_text("mute")
+                .with_selector(self.audio_settings.muted.selector())
+                .with_closure(self.audio_settings.muted.toggle_action())
+                .wrap(),
+            self.audio_settings.to_element("volume".to_string()),
+        ];
 
         WindowBuilder::default()
             .with_title("Audio Settings".to_string())
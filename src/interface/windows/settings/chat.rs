@@ -0,0 +1,66 @@
+use derive_new::new;
+use procedural::*;
+
+use crate::input::UserEvent;
+use crate::interface::*;
+
+#[derive(new)]
+pub struct ChatSettingsWindow {
+    show_timestamps: TrackedState<bool>,
+    log_to_disk: TrackedState<bool>,
+    afk_minutes: TrackedState<u32>,
+}
+
+impl ChatSettingsWindow {
+    pub const WINDOW_CLASS: &'static str = "chat_settings";
+}
+
+impl PrototypeWindow for ChatSettingsWindow {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: Size) -> Window {
+        let afk_button = |text: &'static str, minutes: u32| {
+            let selector_state = self.afk_minutes.clone();
+            let mut closure_state = self.afk_minutes.clone();
+
+            StateButton::default()
+                .with_static_text(text)
+                .with_selector(move |_| *selector_state.borrow() == minutes)
+                .with_closure(move || closure_state.set(minutes))
+                .with_width(dimension!(25%))
+                .wrap()
+        };
+
+        let elements: Vec<ElementCell> = vec![
+            StateButton::default()
+                .with_static_text("show timestamps")
+                .with_selector(self.show_timestamps.selector())
+                .with_closure(self.show_timestamps.toggle_action())
+                .wrap(),
+            StateButton::default()
+                .with_static_text("log to disk")
+                .with_selector(self.log_to_disk.selector())
+                .with_closure(self.log_to_disk.toggle_action())
+                .wrap(),
+            Button::default()
+                .with_static_text("open log directory")
+                .with_event(UserEvent::OpenChatLogDirectory)
+                .wrap(),
+            cell!(Headline::new("away after".to_string(), Headline::DEFAULT_SIZE)),
+            afk_button("off", 0),
+            afk_button("5m", 5),
+            afk_button("15m", 15),
+            afk_button("30m", 30),
+        ];
+
+        WindowBuilder::default()
+            .with_title("Chat Settings".to_string())
+            .with_class(Self::WINDOW_CLASS.to_string())
+            .with_size(constraint!(200 > 250 < 300, ?))
+            .with_elements(elements)
+            .closable()
+            .build(window_cache, interface_settings, available_space)
+    }
+}
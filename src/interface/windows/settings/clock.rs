@@ -0,0 +1,132 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use procedural::*;
+
+use crate::input::UserEvent;
+use crate::interface::*;
+use crate::system::{format_offset, Alarm, ClockSettings};
+
+/// Reopened by the client whenever the offset or the alarms change.
+pub struct ClockSettingsWindow {
+    server_offset_minutes: i32,
+    alarms: Vec<Alarm>,
+}
+
+impl ClockSettingsWindow {
+    pub const WINDOW_CLASS: &'static str = "clock_settings";
+
+    pub fn new(clock_settings: &ClockSettings) -> Self {
+        Self {
+            server_offset_minutes: *clock_settings.server_offset_minutes.borrow(),
+            alarms: clock_settings.alarms.borrow().clone(),
+        }
+    }
+}
+
+impl PrototypeWindow for ClockSettingsWindow {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: Size) -> Window {
+        let name = Rc::new(RefCell::new(String::new()));
+        let time = Rc::new(RefCell::new(String::new()));
+        let weekday = Rc::new(RefCell::new(None::<u32>));
+
+        let offset_button = |text: &'static str, minutes: i32| {
+            Button::default()
+                .with_static_text(text)
+                .with_event(UserEvent::ChangeServerTimeOffset(minutes))
+                .with_width(dimension!(25%))
+                .wrap()
+        };
+
+        let weekday_button = |text: &'static str, day: Option<u32>| {
+            let selector_state = weekday.clone();
+            let closure_state = weekday.clone();
+
+            StateButton::default()
+                .with_static_text(text)
+                .with_selector(move |_| *selector_state.borrow() == day)
+                .with_action_closure(move || {
+                    *closure_state.borrow_mut() = day;
+                    Some(ClickAction::ChangeEvent(ChangeEvent::RerenderWindow))
+                })
+                .with_width(dimension!(25%))
+                .wrap()
+        };
+
+        let selector = {
+            let name = name.clone();
+            let time = time.clone();
+            let weekday = weekday.clone();
+            move || Alarm::parse(&name.borrow(), &time.borrow(), *weekday.borrow()).is_some()
+        };
+
+        let action = {
+            let name = name.clone();
+            let time = time.clone();
+            let weekday = weekday.clone();
+            move || {
+                Alarm::parse(&name.borrow(), &time.borrow(), *weekday.borrow())
+                    .map(|alarm| ClickAction::Event(UserEvent::AddClockAlarm(alarm)))
+            }
+        };
+
+        let input_action = || Box::new(|| Some(ClickAction::FocusNext(FocusMode::FocusNext)));
+
+        let mut elements: Vec<ElementCell> = vec![
+            cell!(Headline::new("server time".to_string(), Headline::DEFAULT_SIZE)),
+            Text::default().with_dynamic_text(format_offset(self.server_offset_minutes)).wrap(),
+            offset_button("-1h", -60),
+            offset_button("-15m", -15),
+            offset_button("+15m", 15),
+            offset_button("+1h", 60),
+            cell!(Headline::new("alarms".to_string(), Headline::DEFAULT_SIZE)),
+        ];
+
+        for (index, alarm) in self.alarms.iter().enumerate() {
+            elements.push(
+                Text::default()
+                    .with_dynamic_text(format!("{} ({})", alarm.name, alarm.schedule()))
+                    .with_width(dimension!(75%))
+                    .wrap(),
+            );
+            elements.push(
+                Button::default()
+                    .with_static_text("remove")
+                    .with_event(UserEvent::RemoveClockAlarm(index))
+                    .with_width(dimension!(25%))
+                    .wrap(),
+            );
+        }
+
+        elements.extend([
+            cell!(InputField::<24>::new(name, "alarm name", input_action(), dimension!(100%))) as _,
+            cell!(InputField::<5>::new(
+                time,
+                "server time (hh:mm)",
+                input_action(),
+                dimension!(100%)
+            )),
+            weekday_button("daily", None),
+        ]);
+        elements.extend((0..7).map(|day| weekday_button(Alarm::weekday_name(day), Some(day))));
+        elements.push(
+            Button::default()
+                .with_static_text("add alarm")
+                .with_disabled_selector(selector)
+                .with_action_closure(action)
+                .wrap(),
+        );
+
+        WindowBuilder::default()
+            .with_title("Clock Settings".to_string())
+            .with_class(Self::WINDOW_CLASS.to_string())
+            .with_size(constraint!(200 > 250 < 300, ? < 80%))
+            .with_elements(elements)
+            .closable()
+            .build(window_cache, interface_settings, available_space)
+    }
+}
@@ -0,0 +1,67 @@
+use procedural::*;
+
+use crate::input::{KeyAction, KeyBindings, UserEvent};
+use crate::interface::*;
+
+/// Lists every configurable action together with its key. Clicking a key
+/// waits for the next key press and binds it to the action.
+pub struct ControlsWindow {
+    bindings: Vec<(KeyAction, String)>,
+}
+
+impl ControlsWindow {
+    pub const WINDOW_CLASS: &'static str = "controls";
+
+    pub fn new(key_bindings: &KeyBindings, rebinding: Option<KeyAction>) -> Self {
+        let bindings = KeyAction::ALL
+            .into_iter()
+            .map(|action| {
+                let binding = match rebinding == Some(action) {
+                    true => "press a key".to_string(),
+                    false => key_bindings
+                        .get(action)
+                        .map(|binding| binding.to_string())
+                        .unwrap_or_else(|| "unbound".to_string()),
+                };
+
+                (action, binding)
+            })
+            .collect();
+
+        Self { bindings }
+    }
+}
+
+impl PrototypeWindow for ControlsWindow {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: Size) -> Window {
+        let elements: Vec<ElementCell> = self
+            .bindings
+            .iter()
+            .flat_map(|(action, binding)| {
+                [
+                    Text::default()
+                        .with_dynamic_text(action.display_name())
+                        .with_width(dimension!(50%))
+                        .wrap(),
+                    Button::default()
+                        .with_dynamic_text(binding.clone())
+                        .with_event(UserEvent::StartRebinding(*action))
+                        .with_width(dimension!(!))
+                        .wrap(),
+                ]
+            })
+            .collect();
+
+        WindowBuilder::default()
+            .with_title("Controls".to_string())
+            .with_class(Self::WINDOW_CLASS.to_string())
+            .with_size(constraint!(250 > 300 < 400, ?))
+            .with_elements(elements)
+            .closable()
+            .build(window_cache, interface_settings, available_space)
+    }
+}
@@ -1,10 +1,20 @@
+use derive_new::new;
 use procedural::*;
 
+use crate::graphics::{DeviceId, EffectQuality, EntityShadowMode};
 use crate::input::UserEvent;
 use crate::interface::*;
+use crate::loaders::{CompressionQuality, TextureCompression};
 
-#[derive(Default)]
-pub struct GraphicsSettingsWindow {}
+#[derive(new)]
+pub struct GraphicsSettingsWindow {
+    hud_edit_mode: TrackedState<bool>,
+    /// Names and ids of all devices that can run the client.
+    available_devices: Vec<(String, DeviceId)>,
+    preferred_device: Option<DeviceId>,
+    texture_compression: TextureCompression,
+    compression_quality: CompressionQuality,
+}
 
 impl GraphicsSettingsWindow {
     pub const WINDOW_CLASS: &'static str = "graphics_settings";
@@ -16,15 +26,191 @@ impl PrototypeWindow for GraphicsSettingsWindow {
     }
 
     fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: Size) -> Window {
-        let elements: Vec<ElementCell> = vec![
+        let mut elements: Vec<ElementCell> = vec![
             StateButton::default()
                 .with_static_text("framerate limit")
                 .with_selector(|state_provider| state_provider.graphics_settings.frame_limit)
                 .with_event(UserEvent::ToggleFrameLimit)
                 .wrap(),
-            interface_settings.to_element("interface settings".to_string()),
+            StateButton::default()
+                .with_static_text("entity info card")
+                .with_selector(|state_provider| state_provider.graphics_settings.show_entity_info)
+                .with_event(UserEvent::ToggleShowEntityInfo)
+                .wrap(),
+            StateButton::default()
+                .with_static_text("edit hud")
+                .with_selector(self.hud_edit_mode.selector())
+                .with_closure(self.hud_edit_mode.toggle_action())
+                .wrap(),
+            cell!(Headline::new("render scale".to_string(), Headline::DEFAULT_SIZE)),
+            StateButton::default()
+                .with_static_text("100%")
+                .with_selector(|state_provider| state_provider.graphics_settings.render_scale == 1.0)
+                .with_event(UserEvent::SetRenderScale(1.0))
+                .with_width(dimension!(33.33%))
+                .wrap(),
+            StateButton::default()
+                .with_static_text("75%")
+                .with_selector(|state_provider| state_provider.graphics_settings.render_scale == 0.75)
+                .with_event(UserEvent::SetRenderScale(0.75))
+                .with_width(dimension!(33.33%))
+                .wrap(),
+            StateButton::default()
+                .with_static_text("50%")
+                .with_selector(|state_provider| state_provider.graphics_settings.render_scale == 0.5)
+                .with_event(UserEvent::SetRenderScale(0.5))
+                .with_width(dimension!(!))
+                .wrap(),
+            cell!(Headline::new("fade objects in front of player".to_string(), Headline::DEFAULT_SIZE)),
+            StateButton::default()
+                .with_static_text("off")
+                .with_selector(|state_provider| state_provider.graphics_settings.object_fade_opacity == 1.0)
+                .with_event(UserEvent::SetObjectFadeOpacity(1.0))
+                .with_width(dimension!(33.33%))
+                .wrap(),
+            StateButton::default()
+                .with_static_text("50%")
+                .with_selector(|state_provider| state_provider.graphics_settings.object_fade_opacity == 0.5)
+                .with_event(UserEvent::SetObjectFadeOpacity(0.5))
+                .with_width(dimension!(33.33%))
+                .wrap(),
+            StateButton::default()
+                .with_static_text("20%")
+                .with_selector(|state_provider| state_provider.graphics_settings.object_fade_opacity == 0.2)
+                .with_event(UserEvent::SetObjectFadeOpacity(0.2))
+                .with_width(dimension!(!))
+                .wrap(),
+            cell!(Headline::new("entity shadows".to_string(), Headline::DEFAULT_SIZE)),
+            StateButton::default()
+                .with_static_text("none")
+                .with_selector(|state_provider| state_provider.graphics_settings.entity_shadows == EntityShadowMode::None)
+                .with_event(UserEvent::SetEntityShadowMode(EntityShadowMode::None))
+                .with_width(dimension!(33.33%))
+                .wrap(),
+            StateButton::default()
+                .with_static_text("blob")
+                .with_selector(|state_provider| state_provider.graphics_settings.entity_shadows == EntityShadowMode::Blob)
+                .with_event(UserEvent::SetEntityShadowMode(EntityShadowMode::Blob))
+                .with_width(dimension!(33.33%))
+                .wrap(),
+            StateButton::default()
+                .with_static_text("sprite")
+                .with_selector(|state_provider| state_provider.graphics_settings.entity_shadows == EntityShadowMode::Sprite)
+                .with_event(UserEvent::SetEntityShadowMode(EntityShadowMode::Sprite))
+                .with_width(dimension!(!))
+                .wrap(),
+            cell!(Headline::new("effect quality".to_string(), Headline::DEFAULT_SIZE)),
+            StateButton::default()
+                .with_static_text("low")
+                .with_selector(|state_provider| state_provider.graphics_settings.effect_quality == EffectQuality::Low)
+                .with_event(UserEvent::SetEffectQuality(EffectQuality::Low))
+                .with_width(dimension!(25%))
+                .wrap(),
+            StateButton::default()
+                .with_static_text("medium")
+                .with_selector(|state_provider| state_provider.graphics_settings.effect_quality == EffectQuality::Medium)
+                .with_event(UserEvent::SetEffectQuality(EffectQuality::Medium))
+                .with_width(dimension!(25%))
+                .wrap(),
+            StateButton::default()
+                .with_static_text("high")
+                .with_selector(|state_provider| state_provider.graphics_settings.effect_quality == EffectQuality::High)
+                .with_event(UserEvent::SetEffectQuality(EffectQuality::High))
+                .with_width(dimension!(25%))
+                .wrap(),
+            StateButton::default()
+                .with_static_text("ultra")
+                .with_selector(|state_provider| state_provider.graphics_settings.effect_quality == EffectQuality::Ultra)
+                .with_event(UserEvent::SetEffectQuality(EffectQuality::Ultra))
+                .with_width(dimension!(!))
+                .wrap(),
+            StateButton::default()
+                .with_static_text("hide roofs above player")
+                .with_selector(|state_provider| state_provider.graphics_settings.hide_roofs)
+                .with_event(UserEvent::ToggleHideRoofs)
+                .wrap(),
+            StateButton::default()
+                .with_static_text("custom window decorations")
+                .with_selector(|state_provider| state_provider.window_settings.custom_decorations)
+                .with_event(UserEvent::ToggleCustomDecorations)
+                .wrap(),
+            cell!(Headline::new("device (applied after restart)".to_string(), Headline::DEFAULT_SIZE)),
+        ];
+
+        let is_automatic = self.preferred_device.is_none();
+        elements.push(
+            StateButton::default()
+                .with_static_text("automatic")
+                .with_selector(move |_| is_automatic)
+                .with_event(UserEvent::SetPreferredDevice(None))
+                .wrap(),
+        );
+
+        for (device_name, device_id) in &self.available_devices {
+            let is_preferred = self.preferred_device == Some(*device_id);
+            elements.push(
+                StateButton::default()
+                    .with_dynamic_text(device_name.clone())
+                    .with_selector(move |_| is_preferred)
+                    .with_event(UserEvent::SetPreferredDevice(Some(*device_id)))
+                    .wrap(),
+            );
+        }
+
+        elements.push(cell!(Headline::new(
+            "texture compression (applied after restart)".to_string(),
+            Headline::DEFAULT_SIZE
+        )));
+
+        let compressions = [
+            ("off", TextureCompression::Uncompressed),
+            ("bc1", TextureCompression::Bc1),
+            ("bc3", TextureCompression::Bc3),
+            ("bc7", TextureCompression::Bc7),
+        ];
+
+        for (index, (name, compression)) in compressions.into_iter().enumerate() {
+            let is_selected = self.texture_compression == compression;
+            let width = match index == compressions.len() - 1 {
+                true => dimension!(!),
+                false => dimension!(25%),
+            };
+
+            elements.push(
+                StateButton::default()
+                    .with_static_text(name)
+                    .with_selector(move |_| is_selected)
+                    .with_event(UserEvent::SetTextureCompression(compression))
+                    .with_width(width)
+                    .wrap(),
+            );
+        }
+
+        let qualities = [
+            ("fast", CompressionQuality::Fast),
+            ("balanced", CompressionQuality::Balanced),
+            ("best", CompressionQuality::Best),
         ];
 
+        for (index, (name, quality)) in qualities.into_iter().enumerate() {
+            let is_selected = self.compression_quality == quality;
+            let width = match index == qualities.len() - 1 {
+                true => dimension!(!),
+                false => dimension!(33.33%),
+            };
+
+            elements.push(
+                StateButton::default()
+                    .with_static_text(name)
+                    .with_selector(move |_| is_selected)
+                    .with_event(UserEvent::SetCompressionQuality(quality))
+                    .with_width(width)
+                    .wrap(),
+            );
+        }
+
+        elements.push(interface_settings.to_element("interface settings".to_string()));
+
         WindowBuilder::default()
             .with_title("Graphics Settings".to_string())
             .with_class(Self::WINDOW_CLASS.to_string())
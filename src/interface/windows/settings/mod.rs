@@ -1,9 +1,15 @@
 mod audio;
+mod chat;
+mod clock;
+mod controls;
 mod graphics;
 #[cfg(feature = "debug")]
 mod render;
 
 pub use self::audio::AudioSettingsWindow;
+pub use self::chat::ChatSettingsWindow;
+pub use self::clock::ClockSettingsWindow;
+pub use self::controls::ControlsWindow;
 pub use self::graphics::GraphicsSettingsWindow;
 #[cfg(feature = "debug")]
 pub use self::render::RenderSettingsWindow;
@@ -20,6 +20,7 @@ fn general_expandable() -> ElementCell {
         render_state_button!("show wireframe", UserEvent::ToggleShowWireframe, show_wireframe),
         render_state_button!("frustum culling", UserEvent::ToggleFrustumCulling, frustum_culling),
         render_state_button!("show bounding boxes", UserEvent::ToggleShowBoundingBoxes, show_bounding_boxes),
+        render_state_button!("cache windows", UserEvent::ToggleCacheWindows, cache_windows),
     ];
 
     cell!(Expandable::new("general".to_string(), buttons, true))
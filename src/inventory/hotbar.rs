@@ -0,0 +1,145 @@
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+
+use super::Item;
+#[cfg(feature = "debug")]
+use crate::debug::*;
+use crate::input::UserEvent;
+use crate::interface::{ItemCategory, ItemMove, ItemSource, TrackedState};
+use crate::network::{ItemId, SkillInformation};
+use crate::system::{character_file_name, profile_file};
+
+/// Number of slots, one for each of the keys from F1 to F9.
+pub const HOTBAR_SLOT_COUNT: usize = 9;
+const HOTBAR_DIRECTORY: &str = "hotbars";
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum HotbarEntry {
+    Skill {
+        skill_id: u16,
+        skill_level: u16,
+        skill_name: String,
+    },
+    /// Items are stored by their id, since the index of an item changes
+    /// between sessions.
+    Item { item_id: u32 },
+}
+
+impl HotbarEntry {
+    pub fn from_skill(skill: &SkillInformation) -> Self {
+        Self::Skill {
+            skill_id: skill.skill_id,
+            skill_level: skill.skill_level,
+            skill_name: skill.skill_name.clone(),
+        }
+    }
+
+    pub fn from_item(item: &Item) -> Self {
+        Self::Item { item_id: item.item_id.0 }
+    }
+}
+
+/// Skills and items assigned to the hotbar of the current character. Like the
+/// chat tabs, they are loaded when a character logs in and saved when
+/// switching to another one.
+pub struct Hotbar {
+    character_name: Option<String>,
+    slots: TrackedState<Vec<Option<HotbarEntry>>>,
+}
+
+impl Default for Hotbar {
+    fn default() -> Self {
+        Self {
+            character_name: None,
+            slots: TrackedState::new(vec![None; HOTBAR_SLOT_COUNT]),
+        }
+    }
+}
+
+impl Hotbar {
+    fn hotbar_file(character_name: &str) -> String {
+        profile_file(&format!("{}/{}.ron", HOTBAR_DIRECTORY, character_name))
+    }
+
+    pub fn set_character(&mut self, character_name: &str) {
+        self.save();
+
+        let character_name = character_file_name(character_name);
+        let mut slots = Self::load(&character_name).unwrap_or_else(|| {
+            #[cfg(feature = "debug")]
+            print_debug!("failed to load hotbar for {}{}{}", MAGENTA, character_name, NONE);
+
+            Vec::new()
+        });
+
+        slots.resize(HOTBAR_SLOT_COUNT, None);
+        self.slots.set(slots);
+        self.character_name = Some(character_name);
+    }
+
+    fn load(character_name: &str) -> Option<Vec<Option<HotbarEntry>>> {
+        #[cfg(feature = "debug")]
+        print_debug!("loading hotbar for {}{}{}", MAGENTA, character_name, NONE);
+
+        std::fs::read_to_string(Self::hotbar_file(character_name))
+            .ok()
+            .and_then(|data| ron::from_str(&data).ok())
+    }
+
+    pub fn save(&self) {
+        let Some(character_name) = &self.character_name else {
+            return;
+        };
+
+        #[cfg(feature = "debug")]
+        print_debug!("saving hotbar for {}{}{}", MAGENTA, character_name, NONE);
+
+        let data = ron::ser::to_string_pretty(&*self.slots.borrow(), PrettyConfig::new()).unwrap();
+        std::fs::create_dir_all(profile_file(HOTBAR_DIRECTORY)).expect("unable to create hotbar directory");
+        std::fs::write(Self::hotbar_file(character_name), data).expect("unable to write file");
+    }
+
+    pub fn set_slot(&mut self, slot: usize, entry: Option<HotbarEntry>) {
+        self.slots.with_mut(|slots, changed| {
+            if let Some(slot) = slots.get_mut(slot) {
+                *slot = entry;
+                changed();
+            }
+        });
+    }
+
+    pub fn get_slot_state(&self) -> TrackedState<Vec<Option<HotbarEntry>>> {
+        self.slots.clone()
+    }
+
+    /// The event that activating a slot stands for. Items are looked up in the
+    /// inventory, so nothing happens once the player runs out of them.
+    pub fn activate(&self, slot: usize, items: &[Item]) -> Option<UserEvent> {
+        match self.slots.borrow().get(slot)?.as_ref()? {
+            HotbarEntry::Skill {
+                skill_id, skill_level, ..
+            } => Some(UserEvent::UseSkill(*skill_id, *skill_level)),
+            HotbarEntry::Item { item_id } => {
+                let item = items.iter().find(|item| item.item_id == ItemId(*item_id))?;
+
+                match ItemCategory::from_item_id(item.item_id) {
+                    ItemCategory::Usable => Some(UserEvent::UseItem(item.index)),
+                    ItemCategory::Equipment => Some(UserEvent::MoveItem(ItemMove {
+                        source: ItemSource::Inventory,
+                        destination: ItemSource::Equipment {
+                            position: item.equip_position,
+                        },
+                        item: item.clone(),
+                    })),
+                    _ => None,
+                }
+            }
+        }
+    }
+}
+
+impl Drop for Hotbar {
+    fn drop(&mut self) {
+        self.save();
+    }
+}
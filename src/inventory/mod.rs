@@ -1,8 +1,14 @@
+mod hotbar;
+mod trade;
+
 use crate::graphics::Texture;
 use crate::interface::TrackedState;
 use crate::loaders::{GameFileLoader, ScriptLoader, TextureLoader};
 use crate::network::{EquipPosition, ItemId, ItemIndex, ItemOptions};
 
+pub use self::hotbar::{Hotbar, HotbarEntry, HOTBAR_SLOT_COUNT};
+pub use self::trade::{Trade, TRADE_SLOT_COUNT};
+
 enum ItemDetails {
     Regular {
         amount: u16,
@@ -27,6 +33,10 @@ pub struct Item {
     pub item_id: ItemId,
     pub equip_position: EquipPosition,
     pub equipped_position: EquipPosition,
+    /// Cards inserted into the item. Empty slots are `ItemId(0)`.
+    pub cards: [ItemId; 4],
+    /// Always 1 for equipment, since it doesn't stack.
+    pub amount: u16,
     //pub item_type: u8,
     //pub wear_state: u32,
     //pub slot: [u32; 4], // card ?
@@ -34,9 +44,25 @@ pub struct Item {
     pub texture: Texture,
 }
 
+impl Item {
+    /// Lines shown in the tooltip when hovering the item.
+    pub fn info_lines(&self, script_loader: &ScriptLoader) -> Vec<String> {
+        let mut lines = vec![script_loader.get_item_name_from_id(self.item_id)];
+
+        let slot_count = script_loader.get_item_slot_count(self.item_id).min(self.cards.len());
+        lines.extend(self.cards[..slot_count].iter().map(|card_id| match *card_id {
+            ItemId(0) => "[empty slot]".to_string(),
+            card_id => format!("[{}]", script_loader.get_item_name_from_id(card_id)),
+        }));
+
+        lines
+    }
+}
+
 #[derive(Default)]
 pub struct Inventory {
     items: TrackedState<Vec<Item>>,
+    highlighted_items: TrackedState<Vec<ItemId>>,
 }
 
 impl Inventory {
@@ -45,7 +71,7 @@ impl Inventory {
         game_file_loader: &mut GameFileLoader,
         texture_loader: &mut TextureLoader,
         script_loader: &ScriptLoader,
-        item_data: Vec<(ItemIndex, ItemId, EquipPosition, EquipPosition)>,
+        item_data: Vec<(ItemIndex, ItemId, EquipPosition, EquipPosition, [ItemId; 4], u16)>,
     ) {
         let items = item_data
             .into_iter()
@@ -58,6 +84,8 @@ impl Inventory {
                     item_id: item_data.1,
                     equip_position: item_data.2,
                     equipped_position: item_data.3,
+                    cards: item_data.4,
+                    amount: item_data.5,
                     texture,
                 }
             })
@@ -75,13 +103,17 @@ impl Inventory {
         item_id: ItemId,
         equip_position: EquipPosition,
         equipped_position: EquipPosition,
+        cards: [ItemId; 4],
+        amount: u16,
     ) {
         self.items.with_mut(|items, changed| {
             // Set changed ahead of time since we might exit early.
             changed();
 
-            if let Some(_stack) = items.iter_mut().find(|item| item.item_id == item_id) {
-                //stack.amount += item_data.amount;
+            // Items that stack keep their index, so a new item with a known index is added to
+            // the existing stack.
+            if let Some(stack) = items.iter_mut().find(|item| item.index == item_index) {
+                stack.amount += amount;
                 return;
             }
 
@@ -93,6 +125,8 @@ impl Inventory {
                 item_id,
                 equip_position,
                 equipped_position,
+                cards,
+                amount,
                 texture,
             };
 
@@ -100,6 +134,42 @@ impl Inventory {
         });
     }
 
+    /// Remove part of a stack, or the whole item once nothing is left.
+    pub fn remove_item(&mut self, index: ItemIndex, amount: u16) {
+        self.items.with_mut(|items, changed| {
+            let Some(position) = items.iter().position(|item| item.index == index) else {
+                return;
+            };
+
+            let item = &mut items[position];
+            item.amount = item.amount.saturating_sub(amount);
+
+            if item.amount == 0 {
+                items.remove(position);
+            }
+
+            changed();
+        });
+    }
+
+    /// Set the amount of a stack, or remove the item once nothing is left.
+    pub fn update_amount(&mut self, index: ItemIndex, amount: u16) {
+        self.items.with_mut(|items, changed| {
+            let Some(position) = items.iter().position(|item| item.index == index) else {
+                return;
+            };
+
+            match amount {
+                0 => {
+                    items.remove(position);
+                }
+                amount => items[position].amount = amount,
+            }
+
+            changed();
+        });
+    }
+
     pub fn update_equipped_position(&mut self, index: ItemIndex, equipped_position: EquipPosition) {
         self.items.with_mut(|items, changed| {
             items.iter_mut().find(|item| item.index == index).unwrap().equipped_position = equipped_position;
@@ -107,7 +177,35 @@ impl Inventory {
         });
     }
 
+    /// Move a card into the first free slot of a piece of equipment.
+    pub fn insert_card(&mut self, card_index: ItemIndex, equipment_index: ItemIndex) {
+        self.items.with_mut(|items, changed| {
+            let Some(card_position) = items.iter().position(|item| item.index == card_index) else {
+                return;
+            };
+
+            let card = items.remove(card_position);
+            changed();
+
+            if let Some(equipment) = items.iter_mut().find(|item| item.index == equipment_index)
+                && let Some(slot) = equipment.cards.iter_mut().find(|slot| **slot == ItemId(0))
+            {
+                *slot = card.item_id;
+            }
+        });
+    }
+
     pub fn get_item_state(&self) -> TrackedState<Vec<Item>> {
         self.items.clone()
     }
+
+    /// Highlight all items with the given ids, for example the materials
+    /// needed by the refine window.
+    pub fn highlight_items(&mut self, item_ids: Vec<ItemId>) {
+        self.highlighted_items.set(item_ids);
+    }
+
+    pub fn get_highlighted_item_state(&self) -> TrackedState<Vec<ItemId>> {
+        self.highlighted_items.clone()
+    }
 }
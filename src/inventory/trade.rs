@@ -0,0 +1,118 @@
+use super::Item;
+use crate::graphics::Texture;
+use crate::interface::TrackedState;
+use crate::network::{EquipPosition, ItemId, ItemIndex};
+
+/// Maximum number of items each player can offer in a single trade.
+pub const TRADE_SLOT_COUNT: usize = 10;
+
+/// Items offered by both sides of the current trade. Offered items are taken
+/// out of the inventory once the server accepts them and are given back by
+/// the server if the trade is cancelled.
+#[derive(Default)]
+pub struct Trade {
+    partner_name: String,
+    items: TrackedState<Vec<Item>>,
+    partner_items: TrackedState<Vec<Item>>,
+    partner_zeny: u32,
+    /// Items the player offered that the server did not confirm yet.
+    pending_items: Vec<Item>,
+    locked: bool,
+    partner_locked: bool,
+}
+
+impl Trade {
+    /// Clears the offers of a previous trade.
+    pub fn start(&mut self, partner_name: String) {
+        self.partner_name = partner_name;
+        self.items.set(Vec::new());
+        self.partner_items.set(Vec::new());
+        self.partner_zeny = 0;
+        self.pending_items.clear();
+        self.locked = false;
+        self.partner_locked = false;
+    }
+
+    pub fn get_partner_name(&self) -> &str {
+        &self.partner_name
+    }
+
+    /// Returns false if the item can't be offered, because the offer is
+    /// already locked or full.
+    pub fn offer_item(&mut self, item: Item) -> bool {
+        let offered_count = self.items.borrow().len() + self.pending_items.len();
+
+        if self.locked || offered_count >= TRADE_SLOT_COUNT || item.equipped_position != EquipPosition::None {
+            return false;
+        }
+
+        self.pending_items.push(item);
+        true
+    }
+
+    /// Moves an offered item into the trade, returning it so it can be removed
+    /// from the inventory.
+    pub fn accept_item(&mut self, index: ItemIndex) -> Option<Item> {
+        let position = self.pending_items.iter().position(|item| item.index == index)?;
+        let item = self.pending_items.remove(position);
+
+        self.items.with_mut(|items, changed| {
+            items.push(item.clone());
+            changed();
+        });
+
+        Some(item)
+    }
+
+    pub fn reject_item(&mut self, index: ItemIndex) {
+        self.pending_items.retain(|item| item.index != index);
+    }
+
+    pub fn add_partner_item(&mut self, item_id: ItemId, cards: [ItemId; 4], amount: u16, texture: Texture) {
+        self.partner_items.with_mut(|items, changed| {
+            let item = Item {
+                index: ItemIndex::from_position(items.len()),
+                item_id,
+                equip_position: EquipPosition::None,
+                equipped_position: EquipPosition::None,
+                cards,
+                amount,
+                texture,
+            };
+
+            items.push(item);
+            changed();
+        });
+    }
+
+    pub fn add_partner_zeny(&mut self, zeny: u32) {
+        self.partner_zeny += zeny;
+    }
+
+    pub fn get_partner_zeny(&self) -> u32 {
+        self.partner_zeny
+    }
+
+    pub fn set_locked(&mut self, partner: bool) {
+        match partner {
+            true => self.partner_locked = true,
+            false => self.locked = true,
+        }
+    }
+
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+
+    pub fn is_partner_locked(&self) -> bool {
+        self.partner_locked
+    }
+
+    pub fn get_item_state(&self) -> TrackedState<Vec<Item>> {
+        self.items.clone()
+    }
+
+    pub fn get_partner_item_state(&self) -> TrackedState<Vec<Item>> {
+        self.partner_items.clone()
+    }
+}
@@ -18,7 +18,7 @@ use crate::network::ClientTick;
 //pub enum Animations {
 //}
 
-#[derive(new)]
+#[derive(Clone, new)]
 pub struct AnimationState {
     #[new(default)]
     pub action: usize,
@@ -39,14 +39,45 @@ impl AnimationState {
         self.factor = None;
     }
 
+    /// Start the idle animation somewhere in the middle, so entities that
+    /// appear at the same time don't move in lockstep.
+    pub fn offset_idle(&mut self, offset: u32) {
+        if self.action == 0 && self.duration.is_none() {
+            self.start_time = ClientTick(self.start_time.0.saturating_sub(offset));
+        }
+    }
+
     pub fn walk(&mut self, movement_speed: usize, client_tick: ClientTick) {
         self.action = 1;
         self.start_time = client_tick;
         self.duration = None;
-        self.factor = Some(movement_speed as f32 * 100.0 / 150.0);
+        self.factor = Some(Self::walk_factor(movement_speed));
+    }
+
+    /// Change the speed of a walk that is already playing, for example when
+    /// the entity mounts or dismounts on the way.
+    pub fn update_walk_speed(&mut self, movement_speed: usize) {
+        if self.action == 1 && self.duration.is_none() {
+            self.factor = Some(Self::walk_factor(movement_speed));
+        }
+    }
+
+    fn walk_factor(movement_speed: usize) -> f32 {
+        movement_speed as f32 * 100.0 / 150.0
+    }
+
+    /// Play an attack once and return to idle afterwards.
+    pub fn attack(&mut self, action: usize, attack_duration: u32, client_tick: ClientTick) {
+        self.action = action;
+        self.start_time = client_tick;
+        // The duration is used to pick the frame, so it can't be zero.
+        self.duration = Some(attack_duration.max(1));
+        self.factor = None;
     }
 
-    pub fn update(&mut self, client_tick: ClientTick) {
+    /// The movement speed is only set while the entity is moving, so it can go
+    /// back to walking once an attack is over.
+    pub fn update(&mut self, client_tick: ClientTick, movement_speed: Option<usize>) {
         let mut time = client_tick.0 - self.start_time.0;
 
         // TODO: make everything have a duration so that we can update the start_time
@@ -55,9 +86,12 @@ impl AnimationState {
         // accurately. When fixed remove set_start_time in MouseCursor.
         if let Some(duration) = self.duration && time > duration {
 
-            //self.action = self.next_action;
-            self.start_time = client_tick;
-            self.duration = None;
+            // Only attacks have a duration for now, so the entity goes back to walking or
+            // idle.
+            match movement_speed {
+                Some(movement_speed) => self.walk(movement_speed, client_tick),
+                None => self.idle(client_tick),
+            }
 
             time = 0;
         }
@@ -73,14 +107,7 @@ pub struct Actions {
 }
 
 impl Actions {
-    pub fn render(
-        &self,
-        sprite: &Sprite,
-        animation_state: &AnimationState,
-        camera_direction: usize,
-        head_direction: usize,
-    ) -> (Texture, Vector2<f32>, bool) {
-        let direction = (camera_direction + head_direction) % 8;
+    fn motion(&self, animation_state: &AnimationState, direction: usize) -> &Motion {
         let aa = animation_state.action * 8 + direction;
         let a = &self.actions[aa % self.actions.len()];
         let delay = self.delays[aa % self.delays.len()];
@@ -97,19 +124,43 @@ impl Actions {
         // TODO: work out how to avoid losing digits when casting timg to an f32. When
         // fixed remove set_start_time in MouseCursor.
 
-        let fs = &a.motions[frame as usize % a.motions.len()];
+        &a.motions[frame as usize % a.motions.len()]
+    }
+
+    pub fn render(
+        &self,
+        sprite: &Sprite,
+        animation_state: &AnimationState,
+        camera_direction: usize,
+        head_direction: usize,
+    ) -> (Texture, Option<Texture>, Vector2<f32>, bool) {
+        let direction = (camera_direction + head_direction) % 8;
+        let fs = self.motion(animation_state, direction);
 
-        let texture = sprite.textures[fs.sprite_clips[0].sprite_number as usize].clone();
+        let sprite_number = fs.sprite_clips[0].sprite_number as usize;
+        let texture = sprite.textures[sprite_number].clone();
         let texture_size = texture.image().dimensions().width_height().map(|component| component as f32);
         let offset = fs.sprite_clips[0].position.map(|component| component as f32);
 
         (
             texture,
+            sprite.texture_palette(sprite_number),
             Vector2::new(-offset.x, offset.y + texture_size[1] / 2.0) / 10.0,
             fs.sprite_clips[0].mirror_on != 0,
         )
     }
 
+    /// Anchor point of the current frame in pixels. Used to line up layered
+    /// sprites, such as weapons, with the body.
+    pub fn attach_point(&self, animation_state: &AnimationState, camera_direction: usize, head_direction: usize) -> Option<Vector2<f32>> {
+        let direction = (camera_direction + head_direction) % 8;
+
+        self.motion(animation_state, direction)
+            .attach_points
+            .first()
+            .map(|attach_point| attach_point.position.map(|component| component as f32))
+    }
+
     pub fn render2(
         &self,
         render_target: &mut <DeferredRenderer as Renderer>::Target,
@@ -122,23 +173,7 @@ impl Actions {
         interface_settings: &InterfaceSettings,
     ) {
         let direction = camera_direction % 8;
-        let aa = animation_state.action * 8 + direction;
-        let a = &self.actions[aa % self.actions.len()];
-        let delay = self.delays[aa % self.delays.len()];
-
-        let factor = animation_state
-            .factor
-            .map(|factor| delay * (factor / 5.0))
-            .unwrap_or_else(|| delay * 50.0);
-
-        let frame = animation_state
-            .duration
-            .map(|duration| animation_state.time * a.motions.len() as u32 / duration)
-            .unwrap_or_else(|| (animation_state.time as f32 / factor) as u32);
-        // TODO: work out how to avoid losing digits when casting timg to an f32. When
-        // fixed remove set_start_time in MouseCursor.
-
-        let fs = &a.motions[frame as usize % a.motions.len()];
+        let fs = self.motion(animation_state, direction);
 
         for sprite_clip in &fs.sprite_clips {
             let texture = &sprite.textures[sprite_clip.sprite_number as usize];
@@ -1,3 +1,4 @@
+use std::ops::Range;
 use std::sync::Arc;
 
 use cgmath::{Array, Vector2};
@@ -14,12 +15,13 @@ use vulkano::image::{ImageAccess, ImageCreateFlags, ImageDimensions, ImageUsage,
 use vulkano::sync::{FenceSignalFuture, GpuFuture};
 
 use super::GameFileLoader;
-use crate::graphics::{Color, CommandBuilder, MemoryAllocator};
+use crate::graphics::{Color, CommandBuilder, MemoryAllocator, MemoryCategory, TrackedAllocation};
 
 pub struct FontLoader {
     memory_allocator: Arc<MemoryAllocator>,
     queue: Arc<Queue>,
     font_atlas: Arc<ImageView<StorageImage>>,
+    _font_atlas_allocation: TrackedAllocation,
     cache: Box<Cache<'static>>,
     load_buffer: Option<CommandBuilder>,
     font: Box<Font<'static>>,
@@ -28,6 +30,8 @@ pub struct FontLoader {
 struct GlyphData {
     glyph: PositionedGlyph<'static>,
     color: Color,
+    /// Byte offset of the character in the text.
+    index: usize,
 }
 
 fn layout_paragraph(font: &Font<'static>, scale: Scale, width: f32, text: &str, default_color: Color) -> (Vec<GlyphData>, f32) {
@@ -37,9 +41,9 @@ fn layout_paragraph(font: &Font<'static>, scale: Scale, width: f32, text: &str,
     let mut caret = point(0.0, v_metrics.ascent);
     let mut last_glyph_id = None;
     let mut color = default_color;
-    let mut chars = text.chars();
+    let mut chars = text.char_indices();
 
-    while let Some(character) = chars.next() {
+    while let Some((index, character)) = chars.next() {
         if character.is_control() {
             match character {
                 '\r' => {
@@ -53,7 +57,7 @@ fn layout_paragraph(font: &Font<'static>, scale: Scale, width: f32, text: &str,
 
         // Color code following.
         if character == '^' {
-            let color_code: String = (0..6).map(|_| chars.next().unwrap()).collect();
+            let color_code: String = (0..6).map(|_| chars.next().unwrap().1).collect();
 
             color = match color_code.as_str() {
                 "000000" => default_color,
@@ -81,7 +85,7 @@ fn layout_paragraph(font: &Font<'static>, scale: Scale, width: f32, text: &str,
         }
 
         caret.x += glyph.unpositioned().h_metrics().advance_width;
-        result.push(GlyphData { glyph, color });
+        result.push(GlyphData { glyph, color, index });
     }
 
     (result, caret.y)
@@ -116,12 +120,13 @@ impl FontLoader {
             0..2,
         )
         .unwrap();
+        let font_atlas_allocation = memory_allocator.track_allocation(MemoryCategory::Interface, cache_size.x as u64 * cache_size.y as u64);
 
         let font_atlas = ImageView::new_default(font_atlas_image.clone()).unwrap();
 
         let font_path = "data\\WenQuanYiMicroHei.ttf";
         let data = game_file_loader.get(font_path).unwrap();
-        let font = Font::try_from_vec(data).unwrap_or_else(|| {
+        let font = Font::try_from_vec(data.to_vec()).unwrap_or_else(|| {
             panic!("error constructing a Font from data at {:?}", font_path);
         });
 
@@ -143,6 +148,7 @@ impl FontLoader {
             memory_allocator,
             queue,
             font_atlas,
+            _font_atlas_allocation: font_atlas_allocation,
             cache: Box::new(cache),
             load_buffer: builder.into(),
             font: Box::new(font),
@@ -161,6 +167,36 @@ impl FontLoader {
         height
     }
 
+    /// Areas covered by the characters in `range` of the text, one for every
+    /// line they are on. Each area is given as its top left and bottom right
+    /// corner.
+    pub fn get_text_areas(
+        &mut self,
+        text: &str,
+        font_size: f32,
+        available_width: f32,
+        range: Range<usize>,
+    ) -> Vec<(Vector2<f32>, Vector2<f32>)> {
+        let scale = Scale::uniform(font_size);
+        let v_metrics = self.font.v_metrics(scale);
+        let line_height = v_metrics.ascent - v_metrics.descent + v_metrics.line_gap;
+        let (glyphs, _) = layout_paragraph(&self.font, scale, available_width, text, Color::monochrome(0));
+        let mut areas: Vec<(Vector2<f32>, Vector2<f32>)> = Vec::new();
+
+        for glyph in glyphs.iter().filter(|glyph| range.contains(&glyph.index)) {
+            let position = glyph.glyph.position();
+            let top = position.y - v_metrics.ascent;
+            let right = position.x + glyph.glyph.unpositioned().h_metrics().advance_width;
+
+            match areas.last_mut() {
+                Some((start, end)) if start.y == top => end.x = right,
+                _ => areas.push((Vector2::new(position.x, top), Vector2::new(right, top + line_height))),
+            }
+        }
+
+        areas
+    }
+
     pub fn get(
         &mut self,
         text: &str,
@@ -0,0 +1,265 @@
+//! Decryption of archive entries. The archives use a modified DES with a
+//! single round and no key, applied to some of the 8 byte blocks of an entry.
+
+/// The entry is a file rather than a directory.
+pub const FILE_FLAG: u8 = 0x01;
+/// Some blocks of the whole entry are encrypted or shuffled.
+pub const MIXED_ENCRYPTION_FLAG: u8 = 0x02;
+/// Only the first blocks of the entry are encrypted.
+pub const HEADER_ENCRYPTION_FLAG: u8 = 0x04;
+
+const BLOCK_SIZE: usize = 8;
+/// Number of blocks at the start of an entry that are always encrypted.
+const ENCRYPTED_HEADER_BLOCKS: usize = 20;
+/// Every 7th unencrypted block after the header is shuffled.
+const SHUFFLE_CYCLE: usize = 7;
+
+const BIT_MASK: [u8; 8] = [0x80, 0x40, 0x20, 0x10, 0x08, 0x04, 0x02, 0x01];
+
+const INITIAL_PERMUTATION: [u8; 64] = [
+    58, 50, 42, 34, 26, 18, 10, 2, 60, 52, 44, 36, 28, 20, 12, 4, 62, 54, 46, 38, 30, 22, 14, 6, 64, 56, 48, 40, 32, 24, 16, 8, 57, 49, 41,
+    33, 25, 17, 9, 1, 59, 51, 43, 35, 27, 19, 11, 3, 61, 53, 45, 37, 29, 21, 13, 5, 63, 55, 47, 39, 31, 23, 15, 7,
+];
+
+const FINAL_PERMUTATION: [u8; 64] = [
+    40, 8, 48, 16, 56, 24, 64, 32, 39, 7, 47, 15, 55, 23, 63, 31, 38, 6, 46, 14, 54, 22, 62, 30, 37, 5, 45, 13, 53, 21, 61, 29, 36, 4, 44,
+    12, 52, 20, 60, 28, 35, 3, 43, 11, 51, 19, 59, 27, 34, 2, 42, 10, 50, 18, 58, 26, 33, 1, 41, 9, 49, 17, 57, 25,
+];
+
+const TRANSPOSITION: [u8; 32] = [
+    16, 7, 20, 21, 29, 12, 28, 17, 1, 15, 23, 26, 5, 18, 31, 10, 2, 8, 24, 14, 32, 27, 3, 9, 19, 13, 30, 6, 22, 11, 4, 25,
+];
+
+/// The eight DES substitution boxes, two per table. The high nibble holds the
+/// result of the first box and the low nibble that of the second one, both
+/// indexed by the 6 bit input directly.
+const SUBSTITUTION: [[u8; 64]; 4] = [
+    [
+        0xef, 0x03, 0x41, 0xfd, 0xd8, 0x74, 0x1e, 0x47, 0x26, 0xef, 0xfb, 0x22, 0xb3, 0xd8, 0x84, 0x1e, 0x39, 0xac, 0xa7, 0x60, 0x62, 0xc1,
+        0xcd, 0xba, 0x5c, 0x96, 0x90, 0x59, 0x05, 0x3b, 0x7a, 0x85, 0x40, 0xfd, 0x1e, 0xc8, 0xe7, 0x8a, 0x8b, 0x21, 0xda, 0x43, 0x64, 0x9f,
+        0x2d, 0x14, 0xb1, 0x72, 0xf5, 0x5b, 0xc8, 0xb6, 0x9c, 0x37, 0x76, 0xec, 0x39, 0xa0, 0xa3, 0x05, 0x52, 0x6e, 0x0f, 0xd9,
+    ],
+    [
+        0xa7, 0xdd, 0x0d, 0x78, 0x9e, 0x0b, 0xe3, 0x95, 0x60, 0x36, 0x36, 0x4f, 0xf9, 0x60, 0x5a, 0xa3, 0x11, 0x24, 0xd2, 0x87, 0xc8, 0x52,
+        0x75, 0xec, 0xbb, 0xc1, 0x4c, 0xba, 0x24, 0xfe, 0x8f, 0x19, 0xda, 0x13, 0x66, 0xaf, 0x49, 0xd0, 0x90, 0x06, 0x8c, 0x6a, 0xfb, 0x91,
+        0x37, 0x8d, 0x0d, 0x78, 0xbf, 0x49, 0x11, 0xf4, 0x23, 0xe5, 0xce, 0x3b, 0x55, 0xbc, 0xa2, 0x57, 0xe8, 0x22, 0x74, 0xce,
+    ],
+    [
+        0x2c, 0xea, 0xc1, 0xbf, 0x4a, 0x24, 0x1f, 0xc2, 0x79, 0x47, 0xa2, 0x7c, 0xb6, 0xd9, 0x68, 0x15, 0x80, 0x56, 0x5d, 0x01, 0x33, 0xfd,
+        0xf4, 0xae, 0xde, 0x30, 0x07, 0x9b, 0xe5, 0x83, 0x9b, 0x68, 0x49, 0xb4, 0x2e, 0x83, 0x1f, 0xc2, 0xb5, 0x7c, 0xa2, 0x19, 0xd8, 0xe5,
+        0x7c, 0x2f, 0x83, 0xda, 0xf7, 0x6b, 0x90, 0xfe, 0xc4, 0x01, 0x5a, 0x97, 0x61, 0xa6, 0x3d, 0x40, 0x0b, 0x58, 0xe6, 0x3d,
+    ],
+    [
+        0x4d, 0xd1, 0xb2, 0x0f, 0x28, 0xbd, 0xe4, 0x78, 0xf6, 0x4a, 0x0f, 0x93, 0x8b, 0x17, 0xd1, 0xa4, 0x3a, 0xec, 0xc9, 0x35, 0x93, 0x56,
+        0x7e, 0xcb, 0x55, 0x20, 0xa0, 0xfe, 0x6c, 0x89, 0x17, 0x62, 0x17, 0x62, 0x4b, 0xb1, 0xb4, 0xde, 0xd1, 0x87, 0xc9, 0x14, 0x3c, 0x4a,
+        0x7e, 0xa8, 0xe2, 0x7d, 0xa0, 0x9f, 0xf6, 0x5c, 0x6a, 0x09, 0x8d, 0xf0, 0x0f, 0xe3, 0x53, 0x25, 0x95, 0x36, 0x28, 0xcb,
+    ],
+];
+
+type Block = [u8; BLOCK_SIZE];
+
+/// Moves bit `table[index] - 1` of the source to bit `index` of the result,
+/// starting at byte `offset` of the result.
+fn permute(source: &Block, table: &[u8], offset: usize) -> Block {
+    let mut result = [0; BLOCK_SIZE];
+
+    for (index, &bit) in table.iter().enumerate() {
+        let bit = bit as usize - 1;
+
+        if source[bit >> 3] & BIT_MASK[bit & 7] != 0 {
+            result[offset + (index >> 3)] |= BIT_MASK[index & 7];
+        }
+    }
+
+    result
+}
+
+/// Expands the right half of the block into eight 6 bit values.
+fn expand(block: &Block) -> Block {
+    [
+        ((block[7] << 5) | (block[4] >> 3)) & 0x3f,
+        ((block[4] << 1) | (block[5] >> 7)) & 0x3f,
+        ((block[4] << 5) | (block[5] >> 3)) & 0x3f,
+        ((block[5] << 1) | (block[6] >> 7)) & 0x3f,
+        ((block[5] << 5) | (block[6] >> 3)) & 0x3f,
+        ((block[6] << 1) | (block[7] >> 7)) & 0x3f,
+        ((block[6] << 5) | (block[7] >> 3)) & 0x3f,
+        ((block[7] << 1) | (block[4] >> 7)) & 0x3f,
+    ]
+}
+
+fn substitute(block: &Block) -> Block {
+    let mut result = [0; BLOCK_SIZE];
+
+    for (index, table) in SUBSTITUTION.iter().enumerate() {
+        result[index] = (table[block[index * 2] as usize] & 0xf0) | (table[block[index * 2 + 1] as usize] & 0x0f);
+    }
+
+    result
+}
+
+fn decrypt_block(block: &mut [u8]) {
+    let source: Block = (&*block).try_into().unwrap();
+    let mut permuted = permute(&source, &INITIAL_PERMUTATION, 0);
+    let round = permute(&substitute(&expand(&permuted)), &TRANSPOSITION, 4);
+
+    // Only the left half is changed, since there is just one round.
+    permuted.iter_mut().zip(&round[4..]).for_each(|(left, right)| *left ^= right);
+
+    block.copy_from_slice(&permute(&permuted, &FINAL_PERMUTATION, 0));
+}
+
+fn substitute_byte(byte: u8) -> u8 {
+    match byte {
+        0x00 => 0x2b,
+        0x2b => 0x00,
+        0x6c => 0x80,
+        0x80 => 0x6c,
+        0x01 => 0x68,
+        0x68 => 0x01,
+        0x48 => 0x77,
+        0x77 => 0x48,
+        0x60 => 0xff,
+        0xff => 0x60,
+        0xb9 => 0xc0,
+        0xc0 => 0xb9,
+        0xfe => 0xeb,
+        0xeb => 0xfe,
+        other => other,
+    }
+}
+
+fn unshuffle_block(block: &mut [u8]) {
+    let shuffled: Block = (&*block).try_into().unwrap();
+
+    block.copy_from_slice(&[
+        shuffled[3],
+        shuffled[4],
+        shuffled[6],
+        shuffled[0],
+        shuffled[1],
+        shuffled[2],
+        shuffled[5],
+        substitute_byte(shuffled[7]),
+    ]);
+}
+
+/// Distance between two encrypted blocks after the header, which depends on
+/// the number of digits of the compressed size.
+fn encryption_cycle(compressed_size: usize) -> usize {
+    let digits = compressed_size.max(1).ilog10() as usize + 1;
+
+    match digits {
+        0..=2 => 1,
+        3..=4 => digits + 1,
+        5..=6 => digits + 9,
+        _ => digits + 15,
+    }
+}
+
+pub fn is_encrypted(flags: u8) -> bool {
+    flags & (MIXED_ENCRYPTION_FLAG | HEADER_ENCRYPTION_FLAG) != 0
+}
+
+/// Decrypts the data of an entry in place. `data` is the aligned data as it
+/// is stored in the archive.
+pub fn decrypt_entry(data: &mut [u8], flags: u8, compressed_size: usize) {
+    let mixed = flags & MIXED_ENCRYPTION_FLAG != 0;
+
+    let mut blocks = data.chunks_exact_mut(BLOCK_SIZE);
+    blocks.by_ref().take(ENCRYPTED_HEADER_BLOCKS).for_each(decrypt_block);
+
+    if !mixed {
+        return;
+    }
+
+    let cycle = encryption_cycle(compressed_size);
+    let mut plain_blocks = 0;
+
+    for (index, block) in (ENCRYPTED_HEADER_BLOCKS..).zip(blocks) {
+        if index % cycle == 0 {
+            decrypt_block(block);
+            continue;
+        }
+
+        if plain_blocks % SHUFFLE_CYCLE == 0 && plain_blocks != 0 {
+            unshuffle_block(block);
+        }
+
+        plain_blocks += 1;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Data that is different in every byte of a block, so that moved bytes
+    /// are noticed.
+    fn test_data(length: usize) -> Vec<u8> {
+        (0..length).map(|index| (index * 37 + 11) as u8).collect()
+    }
+
+    fn block(data: &[u8], index: usize) -> &[u8] {
+        &data[index * BLOCK_SIZE..(index + 1) * BLOCK_SIZE]
+    }
+
+    #[test]
+    fn decrypt_zero_block() {
+        let mut zero_block = [0; BLOCK_SIZE];
+        decrypt_block(&mut zero_block);
+        assert_eq!(zero_block, [0x04, 0x04, 0x01, 0x55, 0x55, 0x01, 0x54, 0x55]);
+    }
+
+    #[test]
+    fn unshuffle_substitutes_last_byte() {
+        let mut shuffled_block = [0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x00];
+        unshuffle_block(&mut shuffled_block);
+        assert_eq!(shuffled_block, [0x13, 0x14, 0x16, 0x10, 0x11, 0x12, 0x15, 0x2b]);
+    }
+
+    #[test]
+    fn decrypt_header_only_entry() {
+        let data = test_data(24 * BLOCK_SIZE);
+        let mut decrypted = data.clone();
+        decrypt_entry(&mut decrypted, FILE_FLAG | HEADER_ENCRYPTION_FLAG, 190);
+
+        assert_eq!(block(&decrypted, 19), [0x13, 0x7d, 0x58, 0x72, 0xd7, 0xb9, 0xe0, 0x16]);
+        assert_eq!(decrypted[20 * BLOCK_SIZE..], data[20 * BLOCK_SIZE..]);
+    }
+
+    #[test]
+    fn decrypt_mixed_entry() {
+        let data = test_data(40 * BLOCK_SIZE);
+        let mut decrypted = data.clone();
+        // The compressed size has three digits, so every 4th block is encrypted.
+        decrypt_entry(&mut decrypted, FILE_FLAG | MIXED_ENCRYPTION_FLAG, 315);
+
+        assert_eq!(block(&decrypted, 20), [0x3b, 0x04, 0x24, 0x8b, 0xef, 0xf5, 0x4d, 0x6e]);
+        assert_eq!(block(&decrypted, 24), [0x9b, 0xb1, 0x14, 0x6b, 0x0e, 0xd5, 0xf9, 0xca]);
+        assert_eq!(block(&decrypted, 21), block(&data, 21));
+        assert_eq!(block(&decrypted, 29), block(&data, 29));
+
+        // Blocks 30 and 39 are the 8th and 15th unencrypted block.
+        assert_eq!(block(&decrypted, 30), [0x2a, 0x4f, 0x99, 0xbb, 0xe0, 0x05, 0x74, 0xbe]);
+        assert_eq!(block(&decrypted, 39), [0x92, 0xb7, 0x01, 0x23, 0x48, 0x6d, 0xdc, 0x26]);
+    }
+
+    #[test]
+    fn decrypt_short_entry() {
+        let data = test_data(12 * BLOCK_SIZE + 3);
+        let mut decrypted = data.clone();
+        decrypt_entry(&mut decrypted, FILE_FLAG | MIXED_ENCRYPTION_FLAG, 90);
+
+        // All blocks are part of the header, the bytes after the last full block
+        // are left alone.
+        for index in 0..12 {
+            let mut expected: Block = block(&data, index).try_into().unwrap();
+            decrypt_block(&mut expected);
+            assert_eq!(block(&decrypted, index), expected);
+        }
+
+        assert_eq!(block(&decrypted, 11), [0xc7, 0xad, 0x19, 0x62, 0x16, 0x79, 0xe0, 0xc6]);
+        assert_eq!(decrypted[12 * BLOCK_SIZE..], data[12 * BLOCK_SIZE..]);
+    }
+}
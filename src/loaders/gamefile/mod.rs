@@ -1,11 +1,18 @@
+mod encryption;
+
 use std::collections::HashMap;
-use std::fs;
+use std::fs::{self, File};
+use std::io::Write;
+use std::ops::{Deref, Range};
 use std::path::Path;
+use std::sync::Arc;
 
 use derive_new::new;
+use memmap2::Mmap;
 use procedural::*;
 use yazi::*;
 
+use self::encryption::{decrypt_entry, is_encrypted, FILE_FLAG};
 #[cfg(feature = "debug")]
 use crate::debug::*;
 use crate::loaders::{ByteConvertable, ByteStream};
@@ -56,12 +63,120 @@ pub struct FileInformation {
     pub offset: u32,
 }
 
-#[derive(Clone, Default, new)]
+/// Paths are looked up case insensitive and with either kind of slash, since
+/// the client and the archives don't agree on either.
+fn normalize_path(path: &str) -> String {
+    path.to_lowercase().replace('/', "\\")
+}
+
+/// Entries that didn't shrink when compressing are stored as is, which the
+/// file table marks by giving both sizes the same value. The data itself can't
+/// tell them apart, since a stored file may start with anything.
+fn is_stored(file_information: &FileInformation) -> bool {
+    file_information.compressed_size == file_information.uncompressed_size
+}
+
+/// Turns the (decrypted) data of an entry into the contents of the file.
+fn unpack(stored: FileData, file_information: &FileInformation) -> Option<FileData> {
+    let uncompressed_size = file_information.uncompressed_size as usize;
+
+    if is_stored(file_information) {
+        return stored.prefix(uncompressed_size);
+    }
+
+    // decompress straight from the archive into a buffer of the final size
+    let mut uncompressed = Vec::with_capacity(uncompressed_size);
+    let mut decoder = Decoder::new();
+    decoder.set_format(Format::Zlib);
+
+    // corrupted entries are treated like missing files
+    let mut stream = decoder.stream_into_vec(&mut uncompressed);
+    stream.write_all(&stored).ok()?;
+    stream.finish().ok()?;
+
+    FileData::Owned(uncompressed).into()
+}
+
+/// Archives loaded from disk are memory mapped, so only the parts that are
+/// actually read are paged in. Archives created at runtime own their data.
+pub enum ArchiveData {
+    Mapped(Arc<Mmap>),
+    Owned(Vec<u8>),
+}
+
+impl ArchiveData {
+    fn slice(&self, range: Range<usize>) -> FileData {
+        match self {
+            Self::Mapped(mmap) => FileData::Mapped { mmap: mmap.clone(), range },
+            Self::Owned(data) => FileData::Owned(data[range].to_vec()),
+        }
+    }
+}
+
+impl Default for ArchiveData {
+    fn default() -> Self {
+        Self::Owned(Vec::new())
+    }
+}
+
+impl Deref for ArchiveData {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            Self::Mapped(mmap) => mmap,
+            Self::Owned(data) => data,
+        }
+    }
+}
+
+/// Contents of a file. Entries that are stored as is point straight into the
+/// memory mapped archive, all others own their data.
+pub enum FileData {
+    Mapped { mmap: Arc<Mmap>, range: Range<usize> },
+    Owned(Vec<u8>),
+}
+
+impl FileData {
+    /// Only keep the first `length` bytes, without copying them.
+    fn prefix(self, length: usize) -> Option<Self> {
+        match self {
+            Self::Mapped { mmap, range } if range.len() >= length => Some(Self::Mapped {
+                mmap,
+                range: range.start..range.start + length,
+            }),
+            Self::Owned(mut data) if data.len() >= length => {
+                data.truncate(length);
+                Some(Self::Owned(data))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Deref for FileData {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            Self::Mapped { mmap, range } => &mmap[range.clone()],
+            Self::Owned(data) => data,
+        }
+    }
+}
+
+impl AsRef<[u8]> for FileData {
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+#[derive(Default, new)]
 pub struct GameArchive {
     #[new(default)]
     cache: HashMap<String, Vec<u8>>,
     files: HashMap<String, FileInformation>,
-    data: Vec<u8>,
+    data: ArchiveData,
 }
 
 impl GameArchive {
@@ -69,7 +184,10 @@ impl GameArchive {
         #[cfg(feature = "debug")]
         let timer = Timer::new_dynamic(format!("load game data from {}{}{}", MAGENTA, path, NONE));
 
-        let bytes = fs::read(path).unwrap_or_else(|_| panic!("failed to load archive from {}", path));
+        let file = File::open(path).unwrap_or_else(|_| panic!("failed to load archive from {}", path));
+        // SAFETY: the archive is not expected to be modified while the client is
+        // running
+        let bytes = unsafe { Mmap::map(&file) }.unwrap_or_else(|_| panic!("failed to map archive from {}", path));
         let mut byte_stream = ByteStream::new(&bytes);
 
         assert!(
@@ -83,8 +201,9 @@ impl GameArchive {
         byte_stream.skip(file_header.get_file_table_offset());
         let file_table = FileTable::from_bytes(&mut byte_stream, None);
 
-        let compressed = byte_stream.slice(file_table.get_compressed_size());
-        let (decompressed, _checksum) = decompress(&compressed, Format::Zlib).unwrap();
+        let file_table_start = byte_stream.get_offset();
+        let compressed = &bytes[file_table_start..file_table_start + file_table.get_compressed_size()];
+        let (decompressed, _checksum) = decompress(compressed, Format::Zlib).unwrap();
 
         let file_count = file_header.get_file_count();
 
@@ -93,7 +212,13 @@ impl GameArchive {
 
         for _index in 0..file_count {
             let file_information = FileInformation::from_bytes(&mut byte_stream, None);
-            let file_name = file_information.file_name.to_lowercase();
+
+            // directories are listed as well but don't have any data
+            if file_information.flags & FILE_FLAG == 0 {
+                continue;
+            }
+
+            let file_name = normalize_path(&file_information.file_name);
 
             if file_name.contains(".lub") {
                 lua_files.push(file_name.clone());
@@ -107,7 +232,7 @@ impl GameArchive {
 
         // TODO: only take 64..? bytes so that loaded game archives can be extended
         // aswell
-        Self::new(files, bytes)
+        Self::new(files, ArchiveData::Mapped(Arc::new(bytes)))
     }
 
     pub fn save(&mut self, file_name: &str) {
@@ -142,21 +267,32 @@ impl GameArchive {
         fs::write(file_name, bytes).expect("unable to write file");
     }
 
-    fn load_data(&self, file_path: &str) -> Option<Vec<u8>> {
+    fn load_data(&self, file_path: &str) -> Option<FileData> {
         let file_information = self.files.get(file_path)?;
 
-        let mut byte_stream = ByteStream::new(&self.data);
-        byte_stream.skip(file_information.offset as usize + 46);
-
-        let compressed = byte_stream.slice(file_information.compressed_size_aligned as usize);
-        let (uncompressed, _checksum) = decompress(&compressed, Format::Zlib).unwrap();
+        let start = file_information.offset as usize + 46;
+        let range = start..start + file_information.compressed_size_aligned as usize;
+
+        // encrypted entries have to be decrypted before they can be decompressed
+        let stored = match is_encrypted(file_information.flags) {
+            true => {
+                let mut decrypted = self.data[range].to_vec();
+                decrypt_entry(
+                    &mut decrypted,
+                    file_information.flags,
+                    file_information.compressed_size as usize,
+                );
+                FileData::Owned(decrypted)
+            }
+            false => self.data.slice(range),
+        };
 
-        uncompressed.into()
+        unpack(stored, file_information)
     }
 
-    pub fn get(&mut self, path: &str) -> Option<Vec<u8>> {
+    pub fn get(&self, path: &str) -> Option<FileData> {
         match self.cache.get(path) {
-            Some(data) => data.clone().into(),
+            Some(data) => FileData::Owned(data.clone()).into(),
             None => self.load_data(path),
         }
     }
@@ -171,6 +307,10 @@ impl GameArchive {
         let flags = 1;
         let offset = self.data.len() as u32;
 
+        if let ArchiveData::Mapped(mmap) = &self.data {
+            self.data = ArchiveData::Owned(mmap.to_vec());
+        }
+
         let file_information = FileInformation {
             file_name,
             compressed_size,
@@ -180,28 +320,32 @@ impl GameArchive {
             offset,
         };
 
-        self.data.extend_from_slice(&compressed);
+        let ArchiveData::Owned(data) = &mut self.data else { unreachable!() };
+
+        data.extend_from_slice(&compressed);
         self.files.insert(path, file_information);
     }
 }
 
-#[derive(Default)]
+/// Cloning is cheap since the archives are shared, so files can be loaded on
+/// other threads.
+#[derive(Clone, Default)]
 pub struct GameFileLoader {
-    archives: Vec<GameArchive>,
+    archives: Vec<Arc<GameArchive>>,
     lua_files: Vec<String>,
 }
 
 impl GameFileLoader {
     pub fn add_archive(&mut self, path: String) {
         let game_archive = GameArchive::load(&path, &mut self.lua_files);
-        self.archives.insert(0, game_archive);
+        self.archives.insert(0, Arc::new(game_archive));
     }
 
-    pub fn get(&mut self, path: &str) -> Result<Vec<u8>, String> {
+    pub fn get(&mut self, path: &str) -> Result<FileData, String> {
         let result = self
             .archives
-            .iter_mut() // convert this to a multithreaded iter ?
-            .find_map(|archive| archive.get(&path.to_lowercase()))
+            .iter() // convert this to a multithreaded iter ?
+            .find_map(|archive| archive.get(&normalize_path(path)))
             .ok_or(format!("failed to find file {}", path));
 
         if result.is_err() {
@@ -223,10 +367,10 @@ impl GameFileLoader {
         result
     }
 
-    pub fn patch(&mut self) {
+    pub fn patch(&mut self, lua_archive_path: &str) {
         use lunify::{unify, Format};
 
-        if Path::new("lua_files.grf").exists() {
+        if Path::new(lua_archive_path).exists() {
             return;
         }
 
@@ -245,6 +389,42 @@ impl GameFileLoader {
             }
         }
 
-        lua_archive.save("lua_files.grf");
+        lua_archive.save(lua_archive_path);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn file_information(compressed_size: usize, uncompressed_size: usize) -> FileInformation {
+        FileInformation {
+            file_name: "data\\test.txt".to_string(),
+            compressed_size: compressed_size as u32,
+            compressed_size_aligned: compressed_size as u32,
+            uncompressed_size: uncompressed_size as u32,
+            flags: FILE_FLAG,
+            offset: 0,
+        }
+    }
+
+    #[test]
+    fn stored_entry_that_looks_compressed() {
+        // 0x78 0x9c is a valid zlib header.
+        let data = b"x\x9c is not compressed".to_vec();
+        let file_information = file_information(data.len(), data.len());
+
+        let unpacked = unpack(FileData::Owned(data.clone()), &file_information).unwrap();
+        assert_eq!(*unpacked, *data);
+    }
+
+    #[test]
+    fn compressed_entry() {
+        let data = b"compressed compressed compressed compressed".to_vec();
+        let compressed = compress(&data, Format::Zlib, CompressionLevel::Default).unwrap();
+        let file_information = file_information(compressed.len(), data.len());
+
+        let unpacked = unpack(FileData::Owned(compressed), &file_information).unwrap();
+        assert_eq!(*unpacked, *data);
     }
 }
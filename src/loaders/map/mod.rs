@@ -1,22 +1,37 @@
+mod preload;
 mod resource;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
+#[cfg(feature = "debug")]
+use std::time::Instant;
 
-use cgmath::{Deg, Vector2, Vector3};
+use cgmath::{Deg, SquareMatrix, Vector2, Vector3};
 use derive_new::new;
 use procedural::*;
-use vulkano::buffer::{BufferUsage, CpuAccessibleBuffer};
+use rayon::prelude::*;
+use vulkano::buffer::{BufferAccess, BufferUsage, CpuAccessibleBuffer};
 
+pub use self::preload::MapPreloader;
 use self::resource::ResourceType;
 #[cfg(feature = "debug")]
 use crate::debug::*;
-use crate::graphics::{Color, MemoryAllocator, ModelVertex, NativeModelVertex, PickerTarget, TileVertex, Transform, WaterVertex};
+use crate::graphics::{
+    Color, MemoryAllocator, MemoryCategory, ModelVertex, NativeModelVertex, PickerTarget, TileVertex, Transform, WaterVertex,
+};
 use crate::loaders::{ByteStream, GameFileLoader, ModelLoader, TextureLoader, Version};
 use crate::world::*;
 
 const MAP_OFFSET: f32 = 5.0;
 const TILE_SIZE: f32 = 10.0;
+const SURFACE_SIZE: usize = 40;
+const GAT_TILE_SIZE: usize = 20;
+
+#[cfg(feature = "debug")]
+fn record_stage(resource_file: &str, stage: &str, stage_start: &mut Instant) {
+    record_load_timing(format!("{} {}", resource_file, stage), stage_start.elapsed());
+    *stage_start = Instant::now();
+}
 
 #[derive(Copy, Clone, Debug)]
 pub enum SurfaceType {
@@ -38,7 +53,7 @@ impl Surface {
         Self {
             u,
             v,
-            texture_index: texture_index % 29, // TODO: remove % 29 and derive new
+            texture_index,
             _light_map_index: light_map_index,
             _color: color,
         }
@@ -155,14 +170,51 @@ struct MapData {
     pub water_animation_speed: Option<i32>,
 }
 
+/// Number of frames a map that is no longer needed is kept around before it
+/// is released, so releasing it doesn't add to the frame of the map change.
+const RELEASE_DELAY_FRAMES: usize = 3;
+
 #[derive(new)]
 pub struct MapLoader {
     memory_allocator: Arc<MemoryAllocator>,
     #[new(default)]
     cache: HashMap<String, Arc<Map>>,
+    /// Maps waiting to be released, together with the frames left until then.
+    #[new(default)]
+    retired: VecDeque<(Arc<Map>, usize)>,
 }
 
 impl MapLoader {
+    /// Remove all maps from the cache that are not kept. They are released
+    /// over the next frames by [`release_retired_map`](Self::release_retired_map).
+    pub fn retire_maps(&mut self, keep: impl Fn(&str) -> bool) {
+        let retired_names: Vec<String> = self.cache.keys().filter(|map_name| !keep(map_name)).cloned().collect();
+
+        for map_name in retired_names {
+            #[cfg(feature = "debug")]
+            print_debug!("retiring map {}{}{}", MAGENTA, map_name, NONE);
+
+            let map = self.cache.remove(&map_name).unwrap();
+            self.retired.push_back((map, RELEASE_DELAY_FRAMES));
+        }
+    }
+
+    /// Release at most one retired map, so that the buffers and textures of
+    /// multiple maps are never all freed in the same frame. Called once per
+    /// frame.
+    pub fn release_retired_map(&mut self) {
+        let Some((_, frames_left)) = self.retired.front_mut() else {
+            return;
+        };
+
+        match *frames_left {
+            0 => {
+                self.retired.pop_front();
+            }
+            _ => *frames_left -= 1,
+        }
+    }
+
     fn load(
         &mut self,
         resource_file: String,
@@ -173,6 +225,9 @@ impl MapLoader {
         #[cfg(feature = "debug")]
         let timer = Timer::new_dynamic(format!("load map from {}", resource_file));
 
+        #[cfg(feature = "debug")]
+        let mut stage_start = Instant::now();
+
         let bytes = game_file_loader.get(&format!("data\\{}.rsw", resource_file))?;
         let mut byte_stream = ByteStream::new(&bytes);
 
@@ -361,6 +416,9 @@ impl MapLoader {
         #[cfg(feature = "debug")]
         byte_stream.assert_empty(&resource_file);
 
+        #[cfg(feature = "debug")]
+        record_stage(&resource_file, "resources", &mut stage_start);
+
         let bytes = game_file_loader.get(&format!("data\\{}", ground_file))?;
         let mut byte_stream = ByteStream::new(&bytes);
 
@@ -381,13 +439,10 @@ impl MapLoader {
         let texture_count = byte_stream.integer32();
         let texture_name_length = byte_stream.integer32();
 
-        let mut textures = Vec::new();
-
-        for _index in 0..texture_count {
-            let texture_name = byte_stream.string(texture_name_length as usize);
-            let texture = texture_loader.get(&texture_name, game_file_loader)?;
-            textures.push(texture);
-        }
+        let texture_names: Vec<String> = (0..texture_count)
+            .map(|_| byte_stream.string(texture_name_length as usize))
+            .collect();
+        let ground_texture_array = texture_loader.get_array(&texture_names, game_file_loader)?;
 
         let light_map_count = byte_stream.integer32() as usize;
         let light_map_width = byte_stream.integer32() as usize;
@@ -402,73 +457,89 @@ impl MapLoader {
             false => byte_stream.skip(light_map_count * 16),
         }
 
-        let surface_count = byte_stream.integer32();
-        let mut surfaces = Vec::new();
-
-        for _index in 0..surface_count {
-            let u = [
-                byte_stream.float32(),
-                byte_stream.float32(),
-                byte_stream.float32(),
-                byte_stream.float32(),
-            ];
-            let v = [
-                byte_stream.float32(),
-                byte_stream.float32(),
-                byte_stream.float32(),
-                byte_stream.float32(),
-            ];
-
-            let texture_index = byte_stream.integer16() as i32;
-            let light_map_index = byte_stream.integer16() as i32;
-            let color_bgra = byte_stream.slice(4);
-
-            let color = Color::rgb(color_bgra[2], color_bgra[1], color_bgra[0]);
-            surfaces.push(Surface::new(u, v, texture_index, light_map_index, color));
-        }
+        let surface_count = byte_stream.integer32() as usize;
+        let surface_bytes = &bytes[byte_stream.get_offset()..][..surface_count * SURFACE_SIZE];
+        byte_stream.skip(surface_count * SURFACE_SIZE);
+
+        let surfaces: Vec<Surface> = surface_bytes
+            .par_chunks_exact(SURFACE_SIZE)
+            .map(|surface_bytes| {
+                let mut byte_stream = ByteStream::new(surface_bytes);
+
+                let u = [
+                    byte_stream.float32(),
+                    byte_stream.float32(),
+                    byte_stream.float32(),
+                    byte_stream.float32(),
+                ];
+                let v = [
+                    byte_stream.float32(),
+                    byte_stream.float32(),
+                    byte_stream.float32(),
+                    byte_stream.float32(),
+                ];
+
+                let texture_index = byte_stream.integer16() as i32;
+                let light_map_index = byte_stream.integer16() as i32;
+                let color_bgra = byte_stream.slice(4);
+
+                let color = Color::rgb(color_bgra[2], color_bgra[1], color_bgra[0]);
+                Surface::new(u, v, texture_index, light_map_index, color)
+            })
+            .collect();
+
+        let wide_surface_indices = ground_version.equals_or_above(1, 7);
+        let ground_tile_size = match wide_surface_indices {
+            true => 28,
+            false => 22,
+        };
 
-        let mut ground_tiles = Vec::new();
-
-        for _index in 0..dimensions {
-            let upper_left_height = byte_stream.float32();
-            let upper_right_height = byte_stream.float32();
-            let lower_left_height = byte_stream.float32();
-            let lower_right_height = byte_stream.float32();
-
-            let top_surface_index = match ground_version.equals_or_above(1, 7) {
-                true => byte_stream.integer32(),
-                false => byte_stream.integer16() as i32,
-            };
-
-            let front_surface_index = match ground_version.equals_or_above(1, 7) {
-                true => byte_stream.integer32(),
-                false => byte_stream.integer16() as i32,
-            };
-
-            let right_surface_index = match ground_version.equals_or_above(1, 7) {
-                true => byte_stream.integer32(),
-                false => byte_stream.integer16() as i32,
-            };
-
-            ground_tiles.push(GroundTile::new(
-                upper_left_height,
-                upper_right_height,
-                lower_left_height,
-                lower_right_height,
-                top_surface_index,
-                front_surface_index,
-                right_surface_index,
-            ));
-        }
+        let ground_tile_bytes = &bytes[byte_stream.get_offset()..][..dimensions * ground_tile_size];
+        byte_stream.skip(dimensions * ground_tile_size);
+
+        let ground_tiles: Vec<GroundTile> = ground_tile_bytes
+            .par_chunks_exact(ground_tile_size)
+            .map(|ground_tile_bytes| {
+                let mut byte_stream = ByteStream::new(ground_tile_bytes);
+
+                let upper_left_height = byte_stream.float32();
+                let upper_right_height = byte_stream.float32();
+                let lower_left_height = byte_stream.float32();
+                let lower_right_height = byte_stream.float32();
+
+                let mut surface_index = || match wide_surface_indices {
+                    true => byte_stream.integer32(),
+                    false => byte_stream.integer16() as i32,
+                };
+
+                let top_surface_index = surface_index();
+                let front_surface_index = surface_index();
+                let right_surface_index = surface_index();
+
+                GroundTile::new(
+                    upper_left_height,
+                    upper_right_height,
+                    lower_left_height,
+                    lower_right_height,
+                    top_surface_index,
+                    front_surface_index,
+                    right_surface_index,
+                )
+            })
+            .collect();
 
         #[cfg(feature = "debug")]
         byte_stream.assert_empty(&ground_file);
 
+        #[cfg(feature = "debug")]
+        record_stage(&resource_file, "ground", &mut stage_start);
+
         let mut map_width = width;
         let mut map_height = height;
         let mut tiles = Vec::new();
         let mut tile_vertex_buffer = None;
         let mut tile_picker_vertex_buffer = None;
+        let mut geometry_size = 0;
 
         if let Some(gat_file) = gat_file {
             let bytes = game_file_loader.get(&format!("data\\{}", gat_file))?;
@@ -488,106 +559,127 @@ impl MapLoader {
             map_width = byte_stream.integer32() as usize; // todo: unsigned
             map_height = byte_stream.integer32() as usize; // todo: unsigned
 
-            let mut tile_vertices = Vec::new();
-            let mut tile_picker_vertices = Vec::new();
+            let row_size = map_width * GAT_TILE_SIZE;
+            let tile_bytes = &bytes[byte_stream.get_offset()..][..row_size * map_height];
+            byte_stream.skip(row_size * map_height);
+
+            let rows: Vec<(Vec<Tile>, Vec<ModelVertex>, Vec<TileVertex>)> = tile_bytes
+                .par_chunks_exact(row_size)
+                .enumerate()
+                .map(|(y, row_bytes)| {
+                    let mut byte_stream = ByteStream::new(row_bytes);
+                    let mut tiles = Vec::with_capacity(map_width);
+                    let mut tile_vertices = Vec::new();
+                    let mut tile_picker_vertices = Vec::new();
+
+                    for x in 0..map_width {
+                        let upper_left_height = -byte_stream.float32();
+                        let upper_right_height = -byte_stream.float32();
+                        let lower_left_height = -byte_stream.float32();
+                        let lower_right_height = -byte_stream.float32();
+                        let tile_type_index = byte_stream.byte();
+                        let tile_type = TileType::new(tile_type_index);
+
+                        // unknown
+                        byte_stream.skip(3);
+
+                        tiles.push(Tile::new(
+                            upper_left_height,
+                            upper_right_height,
+                            lower_left_height,
+                            lower_right_height,
+                            tile_type,
+                        ));
 
-            for y in 0..map_height {
-                for x in 0..map_width {
-                    let upper_left_height = -byte_stream.float32();
-                    let upper_right_height = -byte_stream.float32();
-                    let lower_left_height = -byte_stream.float32();
-                    let lower_right_height = -byte_stream.float32();
-                    let tile_type_index = byte_stream.byte();
-                    let tile_type = TileType::new(tile_type_index);
-
-                    // unknown
-                    byte_stream.skip(3);
-
-                    tiles.push(Tile::new(
-                        upper_left_height,
-                        upper_right_height,
-                        lower_left_height,
-                        lower_right_height,
-                        tile_type,
-                    ));
+                        if tile_type.is_none() {
+                            continue;
+                        }
 
-                    if tile_type.is_none() {
-                        continue;
-                    }
+                        let offset = Vector2::new(x as f32 * 5.0, y as f32 * 5.0);
 
-                    let offset = Vector2::new(x as f32 * 5.0, y as f32 * 5.0);
+                        let first_position = Vector3::new(offset.x, upper_left_height + 1.0, offset.y);
+                        let second_position = Vector3::new(offset.x + 5.0, upper_right_height + 1.0, offset.y);
+                        let third_position = Vector3::new(offset.x + 5.0, lower_right_height + 1.0, offset.y + 5.0);
+                        let fourth_position = Vector3::new(offset.x, lower_left_height + 1.0, offset.y + 5.0);
 
-                    let first_position = Vector3::new(offset.x, upper_left_height + 1.0, offset.y);
-                    let second_position = Vector3::new(offset.x + 5.0, upper_right_height + 1.0, offset.y);
-                    let third_position = Vector3::new(offset.x + 5.0, lower_right_height + 1.0, offset.y + 5.0);
-                    let fourth_position = Vector3::new(offset.x, lower_left_height + 1.0, offset.y + 5.0);
+                        let first_normal = NativeModelVertex::calculate_normal(first_position, second_position, third_position);
+                        let second_normal = NativeModelVertex::calculate_normal(fourth_position, first_position, third_position);
 
-                    let first_normal = NativeModelVertex::calculate_normal(first_position, second_position, third_position);
-                    let second_normal = NativeModelVertex::calculate_normal(fourth_position, first_position, third_position);
+                        let first_texture_coordinates = Vector2::new(0.0, 0.0);
+                        let second_texture_coordinates = Vector2::new(0.0, 1.0);
+                        let third_texture_coordinates = Vector2::new(1.0, 1.0);
+                        let fourth_texture_coordinates = Vector2::new(1.0, 0.0);
 
-                    let first_texture_coordinates = Vector2::new(0.0, 0.0);
-                    let second_texture_coordinates = Vector2::new(0.0, 1.0);
-                    let third_texture_coordinates = Vector2::new(1.0, 1.0);
-                    let fourth_texture_coordinates = Vector2::new(1.0, 0.0);
+                        tile_vertices.push(ModelVertex::new(
+                            first_position,
+                            first_normal,
+                            first_texture_coordinates,
+                            tile_type_index as i32,
+                            0.0,
+                        ));
+                        tile_vertices.push(ModelVertex::new(
+                            second_position,
+                            first_normal,
+                            second_texture_coordinates,
+                            tile_type_index as i32,
+                            0.0,
+                        ));
+                        tile_vertices.push(ModelVertex::new(
+                            third_position,
+                            first_normal,
+                            third_texture_coordinates,
+                            tile_type_index as i32,
+                            0.0,
+                        ));
 
-                    tile_vertices.push(ModelVertex::new(
-                        first_position,
-                        first_normal,
-                        first_texture_coordinates,
-                        tile_type_index as i32,
-                        0.0,
-                    ));
-                    tile_vertices.push(ModelVertex::new(
-                        second_position,
-                        first_normal,
-                        second_texture_coordinates,
-                        tile_type_index as i32,
-                        0.0,
-                    ));
-                    tile_vertices.push(ModelVertex::new(
-                        third_position,
-                        first_normal,
-                        third_texture_coordinates,
-                        tile_type_index as i32,
-                        0.0,
-                    ));
+                        tile_vertices.push(ModelVertex::new(
+                            first_position,
+                            second_normal,
+                            first_texture_coordinates,
+                            tile_type_index as i32,
+                            0.0,
+                        ));
+                        tile_vertices.push(ModelVertex::new(
+                            third_position,
+                            second_normal,
+                            third_texture_coordinates,
+                            tile_type_index as i32,
+                            0.0,
+                        ));
+                        tile_vertices.push(ModelVertex::new(
+                            fourth_position,
+                            second_normal,
+                            fourth_texture_coordinates,
+                            tile_type_index as i32,
+                            0.0,
+                        ));
 
-                    tile_vertices.push(ModelVertex::new(
-                        first_position,
-                        second_normal,
-                        first_texture_coordinates,
-                        tile_type_index as i32,
-                        0.0,
-                    ));
-                    tile_vertices.push(ModelVertex::new(
-                        third_position,
-                        second_normal,
-                        third_texture_coordinates,
-                        tile_type_index as i32,
-                        0.0,
-                    ));
-                    tile_vertices.push(ModelVertex::new(
-                        fourth_position,
-                        second_normal,
-                        fourth_texture_coordinates,
-                        tile_type_index as i32,
-                        0.0,
-                    ));
+                        let first_position = Vector3::new(offset.x, upper_left_height, offset.y);
+                        let second_position = Vector3::new(offset.x + 5.0, upper_right_height, offset.y);
+                        let third_position = Vector3::new(offset.x + 5.0, lower_right_height, offset.y + 5.0);
+                        let fourth_position = Vector3::new(offset.x, lower_left_height, offset.y + 5.0);
 
-                    let first_position = Vector3::new(offset.x, upper_left_height, offset.y);
-                    let second_position = Vector3::new(offset.x + 5.0, upper_right_height, offset.y);
-                    let third_position = Vector3::new(offset.x + 5.0, lower_right_height, offset.y + 5.0);
-                    let fourth_position = Vector3::new(offset.x, lower_left_height, offset.y + 5.0);
+                        let color = PickerTarget::Tile(x as u16, y as u16).into();
+                        tile_picker_vertices.push(TileVertex::new(first_position, color));
+                        tile_picker_vertices.push(TileVertex::new(second_position, color));
+                        tile_picker_vertices.push(TileVertex::new(third_position, color));
 
-                    let color = PickerTarget::Tile(x as u16, y as u16).into();
-                    tile_picker_vertices.push(TileVertex::new(first_position, color));
-                    tile_picker_vertices.push(TileVertex::new(second_position, color));
-                    tile_picker_vertices.push(TileVertex::new(third_position, color));
+                        tile_picker_vertices.push(TileVertex::new(first_position, color));
+                        tile_picker_vertices.push(TileVertex::new(third_position, color));
+                        tile_picker_vertices.push(TileVertex::new(fourth_position, color));
+                    }
 
-                    tile_picker_vertices.push(TileVertex::new(first_position, color));
-                    tile_picker_vertices.push(TileVertex::new(third_position, color));
-                    tile_picker_vertices.push(TileVertex::new(fourth_position, color));
-                }
+                    (tiles, tile_vertices, tile_picker_vertices)
+                })
+                .collect();
+
+            let mut tile_vertices = Vec::new();
+            let mut tile_picker_vertices = Vec::new();
+
+            for (row_tiles, row_tile_vertices, row_tile_picker_vertices) in rows {
+                tiles.extend(row_tiles);
+                tile_vertices.extend(row_tile_vertices);
+                tile_picker_vertices.extend(row_tile_picker_vertices);
             }
 
             #[cfg(feature = "debug")]
@@ -603,6 +695,7 @@ impl MapLoader {
                 tile_vertices.into_iter(),
             )
             .unwrap();
+            geometry_size += vertex_buffer.size();
             tile_vertex_buffer = Some(vertex_buffer);
 
             let vertex_buffer = CpuAccessibleBuffer::from_iter(
@@ -615,145 +708,159 @@ impl MapLoader {
                 tile_picker_vertices.into_iter(),
             )
             .unwrap();
+            geometry_size += vertex_buffer.size();
             tile_picker_vertex_buffer = Some(vertex_buffer);
         }
 
-        let mut native_ground_vertices = Vec::new();
-        let mut water_vertices = Vec::new();
-
-        for x in 0..width {
-            for y in 0..height {
-                let current_tile = &ground_tiles[x + y * width];
-
-                for surface_type in [SurfaceType::Front, SurfaceType::Right, SurfaceType::Top].iter() {
-                    let surface_index = tile_surface_index(current_tile, *surface_type);
-
-                    if surface_index > -1 {
-                        let surface_alignment = tile_surface_alignment(*surface_type);
-                        let neighbor_tile_index = neighbor_tile_index(*surface_type);
-
-                        let neighbor_x = x + neighbor_tile_index.x;
-                        let neighbor_y = y + neighbor_tile_index.y;
-                        let Some(neighbor_tile) = ground_tiles.get(neighbor_x + neighbor_y * width) else {
-                            continue;
-                        };
-
-                        let (surface_offset, surface_height) = surface_alignment[0];
-                        let height = get_tile_height_at(current_tile, surface_height);
-                        let first_position = Vector3::new(
-                            (x + surface_offset.x) as f32 * TILE_SIZE,
-                            -height,
-                            (y + surface_offset.y) as f32 * TILE_SIZE,
-                        );
+        #[cfg(feature = "debug")]
+        record_stage(&resource_file, "tiles", &mut stage_start);
+
+        // every column is built on its own and the results are joined afterwards, so
+        // the vertex order is the same as building them sequentially
+        let (ground_columns, water_columns): (Vec<Vec<NativeModelVertex>>, Vec<Vec<WaterVertex>>) = (0..width)
+            .into_par_iter()
+            .map(|x| {
+                let mut native_ground_vertices = Vec::new();
+                let mut water_vertices = Vec::new();
+
+                for y in 0..height {
+                    let current_tile = &ground_tiles[x + y * width];
+
+                    for surface_type in [SurfaceType::Front, SurfaceType::Right, SurfaceType::Top].iter() {
+                        let surface_index = tile_surface_index(current_tile, *surface_type);
+
+                        if surface_index > -1 {
+                            let surface_alignment = tile_surface_alignment(*surface_type);
+                            let neighbor_tile_index = neighbor_tile_index(*surface_type);
+
+                            let neighbor_x = x + neighbor_tile_index.x;
+                            let neighbor_y = y + neighbor_tile_index.y;
+                            let Some(neighbor_tile) = ground_tiles.get(neighbor_x + neighbor_y * width) else {
+                                continue;
+                            };
+
+                            let (surface_offset, surface_height) = surface_alignment[0];
+                            let height = get_tile_height_at(current_tile, surface_height);
+                            let first_position = Vector3::new(
+                                (x + surface_offset.x) as f32 * TILE_SIZE,
+                                -height,
+                                (y + surface_offset.y) as f32 * TILE_SIZE,
+                            );
+
+                            let (surface_offset, surface_height) = surface_alignment[1];
+                            let height = get_tile_height_at(current_tile, surface_height);
+                            let second_position = Vector3::new(
+                                (x + surface_offset.x) as f32 * TILE_SIZE,
+                                -height,
+                                (y + surface_offset.y) as f32 * TILE_SIZE,
+                            );
+
+                            let (surface_offset, surface_height) = surface_alignment[2];
+                            let height = get_tile_height_at(neighbor_tile, surface_height);
+                            let third_position = Vector3::new(
+                                (x + surface_offset.x) as f32 * TILE_SIZE,
+                                -height,
+                                (y + surface_offset.y) as f32 * TILE_SIZE,
+                            );
+
+                            let (surface_offset, surface_height) = surface_alignment[3];
+                            let height = get_tile_height_at(neighbor_tile, surface_height);
+                            let fourth_position = Vector3::new(
+                                (x + surface_offset.x) as f32 * TILE_SIZE,
+                                -height,
+                                (y + surface_offset.y) as f32 * TILE_SIZE,
+                            );
+
+                            let first_normal = NativeModelVertex::calculate_normal(first_position, second_position, third_position);
+                            let second_normal = NativeModelVertex::calculate_normal(fourth_position, first_position, third_position);
+
+                            let ground_surface = &surfaces[surface_index as usize];
+
+                            let first_texture_coordinates = Vector2::new(ground_surface.u[0], ground_surface.v[0]);
+                            let second_texture_coordinates = Vector2::new(ground_surface.u[1], ground_surface.v[1]);
+                            let third_texture_coordinates = Vector2::new(ground_surface.u[3], ground_surface.v[3]);
+                            let fourth_texture_coordinates = Vector2::new(ground_surface.u[2], ground_surface.v[2]);
+
+                            native_ground_vertices.push(NativeModelVertex::new(
+                                first_position,
+                                first_normal,
+                                first_texture_coordinates,
+                                ground_surface.texture_index,
+                                0.0,
+                            ));
+                            native_ground_vertices.push(NativeModelVertex::new(
+                                second_position,
+                                first_normal,
+                                second_texture_coordinates,
+                                ground_surface.texture_index,
+                                0.0,
+                            ));
+                            native_ground_vertices.push(NativeModelVertex::new(
+                                third_position,
+                                first_normal,
+                                third_texture_coordinates,
+                                ground_surface.texture_index,
+                                0.0,
+                            ));
+
+                            native_ground_vertices.push(NativeModelVertex::new(
+                                first_position,
+                                second_normal,
+                                first_texture_coordinates,
+                                ground_surface.texture_index,
+                                0.0,
+                            ));
+                            native_ground_vertices.push(NativeModelVertex::new(
+                                third_position,
+                                second_normal,
+                                third_texture_coordinates,
+                                ground_surface.texture_index,
+                                0.0,
+                            ));
+                            native_ground_vertices.push(NativeModelVertex::new(
+                                fourth_position,
+                                second_normal,
+                                fourth_texture_coordinates,
+                                ground_surface.texture_index,
+                                0.0,
+                            ));
+                        }
+                    }
 
-                        let (surface_offset, surface_height) = surface_alignment[1];
-                        let height = get_tile_height_at(current_tile, surface_height);
+                    if -current_tile.get_lowest_point() < water_settings.water_level {
+                        let first_position = Vector3::new(x as f32 * TILE_SIZE, water_settings.water_level, y as f32 * TILE_SIZE);
                         let second_position = Vector3::new(
-                            (x + surface_offset.x) as f32 * TILE_SIZE,
-                            -height,
-                            (y + surface_offset.y) as f32 * TILE_SIZE,
+                            TILE_SIZE + x as f32 * TILE_SIZE,
+                            water_settings.water_level,
+                            y as f32 * TILE_SIZE,
                         );
-
-                        let (surface_offset, surface_height) = surface_alignment[2];
-                        let height = get_tile_height_at(neighbor_tile, surface_height);
                         let third_position = Vector3::new(
-                            (x + surface_offset.x) as f32 * TILE_SIZE,
-                            -height,
-                            (y + surface_offset.y) as f32 * TILE_SIZE,
+                            TILE_SIZE + x as f32 * TILE_SIZE,
+                            water_settings.water_level,
+                            TILE_SIZE + y as f32 * TILE_SIZE,
                         );
-
-                        let (surface_offset, surface_height) = surface_alignment[3];
-                        let height = get_tile_height_at(neighbor_tile, surface_height);
                         let fourth_position = Vector3::new(
-                            (x + surface_offset.x) as f32 * TILE_SIZE,
-                            -height,
-                            (y + surface_offset.y) as f32 * TILE_SIZE,
+                            x as f32 * TILE_SIZE,
+                            water_settings.water_level,
+                            TILE_SIZE + y as f32 * TILE_SIZE,
                         );
 
-                        let first_normal = NativeModelVertex::calculate_normal(first_position, second_position, third_position);
-                        let second_normal = NativeModelVertex::calculate_normal(fourth_position, first_position, third_position);
-
-                        let ground_surface = &surfaces[surface_index as usize];
-
-                        let first_texture_coordinates = Vector2::new(ground_surface.u[0], ground_surface.v[0]);
-                        let second_texture_coordinates = Vector2::new(ground_surface.u[1], ground_surface.v[1]);
-                        let third_texture_coordinates = Vector2::new(ground_surface.u[3], ground_surface.v[3]);
-                        let fourth_texture_coordinates = Vector2::new(ground_surface.u[2], ground_surface.v[2]);
+                        water_vertices.push(WaterVertex::new(first_position));
+                        water_vertices.push(WaterVertex::new(second_position));
+                        water_vertices.push(WaterVertex::new(third_position));
 
-                        native_ground_vertices.push(NativeModelVertex::new(
-                            first_position,
-                            first_normal,
-                            first_texture_coordinates,
-                            ground_surface.texture_index,
-                            0.0,
-                        ));
-                        native_ground_vertices.push(NativeModelVertex::new(
-                            second_position,
-                            first_normal,
-                            second_texture_coordinates,
-                            ground_surface.texture_index,
-                            0.0,
-                        ));
-                        native_ground_vertices.push(NativeModelVertex::new(
-                            third_position,
-                            first_normal,
-                            third_texture_coordinates,
-                            ground_surface.texture_index,
-                            0.0,
-                        ));
-
-                        native_ground_vertices.push(NativeModelVertex::new(
-                            first_position,
-                            second_normal,
-                            first_texture_coordinates,
-                            ground_surface.texture_index,
-                            0.0,
-                        ));
-                        native_ground_vertices.push(NativeModelVertex::new(
-                            third_position,
-                            second_normal,
-                            third_texture_coordinates,
-                            ground_surface.texture_index,
-                            0.0,
-                        ));
-                        native_ground_vertices.push(NativeModelVertex::new(
-                            fourth_position,
-                            second_normal,
-                            fourth_texture_coordinates,
-                            ground_surface.texture_index,
-                            0.0,
-                        ));
+                        water_vertices.push(WaterVertex::new(first_position));
+                        water_vertices.push(WaterVertex::new(third_position));
+                        water_vertices.push(WaterVertex::new(fourth_position));
                     }
                 }
 
-                if -current_tile.get_lowest_point() < water_settings.water_level {
-                    let first_position = Vector3::new(x as f32 * TILE_SIZE, water_settings.water_level, y as f32 * TILE_SIZE);
-                    let second_position = Vector3::new(
-                        TILE_SIZE + x as f32 * TILE_SIZE,
-                        water_settings.water_level,
-                        y as f32 * TILE_SIZE,
-                    );
-                    let third_position = Vector3::new(
-                        TILE_SIZE + x as f32 * TILE_SIZE,
-                        water_settings.water_level,
-                        TILE_SIZE + y as f32 * TILE_SIZE,
-                    );
-                    let fourth_position = Vector3::new(
-                        x as f32 * TILE_SIZE,
-                        water_settings.water_level,
-                        TILE_SIZE + y as f32 * TILE_SIZE,
-                    );
-
-                    water_vertices.push(WaterVertex::new(first_position));
-                    water_vertices.push(WaterVertex::new(second_position));
-                    water_vertices.push(WaterVertex::new(third_position));
-
-                    water_vertices.push(WaterVertex::new(first_position));
-                    water_vertices.push(WaterVertex::new(third_position));
-                    water_vertices.push(WaterVertex::new(fourth_position));
-                }
-            }
-        }
+                (native_ground_vertices, water_vertices)
+            })
+            .unzip();
+
+        let native_ground_vertices: Vec<NativeModelVertex> = ground_columns.into_iter().flatten().collect();
+        let water_vertices: Vec<WaterVertex> = water_columns.into_iter().flatten().collect();
 
         /*let row_/size = width * 6;
 
@@ -783,7 +890,12 @@ impl MapLoader {
             indices.iter().for_each(|index| native_ground_vertices[*index].normal = new_normal);
         }*/
 
+        let native_ground_vertices = NativeModelVertex::remove_degenerate_triangles(native_ground_vertices);
         let ground_vertices = NativeModelVertex::to_vertices(native_ground_vertices);
+
+        #[cfg(feature = "debug")]
+        record_stage(&resource_file, "ground mesh", &mut stage_start);
+
         let ground_vertex_buffer = CpuAccessibleBuffer::from_iter(
             &*self.memory_allocator,
             BufferUsage {
@@ -794,6 +906,7 @@ impl MapLoader {
             ground_vertices.into_iter(),
         )
         .unwrap();
+        geometry_size += ground_vertex_buffer.size();
 
         let water_vertex_buffer = match !water_vertices.is_empty() {
             true => CpuAccessibleBuffer::from_iter(
@@ -810,12 +923,21 @@ impl MapLoader {
             false => None,
         };
 
+        if let Some(water_vertex_buffer) = &water_vertex_buffer {
+            geometry_size += water_vertex_buffer.size();
+        }
+
+        // Counted until the map is released.
+        let geometry_allocation = self.memory_allocator.track_allocation(MemoryCategory::MapGeometry, geometry_size);
+
         let offset = Vector3::new(width as f32 * MAP_OFFSET, 0.0, height as f32 * MAP_OFFSET);
         objects.iter_mut().for_each(|object| object.offset(offset));
         light_sources.iter_mut().for_each(|light_source| light_source.offset(offset));
         sound_sources.iter_mut().for_each(|sound_source| sound_source.offset(offset));
         effect_sources.iter_mut().for_each(|effect_source| effect_source.offset(offset));
 
+        let object_inverse_matrices = objects.iter().map(|object| object.get_bounding_box_matrix().invert()).collect();
+
         let map = Arc::new(Map::new(
             resource_version,
             ground_version,
@@ -826,13 +948,15 @@ impl MapLoader {
             tiles,
             ground_vertex_buffer,
             water_vertex_buffer,
-            textures,
+            ground_texture_array,
             objects,
+            object_inverse_matrices,
             light_sources,
             sound_sources,
             effect_sources,
             tile_picker_vertex_buffer.unwrap(),
             tile_vertex_buffer.unwrap(),
+            geometry_allocation,
         ));
 
         self.cache.insert(resource_file, map.clone());
@@ -843,6 +967,16 @@ impl MapLoader {
         Ok(map)
     }
 
+    pub fn is_cached(&self, resource_file: &str) -> bool {
+        self.cache.contains_key(resource_file)
+    }
+
+    /// Add a map that was loaded on another thread. If the map was loaded here
+    /// in the meantime, that one is kept.
+    pub fn insert(&mut self, resource_file: String, map: Arc<Map>) {
+        self.cache.entry(resource_file).or_insert(map);
+    }
+
     pub fn get(
         &mut self,
         resource_file: String,
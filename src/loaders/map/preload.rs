@@ -0,0 +1,189 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::Arc;
+
+use cgmath::Vector2;
+use rayon::ThreadPool;
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+use vulkano::device::Queue;
+use vulkano::sync::GpuFuture;
+
+use super::MapLoader;
+#[cfg(feature = "debug")]
+use crate::debug::*;
+use crate::graphics::MemoryAllocator;
+use crate::loaders::{GameFileLoader, ModelLoader, TextureCache, TextureLoader};
+use crate::system::profile_file;
+use crate::world::Map;
+
+const MAP_CONNECTIONS_FILE: &str = "map_connections.ron";
+/// Distance in tiles at which the destination of a warp starts loading.
+const PRELOAD_DISTANCE: usize = 8;
+
+/// A preloaded map and the textures that were uploaded for it.
+type PreloadedMap = (String, Result<Arc<Map>, String>, TextureCache);
+
+#[derive(Serialize, Deserialize)]
+struct MapConnection {
+    warp_position: Vector2<usize>,
+    destination: String,
+}
+
+/// The client doesn't know where a warp leads until the player uses it, so the
+/// connections between maps are learned and remembered across sessions. Once
+/// the player walks close to a known warp, its destination is loaded on the
+/// thread pool, so the map change itself only has to hit the map cache.
+pub struct MapPreloader {
+    connections: HashMap<String, Vec<MapConnection>>,
+    current_map: Option<String>,
+    requested: HashSet<String>,
+    loaded_sender: Sender<PreloadedMap>,
+    loaded_receiver: Receiver<PreloadedMap>,
+}
+
+impl MapPreloader {
+    pub fn new() -> Self {
+        let connections = Self::load().unwrap_or_else(|| {
+            #[cfg(feature = "debug")]
+            print_debug!(
+                "failed to load map connections from {}{}{}",
+                MAGENTA,
+                MAP_CONNECTIONS_FILE,
+                NONE
+            );
+
+            Default::default()
+        });
+
+        let (loaded_sender, loaded_receiver) = channel();
+
+        Self {
+            connections,
+            current_map: None,
+            requested: HashSet::new(),
+            loaded_sender,
+            loaded_receiver,
+        }
+    }
+
+    fn load() -> Option<HashMap<String, Vec<MapConnection>>> {
+        #[cfg(feature = "debug")]
+        print_debug!("loading map connections from {}{}{}", MAGENTA, MAP_CONNECTIONS_FILE, NONE);
+
+        std::fs::read_to_string(profile_file(MAP_CONNECTIONS_FILE))
+            .ok()
+            .and_then(|data| ron::from_str(&data).ok())
+    }
+
+    pub fn save(&self) {
+        #[cfg(feature = "debug")]
+        print_debug!("saving map connections to {}{}{}", MAGENTA, MAP_CONNECTIONS_FILE, NONE);
+
+        let data = ron::ser::to_string_pretty(&self.connections, PrettyConfig::new()).unwrap();
+        std::fs::write(profile_file(MAP_CONNECTIONS_FILE), data).expect("unable to write file");
+    }
+
+    /// Called on every map change. If the player left the previous map through
+    /// a warp, the connection is remembered.
+    pub fn enter_map(&mut self, map_name: &str, used_warp: Option<Vector2<usize>>) {
+        if let Some(previous_map) = self.current_map.take()
+            && let Some(warp_position) = used_warp
+            && previous_map != map_name
+        {
+            let connections = self.connections.entry(previous_map).or_default();
+
+            match connections.iter_mut().find(|connection| connection.warp_position == warp_position) {
+                Some(connection) => connection.destination = map_name.to_owned(),
+                None => connections.push(MapConnection {
+                    warp_position,
+                    destination: map_name.to_owned(),
+                }),
+            }
+        }
+
+        self.current_map = Some(map_name.to_owned());
+        self.requested.clear();
+    }
+
+    pub fn current_map(&self) -> Option<&str> {
+        self.current_map.as_deref()
+    }
+
+    /// Whether the map is the current one or can be reached from it through a
+    /// known warp, in which case it should stay loaded.
+    pub fn is_nearby(&self, map_name: &str) -> bool {
+        let Some(current_map) = &self.current_map else {
+            return false;
+        };
+
+        current_map == map_name
+            || self
+                .connections
+                .get(current_map)
+                .map_or(false, |connections| connections.iter().any(|connection| connection.destination == map_name))
+    }
+
+    /// Returns the name of a map that should be loaded now, if any.
+    pub fn update(&mut self, player_position: Vector2<usize>) -> Option<String> {
+        let connections = self.connections.get(self.current_map.as_ref()?)?;
+
+        let connection = connections.iter().find(|connection| {
+            let distance_x = connection.warp_position.x.abs_diff(player_position.x);
+            let distance_y = connection.warp_position.y.abs_diff(player_position.y);
+
+            distance_x.max(distance_y) <= PRELOAD_DISTANCE && !self.requested.contains(&connection.destination)
+        })?;
+
+        self.requested.insert(connection.destination.clone());
+        Some(connection.destination.clone())
+    }
+
+    /// Load a map on the thread pool. The loaders of the main thread can't be
+    /// shared, so the job uses its own model and texture loaders and uploads
+    /// the textures itself before handing the map back. The texture loader
+    /// starts out with the cache of the main one, and its cache has to be
+    /// merged back once the map is received.
+    pub fn preload(
+        &self,
+        thread_pool: &ThreadPool,
+        map_name: String,
+        game_file_loader: &GameFileLoader,
+        texture_loader: &TextureLoader,
+        memory_allocator: &Arc<MemoryAllocator>,
+        queue: &Arc<Queue>,
+    ) {
+        let mut game_file_loader = game_file_loader.clone();
+        let texture_cache = texture_loader.cache();
+        let memory_allocator = memory_allocator.clone();
+        let queue = queue.clone();
+        let loaded_sender = self.loaded_sender.clone();
+
+        thread_pool.spawn(move || {
+            let mut model_loader = ModelLoader::new(memory_allocator.clone());
+            let mut texture_loader = TextureLoader::with_cache(memory_allocator.clone(), queue, texture_cache);
+            let mut map_loader = MapLoader::new(memory_allocator);
+
+            let result = map_loader.get(map_name.clone(), &mut game_file_loader, &mut model_loader, &mut texture_loader);
+
+            if let Some(mut fence) = texture_loader.submit_load_buffer() {
+                fence.wait(None).unwrap();
+                fence.cleanup_finished();
+            }
+
+            // The receiver only goes away when the client shuts down.
+            let _ = loaded_sender.send((map_name, result, texture_loader.into_cache()));
+        });
+    }
+
+    /// Maps that finished loading on the thread pool since the last call.
+    pub fn loaded_maps(&self) -> impl Iterator<Item = PreloadedMap> + '_ {
+        self.loaded_receiver.try_iter()
+    }
+}
+
+impl Drop for MapPreloader {
+    fn drop(&mut self) {
+        self.save();
+    }
+}
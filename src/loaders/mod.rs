@@ -4,23 +4,27 @@ mod font;
 mod gamefile;
 mod map;
 mod model;
+mod monster;
 mod script;
 mod sprite;
 mod stream;
 mod texture;
 mod version;
+mod world_map;
 
 pub use self::action::*;
 pub use self::convertable::ByteConvertable;
 pub use self::font::FontLoader;
-pub use self::gamefile::GameFileLoader;
+pub use self::gamefile::{FileData, GameFileLoader};
 //pub use self::model::ModelLoader;
-pub use self::map::MapLoader;
+pub use self::map::{MapLoader, MapPreloader};
 //pub use self::sprite::SpriteLoader;
 //pub use self::action::ActionLoader;
 pub use self::model::*;
+pub use self::monster::{ElementType, MonsterDatabase, MonsterDrop, MonsterInfo, RaceType, SizeType};
 pub use self::script::ScriptLoader;
 pub use self::sprite::*;
 pub use self::stream::ByteStream;
-pub use self::texture::TextureLoader;
+pub use self::texture::{CompressionQuality, TextureCache, TextureCompression, TextureCompressionSettings, TextureLoader};
 pub use self::version::Version;
+pub use self::world_map::{WorldMapDatabase, WorldMapEntry};
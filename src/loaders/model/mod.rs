@@ -1,14 +1,14 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use cgmath::{Matrix3, Matrix4, Quaternion, Rad, SquareMatrix, Vector2, Vector3};
+use cgmath::{InnerSpace, Matrix3, Matrix4, Quaternion, Rad, SquareMatrix, Vector2, Vector3};
 use derive_new::new;
 use procedural::*;
-use vulkano::buffer::{BufferUsage, CpuAccessibleBuffer};
+use vulkano::buffer::{BufferAccess, BufferUsage, CpuAccessibleBuffer};
 
 #[cfg(feature = "debug")]
 use crate::debug::*;
-use crate::graphics::{MemoryAllocator, NativeModelVertex, Texture};
+use crate::graphics::{MemoryAllocator, MemoryCategory, NativeModelVertex, Texture};
 use crate::loaders::{ByteConvertable, ByteStream, GameFileLoader, TextureLoader, Version};
 use crate::system::multiply_matrix4_and_vector3;
 use crate::world::{BoundingBox, Model, Node};
@@ -141,35 +141,23 @@ impl ModelLoader {
         native_vertices: &mut Vec<NativeModelVertex>,
         vertex_positions: &[Vector3<f32>],
         texture_coordinates: &[Vector2<f32>],
+        normals: &[Vector3<f32>],
         texture_index: u16,
         reverse_vertices: bool,
-        reverse_normal: bool,
     ) {
-        let normal = match reverse_normal {
-            true => NativeModelVertex::calculate_normal(vertex_positions[0], vertex_positions[1], vertex_positions[2]),
-            false => NativeModelVertex::calculate_normal(vertex_positions[2], vertex_positions[1], vertex_positions[0]),
+        let vertex_order = match reverse_vertices {
+            true => [2, 1, 0],
+            false => [0, 1, 2],
         };
 
-        if reverse_vertices {
-            for (vertex_position, texture_coordinates) in vertex_positions.iter().copied().zip(texture_coordinates).rev() {
-                native_vertices.push(NativeModelVertex::new(
-                    vertex_position,
-                    normal,
-                    *texture_coordinates,
-                    texture_index as i32,
-                    0.0, // TODO: actually add wind affinity
-                ));
-            }
-        } else {
-            for (vertex_position, texture_coordinates) in vertex_positions.iter().copied().zip(texture_coordinates) {
-                native_vertices.push(NativeModelVertex::new(
-                    vertex_position,
-                    normal,
-                    *texture_coordinates,
-                    texture_index as i32,
-                    0.0, // TODO: actually add wind affinity
-                ));
-            }
+        for index in vertex_order {
+            native_vertices.push(NativeModelVertex::new(
+                vertex_positions[index],
+                normals[index],
+                texture_coordinates[index],
+                texture_index as i32,
+                0.0, // TODO: actually add wind affinity
+            ));
         }
     }
 
@@ -183,15 +171,45 @@ impl ModelLoader {
             panic!("this can actually happen");
         }
 
-        for face in &node.faces {
-            // collect into tiny vec instead ?
-            let vertex_positions: Vec<Vector3<f32>> = face
-                .vertex_position_indices
-                .iter()
-                .copied()
-                .map(|index| node.vertex_positions[index as usize])
-                .map(|position| multiply_matrix4_and_vector3(main_matrix, position))
-                .collect();
+        let face_positions: Vec<[Vector3<f32>; 3]> = node
+            .faces
+            .iter()
+            .map(|face| {
+                face.vertex_position_indices
+                    .map(|index| multiply_matrix4_and_vector3(main_matrix, node.vertex_positions[index as usize]))
+            })
+            .collect();
+
+        // faces that share a vertex and a smoothing group also share the normal at that
+        // vertex. the face normals are not normalized, so bigger faces have more weight
+        let mut smooth_normals: HashMap<(u16, i32), Vector3<f32>> = HashMap::new();
+
+        for (face, vertex_positions) in node.faces.iter().zip(&face_positions) {
+            let face_normal = NativeModelVertex::calculate_normal(vertex_positions[2], vertex_positions[1], vertex_positions[0]);
+
+            for index in face.vertex_position_indices {
+                *smooth_normals
+                    .entry((index, face.smooth_group))
+                    .or_insert_with(|| Vector3::new(0.0, 0.0, 0.0)) += face_normal;
+            }
+        }
+
+        for (face, vertex_positions) in node.faces.iter().zip(&face_positions) {
+            if NativeModelVertex::is_degenerate(vertex_positions[0], vertex_positions[1], vertex_positions[2]) {
+                continue;
+            }
+
+            let face_normal = NativeModelVertex::calculate_normal(vertex_positions[2], vertex_positions[1], vertex_positions[0]);
+
+            // opposing faces in the same smoothing group can cancel each other out
+            let normals = face.vertex_position_indices.map(|index| {
+                let smooth_normal = smooth_normals[&(index, face.smooth_group)];
+
+                match smooth_normal.magnitude2() > 0.0 {
+                    true => smooth_normal,
+                    false => face_normal,
+                }
+            });
 
             let texture_coordinates: Vec<Vector2<f32>> = face
                 .texture_coordinate_indices
@@ -202,21 +220,21 @@ impl ModelLoader {
 
             Self::add_vertices(
                 &mut native_vertices,
-                &vertex_positions,
+                vertex_positions,
                 &texture_coordinates,
+                &normals,
                 face.texture_index,
                 reverse_order,
-                false,
             );
 
             if face.two_sided != 0 {
                 Self::add_vertices(
                     &mut native_vertices,
-                    &vertex_positions,
+                    vertex_positions,
                     &texture_coordinates,
+                    &normals.map(|normal| -normal),
                     face.texture_index,
                     !reverse_order,
-                    true,
                 );
             }
         }
@@ -242,7 +260,7 @@ impl ModelLoader {
     }
 
     fn process_node_mesh(
-        memory_allocator: &MemoryAllocator,
+        memory_allocator: &Arc<MemoryAllocator>,
         current_node: &NodeData,
         nodes: &Vec<NodeData>,
         textures: &Vec<Texture>,
@@ -254,7 +272,7 @@ impl ModelLoader {
         let (main_matrix, transform_matrix, box_transform_matrix) = Self::calculate_matrices(current_node, parent_matrix);
         let vertices = NativeModelVertex::to_vertices(Self::make_vertices(current_node, &main_matrix, reverse_order));
         let vertex_buffer = CpuAccessibleBuffer::from_iter(
-            memory_allocator,
+            &**memory_allocator,
             BufferUsage {
                 vertex_buffer: true,
                 ..Default::default()
@@ -263,6 +281,7 @@ impl ModelLoader {
             vertices.into_iter(),
         )
         .unwrap();
+        let vertex_allocation = memory_allocator.track_allocation(MemoryCategory::MapGeometry, vertex_buffer.size());
 
         let box_matrix = box_transform_matrix * main_matrix;
         let bounding_box = BoundingBox::new(
@@ -312,6 +331,7 @@ impl ModelLoader {
         Node::new(
             final_matrix,
             vertex_buffer,
+            vertex_allocation,
             node_textures,
             child_nodes,
             current_node.rotation_keyframes.clone(),
@@ -0,0 +1,133 @@
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "debug")]
+use crate::debug::*;
+
+const MONSTER_DATABASE_FILE: &str = "client/monster_database.ron";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ElementType {
+    Neutral,
+    Water,
+    Earth,
+    Fire,
+    Wind,
+    Poison,
+    Holy,
+    Shadow,
+    Ghost,
+    Undead,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RaceType {
+    Formless,
+    Undead,
+    Brute,
+    Plant,
+    Insect,
+    Fish,
+    Demon,
+    DemiHuman,
+    Angel,
+    Dragon,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SizeType {
+    Small,
+    Medium,
+    Large,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MonsterDrop {
+    pub item_id: u32,
+    /// Chance in hundredths of a percent, so 10000 is a guaranteed drop.
+    pub rate: u32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MonsterInfo {
+    /// The job id that the server sends for this monster.
+    pub id: usize,
+    pub name: String,
+    pub level: usize,
+    pub health_points: usize,
+    pub base_experience: usize,
+    pub job_experience: usize,
+    pub minimum_attack: usize,
+    pub maximum_attack: usize,
+    pub defense: usize,
+    pub magic_defense: usize,
+    pub element: ElementType,
+    pub element_level: usize,
+    pub race: RaceType,
+    pub size: SizeType,
+    pub drops: Vec<MonsterDrop>,
+    pub spawn_maps: Vec<String>,
+}
+
+/// Static monster information that the server never sends to the client. The
+/// database is shipped with the client and never written back.
+#[derive(Default)]
+pub struct MonsterDatabase {
+    monsters: Vec<MonsterInfo>,
+}
+
+impl MonsterDatabase {
+    pub fn new() -> Self {
+        let monsters = Self::load().unwrap_or_else(|| {
+            #[cfg(feature = "debug")]
+            print_debug!(
+                "failed to load monster database from {}{}{}",
+                MAGENTA,
+                MONSTER_DATABASE_FILE,
+                NONE
+            );
+
+            Default::default()
+        });
+
+        Self { monsters }
+    }
+
+    fn load() -> Option<Vec<MonsterInfo>> {
+        #[cfg(feature = "debug")]
+        print_debug!("loading monster database from {}{}{}", MAGENTA, MONSTER_DATABASE_FILE, NONE);
+
+        std::fs::read_to_string(MONSTER_DATABASE_FILE)
+            .ok()
+            .and_then(|data| ron::from_str(&data).ok())
+    }
+
+    pub fn get(&self, id: usize) -> Option<&MonsterInfo> {
+        self.monsters.iter().find(|monster| monster.id == id)
+    }
+
+    /// All monsters that drop the given item, together with the drop rate.
+    pub fn dropped_by(&self, item_id: u32) -> Vec<(&MonsterInfo, u32)> {
+        self.monsters
+            .iter()
+            .flat_map(|monster| {
+                monster
+                    .drops
+                    .iter()
+                    .filter(move |drop| drop.item_id == item_id)
+                    .map(move |drop| (monster, drop.rate))
+            })
+            .collect()
+    }
+
+    /// Case insensitive search by name. Searching for a number also matches
+    /// the monster id.
+    pub fn search(&self, query: &str) -> Vec<&MonsterInfo> {
+        let query = query.trim().to_lowercase();
+        let id = query.parse::<usize>().ok();
+
+        self.monsters
+            .iter()
+            .filter(|monster| Some(monster.id) == id || monster.name.to_lowercase().contains(&query))
+            .collect()
+    }
+}
@@ -1,5 +1,7 @@
 use mlua::Lua;
 
+#[cfg(feature = "debug")]
+use crate::debug::*;
 use crate::loaders::GameFileLoader;
 use crate::network::ItemId;
 
@@ -49,6 +51,29 @@ end
 
         state.load(job_id_function).exec().unwrap();
 
+        // Only needed for equipment sprites, so the client still works if these are
+        // missing. The id tables have to be loaded before the name tables that use
+        // them.
+        let file_names = [
+            "jobname.lub",
+            "weapontable.lub",
+            "accessoryid.lub",
+            "accname.lub",
+            "spriterobeid.lub",
+            "spriterobename.lub",
+        ];
+
+        for file_name in file_names {
+            let data = game_file_loader.get(&format!("data\\luafiles514\\lua files\\datainfo\\{}", file_name));
+
+            if let Ok(data) = data && state.load(&*data).exec().is_ok() {
+                continue;
+            }
+
+            #[cfg(feature = "debug")]
+            print_debug!("failed to load {}{}{}", MAGENTA, file_name, NONE);
+        }
+
         Self { state }
     }
 
@@ -68,6 +93,39 @@ end
             .replace("CHONCHON", "chocho") // TODO: find a way to do this properly
     }
 
+    /// Korean name of a job, as it is used in the paths of weapon and shield
+    /// sprites.
+    pub fn get_job_sprite_name_from_id(&self, job_id: usize) -> Option<String> {
+        self.get_sprite_name("JobNameTable", job_id)
+    }
+
+    /// Name of a weapon view, including the leading underscore.
+    pub fn get_weapon_sprite_name_from_id(&self, weapon_id: usize) -> Option<String> {
+        self.get_sprite_name("WeaponNameTable", weapon_id)
+    }
+
+    /// Name of a headgear view, including the leading underscore.
+    pub fn get_headgear_sprite_name_from_id(&self, headgear_id: usize) -> Option<String> {
+        self.get_sprite_name("AccNameTable", headgear_id)
+    }
+
+    pub fn get_garment_sprite_name_from_id(&self, garment_id: usize) -> Option<String> {
+        self.get_sprite_name("RobeNameTable", garment_id)
+    }
+
+    fn get_sprite_name(&self, table_name: &str, index: usize) -> Option<String> {
+        use mlua::prelude::*;
+
+        let globals = self.state.globals();
+
+        // Sprite names are EUC-KR, but paths are stored byte for byte in the GRF.
+        globals
+            .get::<_, LuaTable>(table_name)
+            .and_then(|table| table.get::<_, LuaString>(index))
+            .ok()
+            .map(|name| name.as_bytes().iter().map(|&byte| byte as char).collect())
+    }
+
     // TODO: move this to a different class that utilizes the script loader
     pub fn get_item_name_from_id(&self, item_id: ItemId) -> String {
         use mlua::prelude::*;
@@ -103,4 +161,60 @@ end
             .unwrap()
             .to_owned()
     }
+
+    // TODO: move this to a different class that utilizes the script loader
+    pub fn get_item_slot_count(&self, item_id: ItemId) -> usize {
+        use mlua::prelude::*;
+
+        let globals = self.state.globals();
+
+        globals
+            .get::<_, LuaTable>("tbl")
+            .unwrap()
+            .get::<_, LuaTable>(item_id.0)
+            .and_then(|item| item.get::<_, usize>("slotCount"))
+            .unwrap_or_default()
+    }
+
+    // TODO: move this to a different class that utilizes the script loader
+    pub fn get_item_list(&self) -> Vec<(ItemId, String)> {
+        use mlua::prelude::*;
+
+        let globals = self.state.globals();
+
+        let mut items: Vec<(ItemId, String)> = globals
+            .get::<_, LuaTable>("tbl")
+            .unwrap()
+            .pairs::<u32, LuaTable>()
+            .filter_map(Result::ok)
+            .filter_map(|(item_id, item)| {
+                let name = item.get::<_, LuaString>("identifiedDisplayName").ok()?;
+                Some((ItemId(item_id), name.to_str().ok()?.to_owned()))
+            })
+            .collect();
+
+        items.sort_by_key(|(item_id, _)| item_id.0);
+        items
+    }
+
+    // TODO: move this to a different class that utilizes the script loader
+    pub fn get_item_description_from_id(&self, item_id: ItemId) -> Vec<String> {
+        use mlua::prelude::*;
+
+        let globals = self.state.globals();
+
+        globals
+            .get::<_, LuaTable>("tbl")
+            .unwrap()
+            .get::<_, LuaTable>(item_id.0)
+            .and_then(|item| item.get::<_, LuaTable>("identifiedDescriptionName"))
+            .map(|description| {
+                description
+                    .sequence_values::<LuaString>()
+                    .filter_map(Result::ok)
+                    .filter_map(|line| line.to_str().ok().map(str::to_owned))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
 }
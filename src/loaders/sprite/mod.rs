@@ -1,3 +1,5 @@
+mod preload;
+
 use std::collections::HashMap;
 use std::sync::Arc;
 
@@ -10,9 +12,10 @@ use vulkano::image::view::ImageView;
 use vulkano::image::{ImageDimensions, ImmutableImage, MipmapsCount};
 use vulkano::sync::{FenceSignalFuture, GpuFuture};
 
+pub use self::preload::SpritePreloader;
 #[cfg(feature = "debug")]
 use crate::debug::*;
-use crate::graphics::{MemoryAllocator, Texture};
+use crate::graphics::{MemoryAllocator, MemoryCategory, Texture, TrackedAllocation};
 use crate::interface::{ElementCell, PrototypeElement};
 use crate::loaders::{ByteConvertable, ByteStream, GameFileLoader, Version};
 
@@ -20,10 +23,26 @@ use crate::loaders::{ByteConvertable, ByteStream, GameFileLoader, Version};
 pub struct Sprite {
     #[hidden_element]
     pub textures: Vec<Texture>,
+    /// Only set for sprites that were loaded with palette indices, in which
+    /// case all palette images store the index in the red channel.
+    #[hidden_element]
+    palette: Option<Texture>,
+    #[hidden_element]
+    rgba_image_count: usize,
+    #[hidden_element]
+    _allocation: Arc<TrackedAllocation>,
     #[cfg(feature = "debug")]
     sprite_data: SpriteData,
 }
 
+impl Sprite {
+    /// Palette that has to be used to render the texture at the given index,
+    /// if the texture stores palette indices instead of colors.
+    pub fn texture_palette(&self, index: usize) -> Option<Texture> {
+        self.palette.clone().filter(|_| index >= self.rgba_image_count)
+    }
+}
+
 #[derive(Clone, Debug)]
 struct EncodedData(pub Vec<u8>);
 
@@ -102,12 +121,15 @@ struct PaletteColor {
 
 impl PaletteColor {
     pub fn color_bytes(&self, index: u8) -> [u8; 4] {
-        let alpha = match index {
-            0 => 0,
-            _ => 255,
-        };
+        [self.red, self.green, self.blue, index_alpha(index)]
+    }
+}
 
-        [self.red, self.green, self.blue, alpha]
+/// The first color of every palette is transparent.
+fn index_alpha(index: u8) -> u8 {
+    match index {
+        0 => 0,
+        _ => 255,
     }
 }
 
@@ -116,6 +138,23 @@ struct Palette {
     pub colors: [PaletteColor; 256],
 }
 
+impl From<Palette> for RgbaImageData {
+    fn from(palette: Palette) -> Self {
+        // The transparency comes from the index, so all colors are opaque here.
+        let data = palette
+            .colors
+            .iter()
+            .flat_map(|color| [color.red, color.green, color.blue, 255])
+            .collect();
+
+        Self {
+            width: 256,
+            height: 1,
+            data,
+        }
+    }
+}
+
 #[derive(Clone, Debug, ByteConvertable, PrototypeElement)]
 struct SpriteData {
     #[version]
@@ -139,10 +178,50 @@ pub struct SpriteLoader {
     load_buffer: Option<AutoCommandBufferBuilder<PrimaryAutoCommandBuffer, MemoryAllocator>>,
     #[new(default)]
     cache: HashMap<String, Arc<Sprite>>,
+    #[new(default)]
+    indexed_cache: HashMap<String, Arc<Sprite>>,
+    #[new(default)]
+    palette_cache: HashMap<String, (Texture, TrackedAllocation)>,
+}
+
+fn create_texture(
+    memory_allocator: &MemoryAllocator,
+    load_buffer: &mut AutoCommandBufferBuilder<PrimaryAutoCommandBuffer, MemoryAllocator>,
+    image_data: RgbaImageData,
+    format: Format,
+) -> Texture {
+    let image = ImmutableImage::from_iter(
+        memory_allocator,
+        image_data.data.into_iter(),
+        ImageDimensions::Dim2d {
+            width: image_data.width as u32,
+            height: image_data.height as u32,
+            array_layers: 1,
+        },
+        MipmapsCount::One,
+        format,
+        load_buffer,
+    )
+    .unwrap();
+
+    ImageView::new_default(Arc::new(image)).unwrap()
 }
 
 impl SpriteLoader {
-    fn load(&mut self, path: &str, game_file_loader: &mut GameFileLoader) -> Result<Arc<Sprite>, String> {
+    fn load_buffer(&mut self) -> &mut AutoCommandBufferBuilder<PrimaryAutoCommandBuffer, MemoryAllocator> {
+        self.load_buffer.get_or_insert_with(|| {
+            AutoCommandBufferBuilder::primary(
+                &*self.memory_allocator,
+                self.queue.queue_family_index(),
+                CommandBufferUsage::OneTimeSubmit,
+            )
+            .unwrap()
+        })
+    }
+
+    /// With `indexed` set, palette images keep their palette indices, so they
+    /// can be rendered with a different palette.
+    fn load(&mut self, path: &str, game_file_loader: &mut GameFileLoader, indexed: bool) -> Result<Arc<Sprite>, String> {
         #[cfg(feature = "debug")]
         let timer = Timer::new_dynamic(format!("load sprite from {}{}{}", MAGENTA, path, NONE));
 
@@ -162,6 +241,7 @@ impl SpriteLoader {
         let palette = sprite_data.palette.unwrap(); // unwrap_or_default() as soon as i know what
         // the default palette is
 
+        let rgba_image_count = sprite_data.rgba_image_data.len();
         let rgba_images/*: Vec<Arc<ImmutableImage>>*/ = sprite_data
             .rgba_image_data
             .into_iter();
@@ -173,7 +253,10 @@ impl SpriteLoader {
                 .map(|encoded| encoded.0)
                 .unwrap_or_else(|| image_data.raw_data.unwrap())
                 .iter()
-                .flat_map(|palette_index| palette.colors[*palette_index as usize].color_bytes(*palette_index))
+                .flat_map(|palette_index| match indexed {
+                    true => [*palette_index, 0, 0, index_alpha(*palette_index)],
+                    false => palette.colors[*palette_index as usize].color_bytes(*palette_index),
+                })
                 .collect();
 
             RgbaImageData {
@@ -183,43 +266,44 @@ impl SpriteLoader {
             }
         });
 
-        let load_buffer = self.load_buffer.get_or_insert_with(|| {
-            AutoCommandBufferBuilder::primary(
-                &*self.memory_allocator,
-                self.queue.queue_family_index(),
-                CommandBufferUsage::OneTimeSubmit,
-            )
-            .unwrap()
-        });
+        // Palette indices must not be converted from sRGB.
+        let palette_format = match indexed {
+            true => Format::R8G8B8A8_UNORM,
+            false => Format::R8G8B8A8_SRGB,
+        };
 
+        let memory_allocator = self.memory_allocator.clone();
+        let load_buffer = self.load_buffer();
+
+        let mut size = 0;
         let textures = rgba_images
-            .chain(palette_images)
-            .map(|image_data| {
-                let image = ImmutableImage::from_iter(
-                    &*self.memory_allocator,
-                    image_data.data.iter().cloned(),
-                    ImageDimensions::Dim2d {
-                        width: image_data.width as u32,
-                        height: image_data.height as u32,
-                        array_layers: 1,
-                    },
-                    MipmapsCount::One,
-                    Format::R8G8B8A8_SRGB,
-                    load_buffer,
-                )
-                .unwrap();
-
-                ImageView::new_default(Arc::new(image)).unwrap()
+            .map(|image_data| (image_data, Format::R8G8B8A8_SRGB))
+            .chain(palette_images.map(|image_data| (image_data, palette_format)))
+            .map(|(image_data, format)| {
+                size += image_data.data.len() as u64;
+                create_texture(&memory_allocator, load_buffer, image_data, format)
             })
             .collect();
 
+        let palette = indexed.then(|| {
+            let palette_data: RgbaImageData = palette.into();
+            size += palette_data.data.len() as u64;
+            create_texture(&memory_allocator, load_buffer, palette_data, Format::R8G8B8A8_SRGB)
+        });
+
         let sprite = Arc::new(Sprite {
             textures,
+            palette,
+            rgba_image_count,
+            _allocation: Arc::new(memory_allocator.track_allocation(MemoryCategory::Sprites, size)),
             #[cfg(feature = "debug")]
             sprite_data: cloned_sprite_data,
         });
 
-        self.cache.insert(path.to_string(), sprite.clone());
+        match indexed {
+            true => self.indexed_cache.insert(path.to_string(), sprite.clone()),
+            false => self.cache.insert(path.to_string(), sprite.clone()),
+        };
 
         #[cfg(feature = "debug")]
         timer.stop();
@@ -230,7 +314,52 @@ impl SpriteLoader {
     pub fn get(&mut self, path: &str, game_file_loader: &mut GameFileLoader) -> Result<Arc<Sprite>, String> {
         match self.cache.get(path) {
             Some(sprite) => Ok(sprite.clone()),
-            None => self.load(path, game_file_loader),
+            None => self.load(path, game_file_loader, false),
+        }
+    }
+
+    /// Same as [`get`](Self::get), but the sprite can be rendered with any
+    /// palette.
+    pub fn get_indexed(&mut self, path: &str, game_file_loader: &mut GameFileLoader) -> Result<Arc<Sprite>, String> {
+        match self.indexed_cache.get(path) {
+            Some(sprite) => Ok(sprite.clone()),
+            None => self.load(path, game_file_loader, true),
+        }
+    }
+
+    fn load_palette(&mut self, path: &str, game_file_loader: &mut GameFileLoader) -> Result<Texture, String> {
+        #[cfg(feature = "debug")]
+        let timer = Timer::new_dynamic(format!("load palette from {}{}{}", MAGENTA, path, NONE));
+
+        let bytes = game_file_loader.get(&format!("data\\palette\\{}", path))?;
+
+        if bytes.len() < 1024 {
+            return Err(format!("palette {} is too short", path));
+        }
+
+        let mut byte_stream = ByteStream::new(&bytes);
+        let palette = Palette::from_bytes(&mut byte_stream, None);
+
+        let memory_allocator = self.memory_allocator.clone();
+        let palette_data: RgbaImageData = palette.into();
+        let allocation = memory_allocator.track_allocation(MemoryCategory::Sprites, palette_data.data.len() as u64);
+        let texture = create_texture(&memory_allocator, self.load_buffer(), palette_data, Format::R8G8B8A8_SRGB);
+
+        self.palette_cache.insert(path.to_string(), (texture.clone(), allocation));
+
+        #[cfg(feature = "debug")]
+        timer.stop();
+
+        Ok(texture)
+    }
+
+    /// A palette file as a texture with a width of 256 and a height of 1, used
+    /// to render sprites that were loaded with
+    /// [`get_indexed`](Self::get_indexed).
+    pub fn get_palette(&mut self, path: &str, game_file_loader: &mut GameFileLoader) -> Result<Texture, String> {
+        match self.palette_cache.get(path) {
+            Some((texture, _)) => Ok(texture.clone()),
+            None => self.load_palette(path, game_file_loader),
         }
     }
 
@@ -0,0 +1,99 @@
+use std::collections::{HashMap, HashSet};
+
+use ron::ser::PrettyConfig;
+
+#[cfg(feature = "debug")]
+use crate::debug::*;
+use crate::loaders::{ActionLoader, GameFileLoader, ScriptLoader, SpriteLoader};
+use crate::system::profile_file;
+use crate::world::EntityType;
+
+const SPAWN_TABLE_FILE: &str = "spawn_table.ron";
+
+/// Remembers which entities appeared on each map, so their sprites and actions
+/// can be loaded together with the map instead of the first time they come
+/// into view.
+pub struct SpritePreloader {
+    spawn_table: HashMap<String, HashSet<usize>>,
+    current_map: Option<String>,
+}
+
+impl SpritePreloader {
+    pub fn new() -> Self {
+        let spawn_table = Self::load().unwrap_or_else(|| {
+            #[cfg(feature = "debug")]
+            print_debug!("failed to load spawn table from {}{}{}", MAGENTA, SPAWN_TABLE_FILE, NONE);
+
+            Default::default()
+        });
+
+        Self {
+            spawn_table,
+            current_map: None,
+        }
+    }
+
+    fn load() -> Option<HashMap<String, HashSet<usize>>> {
+        #[cfg(feature = "debug")]
+        print_debug!("loading spawn table from {}{}{}", MAGENTA, SPAWN_TABLE_FILE, NONE);
+
+        std::fs::read_to_string(profile_file(SPAWN_TABLE_FILE))
+            .ok()
+            .and_then(|data| ron::from_str(&data).ok())
+    }
+
+    pub fn save(&self) {
+        #[cfg(feature = "debug")]
+        print_debug!("saving spawn table to {}{}{}", MAGENTA, SPAWN_TABLE_FILE, NONE);
+
+        let data = ron::ser::to_string_pretty(&self.spawn_table, PrettyConfig::new()).unwrap();
+        std::fs::write(profile_file(SPAWN_TABLE_FILE), data).expect("unable to write file");
+    }
+
+    pub fn enter_map(
+        &mut self,
+        map_name: &str,
+        game_file_loader: &mut GameFileLoader,
+        sprite_loader: &mut SpriteLoader,
+        action_loader: &mut ActionLoader,
+        script_loader: &ScriptLoader,
+    ) {
+        self.current_map = Some(map_name.to_owned());
+
+        let Some(job_ids) = self.spawn_table.get(map_name) else {
+            return;
+        };
+
+        #[cfg(feature = "debug")]
+        let timer = Timer::new_dynamic(format!("preload {} sprites for {}", job_ids.len(), map_name));
+
+        for job_id in job_ids.iter().copied() {
+            let file_path = EntityType::from_job_id(job_id).sprite_file_path(script_loader, job_id);
+
+            // errors will show up once the entity actually appears, so they are not
+            // reported here
+            let _ = sprite_loader.get(&format!("{}.spr", file_path), game_file_loader);
+            let _ = action_loader.get(&format!("{}.act", file_path), game_file_loader);
+        }
+
+        #[cfg(feature = "debug")]
+        timer.stop();
+    }
+
+    pub fn register_spawn(&mut self, job_id: usize) {
+        // player sprites depend on the character and not the map
+        if EntityType::from_job_id(job_id) == EntityType::Player {
+            return;
+        }
+
+        if let Some(map_name) = &self.current_map {
+            self.spawn_table.entry(map_name.clone()).or_default().insert(job_id);
+        }
+    }
+}
+
+impl Drop for SpritePreloader {
+    fn drop(&mut self) {
+        self.save();
+    }
+}
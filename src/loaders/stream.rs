@@ -1,3 +1,6 @@
+#[cfg(feature = "debug_network")]
+use std::time::Duration;
+
 use cgmath::Vector3;
 use derive_new::new;
 
@@ -10,7 +13,7 @@ use crate::interface::PacketEntry;
 use crate::interface::TrackedState;
 use crate::loaders::Version;
 #[cfg(feature = "debug_network")]
-use crate::network::Packet;
+use crate::network::{NetworkStatisticsRecorder, Packet, PacketDirection, PacketRecord};
 
 #[derive(new)]
 pub struct ByteStream<'b> {
@@ -22,6 +25,9 @@ pub struct ByteStream<'b> {
     #[cfg(feature = "debug_network")]
     #[new(default)]
     packet_history: Vec<PacketEntry>,
+    #[cfg(feature = "debug_network")]
+    #[new(default)]
+    packet_records: Vec<PacketRecord>,
 }
 
 impl<'b> ByteStream<'b> {
@@ -164,19 +170,56 @@ impl<'b> ByteStream<'b> {
         self.offset += count;
     }
 
+    pub fn get_offset(&self) -> usize {
+        self.offset
+    }
+
     #[cfg(feature = "debug_network")]
-    pub fn incoming_packet(&mut self, packet: &(impl Packet + 'static), name: &'static str, is_ping: bool) {
-        self.packet_history.push(PacketEntry::new_incoming(packet, name, is_ping));
+    pub fn incoming_packet<P: Packet + 'static>(&mut self, packet: &P, size: usize, deserialization_time: Duration) {
+        let bytes = self.data[self.offset - size..self.offset].to_vec();
+
+        self.packet_history.push(PacketEntry::new_incoming(
+            packet,
+            P::header(),
+            P::PACKET_NAME,
+            bytes,
+            P::IS_PING,
+        ));
+        self.packet_records.push(PacketRecord {
+            header: P::header(),
+            name: P::PACKET_NAME,
+            direction: PacketDirection::Incoming,
+            size,
+            serialization_time: deserialization_time,
+        });
     }
 
     #[cfg(feature = "debug_network")]
     pub fn incoming_unknown_packet(&mut self, bytes: Vec<u8>) {
-        self.packet_history.push(PacketEntry::new_incoming(&bytes, "UNKNOWN", false));
+        let header = match bytes.len() >= 2 {
+            true => [bytes[0], bytes[1]],
+            false => [0, 0],
+        };
+
+        self.packet_records.push(PacketRecord {
+            header,
+            name: "UNKNOWN",
+            direction: PacketDirection::Incoming,
+            size: bytes.len(),
+            serialization_time: Duration::ZERO,
+        });
+        self.packet_history
+            .push(PacketEntry::new_incoming(&bytes, header, "UNKNOWN", bytes.clone(), false));
     }
 
     #[cfg(feature = "debug_network")]
-    pub fn transfer_packet_history(&mut self, packet_history: &mut TrackedState<Vec<PacketEntry>>) {
+    pub fn transfer_packet_history(
+        &mut self,
+        packet_history: &mut TrackedState<Vec<PacketEntry>>,
+        statistics_recorder: &mut NetworkStatisticsRecorder,
+    ) {
         packet_history.append(&mut self.packet_history);
+        self.packet_records.drain(..).for_each(|record| statistics_recorder.record(record));
     }
 
     #[cfg(feature = "debug")]
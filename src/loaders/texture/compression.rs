@@ -0,0 +1,218 @@
+use image::imageops::FilterType;
+use image::RgbaImage;
+use intel_tex_2::{bc1, bc3, bc7, RgbaSurface};
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+use vulkano::device::physical::PhysicalDevice;
+use vulkano::format::Format;
+
+#[cfg(feature = "debug")]
+use crate::debug::*;
+use crate::system::profile_file;
+
+const CACHE_DIRECTORY: &str = "client/texture_cache";
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TextureCompression {
+    #[default]
+    Uncompressed,
+    Bc1,
+    Bc3,
+    Bc7,
+}
+
+impl TextureCompression {
+    pub fn format(self) -> Format {
+        match self {
+            TextureCompression::Uncompressed => Format::R8G8B8A8_SRGB,
+            TextureCompression::Bc1 => Format::BC1_RGBA_SRGB_BLOCK,
+            TextureCompression::Bc3 => Format::BC3_SRGB_BLOCK,
+            TextureCompression::Bc7 => Format::BC7_SRGB_BLOCK,
+        }
+    }
+
+    pub fn is_supported(self, physical_device: &PhysicalDevice) -> bool {
+        physical_device
+            .format_properties(self.format())
+            .optimal_tiling_features
+            .sampled_image
+    }
+
+    /// Size of a block of 4x4 pixels in bytes.
+    pub fn block_size(self) -> u32 {
+        match self {
+            TextureCompression::Uncompressed => 64,
+            TextureCompression::Bc1 => 8,
+            TextureCompression::Bc3 | TextureCompression::Bc7 => 16,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            TextureCompression::Uncompressed => "uncompressed",
+            TextureCompression::Bc1 => "bc1",
+            TextureCompression::Bc3 => "bc3",
+            TextureCompression::Bc7 => "bc7",
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CompressionQuality {
+    Fast,
+    #[default]
+    Balanced,
+    Best,
+}
+
+impl CompressionQuality {
+    fn name(self) -> &'static str {
+        match self {
+            CompressionQuality::Fast => "fast",
+            CompressionQuality::Balanced => "balanced",
+            CompressionQuality::Best => "best",
+        }
+    }
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct TextureCompressionSettings {
+    pub compression: TextureCompression,
+    pub quality: CompressionQuality,
+}
+
+impl TextureCompressionSettings {
+    pub fn new() -> Self {
+        Self::load().unwrap_or_else(|| {
+            #[cfg(feature = "debug")]
+            print_debug!("failed to load texture compression settings from {}filename{}", MAGENTA, NONE);
+
+            Default::default()
+        })
+    }
+
+    pub fn load() -> Option<Self> {
+        #[cfg(feature = "debug")]
+        print_debug!("loading texture compression settings from {}filename{}", MAGENTA, NONE);
+
+        std::fs::read_to_string(profile_file("texture_compression_settings.ron"))
+            .ok()
+            .and_then(|data| ron::from_str(&data).ok())
+    }
+
+    pub fn save(&self) {
+        #[cfg(feature = "debug")]
+        print_debug!("saving texture compression settings to {}filename{}", MAGENTA, NONE);
+
+        let data = ron::ser::to_string_pretty(self, PrettyConfig::new()).unwrap();
+        std::fs::write(profile_file("texture_compression_settings.ron"), data).expect("unable to write file");
+    }
+}
+
+impl Drop for TextureCompressionSettings {
+    fn drop(&mut self) {
+        self.save();
+    }
+}
+
+pub fn can_compress(image_buffer: &RgbaImage) -> bool {
+    image_buffer.width() % 4 == 0 && image_buffer.height() % 4 == 0
+}
+
+pub fn has_transparency(image_buffer: &RgbaImage) -> bool {
+    image_buffer.pixels().any(|pixel| pixel.0[3] != 255)
+}
+
+/// Mip levels of the image, down to the last one that can still be block
+/// compressed. The first level is the image itself.
+pub fn mip_chain(image_buffer: RgbaImage) -> Vec<RgbaImage> {
+    let (width, height) = image_buffer.dimensions();
+    let mut mip_chain = vec![image_buffer];
+
+    for level in 1.. {
+        let (level_width, level_height) = (width >> level, height >> level);
+
+        if level_width < 4 || level_height < 4 || level_width % 4 != 0 || level_height % 4 != 0 {
+            break;
+        }
+
+        let mip = image::imageops::resize(&mip_chain[0], level_width, level_height, FilterType::Triangle);
+        mip_chain.push(mip);
+    }
+
+    mip_chain
+}
+
+/// The cache is keyed on the size and the content of the image as well, so
+/// that a texture which changed in the game files is not served stale.
+fn cache_path(path: &str, image_buffer: &RgbaImage, compression: TextureCompression, quality: CompressionQuality) -> String {
+    let file_name = path.replace(['\\', '/'], "_");
+    let content_hash = md5::compute(image_buffer.as_raw());
+
+    format!(
+        "{}/{}_{}/{}_{}x{}_{:x}.bin",
+        CACHE_DIRECTORY,
+        compression.name(),
+        quality.name(),
+        file_name,
+        image_buffer.width(),
+        image_buffer.height(),
+        content_hash
+    )
+}
+
+fn encode(image_buffer: &RgbaImage, compression: TextureCompression, quality: CompressionQuality) -> Vec<u8> {
+    let surface = RgbaSurface {
+        data: image_buffer.as_raw(),
+        width: image_buffer.width(),
+        height: image_buffer.height(),
+        stride: image_buffer.width() * 4,
+    };
+
+    match compression {
+        TextureCompression::Uncompressed => image_buffer.as_raw().clone(),
+        TextureCompression::Bc1 => bc1::compress_blocks(&surface),
+        TextureCompression::Bc3 => bc3::compress_blocks(&surface),
+        TextureCompression::Bc7 => {
+            let settings = match (quality, has_transparency(image_buffer)) {
+                (CompressionQuality::Fast, false) => bc7::opaque_ultra_fast_settings(),
+                (CompressionQuality::Fast, true) => bc7::alpha_ultra_fast_settings(),
+                (CompressionQuality::Balanced, false) => bc7::opaque_fast_settings(),
+                (CompressionQuality::Balanced, true) => bc7::alpha_fast_settings(),
+                (CompressionQuality::Best, false) => bc7::opaque_slow_settings(),
+                (CompressionQuality::Best, true) => bc7::alpha_slow_settings(),
+            };
+
+            bc7::compress_blocks(&settings, &surface)
+        }
+    }
+}
+
+/// Compressed textures are cached on disk, since encoding BC7 at a high quality
+/// is too slow to do every time a map is loaded.
+pub fn compress(path: &str, image_buffer: &RgbaImage, compression: TextureCompression, quality: CompressionQuality) -> Vec<u8> {
+    let cache_path = cache_path(path, image_buffer, compression, quality);
+
+    if let Ok(data) = std::fs::read(&cache_path) {
+        return data;
+    }
+
+    #[cfg(feature = "debug")]
+    let timer = Timer::new_dynamic(format!("compress texture {}{}{}", MAGENTA, path, NONE));
+
+    let data = encode(image_buffer, compression, quality);
+
+    if let Some(directory) = std::path::Path::new(&cache_path).parent() {
+        let _ = std::fs::create_dir_all(directory);
+    }
+
+    if std::fs::write(&cache_path, &data).is_err() {
+        #[cfg(feature = "debug")]
+        print_debug!("failed to write compressed texture to {}{}{}", MAGENTA, cache_path, NONE);
+    }
+
+    #[cfg(feature = "debug")]
+    timer.stop();
+
+    data
+}
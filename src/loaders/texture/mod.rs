@@ -1,37 +1,150 @@
+mod compression;
+
 use std::collections::HashMap;
 use std::io::Cursor;
 use std::sync::Arc;
 
-use derive_new::new;
+use image::imageops::FilterType;
 use image::io::Reader as ImageReader;
-use image::{EncodableLayout, ImageFormat, Rgba};
-use vulkano::command_buffer::{AutoCommandBufferBuilder, CommandBufferUsage, PrimaryAutoCommandBuffer, PrimaryCommandBufferAbstract};
-use vulkano::device::Queue;
-use vulkano::format::Format;
-use vulkano::image::view::ImageView;
-use vulkano::image::{ImageDimensions, ImmutableImage, MipmapsCount};
+use image::{ImageFormat, Rgba, RgbaImage};
+use vulkano::buffer::{BufferUsage, CpuAccessibleBuffer};
+use vulkano::command_buffer::{
+    AutoCommandBufferBuilder, BufferImageCopy, CommandBufferUsage, CopyBufferToImageInfo, PrimaryAutoCommandBuffer,
+    PrimaryCommandBufferAbstract,
+};
+use vulkano::device::{DeviceOwned, Queue};
+use vulkano::image::view::{ImageView, ImageViewCreateInfo, ImageViewType};
+use vulkano::image::{
+    ImageAccess, ImageCreateFlags, ImageDimensions, ImageLayout, ImageSubresourceLayers, ImageUsage, ImmutableImage, MipmapsCount,
+};
 use vulkano::sync::{FenceSignalFuture, GpuFuture};
 
+pub use self::compression::{CompressionQuality, TextureCompression, TextureCompressionSettings};
 #[cfg(feature = "debug")]
 use crate::debug::*;
-use crate::graphics::{MemoryAllocator, Texture};
+use crate::graphics::{MemoryAllocator, MemoryCategory, Texture, TrackedAllocation};
 use crate::loaders::GameFileLoader;
 
-#[derive(new)]
+/// Textures uploaded by a loader, together with the compression they were
+/// uploaded with. A loader on the thread pool starts out with a copy of the
+/// main cache, so it doesn't upload textures that are already on the GPU, and
+/// hands its cache back to be merged once it is done.
+#[derive(Clone)]
+pub struct TextureCache {
+    compression: TextureCompression,
+    quality: CompressionQuality,
+    textures: HashMap<String, (Texture, Arc<TrackedAllocation>)>,
+    arrays: HashMap<Vec<String>, (Texture, Arc<TrackedAllocation>)>,
+}
+
 pub struct TextureLoader {
     memory_allocator: Arc<MemoryAllocator>,
     queue: Arc<Queue>,
-    #[new(default)]
     load_buffer: Option<AutoCommandBufferBuilder<PrimaryAutoCommandBuffer, MemoryAllocator>>,
-    #[new(value = "HashMap::new()")]
-    cache: HashMap<String, Texture>,
+    cache: TextureCache,
 }
 
 impl TextureLoader {
-    fn load(&mut self, path: &str, game_file_loader: &mut GameFileLoader) -> Result<Texture, String> {
-        #[cfg(feature = "debug")]
-        let timer = Timer::new_dynamic(format!("load texture from {}{}{}", MAGENTA, path, NONE));
+    pub fn new(memory_allocator: Arc<MemoryAllocator>, queue: Arc<Queue>, compression_settings: &TextureCompressionSettings) -> Self {
+        let mut compression = compression_settings.compression;
+
+        // BCn formats can only be sampled if the device supports and enables them, so
+        // fall back to uncompressed textures otherwise.
+        if compression != TextureCompression::Uncompressed
+            && (!queue.device().enabled_features().texture_compression_bc || !compression.is_supported(queue.device().physical_device()))
+        {
+            #[cfg(feature = "debug")]
+            print_debug!(
+                "{}{:?}{} is not supported by the device, falling back to uncompressed textures",
+                MAGENTA,
+                compression,
+                NONE
+            );
+
+            compression = TextureCompression::Uncompressed;
+        }
+
+        let cache = TextureCache {
+            compression,
+            quality: compression_settings.quality,
+            textures: HashMap::new(),
+            arrays: HashMap::new(),
+        };
+
+        Self::with_cache(memory_allocator, queue, cache)
+    }
+
+    /// Creates a loader that continues from the cache of another one.
+    pub fn with_cache(memory_allocator: Arc<MemoryAllocator>, queue: Arc<Queue>, cache: TextureCache) -> Self {
+        Self {
+            memory_allocator,
+            queue,
+            load_buffer: None,
+            cache,
+        }
+    }
+
+    pub fn cache(&self) -> TextureCache {
+        self.cache.clone()
+    }
+
+    pub fn into_cache(self) -> TextureCache {
+        self.cache
+    }
+
+    /// Takes over the textures that another loader uploaded. Textures that are
+    /// cached here already are kept, the other copy is freed once nothing
+    /// references it anymore.
+    pub fn merge_cache(&mut self, cache: TextureCache) {
+        for (path, entry) in cache.textures {
+            self.cache.textures.entry(path).or_insert(entry);
+        }
+
+        for (paths, entry) in cache.arrays {
+            self.cache.arrays.entry(paths).or_insert(entry);
+        }
+    }
 
+    fn texture_compression(&self, image_buffers: &[RgbaImage]) -> TextureCompression {
+        if !image_buffers.iter().all(compression::can_compress) {
+            return TextureCompression::Uncompressed;
+        }
+
+        // BC1 only has one bit of alpha, which is not enough for most textures that
+        // have any transparency.
+        match self.cache.compression {
+            TextureCompression::Bc1 if image_buffers.iter().any(compression::has_transparency) => TextureCompression::Bc3,
+            compression => compression,
+        }
+    }
+
+    /// Returns the data of all layers and the number of mip levels in it.
+    /// Compressed data is ordered by mip level first and layer second, so
+    /// that each level can be copied to all layers at once.
+    fn encode_layers(&self, paths: &[String], image_buffers: Vec<RgbaImage>) -> (Vec<u8>, TextureCompression, u32) {
+        let compression = self.texture_compression(&image_buffers);
+
+        if compression == TextureCompression::Uncompressed {
+            let image_data = image_buffers.into_iter().flat_map(RgbaImage::into_raw).collect();
+            return (image_data, compression, 1);
+        }
+
+        let mip_chains: Vec<Vec<RgbaImage>> = image_buffers.into_iter().map(compression::mip_chain).collect();
+        let mip_levels = mip_chains[0].len();
+
+        let image_data = (0..mip_levels)
+            .flat_map(|mip_level| {
+                paths
+                    .iter()
+                    .zip(mip_chains.iter())
+                    .flat_map(move |(path, mip_chain)| compression::compress(path, &mip_chain[mip_level], compression, self.cache.quality))
+            })
+            .collect();
+
+        (image_data, compression, mip_levels as u32)
+    }
+
+    fn load_image_buffer(path: &str, game_file_loader: &mut GameFileLoader) -> Result<RgbaImage, String> {
         let image_format = match &path[path.len() - 4..] {
             ".png" => ImageFormat::Png,
             ".bmp" | ".BMP" => ImageFormat::Bmp,
@@ -54,13 +167,16 @@ impl TextureLoader {
                 .for_each(|pixel| *pixel = Rgba([0; 4]));
         }
 
-        let image_data = image_buffer.as_bytes().to_vec();
-        let dimensions = ImageDimensions::Dim2d {
-            width: image_buffer.width(),
-            height: image_buffer.height(),
-            array_layers: 1,
-        };
+        Ok(image_buffer)
+    }
 
+    fn create_image(
+        &mut self,
+        image_data: Vec<u8>,
+        dimensions: ImageDimensions,
+        compression: TextureCompression,
+        mip_levels: u32,
+    ) -> (Arc<ImmutableImage>, TrackedAllocation) {
         let load_buffer = self.load_buffer.get_or_insert_with(|| {
             AutoCommandBufferBuilder::primary(
                 &*self.memory_allocator,
@@ -70,18 +186,167 @@ impl TextureLoader {
             .unwrap()
         });
 
-        let image = ImmutableImage::from_iter(
-            &*self.memory_allocator,
-            image_data.iter().cloned(),
-            dimensions,
-            MipmapsCount::Log2,
-            Format::R8G8B8A8_SRGB,
-            load_buffer,
-        )
-        .unwrap();
+        // Mipmaps are generated by blitting, which is not possible for block compressed
+        // formats. Their mip levels are encoded up front instead and copied level by
+        // level. A full mip chain adds roughly a third to the size of the image.
+        let (image, size) = match compression {
+            TextureCompression::Uncompressed => {
+                let size = image_data.len() as u64 * 4 / 3;
+                let image = ImmutableImage::from_iter(
+                    &*self.memory_allocator,
+                    image_data.into_iter(),
+                    dimensions,
+                    MipmapsCount::Log2,
+                    compression.format(),
+                    load_buffer,
+                )
+                .unwrap();
+
+                (image, size)
+            }
+            compression => {
+                let size = image_data.len() as u64;
+                let usage = ImageUsage {
+                    transfer_dst: true,
+                    sampled: true,
+                    ..ImageUsage::empty()
+                };
+
+                let (image, initialization) = ImmutableImage::uninitialized(
+                    &*self.memory_allocator,
+                    dimensions,
+                    compression.format(),
+                    MipmapsCount::Specific(mip_levels),
+                    usage,
+                    ImageCreateFlags::empty(),
+                    ImageLayout::ShaderReadOnlyOptimal,
+                    self.queue.device().active_queue_family_indices().iter().copied(),
+                )
+                .unwrap();
+
+                let mut buffer_offset = 0;
+                let regions = (0..mip_levels)
+                    .map(|mip_level| {
+                        let width = dimensions.width() >> mip_level;
+                        let height = dimensions.height() >> mip_level;
+                        let region = BufferImageCopy {
+                            buffer_offset,
+                            image_subresource: ImageSubresourceLayers {
+                                mip_level,
+                                ..initialization.subresource_layers()
+                            },
+                            image_extent: [width, height, 1],
+                            ..Default::default()
+                        };
+
+                        buffer_offset += ((width / 4) * (height / 4) * compression.block_size() * dimensions.array_layers()) as u64;
+                        region
+                    })
+                    .collect();
+
+                let buffer_usage = BufferUsage {
+                    transfer_src: true,
+                    ..BufferUsage::empty()
+                };
+                let buffer = CpuAccessibleBuffer::from_iter(&*self.memory_allocator, buffer_usage, false, image_data).unwrap();
+
+                load_buffer
+                    .copy_buffer_to_image(CopyBufferToImageInfo {
+                        regions,
+                        ..CopyBufferToImageInfo::buffer_image(buffer, initialization)
+                    })
+                    .unwrap();
 
+                (image, size)
+            }
+        };
+
+        let allocation = self.memory_allocator.track_allocation(MemoryCategory::Textures, size);
+
+        (image, allocation)
+    }
+
+    /// Drops all cached textures that are not used anywhere else, freeing their
+    /// memory once the last command buffer referencing them has finished.
+    fn evict_unused(&mut self) {
+        self.cache.textures.retain(|_, (texture, _)| Arc::strong_count(texture) > 1);
+        self.cache.arrays.retain(|_, (texture, _)| Arc::strong_count(texture) > 1);
+
+        #[cfg(feature = "debug")]
+        print_debug!("evicted unused textures from the {}texture cache{}", MAGENTA, NONE);
+    }
+
+    fn load(&mut self, path: &str, game_file_loader: &mut GameFileLoader) -> Result<Texture, String> {
+        #[cfg(feature = "debug")]
+        let timer = Timer::new_dynamic(format!("load texture from {}{}{}", MAGENTA, path, NONE));
+
+        let image_buffer = Self::load_image_buffer(path, game_file_loader)?;
+        let texture = self.create_texture(path, image_buffer);
+
+        #[cfg(feature = "debug")]
+        timer.stop();
+
+        Ok(texture)
+    }
+
+    fn create_texture(&mut self, path: &str, image_buffer: RgbaImage) -> Texture {
+        let dimensions = ImageDimensions::Dim2d {
+            width: image_buffer.width(),
+            height: image_buffer.height(),
+            array_layers: 1,
+        };
+
+        let paths = [path.to_string()];
+        let (image_data, compression, mip_levels) = self.encode_layers(&paths, vec![image_buffer]);
+        let (image, allocation) = self.create_image(image_data, dimensions, compression, mip_levels);
         let texture = ImageView::new_default(Arc::new(image)).unwrap();
-        self.cache.insert(path.to_string(), texture.clone());
+        self.cache
+            .textures
+            .insert(path.to_string(), (texture.clone(), Arc::new(allocation)));
+
+        texture
+    }
+
+    fn load_array(&mut self, paths: &[String], game_file_loader: &mut GameFileLoader) -> Result<Texture, String> {
+        #[cfg(feature = "debug")]
+        let timer = Timer::new_dynamic(format!("load texture array of {}{}{} textures", MAGENTA, paths.len(), NONE));
+
+        if paths.is_empty() {
+            return Err("texture array needs at least one texture".to_string());
+        }
+
+        let image_buffers = paths
+            .iter()
+            .map(|path| Self::load_image_buffer(path, game_file_loader))
+            .collect::<Result<Vec<RgbaImage>, String>>()?;
+
+        // All layers of an array texture need to have the same size, so smaller
+        // textures are scaled up to the largest one.
+        let width = image_buffers.iter().map(RgbaImage::width).max().unwrap();
+        let height = image_buffers.iter().map(RgbaImage::height).max().unwrap();
+
+        let image_buffers = image_buffers
+            .into_iter()
+            .map(|image_buffer| match image_buffer.dimensions() == (width, height) {
+                true => image_buffer,
+                false => image::imageops::resize(&image_buffer, width, height, FilterType::Triangle),
+            })
+            .collect();
+
+        let dimensions = ImageDimensions::Dim2d {
+            width,
+            height,
+            array_layers: paths.len() as u32,
+        };
+
+        let (image_data, compression, mip_levels) = self.encode_layers(paths, image_buffers);
+        let (image, allocation) = self.create_image(image_data, dimensions, compression, mip_levels);
+        let create_info = ImageViewCreateInfo {
+            view_type: ImageViewType::Dim2dArray,
+            ..ImageViewCreateInfo::from_image(&image)
+        };
+        let texture = ImageView::new(Arc::new(image), create_info).unwrap();
+        self.cache.arrays.insert(paths.to_vec(), (texture.clone(), Arc::new(allocation)));
 
         #[cfg(feature = "debug")]
         timer.stop();
@@ -90,9 +355,43 @@ impl TextureLoader {
     }
 
     pub fn get(&mut self, path: &str, game_file_loader: &mut GameFileLoader) -> Result<Texture, String> {
-        match self.cache.get(path) {
-            Some(texture) => Ok(texture.clone()),
-            None => self.load(path, game_file_loader),
+        match self.cache.textures.get(path) {
+            Some((texture, _)) => Ok(texture.clone()),
+            None => {
+                if self.memory_allocator.is_near_budget() {
+                    self.evict_unused();
+                }
+
+                self.load(path, game_file_loader)
+            }
+        }
+    }
+
+    /// Textures that are generated at runtime instead of being loaded from a
+    /// file. They are cached under the given name just like loaded ones.
+    pub fn get_generated(&mut self, name: &str, generate: impl FnOnce() -> RgbaImage) -> Texture {
+        match self.cache.textures.get(name) {
+            Some((texture, _)) => texture.clone(),
+            None => {
+                if self.memory_allocator.is_near_budget() {
+                    self.evict_unused();
+                }
+
+                self.create_texture(name, generate())
+            }
+        }
+    }
+
+    pub fn get_array(&mut self, paths: &[String], game_file_loader: &mut GameFileLoader) -> Result<Texture, String> {
+        match self.cache.arrays.get(paths) {
+            Some((texture, _)) => Ok(texture.clone()),
+            None => {
+                if self.memory_allocator.is_near_budget() {
+                    self.evict_unused();
+                }
+
+                self.load_array(paths, game_file_loader)
+            }
         }
     }
 
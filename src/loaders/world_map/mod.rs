@@ -0,0 +1,52 @@
+use cgmath::Vector2;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "debug")]
+use crate::debug::*;
+
+const WORLD_MAP_FILE: &str = "client/world_map.ron";
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WorldMapEntry {
+    pub name: String,
+    /// Cell on the continent layout, with north facing up.
+    pub position: Vector2<usize>,
+    /// Where warping to the map puts the player. Without one the server picks
+    /// a random cell.
+    pub warp_position: Option<Vector2<usize>>,
+    /// Maps that can be reached by walking off the edge of this one.
+    pub neighbours: Vec<String>,
+}
+
+/// Layout of the continent and how maps connect to each other. The table is
+/// shipped with the client and never written back.
+#[derive(Default)]
+pub struct WorldMapDatabase {
+    maps: Vec<WorldMapEntry>,
+}
+
+impl WorldMapDatabase {
+    pub fn new() -> Self {
+        let maps = Self::load().unwrap_or_else(|| {
+            #[cfg(feature = "debug")]
+            print_debug!("failed to load world map from {}{}{}", MAGENTA, WORLD_MAP_FILE, NONE);
+
+            Default::default()
+        });
+
+        Self { maps }
+    }
+
+    fn load() -> Option<Vec<WorldMapEntry>> {
+        #[cfg(feature = "debug")]
+        print_debug!("loading world map from {}{}{}", MAGENTA, WORLD_MAP_FILE, NONE);
+
+        std::fs::read_to_string(WORLD_MAP_FILE)
+            .ok()
+            .and_then(|data| ron::from_str(&data).ok())
+    }
+
+    pub fn get_maps(&self) -> &[WorldMapEntry] {
+        &self.maps
+    }
+}
@@ -10,7 +10,10 @@
 #![feature(auto_traits)]
 #![feature(let_chains)]
 #![feature(variant_count)]
+#![feature(once_cell)]
 
+mod audio;
+mod combat;
 #[cfg(feature = "debug")]
 #[macro_use]
 mod debug;
@@ -25,36 +28,70 @@ mod network;
 mod world;
 
 use std::cell::RefCell;
-use std::io::Cursor;
+use std::collections::HashMap;
 use std::rc::Rc;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use image::io::Reader as ImageReader;
-use image::{EncodableLayout, ImageFormat};
+use cgmath::Vector2;
 use procedural::debug_condition;
-use vulkano::device::{Device, DeviceCreateInfo, QueueCreateInfo};
+use vulkano::device::{Device, DeviceCreateInfo, DeviceExtensions, QueueCreateInfo};
 #[cfg(feature = "debug")]
 use vulkano::instance::debug::{DebugUtilsMessageSeverity, DebugUtilsMessageType, DebugUtilsMessenger, DebugUtilsMessengerCreateInfo};
 use vulkano::instance::{Instance, InstanceCreateInfo};
 use vulkano::sync::{now, GpuFuture};
 use vulkano::VulkanLibrary;
 use vulkano_win::VkSurfaceBuild;
-use winit::event::{Event, WindowEvent};
+use winit::dpi::PhysicalPosition;
+use winit::event::{ElementState, Event, WindowEvent};
 use winit::event_loop::{ControlFlow, EventLoop};
-use winit::window::{Icon, WindowBuilder};
 
+use crate::audio::AudioEngine;
+use crate::combat::{
+    is_large_skill, next_target, skill_info_lines, CastState, PvpMode, SkillCooldowns, SkillPlacement, SkillTarget, TargetStatus,
+};
 #[cfg(feature = "debug")]
 use crate::debug::*;
 use crate::graphics::*;
 use crate::input::{FocusState, InputSystem, UserEvent};
 use crate::interface::*;
-use crate::inventory::Inventory;
+use crate::inventory::{Hotbar, HotbarEntry, Inventory, Trade};
 use crate::loaders::*;
-use crate::network::{ChatMessage, NetworkEvent, NetworkingSystem};
-use crate::system::{get_device_extensions, get_instance_extensions, get_layers, GameTimer};
+use crate::network::{
+    parse_whisper_command, AccountId, ChatChannel, ChatLink, ChatLog, ChatMessage, ChatSettings, ChatTabs, EntityId, ItemId, MapLink,
+    NetworkEvent, NetworkingSystem, PetCommand, PetStatus, RefineResult, SkillInformation, StatusType, WhisperCommand,
+};
+#[cfg(feature = "status_server")]
+use crate::system::StatusServer;
+use crate::system::{
+    choose_physical_device, create_window_builder, get_device_extensions, get_device_features, get_instance_extensions, get_layers,
+    get_suitable_devices, set_profile, surface_window, AfkState, AlarmNotifier, Arguments, ClockSettings, EffectRandom, ExperienceTracker,
+    GameTimer, PreferredDevice, RandomStream, SessionStatistics, WindowSettings, WindowStatus,
+};
 use crate::world::*;
 
+/// Time in seconds that an entity has to be hovered before its info card is
+/// shown.
+const ENTITY_INFO_DELAY: f64 = 0.4;
+/// Height above the feet of the player from which terrain collision of the
+/// camera is checked.
+const CAMERA_COLLISION_HEIGHT: f32 = 10.0;
+/// The camera never moves closer to the player than this part of its distance,
+/// even if the terrain is in the way.
+const MINIMUM_CAMERA_FRACTION: f32 = 0.1;
+/// Part of the maximum health points that a single hit has to take for the
+/// camera to punch in.
+const HEAVY_HIT_FRACTION: f32 = 0.1;
+/// Minimum time between two frames while the player is away, to save power.
+const AWAY_FRAME_TIME: Duration = Duration::from_millis(100);
+/// Sound played when an entity vanishes or appears through teleportation.
+const TELEPORT_SOUND: &str = "ef_teleportation.wav";
+
 fn main() {
+    let arguments = Arguments::new();
+
+    set_profile(arguments.profile.as_deref());
+
     #[cfg(feature = "debug")]
     let timer = Timer::new("create device");
 
@@ -98,30 +135,15 @@ fn main() {
     #[cfg(feature = "debug")]
     let timer = Timer::new("create window");
 
-    // TODO: move this somewhere else
-    let file_data = include_bytes!("../icon.png");
-
-    let reader = ImageReader::with_format(Cursor::new(file_data), ImageFormat::Png);
-    let image_buffer = reader.decode().unwrap().to_rgba8();
-    let image_data = image_buffer.as_bytes().to_vec();
-
-    assert_eq!(image_buffer.width(), image_buffer.height(), "icon must be square");
-    let icon = Icon::from_rgba(image_data, image_buffer.width(), image_buffer.height()).unwrap();
-    //
-
+    let mut window_settings = WindowSettings::new();
     let events_loop = EventLoop::new();
-    let surface = WindowBuilder::new()
-        .with_title("Korangar".to_string())
-        .with_window_icon(Some(icon))
+    let mut surface = create_window_builder(&window_settings)
         .build_vk_surface(&events_loop, instance.clone())
         .unwrap();
 
-    surface
-        .object()
-        .unwrap()
-        .downcast_ref::<winit::window::Window>()
-        .unwrap()
-        .set_cursor_visible(false);
+    surface_window(&surface).set_cursor_visible(false);
+    // Input methods are needed to type characters that aren't on the keyboard.
+    surface_window(&surface).set_ime_allowed(true);
 
     #[cfg(feature = "debug")]
     print_debug!("created {}window{}", MAGENTA, NONE);
@@ -132,8 +154,26 @@ fn main() {
     #[cfg(feature = "debug")]
     let timer = Timer::new("choose physical device");
 
+    let mut device_settings = DeviceSettings::new();
     let desired_device_extensions = get_device_extensions();
-    let (physical_device, queue_family_index) = choose_physical_device!(&instance, &surface, &desired_device_extensions);
+    let required_device_features = get_device_features();
+    let suitable_devices = get_suitable_devices(&instance, &surface, &desired_device_extensions, &required_device_features);
+    let preferred_device = match &arguments.gpu {
+        Some(gpu) => PreferredDevice::parse(gpu),
+        None => device_settings.preferred_device.map(PreferredDevice::Id),
+    };
+    let (physical_device, queue_family_index) = choose_physical_device(&suitable_devices, preferred_device);
+
+    let available_devices: Vec<(String, DeviceId)> = suitable_devices
+        .iter()
+        .map(|(physical_device, _)| {
+            let device_name = physical_device.properties().device_name.clone();
+            (device_name, DeviceId::from_device(physical_device))
+        })
+        .collect();
+
+    #[cfg(feature = "debug")]
+    print_debug!("using device {}{}{}", MAGENTA, physical_device.properties().device_name, NONE);
 
     #[cfg(feature = "debug")]
     timer.stop();
@@ -142,12 +182,15 @@ fn main() {
     let timer = Timer::new("create device");
 
     let (device, mut queues) = Device::new(physical_device.clone(), DeviceCreateInfo {
-        enabled_extensions: get_device_extensions(),
+        enabled_extensions: DeviceExtensions {
+            ext_memory_budget: MemoryAllocator::supports_memory_budget(&physical_device),
+            ..desired_device_extensions
+        },
         enabled_features: vulkano::device::Features {
-            sampler_anisotropy: true,
+            texture_compression_bc: physical_device.supported_features().texture_compression_bc,
             #[cfg(feature = "debug")]
             fill_mode_non_solid: true,
-            ..Default::default()
+            ..required_device_features
         },
         queue_create_infos: vec![QueueCreateInfo {
             queue_family_index,
@@ -175,15 +218,15 @@ fn main() {
 
     let mut game_file_loader = GameFileLoader::default();
 
-    game_file_loader.add_archive("data.grf".to_string());
-    game_file_loader.add_archive("rdata.grf".to_string());
-    game_file_loader.add_archive("korangar.grf".to_string());
+    game_file_loader.add_archive(arguments.data_file("data.grf"));
+    game_file_loader.add_archive(arguments.data_file("rdata.grf"));
+    game_file_loader.add_archive(arguments.data_file("korangar.grf"));
 
     // Patch precompiled lua files to lua 5.1 64 bit.
-    game_file_loader.patch();
+    game_file_loader.patch(&arguments.data_file("lua_files.grf"));
 
     // Load patched files to overwrite the original ones.
-    game_file_loader.add_archive("lua_files.grf".to_string());
+    game_file_loader.add_archive(arguments.data_file("lua_files.grf"));
 
     let memory_allocator = Arc::new(MemoryAllocator::new(device.clone()));
 
@@ -194,11 +237,16 @@ fn main() {
     )));
 
     let mut model_loader = ModelLoader::new(memory_allocator.clone());
-    let mut texture_loader = TextureLoader::new(memory_allocator.clone(), queue.clone());
+    let mut texture_compression_settings = TextureCompressionSettings::new();
+    let mut texture_loader = TextureLoader::new(memory_allocator.clone(), queue.clone(), &texture_compression_settings);
     let mut map_loader = MapLoader::new(memory_allocator.clone());
+    let mut map_preloader = MapPreloader::new();
+    let mut sprite_preloader = SpritePreloader::new();
     let mut sprite_loader = SpriteLoader::new(memory_allocator.clone(), queue.clone());
     let mut action_loader = ActionLoader::default();
     let script_loader = ScriptLoader::new(&mut game_file_loader);
+    let monster_database = MonsterDatabase::new();
+    let world_map_database = WorldMapDatabase::new();
 
     #[cfg(feature = "debug")]
     timer.stop();
@@ -206,9 +254,21 @@ fn main() {
     #[cfg(feature = "debug")]
     let timer = Timer::new("load resources");
 
+    #[cfg(feature = "debug")]
+    let snapshot = match arguments.restore_snapshot {
+        true => ClientSnapshot::load(),
+        false => None,
+    };
+    #[cfg(feature = "debug")]
+    let initial_map = snapshot
+        .as_ref()
+        .map_or_else(|| arguments.map.clone(), |snapshot| snapshot.map_name.clone());
+    #[cfg(not(feature = "debug"))]
+    let initial_map = arguments.map.clone();
+
     let mut map = map_loader
         .get(
-            "geffen".to_string(),
+            initial_map.clone(),
             &mut game_file_loader,
             &mut model_loader,
             &mut texture_loader,
@@ -221,7 +281,7 @@ fn main() {
     #[cfg(feature = "debug")]
     let timer = Timer::new("create swapchain");
 
-    let mut swapchain_holder = SwapchainHolder::new(&physical_device, device.clone(), queue.clone(), surface.clone());
+    let mut swapchain_holder = SwapchainHolder::new(device.clone(), queue.clone(), surface.clone());
     let viewport = swapchain_holder.viewport();
 
     #[cfg(feature = "debug")]
@@ -230,12 +290,15 @@ fn main() {
     #[cfg(feature = "debug")]
     let timer = Timer::new("create renderers");
 
+    let mut graphics_settings = GraphicsSettings::new();
+
     let mut deferred_renderer = DeferredRenderer::new(
         memory_allocator.clone(),
         queue.clone(),
         swapchain_holder.swapchain_format(),
         viewport.clone(),
         swapchain_holder.window_size_u32(),
+        graphics_settings.render_scale,
         &mut game_file_loader,
         &mut texture_loader,
     );
@@ -257,7 +320,9 @@ fn main() {
         swapchain_holder.window_size_u32(),
     );
 
-    let shadow_renderer = ShadowRenderer::new(memory_allocator, queue);
+    let preview_renderer = PreviewRenderer::new(memory_allocator.clone(), queue.clone());
+
+    let shadow_renderer = ShadowRenderer::new(memory_allocator.clone(), queue);
 
     #[cfg(feature = "debug")]
     timer.stop();
@@ -273,6 +338,8 @@ fn main() {
 
     let mut interface_target = interface_renderer.create_render_target();
 
+    let mut preview_target = preview_renderer.create_render_target();
+
     let mut picker_targets = swapchain_holder
         .get_swapchain_images()
         .into_iter()
@@ -295,11 +362,11 @@ fn main() {
         &mut game_file_loader,
         &mut sprite_loader,
         &mut action_loader,
+        &mut texture_loader,
         swapchain_holder.window_size_f32(),
     );
     let mut focus_state = FocusState::default();
     let mut input_system = InputSystem::new();
-    let mut graphics_settings = GraphicsSettings::new();
     #[cfg(feature = "debug")]
     let mut render_settings = RenderSettings::new();
 
@@ -321,33 +388,136 @@ fn main() {
     let mut debug_camera = DebugCamera::new();
     let mut start_camera = StartCamera::new();
     let mut player_camera = PlayerCamera::new();
+    let mut camera_presets = CameraPresets::new();
+    let mut object_fading = ObjectFading::default();
     let mut directional_shadow_camera = ShadowCamera::new();
+    let mut preview_camera = PreviewCamera::new();
 
     start_camera.set_focus_point(cgmath::Vector3::new(600.0, 0.0, 240.0));
     directional_shadow_camera.set_focus_point(cgmath::Vector3::new(600.0, 0.0, 240.0));
 
+    #[cfg(feature = "debug")]
+    if let Some(snapshot) = &snapshot {
+        debug_camera.restore_state(snapshot.debug_camera);
+        player_camera.apply_preset(&snapshot.player_camera);
+
+        if snapshot.use_debug_camera != render_settings.use_debug_camera {
+            render_settings.toggle_use_debug_camera();
+        }
+    }
+
     #[cfg(feature = "debug")]
     timer.stop();
 
     #[cfg(feature = "debug")]
     let timer = Timer::new("initialize networking");
 
-    let mut networking_system = NetworkingSystem::new();
+    let mut networking_system = NetworkingSystem::new(arguments.server.as_deref());
+    let mut character_list = networking_system.get_characters();
+    // Characters on the selection screen, each with the image its sprite is rendered
+    // into.
+    let mut character_preview_entities: Vec<(usize, Entity, <PreviewRenderer as Renderer>::Target)> = Vec::new();
+    let mut character_previews = TrackedState::new(Vec::new());
 
     interface.open_window(
         &mut focus_state,
         &LoginWindow::new(networking_system.get_login_settings().clone()),
     );
 
+    if window_settings.custom_decorations {
+        interface.open_window(&mut focus_state, &TitleBarWindow::default());
+    }
+
     #[cfg(feature = "debug")]
     timer.stop();
 
-    let mut particle_holder = ParticleHolder::default();
+    let mut particle_holder = ParticleHolder::new(graphics_settings.effect_quality.budget());
+    let mut effect_random = EffectRandom::new(arguments.seed);
+    print_debug!("using effect seed {}{}{}", MAGENTA, effect_random.get_seed(), NONE);
+    let mut status_indicator_sprites = StatusIndicatorSprites::new(
+        &mut game_file_loader,
+        &mut sprite_loader,
+        &mut action_loader,
+        game_timer.get_client_tick(),
+    );
+    let mut audio_engine = AudioEngine::new(&mut game_file_loader, arguments.data_dir.clone());
     let mut entities = Vec::<Entity>::new();
+    // Entities restored from a snapshot are kept apart from the other entities, since
+    // the first entity is always the player. They only exist on the login screen.
+    #[cfg(feature = "debug")]
+    let mut snapshot_entities = Vec::<Entity>::new();
+
+    #[cfg(feature = "debug")]
+    if let Some(snapshot) = &snapshot {
+        for entity_data in snapshot.entity_data() {
+            let npc = Npc::new(
+                &mut game_file_loader,
+                &mut sprite_loader,
+                &mut action_loader,
+                &script_loader,
+                &map,
+                entity_data,
+                game_timer.get_client_tick(),
+            );
+            snapshot_entities.push(Entity::Npc(npc));
+        }
+    }
+
+    // Windows of the snapshot are opened on the first frame, through the same events
+    // as the buttons that open them.
+    #[cfg(feature = "debug")]
+    let mut restored_events = snapshot.as_ref().map(ClientSnapshot::window_events).unwrap_or_default();
     let mut player_inventory = Inventory::default();
+    let mut trade = Trade::default();
+
+    #[cfg(feature = "status_server")]
+    let status_server = StatusServer::new();
 
-    let welcome_message = ChatMessage::new("Welcome to Korangar!".to_string(), Color::rgb(220, 170, 220));
+    let welcome_message = ChatMessage::new(
+        "Welcome to Korangar!".to_string(),
+        Color::rgb(220, 170, 220),
+        ChatChannel::Client,
+    );
     let chat_messages = Rc::new(RefCell::new(vec![welcome_message]));
+    let mut chat_settings = ChatSettings::new();
+    let mut clock_settings = ClockSettings::new();
+    let mut alarm_notifier = AlarmNotifier::default();
+    let mut chat_log = ChatLog::default();
+    let mut chat_tabs = ChatTabs::default();
+    let mut hotbar = Hotbar::default();
+    // where the candidate window of input methods was last placed
+    let mut ime_position = None;
+    // the entity under the cursor and for how long it has been hovered
+    let mut entity_hover: Option<(EntityId, f64)> = None;
+    // equipment that the card currently being inserted fits into
+    let mut card_targets = Vec::new();
+    // set after using a taming item, the next monster clicked will be captured
+    let mut capturing_pet = false;
+    let mut pet_status: Option<PetStatus> = None;
+    let mut skills: Vec<SkillInformation> = Vec::new();
+    // the monster the player attacked last, used for damage estimates
+    let mut current_target: Option<EntityId> = None;
+    // current and maximum values shown by the status bar HUD
+    let mut player_health_points = TrackedState::new((0, 0));
+    let mut player_spell_points = TrackedState::new((0, 0));
+    let mut base_experience = TrackedState::new(ExperienceTracker::new("base"));
+    let mut job_experience = TrackedState::new(ExperienceTracker::new("job"));
+    let mut cast_state = TrackedState::new(CastState::default());
+    let mut skill_cooldowns = TrackedState::new(SkillCooldowns::default());
+    let mut target_status: TrackedState<Option<TargetStatus>> = TrackedState::new(None);
+    let mut minimap_state = TrackedState::new(MinimapState::default());
+    let mut current_map = TrackedState::new(initial_map);
+    let mut waypoint: TrackedState<Option<MapLink>> = TrackedState::new(None);
+    let mut party_member_positions: HashMap<AccountId, Vector2<usize>> = HashMap::new();
+    let mut target_cast = TrackedState::new(CastState::default());
+    let mut session_statistics = SessionStatistics::default();
+    // ground skill that is waiting for the player to click a cell
+    let mut skill_placement: Option<SkillPlacement> = None;
+    let mut pvp_mode = PvpMode::default();
+    let mut pvp_aggressive = false;
+    let mut afk_state = AfkState::default();
+    let mut window_status = WindowStatus::default();
+    let mut last_frame = Instant::now();
 
     let thread_pool = rayon::ThreadPoolBuilder::new().num_threads(3).build().unwrap();
 
@@ -374,6 +544,8 @@ fn main() {
                 event: WindowEvent::Focused(focused),
                 ..
             } => {
+                window_status.set_focused(focused);
+
                 if !focused {
                     input_system.reset();
                     focus_state.remove_focus();
@@ -390,19 +562,35 @@ fn main() {
             Event::WindowEvent {
                 event: WindowEvent::CursorMoved { position, .. },
                 ..
-            } => input_system.update_mouse_position(position),
+            } => {
+                afk_state.register_input();
+                input_system.update_mouse_position(position);
+            }
             Event::WindowEvent {
                 event: WindowEvent::MouseInput { button, state, .. },
                 ..
-            } => input_system.update_mouse_buttons(button, state),
+            } => {
+                if state == ElementState::Pressed {
+                    afk_state.register_input();
+                }
+
+                input_system.update_mouse_buttons(button, state);
+            }
             Event::WindowEvent {
                 event: WindowEvent::MouseWheel { delta, .. },
                 ..
-            } => input_system.update_mouse_wheel(delta),
+            } => {
+                afk_state.register_input();
+                input_system.update_mouse_wheel(delta);
+            }
             Event::WindowEvent {
                 event: WindowEvent::KeyboardInput { input, .. },
                 ..
             } => {
+                if input.state == ElementState::Pressed {
+                    afk_state.register_input();
+                }
+
                 if let Some(keycode) = input.virtual_keycode {
                     input_system.update_keyboard(keycode, input.state)
                 }
@@ -411,7 +599,25 @@ fn main() {
                 event: WindowEvent::ReceivedCharacter(character),
                 ..
             } => input_system.buffer_character(character),
+            Event::WindowEvent {
+                event: WindowEvent::Ime(ime),
+                ..
+            } => input_system.buffer_ime(ime),
             Event::MainEventsCleared => {
+                // Render at a low rate while the player is away or the window is minimized. Any
+                // input wakes the event loop up again.
+                if afk_state.is_away(*chat_settings.afk_minutes.borrow()) || swapchain_holder.is_minimized() {
+                    let next_frame = last_frame + AWAY_FRAME_TIME;
+
+                    if Instant::now() < next_frame {
+                        *control_flow = ControlFlow::WaitUntil(next_frame);
+                        return;
+                    }
+                }
+
+                *control_flow = ControlFlow::Poll;
+                last_frame = Instant::now();
+
                 input_system.update_delta();
 
                 let delta_time = game_timer.update();
@@ -421,6 +627,12 @@ fn main() {
 
                 networking_system.keep_alive(delta_time, client_tick);
 
+                for message in alarm_notifier.update(&clock_settings) {
+                    let message = ChatMessage::new(message, Color::rgb(255, 200, 100), ChatChannel::Client);
+                    chat_messages.borrow_mut().push(message);
+                    window_status.notify(surface_window(&surface));
+                }
+
                 let network_events = networking_system.network_events();
                 let (user_events, hovered_element, focused_element, mouse_target) = input_system.user_events(
                     &mut interface,
@@ -430,8 +642,49 @@ fn main() {
                     &render_settings,
                     swapchain_holder.window_size(),
                     client_tick,
+                    current_target.is_some() || skill_placement.is_some(),
                 );
 
+                // Input methods show their candidates at the text cursor of the focused input
+                // field.
+                let caret_position = focused_element.as_ref().and_then(|element| interface.caret_position(element));
+
+                if caret_position != ime_position {
+                    if let Some(position) = caret_position {
+                        surface_window(&surface).set_ime_position(PhysicalPosition::new(position.x, position.y));
+                    }
+
+                    ime_position = caret_position;
+                }
+
+                entity_hover = match mouse_target {
+                    Some(PickerTarget::Entity(entity_id)) => match entity_hover {
+                        Some((hovered_entity_id, hover_time)) if hovered_entity_id == entity_id => {
+                            Some((entity_id, hover_time + delta_time))
+                        }
+                        _ => Some((entity_id, 0.0)),
+                    },
+                    _ => None,
+                };
+
+                let tooltip = interface.update_tooltip(hovered_element.as_ref(), delta_time);
+                let tooltip_lines = tooltip.and_then(|tooltip| match tooltip {
+                    TooltipContent::Text(lines) => Some(lines),
+                    TooltipContent::Item(item) => Some(item.info_lines(&script_loader)),
+                    TooltipContent::Skill(skill) => {
+                        let Some(Entity::Player(player)) = entities.first() else {
+                            return None;
+                        };
+
+                        let target = current_target
+                            .and_then(|entity_id| entities.iter().find(|entity| entity.get_entity_id() == entity_id))
+                            .filter(|entity| matches!(entity.get_entity_type(), EntityType::Monster))
+                            .and_then(|entity| monster_database.get(entity.get_job()));
+
+                        Some(skill_info_lines(&skill, &player.attack_stats, target))
+                    }
+                });
+
                 if let Some(PickerTarget::Entity(entity_id)) = mouse_target {
                     if let Some(entity) = entities.iter_mut().find(|entity| entity.get_entity_id() == entity_id) {
                         if entity.are_details_unavailable() {
@@ -443,29 +696,72 @@ fn main() {
                             EntityType::Npc => interface.set_mouse_cursor_state(MouseCursorState::Dialog, client_tick),
                             EntityType::Warp => interface.set_mouse_cursor_state(MouseCursorState::Warp, client_tick),
                             EntityType::Monster => interface.set_mouse_cursor_state(MouseCursorState::Attack, client_tick),
+                            EntityType::Player if pvp_mode.allows_player_attacks() && pvp_aggressive => {
+                                interface.set_mouse_cursor_state(MouseCursorState::Attack, client_tick)
+                            }
                             _ => {} // TODO: fill other entity types
                         }
                     }
                 }
 
+                let mut pet_status_changed = false;
+                let mut session_statistics_changed = false;
+
                 for event in network_events {
                     match event {
                         NetworkEvent::AddEntity(entity_appeared_data) => {
-                            let npc = Npc::new(
-                                &mut game_file_loader,
-                                &mut sprite_loader,
-                                &mut action_loader,
-                                &script_loader,
-                                &map,
-                                entity_appeared_data,
-                                game_timer.get_client_tick(),
-                            );
-                            let npc = Entity::Npc(npc);
-                            entities.push(npc);
+                            let entity_id = entity_appeared_data.entity_id;
+
+                            // Entities that are already known are sent again when they change their
+                            // mount or speed, so they are updated instead of added a second time.
+                            if let Some(entity) = entities.iter_mut().find(|entity| entity.get_entity_id() == entity_id) {
+                                entity.update_movement_speed(entity_appeared_data.movement_speed as usize);
+                                entity.update_effect_state(
+                                    &mut game_file_loader,
+                                    &mut sprite_loader,
+                                    &mut action_loader,
+                                    &script_loader,
+                                    entity_appeared_data.effect_state,
+                                    game_timer.get_client_tick(),
+                                );
+                            } else {
+                                sprite_preloader.register_spawn(entity_appeared_data.job as usize);
+
+                                let mut npc = Npc::new(
+                                    &mut game_file_loader,
+                                    &mut sprite_loader,
+                                    &mut action_loader,
+                                    &script_loader,
+                                    &map,
+                                    entity_appeared_data,
+                                    game_timer.get_client_tick(),
+                                );
+                                npc.get_common_mut().offset_idle_animation(effect_random.stream(RandomStream::IdleAnimations));
+                                let npc = Entity::Npc(npc);
+                                entities.push(npc);
+                            }
                         }
                         NetworkEvent::RemoveEntity(entity_id) => {
                             entities.retain(|entity| entity.get_entity_id() != entity_id);
                         }
+                        NetworkEvent::EntityDied(entity_id) => {
+                            // Only the monster the player is fighting counts as killed by the player.
+                            if current_target == Some(entity_id)
+                                && let Some(entity) = entities.iter().find(|entity| entity.get_entity_id() == entity_id)
+                                && entity.get_entity_type() == EntityType::Monster
+                            {
+                                let monster_name = entity
+                                    .get_details()
+                                    .map(|details| details.split('#').next().unwrap().to_owned())
+                                    .or_else(|| monster_database.get(entity.get_job()).map(|info| info.name.clone()))
+                                    .unwrap_or_else(|| format!("monster {}", entity.get_job()));
+
+                                session_statistics.add_kill(monster_name);
+                                session_statistics_changed = true;
+                            }
+
+                            entities.retain(|entity| entity.get_entity_id() != entity_id);
+                        }
                         NetworkEvent::EntityMove(entity_id, position_from, position_to, starting_timestamp) => {
                             let entity = entities.iter_mut().find(|entity| entity.get_entity_id() == entity_id);
 
@@ -480,18 +776,101 @@ fn main() {
                             /*#[cfg(feature = "debug")]
                             entities[0].generate_steps_vertex_buffer(device.clone(), &map);*/
                         }
+                        NetworkEvent::EntityStopMove(entity_id, position) => {
+                            let entity = entities.iter_mut().find(|entity| entity.get_entity_id() == entity_id);
+
+                            if let Some(entity) = entity {
+                                entity.stop_at(&map, position, game_timer.get_client_tick());
+                            }
+                        }
+                        NetworkEvent::EntitySlide(entity_id, position) => {
+                            let entity = entities.iter_mut().find(|entity| entity.get_entity_id() == entity_id);
+
+                            if let Some(entity) = entity {
+                                entity.slide_to(&map, position, game_timer.get_client_tick());
+                            }
+                        }
+                        NetworkEvent::EntityJump(entity_id) => {
+                            let entity = entities.iter_mut().find(|entity| entity.get_entity_id() == entity_id);
+
+                            if let Some(entity) = entity {
+                                entity.prepare_jump();
+                            }
+                        }
+                        NetworkEvent::EntityTeleported(entity_id) => {
+                            if let Some(entity) = entities.iter().find(|entity| entity.get_entity_id() == entity_id) {
+                                particle_holder.spawn_particle(Box::new(TeleportEffect::new(entity.get_position(), false)));
+                                audio_engine.play_sound_effect(&mut game_file_loader, TELEPORT_SOUND);
+                            }
+
+                            entities.retain(|entity| entity.get_entity_id() != entity_id);
+                        }
                         NetworkEvent::ChangeMap(map_name, player_position) => {
+                            // Teleporting on the same map (Fly Wing, Teleport, ...) doesn't need to load
+                            // anything.
+                            let same_map = map_preloader.current_map() == Some(map_name.as_str());
+
+                            if same_map {
+                                particle_holder.spawn_particle(Box::new(TeleportEffect::new(entities[0].get_position(), false)));
+                                audio_engine.play_sound_effect(&mut game_file_loader, TELEPORT_SOUND);
+                            } else {
+                                let previous_position = entities[0].get_grid_position();
+                                let used_warp = entities
+                                    .iter()
+                                    .filter(|entity| entity.get_entity_type() == EntityType::Warp)
+                                    .map(|entity| entity.get_grid_position())
+                                    .find(|warp_position| {
+                                        warp_position.x.abs_diff(previous_position.x) <= 2
+                                            && warp_position.y.abs_diff(previous_position.y) <= 2
+                                    });
+
+                                map_preloader.enter_map(&map_name, used_warp);
+                                sprite_preloader.enter_map(
+                                    &map_name,
+                                    &mut game_file_loader,
+                                    &mut sprite_loader,
+                                    &mut action_loader,
+                                    &script_loader,
+                                );
+
+                                #[cfg(feature = "status_server")]
+                                if let Some(status_server) = &status_server {
+                                    status_server.change_map(&map_name);
+                                }
+
+                                audio_engine.play_map_music(&mut game_file_loader, &map_name);
+                                map = map_loader
+                                    .get(map_name.clone(), &mut game_file_loader, &mut model_loader, &mut texture_loader)
+                                    .unwrap();
+                                map_loader.retire_maps(|map_name| map_preloader.is_nearby(map_name));
+
+                                minimap_state.with_mut(|minimap_state, changed| {
+                                    minimap_state.load_map(&map, &map_name, &mut game_file_loader, &mut texture_loader);
+                                    changed();
+                                });
+                                current_map.set(map_name.clone());
+                                party_member_positions.clear();
+
+                                particle_holder.clear();
+                                audio_engine.clear_ambient_sounds();
+                            }
+
                             while entities.len() > 1 {
                                 entities.pop();
                             }
 
-                            map = map_loader
-                                .get(map_name, &mut game_file_loader, &mut model_loader, &mut texture_loader)
-                                .unwrap();
+                            entities[0].set_chat_room(None);
+
+                            if interface.window_exists(Some(ChatRoomWindow::WINDOW_CLASS)) {
+                                interface.close_window_with_class(&mut focus_state, ChatRoomWindow::WINDOW_CLASS);
+                            }
 
                             entities[0].set_position(&map, player_position, game_timer.get_client_tick());
 
-                            particle_holder.clear();
+                            if same_map {
+                                particle_holder.spawn_particle(Box::new(TeleportEffect::new(entities[0].get_position(), true)));
+                            }
+
                             networking_system.map_loaded();
                             // TODO: this is just a workaround until i find a better solution to make the
                             // cursor always look correct.
@@ -501,8 +880,73 @@ fn main() {
                             game_timer.set_client_tick(client_tick);
                         }
                         NetworkEvent::ChatMessage(message) => {
+                            if *chat_settings.log_to_disk.borrow() {
+                                chat_log.write(&message);
+                            }
+
                             chat_messages.borrow_mut().push(message);
                         }
+                        NetworkEvent::WhisperReceived(sender) => {
+                            window_status.notify(surface_window(&surface));
+
+                            if afk_state.is_away(*chat_settings.afk_minutes.borrow()) && afk_state.should_reply(&sender) {
+                                let afk_message = chat_settings.afk_message.borrow().clone();
+                                let message = networking_system.send_whisper(sender, afk_message);
+
+                                if *chat_settings.log_to_disk.borrow() {
+                                    chat_log.write(&message);
+                                }
+
+                                chat_messages.borrow_mut().push(message);
+                            }
+                        }
+                        NetworkEvent::PartyInvitation => window_status.notify(surface_window(&surface)),
+                        NetworkEvent::PartyMemberPosition(account_id, position) => {
+                            party_member_positions.insert(account_id, position);
+                        }
+                        NetworkEvent::PartyMemberLeft(account_id) => {
+                            party_member_positions.remove(&account_id);
+                        }
+                        NetworkEvent::UpdatePartyMembers(members) => {
+                            party_member_positions.retain(|account_id, _| members.contains(account_id));
+                        }
+                        NetworkEvent::UpdateChatRoom(entity_id, chat_room) => {
+                            let entity = entities.iter_mut().find(|entity| entity.get_entity_id() == entity_id);
+
+                            if let Some(entity) = entity {
+                                entity.set_chat_room(Some(chat_room));
+                            }
+                        }
+                        NetworkEvent::RemoveChatRoom(room_id) => entities
+                            .iter_mut()
+                            .filter(|entity| entity.get_chat_room().map_or(false, |chat_room| chat_room.room_id == room_id))
+                            .for_each(|entity| entity.set_chat_room(None)),
+                        NetworkEvent::ChatRoomEntered(room_id) => {
+                            let title = entities
+                                .iter()
+                                .find_map(|entity| entity.get_chat_room().filter(|chat_room| chat_room.room_id == room_id))
+                                .map(|chat_room| chat_room.title.clone())
+                                .unwrap_or_default();
+
+                            if interface.window_exists(Some(ChatRoomJoinWindow::WINDOW_CLASS)) {
+                                interface.close_window_with_class(&mut focus_state, ChatRoomJoinWindow::WINDOW_CLASS);
+                            }
+
+                            interface.open_window(
+                                &mut focus_state,
+                                &ChatRoomWindow::new(
+                                    title,
+                                    chat_messages.clone(),
+                                    font_loader.clone(),
+                                    chat_settings.show_timestamps.clone(),
+                                ),
+                            );
+                        }
+                        NetworkEvent::ChatRoomLeft => {
+                            if interface.window_exists(Some(ChatRoomWindow::WINDOW_CLASS)) {
+                                interface.close_window_with_class(&mut focus_state, ChatRoomWindow::WINDOW_CLASS);
+                            }
+                        }
                         NetworkEvent::UpdateEntityDetails(entity_id, name) => {
                             let entity = entities.iter_mut().find(|entity| entity.get_entity_id() == entity_id);
 
@@ -516,7 +960,22 @@ fn main() {
                                 .find(|entity| entity.get_entity_id() == entity_id)
                                 .unwrap_or(&entities[0]);
 
-                            particle_holder.spawn_particle(Box::new(DamageNumber::new(entity.get_position(), damage_amount.to_string())));
+                            let damage_number = DamageNumber::new(
+                                entity.get_position(),
+                                damage_amount.to_string(),
+                                effect_random.stream(RandomStream::Particles),
+                            );
+                            particle_holder.spawn_particle(Box::new(damage_number));
+                        }
+                        NetworkEvent::SkillDamage(skill_id, entity_id) => {
+                            // Only entities in sight are known, so this doesn't shake the camera for
+                            // skills far away from the player.
+                            let is_visible = entities.iter().any(|entity| entity.get_entity_id() == entity_id);
+
+                            if is_large_skill(skill_id) && is_visible {
+                                player_camera.add_effect(CameraEffect::zoom_pulse(0.06, 3.0, 0.6));
+                                player_camera.add_effect(CameraEffect::shake(1.5, 10.0, 0.4));
+                            }
                         }
                         NetworkEvent::UpdateEntityHealth(entity_id, health_points, maximum_health_points) => {
                             let entity = entities.iter_mut().find(|entity| entity.get_entity_id() == entity_id);
@@ -525,12 +984,95 @@ fn main() {
                                 entity.update_health(health_points, maximum_health_points);
                             }
                         }
+                        NetworkEvent::UpdateEntityState(entity_id, body_state, health_state, effect_state) => {
+                            let entity = entities.iter_mut().find(|entity| entity.get_entity_id() == entity_id);
+
+                            if let Some(entity) = entity {
+                                entity.update_states(body_state, health_state);
+                                entity.update_effect_state(
+                                    &mut game_file_loader,
+                                    &mut sprite_loader,
+                                    &mut action_loader,
+                                    &script_loader,
+                                    effect_state,
+                                    client_tick,
+                                );
+                            }
+                        }
+                        NetworkEvent::UpdateEntityLook(entity_id, look_change) => {
+                            let entity = entities.iter_mut().find(|entity| entity.get_entity_id() == entity_id);
+
+                            if let Some(entity) = entity {
+                                entity.update_look(
+                                    &mut game_file_loader,
+                                    &mut sprite_loader,
+                                    &mut action_loader,
+                                    &script_loader,
+                                    look_change,
+                                );
+                            }
+                        }
+                        NetworkEvent::EntityAttack(entity_id, attack_duration) => {
+                            let entity = entities.iter_mut().find(|entity| entity.get_entity_id() == entity_id);
+
+                            if let Some(entity) = entity {
+                                entity.attack(attack_duration, game_timer.get_client_tick());
+                            }
+                        }
                         NetworkEvent::UpdateStatus(status_type) => {
                             let Entity::Player(player) = &mut entities[0] else {
                                 panic!();
                             };
 
+                            // The player entity doesn't receive damage effects, so damage taken is
+                            // only visible through the health points going down.
+                            if let StatusType::HealthPoints(value) = status_type {
+                                let (health_points, maximum_health_points) = player.get_health_points();
+
+                                if (value as usize) < health_points && maximum_health_points > 0 {
+                                    let lost_fraction = (health_points - value as usize) as f32 / maximum_health_points as f32;
+                                    player_camera.add_effect(CameraEffect::shake(1.0 + lost_fraction * 8.0, 14.0, 0.25 + lost_fraction));
+
+                                    // Heavy hits also pull the camera towards the player.
+                                    if lost_fraction >= HEAVY_HIT_FRACTION {
+                                        player_camera.add_effect(CameraEffect::punch(0.08, 0.35));
+                                    }
+                                }
+                            }
+
+                            match status_type {
+                                StatusType::BaseExperience(value) => base_experience.with_mut(|tracker, changed| {
+                                    session_statistics.add_base_experience(tracker.set_current(value));
+                                    session_statistics_changed = true;
+                                    changed();
+                                }),
+                                StatusType::NextBaseExperience(value) => base_experience.with_mut(|tracker, changed| {
+                                    tracker.set_next(value);
+                                    changed();
+                                }),
+                                StatusType::JobExperience(value) => job_experience.with_mut(|tracker, changed| {
+                                    session_statistics.add_job_experience(tracker.set_current(value));
+                                    session_statistics_changed = true;
+                                    changed();
+                                }),
+                                StatusType::NextJobExperience(value) => job_experience.with_mut(|tracker, changed| {
+                                    tracker.set_next(value);
+                                    changed();
+                                }),
+                                StatusType::Zeny(value) => {
+                                    session_statistics.set_zeny(value);
+                                    session_statistics_changed = true;
+                                }
+                                StatusType::HealthPoints(0) if player.get_health_points().0 > 0 => {
+                                    session_statistics.add_death();
+                                    session_statistics_changed = true;
+                                }
+                                _ => {}
+                            }
+
                             player.update_status(status_type);
+                            player_health_points.set(player.get_health_points());
+                            player_spell_points.set(player.get_spell_points());
                         }
                         NetworkEvent::OpenDialog(text, npc_id) => interface.open_dialog_window(&mut focus_state, text, npc_id),
                         NetworkEvent::AddNextButton => interface.add_next_button(),
@@ -543,7 +1085,11 @@ fn main() {
                         NetworkEvent::Inventory(item_data) => {
                             player_inventory.fill(&mut game_file_loader, &mut texture_loader, &script_loader, item_data);
                         }
-                        NetworkEvent::AddIventoryItem(item_index, item_data, equip_position, equipped_position) => {
+                        NetworkEvent::ItemPickedUp(count) => {
+                            session_statistics.add_loot(count);
+                            session_statistics_changed = true;
+                        }
+                        NetworkEvent::AddIventoryItem(item_index, item_data, equip_position, equipped_position, cards, amount) => {
                             player_inventory.add_item(
                                 &mut game_file_loader,
                                 &mut texture_loader,
@@ -552,31 +1098,357 @@ fn main() {
                                 item_data,
                                 equip_position,
                                 equipped_position,
+                                cards,
+                                amount,
                             );
                         }
+                        NetworkEvent::RemoveInventoryItem(item_index, amount) => player_inventory.remove_item(item_index, amount),
+                        NetworkEvent::UpdateInventoryItemAmount(item_index, amount) => player_inventory.update_amount(item_index, amount),
                         NetworkEvent::UpdateEquippedPosition { index, equipped_position } => {
                             player_inventory.update_equipped_position(index, equipped_position);
                         }
+                        NetworkEvent::OpenRefineWindow => {
+                            let window = RefineWindow::new(&player_inventory.get_item_state().borrow(), &script_loader, None);
+                            interface.open_window(&mut focus_state, &window);
+                        }
+                        NetworkEvent::RefineItemInformation(item_index, materials) => {
+                            player_inventory.highlight_items(materials.iter().map(|material| material.item_id).collect());
+
+                            let window = RefineWindow::new(
+                                &player_inventory.get_item_state().borrow(),
+                                &script_loader,
+                                Some((item_index, materials)),
+                            );
+
+                            interface.reopen_window(&mut focus_state, &window);
+                        }
+                        NetworkEvent::RefineResult(_item_index, result, refinement_level) => {
+                            player_inventory.highlight_items(Vec::new());
+
+                            let (text, success) = match result {
+                                RefineResult::Success => (format!("success! the item is now +{}", refinement_level), true),
+                                RefineResult::Failure => ("failure, the item broke".to_string(), false),
+                                RefineResult::Downgrade => (format!("failure, the item dropped to +{}", refinement_level), false),
+                                RefineResult::FailureKeepItem => ("failure, but the item is unharmed".to_string(), false),
+                            };
+
+                            let window = RefineWindow::new(&player_inventory.get_item_state().borrow(), &script_loader, None);
+                            interface.reopen_window(&mut focus_state, &window);
+
+                            interface.reopen_window(&mut focus_state, &CraftingResultWindow::new(text, success));
+                        }
+                        NetworkEvent::CraftableItems(items) => {
+                            let material_ids = items
+                                .iter()
+                                .flat_map(|item| item.material_ids)
+                                .filter(|material_id| *material_id != ItemId(0))
+                                .collect();
+                            player_inventory.highlight_items(material_ids);
+
+                            interface.reopen_window(&mut focus_state, &CraftingWindow::new(items, &script_loader));
+                        }
+                        NetworkEvent::CraftResult(item_id, success) => {
+                            let name = script_loader.get_item_name_from_id(item_id);
+                            let text = match success {
+                                true => format!("crafted {}", name),
+                                false => format!("failed to craft {}", name),
+                            };
+
+                            interface.reopen_window(&mut focus_state, &CraftingResultWindow::new(text, success));
+                        }
+                        NetworkEvent::CardTargets(card_index, target_indices) => {
+                            card_targets = target_indices;
+
+                            let window = CardCompoundWindow::new(
+                                &player_inventory.get_item_state().borrow(),
+                                &script_loader,
+                                card_index,
+                                card_targets.clone(),
+                                None,
+                            );
+
+                            interface.reopen_window(&mut focus_state, &window);
+                        }
+                        NetworkEvent::CardInserted {
+                            card_index,
+                            equipment_index,
+                        } => {
+                            player_inventory.insert_card(card_index, equipment_index);
+
+                            let message = ChatMessage::new("card inserted".to_string(), Color::rgb(100, 255, 100), ChatChannel::Client);
+                            chat_messages.borrow_mut().push(message);
+                        }
+                        NetworkEvent::CardInsertFailed => {
+                            let message = ChatMessage::new(
+                                "failed to insert the card".to_string(),
+                                Color::rgb(255, 100, 100),
+                                ChatChannel::Client,
+                            );
+                            chat_messages.borrow_mut().push(message);
+                        }
+                        NetworkEvent::StartPetCapture => {
+                            capturing_pet = true;
+
+                            let message = ChatMessage::new(
+                                "select a monster to capture".to_string(),
+                                Color::rgb(255, 255, 100),
+                                ChatChannel::Client,
+                            );
+                            chat_messages.borrow_mut().push(message);
+                        }
+                        NetworkEvent::PetCaptureResult(success) => {
+                            let message = match success {
+                                true => ChatMessage::new("capture succeeded".to_string(), Color::rgb(100, 255, 100), ChatChannel::Client),
+                                false => ChatMessage::new("capture failed".to_string(), Color::rgb(255, 100, 100), ChatChannel::Client),
+                            };
+                            chat_messages.borrow_mut().push(message);
+                        }
+                        NetworkEvent::PetEggList(egg_indices) => {
+                            let window = PetEggWindow::new(&player_inventory.get_item_state().borrow(), &script_loader, egg_indices);
+                            interface.reopen_window(&mut focus_state, &window);
+                        }
+                        NetworkEvent::SkillCast {
+                            entity_id,
+                            skill_id,
+                            duration,
+                        } => {
+                            let state = match entity_id == entities[0].get_entity_id() {
+                                true => Some(&mut cast_state),
+                                false => (current_target == Some(entity_id)).then_some(&mut target_cast),
+                            };
+
+                            if let Some(state) = state
+                                && duration > 0
+                            {
+                                let skill_name = skills
+                                    .iter()
+                                    .find(|skill| skill.skill_id == skill_id)
+                                    .map(|skill| skill.skill_name.clone())
+                                    .unwrap_or_else(|| format!("skill {}", skill_id));
+
+                                state.with_mut(|cast_state, changed| {
+                                    cast_state.start(skill_name, Duration::from_millis(duration as u64));
+                                    changed();
+                                });
+                            }
+                        }
+                        NetworkEvent::CastCancelled(entity_id) => {
+                            let state = match entity_id == entities[0].get_entity_id() {
+                                true => Some(&mut cast_state),
+                                false => (current_target == Some(entity_id)).then_some(&mut target_cast),
+                            };
+
+                            if let Some(state) = state {
+                                state.with_mut(|cast_state, changed| {
+                                    cast_state.interrupt();
+                                    changed();
+                                });
+                            }
+                        }
+                        NetworkEvent::SkillCooldown(skill_id, duration) => {
+                            if duration > 0 {
+                                let skill_name = skills
+                                    .iter()
+                                    .find(|skill| skill.skill_id == skill_id)
+                                    .map(|skill| skill.skill_name.clone())
+                                    .unwrap_or_else(|| format!("skill {}", skill_id));
+                                // Skill icons are stored by their lowercase name, next to the item icons.
+                                let icon_path = format!("À¯ÀúÀÎÅÍÆäÀÌ½º\\item\\{}.bmp", skill_name.to_lowercase());
+                                let icon = texture_loader.get(&icon_path, &mut game_file_loader).ok();
+
+                                skill_cooldowns.with_mut(|skill_cooldowns, changed| {
+                                    skill_cooldowns.add(skill_id, skill_name, icon, Duration::from_millis(duration as u64));
+                                    changed();
+                                });
+                            }
+                        }
+                        NetworkEvent::TradeRequested(name) => {
+                            trade.start(name.clone());
+                            interface.open_window(&mut focus_state, &TradeRequestWindow::new(name));
+                        }
+                        NetworkEvent::TradeStarted => {
+                            if interface.window_exists(Some(TradeRequestWindow::WINDOW_CLASS)) {
+                                interface.close_window_with_class(&mut focus_state, TradeRequestWindow::WINDOW_CLASS);
+                            }
+
+                            interface.open_window(&mut focus_state, &TradeWindow::new(&trade));
+                        }
+                        NetworkEvent::TradeItemAdded { item_id, cards, amount } => {
+                            let resource_name = script_loader.get_item_resource_from_id(item_id);
+                            let full_path = format!("À¯ÀúÀÎÅÍÆäÀÌ½º\\item\\{}.bmp", resource_name);
+                            let texture = texture_loader.get(&full_path, &mut game_file_loader).unwrap();
+
+                            trade.add_partner_item(item_id, cards, amount, texture);
+                        }
+                        NetworkEvent::TradeZenyAdded(zeny) => {
+                            trade.add_partner_zeny(zeny);
+                            interface.open_window(&mut focus_state, &TradeWindow::new(&trade));
+                        }
+                        NetworkEvent::TradeItemAccepted(index) => {
+                            if let Some(item) = trade.accept_item(index) {
+                                player_inventory.remove_item(item.index, item.amount);
+                            }
+                        }
+                        NetworkEvent::TradeItemRejected(index) => trade.reject_item(index),
+                        NetworkEvent::TradeLocked { partner } => {
+                            trade.set_locked(partner);
+                            interface.open_window(&mut focus_state, &TradeWindow::new(&trade));
+                        }
+                        NetworkEvent::TradeEnded => {
+                            if interface.window_exists(Some(TradeWindow::WINDOW_CLASS)) {
+                                interface.close_window_with_class(&mut focus_state, TradeWindow::WINDOW_CLASS);
+                            }
+                        }
+                        NetworkEvent::UpdateMapType(map_type, flags) => {
+                            let new_pvp_mode = PvpMode::from_map_type(map_type, flags);
+
+                            if new_pvp_mode != pvp_mode {
+                                pvp_mode = new_pvp_mode;
+                                pvp_aggressive = false;
+
+                                match pvp_mode.allows_player_attacks() {
+                                    true => interface.open_window(&mut focus_state, &PvpConfirmationWindow::new(pvp_mode)),
+                                    false if interface.window_exists(Some(PvpConfirmationWindow::WINDOW_CLASS)) => {
+                                        interface.close_window_with_class(&mut focus_state, PvpConfirmationWindow::WINDOW_CLASS)
+                                    }
+                                    false => {}
+                                }
+                            }
+                        }
+                        NetworkEvent::PvpRanking { entity_id, ranking, total } => {
+                            let entity = entities.iter_mut().find(|entity| entity.get_entity_id() == entity_id);
+
+                            if let Some(entity) = entity {
+                                entity.set_pvp_rank(ranking, total);
+                            }
+                        }
+                        NetworkEvent::SkillTree(skill_information) => {
+                            skills = skill_information;
+
+                            if interface.window_exists(Some(SkillTreeWindow::WINDOW_CLASS)) {
+                                interface.reopen_window(&mut focus_state, &SkillTreeWindow::new(skills.clone()));
+                            }
+                        }
+                        NetworkEvent::PetStatus(status) => {
+                            pet_status = Some(status);
+                            pet_status_changed = true;
+                        }
+                        NetworkEvent::UpdatePetIntimacy(intimacy) => {
+                            if let Some(status) = &mut pet_status {
+                                status.intimacy = intimacy;
+                                pet_status_changed = true;
+                            }
+                        }
+                        NetworkEvent::UpdatePetHunger(hunger) => {
+                            if let Some(status) = &mut pet_status {
+                                status.hunger = hunger;
+                                pet_status_changed = true;
+                            }
+                        }
+                        NetworkEvent::UpdatePetAccessory(accessory) => {
+                            if let Some(status) = &mut pet_status {
+                                status.accessory = accessory;
+                                pet_status_changed = true;
+                            }
+                        }
                     }
                 }
 
+                // the status window only shows a snapshot, so it has to be rebuilt
+                if pet_status_changed
+                    && let Some(status) = &pet_status
+                    && interface.window_exists(Some(PetStatusWindow::WINDOW_CLASS))
+                {
+                    interface.reopen_window(&mut focus_state, &PetStatusWindow::new(status.clone(), &script_loader));
+                }
+
+                if session_statistics_changed && interface.window_exists(Some(SessionStatsWindow::WINDOW_CLASS)) {
+                    interface.reopen_window(&mut focus_state, &SessionStatsWindow::new(&session_statistics));
+                }
+
+                #[cfg(feature = "debug")]
+                let user_events: Vec<UserEvent> = restored_events.drain(..).chain(user_events).collect();
+
+                // Hotbar slots stand for other events, so they are replaced before anything is
+                // handled.
+                let user_events: Vec<UserEvent> = user_events
+                    .into_iter()
+                    .filter_map(|event| match event {
+                        UserEvent::HotbarSlot(slot) => hotbar.activate(slot, &player_inventory.get_item_state().borrow()),
+                        event => Some(event),
+                    })
+                    .collect();
+
                 for event in user_events {
                     match event {
                         UserEvent::LogIn(username, password) => match networking_system.log_in(username, password) {
-                            Ok(character_selection_window) => {
+                            Ok(server_selection_window) => {
                                 // TODO: this will do one unnecessary restore_focus. check if
                                 // that will be problematic
                                 interface.close_window_with_class(&mut focus_state, LoginWindow::WINDOW_CLASS);
-                                interface.open_window(&mut focus_state, &character_selection_window);
+                                interface.open_window(&mut focus_state, &server_selection_window);
                             }
                             Err(message) => interface.open_window(&mut focus_state, &ErrorWindow::new(message)),
                         },
-                        UserEvent::LogOut => networking_system.log_out().unwrap(),
+                        UserEvent::SelectCharacterServer(index) => {
+                            interface.close_window_with_class(&mut focus_state, ServerSelectionWindow::WINDOW_CLASS);
+
+                            match networking_system.select_character_server(index, character_previews.clone()) {
+                                Ok(character_selection_window) => {
+                                    window_status.set_character(None);
+                                    interface.open_window(&mut focus_state, &character_selection_window);
+                                }
+                                Err(message) => {
+                                    let login_window = LoginWindow::new(networking_system.get_login_settings().clone());
+                                    interface.open_window(&mut focus_state, &login_window);
+                                    interface.open_window(&mut focus_state, &ErrorWindow::new(message));
+                                }
+                            }
+                        }
+                        UserEvent::CancelServerSelection => {
+                            networking_system.disconnect_from_login_server();
+                            window_status.set_character(None);
+                            interface.close_window_with_class(&mut focus_state, ServerSelectionWindow::WINDOW_CLASS);
+
+                            let login_window = LoginWindow::new(networking_system.get_login_settings().clone());
+                            interface.open_window(&mut focus_state, &login_window);
+                        }
+                        UserEvent::LogOut => {
+                            networking_system.log_out().unwrap();
+                            window_status.set_character(None);
+                        }
                         UserEvent::Exit => *control_flow = ControlFlow::Exit,
                         UserEvent::ToggleRemeberUsername => networking_system.toggle_remember_username(),
                         UserEvent::ToggleRemeberPassword => networking_system.toggle_remember_password(),
+                        UserEvent::SetPasswordEncryption(password_encryption) => {
+                            networking_system.set_password_encryption(password_encryption)
+                        }
                         UserEvent::CameraZoom(factor) => player_camera.soft_zoom(factor),
                         UserEvent::CameraRotate(factor) => player_camera.soft_rotate(factor),
+                        UserEvent::CameraTilt(factor) => player_camera.soft_tilt(factor),
+                        UserEvent::CameraPitch(factor) => player_camera.soft_pitch(factor),
+                        UserEvent::ToggleCameraMode => {
+                            player_camera.toggle_mode();
+
+                            let text = match player_camera.get_mode() {
+                                CameraMode::Fixed => "switched to the fixed camera",
+                                CameraMode::Free => "switched to the free camera",
+                            };
+                            let message = ChatMessage::new(text.to_string(), Color::rgb(220, 170, 220), ChatChannel::Client);
+                            chat_messages.borrow_mut().push(message);
+                        }
+                        UserEvent::SaveCameraPreset(slot) => {
+                            camera_presets.set(slot, player_camera.get_preset());
+
+                            let text = format!("saved camera preset {} (recall with F{})", slot + 1, slot + 1);
+                            let message = ChatMessage::new(text, Color::rgb(220, 170, 220), ChatChannel::Client);
+                            chat_messages.borrow_mut().push(message);
+                        }
+                        UserEvent::RecallCameraPreset(slot) => {
+                            if let Some(preset) = camera_presets.get(slot) {
+                                player_camera.apply_preset(preset);
+                            }
+                        }
                         UserEvent::ToggleFrameLimit => {
                             graphics_settings.toggle_frame_limit();
                             swapchain_holder.set_frame_limit(graphics_settings.frame_limit);
@@ -585,20 +1457,211 @@ fn main() {
                             // recreating the swapchain, so we need to render it again
                             interface.schedule_rerender();
                         }
-                        UserEvent::ToggleShowInterface => graphics_settings.toggle_show_interface(),
+                        UserEvent::ToggleShowInterface => graphics_settings.toggle_show_interface(),
+                        UserEvent::ToggleShowEntityInfo => graphics_settings.toggle_show_entity_info(),
+                        UserEvent::SetObjectFadeOpacity(opacity) => graphics_settings.object_fade_opacity = opacity,
+                        UserEvent::ToggleHideRoofs => graphics_settings.toggle_hide_roofs(),
+                        UserEvent::SetEntityShadowMode(mode) => graphics_settings.entity_shadows = mode,
+                        UserEvent::SetEffectQuality(quality) => {
+                            graphics_settings.effect_quality = quality;
+                            particle_holder.set_budget(quality.budget());
+                        }
+                        UserEvent::ToggleCustomDecorations => {
+                            window_settings.custom_decorations = !window_settings.custom_decorations;
+                            surface_window(&surface).set_decorations(!window_settings.custom_decorations);
+
+                            match window_settings.custom_decorations {
+                                true => interface.open_window(&mut focus_state, &TitleBarWindow::default()),
+                                false => interface.close_window_with_class(&mut focus_state, TitleBarWindow::WINDOW_CLASS),
+                            }
+                        }
+                        UserEvent::DragWindow => {
+                            // The platform takes over the mouse until the window is dropped, so the button
+                            // release is never seen.
+                            if surface_window(&surface).drag_window().is_ok() {
+                                input_system.reset();
+                            }
+                        }
+                        UserEvent::MinimizeWindow => surface_window(&surface).set_minimized(true),
+                        UserEvent::ToggleMaximizeWindow => {
+                            let window = surface_window(&surface);
+                            window.set_maximized(!window.is_maximized());
+                        }
+                        UserEvent::SetPreferredDevice(device_id) => {
+                            device_settings.preferred_device = device_id;
+                            interface.reopen_window(
+                                &mut focus_state,
+                                &GraphicsSettingsWindow::new(
+                                    interface.get_hud_edit_mode_state(),
+                                    available_devices.clone(),
+                                    device_settings.preferred_device,
+                                    texture_compression_settings.compression,
+                                    texture_compression_settings.quality,
+                                ),
+                            );
+                        }
+                        UserEvent::SetTextureCompression(compression) => {
+                            texture_compression_settings.compression = compression;
+                            interface.reopen_window(
+                                &mut focus_state,
+                                &GraphicsSettingsWindow::new(
+                                    interface.get_hud_edit_mode_state(),
+                                    available_devices.clone(),
+                                    device_settings.preferred_device,
+                                    texture_compression_settings.compression,
+                                    texture_compression_settings.quality,
+                                ),
+                            );
+                        }
+                        UserEvent::SetCompressionQuality(quality) => {
+                            texture_compression_settings.quality = quality;
+                            interface.reopen_window(
+                                &mut focus_state,
+                                &GraphicsSettingsWindow::new(
+                                    interface.get_hud_edit_mode_state(),
+                                    available_devices.clone(),
+                                    device_settings.preferred_device,
+                                    texture_compression_settings.compression,
+                                    texture_compression_settings.quality,
+                                ),
+                            );
+                        }
+                        UserEvent::SetRenderScale(render_scale) => {
+                            graphics_settings.render_scale = render_scale;
+                            swapchain_holder.invalidate_swapchain();
+
+                            // for some reason the interface buffer becomes messed up when
+                            // recreating the swapchain, so we need to render it again
+                            interface.schedule_rerender();
+                        }
+                        UserEvent::SetWindowOpacity(window_class, opacity) => interface.set_window_opacity(&window_class, opacity),
+                        UserEvent::ToggleWindowClickThrough(window_class) => interface.toggle_window_click_through(&window_class),
+                        UserEvent::MoveWindowsOnScreen => interface.move_windows_on_screen(),
                         UserEvent::OpenMenuWindow => interface.open_window(&mut focus_state, &MenuWindow::default()),
                         UserEvent::OpenInventoryWindow => {
-                            interface.open_window(&mut focus_state, &InventoryWindow::new(player_inventory.get_item_state()))
+                            interface.open_window(&mut focus_state, &InventoryWindow::new(
+                                    player_inventory.get_item_state(),
+                                    player_inventory.get_highlighted_item_state(),
+                                ),)
                         }
+                        UserEvent::OpenSkillTreeWindow => interface.open_window(&mut focus_state, &SkillTreeWindow::new(skills.clone())),
                         UserEvent::OpenEquipmentWindow => {
-                            interface.open_window(&mut focus_state, &EquipmentWindow::new(player_inventory.get_item_state()))
+                            interface.open_window(
+                                &mut focus_state,
+                                &EquipmentWindow::new(player_inventory.get_item_state(), preview_target.get_image()),
+                            )
                         }
                         UserEvent::OpenGraphicsSettingsWindow => {
-                            interface.open_window(&mut focus_state, &GraphicsSettingsWindow::default())
+                            interface.open_window(
+                                &mut focus_state,
+                                &GraphicsSettingsWindow::new(
+                                    interface.get_hud_edit_mode_state(),
+                                    available_devices.clone(),
+                                    device_settings.preferred_device,
+                                    texture_compression_settings.compression,
+                                    texture_compression_settings.quality,
+                                ),
+                            )
+                        }
+                        UserEvent::OpenControlsWindow => interface.open_window(
+                            &mut focus_state,
+                            &ControlsWindow::new(input_system.get_key_bindings(), input_system.get_rebinding()),
+                        ),
+                        UserEvent::StartRebinding(action) => {
+                            input_system.start_rebinding(action);
+                            interface.reopen_window(
+                                &mut focus_state,
+                                &ControlsWindow::new(input_system.get_key_bindings(), input_system.get_rebinding()),
+                            );
+                        }
+                        UserEvent::KeyBindingsChanged => {
+                            if interface.window_exists(Some(ControlsWindow::WINDOW_CLASS)) {
+                                interface.reopen_window(
+                                    &mut focus_state,
+                                    &ControlsWindow::new(input_system.get_key_bindings(), input_system.get_rebinding()),
+                                );
+                            }
+                        }
+                        UserEvent::OpenAudioSettingsWindow => {
+                            interface.open_window(&mut focus_state, &AudioSettingsWindow::new(audio_engine.get_settings()))
+                        }
+                        UserEvent::OpenChatSettingsWindow => interface.open_window(
+                            &mut focus_state,
+                            &ChatSettingsWindow::new(
+                                chat_settings.show_timestamps.clone(),
+                                chat_settings.log_to_disk.clone(),
+                                chat_settings.afk_minutes.clone(),
+                            ),
+                        ),
+                        UserEvent::OpenChatLogDirectory => ChatLog::open_directory(),
+                        UserEvent::OpenSessionStatsWindow => {
+                            interface.open_window(&mut focus_state, &SessionStatsWindow::new(&session_statistics))
+                        }
+                        UserEvent::ResetSessionStatistics => {
+                            session_statistics.reset();
+                            interface.reopen_window(&mut focus_state, &SessionStatsWindow::new(&session_statistics));
+                        }
+                        UserEvent::OpenMinimapWindow => interface.open_window(
+                            &mut focus_state,
+                            &MinimapWindow::new(minimap_state.new_remote(), waypoint.new_remote()),
+                        ),
+                        UserEvent::OpenWorldMapWindow => interface.open_window(
+                            &mut focus_state,
+                            &WorldMapWindow::new(&world_map_database, current_map.new_remote(), waypoint.new_remote()),
+                        ),
+                        UserEvent::OpenClockWindow => interface.open_window(
+                            &mut focus_state,
+                            &ClockWindow::new(
+                                clock_settings.server_offset_minutes.new_remote(),
+                                clock_settings.alarms.new_remote(),
+                            ),
+                        ),
+                        UserEvent::OpenClockSettingsWindow => {
+                            interface.open_window(&mut focus_state, &ClockSettingsWindow::new(&clock_settings))
                         }
-                        UserEvent::OpenAudioSettingsWindow => interface.open_window(&mut focus_state, &AudioSettingsWindow::default()),
-                        UserEvent::ReloadTheme => interface.reload_theme(),
+                        UserEvent::ChangeServerTimeOffset(minutes) => {
+                            clock_settings.shift_server_offset(minutes);
+                            interface.reopen_window(&mut focus_state, &ClockSettingsWindow::new(&clock_settings));
+                        }
+                        UserEvent::AddClockAlarm(alarm) => {
+                            clock_settings.alarms.push(alarm);
+                            interface.reopen_window(&mut focus_state, &ClockSettingsWindow::new(&clock_settings));
+                        }
+                        UserEvent::RemoveClockAlarm(index) => {
+                            clock_settings.remove_alarm(index);
+                            interface.reopen_window(&mut focus_state, &ClockSettingsWindow::new(&clock_settings));
+                        }
+                        UserEvent::ExportSessionStatistics => {
+                            let message = match session_statistics.export() {
+                                Ok(path) => ChatMessage::new(
+                                    format!("session statistics saved to {}", path),
+                                    Color::rgb(100, 255, 100),
+                                    ChatChannel::Client,
+                                ),
+                                Err(error) => ChatMessage::new(
+                                    format!("failed to save session statistics: {}", error),
+                                    Color::rgb(255, 100, 100),
+                                    ChatChannel::Client,
+                                ),
+                            };
+
+                            chat_messages.borrow_mut().push(message);
+                        }
+                        UserEvent::OpenChatTabWindow => interface.open_window(
+                            &mut focus_state,
+                            &ChatTabWindow::new(chat_tabs.tabs.clone(), chat_tabs.selected.clone()),
+                        ),
+                        UserEvent::ReloadTheme => interface.reload_theme(&mut game_file_loader, &mut texture_loader),
                         UserEvent::SaveTheme => interface.save_theme(),
+                        UserEvent::ExportTheme => {
+                            let export_file = interface.export_theme();
+                            let message = ChatMessage::new(
+                                format!("theme exported to {}", export_file),
+                                Color::rgb(100, 255, 100),
+                                ChatChannel::Client,
+                            );
+                            chat_messages.borrow_mut().push(message);
+                        }
                         UserEvent::SelectCharacter(character_slot) => {
                             match networking_system.select_character(character_slot, &chat_messages) {
                                 Ok((map_name, player_position, character_information, client_tick)) => {
@@ -606,11 +1669,80 @@ fn main() {
                                     // that will be problematic
                                     interface.close_window_with_class(&mut focus_state, CharacterSelectionWindow::WINDOW_CLASS);
                                     interface.open_window(&mut focus_state, &CharacterOverviewWindow::new());
-                                    interface.open_window(&mut focus_state, &ChatWindow::new(chat_messages.clone(), font_loader.clone()));
+                                    interface.open_window(
+                                        &mut focus_state,
+                                        &StatusBarWindow::new(player_health_points.new_remote(), player_spell_points.new_remote()),
+                                    );
+
+                                    base_experience.with_mut(|tracker, changed| {
+                                        tracker.reset();
+                                        changed();
+                                    });
+                                    session_statistics = SessionStatistics::default();
+                                    job_experience.with_mut(|tracker, changed| {
+                                        tracker.reset();
+                                        changed();
+                                    });
+                                    interface.open_window(
+                                        &mut focus_state,
+                                        &ExperienceBarWindow::new(base_experience.new_remote(), job_experience.new_remote()),
+                                    );
+                                    interface.open_window(&mut focus_state, &CastBarWindow::new(cast_state.new_remote()));
+                                    interface.open_window(&mut focus_state, &CooldownWindow::new(skill_cooldowns.new_remote()));
+                                    interface.open_window(
+                                        &mut focus_state,
+                                        &TargetFrameWindow::new(target_status.new_remote(), target_cast.new_remote()),
+                                    );
+                                    chat_log.set_character(&character_information.name);
+                                    window_status.set_character(Some(&character_information.name));
+                                    chat_tabs.set_character(&character_information.name);
+                                    hotbar.set_character(&character_information.name);
+
+                                    interface.open_window(
+                                        &mut focus_state,
+                                        &HotbarWindow::new(
+                                            hotbar.get_slot_state().new_remote(),
+                                            player_inventory.get_item_state().new_remote(),
+                                        ),
+                                    );
+
+                                    interface.open_window(
+                                        &mut focus_state,
+                                        &ChatWindow::new(
+                                            chat_messages.clone(),
+                                            font_loader.clone(),
+                                            chat_settings.show_timestamps.clone(),
+                                            chat_tabs.tabs.clone(),
+                                            chat_tabs.selected.clone(),
+                                        ),
+                                    );
+
+                                    #[cfg(feature = "status_server")]
+                                    if let Some(status_server) = &status_server {
+                                        status_server.change_map(&map_name);
+                                    }
+
+                                    map_preloader.enter_map(&map_name, None);
+                                    sprite_preloader.enter_map(
+                                        &map_name,
+                                        &mut game_file_loader,
+                                        &mut sprite_loader,
+                                        &mut action_loader,
+                                        &script_loader,
+                                    );
 
+                                    audio_engine.play_map_music(&mut game_file_loader, &map_name);
                                     map = map_loader
-                                        .get(map_name, &mut game_file_loader, &mut model_loader, &mut texture_loader)
+                                        .get(map_name.clone(), &mut game_file_loader, &mut model_loader, &mut texture_loader)
                                         .unwrap();
+                                    map_loader.retire_maps(|map_name| map_preloader.is_nearby(map_name));
+
+                                    minimap_state.with_mut(|minimap_state, changed| {
+                                        minimap_state.load_map(&map, &map_name, &mut game_file_loader, &mut texture_loader);
+                                        changed();
+                                    });
+                                    current_map.set(map_name.clone());
+                                    party_member_positions.clear();
 
                                     let player = Player::new(
                                         &mut game_file_loader,
@@ -622,12 +1754,18 @@ fn main() {
                                         player_position,
                                         client_tick,
                                     );
+                                    player_health_points.set(player.get_health_points());
+                                    player_spell_points.set(player.get_spell_points());
                                     let player = Entity::Player(player);
 
                                     player_camera.set_focus_point(player.get_position());
                                     entities.push(player);
 
+                                    #[cfg(feature = "debug")]
+                                    snapshot_entities.clear();
+
                                     particle_holder.clear();
+                                    audio_engine.clear_ambient_sounds();
                                     networking_system.map_loaded();
                                     // TODO: this is just a workaround until i find a better solution to make the
                                     // cursor always look correct.
@@ -655,7 +1793,18 @@ fn main() {
                             interface.handle_result(&mut focus_state, networking_system.switch_character_slot(destination_slot))
                         }
                         UserEvent::RequestPlayerMove(destination) => {
-                            if !entities.is_empty() {
+                            if let Some(placement) = skill_placement.take() {
+                                match placement.is_valid_cell(&map, entities[0].get_grid_position(), destination) {
+                                    true => {
+                                        networking_system.request_ground_skill_use(placement.skill_id, placement.skill_level, destination)
+                                    }
+                                    false => {
+                                        let text = "the skill can not be used there".to_string();
+                                        let message = ChatMessage::new(text, Color::rgb(255, 100, 100), ChatChannel::Client);
+                                        chat_messages.borrow_mut().push(message);
+                                    }
+                                }
+                            } else if !entities.is_empty() {
                                 networking_system.request_player_move(destination)
                             }
                         }
@@ -665,19 +1814,287 @@ fn main() {
                             if let Some(entity) = entity {
                                 match entity.get_entity_type() {
                                     EntityType::Npc => networking_system.start_dialog(entity_id),
-                                    EntityType::Monster => networking_system.request_player_attack(entity_id),
+                                    EntityType::Monster if capturing_pet => {
+                                        networking_system.request_pet_capture(entity_id);
+                                        capturing_pet = false;
+                                    }
+                                    EntityType::Monster => {
+                                        networking_system.request_player_attack(entity_id);
+                                        current_target = Some(entity_id);
+                                    }
+                                    EntityType::Player if pvp_mode.allows_player_attacks() && pvp_aggressive => {
+                                        networking_system.request_player_attack(entity_id);
+                                        current_target = Some(entity_id);
+                                    }
+                                    EntityType::Player if entity.get_chat_room().is_some() => {
+                                        let chat_room = entity.get_chat_room().unwrap().clone();
+                                        interface.open_window(&mut focus_state, &ChatRoomJoinWindow::new(chat_room));
+                                    }
                                     EntityType::Warp => networking_system.request_player_move(entity.get_grid_position()),
                                     _ => {} // TODO: add other interactions
                                 }
                             }
                         }
+                        UserEvent::OpenEntityContextMenu(entity_id) => {
+                            let is_player = entities.first().map(|player| player.get_entity_id()) == Some(entity_id);
+                            let entity = entities.iter().find(|entity| entity.get_entity_id() == entity_id);
+
+                            if let Some(entity) = entity
+                                && !is_player
+                            {
+                                let entries = match entity.get_entity_type() {
+                                    EntityType::Npc => vec![ContextMenuEntry::action("talk", UserEvent::RequestPlayerInteract(entity_id))],
+                                    EntityType::Monster => {
+                                        vec![ContextMenuEntry::action("attack", UserEvent::RequestPlayerInteract(entity_id))]
+                                    }
+                                    EntityType::Player => vec![ContextMenuEntry::action("trade", UserEvent::RequestTrade(entity_id))],
+                                    _ => Vec::new(),
+                                };
+
+                                interface.open_context_menu(&mut focus_state, input_system.get_mouse_position(), entries);
+                            }
+                        }
+                        UserEvent::RequestTrade(entity_id) => {
+                            let name = entities
+                                .iter()
+                                .find(|entity| entity.get_entity_id() == entity_id)
+                                .and_then(|entity| entity.get_details())
+                                .map(|details| details.split('#').next().unwrap().to_owned())
+                                .unwrap_or_default();
+
+                            trade.start(name);
+                            networking_system.request_trade(entity_id);
+                        }
+                        UserEvent::RespondToTradeRequest(accept) => {
+                            interface.close_window_with_class(&mut focus_state, TradeRequestWindow::WINDOW_CLASS);
+                            networking_system.respond_to_trade_request(accept);
+                        }
+                        UserEvent::LockTrade => networking_system.lock_trade(),
+                        UserEvent::CancelTrade => networking_system.cancel_trade(),
+                        UserEvent::CommitTrade => networking_system.commit_trade(),
                         UserEvent::RequestWarpToMap(map_name, position) => networking_system.request_warp_to_map(map_name, position),
                         UserEvent::SendMessage(message) => {
-                            networking_system.send_message(message);
+                            match parse_whisper_command(&message) {
+                                Some(Ok(WhisperCommand::Open(partner))) => interface.open_window(
+                                    &mut focus_state,
+                                    &WhisperWindow::new(
+                                        partner,
+                                        chat_messages.clone(),
+                                        font_loader.clone(),
+                                        chat_settings.show_timestamps.clone(),
+                                    ),
+                                ),
+                                Some(Ok(WhisperCommand::Send(partner, message))) => {
+                                    let message = networking_system.send_whisper(partner, message);
+
+                                    if *chat_settings.log_to_disk.borrow() {
+                                        chat_log.write(&message);
+                                    }
+
+                                    chat_messages.borrow_mut().push(message);
+                                }
+                                Some(Err(error)) => {
+                                    let message = ChatMessage::new(error, Color::rgb(255, 100, 100), ChatChannel::Client);
+                                    chat_messages.borrow_mut().push(message);
+                                }
+                                None if message == "/chatlog" => ChatLog::open_directory(),
+                                None if message == "/windows" => interface.move_windows_on_screen(),
+                                None if message == "/afk" || message.starts_with("/afk ") => {
+                                    let text = message["/afk".len()..].trim();
+
+                                    if !text.is_empty() {
+                                        chat_settings.afk_message.set(text.to_owned());
+                                    }
+
+                                    afk_state.set_away();
+
+                                    let text = format!("you are now away: {}", chat_settings.afk_message.borrow());
+                                    let message = ChatMessage::new(text, Color::rgb(220, 170, 220), ChatChannel::Client);
+                                    chat_messages.borrow_mut().push(message);
+                                }
+                                None if message == "/pos" || message.starts_with("/pos ") => {
+                                    if let Some(map_name) = map_preloader.current_map() {
+                                        let link = MapLink {
+                                            map_name: map_name.to_owned(),
+                                            position: entities[0].get_grid_position(),
+                                        };
+
+                                        let text = message["/pos".len()..].trim();
+                                        let message = match text.is_empty() {
+                                            true => link.to_string(),
+                                            false => format!("{} {}", text, link),
+                                        };
+
+                                        networking_system.send_message(message);
+                                    }
+                                }
+                                // Same prefixes as in the official client.
+                                None if message.starts_with('%') => {
+                                    networking_system.send_party_message(message[1..].trim_start().to_owned())
+                                }
+                                None if message.starts_with('$') => {
+                                    networking_system.send_guild_message(message[1..].trim_start().to_owned())
+                                }
+                                None => networking_system.send_message(message),
+                            }
+
                             // TODO: maybe find a better solution for unfocusing the message box if
                             // this becomes problematic
                             focus_state.remove_focus();
                         }
+                        UserEvent::EnterChatRoom(room_id, password) => networking_system.enter_chat_room(room_id, password),
+                        UserEvent::LeaveChatRoom => networking_system.leave_chat_room(),
+                        UserEvent::SendWhisper(partner, message) => {
+                            let message = networking_system.send_whisper(partner, message);
+
+                            if *chat_settings.log_to_disk.borrow() {
+                                chat_log.write(&message);
+                            }
+
+                            chat_messages.borrow_mut().push(message);
+                        }
+                        UserEvent::FollowChatLink(ChatLink::Map(link)) => {
+                            // Links only mark the spot, walking there is up to the player. The map that
+                            // shows the waypoint is opened if it isn't already.
+                            let on_current_map = map_preloader.current_map() == Some(link.map_name.as_str());
+                            let text = format!("waypoint set to {}", link);
+                            let message = ChatMessage::new(text, Color::rgb(220, 220, 220), ChatChannel::Client);
+                            chat_messages.borrow_mut().push(message);
+                            waypoint.set(Some(link));
+
+                            if on_current_map && !interface.window_exists(Some(MinimapWindow::WINDOW_CLASS)) {
+                                interface.open_window(
+                                    &mut focus_state,
+                                    &MinimapWindow::new(minimap_state.new_remote(), waypoint.new_remote()),
+                                );
+                            } else if !on_current_map && !interface.window_exists(Some(WorldMapWindow::WINDOW_CLASS)) {
+                                interface.open_window(
+                                    &mut focus_state,
+                                    &WorldMapWindow::new(&world_map_database, current_map.new_remote(), waypoint.new_remote()),
+                                );
+                            }
+                        }
+                        UserEvent::SearchMonsters(query) => {
+                            interface.reopen_window(
+                                &mut focus_state,
+                                &MonsterInfoWindow::new(&monster_database, &script_loader, query, None),
+                            );
+                        }
+                        UserEvent::OpenMonsterInfo(monster_id) | UserEvent::FollowChatLink(ChatLink::Monster(monster_id)) => {
+                            interface.reopen_window(
+                                &mut focus_state,
+                                &MonsterInfoWindow::new(&monster_database, &script_loader, String::new(), Some(monster_id)),
+                            );
+                        }
+                        UserEvent::SearchItems(search) => {
+                            let window = ItemSearchWindow::new(
+                                search,
+                                &script_loader,
+                                &mut texture_loader,
+                                &mut game_file_loader,
+                                &monster_database,
+                            );
+
+                            interface.reopen_window(&mut focus_state, &window);
+                        }
+                        UserEvent::SelectRefineItem(item_index) => networking_system.request_refine_materials(item_index),
+                        UserEvent::RefineItem(item_index, material_id) => networking_system.request_refine(item_index, material_id),
+                        UserEvent::CloseRefineWindow => {
+                            networking_system.close_refine_window();
+                            player_inventory.highlight_items(Vec::new());
+                            interface.close_window_with_class(&mut focus_state, RefineWindow::WINDOW_CLASS);
+                        }
+                        UserEvent::CraftItem(item_id, material_ids) => {
+                            networking_system.request_craft(item_id, material_ids);
+                            player_inventory.highlight_items(Vec::new());
+                            interface.close_window_with_class(&mut focus_state, CraftingWindow::WINDOW_CLASS);
+                        }
+                        UserEvent::SelectCard(card_index) => networking_system.request_card_targets(card_index),
+                        UserEvent::SelectCardTarget(card_index, equipment_index) => {
+                            let window = CardCompoundWindow::new(
+                                &player_inventory.get_item_state().borrow(),
+                                &script_loader,
+                                card_index,
+                                card_targets.clone(),
+                                Some(equipment_index),
+                            );
+
+                            interface.reopen_window(&mut focus_state, &window);
+                        }
+                        UserEvent::InsertCard(card_index, equipment_index) => {
+                            networking_system.insert_card(card_index, equipment_index);
+                            interface.close_window_with_class(&mut focus_state, CardCompoundWindow::WINDOW_CLASS);
+                        }
+                        UserEvent::UseItem(item_index) => networking_system.request_item_use(item_index),
+                        UserEvent::DropItem(item_index, amount) => networking_system.request_item_drop(item_index, amount),
+                        UserEvent::SelectPetEgg(item_index) => {
+                            networking_system.select_pet_egg(item_index);
+                            interface.close_window_with_class(&mut focus_state, PetEggWindow::WINDOW_CLASS);
+                        }
+                        UserEvent::OpenPetStatusWindow => match &pet_status {
+                            Some(status) => {
+                                networking_system.send_pet_command(PetCommand::Information);
+                                interface.open_window(&mut focus_state, &PetStatusWindow::new(status.clone(), &script_loader));
+                            }
+                            None => {
+                                let text = "you have no pet".to_string();
+                                let message = ChatMessage::new(text, Color::rgb(255, 100, 100), ChatChannel::Client);
+                                chat_messages.borrow_mut().push(message);
+                            }
+                        },
+                        UserEvent::TargetNextMonster => match next_target(&entities, current_target) {
+                            Some(entity_id) => current_target = Some(entity_id),
+                            // Outside of the game there is nothing to target, so tab moves the focus into
+                            // the interface instead.
+                            None if entities.is_empty() => interface.first_focused_element(&mut focus_state),
+                            None => {}
+                        },
+                        // A ground skill that is being placed is canceled before the target is cleared.
+                        UserEvent::SetPvpAggressive(aggressive) => {
+                            pvp_aggressive = aggressive;
+                            interface.close_window_with_class(&mut focus_state, PvpConfirmationWindow::WINDOW_CLASS);
+                        }
+                        UserEvent::ClearTarget => match skill_placement.is_some() {
+                            true => skill_placement = None,
+                            false => current_target = None,
+                        },
+                        UserEvent::UseSkill(skill_id, skill_level) => {
+                            let skill = skills.iter().find(|skill| skill.skill_id == skill_id);
+
+                            let skill_target = skill.map(|skill| SkillTarget::from_skill_type(skill.skill_type));
+
+                            let target_id = match skill_target {
+                                Some(SkillTarget::Enemy) => current_target,
+                                Some(SkillTarget::Caster | SkillTarget::Support) => Some(entities[0].get_entity_id()),
+                                // Ground skills are used once the player picks a cell.
+                                Some(SkillTarget::Ground) => {
+                                    skill_placement = skill.map(SkillPlacement::new);
+                                    None
+                                }
+                                Some(SkillTarget::Passive) | None => None,
+                            };
+
+                            match target_id {
+                                Some(target_id) => networking_system.request_skill_use(skill_id, skill_level, target_id),
+                                None if skill_target == Some(SkillTarget::Ground) => {}
+                                None => {
+                                    let text = "this skill can not be used right now".to_string();
+                                    let message = ChatMessage::new(text, Color::rgb(255, 100, 100), ChatChannel::Client);
+                                    chat_messages.borrow_mut().push(message);
+                                }
+                            }
+                        }
+                        // Replaced by the event of the slot before the events are handled.
+                        UserEvent::HotbarSlot(_) => {}
+                        UserEvent::SetHotbarSlot(slot, entry) => hotbar.set_slot(slot, entry),
+                        UserEvent::SendPetCommand(command) => {
+                            networking_system.send_pet_command(command);
+
+                            if let PetCommand::ReturnToEgg = command {
+                                pet_status = None;
+                                interface.close_window_with_class(&mut focus_state, PetStatusWindow::WINDOW_CLASS);
+                            }
+                        }
                         UserEvent::NextDialog(npc_id) => networking_system.next_dialog(npc_id),
                         UserEvent::CloseDialog(npc_id) => {
                             networking_system.close_dialog(npc_id);
@@ -697,11 +2114,21 @@ fn main() {
                             (ItemSource::Equipment { .. }, ItemSource::Inventory) => {
                                 networking_system.request_item_unequip(item_move.item.index);
                             }
+                            (ItemSource::Inventory, ItemSource::Trade) => {
+                                let (index, amount) = (item_move.item.index, item_move.item.amount);
+
+                                if trade.offer_item(item_move.item) {
+                                    networking_system.add_trade_item(index, amount);
+                                }
+                            }
+                            (_, ItemSource::Hotbar { slot }) => hotbar.set_slot(slot, Some(HotbarEntry::from_item(&item_move.item))),
                             _ => {}
                         },
                         #[cfg(feature = "debug")]
                         UserEvent::ToggleFrustumCulling => render_settings.toggle_frustum_culling(),
                         #[cfg(feature = "debug")]
+                        UserEvent::ToggleCacheWindows => render_settings.toggle_cache_windows(),
+                        #[cfg(feature = "debug")]
                         UserEvent::ToggleShowBoundingBoxes => render_settings.toggle_show_bounding_boxes(),
                         #[cfg(feature = "debug")]
                         UserEvent::OpenMarkerDetails(marker_identifier) => {
@@ -714,6 +2141,20 @@ fn main() {
                         #[cfg(feature = "debug")]
                         UserEvent::OpenMapsWindow => interface.open_window(&mut focus_state, &MapsWindow::default()),
                         #[cfg(feature = "debug")]
+                        UserEvent::TeleportDebugCamera(fraction) => {
+                            let map_size = map.get_size();
+                            let tile = Vector2::new(
+                                ((fraction.x * map_size.x as f32) as usize).min(map_size.x.saturating_sub(1)),
+                                ((fraction.y * map_size.y as f32) as usize).min(map_size.y.saturating_sub(1)),
+                            );
+
+                            debug_camera.teleport(map.get_world_position(tile));
+
+                            if !render_settings.use_debug_camera {
+                                render_settings.toggle_use_debug_camera();
+                            }
+                        }
+                        #[cfg(feature = "debug")]
                         UserEvent::OpenTimeWindow => interface.open_window(&mut focus_state, &TimeWindow::default()),
                         #[cfg(feature = "debug")]
                         UserEvent::SetDawn => game_timer.set_day_timer(0.0),
@@ -724,13 +2165,58 @@ fn main() {
                         #[cfg(feature = "debug")]
                         UserEvent::SetMidnight => game_timer.set_day_timer(-std::f32::consts::FRAC_PI_2),
                         #[cfg(feature = "debug")]
-                        UserEvent::OpenThemeViewerWindow => interface.open_theme_viewer_window(&mut focus_state),
+                        UserEvent::OpenThemeEditorWindow => interface.open_theme_editor_window(&mut focus_state),
                         #[cfg(feature = "debug")]
                         UserEvent::OpenProfilerWindow => interface.open_window(&mut focus_state, &ProfilerWindow::default()),
+                        #[cfg(feature = "debug")]
+                        UserEvent::OpenRenderStatisticsWindow => {
+                            interface.open_window(&mut focus_state, &RenderStatisticsWindow::new(memory_allocator.clone()))
+                        }
                         #[cfg(feature = "debug_network")]
                         UserEvent::OpenPacketWindow => {
                             interface.open_window(&mut focus_state, &PacketWindow::new(networking_system.packets()))
                         }
+                        #[cfg(feature = "debug_network")]
+                        UserEvent::OpenNetworkStatisticsWindow => interface.open_window(
+                            &mut focus_state,
+                            &NetworkStatisticsWindow::new(networking_system.get_statistics()),
+                        ),
+                        #[cfg(feature = "debug")]
+                        UserEvent::OpenInspectorWindow => {
+                            interface.open_window(&mut focus_state, &InspectorWindow::new(interface.get_inspector_lines()))
+                        }
+                        #[cfg(feature = "debug")]
+                        UserEvent::PickInspectedElement => interface.start_picking_element(),
+                        #[cfg(feature = "debug")]
+                        UserEvent::TweakInspectedElement(growth) => interface.tweak_inspected_element(growth),
+                        #[cfg(feature = "debug")]
+                        UserEvent::ResetInspectedElement => interface.reset_inspected_element(),
+                        #[cfg(feature = "debug")]
+                        UserEvent::SaveSnapshot => {
+                            let entities = entities
+                                .iter()
+                                .chain(snapshot_entities.iter())
+                                .filter(|entity| matches!(entity, Entity::Npc(_)))
+                                .map(|entity| SnapshotEntity {
+                                    job: entity.get_job(),
+                                    position: entity.get_grid_position(),
+                                    head_direction: entity.get_head_direction(),
+                                })
+                                .collect();
+
+                            let snapshot = ClientSnapshot {
+                                map_name: current_map.borrow().clone(),
+                                use_debug_camera: render_settings.use_debug_camera,
+                                debug_camera: debug_camera.get_state(),
+                                player_camera: player_camera.get_preset(),
+                                open_windows: interface.get_window_classes(),
+                                entities,
+                            };
+
+                            if let Err(message) = snapshot.save() {
+                                interface.open_window(&mut focus_state, &ErrorWindow::new(message));
+                            }
+                        }
                         #[cfg(feature = "debug")]
                         UserEvent::ToggleUseDebugCamera => render_settings.toggle_use_debug_camera(),
                         #[cfg(feature = "debug")]
@@ -811,33 +2297,218 @@ fn main() {
                     }
                 }
 
+                if interface.window_exists(Some(CharacterSelectionWindow::WINDOW_CLASS)) {
+                    let characters_changed = character_list.consume_changed();
+
+                    if characters_changed || character_preview_entities.len() != character_list.borrow().len() {
+                        character_preview_entities = character_list
+                            .borrow()
+                            .iter()
+                            .map(|character_information| {
+                                let player = Player::new(
+                                    &mut game_file_loader,
+                                    &mut sprite_loader,
+                                    &mut action_loader,
+                                    &script_loader,
+                                    &map,
+                                    character_information.clone(),
+                                    Vector2::new(0, 0),
+                                    game_timer.get_client_tick(),
+                                );
+                                let slot = character_information.character_number as usize;
+
+                                (slot, Entity::Player(player), preview_renderer.create_render_target())
+                            })
+                            .collect();
+
+                        let previews = character_preview_entities
+                            .iter()
+                            .map(|(slot, _, preview_target)| (*slot, preview_target.get_image()))
+                            .collect();
+                        character_previews.set(previews);
+                    }
+
+                    character_preview_entities
+                        .iter_mut()
+                        .for_each(|(_, entity, _)| entity.update(&map, delta_time as f32, game_timer.get_client_tick()));
+                } else if !character_preview_entities.is_empty() {
+                    character_preview_entities.clear();
+                    character_previews.clear();
+                }
+
                 let texture_fence = texture_loader.submit_load_buffer();
                 let sprite_fence = sprite_loader.submit_load_buffer();
 
                 particle_holder.update(delta_time as f32);
+                status_indicator_sprites.update(game_timer.get_client_tick());
+                map_loader.release_retired_map();
 
                 entities
                     .iter_mut()
                     .for_each(|entity| entity.update(&map, delta_time as f32, game_timer.get_client_tick()));
 
+                #[cfg(feature = "debug")]
+                snapshot_entities
+                    .iter_mut()
+                    .for_each(|entity| entity.update(&map, delta_time as f32, game_timer.get_client_tick()));
+
                 if !entities.is_empty() {
                     let player_position = entities[0].get_position();
                     player_camera.set_focus_point(player_position);
                     directional_shadow_camera.set_focus_point(player_position);
+
+                    if let Some(map_name) = map_preloader.update(entities[0].get_grid_position())
+                        && !map_loader.is_cached(&map_name)
+                    {
+                        map_preloader.preload(
+                            &thread_pool,
+                            map_name,
+                            &game_file_loader,
+                            &texture_loader,
+                            &memory_allocator,
+                            &queue,
+                        );
+                    }
+
+                    for (map_name, result, texture_cache) in map_preloader.loaded_maps() {
+                        texture_loader.merge_cache(texture_cache);
+
+                        match result {
+                            Ok(map) => map_loader.insert(map_name, map),
+                            Err(_message) => {
+                                #[cfg(feature = "debug")]
+                                print_debug!("failed to preload map: {}", _message);
+                            }
+                        }
+                    }
+
+                    audio_engine.update(
+                        &mut game_file_loader,
+                        delta_time as f32,
+                        map.sound_sources(),
+                        player_camera.focus_point(),
+                        player_camera.ground_view_direction(),
+                    );
+                }
+
+                #[cfg(feature = "status_server")]
+                if let Some(status_server) = &status_server
+                    && let Some(Entity::Player(player)) = entities.first()
+                {
+                    status_server.update(player, game_timer.last_frames_per_second());
+                }
+
+                if let Some(entity_id) = current_target
+                    && let Some(entity) = entities.iter_mut().find(|entity| entity.get_entity_id() == entity_id)
+                    && entity.are_details_unavailable()
+                {
+                    networking_system.request_entity_details(entity_id);
+                    entity.set_details_requested();
+                }
+
+                let new_target_status = current_target
+                    .and_then(|entity_id| entities.iter().find(|entity| entity.get_entity_id() == entity_id))
+                    .map(TargetStatus::from_entity);
+                // Targets that died or left the screen are no longer locked.
+                current_target = new_target_status.as_ref().map(|status| status.entity_id);
+
+                if *target_status.borrow() != new_target_status {
+                    let previous_target = target_status.borrow().as_ref().map(|status| status.entity_id);
+
+                    if previous_target != current_target {
+                        target_cast.set(CastState::default());
+                    }
+
+                    target_status.set(new_target_status);
+                }
+
+                if !entities.is_empty() && interface.window_exists(Some(MinimapWindow::WINDOW_CLASS)) {
+                    let focus_point = player_camera.focus_point();
+                    let player_position = Vector2::new(focus_point.x / 5.0, focus_point.z / 5.0);
+                    let markers = entities
+                        .iter()
+                        .skip(1)
+                        .filter_map(|entity| match entity.get_entity_type() {
+                            EntityType::Npc => Some((MinimapMarker::Npc, entity.get_grid_position())),
+                            EntityType::Warp => Some((MinimapMarker::Warp, entity.get_grid_position())),
+                            _ => None,
+                        })
+                        .chain(
+                            party_member_positions
+                                .values()
+                                .map(|position| (MinimapMarker::PartyMember, *position)),
+                        )
+                        .collect();
+
+                    minimap_state.with_mut(|minimap_state, changed| {
+                        if minimap_state.update(player_position, player_camera.ground_view_direction(), markers) {
+                            changed();
+                        }
+                    });
+                }
+
+                // The waypoint is done once the player stands on it.
+                let reached_waypoint = waypoint.borrow().as_ref().is_some_and(|waypoint| {
+                    !entities.is_empty()
+                        && map_preloader.current_map() == Some(waypoint.map_name.as_str())
+                        && entities[0].get_grid_position() == waypoint.position
+                });
+
+                if reached_waypoint {
+                    waypoint.set(None);
                 }
 
                 start_camera.update(delta_time);
                 player_camera.update(delta_time);
                 directional_shadow_camera.update(day_timer);
 
+                if !entities.is_empty() {
+                    // Never let the camera go far enough into the terrain to lose sight of the player.
+                    // The line starts above the feet of the player, since the ground right next to
+                    // them is always close.
+                    let player_center = player_camera.focus_point() + cgmath::Vector3::new(0.0, CAMERA_COLLISION_HEIGHT, 0.0);
+                    let unobstructed_fraction = map.unobstructed_fraction(player_center, player_camera.orbit_position());
+                    player_camera.set_collision_fraction(unobstructed_fraction.max(MINIMUM_CAMERA_FRACTION), delta_time);
+
+                    object_fading.update(
+                        &map,
+                        &player_camera,
+                        graphics_settings.object_fade_opacity,
+                        graphics_settings.hide_roofs,
+                        delta_time as f32,
+                    );
+                }
+
+                interface.reload_theme_if_changed(&mut game_file_loader, &mut texture_loader);
                 let (clear_interface, rerender_interface) = interface.update(&mut focus_state, game_timer.get_client_tick());
 
+                window_status.set_map(map_preloader.current_map());
+                window_status.set_latency(networking_system.get_latency());
+                window_status.update_title(surface_window(&surface));
+
+                // There is nothing to render to while the window is minimized.
+                if swapchain_holder.is_minimized() {
+                    return;
+                }
+
+                if swapchain_holder.is_surface_lost() {
+                    let window = surface.object().unwrap().clone().downcast::<winit::window::Window>().unwrap();
+                    surface = vulkano_win::create_surface_from_winit(window, instance.clone()).expect("failed to recreate surface");
+                    swapchain_holder.recreate_surface(surface.clone());
+
+                    #[cfg(feature = "debug")]
+                    print_debug!("recreated lost {}surface{}", MAGENTA, NONE);
+                }
+
                 if swapchain_holder.is_swapchain_invalid() {
-                    let viewport = swapchain_holder.recreate_swapchain();
+                    let Some(viewport) = swapchain_holder.recreate_swapchain() else {
+                        return;
+                    };
 
                     deferred_renderer.recreate_pipeline(
                         viewport.clone(),
                         swapchain_holder.window_size_u32(),
+                        graphics_settings.render_scale,
                         #[cfg(feature = "debug")]
                         render_settings.show_wireframe,
                     );
@@ -859,8 +2530,8 @@ fn main() {
                         .collect();
                 }
 
+                // The swapchain or the surface is recreated on the next frame.
                 if swapchain_holder.acquire_next_image().is_err() {
-                    // temporary check?
                     return;
                 }
 
@@ -925,6 +2596,37 @@ fn main() {
                     _ => None,
                 };
 
+                // The interface waits for the preview on the GPU before sampling it.
+                let mut preview_future = None;
+
+                if !entities.is_empty() && interface.window_exists(Some(EquipmentWindow::WINDOW_CLASS)) {
+                    preview_camera.set_focus_point(entities[0].get_position());
+                    preview_camera.generate_view_projection(preview_target.get_size());
+
+                    preview_target.start(image_number);
+                    entities[0].render(&mut preview_target, &preview_renderer, &preview_camera);
+                    preview_target.finish();
+
+                    preview_future = preview_target.state.try_take_fence().map(|fence| fence.boxed());
+                }
+
+                for (_, entity, preview_target) in &mut character_preview_entities {
+                    preview_camera.set_focus_point(entity.get_position());
+                    preview_camera.generate_view_projection(preview_target.get_size());
+
+                    preview_target.start(image_number);
+                    entity.render(preview_target, &preview_renderer, &preview_camera);
+                    preview_target.finish();
+
+                    // All previews are submitted first and the interface waits for them at once.
+                    if let Some(fence) = preview_target.state.try_take_fence() {
+                        preview_future = Some(match preview_future.take() {
+                            Some(future) => future.join(fence).boxed(),
+                            None => fence.boxed(),
+                        });
+                    }
+                }
+
                 thread_pool.in_place_scope(|scope| {
                     scope.spawn(|_| {
                         let picker_target = &mut picker_targets[image_number];
@@ -972,15 +2674,23 @@ fn main() {
                             &shadow_renderer,
                             &directional_shadow_camera,
                             client_tick,
+                            &object_fading,
                             animation_timer,
                             #[cfg(feature = "debug")]
                             render_settings.frustum_culling,
                         );
 
                         #[debug_condition(render_settings.show_entities)]
-                        entities
-                            .iter()
-                            .for_each(|entity| entity.render(directional_shadow_target, &shadow_renderer, &directional_shadow_camera));
+                        if graphics_settings.entity_shadows == EntityShadowMode::Sprite {
+                            entities
+                                .iter()
+                                .for_each(|entity| entity.render(directional_shadow_target, &shadow_renderer, &directional_shadow_camera));
+
+                            #[cfg(feature = "debug")]
+                            snapshot_entities
+                                .iter()
+                                .for_each(|entity| entity.render(directional_shadow_target, &shadow_renderer, &directional_shadow_camera));
+                        }
 
                         directional_shadow_target.finish();
                     });
@@ -1002,6 +2712,7 @@ fn main() {
                             &deferred_renderer,
                             current_camera,
                             client_tick,
+                            &object_fading,
                             animation_timer,
                             #[cfg(feature = "debug")]
                             render_settings.frustum_culling,
@@ -1012,6 +2723,25 @@ fn main() {
                             .iter()
                             .for_each(|entity| entity.render(screen_target, &deferred_renderer, current_camera));
 
+                        #[cfg(feature = "debug")]
+                        if render_settings.show_entities {
+                            snapshot_entities
+                                .iter()
+                                .for_each(|entity| entity.render(screen_target, &deferred_renderer, current_camera));
+                        }
+
+                        #[debug_condition(render_settings.show_entities)]
+                        entities.iter().for_each(|entity| {
+                            entity.render_status_indicators(screen_target, &deferred_renderer, current_camera, &status_indicator_sprites)
+                        });
+
+                        #[debug_condition(render_settings.show_entities)]
+                        if graphics_settings.entity_shadows == EntityShadowMode::Blob {
+                            entities
+                                .iter()
+                                .for_each(|entity| entity.render_blob_shadow(screen_target, &deferred_renderer, current_camera, &map));
+                        }
+
                         #[debug_condition(render_settings.show_water)]
                         map.render_water(screen_target, &deferred_renderer, current_camera, animation_timer);
 
@@ -1039,16 +2769,6 @@ fn main() {
                         #[debug_condition(render_settings.show_water && !render_settings.show_buffers())]
                         map.water_light(screen_target, &deferred_renderer, current_camera);
 
-                        #[cfg(feature = "debug")]
-                        map.render_markers(
-                            screen_target,
-                            &deferred_renderer,
-                            current_camera,
-                            &render_settings,
-                            entities,
-                            hovered_marker_identifier,
-                        );
-
                         #[cfg(feature = "debug")]
                         if render_settings.show_bounding_boxes {
                             map.render_bounding(
@@ -1064,8 +2784,6 @@ fn main() {
                         if let Some(marker_identifier) = hovered_marker_identifier {
                             map.render_marker_box(screen_target, &deferred_renderer, current_camera, marker_identifier);
                         }
-
-                        particle_holder.render(screen_target, &deferred_renderer, current_camera, window_size, entities);
                     });
 
                     if rerender_interface {
@@ -1076,6 +2794,7 @@ fn main() {
                             #[cfg(feature = "debug")]
                             &render_settings,
                             networking_system.get_login_settings(),
+                            &window_settings,
                         );
 
                         interface.render(
@@ -1085,10 +2804,12 @@ fn main() {
                             hovered_element,
                             focused_element,
                             input_system.get_mouse_mode(),
+                            #[cfg(feature = "debug")]
+                            render_settings.cache_windows,
                         );
 
                         let font_future = font_loader.borrow_mut().submit_load_buffer();
-                        interface_target.finish(font_future);
+                        interface_target.finish(font_future, preview_future.take());
                     }
                 });
 
@@ -1109,6 +2830,34 @@ fn main() {
                     );
                 }
 
+                screen_target.interface_pass();
+
+                #[cfg(feature = "debug")]
+                map.render_markers(
+                    screen_target,
+                    &deferred_renderer,
+                    current_camera,
+                    &render_settings,
+                    entities,
+                    hovered_marker_identifier,
+                );
+
+                particle_holder.render(screen_target, &deferred_renderer, current_camera, window_size, entities);
+
+                if pvp_mode.shows_ranking() {
+                    entities
+                        .iter()
+                        .for_each(|entity| entity.render_pvp_rank(screen_target, &deferred_renderer, current_camera, window_size));
+                }
+
+                entities
+                    .iter()
+                    .for_each(|entity| entity.render_chat_room(screen_target, &deferred_renderer, current_camera, window_size));
+
+                if afk_state.is_away(*chat_settings.afk_minutes.borrow()) {
+                    entities[0].render_away_badge(screen_target, &deferred_renderer, current_camera, window_size);
+                }
+
                 if let Some(PickerTarget::Entity(entity_id)) = mouse_target {
                     let entity = entities.iter().find(|entity| entity.get_entity_id() == entity_id);
 
@@ -1120,6 +2869,17 @@ fn main() {
                             interface.render_hover_text(screen_target, &deferred_renderer, name, input_system.get_mouse_position());
                         }
 
+                        let card_delay_passed = entity_hover.map_or(false, |(_, hover_time)| hover_time >= ENTITY_INFO_DELAY);
+
+                        if graphics_settings.show_entity_info && card_delay_passed {
+                            interface.render_entity_info(
+                                screen_target,
+                                &deferred_renderer,
+                                &entity.info_lines(&monster_database),
+                                input_system.get_mouse_position(),
+                            );
+                        }
+
                         entity.render_status(screen_target, &deferred_renderer, current_camera, window_size);
                     }
                 }
@@ -1128,6 +2888,32 @@ fn main() {
                     entities[0].render_status(screen_target, &deferred_renderer, current_camera, window_size);
                 }
 
+                // The locked target always shows its status, so it can be found in a crowd.
+                let target = current_target.and_then(|entity_id| entities.iter().find(|entity| entity.get_entity_id() == entity_id));
+
+                if let Some(entity) = target {
+                    entity.render_status(screen_target, &deferred_renderer, current_camera, window_size);
+                }
+
+                if let Some(placement) = &skill_placement
+                    && !entities.is_empty()
+                {
+                    let hovered_cell = match mouse_target {
+                        Some(PickerTarget::Tile(x, y)) => Some(cgmath::Vector2::new(x as usize, y as usize)),
+                        _ => None,
+                    };
+
+                    placement.render(
+                        screen_target,
+                        &deferred_renderer,
+                        current_camera,
+                        window_size,
+                        &map,
+                        entities[0].get_grid_position(),
+                        hovered_cell,
+                    );
+                }
+
                 #[cfg(feature = "debug")]
                 if render_settings.show_frames_per_second {
                     interface.render_frames_per_second(screen_target, &deferred_renderer, game_timer.last_frames_per_second());
@@ -1136,6 +2922,19 @@ fn main() {
                 if graphics_settings.show_interface {
                     deferred_renderer.overlay_interface(screen_target, interface_target.image.clone());
 
+                    #[cfg(feature = "debug")]
+                    interface.render_inspector_highlight(
+                        screen_target,
+                        &deferred_renderer,
+                        input_system.get_mouse_position(),
+                        input_system.get_mouse_mode(),
+                    );
+
+                    // Rendered on top of the interface so no window can cover it.
+                    if let Some(lines) = &tooltip_lines {
+                        interface.render_tooltip(screen_target, &deferred_renderer, lines, input_system.get_mouse_position());
+                    }
+
                     interface.render_mouse_cursor(
                         screen_target,
                         &deferred_renderer,
@@ -1151,12 +2950,21 @@ fn main() {
                 let directional_shadow_future = directional_shadow_targets[image_number].state.take_semaphore();
                 let swapchain_acquire_future = swapchain_holder.take_acquire_future();
 
-                let combined_future = interface_future
+                let mut combined_future = interface_future
                     .join(directional_shadow_future)
                     .join(swapchain_acquire_future)
                     .boxed();
 
+                // Only left over if the interface was not rendered this frame.
+                if let Some(preview_future) = preview_future {
+                    combined_future = combined_future.join(preview_future).boxed();
+                }
+
                 screen_target.finish(swapchain_holder.get_swapchain(), combined_future, image_number);
+
+                if let RenderTargetState::OutOfDate = screen_target.state {
+                    swapchain_holder.invalidate_swapchain();
+                }
             }
             _ignored => (),
         }
@@ -0,0 +1,528 @@
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::ops::Range;
+use std::process::Command;
+
+use cgmath::Vector2;
+use chrono::{Duration, Local, NaiveDate};
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+
+use super::ChatMessage;
+#[cfg(feature = "debug")]
+use crate::debug::*;
+use crate::interface::TrackedState;
+use crate::system::{character_file_name, profile_file};
+
+const CHAT_LOG_DIRECTORY: &str = "chat_logs";
+const CHAT_TAB_DIRECTORY: &str = "chat_tabs";
+const MAXIMUM_LOG_AGE_DAYS: i64 = 30;
+const LINK_COLOR: &str = "^55AAFF";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChatChannel {
+    Public,
+    Party,
+    Guild,
+    Broadcast,
+    Server,
+    Client,
+    Whisper,
+    Room,
+}
+
+impl ChatChannel {
+    pub const ALL: [ChatChannel; 8] = [
+        ChatChannel::Public,
+        ChatChannel::Party,
+        ChatChannel::Guild,
+        ChatChannel::Broadcast,
+        ChatChannel::Server,
+        ChatChannel::Client,
+        ChatChannel::Whisper,
+        ChatChannel::Room,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ChatChannel::Public => "public",
+            ChatChannel::Party => "party",
+            ChatChannel::Guild => "guild",
+            ChatChannel::Broadcast => "broadcast",
+            ChatChannel::Server => "server",
+            ChatChannel::Client => "client",
+            ChatChannel::Whisper => "whisper",
+            ChatChannel::Room => "chat room",
+        }
+    }
+}
+
+/// A chat room opened by a player. Shown as a sign above the head of the
+/// owner.
+#[derive(Clone, Debug)]
+pub struct ChatRoom {
+    pub room_id: u32,
+    pub title: String,
+    pub users: usize,
+    pub limit: usize,
+    /// Private rooms can only be entered with a password.
+    pub private: bool,
+}
+
+impl ChatRoom {
+    pub fn sign_text(&self) -> String {
+        match self.private {
+            true => format!("[private] {} ({}/{})", self.title, self.users, self.limit),
+            false => format!("{} ({}/{})", self.title, self.users, self.limit),
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ChatTab {
+    pub name: String,
+    pub channels: Vec<ChatChannel>,
+    /// Every channel that existed when the tab was saved, so channels added
+    /// since then can be told apart from the ones that were hidden on purpose.
+    #[serde(default = "ChatTab::untracked_channels")]
+    known_channels: Vec<ChatChannel>,
+}
+
+impl Default for ChatTab {
+    fn default() -> Self {
+        Self::new("all".to_string())
+    }
+}
+
+impl ChatTab {
+    /// A tab that shows every channel.
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            channels: ChatChannel::ALL.to_vec(),
+            known_channels: ChatChannel::ALL.to_vec(),
+        }
+    }
+
+    /// Channels that existed before tabs kept track of them.
+    fn untracked_channels() -> Vec<ChatChannel> {
+        ChatChannel::ALL
+            .into_iter()
+            .filter(|channel| !matches!(channel, ChatChannel::Party | ChatChannel::Guild))
+            .collect()
+    }
+
+    /// Tabs that showed every channel they knew about also show the channels
+    /// that were added after they were saved.
+    fn add_new_channels(&mut self) {
+        if self.known_channels.iter().all(|channel| self.shows(*channel)) {
+            let new_channels = ChatChannel::ALL
+                .into_iter()
+                .filter(|channel| !self.known_channels.contains(channel) && !self.shows(*channel))
+                .collect::<Vec<_>>();
+
+            self.channels.extend(new_channels);
+        }
+
+        self.known_channels = ChatChannel::ALL.to_vec();
+    }
+
+    pub fn shows(&self, channel: ChatChannel) -> bool {
+        self.channels.contains(&channel)
+    }
+
+    pub fn toggle_channel(&mut self, channel: ChatChannel) {
+        match self.shows(channel) {
+            true => self.channels.retain(|other| *other != channel),
+            false => self.channels.push(channel),
+        }
+    }
+}
+
+/// A position on a map, written as `<map,x,y>` inside of chat messages.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MapLink {
+    pub map_name: String,
+    pub position: Vector2<usize>,
+}
+
+impl Display for MapLink {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> FmtResult {
+        write!(formatter, "<{},{},{}>", self.map_name, self.position.x, self.position.y)
+    }
+}
+
+impl MapLink {
+    fn parse(link: &str) -> Option<Self> {
+        let mut parts = link.split(',').map(str::trim);
+
+        let map_name = parts.next().filter(|map_name| !map_name.is_empty())?;
+        let x = parts.next()?.parse().ok()?;
+        let y = parts.next()?.parse().ok()?;
+
+        if parts.next().is_some() {
+            return None;
+        }
+
+        Some(Self {
+            map_name: map_name.to_string(),
+            position: Vector2::new(x, y),
+        })
+    }
+}
+
+/// Anything that can be linked inside of a chat message.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ChatLink {
+    Map(MapLink),
+    /// A monster database entry, written as `<monster,id>`.
+    Monster(usize),
+}
+
+impl Display for ChatLink {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            ChatLink::Map(link) => link.fmt(formatter),
+            ChatLink::Monster(monster_id) => write!(formatter, "<monster,{}>", monster_id),
+        }
+    }
+}
+
+impl ChatLink {
+    fn parse(link: &str) -> Option<Self> {
+        if let Some(monster_id) = link.strip_prefix("monster,") {
+            return monster_id.trim().parse().ok().map(ChatLink::Monster);
+        }
+
+        MapLink::parse(link).map(ChatLink::Map)
+    }
+
+    /// Finds all links in `text` and colors them. Text that only looks similar
+    /// to a link, like `<3`, is left untouched. Every link comes with the part
+    /// of the colored text that it covers.
+    pub fn highlight(text: &str) -> (String, Vec<(Range<usize>, ChatLink)>) {
+        let mut highlighted = String::with_capacity(text.len());
+        let mut links = Vec::new();
+        let mut remaining = text;
+
+        while let Some(start) = remaining.find('<') {
+            let link = remaining[start..]
+                .find('>')
+                .and_then(|end| Self::parse(&remaining[start + 1..start + end]).map(|link| (end, link)));
+
+            match link {
+                Some((end, link)) => {
+                    highlighted.push_str(&remaining[..start]);
+                    highlighted.push_str(LINK_COLOR);
+                    let link_start = highlighted.len();
+                    highlighted.push_str(&remaining[start..start + end + 1]);
+                    links.push((link_start..highlighted.len(), link));
+                    highlighted.push_str("^000000");
+                    remaining = &remaining[start + end + 1..];
+                }
+                None => {
+                    highlighted.push_str(&remaining[..start + 1]);
+                    remaining = &remaining[start + 1..];
+                }
+            }
+        }
+
+        highlighted.push_str(remaining);
+        (highlighted, links)
+    }
+}
+
+/// Character names are limited to 23 bytes, since the server stores them with
+/// a trailing zero.
+const MAXIMUM_NAME_LENGTH: usize = 23;
+
+pub enum WhisperCommand {
+    /// Open the conversation window without sending anything.
+    Open(String),
+    Send(String, String),
+}
+
+/// Parses `/w <name> [message]`. Names containing spaces have to be put in
+/// quotes, like `/w "some name" hello`. Returns `None` if the message is not a
+/// whisper command at all.
+pub fn parse_whisper_command(message: &str) -> Option<Result<WhisperCommand, String>> {
+    let arguments = message.strip_prefix("/w")?;
+
+    // other commands starting with the same letter, like `/where`
+    if !arguments.is_empty() && !arguments.starts_with(' ') {
+        return None;
+    }
+
+    let arguments = arguments.trim_start();
+
+    let (name, message) = match arguments.strip_prefix('"') {
+        Some(quoted) => match quoted.split_once('"') {
+            Some((name, message)) => (name, message),
+            None => return Some(Err("missing closing quote after the name".to_string())),
+        },
+        None => arguments.split_once(' ').unwrap_or((arguments, "")),
+    };
+
+    let message = message.trim();
+
+    if name.is_empty() {
+        return Some(Err("usage: /w <name> [message]".to_string()));
+    }
+
+    if name.len() > MAXIMUM_NAME_LENGTH {
+        return Some(Err(format!("{} is not a valid character name", name)));
+    }
+
+    Some(Ok(match message.is_empty() {
+        true => WhisperCommand::Open(name.to_string()),
+        false => WhisperCommand::Send(name.to_string(), message.to_string()),
+    }))
+}
+
+/// The chat tabs of the current character. They are loaded when a character
+/// logs in and saved when switching to another one.
+pub struct ChatTabs {
+    character_name: Option<String>,
+    pub tabs: TrackedState<Vec<ChatTab>>,
+    pub selected: TrackedState<usize>,
+}
+
+impl Default for ChatTabs {
+    fn default() -> Self {
+        Self {
+            character_name: None,
+            tabs: TrackedState::new(vec![ChatTab::default()]),
+            selected: TrackedState::new(0),
+        }
+    }
+}
+
+impl ChatTabs {
+    fn tab_file(character_name: &str) -> String {
+        profile_file(&format!("{}/{}.ron", CHAT_TAB_DIRECTORY, character_name))
+    }
+
+    pub fn set_character(&mut self, character_name: &str) {
+        self.save();
+
+        let character_name = character_file_name(character_name);
+        let tabs = Self::load(&character_name).unwrap_or_else(|| {
+            #[cfg(feature = "debug")]
+            print_debug!("failed to load chat tabs for {}{}{}", MAGENTA, character_name, NONE);
+
+            vec![ChatTab::default()]
+        });
+
+        self.tabs.set(tabs);
+        self.selected.set(0);
+        self.character_name = Some(character_name);
+    }
+
+    fn load(character_name: &str) -> Option<Vec<ChatTab>> {
+        #[cfg(feature = "debug")]
+        print_debug!("loading chat tabs for {}{}{}", MAGENTA, character_name, NONE);
+
+        std::fs::read_to_string(Self::tab_file(character_name))
+            .ok()
+            .and_then(|data| ron::from_str::<Vec<ChatTab>>(&data).ok())
+            .filter(|tabs| !tabs.is_empty())
+            .map(|mut tabs| {
+                tabs.iter_mut().for_each(ChatTab::add_new_channels);
+                tabs
+            })
+    }
+
+    pub fn save(&self) {
+        let Some(character_name) = &self.character_name else {
+            return;
+        };
+
+        #[cfg(feature = "debug")]
+        print_debug!("saving chat tabs for {}{}{}", MAGENTA, character_name, NONE);
+
+        let data = ron::ser::to_string_pretty(&*self.tabs.borrow(), PrettyConfig::new()).unwrap();
+        std::fs::create_dir_all(profile_file(CHAT_TAB_DIRECTORY)).expect("unable to create chat tab directory");
+        std::fs::write(Self::tab_file(character_name), data).expect("unable to write file");
+    }
+}
+
+impl Drop for ChatTabs {
+    fn drop(&mut self) {
+        self.save();
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChatSettings {
+    pub show_timestamps: TrackedState<bool>,
+    pub log_to_disk: TrackedState<bool>,
+    /// Minutes without input after which the player is considered away. Zero
+    /// disables it.
+    pub afk_minutes: TrackedState<u32>,
+    /// Sent to players that whisper while the player is away.
+    pub afk_message: TrackedState<String>,
+}
+
+impl Default for ChatSettings {
+    fn default() -> Self {
+        Self {
+            show_timestamps: TrackedState::new(true),
+            log_to_disk: TrackedState::new(false),
+            afk_minutes: TrackedState::new(0),
+            afk_message: TrackedState::new("I am away from the keyboard right now".to_string()),
+        }
+    }
+}
+
+impl ChatSettings {
+    pub fn new() -> Self {
+        Self::load().unwrap_or_else(|| {
+            #[cfg(feature = "debug")]
+            print_debug!("failed to load chat settings from {}filename{}", MAGENTA, NONE);
+
+            Default::default()
+        })
+    }
+
+    pub fn load() -> Option<Self> {
+        #[cfg(feature = "debug")]
+        print_debug!("loading chat settings from {}filename{}", MAGENTA, NONE);
+
+        std::fs::read_to_string(profile_file("chat_settings.ron"))
+            .ok()
+            .and_then(|data| ron::from_str(&data).ok())
+    }
+
+    pub fn save(&self) {
+        #[cfg(feature = "debug")]
+        print_debug!("saving chat settings to {}filename{}", MAGENTA, NONE);
+
+        let data = ron::ser::to_string_pretty(self, PrettyConfig::new()).unwrap();
+        std::fs::write(profile_file("chat_settings.ron"), data).expect("unable to write file");
+    }
+}
+
+impl Drop for ChatSettings {
+    fn drop(&mut self) {
+        self.save();
+    }
+}
+
+fn strip_color_codes(text: &str) -> String {
+    let mut stripped = String::with_capacity(text.len());
+    let mut remaining = text;
+
+    while let Some(index) = remaining.find('^') {
+        stripped.push_str(&remaining[..index]);
+
+        let is_color_code = remaining[index + 1..]
+            .get(..6)
+            .map_or(false, |code| code.chars().all(|character| character.is_ascii_hexdigit()));
+
+        match is_color_code {
+            true => remaining = &remaining[index + 7..],
+            false => {
+                stripped.push('^');
+                remaining = &remaining[index + 1..];
+            }
+        }
+    }
+
+    stripped.push_str(remaining);
+    stripped
+}
+
+/// Writes chat messages to one file per character and day. Logs older than
+/// [`MAXIMUM_LOG_AGE_DAYS`] are removed when a character logs in.
+#[derive(Default)]
+pub struct ChatLog {
+    character_name: Option<String>,
+    file: Option<(NaiveDate, File)>,
+}
+
+impl ChatLog {
+    pub fn directory() -> String {
+        profile_file(CHAT_LOG_DIRECTORY)
+    }
+
+    pub fn open_directory() {
+        let directory = Self::directory();
+        let _ = std::fs::create_dir_all(&directory);
+
+        #[cfg(target_os = "windows")]
+        let program = "explorer";
+        #[cfg(target_os = "macos")]
+        let program = "open";
+        #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+        let program = "xdg-open";
+
+        if let Err(_error) = Command::new(program).arg(&directory).spawn() {
+            #[cfg(feature = "debug")]
+            print_debug!("failed to open {}{}{}: {}", MAGENTA, directory, NONE, _error);
+        }
+    }
+
+    pub fn set_character(&mut self, character_name: &str) {
+        self.character_name = Some(character_file_name(character_name));
+        self.file = None;
+        self.remove_old_logs();
+    }
+
+    fn remove_old_logs(&self) {
+        let Some(character_name) = &self.character_name else {
+            return;
+        };
+
+        let Ok(entries) = std::fs::read_dir(Self::directory()) else {
+            return;
+        };
+
+        let oldest_date = Local::now().date_naive() - Duration::days(MAXIMUM_LOG_AGE_DAYS);
+        let prefix = format!("{}_", character_name);
+
+        for entry in entries.flatten() {
+            let file_name = entry.file_name();
+            let date = file_name
+                .to_str()
+                .and_then(|file_name| file_name.strip_prefix(&prefix))
+                .and_then(|file_name| file_name.strip_suffix(".log"))
+                .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok());
+
+            if let Some(date) = date
+                && date < oldest_date
+            {
+                let _ = std::fs::remove_file(entry.path());
+            }
+        }
+    }
+
+    pub fn write(&mut self, message: &ChatMessage) {
+        let Some(character_name) = &self.character_name else {
+            return;
+        };
+
+        let date = Local::now().date_naive();
+
+        if !matches!(&self.file, Some((file_date, _)) if *file_date == date) {
+            let directory = Self::directory();
+            let path = format!("{}/{}_{}.log", directory, character_name, date.format("%Y-%m-%d"));
+
+            let file = std::fs::create_dir_all(&directory).and_then(|_| OpenOptions::new().create(true).append(true).open(&path));
+
+            match file {
+                Ok(file) => self.file = Some((date, file)),
+                Err(_error) => {
+                    #[cfg(feature = "debug")]
+                    print_debug!("failed to open chat log {}{}{}: {}", MAGENTA, path, NONE, _error);
+
+                    return;
+                }
+            }
+        }
+
+        let (_date, file) = self.file.as_mut().unwrap();
+        let _ = writeln!(file, "{}", strip_color_codes(message.stamped_text(true)));
+    }
+}
@@ -3,6 +3,34 @@ use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "debug")]
 use crate::debug::*;
+use crate::system::profile_file;
+
+/// How the password is sent to the login server. This has to match the
+/// `passwordencrypt` setting of the server.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PasswordEncryption {
+    #[default]
+    Plain,
+    /// MD5 hash of the key sent by the server followed by the password, which
+    /// is `passwordencrypt: 1`.
+    Md5KeyFirst,
+    /// MD5 hash of the password followed by the key sent by the server, which
+    /// is `passwordencrypt: 2`.
+    Md5KeyLast,
+}
+
+impl PasswordEncryption {
+    /// Returns `None` if the password is sent as is.
+    pub fn hash(self, password: &str, key: &[u8]) -> Option<[u8; 16]> {
+        let data = match self {
+            PasswordEncryption::Plain => return None,
+            PasswordEncryption::Md5KeyFirst => [key, password.as_bytes()].concat(),
+            PasswordEncryption::Md5KeyLast => [password.as_bytes(), key].concat(),
+        };
+
+        Some(md5::compute(data).0)
+    }
+}
 
 #[derive(Clone, Default, Serialize, Deserialize)]
 pub struct LoginSettings {
@@ -10,6 +38,8 @@ pub struct LoginSettings {
     pub password: String,
     pub remember_username: bool,
     pub remember_password: bool,
+    #[serde(default)]
+    pub password_encryption: PasswordEncryption,
 }
 
 impl LoginSettings {
@@ -26,7 +56,7 @@ impl LoginSettings {
         #[cfg(feature = "debug")]
         print_debug!("loading login settings from {}filename{}", MAGENTA, NONE);
 
-        std::fs::read_to_string("client/login_settings.ron")
+        std::fs::read_to_string(profile_file("login_settings.ron"))
             .ok()
             .and_then(|data| ron::from_str(&data).ok())
     }
@@ -36,7 +66,7 @@ impl LoginSettings {
         print_debug!("saving login settings to {}filename{}", MAGENTA, NONE);
 
         let data = ron::ser::to_string_pretty(self, PrettyConfig::new()).unwrap();
-        std::fs::write("client/login_settings.ron", data).expect("unable to write file");
+        std::fs::write(profile_file("login_settings.ron"), data).expect("unable to write file");
     }
 }
 
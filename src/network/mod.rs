@@ -1,24 +1,36 @@
+mod chat;
 mod login;
+#[cfg(feature = "debug_network")]
+mod statistics;
 
 use std::cell::RefCell;
+use std::collections::VecDeque;
 use std::fmt::Debug;
 use std::io::prelude::*;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr, TcpStream};
+use std::ops::Range;
 use std::rc::Rc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use cgmath::Vector2;
 use chrono::Local;
 use derive_new::new;
 use procedural::*;
 
-pub use self::login::LoginSettings;
+pub use self::chat::{
+    parse_whisper_command, ChatChannel, ChatLink, ChatLog, ChatRoom, ChatSettings, ChatTab, ChatTabs, MapLink, WhisperCommand,
+};
+pub use self::login::{LoginSettings, PasswordEncryption};
+#[cfg(feature = "debug_network")]
+pub use self::statistics::{
+    NetworkStatistics, NetworkStatisticsRecorder, PacketDirection, PacketRecord, PacketTotal, TrafficCounter, TrafficSample,
+};
 #[cfg(feature = "debug_network")]
 use crate::debug::Timer;
-use crate::graphics::{Color, ColorBGR, ColorRGB};
+use crate::graphics::{Color, ColorBGR, ColorRGB, ImageBuffer};
 #[cfg(feature = "debug_network")]
 use crate::interface::PacketEntry;
-use crate::interface::{CharacterSelectionWindow, ElementCell, PrototypeElement, TrackedState};
+use crate::interface::{CharacterSelectionWindow, ElementCell, PrototypeElement, Remote, ServerSelectionWindow, TrackedState};
 use crate::loaders::{ByteConvertable, ByteStream};
 
 #[derive(Clone, Copy, Debug, ByteConvertable, PrototypeElement)]
@@ -39,6 +51,14 @@ pub struct EntityId(pub u32);
 #[derive(Clone, Copy, Debug, PrototypeElement, PartialEq, Eq, Hash)]
 pub struct ItemIndex(u16);
 
+impl ItemIndex {
+    /// Items that are not in the inventory of the player, such as the items
+    /// offered by a trade partner, are numbered by their position instead.
+    pub fn from_position(position: usize) -> Self {
+        Self(position as u16)
+    }
+}
+
 impl ByteConvertable for ItemIndex {
     fn from_bytes(byte_stream: &mut ByteStream, length_hint: Option<usize>) -> Self {
         Self(u16::from_bytes(byte_stream, length_hint) - 2)
@@ -66,6 +86,20 @@ pub trait Packet: PrototypeElement + Clone {
     fn to_bytes(&self) -> Vec<u8>;
 }
 
+/// Part of the look of an entity that was changed by the server.
+#[derive(Clone, Copy, Debug)]
+pub enum LookChange {
+    HairStyle(usize),
+    HairColor(usize),
+    ClothesColor(usize),
+    Weapon { weapon: u32, shield: u32 },
+    Shield(u32),
+    HeadBottom(usize),
+    HeadTop(usize),
+    HeadMiddle(usize),
+    Garment(usize),
+}
+
 /// An event triggered by the map server.
 pub enum NetworkEvent {
     /// Add an entity to the list of entities that the client is aware of
@@ -77,19 +111,69 @@ pub enum NetworkEvent {
     PlayerMove(Vector2<usize>, Vector2<usize>, ClientTick),
     /// An Entity nearby is pathing to a new position
     EntityMove(EntityId, Vector2<usize>, Vector2<usize>, ClientTick),
+    /// An entity stopped walking, it should not go any further than the given
+    /// position
+    EntityStopMove(EntityId, Vector2<usize>),
     /// Player was moved to a new position on a different map or the current map
     ChangeMap(String, Vector2<usize>),
+    /// An entity was moved to a new position without walking, for example by
+    /// being knocked back
+    EntitySlide(EntityId, Vector2<usize>),
+    /// An entity used a skill that makes it jump to the position of its next
+    /// slide
+    EntityJump(EntityId),
+    /// An entity teleported away, as opposed to walking out of sight
+    EntityTeleported(EntityId),
+    /// An entity died and is removed
+    EntityDied(EntityId),
+    /// Type and flags of the current map, sent after entering it
+    UpdateMapType(u16, u32),
+    /// Rank of a player on a PvP map and the total number of players
+    PvpRanking {
+        entity_id: EntityId,
+        ranking: usize,
+        total: usize,
+    },
     /// Update the client side [tick
     /// counter](crate::system::GameTimer::client_tick) to keep server and
     /// client synchronized
     UpdateClientTick(ClientTick),
     /// New chat message for the client
     ChatMessage(ChatMessage),
+    /// Another player whispered to the player
+    WhisperReceived(String),
+    /// The player was invited to join a party
+    PartyInvitation,
+    /// A party member on the same map moved, used for the minimap
+    PartyMemberPosition(AccountId, Vector2<usize>),
+    /// A player left the party or was expelled from it
+    PartyMemberLeft(AccountId),
+    /// All members of the party that are online. Empty once the player is no
+    /// longer in a party.
+    UpdatePartyMembers(Vec<AccountId>),
+    /// A player nearby opened a chat room or the room changed
+    UpdateChatRoom(EntityId, ChatRoom),
+    /// A chat room nearby was closed
+    RemoveChatRoom(u32),
+    /// The player entered the chat room with the given id
+    ChatRoomEntered(u32),
+    /// The player left the chat room they were in
+    ChatRoomLeft,
     /// Update entity details. Mostly received when the client sends
     /// [RequestDetailsPacket] after the player hovered an entity.
     UpdateEntityDetails(EntityId, String),
     UpdateEntityHealth(EntityId, usize, usize),
+    /// Body state and health state of an entity changed, for example when it
+    /// got stunned or silenced
+    UpdateEntityState(EntityId, u16, u16, u32),
+    /// Part of the look of an entity changed
+    UpdateEntityLook(EntityId, LookChange),
+    /// An entity attacks, the attack animation lasts for the given number of
+    /// milliseconds
+    EntityAttack(EntityId, u32),
     DamageEffect(EntityId, usize),
+    /// An entity was hit by a skill
+    SkillDamage(u16, EntityId),
     UpdateStatus(StatusType),
     OpenDialog(String, EntityId),
     AddNextButton,
@@ -97,35 +181,129 @@ pub enum NetworkEvent {
     AddChoiceButtons(Vec<String>),
     AddQuestEffect(QuestEffectPacket),
     RemoveQuestEffect(EntityId),
-    Inventory(Vec<(ItemIndex, ItemId, EquipPosition, EquipPosition)>),
-    AddIventoryItem(ItemIndex, ItemId, EquipPosition, EquipPosition),
+    Inventory(Vec<(ItemIndex, ItemId, EquipPosition, EquipPosition, [ItemId; 4], u16)>),
+    AddIventoryItem(ItemIndex, ItemId, EquipPosition, EquipPosition, [ItemId; 4], u16),
+    /// Item index and the amount that was removed from the stack
+    RemoveInventoryItem(ItemIndex, u16),
+    /// Item index and the amount that is left after using the item
+    UpdateInventoryItemAmount(ItemIndex, u16),
+    /// The player picked up the given amount of an item
+    ItemPickedUp(usize),
     UpdateEquippedPosition {
         index: ItemIndex,
         equipped_position: EquipPosition,
     },
+    OpenRefineWindow,
+    RefineItemInformation(ItemIndex, Vec<RefineMaterial>),
+    RefineResult(ItemIndex, RefineResult, usize),
+    CraftableItems(Vec<CraftableItem>),
+    CraftResult(ItemId, bool),
+    /// Equipment that the card with the given index can be inserted into.
+    CardTargets(ItemIndex, Vec<ItemIndex>),
+    CardInserted {
+        card_index: ItemIndex,
+        equipment_index: ItemIndex,
+    },
+    CardInsertFailed,
+    /// The player used a taming item and has to pick a monster to capture.
+    StartPetCapture,
+    PetCaptureResult(bool),
+    PetEggList(Vec<ItemIndex>),
+    PetStatus(PetStatus),
+    UpdatePetIntimacy(usize),
+    UpdatePetHunger(usize),
+    UpdatePetAccessory(Option<ItemId>),
+    SkillTree(Vec<SkillInformation>),
+    /// An entity started casting a skill. The duration is in milliseconds.
+    SkillCast {
+        entity_id: EntityId,
+        skill_id: u16,
+        duration: u32,
+    },
+    CastCancelled(EntityId),
+    /// A skill of the player can not be used again for the given amount of
+    /// milliseconds.
+    SkillCooldown(u16, u32),
+    /// Another player asked the player to trade
+    TradeRequested(String),
+    /// A trade request of either player was accepted and the trade window
+    /// should be opened
+    TradeStarted,
+    /// The trade partner offered an item
+    TradeItemAdded {
+        item_id: ItemId,
+        cards: [ItemId; 4],
+        amount: u16,
+    },
+    /// The trade partner offered zeny
+    TradeZenyAdded(u32),
+    /// An item the player offered was added to the trade
+    TradeItemAccepted(ItemIndex),
+    /// An item the player offered could not be added to the trade
+    TradeItemRejected(ItemIndex),
+    TradeLocked {
+        partner: bool,
+    },
+    /// The trade was completed or cancelled
+    TradeEnded,
 }
 
 pub struct ChatMessage {
     pub text: String,
     pub color: Color,
+    pub channel: ChatChannel,
+    /// The other player of a whisper conversation. Only set for messages in
+    /// the [`ChatChannel::Whisper`] channel.
+    pub partner: Option<String>,
+    /// Every link in the message, together with the part of the text that it
+    /// covers.
+    pub links: Vec<(Range<usize>, ChatLink)>,
     offset: usize,
 }
 
 impl ChatMessage {
     // TODO: Maybe this shouldn't modify the text directly but rather save the
     // timestamp.
-    pub fn new(mut text: String, color: Color) -> Self {
+    pub fn new(text: String, color: Color, channel: ChatChannel) -> Self {
         let prefix = Local::now().format("^66BB44%H:%M:%S^000000: ").to_string();
         let offset = prefix.len();
 
+        let (mut text, links) = ChatLink::highlight(&text);
+        let links = links
+            .into_iter()
+            .map(|(range, link)| (range.start + offset..range.end + offset, link))
+            .collect();
+
         text.insert_str(0, &prefix);
-        Self { text, color, offset }
+        Self {
+            text,
+            color,
+            channel,
+            partner: None,
+            links,
+            offset,
+        }
+    }
+
+    pub fn whisper(text: String, color: Color, partner: String) -> Self {
+        Self {
+            partner: Some(partner),
+            ..Self::new(text, color, ChatChannel::Whisper)
+        }
     }
 
     pub fn stamped_text(&self, stamp: bool) -> &str {
         let start = self.offset * !stamp as usize;
         &self.text[start..]
     }
+
+    /// Links together with the part of the stamped text that they cover.
+    pub fn stamped_links(&self, stamp: bool) -> impl Iterator<Item = (Range<usize>, &ChatLink)> {
+        let start = self.offset * !stamp as usize;
+        self.links
+            .iter()
+            .map(move |(range, link)| (range.start - start..range.end - start, link))
+    }
 }
 
 #[derive(Copy, Clone, Debug, ByteConvertable, PrototypeElement)]
@@ -154,6 +332,39 @@ struct LoginServerLoginPacket {
     pub client_type: u8,
 }
 
+/// Sent by the client to the login server instead of [LoginServerLoginPacket]
+/// if the password is hashed. Asks for the key that is hashed together with
+/// the password.
+#[derive(Clone, Debug, Default, Packet, PrototypeElement)]
+#[header(0xdb, 0x01)]
+struct RequestPasswordKeyPacket {}
+
+/// Sent by the login server as a response to [RequestPasswordKeyPacket].
+#[derive(Clone, Debug, Packet, PrototypeElement)]
+#[header(0xdc, 0x01)]
+struct PasswordKeyPacket {
+    pub packet_length: u16,
+    #[repeating(self.packet_length - 4)]
+    pub key: Vec<u8>,
+}
+
+/// Sent by the client to the login server after receiving the
+/// [PasswordKeyPacket]. Same as [LoginServerLoginPacket] but with the hash
+/// of the key and the password.
+#[derive(Clone, Debug, Packet, PrototypeElement, new)]
+#[header(0xdd, 0x01)]
+struct LoginServerHashedLoginPacket {
+    /// Unused
+    #[new(default)]
+    pub version: [u8; 4],
+    #[length_hint(24)]
+    pub name: String,
+    pub password_hash: [u8; 16],
+    /// Unused
+    #[new(default)]
+    pub client_type: u8,
+}
+
 /// Sent by the login server as a response to [LoginServerLoginPacket]
 /// succeeding. After receiving this packet, the client will connect to one of
 /// the character servers provided by this packet.
@@ -507,7 +718,8 @@ pub struct CharacterInformation {
     #[length_hint(16)]
     pub map_name: String,
     pub deletion_reverse_date: i32,
-    pub robe_palette: i32,
+    /// View id of the garment.
+    pub robe: i32,
     pub character_slot_change_count: i32,
     pub character_name_change_count: i32,
     pub sex: Sex,
@@ -570,6 +782,9 @@ struct EntityMovePacket {
     pub timestamp: ClientTick,
 }
 
+/// Sent by the map server to the client.
+/// Informs the client that an entity stopped walking before reaching its
+/// destination, for example because it was hit or started an attack.
 #[derive(Clone, Debug, Packet, PrototypeElement)]
 #[header(0x88, 0x00)]
 struct EntityStopMovePacket {
@@ -578,6 +793,17 @@ struct EntityStopMovePacket {
     pub y: u16,
 }
 
+/// Sent by the map server to the client.
+/// Moves an entity to a new position without walking there. Used both for
+/// knockback and for skills like High Jump.
+#[derive(Clone, Debug, Packet, PrototypeElement)]
+#[header(0xff, 0x01)]
+struct EntitySlidePacket {
+    pub entity_id: EntityId,
+    pub x: u16,
+    pub y: u16,
+}
+
 /// Sent by the map server to the client.
 /// Informs the client that the player is pathing towards a new position.
 /// Provides the initial position and destination of the movement, as well as a
@@ -822,6 +1048,9 @@ struct EquippableSwitchItemListPacket {
     pub item_information: Vec<EquippableSwitchItemInformation>,
 }
 
+/// Sent by the map server to the client when entering a map.
+/// Provides the type of the map, for example if it allows PvP, as well as
+/// additional flags.
 #[derive(Clone, Debug, Packet, PrototypeElement)]
 #[header(0x9b, 0x09)]
 struct MapTypePacket {
@@ -829,6 +1058,17 @@ struct MapTypePacket {
     pub flags: u32,
 }
 
+/// Sent by the map server to the client on PvP maps.
+/// Informs the client about the rank of a player among all players on the
+/// map.
+#[derive(Clone, Debug, Packet, PrototypeElement)]
+#[header(0x9a, 0x01)]
+struct PvpRankingPacket {
+    pub entity_id: EntityId,
+    pub ranking: u32,
+    pub total: u32,
+}
+
 /// Sent by the map server to the client when there is a new chat message from
 /// ??. Provides the message to be displayed in the chat window, as well as
 /// information on how the message should be displayed.
@@ -858,6 +1098,17 @@ struct EntityMessagePacket {
     pub message: String,
 }
 
+/// Sent by the map server to the client when another player says something.
+/// Inside of a chat room, only the other members of the room receive it.
+#[derive(Clone, Debug, Packet, PrototypeElement)]
+#[header(0x8d, 0x00)]
+struct PlayerMessagePacket {
+    pub packet_length: u16,
+    pub entity_id: EntityId,
+    #[length_hint(self.packet_length - 8)]
+    pub message: String,
+}
+
 #[derive(Clone, Debug, Packet, PrototypeElement)]
 #[header(0xc0, 0x00)]
 struct DisplayEmotionPacket {
@@ -1133,6 +1384,349 @@ struct GlobalMessagePacket {
     pub message: String,
 }
 
+/// Sent by the client to the map server when the player asks another player
+/// to trade. The entity id of players is their account id.
+#[derive(Clone, Debug, Packet, PrototypeElement, new)]
+#[header(0xe4, 0x00)]
+struct RequestTradePacket {
+    pub entity_id: EntityId,
+}
+
+/// Sent by older map servers to the client when another player asks the
+/// player to trade.
+#[derive(Clone, Debug, Packet, PrototypeElement)]
+#[header(0xe5, 0x00)]
+struct TradeRequestPacket {
+    #[length_hint(24)]
+    pub name: String,
+}
+
+/// Sent by the map server to the client when another player asks the player
+/// to trade.
+#[derive(Clone, Debug, Packet, PrototypeElement)]
+#[header(0xf4, 0x01)]
+struct TradeRequest2Packet {
+    #[length_hint(24)]
+    pub name: String,
+    pub character_id: CharacterId,
+    pub base_level: u16,
+}
+
+#[derive(Clone, Debug, ByteConvertable, PrototypeElement)]
+enum TradeResponse {
+    #[numeric_value(3)]
+    Accept,
+    #[numeric_value(4)]
+    Decline,
+}
+
+/// Sent by the client to the map server to answer a trade request.
+#[derive(Clone, Debug, Packet, PrototypeElement, new)]
+#[header(0xe6, 0x00)]
+struct RespondToTradeRequestPacket {
+    pub response: TradeResponse,
+}
+
+#[derive(Clone, Debug, ByteConvertable, PrototypeElement)]
+enum TradeRequestResult {
+    TooFarAway,
+    CharacterNotFound,
+    Failed,
+    Accepted,
+    Declined,
+    Busy,
+}
+
+/// Sent by older map servers to the client when a trade request was
+/// answered. The trade starts once the result is
+/// [`Accepted`](TradeRequestResult::Accepted), for both players.
+#[derive(Clone, Debug, Packet, PrototypeElement)]
+#[header(0xe7, 0x00)]
+struct TradeRequestResultPacket {
+    pub result: TradeRequestResult,
+}
+
+/// Sent by the map server to the client when a trade request was answered.
+#[derive(Clone, Debug, Packet, PrototypeElement)]
+#[header(0xf5, 0x01)]
+struct TradeRequestResult2Packet {
+    pub result: TradeRequestResult,
+    pub character_id: CharacterId,
+    pub base_level: u16,
+}
+
+/// Sent by the client to the map server to offer an item in the current
+/// trade.
+#[derive(Clone, Debug, Packet, PrototypeElement, new)]
+#[header(0xe8, 0x00)]
+struct AddTradeItemPacket {
+    pub index: ItemIndex,
+    pub amount: u32,
+}
+
+/// Sent by the map server to the client when the trade partner offered an
+/// item. Zeny is sent as an item with the id 0.
+#[derive(Clone, Debug, Packet, PrototypeElement)]
+#[header(0x42, 0x0b)]
+struct TradeItemAddedPacket {
+    pub item_id: ItemId,
+    pub item_type: u8,
+    pub amount: u32,
+    pub is_identified: u8,
+    pub is_damaged: u8,
+    pub cards: [u32; 4],
+    pub option_data: [ItemOptions; 5], // fix count
+    pub refinement_level: u8,
+    pub enchantment_level: u8,
+}
+
+/// Sent by the map server to the client after the player offered an item.
+/// Any result other than 0 means that the item was not added, for example
+/// because the trade partner can't carry it.
+#[derive(Clone, Debug, Packet, PrototypeElement)]
+#[header(0xea, 0x00)]
+struct AddTradeItemResultPacket {
+    pub index: ItemIndex,
+    pub result: u8,
+}
+
+/// Sent by the client to the map server to lock the offer of the player.
+#[derive(Clone, Debug, Default, Packet, PrototypeElement)]
+#[header(0xeb, 0x00)]
+struct LockTradePacket {}
+
+/// Sent by the map server to the client when the player or the trade partner
+/// locked their offer.
+#[derive(Clone, Debug, Packet, PrototypeElement)]
+#[header(0xec, 0x00)]
+struct TradeLockedPacket {
+    /// 0 for the player, 1 for the trade partner.
+    pub who: u8,
+}
+
+#[derive(Clone, Debug, Default, Packet, PrototypeElement)]
+#[header(0xed, 0x00)]
+struct CancelTradePacket {}
+
+/// Sent by the map server to the client when the trade was cancelled by
+/// either player. Offered items are added back to the inventory by the
+/// server.
+#[derive(Clone, Debug, Packet, PrototypeElement)]
+#[header(0xee, 0x00)]
+struct TradeCancelledPacket {}
+
+/// Sent by the client to the map server to complete a trade after both
+/// offers are locked.
+#[derive(Clone, Debug, Default, Packet, PrototypeElement)]
+#[header(0xef, 0x00)]
+struct CommitTradePacket {}
+
+/// Sent by the map server to the client when the trade is over. 0 means that
+/// the items were exchanged.
+#[derive(Clone, Debug, Packet, PrototypeElement)]
+#[header(0xf0, 0x00)]
+struct TradeCompletedPacket {
+    pub result: u8,
+}
+
+/// Sent by the client to the map server when the player whispers to another
+/// player.
+#[derive(Clone, Debug, Packet, PrototypeElement, new)]
+#[header(0x96, 0x00)]
+struct WhisperPacket {
+    pub packet_length: u16,
+    #[length_hint(24)]
+    pub recipient_name: String,
+    pub message: String,
+}
+
+/// Sent by the map server to the client when another player whispers to the
+/// player.
+#[derive(Clone, Debug, Packet, PrototypeElement)]
+#[header(0x97, 0x00)]
+struct WhisperReceivedPacket {
+    pub packet_length: u16,
+    #[length_hint(24)]
+    pub sender_name: String,
+    pub is_admin: u32,
+    #[length_hint(self.packet_length - 32)]
+    pub message: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, ByteConvertable, PrototypeElement)]
+enum WhisperResult {
+    Success,
+    RecipientOffline,
+    Ignored,
+    AllIgnored,
+}
+
+/// Sent by the map server to the client as a response to [WhisperPacket].
+#[derive(Clone, Debug, Packet, PrototypeElement)]
+#[header(0x98, 0x00)]
+struct WhisperResponsePacket {
+    pub result: WhisperResult,
+}
+
+/// Sent by the client to the map server when the player sends a message to
+/// their party.
+#[derive(Clone, Debug, Packet, PrototypeElement, new)]
+#[header(0x08, 0x01)]
+struct PartyMessagePacket {
+    pub packet_length: u16,
+    pub message: String,
+}
+
+/// Sent by the map server to the client when a member of the party sends a
+/// message, including the player.
+#[derive(Clone, Debug, Packet, PrototypeElement)]
+#[header(0x09, 0x01)]
+struct PartyMessageReceivedPacket {
+    pub packet_length: u16,
+    pub account_id: AccountId,
+    #[length_hint(self.packet_length - 8)]
+    pub message: String,
+}
+
+/// Sent by the client to the map server when the player sends a message to
+/// their guild.
+#[derive(Clone, Debug, Packet, PrototypeElement, new)]
+#[header(0x7e, 0x01)]
+struct GuildMessagePacket {
+    pub packet_length: u16,
+    pub message: String,
+}
+
+/// Sent by the map server to the client when a member of the guild sends a
+/// message, including the player.
+#[derive(Clone, Debug, Packet, PrototypeElement)]
+#[header(0x7f, 0x01)]
+struct GuildMessageReceivedPacket {
+    pub packet_length: u16,
+    #[length_hint(self.packet_length - 4)]
+    pub message: String,
+}
+
+/// Sent by the map server to the client when a player nearby opens a chat
+/// room or when the room changes, for example because someone joined it.
+/// Provides everything shown on the sign above the owner.
+#[derive(Clone, Debug, Packet, PrototypeElement)]
+#[header(0xd7, 0x00)]
+struct ChatRoomEntryPacket {
+    pub packet_length: u16,
+    pub owner_id: EntityId,
+    pub room_id: u32,
+    pub limit: u16,
+    pub users: u16,
+    pub room_type: ChatRoomType,
+    #[length_hint(self.packet_length - 17)]
+    pub title: String,
+}
+
+/// Sent by the map server to the client when the owner changes the settings
+/// of the chat room the player is in.
+#[derive(Clone, Debug, Packet, PrototypeElement)]
+#[header(0xdf, 0x00)]
+struct ChatRoomChangedPacket {
+    pub packet_length: u16,
+    pub owner_id: EntityId,
+    pub room_id: u32,
+    pub limit: u16,
+    pub users: u16,
+    pub room_type: ChatRoomType,
+    #[length_hint(self.packet_length - 17)]
+    pub title: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ByteConvertable, PrototypeElement)]
+enum ChatRoomType {
+    Private,
+    Public,
+}
+
+/// Sent by the map server to the client when a chat room nearby is closed.
+#[derive(Clone, Debug, Packet, PrototypeElement)]
+#[header(0xd8, 0x00)]
+struct ChatRoomDestroyedPacket {
+    pub room_id: u32,
+}
+
+/// Sent by the client to the map server when the player wants to enter a
+/// chat room. The password is ignored for public rooms.
+#[derive(Clone, Debug, Packet, PrototypeElement, new)]
+#[header(0xd9, 0x00)]
+struct EnterChatRoomPacket {
+    pub room_id: u32,
+    #[length_hint(8)]
+    pub password: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, ByteConvertable, PrototypeElement)]
+enum EnterChatRoomResult {
+    RoomFull,
+    WrongPassword,
+    Kicked,
+    Success,
+    NotEnoughZeny,
+    LevelTooLow,
+    LevelTooHigh,
+    UnsuitableJob,
+}
+
+/// Sent by the map server to the client as a response to
+/// [EnterChatRoomPacket] if the player can not enter the room.
+#[derive(Clone, Debug, Packet, PrototypeElement)]
+#[header(0xda, 0x00)]
+struct EnterChatRoomFailedPacket {
+    pub result: EnterChatRoomResult,
+}
+
+#[derive(Clone, Debug, ByteConvertable, PrototypeElement)]
+struct ChatRoomMember {
+    /// Zero for the owner of the room.
+    pub flag: u32,
+    #[length_hint(24)]
+    pub name: String,
+}
+
+/// Sent by the map server to the client as a response to
+/// [EnterChatRoomPacket] if the player entered the room.
+#[derive(Clone, Debug, Packet, PrototypeElement)]
+#[header(0xdb, 0x00)]
+struct ChatRoomEnteredPacket {
+    pub packet_length: u16,
+    pub room_id: u32,
+    #[repeating((self.packet_length - 8) / 28)]
+    pub members: Vec<ChatRoomMember>,
+}
+
+/// Sent by the map server to the client when another player enters the chat
+/// room of the player.
+#[derive(Clone, Debug, Packet, PrototypeElement)]
+#[header(0xdc, 0x00)]
+struct ChatRoomMemberJoinedPacket {
+    pub users: u16,
+    #[length_hint(24)]
+    pub name: String,
+}
+
+/// Sent by the map server to the client when a player leaves the chat room
+/// of the player, including the player themselves.
+#[derive(Clone, Debug, Packet, PrototypeElement)]
+#[header(0xdd, 0x00)]
+struct ChatRoomMemberLeftPacket {
+    pub users: u16,
+    #[length_hint(24)]
+    pub name: String,
+    pub kicked: u8,
+}
+
+/// Sent by the client to the map server when the player leaves the chat room
+/// they are in.
+#[derive(Clone, Debug, Packet, PrototypeElement, new)]
+#[header(0xe3, 0x00)]
+struct LeaveChatRoomPacket {}
+
 #[derive(Clone, Debug, Packet, PrototypeElement)]
 #[header(0x39, 0x01)]
 struct RequestPlayerAttackFailedPacket {
@@ -1156,6 +1750,21 @@ struct UpdateEntityHealthPointsPacket {
 enum DamageType {
 }*/
 
+#[derive(Clone, Debug, Packet, PrototypeElement)]
+#[header(0xde, 0x01)]
+struct SkillDamagePacket {
+    pub skill_id: u16,
+    pub source_entity_id: EntityId,
+    pub destination_entity_id: EntityId,
+    pub client_tick: ClientTick,
+    pub source_delay: u32,
+    pub destination_delay: u32,
+    pub damage_amount: i32,
+    pub skill_level: u16,
+    pub amount_of_hits: u16,
+    pub action: u8,
+}
+
 #[derive(Clone, Debug, Packet, PrototypeElement)]
 #[header(0xc8, 0x08)]
 struct DamagePacket {
@@ -1310,28 +1919,100 @@ struct EntityAppearedPacket {
     pub name: String,
 }
 
-pub struct EntityData {
+/// Sent by the map server to the client.
+/// Same as the [EntityAppearedPacket], but for entities that just came into
+/// existence, like a freshly summoned monster or a player that logged in.
+#[derive(Clone, Debug, Packet, PrototypeElement)]
+#[header(0xfe, 0x09)]
+struct EntitySpawnedPacket {
+    pub packet_length: u16,
+    pub object_type: u8,
     pub entity_id: EntityId,
+    pub group_id: u32, // may be reversed - or completely wrong
     pub movement_speed: u16,
+    pub body_state: u16,
+    pub health_state: u16,
+    pub effect_state: u32,
     pub job: u16,
-    pub position: Vector2<usize>,
-    pub destination: Option<Vector2<usize>>,
-    pub health_points: i32,
-    pub maximum_health_points: i32,
-    pub head_direction: usize,
-}
-
-impl EntityData {
-    pub fn from_character(character_information: CharacterInformation, position: Vector2<usize>) -> Self {
-        Self {
-            entity_id: EntityId(character_information.character_id.0), // TODO: should not mix like that
-            movement_speed: character_information.movement_speed as u16,
-            job: character_information.job as u16,
-            position,
-            destination: None,
-            health_points: character_information.health_points as i32,
-            maximum_health_points: character_information.maximum_health_points as i32,
+    pub head: u16,
+    pub weapon: u32,
+    pub shield: u32,
+    pub accessory: u16,
+    pub accessory2: u16,
+    pub accessory3: u16,
+    pub head_palette: u16,
+    pub body_palette: u16,
+    pub head_direction: u16,
+    pub robe: u16,
+    pub guild_id: u32, // may be reversed - or completely wrong
+    pub emblem_version: u16,
+    pub honor: u16,
+    pub virtue: u32,
+    pub is_pk_mode_on: u8,
+    pub sex: Sex,
+    pub position: WorldPosition,
+    pub x_size: u8,
+    pub y_size: u8,
+    pub c_level: u16,
+    pub font: u16,
+    pub maximum_health_points: i32,
+    pub health_points: i32,
+    pub is_boss: u8,
+    pub body: u16,
+    #[length_hint(24)]
+    pub name: String,
+}
+
+pub struct EntityData {
+    pub entity_id: EntityId,
+    pub movement_speed: u16,
+    pub job: u16,
+    pub position: Vector2<usize>,
+    pub destination: Option<Vector2<usize>>,
+    pub health_points: i32,
+    pub maximum_health_points: i32,
+    pub level: usize,
+    pub head_direction: usize,
+    pub body_state: u16,
+    pub health_state: u16,
+    pub effect_state: u32,
+    pub sex: Sex,
+    pub weapon: u32,
+    pub shield: u32,
+    pub head: u16,
+    pub head_palette: u16,
+    pub body_palette: u16,
+    pub head_top: u16,
+    pub head_middle: u16,
+    pub head_bottom: u16,
+    pub robe: u16,
+}
+
+impl EntityData {
+    pub fn from_character(character_information: CharacterInformation, position: Vector2<usize>) -> Self {
+        Self {
+            entity_id: EntityId(character_information.character_id.0), // TODO: should not mix like that
+            movement_speed: character_information.movement_speed as u16,
+            job: character_information.job as u16,
+            position,
+            destination: None,
+            health_points: character_information.health_points as i32,
+            maximum_health_points: character_information.maximum_health_points as i32,
+            level: character_information.level as usize,
             head_direction: 0, // TODO: get correct rotation
+            body_state: character_information.body_state as u16,
+            health_state: character_information.health_state as u16,
+            effect_state: character_information.effect_state as u32,
+            sex: character_information.sex,
+            weapon: character_information.weapon as u32,
+            shield: character_information.shield as u32,
+            head: character_information.head as u16,
+            head_palette: character_information.head_palette as u16,
+            body_palette: character_information.body_palette as u16,
+            head_top: character_information.accessory2 as u16,
+            head_middle: character_information.accessory3 as u16,
+            head_bottom: character_information.accessory as u16,
+            robe: character_information.robe as u16,
         }
     }
 }
@@ -1346,7 +2027,50 @@ impl From<EntityAppearedPacket> for EntityData {
             destination: None,
             health_points: packet.health_points,
             maximum_health_points: packet.maximum_health_points,
+            level: packet.c_level as usize,
+            head_direction: packet.head_direction as usize,
+            body_state: packet.body_state,
+            health_state: packet.health_state,
+            effect_state: packet.effect_state,
+            sex: packet.sex,
+            weapon: packet.weapon,
+            shield: packet.shield,
+            head: packet.head,
+            head_palette: packet.head_palette,
+            body_palette: packet.body_palette,
+            head_top: packet.accessory2,
+            head_middle: packet.accessory3,
+            head_bottom: packet.accessory,
+            robe: packet.robe,
+        }
+    }
+}
+
+impl From<EntitySpawnedPacket> for EntityData {
+    fn from(packet: EntitySpawnedPacket) -> Self {
+        Self {
+            entity_id: packet.entity_id,
+            movement_speed: packet.movement_speed,
+            job: packet.job,
+            position: packet.position.to_vector(),
+            destination: None,
+            health_points: packet.health_points,
+            maximum_health_points: packet.maximum_health_points,
+            level: packet.c_level as usize,
             head_direction: packet.head_direction as usize,
+            body_state: packet.body_state,
+            health_state: packet.health_state,
+            effect_state: packet.effect_state,
+            sex: packet.sex,
+            weapon: packet.weapon,
+            shield: packet.shield,
+            head: packet.head,
+            head_palette: packet.head_palette,
+            body_palette: packet.body_palette,
+            head_top: packet.accessory2,
+            head_middle: packet.accessory3,
+            head_bottom: packet.accessory,
+            robe: packet.robe,
         }
     }
 }
@@ -1363,13 +2087,27 @@ impl From<MovingEntityAppearedPacket> for EntityData {
             destination: Some(destination),
             health_points: packet.health_points,
             maximum_health_points: packet.maximum_health_points,
+            level: packet.c_level as usize,
             head_direction: packet.head_direction as usize,
+            body_state: packet.body_state,
+            health_state: packet.health_state,
+            effect_state: packet.effect_state,
+            sex: packet.sex,
+            weapon: packet.weapon,
+            shield: packet.shield,
+            head: packet.head,
+            head_palette: packet.head_palette,
+            body_palette: packet.body_palette,
+            head_top: packet.accessory2,
+            head_middle: packet.accessory3,
+            head_bottom: packet.accessory,
+            robe: packet.robe,
         }
     }
 }
 
 #[derive(Clone, Debug, ByteConvertable, PrototypeElement)]
-struct SkillInformation {
+pub struct SkillInformation {
     pub skill_id: u16,
     pub skill_type: u32,
     pub skill_level: u16,
@@ -1403,6 +2141,61 @@ struct UpdateHotkeysPacket {
     pub hotkeys: [HotkeyData; 38],
 }
 
+#[derive(Clone, Debug, Packet, PrototypeElement)]
+#[header(0xc6, 0x02)]
+struct PartyInvitationPacket {
+    pub party_id: u32,
+    #[length_hint(24)]
+    pub party_name: String,
+}
+
+/// Sent by the map server to the client regularly for every party member on
+/// the same map.
+#[derive(Clone, Debug, Packet, PrototypeElement)]
+#[header(0x07, 0x01)]
+struct PartyMemberPositionPacket {
+    pub account_id: AccountId,
+    pub x: u16,
+    pub y: u16,
+}
+
+/// Sent by the map server to all party members when a player left the party
+/// or was expelled from it.
+#[derive(Clone, Debug, Packet, PrototypeElement)]
+#[header(0x05, 0x01)]
+struct PartyMemberLeftPacket {
+    pub account_id: AccountId,
+    #[length_hint(24)]
+    pub character_name: String,
+    pub result: u8,
+}
+
+#[derive(Clone, Debug, ByteConvertable, PrototypeElement)]
+struct PartyMemberInformation {
+    pub account_id: AccountId,
+    pub character_id: CharacterId,
+    #[length_hint(24)]
+    pub character_name: String,
+    #[length_hint(16)]
+    pub map_name: String,
+    pub is_leader: u8,
+    pub is_offline: u8,
+    pub job: u16,
+    pub base_level: u16,
+}
+
+/// Sent by the map server whenever the members of the party change, for
+/// example when a member logs in or out.
+#[derive(Clone, Debug, Packet, PrototypeElement)]
+#[header(0xe5, 0x0a)]
+struct PartyInformationPacket {
+    pub packet_length: u16,
+    #[length_hint(24)]
+    pub party_name: String,
+    #[repeating((self.packet_length - 28) / 54)]
+    pub members: Vec<PartyMemberInformation>,
+}
+
 #[derive(Clone, Debug, Packet, PrototypeElement)]
 #[header(0xc9, 0x02)]
 struct UpdatePartyInvitationStatePacket {
@@ -1704,7 +2497,7 @@ enum RemoveItemReason {
 #[header(0xfa, 0x07)]
 struct RemoveItemFromInventoryPacket {
     pub remove_reason: RemoveItemReason,
-    pub index: u16,
+    pub index: ItemIndex,
     pub amount: u16,
 }
 
@@ -1921,6 +2714,339 @@ struct RequestUnequipItemStatusPacket {
     pub result: RequestUnequipItemStatus,
 }
 
+/// Sent by the map server when the player talks to a refine NPC.
+#[derive(Clone, Debug, Packet, PrototypeElement)]
+#[header(0xa0, 0x0a)]
+struct RefineWindowPacket {}
+
+/// Sent by the client to the map server when the player puts an item into the
+/// refine window.
+#[derive(Clone, Debug, Packet, PrototypeElement, new)]
+#[header(0xa1, 0x0a)]
+struct RefineAddItemPacket {
+    pub index: ItemIndex,
+}
+
+#[derive(Clone, Debug, ByteConvertable, PrototypeElement)]
+pub struct RefineMaterial {
+    pub item_id: ItemId,
+    /// Success chance in percent.
+    pub chance: u8,
+    pub zeny: u32,
+}
+
+/// Response to [RefineAddItemPacket], listing every material that can be used
+/// to refine the item.
+#[derive(Clone, Debug, Packet, PrototypeElement)]
+#[header(0xa2, 0x0a)]
+struct RefineItemInformationPacket {
+    pub packet_length: u16,
+    pub index: ItemIndex,
+    pub blacksmith_blessing: u8,
+    #[repeating((self.packet_length - 7) / 9)]
+    pub materials: Vec<RefineMaterial>,
+}
+
+#[derive(Clone, Debug, Packet, PrototypeElement, new)]
+#[header(0xa3, 0x0a)]
+struct RefineItemPacket {
+    pub index: ItemIndex,
+    pub material_id: ItemId,
+    pub use_blacksmith_blessing: u8,
+}
+
+#[derive(Clone, Debug, Packet, PrototypeElement, new)]
+#[header(0xa4, 0x0a)]
+struct RefineWindowClosePacket {}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ByteConvertable, PrototypeElement)]
+#[numeric_type(u16)]
+pub enum RefineResult {
+    Success,
+    Failure,
+    Downgrade,
+    FailureKeepItem,
+}
+
+#[derive(Clone, Debug, Packet, PrototypeElement)]
+#[header(0x88, 0x01)]
+struct RefineResultPacket {
+    pub result: RefineResult,
+    pub index: ItemIndex,
+    pub refinement_level: u16,
+}
+
+#[derive(Clone, Debug, ByteConvertable, PrototypeElement)]
+pub struct CraftableItem {
+    pub item_id: ItemId,
+    pub material_ids: [ItemId; 3],
+}
+
+/// Sent by the map server when the player uses a forging skill. Lists every
+/// item the player has the materials for.
+#[derive(Clone, Debug, Packet, PrototypeElement)]
+#[header(0x8d, 0x01)]
+struct CraftableItemListPacket {
+    pub packet_length: u16,
+    #[repeating((self.packet_length - 4) / 16)]
+    pub items: Vec<CraftableItem>,
+}
+
+#[derive(Clone, Debug, Packet, PrototypeElement, new)]
+#[header(0x8e, 0x01)]
+struct CraftItemPacket {
+    pub item_id: ItemId,
+    pub material_ids: [ItemId; 3],
+}
+
+#[derive(Clone, Debug, ByteConvertable, PrototypeElement)]
+#[numeric_type(u16)]
+enum CraftResult {
+    Success,
+    Failure,
+}
+
+#[derive(Clone, Debug, Packet, PrototypeElement)]
+#[header(0x8f, 0x01)]
+struct CraftResultPacket {
+    pub result: CraftResult,
+    pub item_id: ItemId,
+}
+
+/// Sent by the client to the map server when the player uses a card. The
+/// server responds with a [CardTargetListPacket].
+#[derive(Clone, Debug, Packet, PrototypeElement, new)]
+#[header(0x7a, 0x01)]
+struct RequestCardTargetsPacket {
+    pub card_index: ItemIndex,
+}
+
+#[derive(Clone, Debug, Packet, PrototypeElement)]
+#[header(0x7b, 0x01)]
+struct CardTargetListPacket {
+    pub packet_length: u16,
+    #[repeating((self.packet_length - 4) / 2)]
+    pub equipment_indices: Vec<ItemIndex>,
+}
+
+#[derive(Clone, Debug, Packet, PrototypeElement, new)]
+#[header(0x7c, 0x01)]
+struct InsertCardPacket {
+    pub card_index: ItemIndex,
+    pub equipment_index: ItemIndex,
+}
+
+#[derive(Clone, Debug, ByteConvertable, PrototypeElement)]
+enum InsertCardResult {
+    Success,
+    Failure,
+}
+
+#[derive(Clone, Debug, Packet, PrototypeElement)]
+#[header(0x7d, 0x01)]
+struct InsertCardResultPacket {
+    pub equipment_index: ItemIndex,
+    pub card_index: ItemIndex,
+    pub result: InsertCardResult,
+}
+
+#[derive(Clone, Debug, Packet, PrototypeElement, new)]
+#[header(0x39, 0x04)]
+struct UseItemPacket {
+    pub index: ItemIndex,
+    pub account_id: AccountId,
+}
+
+/// Sent by the map server when a player nearby used an item. For the
+/// player's own items, the amount is what is left of the stack.
+#[derive(Clone, Debug, Packet, PrototypeElement)]
+#[header(0xc8, 0x01)]
+struct UseItemResponsePacket {
+    pub index: ItemIndex,
+    pub item_id: ItemId,
+    pub account_id: AccountId,
+    pub amount: u16,
+    pub success: u8,
+}
+
+/// Sent by the client to the map server when the player drops an item on the
+/// ground.
+#[derive(Clone, Debug, Packet, PrototypeElement, new)]
+#[header(0x63, 0x03)]
+struct DropItemPacket {
+    pub index: ItemIndex,
+    pub amount: u16,
+}
+
+/// Sent by the map server after the player used a taming item.
+#[derive(Clone, Debug, Packet, PrototypeElement)]
+#[header(0x9e, 0x01)]
+struct StartPetCapturePacket {}
+
+#[derive(Clone, Debug, Packet, PrototypeElement, new)]
+#[header(0x9f, 0x01)]
+struct CapturePetPacket {
+    pub target_id: EntityId,
+}
+
+#[derive(Clone, Debug, ByteConvertable, PrototypeElement)]
+enum PetCaptureResult {
+    Failure,
+    Success,
+}
+
+#[derive(Clone, Debug, Packet, PrototypeElement)]
+#[header(0xa0, 0x01)]
+struct PetCaptureResultPacket {
+    pub result: PetCaptureResult,
+}
+
+/// Sent by the map server after the player used a pet incubator.
+#[derive(Clone, Debug, Packet, PrototypeElement)]
+#[header(0xa6, 0x01)]
+struct PetEggListPacket {
+    pub packet_length: u16,
+    #[repeating((self.packet_length - 4) / 2)]
+    pub egg_indices: Vec<ItemIndex>,
+}
+
+#[derive(Clone, Debug, Packet, PrototypeElement, new)]
+#[header(0xa7, 0x01)]
+struct SelectPetEggPacket {
+    pub index: ItemIndex,
+}
+
+#[derive(Clone, Debug, Packet, PrototypeElement)]
+#[header(0xa2, 0x01)]
+struct PetPropertiesPacket {
+    #[length_hint(24)]
+    pub name: String,
+    pub renamed: u8,
+    pub level: u16,
+    pub hunger: u16,
+    pub intimacy: u16,
+    pub accessory_id: u16,
+    pub class: u16,
+}
+
+#[derive(Clone, Debug, ByteConvertable, PrototypeElement)]
+enum PetStatusChangeType {
+    Spawn,
+    Intimacy,
+    Hunger,
+    Accessory,
+    Performance,
+    Look,
+}
+
+#[derive(Clone, Debug, Packet, PrototypeElement)]
+#[header(0xa4, 0x01)]
+struct PetStatusChangePacket {
+    pub change_type: PetStatusChangeType,
+    pub entity_id: EntityId,
+    pub value: i32,
+}
+
+#[derive(Clone, Copy, Debug, ByteConvertable, PrototypeElement)]
+pub enum PetCommand {
+    Information,
+    Feed,
+    Perform,
+    ReturnToEgg,
+    UnequipAccessory,
+}
+
+#[derive(Clone, Debug, Packet, PrototypeElement, new)]
+#[header(0xa1, 0x01)]
+struct PetCommandPacket {
+    pub command: PetCommand,
+}
+
+#[derive(Clone, Debug)]
+pub struct PetStatus {
+    pub name: String,
+    pub level: usize,
+    /// Fullness from 0 to 100.
+    pub hunger: usize,
+    /// Intimacy from 0 to 1000.
+    pub intimacy: usize,
+    pub accessory: Option<ItemId>,
+}
+
+impl PetStatus {
+    pub fn hunger_description(&self) -> &'static str {
+        match self.hunger {
+            0..=10 => "very hungry",
+            11..=25 => "hungry",
+            26..=75 => "neutral",
+            76..=90 => "satisfied",
+            _ => "stuffed",
+        }
+    }
+
+    pub fn intimacy_description(&self) -> &'static str {
+        match self.intimacy {
+            0..=99 => "awkward",
+            100..=249 => "shy",
+            250..=749 => "neutral",
+            750..=909 => "cordial",
+            _ => "loyal",
+        }
+    }
+}
+
+/// Sent by the client to the map server when the player uses a skill on an
+/// entity.
+#[derive(Clone, Debug, Packet, PrototypeElement, new)]
+#[header(0x38, 0x04)]
+struct UseSkillPacket {
+    pub skill_level: u16,
+    pub skill_id: u16,
+    pub target_id: EntityId,
+}
+
+/// Sent by the client to the map server when the player uses a skill on a
+/// cell.
+#[derive(Clone, Debug, Packet, PrototypeElement, new)]
+#[header(0x66, 0x03)]
+struct UseGroundSkillPacket {
+    pub skill_level: u16,
+    pub skill_id: u16,
+    pub position_x: u16,
+    pub position_y: u16,
+}
+
+/// Sent by the map server when an entity starts casting a skill.
+#[derive(Clone, Debug, Packet, PrototypeElement)]
+#[header(0xfb, 0x07)]
+struct SkillCastPacket {
+    pub source_entity_id: EntityId,
+    pub destination_entity_id: EntityId,
+    pub position_x: u16,
+    pub position_y: u16,
+    pub skill_id: u16,
+    pub element: u32,
+    /// Cast time in milliseconds.
+    pub delay_time: u32,
+    pub disposable: u8,
+}
+
+/// Sent by the map server when the cast of an entity is interrupted.
+#[derive(Clone, Debug, Packet, PrototypeElement)]
+#[header(0xb9, 0x01)]
+struct CastCancelledPacket {
+    pub entity_id: EntityId,
+}
+
+/// Sent by the map server after the player used a skill that has a cooldown.
+#[derive(Clone, Debug, Packet, PrototypeElement)]
+#[header(0x3d, 0x04)]
+struct SkillCooldownPacket {
+    pub skill_id: u16,
+    /// Cooldown in milliseconds.
+    pub delay_time: u32,
+}
+
 #[derive(new)]
 struct NetworkTimer {
     period: Duration,
@@ -1950,7 +3076,13 @@ struct LoginData {
 
 pub struct NetworkingSystem {
     login_settings: LoginSettings,
-    login_stream: TcpStream,
+    login_server_address: String,
+    /// Connected when logging in, so an unreachable login server shows up as
+    /// an error in the interface rather than stopping the client.
+    login_stream: Option<TcpStream>,
+    /// Response of the login server, kept until the player picks one of the
+    /// character servers in it.
+    pending_login: Option<LoginServerLoginSuccessPacket>,
     character_stream: Option<TcpStream>,
     map_stream: Option<TcpStream>,
     login_data: Option<LoginData>,
@@ -1960,19 +3092,37 @@ pub struct NetworkingSystem {
     character_keep_alive_timer: NetworkTimer,
     map_keep_alive_timer: NetworkTimer,
     player_name: String,
+    /// Recipients of whispers that the map server did not respond to yet, in
+    /// the order they were sent.
+    pending_whispers: VecDeque<String>,
+    /// The chat room the player is in. Messages of other players only come
+    /// from inside of the room while this is set.
+    chat_room: Option<u32>,
+    pending_card: Option<ItemIndex>,
+    /// The pet of the player, as announced when it spawns. Status changes of
+    /// other pets nearby are sent as well.
+    pet_entity_id: Option<EntityId>,
+    /// When the last [RequestServerTickPacket] was sent, to measure the
+    /// latency once the map server answers.
+    server_tick_requested: Option<Instant>,
+    latency: Option<Duration>,
     #[cfg(feature = "debug_network")]
     packet_history: TrackedState<Vec<PacketEntry>>,
+    #[cfg(feature = "debug_network")]
+    statistics_recorder: NetworkStatisticsRecorder,
 }
 
 impl NetworkingSystem {
-    pub fn new() -> Self {
-        let login_server_ip = match cfg!(feature = "local") {
+    pub fn new(login_server_address: Option<&str>) -> Self {
+        let default_login_server_address = match cfg!(feature = "local") {
             true => "127.0.0.1:6900",
             false => "167.235.227.244:6900",
         };
+        let login_server_address = login_server_address.unwrap_or(default_login_server_address).to_owned();
 
         let login_settings = LoginSettings::new();
-        let login_stream = TcpStream::connect(login_server_ip).expect("failed to connect to login server");
+        let login_stream = None;
+        let pending_login = None;
 
         let character_stream = None;
         let map_stream = None;
@@ -1983,14 +3133,22 @@ impl NetworkingSystem {
         let character_keep_alive_timer = NetworkTimer::new(Duration::from_secs(10));
         let map_keep_alive_timer = NetworkTimer::new(Duration::from_secs(4));
         let player_name = String::new();
+        let pending_whispers = VecDeque::new();
+        let chat_room = None;
+        let pending_card = None;
+        let pet_entity_id = None;
+        let server_tick_requested = None;
+        let latency = None;
         #[cfg(feature = "debug_network")]
         let packet_history = TrackedState::default();
-
-        login_stream.set_read_timeout(Duration::from_secs(1).into()).unwrap();
+        #[cfg(feature = "debug_network")]
+        let statistics_recorder = NetworkStatisticsRecorder::new();
 
         Self {
             login_settings,
+            login_server_address,
             login_stream,
+            pending_login,
             character_stream,
             move_request,
             login_data,
@@ -2000,11 +3158,23 @@ impl NetworkingSystem {
             character_keep_alive_timer,
             map_keep_alive_timer,
             player_name,
+            pending_whispers,
+            chat_room,
+            pending_card,
+            pet_entity_id,
+            server_tick_requested,
+            latency,
             #[cfg(feature = "debug_network")]
             packet_history,
+            #[cfg(feature = "debug_network")]
+            statistics_recorder,
         }
     }
 
+    pub fn get_characters(&self) -> Remote<Vec<CharacterInformation>> {
+        self.characters.new_remote()
+    }
+
     pub fn get_login_settings(&self) -> &LoginSettings {
         &self.login_settings
     }
@@ -2017,13 +3187,74 @@ impl NetworkingSystem {
         self.login_settings.remember_password = !self.login_settings.remember_password;
     }
 
-    pub fn log_in(&mut self, username: String, password: String) -> Result<CharacterSelectionWindow, String> {
+    pub fn set_password_encryption(&mut self, password_encryption: PasswordEncryption) {
+        self.login_settings.password_encryption = password_encryption;
+    }
+
+    fn connect_to_login_server(&mut self) -> Result<(), String> {
+        if self.login_stream.is_some() {
+            return Ok(());
+        }
+
+        let login_stream = TcpStream::connect(&self.login_server_address)
+            .map_err(|_| format!("failed to connect to login server at {}", self.login_server_address))?;
+        login_stream.set_read_timeout(Duration::from_secs(1).into()).unwrap();
+
+        self.login_stream = Some(login_stream);
+        Ok(())
+    }
+
+    /// Logs into the login server and returns the list of character servers
+    /// to pick from.
+    pub fn log_in(&mut self, username: String, password: String) -> Result<ServerSelectionWindow, String> {
         #[cfg(feature = "debug_network")]
         let timer = Timer::new("log in");
 
-        self.send_packet_to_login_server(LoginServerLoginPacket::new(username.clone(), password.clone()));
+        let result = self.try_log_in(username, password);
+
+        // The login server might close the connection after a failed attempt, so the
+        // next one starts from a new connection.
+        if result.is_err() {
+            self.login_stream = None;
+        }
+
+        #[cfg(feature = "debug_network")]
+        timer.stop();
+
+        result
+    }
+
+    /// Drops the connection to the login server, so the next login starts
+    /// from a new one.
+    pub fn disconnect_from_login_server(&mut self) {
+        self.login_stream = None;
+    }
+
+    fn try_log_in(&mut self, username: String, password: String) -> Result<ServerSelectionWindow, String> {
+        self.connect_to_login_server()?;
+
+        let password_encryption = self.login_settings.password_encryption;
+        match password_encryption {
+            PasswordEncryption::Plain => {
+                self.send_packet_to_login_server(LoginServerLoginPacket::new(username.clone(), password.clone()))?;
+            }
+            _ => {
+                self.send_packet_to_login_server(RequestPasswordKeyPacket::default())?;
+
+                let response = self.get_data_from_login_server()?;
+                let mut byte_stream = ByteStream::new(&response);
+                let password_key_packet =
+                    PasswordKeyPacket::try_from_bytes(&mut byte_stream).map_err(|_| "login server doesn't support hashed passwords")?;
+
+                #[cfg(feature = "debug_network")]
+                byte_stream.transfer_packet_history(&mut self.packet_history, &mut self.statistics_recorder);
 
-        let response = self.get_data_from_login_server();
+                let password_hash = password_encryption.hash(&password, &password_key_packet.key).unwrap();
+                self.send_packet_to_login_server(LoginServerHashedLoginPacket::new(username.clone(), password_hash))?;
+            }
+        }
+
+        let response = self.get_data_from_login_server()?;
         let mut byte_stream = ByteStream::new(&response);
 
         if let Ok(login_failed_packet) = LoginFailedPacket::try_from_bytes(&mut byte_stream) {
@@ -2048,7 +3279,16 @@ impl NetworkingSystem {
             }
         }
 
-        let login_server_login_success_packet = LoginServerLoginSuccessPacket::try_from_bytes(&mut byte_stream).unwrap();
+        let login_server_login_success_packet =
+            LoginServerLoginSuccessPacket::try_from_bytes(&mut byte_stream).map_err(|_| "unexpected response from login server")?;
+
+        #[cfg(feature = "debug_network")]
+        byte_stream.transfer_packet_history(&mut self.packet_history, &mut self.statistics_recorder);
+
+        if login_server_login_success_packet.character_server_information.is_empty() {
+            return Err("no character server available".to_string());
+        }
+
         self.login_data = LoginData::new(
             login_server_login_success_packet.account_id,
             login_server_login_success_packet.login_id1,
@@ -2056,10 +3296,46 @@ impl NetworkingSystem {
         )
         .into();
 
+        let servers = login_server_login_success_packet
+            .character_server_information
+            .iter()
+            .map(|information| {
+                let name = String::from_utf8_lossy(&information.server_name);
+                (name.trim_end_matches('\0').to_owned(), information.user_count as usize)
+            })
+            .collect();
+
+        self.pending_login = Some(login_server_login_success_packet);
+
+        self.login_settings.username = match self.login_settings.remember_username {
+            true => username,
+            // clear in case it was previously saved
+            false => String::new(),
+        };
+
+        self.login_settings.password = match self.login_settings.remember_password {
+            true => password,
+            // clear in case it was previously saved
+            false => String::new(),
+        };
+
+        Ok(ServerSelectionWindow::new(servers))
+    }
+
+    /// The previews are rendered outside of the networking system, since they
+    /// need the entities of the characters.
+    pub fn select_character_server(
+        &mut self,
+        index: usize,
+        character_previews: TrackedState<Vec<(usize, Remote<ImageBuffer>)>>,
+    ) -> Result<CharacterSelectionWindow, String> {
+        #[cfg(feature = "debug_network")]
+        let timer = Timer::new("select character server");
+
+        let login_server_login_success_packet = self.pending_login.take().ok_or("not logged in")?;
         let character_server_information = login_server_login_success_packet
             .character_server_information
-            .into_iter()
-            .next()
+            .get(index)
             .ok_or("no character server available")?;
 
         let server_ip = IpAddr::V4(character_server_information.server_ip);
@@ -2080,9 +3356,6 @@ impl NetworkingSystem {
             .write_all(&character_server_login_packet.to_bytes())
             .map_err(|_| "failed to send packet to character server")?;
 
-        #[cfg(feature = "debug_network")]
-        byte_stream.transfer_packet_history(&mut self.packet_history);
-
         let response = self.get_data_from_character_server();
 
         let mut byte_stream = ByteStream::new(&response);
@@ -2105,7 +3378,7 @@ impl NetworkingSystem {
         self.send_packet_to_character_server(RequestCharacterListPacket::default());
 
         #[cfg(feature = "debug_network")]
-        byte_stream.transfer_packet_history(&mut self.packet_history);
+        byte_stream.transfer_packet_history(&mut self.packet_history, &mut self.statistics_recorder);
 
         let response = self.get_data_from_character_server();
         let mut byte_stream = ByteStream::new(&response);
@@ -2113,20 +3386,8 @@ impl NetworkingSystem {
         let request_character_list_success_packet = RequestCharacterListSuccessPacket::try_from_bytes(&mut byte_stream).unwrap();
         self.characters.set(request_character_list_success_packet.character_information);
 
-        self.login_settings.username = match self.login_settings.remember_username {
-            true => username,
-            // clear in case it was previously saved
-            false => String::new(),
-        };
-
-        self.login_settings.password = match self.login_settings.remember_password {
-            true => password,
-            // clear in case it was previously saved
-            false => String::new(),
-        };
-
         #[cfg(feature = "debug_network")]
-        byte_stream.transfer_packet_history(&mut self.packet_history);
+        byte_stream.transfer_packet_history(&mut self.packet_history, &mut self.statistics_recorder);
 
         #[cfg(feature = "debug_network")]
         timer.stop();
@@ -2134,6 +3395,7 @@ impl NetworkingSystem {
         Ok(CharacterSelectionWindow::new(
             self.characters.clone(),
             self.move_request.clone(),
+            character_previews,
             character_server_login_success_packet.normal_slot_count as usize,
         ))
     }
@@ -2142,24 +3404,30 @@ impl NetworkingSystem {
         #[cfg(feature = "debug_network")]
         let timer = Timer::new("log out");
 
+        self.chat_room = None;
+
         #[cfg(feature = "debug_network")]
         timer.stop();
 
         Ok(())
     }
 
-    fn send_packet_to_login_server<T>(&mut self, packet: T)
+    fn send_packet_to_login_server<T>(&mut self, packet: T) -> Result<(), String>
     where
         T: Packet + 'static,
     {
         #[cfg(feature = "debug_network")]
-        self.packet_history
-            .push(PacketEntry::new_outgoing(&packet, T::PACKET_NAME, T::IS_PING));
+        let serialization_start = Instant::now();
 
         let packet_bytes = packet.to_bytes();
-        self.login_stream
+
+        #[cfg(feature = "debug_network")]
+        self.record_outgoing_packet(&packet, &packet_bytes, serialization_start.elapsed());
+
+        let login_stream = self.login_stream.as_mut().ok_or("no login server connection")?;
+        login_stream
             .write_all(&packet_bytes)
-            .expect("failed to send packet to login server");
+            .map_err(|_| "failed to send packet to login server".to_string())
     }
 
     fn send_packet_to_character_server<T>(&mut self, packet: T)
@@ -2167,10 +3435,13 @@ impl NetworkingSystem {
         T: Packet + 'static,
     {
         #[cfg(feature = "debug_network")]
-        self.packet_history
-            .push(PacketEntry::new_outgoing(&packet, T::PACKET_NAME, T::IS_PING));
+        let serialization_start = Instant::now();
 
         let packet_bytes = packet.to_bytes();
+
+        #[cfg(feature = "debug_network")]
+        self.record_outgoing_packet(&packet, &packet_bytes, serialization_start.elapsed());
+
         let character_stream = self.character_stream.as_mut().expect("no character server connection");
         character_stream
             .write_all(&packet_bytes)
@@ -2182,21 +3453,42 @@ impl NetworkingSystem {
         T: Packet + 'static,
     {
         #[cfg(feature = "debug_network")]
-        self.packet_history
-            .push(PacketEntry::new_outgoing(&packet, T::PACKET_NAME, T::IS_PING));
+        let serialization_start = Instant::now();
 
         let packet_bytes = packet.to_bytes();
+
+        #[cfg(feature = "debug_network")]
+        self.record_outgoing_packet(&packet, &packet_bytes, serialization_start.elapsed());
+
         let map_stream = self.map_stream.as_mut().expect("no map server connection");
         map_stream.write_all(&packet_bytes).expect("failed to send packet to map server");
     }
 
-    fn get_data_from_login_server(&mut self) -> Vec<u8> {
+    #[cfg(feature = "debug_network")]
+    fn record_outgoing_packet<T: Packet + 'static>(&mut self, packet: &T, packet_bytes: &[u8], serialization_time: Duration) {
+        self.packet_history.push(PacketEntry::new_outgoing(
+            packet,
+            T::header(),
+            T::PACKET_NAME,
+            packet_bytes.to_vec(),
+            T::IS_PING,
+        ));
+        self.statistics_recorder.record(PacketRecord {
+            header: T::header(),
+            name: T::PACKET_NAME,
+            direction: PacketDirection::Outgoing,
+            size: packet_bytes.len(),
+            serialization_time,
+        });
+    }
+
+    fn get_data_from_login_server(&mut self) -> Result<Vec<u8>, String> {
         let mut buffer = [0; 4096];
-        let response_lenght = self
-            .login_stream
+        let login_stream = self.login_stream.as_mut().ok_or("no login server connection")?;
+        let response_lenght = login_stream
             .read(&mut buffer)
-            .expect("failed to get response from login server");
-        buffer[..response_lenght].to_vec()
+            .map_err(|_| "failed to get response from login server")?;
+        Ok(buffer[..response_lenght].to_vec())
     }
 
     fn get_data_from_character_server(&mut self) -> Vec<u8> {
@@ -2236,8 +3528,13 @@ impl NetworkingSystem {
     }
 
     pub fn keep_alive(&mut self, delta_time: f64, client_tick: ClientTick) {
-        if self.login_keep_alive_timer.update(delta_time) {
-            self.send_packet_to_login_server(LoginServerKeepalivePacket::default());
+        // A lost connection to the login server only matters for the next login, which
+        // connects again.
+        if self.login_keep_alive_timer.update(delta_time)
+            && self.login_stream.is_some()
+            && self.send_packet_to_login_server(LoginServerKeepalivePacket::default()).is_err()
+        {
+            self.login_stream = None;
         }
 
         if self.character_keep_alive_timer.update(delta_time) && self.character_stream.is_some() {
@@ -2246,9 +3543,15 @@ impl NetworkingSystem {
 
         if self.map_keep_alive_timer.update(delta_time) && self.map_stream.is_some() {
             self.send_packet_to_map_server(RequestServerTickPacket::new(client_tick));
+            self.server_tick_requested = Some(Instant::now());
         }
     }
 
+    /// Round trip time of the last server tick request.
+    pub fn get_latency(&self) -> Option<Duration> {
+        self.latency
+    }
+
     pub fn create_character(&mut self, slot: usize, name: String) -> Result<(), String> {
         let hair_color = 0;
         let hair_style = 0;
@@ -2276,7 +3579,7 @@ impl NetworkingSystem {
         let create_character_success_packet = CreateCharacterSuccessPacket::try_from_bytes(&mut byte_stream).unwrap();
 
         #[cfg(feature = "debug_network")]
-        byte_stream.transfer_packet_history(&mut self.packet_history);
+        byte_stream.transfer_packet_history(&mut self.packet_history, &mut self.statistics_recorder);
 
         self.characters.push(create_character_success_packet.character_information);
         Ok(())
@@ -2301,7 +3604,7 @@ impl NetworkingSystem {
         CharacterDeletionSuccessPacket::try_from_bytes(&mut byte_stream).unwrap();
 
         #[cfg(feature = "debug_network")]
-        byte_stream.transfer_packet_history(&mut self.packet_history);
+        byte_stream.transfer_packet_history(&mut self.packet_history, &mut self.statistics_recorder);
 
         self.characters.retain(|character| character.character_id != character_id);
         Ok(())
@@ -2342,6 +3645,7 @@ impl NetworkingSystem {
         self.map_stream = TcpStream::connect_timeout(&socket_address, Duration::from_secs(1))
             .map_err(|_| "Failed to connect to map server. Please try again")?
             .into();
+        self.chat_room = None;
 
         let login_data = self.login_data.as_ref().unwrap();
         self.send_packet_to_map_server(MapServerLoginPacket::new(
@@ -2353,7 +3657,7 @@ impl NetworkingSystem {
         ));
 
         #[cfg(feature = "debug_network")]
-        byte_stream.transfer_packet_history(&mut self.packet_history);
+        byte_stream.transfer_packet_history(&mut self.packet_history, &mut self.statistics_recorder);
 
         let response = self.get_data_from_map_server();
         let mut byte_stream = ByteStream::new(&response);
@@ -2361,7 +3665,7 @@ impl NetworkingSystem {
         let _packet8302 = Packet8302::try_from_bytes(&mut byte_stream).unwrap();
 
         #[cfg(feature = "debug_network")]
-        byte_stream.transfer_packet_history(&mut self.packet_history);
+        byte_stream.transfer_packet_history(&mut self.packet_history, &mut self.statistics_recorder);
 
         let response = self.get_data_from_map_server();
         let mut byte_stream = ByteStream::new(&response);
@@ -2371,7 +3675,11 @@ impl NetworkingSystem {
 
         let mut chat_messages = chat_messages.borrow_mut();
         while let Ok(server_message_packet) = ServerMessagePacket::try_from_bytes(&mut byte_stream) {
-            chat_messages.push(ChatMessage::new(server_message_packet.message, Color::rgb(230, 230, 200)));
+            chat_messages.push(ChatMessage::new(
+                server_message_packet.message,
+                Color::rgb(230, 230, 200),
+                ChatChannel::Server,
+            ));
         }
 
         let change_map_packet = ChangeMapPacket::try_from_bytes(&mut byte_stream).unwrap();
@@ -2386,7 +3694,7 @@ impl NetworkingSystem {
         self.player_name = character_information.name.clone();
 
         #[cfg(feature = "debug_network")]
-        byte_stream.transfer_packet_history(&mut self.packet_history);
+        byte_stream.transfer_packet_history(&mut self.packet_history, &mut self.statistics_recorder);
 
         Ok((
             change_map_packet.map_name.replace(".gat", ""),
@@ -2434,7 +3742,7 @@ impl NetworkingSystem {
         }
 
         #[cfg(feature = "debug_network")]
-        byte_stream.transfer_packet_history(&mut self.packet_history);
+        byte_stream.transfer_packet_history(&mut self.packet_history, &mut self.statistics_recorder);
 
         self.move_request.take();
         Ok(())
@@ -2460,6 +3768,49 @@ impl NetworkingSystem {
         self.send_packet_to_map_server(RequestActionPacket::new(entity_id, Action::Attack));
     }
 
+    pub fn request_trade(&mut self, entity_id: EntityId) {
+        self.send_packet_to_map_server(RequestTradePacket::new(entity_id));
+    }
+
+    fn trade_request_result_event(result: TradeRequestResult) -> NetworkEvent {
+        let error = match result {
+            TradeRequestResult::Accepted => return NetworkEvent::TradeStarted,
+            TradeRequestResult::TooFarAway => "the player is too far away to trade",
+            TradeRequestResult::CharacterNotFound => "the player does not exist",
+            TradeRequestResult::Failed => "the trade request failed",
+            TradeRequestResult::Declined => "the trade was declined",
+            TradeRequestResult::Busy => "the player is already trading",
+        };
+
+        let chat_message = ChatMessage::new(error.to_owned(), Color::rgb(255, 100, 100), ChatChannel::Server);
+        NetworkEvent::ChatMessage(chat_message)
+    }
+
+    pub fn respond_to_trade_request(&mut self, accept: bool) {
+        let response = match accept {
+            true => TradeResponse::Accept,
+            false => TradeResponse::Decline,
+        };
+
+        self.send_packet_to_map_server(RespondToTradeRequestPacket::new(response));
+    }
+
+    pub fn add_trade_item(&mut self, item_index: ItemIndex, amount: u16) {
+        self.send_packet_to_map_server(AddTradeItemPacket::new(item_index, amount as u32));
+    }
+
+    pub fn lock_trade(&mut self) {
+        self.send_packet_to_map_server(LockTradePacket::default());
+    }
+
+    pub fn cancel_trade(&mut self) {
+        self.send_packet_to_map_server(CancelTradePacket::default());
+    }
+
+    pub fn commit_trade(&mut self) {
+        self.send_packet_to_map_server(CommitTradePacket::default());
+    }
+
     pub fn send_message(&mut self, message: String) {
         let complete_message = format!("{} : {}", self.player_name, message);
 
@@ -2469,6 +3820,54 @@ impl NetworkingSystem {
         ));
     }
 
+    pub fn send_party_message(&mut self, message: String) {
+        let complete_message = format!("{} : {}", self.player_name, message);
+
+        self.send_packet_to_map_server(PartyMessagePacket::new(
+            complete_message.bytes().len() as u16 + 5,
+            complete_message,
+        ));
+    }
+
+    pub fn send_guild_message(&mut self, message: String) {
+        let complete_message = format!("{} : {}", self.player_name, message);
+
+        self.send_packet_to_map_server(GuildMessagePacket::new(
+            complete_message.bytes().len() as u16 + 5,
+            complete_message,
+        ));
+    }
+
+    /// Sends a whisper and returns the message to display for it, since the
+    /// map server doesn't echo whispers back to the sender.
+    pub fn send_whisper(&mut self, recipient_name: String, message: String) -> ChatMessage {
+        self.send_packet_to_map_server(WhisperPacket::new(
+            message.bytes().len() as u16 + 29,
+            recipient_name.clone(),
+            message.clone(),
+        ));
+
+        self.pending_whispers.push_back(recipient_name.clone());
+
+        let text = format!("(To {}): {}", recipient_name, message);
+        ChatMessage::whisper(text, Color::rgb(255, 255, 100), recipient_name)
+    }
+
+    pub fn enter_chat_room(&mut self, room_id: u32, password: String) {
+        self.send_packet_to_map_server(EnterChatRoomPacket::new(room_id, password));
+    }
+
+    pub fn leave_chat_room(&mut self) {
+        self.send_packet_to_map_server(LeaveChatRoomPacket::new());
+    }
+
+    fn message_channel(&self) -> ChatChannel {
+        match self.chat_room {
+            Some(_) => ChatChannel::Room,
+            None => ChatChannel::Public,
+        }
+    }
+
     pub fn start_dialog(&mut self, npc_id: EntityId) {
         self.send_packet_to_map_server(StartDialogPacket::new(npc_id));
     }
@@ -2493,22 +3892,179 @@ impl NetworkingSystem {
         self.send_packet_to_map_server(RequestUnequipItemPacket::new(item_index));
     }
 
+    pub fn request_refine_materials(&mut self, item_index: ItemIndex) {
+        self.send_packet_to_map_server(RefineAddItemPacket::new(item_index));
+    }
+
+    pub fn request_refine(&mut self, item_index: ItemIndex, material_id: ItemId) {
+        self.send_packet_to_map_server(RefineItemPacket::new(item_index, material_id, 0));
+    }
+
+    pub fn close_refine_window(&mut self) {
+        self.send_packet_to_map_server(RefineWindowClosePacket::new());
+    }
+
+    pub fn request_craft(&mut self, item_id: ItemId, material_ids: [ItemId; 3]) {
+        self.send_packet_to_map_server(CraftItemPacket::new(item_id, material_ids));
+    }
+
+    pub fn request_card_targets(&mut self, card_index: ItemIndex) {
+        self.pending_card = Some(card_index);
+        self.send_packet_to_map_server(RequestCardTargetsPacket::new(card_index));
+    }
+
+    pub fn insert_card(&mut self, card_index: ItemIndex, equipment_index: ItemIndex) {
+        self.send_packet_to_map_server(InsertCardPacket::new(card_index, equipment_index));
+    }
+
+    pub fn request_item_use(&mut self, item_index: ItemIndex) {
+        let account_id = self.login_data.as_ref().unwrap().account_id;
+        self.send_packet_to_map_server(UseItemPacket::new(item_index, account_id));
+    }
+
+    pub fn request_item_drop(&mut self, item_index: ItemIndex, amount: u16) {
+        self.send_packet_to_map_server(DropItemPacket::new(item_index, amount));
+    }
+
+    pub fn request_pet_capture(&mut self, entity_id: EntityId) {
+        self.send_packet_to_map_server(CapturePetPacket::new(entity_id));
+    }
+
+    pub fn select_pet_egg(&mut self, item_index: ItemIndex) {
+        self.send_packet_to_map_server(SelectPetEggPacket::new(item_index));
+    }
+
+    pub fn send_pet_command(&mut self, command: PetCommand) {
+        if let PetCommand::ReturnToEgg = command {
+            self.pet_entity_id = None;
+        }
+
+        self.send_packet_to_map_server(PetCommandPacket::new(command));
+    }
+
+    pub fn request_skill_use(&mut self, skill_id: u16, skill_level: u16, target_id: EntityId) {
+        self.send_packet_to_map_server(UseSkillPacket::new(skill_level, skill_id, target_id));
+    }
+
+    pub fn request_ground_skill_use(&mut self, skill_id: u16, skill_level: u16, position: Vector2<usize>) {
+        self.send_packet_to_map_server(UseGroundSkillPacket::new(
+            skill_level,
+            skill_id,
+            position.x as u16,
+            position.y as u16,
+        ));
+    }
+
     pub fn network_events(&mut self) -> Vec<NetworkEvent> {
         let mut events = Vec::new();
 
+        #[cfg(feature = "debug_network")]
+        self.statistics_recorder.update();
+
         while let Some(data) = self.try_get_data_from_map_server() {
             let mut byte_stream = ByteStream::new(&data);
 
             while !byte_stream.is_empty() {
                 if let Ok(packet) = BroadcastMessagePacket::try_from_bytes(&mut byte_stream) {
-                    let chat_message = ChatMessage::new(packet.message, packet.font_color.into());
+                    let chat_message = ChatMessage::new(packet.message, packet.font_color.into(), ChatChannel::Broadcast);
                     events.push(NetworkEvent::ChatMessage(chat_message));
                 } else if let Ok(packet) = ServerMessagePacket::try_from_bytes(&mut byte_stream) {
-                    let chat_message = ChatMessage::new(packet.message, Color::monochrome(255));
+                    // The server echoes messages of the player with this packet as well.
+                    let channel = match self.chat_room {
+                        Some(_) => ChatChannel::Room,
+                        None => ChatChannel::Server,
+                    };
+                    let chat_message = ChatMessage::new(packet.message, Color::monochrome(255), channel);
+                    events.push(NetworkEvent::ChatMessage(chat_message));
+                } else if let Ok(packet) = PlayerMessagePacket::try_from_bytes(&mut byte_stream) {
+                    let chat_message = ChatMessage::new(packet.message, Color::monochrome(255), self.message_channel());
                     events.push(NetworkEvent::ChatMessage(chat_message));
+                } else if let Ok(packet) = ChatRoomEntryPacket::try_from_bytes(&mut byte_stream) {
+                    let chat_room = ChatRoom {
+                        room_id: packet.room_id,
+                        title: packet.title,
+                        users: packet.users as usize,
+                        limit: packet.limit as usize,
+                        private: packet.room_type == ChatRoomType::Private,
+                    };
+                    events.push(NetworkEvent::UpdateChatRoom(packet.owner_id, chat_room));
+                } else if let Ok(packet) = ChatRoomChangedPacket::try_from_bytes(&mut byte_stream) {
+                    let chat_room = ChatRoom {
+                        room_id: packet.room_id,
+                        title: packet.title,
+                        users: packet.users as usize,
+                        limit: packet.limit as usize,
+                        private: packet.room_type == ChatRoomType::Private,
+                    };
+                    events.push(NetworkEvent::UpdateChatRoom(packet.owner_id, chat_room));
+                } else if let Ok(packet) = ChatRoomDestroyedPacket::try_from_bytes(&mut byte_stream) {
+                    events.push(NetworkEvent::RemoveChatRoom(packet.room_id));
+                } else if let Ok(packet) = EnterChatRoomFailedPacket::try_from_bytes(&mut byte_stream) {
+                    let error = match packet.result {
+                        EnterChatRoomResult::RoomFull => "the chat room is full",
+                        EnterChatRoomResult::WrongPassword => "wrong password for the chat room",
+                        EnterChatRoomResult::Kicked => "you were kicked from the chat room",
+                        EnterChatRoomResult::Success => "entered the chat room",
+                        EnterChatRoomResult::NotEnoughZeny => "you do not have enough zeny to enter the chat room",
+                        EnterChatRoomResult::LevelTooLow => "your level is too low for the chat room",
+                        EnterChatRoomResult::LevelTooHigh => "your level is too high for the chat room",
+                        EnterChatRoomResult::UnsuitableJob => "your job can not enter the chat room",
+                    };
+
+                    let chat_message = ChatMessage::new(error.to_owned(), Color::rgb(255, 100, 100), ChatChannel::Server);
+                    events.push(NetworkEvent::ChatMessage(chat_message));
+                } else if let Ok(packet) = ChatRoomEnteredPacket::try_from_bytes(&mut byte_stream) {
+                    self.chat_room = Some(packet.room_id);
+
+                    let members: Vec<String> = packet.members.into_iter().map(|member| member.name).collect();
+                    let text = format!("members: {}", members.join(", "));
+                    let chat_message = ChatMessage::new(text, Color::rgb(200, 220, 255), ChatChannel::Room);
+                    events.push(NetworkEvent::ChatMessage(chat_message));
+                    events.push(NetworkEvent::ChatRoomEntered(packet.room_id));
+                } else if let Ok(packet) = ChatRoomMemberJoinedPacket::try_from_bytes(&mut byte_stream) {
+                    let text = format!("{} entered the room", packet.name);
+                    let chat_message = ChatMessage::new(text, Color::rgb(200, 220, 255), ChatChannel::Room);
+                    events.push(NetworkEvent::ChatMessage(chat_message));
+                } else if let Ok(packet) = ChatRoomMemberLeftPacket::try_from_bytes(&mut byte_stream) {
+                    if packet.name == self.player_name {
+                        self.chat_room = None;
+                        events.push(NetworkEvent::ChatRoomLeft);
+                    } else {
+                        let text = match packet.kicked != 0 {
+                            true => format!("{} was kicked from the room", packet.name),
+                            false => format!("{} left the room", packet.name),
+                        };
+                        let chat_message = ChatMessage::new(text, Color::rgb(200, 220, 255), ChatChannel::Room);
+                        events.push(NetworkEvent::ChatMessage(chat_message));
+                    }
                 } else if let Ok(packet) = EntityMessagePacket::try_from_bytes(&mut byte_stream) {
-                    let chat_message = ChatMessage::new(packet.message, packet.color.into());
+                    let chat_message = ChatMessage::new(packet.message, packet.color.into(), ChatChannel::Public);
                     events.push(NetworkEvent::ChatMessage(chat_message));
+                } else if let Ok(packet) = PartyMessageReceivedPacket::try_from_bytes(&mut byte_stream) {
+                    let chat_message = ChatMessage::new(packet.message, Color::rgb(255, 200, 200), ChatChannel::Party);
+                    events.push(NetworkEvent::ChatMessage(chat_message));
+                } else if let Ok(packet) = GuildMessageReceivedPacket::try_from_bytes(&mut byte_stream) {
+                    let chat_message = ChatMessage::new(packet.message, Color::rgb(180, 255, 180), ChatChannel::Guild);
+                    events.push(NetworkEvent::ChatMessage(chat_message));
+                } else if let Ok(packet) = WhisperReceivedPacket::try_from_bytes(&mut byte_stream) {
+                    let text = format!("(From {}): {}", packet.sender_name, packet.message);
+                    let chat_message = ChatMessage::whisper(text, Color::rgb(255, 255, 100), packet.sender_name.clone());
+                    events.push(NetworkEvent::ChatMessage(chat_message));
+                    events.push(NetworkEvent::WhisperReceived(packet.sender_name));
+                } else if let Ok(packet) = WhisperResponsePacket::try_from_bytes(&mut byte_stream) {
+                    let recipient_name = self.pending_whispers.pop_front().unwrap_or_default();
+
+                    let error = match packet.result {
+                        WhisperResult::Success => None,
+                        WhisperResult::RecipientOffline => Some(format!("{} is not online", recipient_name)),
+                        WhisperResult::Ignored => Some(format!("{} is ignoring you", recipient_name)),
+                        WhisperResult::AllIgnored => Some(format!("{} is ignoring all whispers", recipient_name)),
+                    };
+
+                    if let Some(error) = error {
+                        let chat_message = ChatMessage::whisper(error, Color::rgb(255, 100, 100), recipient_name);
+                        events.push(NetworkEvent::ChatMessage(chat_message));
+                    }
                 } else if let Ok(_packet) = DisplayEmotionPacket::try_from_bytes(&mut byte_stream) {
                 } else if let Ok(packet) = EntityMovePacket::try_from_bytes(&mut byte_stream) {
                     let (origin, destination) = packet.from_to.to_vectors();
@@ -2518,11 +4074,22 @@ impl NetworkingSystem {
                         destination,
                         packet.timestamp,
                     ));
-                } else if let Ok(_packet) = EntityStopMovePacket::try_from_bytes(&mut byte_stream) {
+                } else if let Ok(packet) = EntityStopMovePacket::try_from_bytes(&mut byte_stream) {
+                    events.push(NetworkEvent::EntityStopMove(
+                        packet.entity_id,
+                        Vector2::new(packet.x as usize, packet.y as usize),
+                    ));
+                } else if let Ok(packet) = EntitySlidePacket::try_from_bytes(&mut byte_stream) {
+                    events.push(NetworkEvent::EntitySlide(
+                        packet.entity_id,
+                        Vector2::new(packet.x as usize, packet.y as usize),
+                    ));
                 } else if let Ok(packet) = PlayerMovePacket::try_from_bytes(&mut byte_stream) {
                     let (origin, destination) = packet.from_to.to_vectors();
                     events.push(NetworkEvent::PlayerMove(origin, destination, packet.timestamp));
                 } else if let Ok(packet) = ChangeMapPacket::try_from_bytes(&mut byte_stream) {
+                    // The server removes the player from the chat room when changing maps.
+                    self.chat_room = None;
                     events.push(NetworkEvent::ChangeMap(
                         packet.map_name.replace(".gat", "").to_string(),
                         Vector2::new(packet.x as usize, packet.y as usize),
@@ -2531,8 +4098,14 @@ impl NetworkingSystem {
                     events.push(NetworkEvent::AddEntity(packet.into()));
                 } else if let Ok(packet) = MovingEntityAppearedPacket::try_from_bytes(&mut byte_stream) {
                     events.push(NetworkEvent::AddEntity(packet.into()));
+                } else if let Ok(packet) = EntitySpawnedPacket::try_from_bytes(&mut byte_stream) {
+                    events.push(NetworkEvent::AddEntity(packet.into()));
                 } else if let Ok(packet) = EntityDisappearedPacket::try_from_bytes(&mut byte_stream) {
-                    events.push(NetworkEvent::RemoveEntity(packet.entity_id));
+                    match packet.reason {
+                        DissapearanceReason::Teleported => events.push(NetworkEvent::EntityTeleported(packet.entity_id)),
+                        DissapearanceReason::Died => events.push(NetworkEvent::EntityDied(packet.entity_id)),
+                        _ => events.push(NetworkEvent::RemoveEntity(packet.entity_id)),
+                    }
                 } else if let Ok(packet) = UpdateStatusPacket::try_from_bytes(&mut byte_stream) {
                     events.push(NetworkEvent::UpdateStatus(packet.status_type));
                 } else if let Ok(packet) = UpdateStatusPacket1::try_from_bytes(&mut byte_stream) {
@@ -2546,7 +4119,26 @@ impl NetworkingSystem {
                 } else if let Ok(_packet) = AchievementUpdatePacket::try_from_bytes(&mut byte_stream) {
                 } else if let Ok(_packet) = AchievementListPacket::try_from_bytes(&mut byte_stream) {
                 } else if let Ok(_packet) = CriticalWeightUpdatePacket::try_from_bytes(&mut byte_stream) {
-                } else if let Ok(_packet) = SpriteChangePacket::try_from_bytes(&mut byte_stream) {
+                } else if let Ok(packet) = SpriteChangePacket::try_from_bytes(&mut byte_stream) {
+                    let look_change = match packet.sprite_type {
+                        1 => Some(LookChange::HairStyle(packet.value as usize)),
+                        2 => Some(LookChange::Weapon {
+                            weapon: packet.value,
+                            shield: packet.value2,
+                        }),
+                        3 => Some(LookChange::HeadBottom(packet.value as usize)),
+                        4 => Some(LookChange::HeadTop(packet.value as usize)),
+                        5 => Some(LookChange::HeadMiddle(packet.value as usize)),
+                        6 => Some(LookChange::HairColor(packet.value as usize)),
+                        7 => Some(LookChange::ClothesColor(packet.value as usize)),
+                        8 => Some(LookChange::Shield(packet.value)),
+                        12 => Some(LookChange::Garment(packet.value as usize)),
+                        _ => None,
+                    };
+
+                    if let Some(look_change) = look_change {
+                        events.push(NetworkEvent::UpdateEntityLook(packet.entity_id, look_change));
+                    }
                 } else if let Ok(_packet) = InventoyStartPacket::try_from_bytes(&mut byte_stream) {
                     let mut item_data = Vec::new();
 
@@ -2558,6 +4150,8 @@ impl NetworkingSystem {
                                     item_information.item_id,
                                     EquipPosition::None,
                                     EquipPosition::None,
+                                    item_information.slot.map(ItemId),
+                                    item_information.amount,
                                 )); // TODO: Don't add that data here, only equippable itemes need this data
                             }
                         } else if let Ok(packet) = EquippableItemListPacket::try_from_bytes(&mut byte_stream) {
@@ -2567,6 +4161,8 @@ impl NetworkingSystem {
                                     item_information.item_id,
                                     item_information.equip_position,
                                     item_information.equipped_position,
+                                    item_information.slot.map(ItemId),
+                                    1,
                                 ));
                             }
                         } else {
@@ -2576,11 +4172,43 @@ impl NetworkingSystem {
 
                     events.push(NetworkEvent::Inventory(item_data));
                 } else if let Ok(_packet) = EquippableSwitchItemListPacket::try_from_bytes(&mut byte_stream) {
-                } else if let Ok(_packet) = MapTypePacket::try_from_bytes(&mut byte_stream) {
-                } else if let Ok(_packet) = UpdateSkillTreePacket::try_from_bytes(&mut byte_stream) {
+                } else if let Ok(packet) = MapTypePacket::try_from_bytes(&mut byte_stream) {
+                    events.push(NetworkEvent::UpdateMapType(packet.map_type, packet.flags));
+                } else if let Ok(packet) = PvpRankingPacket::try_from_bytes(&mut byte_stream) {
+                    events.push(NetworkEvent::PvpRanking {
+                        entity_id: packet.entity_id,
+                        ranking: packet.ranking as usize,
+                        total: packet.total as usize,
+                    });
+                } else if let Ok(packet) = UpdateSkillTreePacket::try_from_bytes(&mut byte_stream) {
+                    events.push(NetworkEvent::SkillTree(packet.skill_information));
                 } else if let Ok(_packet) = UpdateHotkeysPacket::try_from_bytes(&mut byte_stream) {
                 } else if let Ok(_packet) = InitialStatusPacket::try_from_bytes(&mut byte_stream) {
+                } else if let Ok(packet) = PartyInvitationPacket::try_from_bytes(&mut byte_stream) {
+                    let text = format!("you were invited to the party {}", packet.party_name);
+                    let chat_message = ChatMessage::new(text, Color::rgb(220, 200, 30), ChatChannel::Server);
+                    events.push(NetworkEvent::ChatMessage(chat_message));
+                    events.push(NetworkEvent::PartyInvitation);
                 } else if let Ok(_packet) = UpdatePartyInvitationStatePacket::try_from_bytes(&mut byte_stream) {
+                } else if let Ok(packet) = PartyMemberPositionPacket::try_from_bytes(&mut byte_stream) {
+                    let position = Vector2::new(packet.x as usize, packet.y as usize);
+                    events.push(NetworkEvent::PartyMemberPosition(packet.account_id, position));
+                } else if let Ok(packet) = PartyMemberLeftPacket::try_from_bytes(&mut byte_stream) {
+                    let account_id = self.login_data.as_ref().unwrap().account_id;
+
+                    match packet.account_id == account_id {
+                        true => events.push(NetworkEvent::UpdatePartyMembers(Vec::new())),
+                        false => events.push(NetworkEvent::PartyMemberLeft(packet.account_id)),
+                    }
+                } else if let Ok(packet) = PartyInformationPacket::try_from_bytes(&mut byte_stream) {
+                    let members = packet
+                        .members
+                        .into_iter()
+                        .filter(|member| member.is_offline == 0)
+                        .map(|member| member.account_id)
+                        .collect();
+
+                    events.push(NetworkEvent::UpdatePartyMembers(members));
                 } else if let Ok(_packet) = UpdateShowEquipPacket::try_from_bytes(&mut byte_stream) {
                 } else if let Ok(_packet) = UpdateConfigurationPacket::try_from_bytes(&mut byte_stream) {
                 } else if let Ok(_packet) = NavigateToMonsterPacket::try_from_bytes(&mut byte_stream) {
@@ -2599,7 +4227,11 @@ impl NetworkingSystem {
 
                     events.push(NetworkEvent::AddChoiceButtons(choices));
                 } else if let Ok(_packet) = DisplaySpecialEffectPacket::try_from_bytes(&mut byte_stream) {
-                } else if let Ok(_packet) = DisplaySkillEffectPacket::try_from_bytes(&mut byte_stream) {
+                } else if let Ok(packet) = DisplaySkillEffectPacket::try_from_bytes(&mut byte_stream) {
+                    // High Jump and Shadow Leap are followed by an EntitySlidePacket.
+                    if matches!(packet.skill_id, 370 | 530) {
+                        events.push(NetworkEvent::EntityJump(packet.source_entity_id));
+                    }
                 } else if let Ok(_packet) = StatusChangePacket::try_from_bytes(&mut byte_stream) {
                 } else if let Ok(_packet) = QuestNotificationPacket1::try_from_bytes(&mut byte_stream) {
                 } else if let Ok(_packet) = HuntingQuestNotificationPacket::try_from_bytes(&mut byte_stream) {
@@ -2609,7 +4241,13 @@ impl NetworkingSystem {
                 } else if let Ok(_packet) = VisualEffectPacket::try_from_bytes(&mut byte_stream) {
                 } else if let Ok(_packet) = DisplayGainedExperiencePacket::try_from_bytes(&mut byte_stream) {
                 } else if let Ok(_packet) = DisplayImagePacket::try_from_bytes(&mut byte_stream) {
-                } else if let Ok(_packet) = StateChangePacket::try_from_bytes(&mut byte_stream) {
+                } else if let Ok(packet) = StateChangePacket::try_from_bytes(&mut byte_stream) {
+                    events.push(NetworkEvent::UpdateEntityState(
+                        packet.entity_id,
+                        packet.body_state,
+                        packet.health_state,
+                        packet.effect_state,
+                    ));
                 } else if let Ok(packet) = QuestEffectPacket::try_from_bytes(&mut byte_stream) {
                     let event = match packet.effect {
                         QuestEffect::None => NetworkEvent::RemoveQuestEffect(packet.entity_id),
@@ -2617,14 +4255,32 @@ impl NetworkingSystem {
                     };
                     events.push(event);
                 } else if let Ok(packet) = ItemPickupPacket::try_from_bytes(&mut byte_stream) {
-                    events.push(NetworkEvent::AddIventoryItem(
-                        packet.index,
-                        packet.item_id,
-                        packet.equip_position,
-                        EquipPosition::None,
-                    ));
-                } else if let Ok(_packet) = RemoveItemFromInventoryPacket::try_from_bytes(&mut byte_stream) {
+                    // Any other result means that the item could not be taken, for example
+                    // because it is too heavy.
+                    if packet.result == 0 {
+                        events.push(NetworkEvent::ItemPickedUp(packet.count as usize));
+                        events.push(NetworkEvent::AddIventoryItem(
+                            packet.index,
+                            packet.item_id,
+                            packet.equip_position,
+                            EquipPosition::None,
+                            packet.cards.map(ItemId),
+                            packet.count,
+                        ));
+                    }
+                } else if let Ok(packet) = RemoveItemFromInventoryPacket::try_from_bytes(&mut byte_stream) {
+                    events.push(NetworkEvent::RemoveInventoryItem(packet.index, packet.amount));
+                } else if let Ok(packet) = UseItemResponsePacket::try_from_bytes(&mut byte_stream) {
+                    let account_id = self.login_data.as_ref().unwrap().account_id;
+
+                    if packet.account_id == account_id && packet.success != 0 {
+                        events.push(NetworkEvent::UpdateInventoryItemAmount(packet.index, packet.amount));
+                    }
                 } else if let Ok(packet) = ServerTickPacket::try_from_bytes(&mut byte_stream) {
+                    if let Some(requested) = self.server_tick_requested.take() {
+                        self.latency = Some(requested.elapsed());
+                    }
+
                     events.push(NetworkEvent::UpdateClientTick(packet.client_tick));
                 } else if let Ok(packet) = RequestPlayerDetailsSuccessPacket::try_from_bytes(&mut byte_stream) {
                     events.push(NetworkEvent::UpdateEntityDetails(EntityId(packet.character_id.0), packet.name));
@@ -2638,10 +4294,20 @@ impl NetworkingSystem {
                     ));
                 } else if let Ok(_packet) = RequestPlayerAttackFailedPacket::try_from_bytes(&mut byte_stream) {
                 } else if let Ok(packet) = DamagePacket::try_from_bytes(&mut byte_stream) {
+                    events.push(NetworkEvent::EntityAttack(packet.source_entity_id, packet.source_movement_speed));
                     events.push(NetworkEvent::DamageEffect(
                         packet.destination_entity_id,
                         packet.damage_amount as usize,
                     ));
+                } else if let Ok(packet) = SkillDamagePacket::try_from_bytes(&mut byte_stream) {
+                    if packet.damage_amount > 0 {
+                        events.push(NetworkEvent::DamageEffect(
+                            packet.destination_entity_id,
+                            packet.damage_amount as usize,
+                        ));
+                    }
+
+                    events.push(NetworkEvent::SkillDamage(packet.skill_id, packet.destination_entity_id));
                 } else if let Ok(packet) = NpcDialogPacket::try_from_bytes(&mut byte_stream) {
                     events.push(NetworkEvent::OpenDialog(packet.text, packet.npc_id));
                 } else if let Ok(packet) = RequestEquipItemStatusPacket::try_from_bytes(&mut byte_stream) {
@@ -2658,6 +4324,120 @@ impl NetworkingSystem {
                             equipped_position: EquipPosition::None,
                         });
                     }
+                } else if let Ok(_packet) = RefineWindowPacket::try_from_bytes(&mut byte_stream) {
+                    events.push(NetworkEvent::OpenRefineWindow);
+                } else if let Ok(packet) = RefineItemInformationPacket::try_from_bytes(&mut byte_stream) {
+                    events.push(NetworkEvent::RefineItemInformation(packet.index, packet.materials));
+                } else if let Ok(packet) = RefineResultPacket::try_from_bytes(&mut byte_stream) {
+                    events.push(NetworkEvent::RefineResult(
+                        packet.index,
+                        packet.result,
+                        packet.refinement_level as usize,
+                    ));
+                } else if let Ok(packet) = CraftableItemListPacket::try_from_bytes(&mut byte_stream) {
+                    events.push(NetworkEvent::CraftableItems(packet.items));
+                } else if let Ok(packet) = CraftResultPacket::try_from_bytes(&mut byte_stream) {
+                    let success = matches!(packet.result, CraftResult::Success);
+                    events.push(NetworkEvent::CraftResult(packet.item_id, success));
+                } else if let Ok(packet) = CardTargetListPacket::try_from_bytes(&mut byte_stream) {
+                    // The server doesn't repeat the card, so use the one we asked about.
+                    if let Some(card_index) = self.pending_card.take() {
+                        events.push(NetworkEvent::CardTargets(card_index, packet.equipment_indices));
+                    }
+                } else if let Ok(packet) = InsertCardResultPacket::try_from_bytes(&mut byte_stream) {
+                    let event = match packet.result {
+                        InsertCardResult::Success => NetworkEvent::CardInserted {
+                            card_index: packet.card_index,
+                            equipment_index: packet.equipment_index,
+                        },
+                        InsertCardResult::Failure => NetworkEvent::CardInsertFailed,
+                    };
+                    events.push(event);
+                } else if let Ok(_packet) = StartPetCapturePacket::try_from_bytes(&mut byte_stream) {
+                    events.push(NetworkEvent::StartPetCapture);
+                } else if let Ok(packet) = PetCaptureResultPacket::try_from_bytes(&mut byte_stream) {
+                    let success = matches!(packet.result, PetCaptureResult::Success);
+                    events.push(NetworkEvent::PetCaptureResult(success));
+                } else if let Ok(packet) = PetEggListPacket::try_from_bytes(&mut byte_stream) {
+                    events.push(NetworkEvent::PetEggList(packet.egg_indices));
+                } else if let Ok(packet) = PetPropertiesPacket::try_from_bytes(&mut byte_stream) {
+                    events.push(NetworkEvent::PetStatus(PetStatus {
+                        name: packet.name,
+                        level: packet.level as usize,
+                        hunger: packet.hunger as usize,
+                        intimacy: packet.intimacy as usize,
+                        accessory: (packet.accessory_id != 0).then_some(ItemId(packet.accessory_id as u32)),
+                    }));
+                } else if let Ok(packet) = PetStatusChangePacket::try_from_bytes(&mut byte_stream) {
+                    if let PetStatusChangeType::Spawn = packet.change_type {
+                        self.pet_entity_id = Some(packet.entity_id);
+                    }
+
+                    if self.pet_entity_id == Some(packet.entity_id) {
+                        let value = packet.value.max(0) as usize;
+
+                        match packet.change_type {
+                            PetStatusChangeType::Intimacy => events.push(NetworkEvent::UpdatePetIntimacy(value)),
+                            PetStatusChangeType::Hunger => events.push(NetworkEvent::UpdatePetHunger(value)),
+                            PetStatusChangeType::Accessory => {
+                                let accessory = (value != 0).then_some(ItemId(value as u32));
+                                events.push(NetworkEvent::UpdatePetAccessory(accessory));
+                            }
+                            _ => {}
+                        }
+                    }
+                } else if let Ok(packet) = SkillCastPacket::try_from_bytes(&mut byte_stream) {
+                    events.push(NetworkEvent::SkillCast {
+                        entity_id: packet.source_entity_id,
+                        skill_id: packet.skill_id,
+                        duration: packet.delay_time,
+                    });
+                } else if let Ok(packet) = CastCancelledPacket::try_from_bytes(&mut byte_stream) {
+                    events.push(NetworkEvent::CastCancelled(packet.entity_id));
+                } else if let Ok(packet) = SkillCooldownPacket::try_from_bytes(&mut byte_stream) {
+                    events.push(NetworkEvent::SkillCooldown(packet.skill_id, packet.delay_time));
+                } else if let Ok(packet) = TradeRequestPacket::try_from_bytes(&mut byte_stream) {
+                    events.push(NetworkEvent::TradeRequested(packet.name));
+                } else if let Ok(packet) = TradeRequest2Packet::try_from_bytes(&mut byte_stream) {
+                    events.push(NetworkEvent::TradeRequested(packet.name));
+                } else if let Ok(packet) = TradeRequestResultPacket::try_from_bytes(&mut byte_stream) {
+                    events.push(Self::trade_request_result_event(packet.result));
+                } else if let Ok(packet) = TradeRequestResult2Packet::try_from_bytes(&mut byte_stream) {
+                    events.push(Self::trade_request_result_event(packet.result));
+                } else if let Ok(packet) = TradeItemAddedPacket::try_from_bytes(&mut byte_stream) {
+                    let event = match packet.item_id {
+                        ItemId(0) => NetworkEvent::TradeZenyAdded(packet.amount),
+                        item_id => NetworkEvent::TradeItemAdded {
+                            item_id,
+                            cards: packet.cards.map(ItemId),
+                            amount: packet.amount as u16,
+                        },
+                    };
+                    events.push(event);
+                } else if let Ok(packet) = AddTradeItemResultPacket::try_from_bytes(&mut byte_stream) {
+                    if packet.result == 0 {
+                        events.push(NetworkEvent::TradeItemAccepted(packet.index));
+                    } else {
+                        let text = "the item can not be traded".to_string();
+                        let chat_message = ChatMessage::new(text, Color::rgb(255, 100, 100), ChatChannel::Server);
+                        events.push(NetworkEvent::ChatMessage(chat_message));
+                        events.push(NetworkEvent::TradeItemRejected(packet.index));
+                    }
+                } else if let Ok(packet) = TradeLockedPacket::try_from_bytes(&mut byte_stream) {
+                    events.push(NetworkEvent::TradeLocked { partner: packet.who != 0 });
+                } else if let Ok(_packet) = TradeCancelledPacket::try_from_bytes(&mut byte_stream) {
+                    let text = "the trade was cancelled".to_string();
+                    let chat_message = ChatMessage::new(text, Color::rgb(220, 200, 30), ChatChannel::Server);
+                    events.push(NetworkEvent::ChatMessage(chat_message));
+                    events.push(NetworkEvent::TradeEnded);
+                } else if let Ok(packet) = TradeCompletedPacket::try_from_bytes(&mut byte_stream) {
+                    let text = match packet.result {
+                        0 => "the trade was completed",
+                        _ => "the trade failed",
+                    };
+                    let chat_message = ChatMessage::new(text.to_string(), Color::rgb(220, 200, 30), ChatChannel::Server);
+                    events.push(NetworkEvent::ChatMessage(chat_message));
+                    events.push(NetworkEvent::TradeEnded);
                 } else {
                     #[cfg(feature = "debug_network")]
                     {
@@ -2670,7 +4450,7 @@ impl NetworkingSystem {
             }
 
             #[cfg(feature = "debug_network")]
-            byte_stream.transfer_packet_history(&mut self.packet_history);
+            byte_stream.transfer_packet_history(&mut self.packet_history, &mut self.statistics_recorder);
         }
 
         events
@@ -2680,4 +4460,9 @@ impl NetworkingSystem {
     pub fn packets(&self) -> TrackedState<Vec<PacketEntry>> {
         self.packet_history.clone()
     }
+
+    #[cfg(feature = "debug_network")]
+    pub fn get_statistics(&self) -> Remote<NetworkStatistics> {
+        self.statistics_recorder.get_statistics()
+    }
 }
@@ -0,0 +1,160 @@
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+use crate::interface::{Remote, TrackedState};
+
+/// Number of seconds shown in the graphs.
+const SAMPLE_COUNT: usize = 60;
+const SAMPLE_DURATION: Duration = Duration::from_secs(1);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PacketDirection {
+    Incoming,
+    Outgoing,
+}
+
+/// A single packet that was sent or received.
+#[derive(Clone, Copy, Debug)]
+pub struct PacketRecord {
+    pub header: [u8; 2],
+    pub name: &'static str,
+    pub direction: PacketDirection,
+    pub size: usize,
+    /// Time spent converting the packet to or from bytes.
+    pub serialization_time: Duration,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TrafficCounter {
+    pub bytes: usize,
+    pub packets: usize,
+    pub serialization_time: Duration,
+}
+
+impl TrafficCounter {
+    fn add(&mut self, record: &PacketRecord) {
+        self.bytes += record.size;
+        self.packets += 1;
+        self.serialization_time += record.serialization_time;
+    }
+}
+
+/// Traffic of one second.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TrafficSample {
+    pub incoming: TrafficCounter,
+    pub outgoing: TrafficCounter,
+}
+
+impl TrafficSample {
+    pub fn get(&self, direction: PacketDirection) -> &TrafficCounter {
+        match direction {
+            PacketDirection::Incoming => &self.incoming,
+            PacketDirection::Outgoing => &self.outgoing,
+        }
+    }
+
+    fn add(&mut self, record: &PacketRecord) {
+        match record.direction {
+            PacketDirection::Incoming => self.incoming.add(record),
+            PacketDirection::Outgoing => self.outgoing.add(record),
+        }
+    }
+}
+
+/// Everything sent and received with the same header since the client
+/// started.
+#[derive(Clone, Copy, Debug)]
+pub struct PacketTotal {
+    pub header: [u8; 2],
+    pub name: &'static str,
+    pub direction: PacketDirection,
+    pub counter: TrafficCounter,
+}
+
+#[derive(Default)]
+pub struct NetworkStatistics {
+    /// The last [SAMPLE_COUNT] seconds, oldest first.
+    samples: VecDeque<TrafficSample>,
+    totals: HashMap<([u8; 2], PacketDirection), PacketTotal>,
+}
+
+impl NetworkStatistics {
+    pub fn get_samples(&self) -> &VecDeque<TrafficSample> {
+        &self.samples
+    }
+
+    /// Packets that used up the most bandwidth, biggest first.
+    pub fn top_packets(&self, count: usize) -> Vec<PacketTotal> {
+        let mut totals: Vec<PacketTotal> = self.totals.values().copied().collect();
+        totals.sort_unstable_by(|first, second| second.counter.bytes.cmp(&first.counter.bytes));
+        totals.truncate(count);
+        totals
+    }
+
+    fn add_sample(&mut self, sample: TrafficSample, records: &[PacketRecord]) {
+        if self.samples.len() == SAMPLE_COUNT {
+            self.samples.pop_front();
+        }
+
+        self.samples.push_back(sample);
+
+        for record in records {
+            self.totals
+                .entry((record.header, record.direction))
+                .or_insert(PacketTotal {
+                    header: record.header,
+                    name: record.name,
+                    direction: record.direction,
+                    counter: TrafficCounter::default(),
+                })
+                .counter
+                .add(record);
+        }
+    }
+}
+
+/// Collects packets until a second has passed and then hands them to the
+/// [NetworkStatistics] in one go, so windows showing them only update once a
+/// second instead of once per packet.
+pub struct NetworkStatisticsRecorder {
+    statistics: TrackedState<NetworkStatistics>,
+    current_sample: TrafficSample,
+    current_records: Vec<PacketRecord>,
+    sample_start: Instant,
+}
+
+impl NetworkStatisticsRecorder {
+    pub fn new() -> Self {
+        Self {
+            statistics: TrackedState::default(),
+            current_sample: TrafficSample::default(),
+            current_records: Vec::new(),
+            sample_start: Instant::now(),
+        }
+    }
+
+    pub fn record(&mut self, record: PacketRecord) {
+        self.current_sample.add(&record);
+        self.current_records.push(record);
+    }
+
+    pub fn update(&mut self) {
+        if self.sample_start.elapsed() < SAMPLE_DURATION {
+            return;
+        }
+
+        let sample = std::mem::take(&mut self.current_sample);
+        let records = std::mem::take(&mut self.current_records);
+        self.sample_start = Instant::now();
+
+        self.statistics.with_mut(|statistics, changed| {
+            statistics.add_sample(sample, &records);
+            changed();
+        });
+    }
+
+    pub fn get_statistics(&self) -> Remote<NetworkStatistics> {
+        self.statistics.new_remote()
+    }
+}
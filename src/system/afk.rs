@@ -0,0 +1,56 @@
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+
+/// Time after going AFK manually during which input doesn't wake the player
+/// up, so the key press that sent the command doesn't end it right away.
+const WAKE_GRACE_TIME: Duration = Duration::from_secs(1);
+
+/// Keeps track of whether the player is away from the keyboard, either
+/// because they said so or because there was no input for a while.
+pub struct AfkState {
+    last_input: Instant,
+    manual_since: Option<Instant>,
+    /// Senders that already got the away message, so that nobody is answered
+    /// more than once while the player is away.
+    replied_to: HashSet<String>,
+}
+
+impl Default for AfkState {
+    fn default() -> Self {
+        Self {
+            last_input: Instant::now(),
+            manual_since: None,
+            replied_to: HashSet::new(),
+        }
+    }
+}
+
+impl AfkState {
+    pub fn register_input(&mut self) {
+        if let Some(manual_since) = self.manual_since
+            && manual_since.elapsed() < WAKE_GRACE_TIME
+        {
+            return;
+        }
+
+        self.manual_since = None;
+        self.last_input = Instant::now();
+        self.replied_to.clear();
+    }
+
+    pub fn set_away(&mut self) {
+        self.manual_since = Some(Instant::now());
+    }
+
+    /// Whether the player is away. An inactivity time of zero means that the
+    /// player is only away after going AFK manually.
+    pub fn is_away(&self, inactivity_minutes: u32) -> bool {
+        let inactive = inactivity_minutes > 0 && self.last_input.elapsed() >= Duration::from_secs(inactivity_minutes as u64 * 60);
+        self.manual_since.is_some() || inactive
+    }
+
+    /// Whether the sender of a whisper should get the away message.
+    pub fn should_reply(&mut self, sender: &str) -> bool {
+        self.replied_to.insert(sender.to_owned())
+    }
+}
@@ -0,0 +1,74 @@
+use std::path::PathBuf;
+
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser};
+
+/// Command line options, which take precedence over the settings files.
+/// `--windowed` is accepted for compatibility and does nothing, since the
+/// client always runs in a window. `--replay` and `--headless` are recognized
+/// but not supported yet, so they exit with an error saying as much.
+#[derive(Parser)]
+#[command(name = "korangar", about = "A Ragnarok Online client", long_about = None)]
+pub struct Arguments {
+    /// Use a separate profile for settings, window positions and stored
+    /// credentials
+    #[arg(long)]
+    pub profile: Option<String>,
+    /// Address of the login server, for example 127.0.0.1:6900
+    #[arg(long)]
+    pub server: Option<String>,
+    /// Map that is loaded before logging in
+    #[arg(long, default_value = "geffen")]
+    pub map: String,
+    /// Directory containing the game archives
+    #[arg(long, default_value = ".")]
+    pub data_dir: PathBuf,
+    /// Graphics device to use, either by its index in the list of suitable
+    /// devices or by its vendor and device id in hexadecimal, for example
+    /// 10de:2684. Overrides the device picked in the graphics settings
+    #[arg(long)]
+    pub gpu: Option<String>,
+    /// Seed for the randomness of visual effects, so that they play out the
+    /// same way every time
+    #[arg(long)]
+    pub seed: Option<u64>,
+    /// Has no effect, the client always runs in a window
+    #[arg(long)]
+    pub windowed: bool,
+    /// Play back a recorded session (not supported yet)
+    #[arg(long, value_name = "FILE")]
+    replay: Option<PathBuf>,
+    /// Run without a window (not supported yet)
+    #[arg(long)]
+    headless: bool,
+    /// Return to the scene saved with the save snapshot button in the menu
+    #[cfg(feature = "debug")]
+    #[arg(long)]
+    pub restore_snapshot: bool,
+}
+
+impl Arguments {
+    pub fn new() -> Self {
+        let arguments = Self::parse();
+
+        if arguments.replay.is_some() {
+            Self::unsupported("--replay");
+        }
+
+        if arguments.headless {
+            Self::unsupported("--headless");
+        }
+
+        arguments
+    }
+
+    fn unsupported(argument: &str) -> ! {
+        Self::command()
+            .error(ErrorKind::ArgumentConflict, format!("{} is not supported yet", argument))
+            .exit()
+    }
+
+    pub fn data_file(&self, file_name: &str) -> String {
+        self.data_dir.join(file_name).to_string_lossy().into_owned()
+    }
+}
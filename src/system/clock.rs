@@ -0,0 +1,212 @@
+use chrono::{Datelike, Duration, Local, NaiveDateTime, NaiveTime, Timelike, Utc};
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "debug")]
+use crate::debug::*;
+use crate::interface::TrackedState;
+use crate::system::profile_file;
+
+/// Minutes before an alarm at which the player is reminded of it, the last
+/// one being the alarm itself.
+const ALARM_WARNINGS: [i64; 3] = [15, 5, 0];
+/// Time zones range from UTC-12:00 to UTC+14:00.
+const MINIMUM_SERVER_OFFSET: i32 = -12 * 60;
+const MAXIMUM_SERVER_OFFSET: i32 = 14 * 60;
+const WEEKDAY_NAMES: [&str; 7] = ["mon", "tue", "wed", "thu", "fri", "sat", "sun"];
+
+/// A countdown to a recurring event like the War of Emperium, in server time.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Alarm {
+    pub name: String,
+    /// Days from monday. Alarms without a weekday go off every day.
+    pub weekday: Option<u32>,
+    pub hour: u32,
+    pub minute: u32,
+}
+
+impl Alarm {
+    /// Parses a time in the form `hh:mm`.
+    pub fn parse(name: &str, time: &str, weekday: Option<u32>) -> Option<Self> {
+        let name = name.trim();
+        let time = NaiveTime::parse_from_str(time.trim(), "%H:%M").ok()?;
+
+        (!name.is_empty()).then(|| Self {
+            name: name.to_string(),
+            weekday,
+            hour: time.hour(),
+            minute: time.minute(),
+        })
+    }
+
+    fn time(&self) -> NaiveTime {
+        NaiveTime::from_hms_opt(self.hour, self.minute, 0).unwrap_or_default()
+    }
+
+    pub fn weekday_name(weekday: u32) -> &'static str {
+        WEEKDAY_NAMES[weekday as usize % 7]
+    }
+
+    /// First time strictly after `after` at which the alarm goes off.
+    pub fn next_occurrence(&self, after: NaiveDateTime) -> NaiveDateTime {
+        let mut date = after.date();
+
+        loop {
+            let occurrence = date.and_time(self.time());
+            let weekday_matches = self
+                .weekday
+                .map_or(true, |weekday| date.weekday().num_days_from_monday() == weekday);
+
+            if weekday_matches && occurrence > after {
+                return occurrence;
+            }
+
+            date = date.succ_opt().unwrap();
+        }
+    }
+
+    pub fn schedule(&self) -> String {
+        match self.weekday {
+            Some(weekday) => format!("{} {}", Self::weekday_name(weekday), self.time().format("%H:%M")),
+            None => format!("daily {}", self.time().format("%H:%M")),
+        }
+    }
+}
+
+/// Formats the time left until an alarm, e.g. `2d 4h`, `1h 20m` or `5m 12s`.
+pub fn format_countdown(remaining: Duration) -> String {
+    let seconds = remaining.num_seconds().max(0);
+    let (days, hours, minutes) = (seconds / 86400, seconds / 3600 % 24, seconds / 60 % 60);
+
+    match (days, hours) {
+        (0, 0) => format!("{}m {}s", minutes, seconds % 60),
+        (0, _) => format!("{}h {}m", hours, minutes),
+        _ => format!("{}d {}h", days, hours),
+    }
+}
+
+pub fn local_time() -> NaiveDateTime {
+    Local::now().naive_local()
+}
+
+/// The server doesn't tell the client its time of day, so server time is
+/// derived from UTC and the offset configured by the player.
+pub fn server_time(offset_minutes: i32) -> NaiveDateTime {
+    Utc::now().naive_utc() + Duration::minutes(offset_minutes as i64)
+}
+
+pub fn format_offset(offset_minutes: i32) -> String {
+    let sign = match offset_minutes < 0 {
+        true => '-',
+        false => '+',
+    };
+
+    format!("UTC{}{:02}:{:02}", sign, offset_minutes.abs() / 60, offset_minutes.abs() % 60)
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct ClockSettings {
+    /// Offset of the server time from UTC in minutes.
+    pub server_offset_minutes: TrackedState<i32>,
+    pub alarms: TrackedState<Vec<Alarm>>,
+}
+
+impl Default for ClockSettings {
+    fn default() -> Self {
+        Self {
+            server_offset_minutes: TrackedState::new(0),
+            alarms: TrackedState::new(Vec::new()),
+        }
+    }
+}
+
+impl ClockSettings {
+    pub fn new() -> Self {
+        Self::load().unwrap_or_else(|| {
+            #[cfg(feature = "debug")]
+            print_debug!("failed to load clock settings from {}filename{}", MAGENTA, NONE);
+
+            Default::default()
+        })
+    }
+
+    pub fn shift_server_offset(&mut self, minutes: i32) {
+        let offset = *self.server_offset_minutes.borrow() + minutes;
+        self.server_offset_minutes
+            .set(offset.clamp(MINIMUM_SERVER_OFFSET, MAXIMUM_SERVER_OFFSET));
+    }
+
+    pub fn remove_alarm(&mut self, index: usize) {
+        self.alarms.with_mut(|alarms, changed| {
+            if index < alarms.len() {
+                alarms.remove(index);
+                changed();
+            }
+        });
+    }
+
+    pub fn load() -> Option<Self> {
+        #[cfg(feature = "debug")]
+        print_debug!("loading clock settings from {}filename{}", MAGENTA, NONE);
+
+        std::fs::read_to_string(profile_file("clock_settings.ron"))
+            .ok()
+            .and_then(|data| ron::from_str(&data).ok())
+    }
+
+    pub fn save(&self) {
+        #[cfg(feature = "debug")]
+        print_debug!("saving clock settings to {}filename{}", MAGENTA, NONE);
+
+        let data = ron::ser::to_string_pretty(self, PrettyConfig::new()).unwrap();
+        std::fs::write(profile_file("clock_settings.ron"), data).expect("unable to write file");
+    }
+}
+
+impl Drop for ClockSettings {
+    fn drop(&mut self) {
+        self.save();
+    }
+}
+
+/// Checks every frame whether one of the alarms or its warnings is due.
+#[derive(Default)]
+pub struct AlarmNotifier {
+    last_check: Option<NaiveDateTime>,
+}
+
+impl AlarmNotifier {
+    /// Messages for all alarms that went off since the last update.
+    pub fn update(&mut self, clock_settings: &ClockSettings) -> Vec<String> {
+        let now = server_time(*clock_settings.server_offset_minutes.borrow());
+
+        // Jumps in time, for example from changing the offset, would otherwise make
+        // alarms go off that were already announced.
+        let Some(last_check) = self
+            .last_check
+            .replace(now)
+            .filter(|last_check| *last_check <= now && now - *last_check < Duration::minutes(1))
+        else {
+            return Vec::new();
+        };
+
+        let mut messages = Vec::new();
+
+        for alarm in clock_settings.alarms.borrow().iter() {
+            for warning in ALARM_WARNINGS {
+                let lead_time = Duration::minutes(warning);
+                let trigger_time = alarm.next_occurrence(last_check + lead_time) - lead_time;
+
+                if trigger_time <= now {
+                    messages.push(match warning {
+                        0 => format!("{} is starting", alarm.name),
+                        minutes => format!("{} starts in {} minutes", alarm.name, minutes),
+                    });
+                }
+            }
+        }
+
+        messages
+    }
+}
@@ -0,0 +1,189 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use crate::interface::BarValue;
+
+/// Time span that is considered when calculating the experience rate.
+const ROLLING_WINDOW: Duration = Duration::from_secs(10 * 60);
+/// Minimum time that has to pass before a rate is shown, so that a single kill
+/// right after logging in doesn't result in an absurd estimate.
+const MINIMUM_TRACKING_TIME: Duration = Duration::from_secs(30);
+
+/// Keeps track of base or job experience and the recent gains, to estimate
+/// how long it will take to reach the next level.
+pub struct ExperienceTracker {
+    label: &'static str,
+    current: Option<u64>,
+    next: u64,
+    /// The server sends the requirement of the new level before the
+    /// experience that was reduced by the level up, so the requirement of the
+    /// previous level is kept around until then.
+    previous_next: u64,
+    next_changed: bool,
+    tracking_start: Instant,
+    gains: VecDeque<(Instant, u64)>,
+}
+
+impl ExperienceTracker {
+    pub fn new(label: &'static str) -> Self {
+        Self {
+            label,
+            current: None,
+            next: 0,
+            previous_next: 0,
+            next_changed: false,
+            tracking_start: Instant::now(),
+            gains: VecDeque::new(),
+        }
+    }
+
+    /// Forget all gains, for example after switching characters.
+    pub fn reset(&mut self) {
+        self.current = None;
+        self.next = 0;
+        self.previous_next = 0;
+        self.next_changed = false;
+        self.tracking_start = Instant::now();
+        self.gains.clear();
+    }
+
+    /// Update the current experience and return how much was gained.
+    pub fn set_current(&mut self, current: u64) -> u64 {
+        // The first value after a reset is not a gain.
+        let gain = match self.current {
+            Some(previous) if current >= previous => current - previous,
+            // Experience is reduced by the requirement on level up, so the gain is what was
+            // missing for the previous level plus everything gained after that.
+            Some(previous) if self.next_changed && self.previous_next > 0 => self.previous_next.saturating_sub(previous) + current,
+            // Experience also drops when dying, which isn't a gain and leaves the
+            // requirement as it is.
+            Some(_) | None => 0,
+        };
+
+        if gain > 0 {
+            self.gains.push_back((Instant::now(), gain));
+        }
+
+        self.current = Some(current);
+        self.next_changed = false;
+        self.prune();
+
+        gain
+    }
+
+    pub fn set_next(&mut self, next: u64) {
+        if next != self.next {
+            self.previous_next = self.next;
+            self.next = next;
+            self.next_changed = true;
+        }
+    }
+
+    fn recent_gains(&self) -> impl Iterator<Item = u64> + '_ {
+        self.gains
+            .iter()
+            .filter(|(time, _)| time.elapsed() <= ROLLING_WINDOW)
+            .map(|(_, gain)| *gain)
+    }
+
+    /// Experience gained per hour over the rolling window.
+    pub fn rate_per_hour(&self) -> Option<f64> {
+        let tracked_time = self.tracking_start.elapsed().min(ROLLING_WINDOW);
+
+        if tracked_time < MINIMUM_TRACKING_TIME {
+            return None;
+        }
+
+        let gained: u64 = self.recent_gains().sum();
+        let rate = gained as f64 / tracked_time.as_secs_f64() * 3600.0;
+
+        (rate > 0.0).then_some(rate)
+    }
+
+    pub fn time_to_level(&self) -> Option<Duration> {
+        let missing = self.next.saturating_sub(self.current.unwrap_or_default());
+        let rate = self.rate_per_hour()?;
+
+        Some(Duration::from_secs_f64(missing as f64 / rate * 3600.0))
+    }
+
+    /// Drop gains that are outside of the rolling window.
+    fn prune(&mut self) {
+        while let Some((time, _)) = self.gains.front() && time.elapsed() > ROLLING_WINDOW {
+            self.gains.pop_front();
+        }
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    let minutes = duration.as_secs() / 60;
+
+    match minutes {
+        0 => "less than a minute".to_string(),
+        minutes if minutes < 60 => format!("{}m", minutes),
+        minutes => format!("{}h {}m", minutes / 60, minutes % 60),
+    }
+}
+
+impl BarValue for ExperienceTracker {
+    fn values(&self) -> (usize, usize) {
+        (self.current.unwrap_or_default() as usize, self.next as usize)
+    }
+
+    fn tooltip(&self) -> Option<Vec<String>> {
+        let current = self.current.unwrap_or_default();
+        let mut lines = vec![format!("{} experience {} / {}", self.label, current, self.next)];
+
+        if self.next > 0 {
+            let percentage = current as f64 / self.next as f64 * 100.0;
+            lines.push(format!("{:.2}% of the next level", percentage));
+        }
+
+        match self.rate_per_hour() {
+            Some(rate) => lines.push(format!("{:.0} per hour", rate)),
+            None => lines.push("no recent experience gain".to_string()),
+        }
+
+        if let Some(time_to_level) = self.time_to_level() {
+            lines.push(format!("next level in {}", format_duration(time_to_level)));
+        }
+
+        Some(lines)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn tracker_at(current: u64, next: u64) -> ExperienceTracker {
+        let mut tracker = ExperienceTracker::new("base");
+        tracker.set_next(next);
+        tracker.set_current(current);
+        tracker
+    }
+
+    #[test]
+    fn gain_is_difference() {
+        let mut tracker = tracker_at(100, 1000);
+        assert_eq!(tracker.set_current(250), 150);
+    }
+
+    #[test]
+    fn level_up_counts_missing_experience() {
+        let mut tracker = tracker_at(900, 1000);
+
+        // The requirement of the new level arrives before the reduced experience.
+        tracker.set_next(1500);
+        assert_eq!(tracker.set_current(50), 150);
+        assert_eq!(tracker.values(), (50, 1500));
+    }
+
+    #[test]
+    fn death_penalty_is_not_a_gain() {
+        let mut tracker = tracker_at(900, 1000);
+
+        assert_eq!(tracker.set_current(890), 0);
+        assert_eq!(tracker.set_current(900), 10);
+    }
+}
@@ -1,6 +1,25 @@
+mod afk;
+mod arguments;
+mod clock;
+mod experience;
+mod profile;
+mod random;
+mod session;
+#[cfg(feature = "status_server")]
+mod status;
 mod timer;
-#[macro_use]
 mod vulkan;
+mod window;
 
+pub use self::afk::AfkState;
+pub use self::arguments::Arguments;
+pub use self::clock::{format_countdown, format_offset, local_time, server_time, Alarm, AlarmNotifier, ClockSettings};
+pub use self::experience::ExperienceTracker;
+pub use self::profile::{character_file_name, profile_file, set_profile};
+pub use self::random::{EffectRandom, RandomStream};
+pub use self::session::SessionStatistics;
+#[cfg(feature = "status_server")]
+pub use self::status::StatusServer;
 pub use self::timer::GameTimer;
 pub use self::vulkan::*;
+pub use self::window::{create_window_builder, surface_window, WindowSettings, WindowStatus};
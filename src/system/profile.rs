@@ -0,0 +1,61 @@
+use std::sync::OnceLock;
+
+#[cfg(feature = "debug")]
+use crate::debug::*;
+
+const DEFAULT_PROFILE_DIRECTORY: &str = "client";
+
+static PROFILE_DIRECTORY: OnceLock<String> = OnceLock::new();
+
+/// Every profile has its own settings, window cache and stored credentials,
+/// so multiple clients can run side by side. The default profile uses the
+/// client directory directly.
+pub fn set_profile(profile_name: Option<&str>) {
+    let directory = match profile_name {
+        Some(profile_name) if is_valid_profile_name(profile_name) => format!("{}/profiles/{}", DEFAULT_PROFILE_DIRECTORY, profile_name),
+        Some(_profile_name) => {
+            #[cfg(feature = "debug")]
+            print_debug!(
+                "invalid profile name {}{}{}, using default profile",
+                MAGENTA,
+                _profile_name,
+                NONE
+            );
+
+            DEFAULT_PROFILE_DIRECTORY.to_string()
+        }
+        None => DEFAULT_PROFILE_DIRECTORY.to_string(),
+    };
+
+    std::fs::create_dir_all(&directory).expect("unable to create profile directory");
+
+    #[cfg(feature = "debug")]
+    print_debug!("using profile directory {}{}{}", MAGENTA, directory, NONE);
+
+    PROFILE_DIRECTORY.set(directory).expect("profile was already set");
+}
+
+fn is_valid_profile_name(profile_name: &str) -> bool {
+    !profile_name.is_empty()
+        && profile_name
+            .chars()
+            .all(|character| character.is_ascii_alphanumeric() || character == '-' || character == '_')
+}
+
+pub fn profile_file(file_name: &str) -> String {
+    let directory = PROFILE_DIRECTORY.get().map(String::as_str).unwrap_or(DEFAULT_PROFILE_DIRECTORY);
+
+    format!("{}/{}", directory, file_name)
+}
+
+/// Character names can contain characters that are not allowed in file names,
+/// so they are replaced for files stored per character.
+pub fn character_file_name(character_name: &str) -> String {
+    character_name
+        .chars()
+        .map(|character| match character.is_alphanumeric() || character == '-' {
+            true => character,
+            false => '_',
+        })
+        .collect()
+}
@@ -0,0 +1,89 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Subsystems that draw random numbers for visual effects. Every subsystem
+/// has its own stream, so spawning more particles doesn't change how idle
+/// animations play out and the other way around.
+#[derive(Clone, Copy, Debug)]
+pub enum RandomStream {
+    /// Particles, including the jitter of damage numbers.
+    Particles,
+    IdleAnimations,
+}
+
+const STREAMS: [RandomStream; 2] = [RandomStream::Particles, RandomStream::IdleAnimations];
+
+/// Random numbers for everything that is purely visual. The same seed
+/// always produces the same effects, which keeps replays and golden-image
+/// tests deterministic.
+pub struct EffectRandom {
+    seed: u64,
+    streams: [StdRng; STREAMS.len()],
+}
+
+impl EffectRandom {
+    /// Picks a random seed if none is given.
+    pub fn new(seed: Option<u64>) -> Self {
+        let seed = seed.unwrap_or_else(|| rand::thread_rng().gen());
+        let streams = STREAMS.map(|stream| Self::create_stream(seed, stream));
+
+        Self { seed, streams }
+    }
+
+    fn create_stream(seed: u64, stream: RandomStream) -> StdRng {
+        // Spread the stream index over all bits so neighbouring seeds don't share
+        // streams.
+        let stream_seed = seed ^ (stream as u64 + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+        StdRng::seed_from_u64(stream_seed)
+    }
+
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn stream(&mut self, stream: RandomStream) -> &mut StdRng {
+        &mut self.streams[stream as usize]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn sequence(effect_random: &mut EffectRandom, stream: RandomStream) -> Vec<u32> {
+        (0..16).map(|_| effect_random.stream(stream).gen()).collect()
+    }
+
+    #[test]
+    fn same_seed_produces_same_sequences() {
+        let mut first = EffectRandom::new(Some(42));
+        let mut second = EffectRandom::new(Some(42));
+
+        for stream in STREAMS {
+            assert_eq!(sequence(&mut first, stream), sequence(&mut second, stream));
+        }
+    }
+
+    #[test]
+    fn streams_are_independent() {
+        let mut first = EffectRandom::new(Some(42));
+        let mut second = EffectRandom::new(Some(42));
+
+        // Drawing from one stream must not change what the other one produces.
+        sequence(&mut first, RandomStream::Particles);
+
+        assert_eq!(
+            sequence(&mut first, RandomStream::IdleAnimations),
+            sequence(&mut second, RandomStream::IdleAnimations)
+        );
+
+        // The streams must not produce the same values either, so the first draws of
+        // both are compared on an instance that hasn't been used yet.
+        let mut fresh = EffectRandom::new(Some(42));
+
+        assert_ne!(
+            sequence(&mut fresh, RandomStream::Particles),
+            sequence(&mut fresh, RandomStream::IdleAnimations)
+        );
+    }
+}
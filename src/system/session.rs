@@ -0,0 +1,129 @@
+use std::collections::BTreeMap;
+use std::time::Instant;
+
+use chrono::Local;
+
+use crate::system::profile_file;
+
+/// Statistics about everything the player achieved since logging in or since
+/// the last reset.
+pub struct SessionStatistics {
+    start: Instant,
+    zeny: Option<u32>,
+    zeny_gained: u64,
+    items_looted: usize,
+    monsters_killed: BTreeMap<String, usize>,
+    deaths: usize,
+    base_experience: u64,
+    job_experience: u64,
+}
+
+impl Default for SessionStatistics {
+    fn default() -> Self {
+        Self {
+            start: Instant::now(),
+            zeny: None,
+            zeny_gained: 0,
+            items_looted: 0,
+            monsters_killed: BTreeMap::new(),
+            deaths: 0,
+            base_experience: 0,
+            job_experience: 0,
+        }
+    }
+}
+
+impl SessionStatistics {
+    /// Start a new session. The current amount of zeny is kept, so that the
+    /// next change is still counted correctly.
+    pub fn reset(&mut self) {
+        *self = Self {
+            zeny: self.zeny,
+            ..Default::default()
+        };
+    }
+
+    /// Only increases count as gains, since zeny spent is not a loss of the
+    /// session.
+    pub fn set_zeny(&mut self, zeny: u32) {
+        if let Some(previous) = self.zeny && zeny > previous {
+            self.zeny_gained += (zeny - previous) as u64;
+        }
+
+        self.zeny = Some(zeny);
+    }
+
+    pub fn add_loot(&mut self, count: usize) {
+        self.items_looted += count;
+    }
+
+    pub fn add_kill(&mut self, monster_name: String) {
+        *self.monsters_killed.entry(monster_name).or_default() += 1;
+    }
+
+    pub fn add_death(&mut self) {
+        self.deaths += 1;
+    }
+
+    pub fn add_base_experience(&mut self, experience: u64) {
+        self.base_experience += experience;
+    }
+
+    pub fn add_job_experience(&mut self, experience: u64) {
+        self.job_experience += experience;
+    }
+
+    fn total_kills(&self) -> usize {
+        self.monsters_killed.values().sum()
+    }
+
+    /// Lines shown in the session statistics window.
+    pub fn lines(&self) -> Vec<String> {
+        let minutes = self.start.elapsed().as_secs() / 60;
+
+        let mut lines = vec![
+            format!("session length {}h {}m", minutes / 60, minutes % 60),
+            format!("zeny gained {}", self.zeny_gained),
+            format!("items looted {}", self.items_looted),
+            format!("base experience {}", self.base_experience),
+            format!("job experience {}", self.job_experience),
+            format!("deaths {}", self.deaths),
+            format!("monsters killed {}", self.total_kills()),
+        ];
+
+        lines.extend(
+            self.monsters_killed
+                .iter()
+                .map(|(monster_name, count)| format!("  {} {}", monster_name, count)),
+        );
+
+        lines
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("statistic,name,value\n");
+
+        csv.push_str(&format!("session_seconds,,{}\n", self.start.elapsed().as_secs()));
+        csv.push_str(&format!("zeny_gained,,{}\n", self.zeny_gained));
+        csv.push_str(&format!("items_looted,,{}\n", self.items_looted));
+        csv.push_str(&format!("base_experience,,{}\n", self.base_experience));
+        csv.push_str(&format!("job_experience,,{}\n", self.job_experience));
+        csv.push_str(&format!("deaths,,{}\n", self.deaths));
+
+        for (monster_name, count) in &self.monsters_killed {
+            // Names may contain commas and quotes, so they are quoted and any quotes
+            // inside are doubled.
+            csv.push_str(&format!("monster_killed,\"{}\",{}\n", monster_name.replace('"', "\"\""), count));
+        }
+
+        csv
+    }
+
+    /// Write the statistics to a new file in the profile directory and return
+    /// its path.
+    pub fn export(&self) -> std::io::Result<String> {
+        let path = profile_file(&format!("session_{}.csv", Local::now().format("%Y-%m-%d_%H-%M-%S")));
+        std::fs::write(&path, self.to_csv())?;
+        Ok(path)
+    }
+}
@@ -0,0 +1,172 @@
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde::Serialize;
+use tungstenite::{Message, WebSocket};
+
+#[cfg(feature = "debug")]
+use crate::debug::*;
+use crate::world::Player;
+
+const STATUS_SERVER_ADDRESS: &str = "127.0.0.1:6080";
+/// Time a client has to send its request or to take a response.
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(1);
+
+type Clients = Arc<Mutex<Vec<WebSocket<TcpStream>>>>;
+
+#[derive(Clone, Default, PartialEq, Serialize)]
+struct PlayerStatus {
+    health_points: usize,
+    maximum_health_points: usize,
+    spell_points: usize,
+    maximum_spell_points: usize,
+    map: String,
+    position: [usize; 2],
+    frames_per_second: usize,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+enum StatusEvent<'a> {
+    Status(&'a PlayerStatus),
+    ChangeMap { map: &'a str },
+}
+
+/// Read-only localhost server for overlays and stream tools. `GET /status`
+/// returns the current player status as JSON, WebSocket clients receive every
+/// change as an event.
+pub struct StatusServer {
+    status: Arc<Mutex<PlayerStatus>>,
+    event_sender: Sender<String>,
+}
+
+impl StatusServer {
+    pub fn new() -> Option<Self> {
+        let listener = TcpListener::bind(STATUS_SERVER_ADDRESS)
+            .map_err(|_error| {
+                #[cfg(feature = "debug")]
+                print_debug!(
+                    "failed to start status server on {}{}{}: {}",
+                    MAGENTA,
+                    STATUS_SERVER_ADDRESS,
+                    NONE,
+                    _error
+                );
+            })
+            .ok()?;
+
+        let status = Arc::new(Mutex::new(PlayerStatus::default()));
+        let clients = Clients::default();
+        let (event_sender, event_receiver) = channel();
+
+        let listener_status = status.clone();
+        let listener_clients = clients.clone();
+        std::thread::spawn(move || accept_connections(listener, listener_status, listener_clients));
+        std::thread::spawn(move || broadcast_events(event_receiver, clients));
+
+        #[cfg(feature = "debug")]
+        print_debug!("started status server on {}{}{}", MAGENTA, STATUS_SERVER_ADDRESS, NONE);
+
+        Some(Self { status, event_sender })
+    }
+
+    fn send_event(&self, event: StatusEvent) {
+        // the broadcast thread only stops if the receiver is dropped, so this can't
+        // fail
+        let _ = self.event_sender.send(serde_json::to_string(&event).unwrap());
+    }
+
+    pub fn update(&self, player: &Player, frames_per_second: usize) {
+        let mut status = self.status.lock().unwrap();
+        let common = player.get_common();
+
+        let new_status = PlayerStatus {
+            health_points: common.health_points,
+            maximum_health_points: common.maximum_health_points,
+            spell_points: player.spell_points,
+            maximum_spell_points: player.maximum_spell_points,
+            map: std::mem::take(&mut status.map),
+            position: common.grid_position.into(),
+            frames_per_second,
+        };
+
+        if new_status != *status {
+            self.send_event(StatusEvent::Status(&new_status));
+        }
+
+        *status = new_status;
+    }
+
+    pub fn change_map(&self, map_name: &str) {
+        self.status.lock().unwrap().map = map_name.to_owned();
+        self.send_event(StatusEvent::ChangeMap { map: map_name });
+    }
+}
+
+fn accept_connections(listener: TcpListener, status: Arc<Mutex<PlayerStatus>>, clients: Clients) {
+    for stream in listener.incoming().flatten() {
+        let status = status.clone();
+        let clients = clients.clone();
+
+        // Every client is handled on its own thread, so a client that never sends its
+        // request doesn't keep the others waiting.
+        std::thread::spawn(move || handle_connection(stream, status, clients));
+    }
+}
+
+fn handle_connection(stream: TcpStream, status: Arc<Mutex<PlayerStatus>>, clients: Clients) {
+    if stream.set_read_timeout(Some(CLIENT_TIMEOUT)).is_err() || stream.set_write_timeout(Some(CLIENT_TIMEOUT)).is_err() {
+        return;
+    }
+
+    let mut buffer = [0; 1024];
+    let Ok(length) = stream.peek(&mut buffer) else {
+        return;
+    };
+
+    let request = String::from_utf8_lossy(&buffer[..length]).to_lowercase();
+
+    if request.contains("upgrade: websocket") {
+        // Events are written without blocking, so a client that stops reading is
+        // dropped instead of stalling the broadcast to all others.
+        if let Ok(websocket) = tungstenite::accept(stream)
+            && websocket.get_ref().set_nonblocking(true).is_ok()
+        {
+            clients.lock().unwrap().push(websocket);
+        }
+
+        return;
+    }
+
+    let body = serde_json::to_string(&*status.lock().unwrap()).unwrap();
+    let _ = respond(stream, &body);
+}
+
+fn respond(mut stream: TcpStream, body: &str) -> std::io::Result<()> {
+    let mut request_line = String::new();
+    BufReader::new(&stream).read_line(&mut request_line)?;
+
+    let response = match request_line.starts_with("GET /status ") {
+        true => format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        ),
+        false => "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_owned(),
+    };
+
+    stream.write_all(response.as_bytes())
+}
+
+/// Clients whose socket can't take an event right away are dropped.
+fn broadcast_events(event_receiver: Receiver<String>, clients: Clients) {
+    for event in event_receiver {
+        clients
+            .lock()
+            .unwrap()
+            .retain_mut(|websocket| websocket.write_message(Message::Text(event.clone())).is_ok());
+    }
+}
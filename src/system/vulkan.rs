@@ -1,15 +1,21 @@
+use std::sync::Arc;
+
 use cgmath::{Matrix4, Vector3};
-use vulkano::device::DeviceExtensions;
-use vulkano::instance::InstanceExtensions;
+use vulkano::device::physical::{PhysicalDevice, PhysicalDeviceType};
+use vulkano::device::{DeviceExtensions, Features};
+use vulkano::instance::{Instance, InstanceExtensions};
+use vulkano::swapchain::Surface;
 use vulkano::VulkanLibrary;
 
 #[cfg(feature = "debug")]
 use crate::debug::*;
+use crate::graphics::DeviceId;
 
 pub fn get_instance_extensions(library: &VulkanLibrary) -> InstanceExtensions {
     InstanceExtensions {
         ext_debug_utils: true,
-        //khr_get_physical_device_properties2: true,
+        // Needed to query the memory budget on Vulkan 1.0.
+        khr_get_physical_device_properties2: library.supported_extensions().khr_get_physical_device_properties2,
         ..vulkano_win::required_extensions(library)
     }
 }
@@ -53,29 +59,116 @@ pub fn get_device_extensions() -> DeviceExtensions {
     }
 }
 
-macro_rules! choose_physical_device {
-    ($instance:expr, $surface:expr, $device_extensions:expr) => {{
-        $instance
-            .enumerate_physical_devices()
-            .unwrap()
-            .filter(|p| p.supported_extensions().contains($device_extensions))
-            .filter_map(|p| {
-                p.queue_family_properties()
-                    .iter()
-                    .enumerate()
-                    .position(|(i, q)| q.queue_flags.graphics && p.surface_support(i as u32, $surface).unwrap_or(false))
-                    .map(|i| (p, i as u32))
-            })
-            .min_by_key(|(p, _)| match p.properties().device_type {
-                vulkano::device::physical::PhysicalDeviceType::DiscreteGpu => 0,
-                vulkano::device::physical::PhysicalDeviceType::IntegratedGpu => 1,
-                vulkano::device::physical::PhysicalDeviceType::VirtualGpu => 2,
-                vulkano::device::physical::PhysicalDeviceType::Cpu => 3,
-                vulkano::device::physical::PhysicalDeviceType::Other => 4,
-                _ => 5,
-            })
-            .unwrap()
-    }};
+pub fn get_device_features() -> Features {
+    Features {
+        sampler_anisotropy: true,
+        ..Features::empty()
+    }
+}
+
+fn device_type_score(device_type: PhysicalDeviceType) -> usize {
+    match device_type {
+        PhysicalDeviceType::DiscreteGpu => 0,
+        PhysicalDeviceType::IntegratedGpu => 1,
+        PhysicalDeviceType::VirtualGpu => 2,
+        PhysicalDeviceType::Cpu => 3,
+        PhysicalDeviceType::Other => 4,
+        _ => 5,
+    }
+}
+
+/// All devices that can present to the surface and support the required
+/// extensions and features, together with the index of their graphics queue
+/// family. Discrete GPUs come before integrated ones.
+pub fn get_suitable_devices(
+    instance: &Arc<Instance>,
+    surface: &Surface,
+    device_extensions: &DeviceExtensions,
+    device_features: &Features,
+) -> Vec<(Arc<PhysicalDevice>, u32)> {
+    let mut suitable_devices: Vec<_> = instance
+        .enumerate_physical_devices()
+        .unwrap()
+        .filter(|physical_device| {
+            let is_supported = physical_device.supported_extensions().contains(device_extensions)
+                && physical_device.supported_features().contains(device_features);
+
+            #[cfg(feature = "debug")]
+            if !is_supported {
+                print_debug!(
+                    "device {}{}{} is missing required extensions or features",
+                    MAGENTA,
+                    physical_device.properties().device_name,
+                    NONE
+                );
+            }
+
+            is_supported
+        })
+        .filter_map(|physical_device| {
+            physical_device
+                .queue_family_properties()
+                .iter()
+                .enumerate()
+                .position(|(index, queue)| {
+                    queue.queue_flags.graphics && physical_device.surface_support(index as u32, surface).unwrap_or(false)
+                })
+                .map(|index| (physical_device, index as u32))
+        })
+        .collect();
+
+    // Stable sort, so devices of the same type keep the order of the driver.
+    suitable_devices.sort_by_key(|(physical_device, _)| device_type_score(physical_device.properties().device_type));
+    suitable_devices
+}
+
+/// A device requested by the player, either by its index in the suitable
+/// devices or by its vendor and device id.
+#[derive(Clone, Copy, Debug)]
+pub enum PreferredDevice {
+    Index(usize),
+    Id(DeviceId),
+}
+
+impl PreferredDevice {
+    /// Indices are written in decimal, ids as `vendor:device` in hexadecimal.
+    pub fn parse(text: &str) -> Option<Self> {
+        text.parse()
+            .ok()
+            .map(PreferredDevice::Index)
+            .or_else(|| DeviceId::parse(text).map(PreferredDevice::Id))
+    }
+}
+
+/// Pick the preferred device. If it can't be found, for example because it
+/// lacks a required feature, fall back to the best suitable device.
+pub fn choose_physical_device(
+    suitable_devices: &[(Arc<PhysicalDevice>, u32)],
+    preferred_device: Option<PreferredDevice>,
+) -> (Arc<PhysicalDevice>, u32) {
+    let preferred = preferred_device.and_then(|preferred_device| match preferred_device {
+        PreferredDevice::Index(index) => suitable_devices.get(index),
+        PreferredDevice::Id(device_id) => suitable_devices
+            .iter()
+            .find(|(physical_device, _)| DeviceId::from_device(physical_device) == device_id),
+    });
+
+    #[cfg(feature = "debug")]
+    if let Some(preferred_device) = preferred_device
+        && preferred.is_none()
+    {
+        print_debug!(
+            "preferred device {}{:?}{} is not available, using the best suitable device",
+            MAGENTA,
+            preferred_device,
+            NONE
+        );
+    }
+
+    preferred
+        .or_else(|| suitable_devices.first())
+        .cloned()
+        .expect("no suitable graphics device found")
 }
 
 pub fn multiply_matrix4_and_vector3(matrix: &Matrix4<f32>, vector: Vector3<f32>) -> Vector3<f32> {
@@ -0,0 +1,194 @@
+use std::io::Cursor;
+use std::time::Duration;
+
+use image::io::Reader as ImageReader;
+use image::{EncodableLayout, ImageFormat};
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+use vulkano::swapchain::Surface;
+#[cfg(target_os = "windows")]
+use winit::platform::windows::WindowBuilderExtWindows;
+use winit::window::{Icon, UserAttentionType, Window, WindowBuilder};
+
+#[cfg(feature = "debug")]
+use crate::debug::*;
+use crate::system::profile_file;
+
+const WINDOW_TITLE: &str = "Korangar";
+
+fn load_icon() -> Icon {
+    let file_data = include_bytes!("../../icon.png");
+
+    let reader = ImageReader::with_format(Cursor::new(file_data), ImageFormat::Png);
+    let image_buffer = reader.decode().unwrap().to_rgba8();
+    let image_data = image_buffer.as_bytes().to_vec();
+
+    assert_eq!(image_buffer.width(), image_buffer.height(), "icon must be square");
+    Icon::from_rgba(image_data, image_buffer.width(), image_buffer.height()).unwrap()
+}
+
+#[derive(Default, Serialize, Deserialize)]
+pub struct WindowSettings {
+    /// Hide the decorations of the operating system and draw a title bar as
+    /// part of the interface instead.
+    pub custom_decorations: bool,
+}
+
+impl WindowSettings {
+    pub fn new() -> Self {
+        Self::load().unwrap_or_else(|| {
+            #[cfg(feature = "debug")]
+            print_debug!("failed to load window settings from {}filename{}", MAGENTA, NONE);
+
+            Default::default()
+        })
+    }
+
+    pub fn load() -> Option<Self> {
+        #[cfg(feature = "debug")]
+        print_debug!("loading window settings from {}filename{}", MAGENTA, NONE);
+
+        std::fs::read_to_string(profile_file("window_settings.ron"))
+            .ok()
+            .and_then(|data| ron::from_str(&data).ok())
+    }
+
+    pub fn save(&self) {
+        #[cfg(feature = "debug")]
+        print_debug!("saving window settings to {}filename{}", MAGENTA, NONE);
+
+        let data = ron::ser::to_string_pretty(self, PrettyConfig::new()).unwrap();
+        std::fs::write(profile_file("window_settings.ron"), data).expect("unable to write file");
+    }
+}
+
+impl Drop for WindowSettings {
+    fn drop(&mut self) {
+        self.save();
+    }
+}
+
+pub fn create_window_builder(window_settings: &WindowSettings) -> WindowBuilder {
+    let icon = load_icon();
+
+    // Windows uses a separate icon for the taskbar, which would otherwise be the
+    // default executable icon.
+    #[cfg(target_os = "windows")]
+    let window_builder = WindowBuilder::new().with_taskbar_icon(Some(icon.clone()));
+
+    #[cfg(not(target_os = "windows"))]
+    let window_builder = WindowBuilder::new();
+
+    window_builder
+        .with_title(WINDOW_TITLE.to_string())
+        .with_window_icon(Some(icon))
+        .with_decorations(!window_settings.custom_decorations)
+}
+
+pub fn surface_window(surface: &Surface) -> &Window {
+    surface.object().unwrap().downcast_ref::<Window>().unwrap()
+}
+
+/// Keeps the window title up to date with the current character, map and
+/// latency, and draws attention to the window when something happens while it
+/// is in the background.
+pub struct WindowStatus {
+    character_name: Option<String>,
+    map_name: Option<String>,
+    latency: Option<Duration>,
+    /// Whispers and invitations received since the window lost focus.
+    unread: usize,
+    focused: bool,
+    changed: bool,
+}
+
+impl Default for WindowStatus {
+    fn default() -> Self {
+        Self {
+            character_name: None,
+            map_name: None,
+            latency: None,
+            unread: 0,
+            focused: true,
+            changed: false,
+        }
+    }
+}
+
+impl WindowStatus {
+    pub fn set_character(&mut self, character_name: Option<&str>) {
+        if self.character_name.as_deref() != character_name {
+            self.character_name = character_name.map(String::from);
+            self.changed = true;
+        }
+    }
+
+    pub fn set_map(&mut self, map_name: Option<&str>) {
+        if self.map_name.as_deref() != map_name {
+            self.map_name = map_name.map(String::from);
+            self.changed = true;
+        }
+    }
+
+    pub fn set_latency(&mut self, latency: Option<Duration>) {
+        // Only whole milliseconds are shown.
+        let latency = latency.map(|latency| Duration::from_millis(latency.as_millis() as u64));
+
+        if self.latency != latency {
+            self.latency = latency;
+            self.changed = true;
+        }
+    }
+
+    pub fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+
+        if focused && self.unread > 0 {
+            self.unread = 0;
+            self.changed = true;
+        }
+    }
+
+    /// Count an unread message and flash the taskbar entry or set the urgency
+    /// hint, depending on the platform. Does nothing while the window is
+    /// focused.
+    pub fn notify(&mut self, window: &Window) {
+        if self.focused {
+            return;
+        }
+
+        self.unread += 1;
+        self.changed = true;
+        window.request_user_attention(Some(UserAttentionType::Informational));
+    }
+
+    fn title(&self) -> String {
+        let mut title = match self.unread {
+            0 => WINDOW_TITLE.to_string(),
+            unread => format!("({}) {}", unread, WINDOW_TITLE),
+        };
+
+        // The map is the one shown in the background of the login screen until a
+        // character is selected.
+        if let Some(character_name) = &self.character_name {
+            title.push_str(&format!(" - {}", character_name));
+
+            if let Some(map_name) = &self.map_name {
+                title.push_str(&format!(" on {}", map_name));
+            }
+
+            if let Some(latency) = self.latency {
+                title.push_str(&format!(" ({} ms)", latency.as_millis()));
+            }
+        }
+
+        title
+    }
+
+    pub fn update_title(&mut self, window: &Window) {
+        if self.changed {
+            window.set_title(&self.title());
+            self.changed = false;
+        }
+    }
+}
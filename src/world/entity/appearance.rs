@@ -0,0 +1,109 @@
+use super::layer::{sex_sprite_name, HeadgearSlot, SpriteLayer};
+use crate::graphics::Texture;
+use crate::loaders::{ActionLoader, GameFileLoader, ScriptLoader, SpriteLoader};
+use crate::network::Sex;
+
+/// Hair style, hair color, dye of the clothes and worn headgears and garment
+/// of a player. A color of zero uses the palette of the sprite itself.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Appearance {
+    pub hair_style: usize,
+    pub hair_color: usize,
+    pub clothes_color: usize,
+    /// View ids of the lower, middle and upper headgear.
+    pub headgears: [usize; 3],
+    pub garment: usize,
+}
+
+impl Appearance {
+    pub fn load_head(
+        &self,
+        game_file_loader: &mut GameFileLoader,
+        sprite_loader: &mut SpriteLoader,
+        action_loader: &mut ActionLoader,
+        sex: Sex,
+    ) -> Option<SpriteLayer> {
+        if self.hair_style == 0 {
+            return None;
+        }
+
+        let sex_name = sex_sprite_name(sex);
+        let file_path = format!("ÀÎ°£Á·\\¸Ó¸®Åë\\{}\\{}_{}", sex_name, self.hair_style, sex_name);
+        let palette_file_path =
+            (self.hair_color != 0).then(|| format!("¸Ó¸®\\¸Ó¸®{}_{}_{}.pal", self.hair_style, sex_name, self.hair_color));
+
+        SpriteLayer::load(
+            game_file_loader,
+            sprite_loader,
+            action_loader,
+            &file_path,
+            palette_file_path.as_deref(),
+        )
+    }
+
+    /// Headgears from the lowest to the highest slot, together with the slot
+    /// they are in. A headgear that covers multiple slots is only loaded for
+    /// the highest one.
+    pub fn load_headgears(
+        &self,
+        game_file_loader: &mut GameFileLoader,
+        sprite_loader: &mut SpriteLoader,
+        action_loader: &mut ActionLoader,
+        script_loader: &ScriptLoader,
+        sex: Sex,
+    ) -> Vec<(HeadgearSlot, SpriteLayer)> {
+        let sex_name = sex_sprite_name(sex);
+
+        self.headgears
+            .iter()
+            .enumerate()
+            .filter(|(index, headgear)| **headgear != 0 && !self.headgears[index + 1..].contains(headgear))
+            .filter_map(|(index, headgear)| {
+                let headgear_name = script_loader.get_headgear_sprite_name_from_id(*headgear)?;
+                let file_path = format!("¾Ç¼¼»ç¸®\\{}\\{}{}", sex_name, sex_name, headgear_name);
+                let layer = SpriteLayer::load(game_file_loader, sprite_loader, action_loader, &file_path, None)?;
+
+                Some((HeadgearSlot::ALL[index], layer))
+            })
+            .collect()
+    }
+
+    pub fn load_garment(
+        &self,
+        game_file_loader: &mut GameFileLoader,
+        sprite_loader: &mut SpriteLoader,
+        action_loader: &mut ActionLoader,
+        script_loader: &ScriptLoader,
+        job_id: usize,
+        sex: Sex,
+    ) -> Option<SpriteLayer> {
+        if self.garment == 0 {
+            return None;
+        }
+
+        let garment_name = script_loader.get_garment_sprite_name_from_id(self.garment)?;
+        let job_name = script_loader.get_job_sprite_name_from_id(job_id)?;
+        let sex_name = sex_sprite_name(sex);
+        let file_path = format!("·Îºê\\{}\\{}\\{}_{}", garment_name, sex_name, job_name, sex_name);
+
+        SpriteLayer::load(game_file_loader, sprite_loader, action_loader, &file_path, None)
+    }
+
+    pub fn load_body_palette(
+        &self,
+        game_file_loader: &mut GameFileLoader,
+        sprite_loader: &mut SpriteLoader,
+        script_loader: &ScriptLoader,
+        job_id: usize,
+        sex: Sex,
+    ) -> Option<Texture> {
+        if self.clothes_color == 0 {
+            return None;
+        }
+
+        let job_name = script_loader.get_job_sprite_name_from_id(job_id)?;
+        let palette_file_path = format!("¸ö\\{}_{}_{}.pal", job_name, sex_sprite_name(sex), self.clothes_color);
+
+        sprite_loader.get_palette(&palette_file_path, game_file_loader).ok()
+    }
+}
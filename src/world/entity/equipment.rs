@@ -0,0 +1,131 @@
+use cgmath::Vector3;
+
+use super::layer::{facing_away, sex_sprite_name, SpriteLayer, LAYER_DEPTH_OFFSET};
+use crate::graphics::{Camera, EntityRenderer, Renderer};
+use crate::loaders::{ActionLoader, Actions, AnimationState, GameFileLoader, ScriptLoader, SpriteLoader};
+use crate::network::{EntityId, Sex};
+
+/// How far the afterimages of a weapon lag behind during an attack, in
+/// milliseconds.
+const WEAPON_TRAIL_DELAYS: [u32; 2] = [40, 80];
+
+fn shield_sprite_name(shield: usize) -> Option<&'static str> {
+    match shield {
+        1 => Some("°¡µå"),
+        2 => Some("¹öÅ¬·¯"),
+        3 => Some("½¯µå"),
+        4 => Some("¹Ì·¯½¯µå"),
+        _ => None,
+    }
+}
+
+/// Weapon and shield sprites of a player.
+#[derive(Default)]
+pub struct EquipmentSprites {
+    weapon: Option<SpriteLayer>,
+    shield: Option<SpriteLayer>,
+}
+
+impl EquipmentSprites {
+    pub fn load(
+        game_file_loader: &mut GameFileLoader,
+        sprite_loader: &mut SpriteLoader,
+        action_loader: &mut ActionLoader,
+        script_loader: &ScriptLoader,
+        job_id: usize,
+        sex: Sex,
+        weapon: usize,
+        shield: usize,
+    ) -> Self {
+        let Some(job_name) = script_loader.get_job_sprite_name_from_id(job_id) else {
+            return Self::default();
+        };
+
+        let sex_name = sex_sprite_name(sex);
+
+        let weapon = (weapon != 0)
+            .then(|| script_loader.get_weapon_sprite_name_from_id(weapon))
+            .flatten()
+            .and_then(|weapon_name| {
+                let file_path = format!("ÀÎ°£Á·\\{}\\{}_{}{}", job_name, job_name, sex_name, weapon_name);
+                SpriteLayer::load(game_file_loader, sprite_loader, action_loader, &file_path, None)
+            });
+
+        let shield = shield_sprite_name(shield).and_then(|shield_name| {
+            let file_path = format!("¹æÆÐ\\{}\\{}_{}_{}_¹æÆÐ", job_name, job_name, sex_name, shield_name);
+            SpriteLayer::load(game_file_loader, sprite_loader, action_loader, &file_path, None)
+        });
+
+        Self { weapon, shield }
+    }
+
+    pub fn render<T>(
+        &self,
+        render_target: &mut T::Target,
+        renderer: &T,
+        camera: &dyn Camera,
+        entity_id: EntityId,
+        position: Vector3<f32>,
+        body_actions: &Actions,
+        animation_state: &AnimationState,
+        head_direction: usize,
+    ) where
+        T: Renderer + EntityRenderer,
+    {
+        if let Some(shield) = &self.shield {
+            // The shield is carried on the side facing away from the camera when the
+            // entity looks north.
+            let depth_offset = match facing_away(camera, head_direction) {
+                true => LAYER_DEPTH_OFFSET,
+                false => -LAYER_DEPTH_OFFSET,
+            };
+
+            shield.render(
+                render_target,
+                renderer,
+                camera,
+                entity_id,
+                position,
+                body_actions,
+                animation_state,
+                head_direction,
+                depth_offset,
+            );
+        }
+
+        if let Some(weapon) = &self.weapon {
+            // Only attacks have a fixed duration, so this is the only time the trail is
+            // shown.
+            if animation_state.duration.is_some() {
+                for delay in WEAPON_TRAIL_DELAYS {
+                    let mut trail_state = animation_state.clone();
+                    trail_state.time = trail_state.time.saturating_sub(delay);
+
+                    weapon.render(
+                        render_target,
+                        renderer,
+                        camera,
+                        entity_id,
+                        position,
+                        body_actions,
+                        &trail_state,
+                        head_direction,
+                        -LAYER_DEPTH_OFFSET * 2.0,
+                    );
+                }
+            }
+
+            weapon.render(
+                render_target,
+                renderer,
+                camera,
+                entity_id,
+                position,
+                body_actions,
+                animation_state,
+                head_direction,
+                -LAYER_DEPTH_OFFSET * 3.0,
+            );
+        }
+    }
+}
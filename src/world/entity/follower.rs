@@ -0,0 +1,250 @@
+use std::f32::consts::TAU;
+use std::sync::Arc;
+
+use cgmath::{Array, InnerSpace, Vector2, Vector3};
+
+#[cfg(feature = "debug")]
+use crate::debug::*;
+use crate::graphics::{Camera, EntityRenderer, Renderer};
+use crate::loaders::{ActionLoader, Actions, AnimationState, GameFileLoader, Sprite, SpriteLoader};
+use crate::network::{ClientTick, EntityId};
+
+const OPTION_CART_1: u32 = 0x8;
+const OPTION_FALCON: u32 = 0x10;
+const OPTION_CART_2: u32 = 0x80;
+const OPTION_CART_3: u32 = 0x100;
+const OPTION_CART_4: u32 = 0x200;
+const OPTION_CART_5: u32 = 0x400;
+const OPTION_WARG: u32 = 0x100000;
+
+/// Distance in world units that the cart is pulled behind its owner.
+const CART_DISTANCE: f32 = 5.0;
+/// Distance in world units between the warg and the side of its owner.
+const WARG_DISTANCE: f32 = 4.0;
+/// How quickly the warg catches up with its place, in parts of the remaining
+/// distance per second.
+const WARG_FOLLOW_SPEED: f32 = 4.0;
+const FALCON_RADIUS: f32 = 4.0;
+const FALCON_HEIGHT: f32 = 12.0;
+/// Time in seconds the falcon takes for one circle around its owner.
+const FALCON_ROUND_TIME: f32 = 4.0;
+/// Followers that move less than this per frame are considered standing.
+const MOVEMENT_THRESHOLD: f32 = 0.01;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum FollowerKind {
+    Falcon,
+    Cart(usize),
+    Warg,
+}
+
+impl FollowerKind {
+    fn from_effect_state(effect_state: u32) -> Vec<Self> {
+        let cart = [OPTION_CART_1, OPTION_CART_2, OPTION_CART_3, OPTION_CART_4, OPTION_CART_5]
+            .into_iter()
+            .position(|option| effect_state & option != 0)
+            .map(|index| FollowerKind::Cart(index + 1));
+
+        [
+            (effect_state & OPTION_FALCON != 0).then_some(FollowerKind::Falcon),
+            cart,
+            (effect_state & OPTION_WARG != 0).then_some(FollowerKind::Warg),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    fn sprite_file_path(self) -> String {
+        match self {
+            FollowerKind::Falcon => "ÀÌÆÑÆ®\\¸Å".to_string(),
+            FollowerKind::Cart(1) => "ÀÌÆÑÆ®\\¼Õ¼ö·¹".to_string(),
+            FollowerKind::Cart(cart_type) => format!("ÀÌÆÑÆ®\\¼Õ¼ö·¹{}", cart_type),
+            // There is no separate sprite for the warg, so the one of the wolf monster is
+            // used.
+            FollowerKind::Warg => "¸ó½ºÅÍ\\wolf".to_string(),
+        }
+    }
+}
+
+/// Direction of a sprite when moving along `offset`, using the same
+/// directions as the head direction of entities.
+fn direction_from_offset(offset: Vector2<f32>) -> usize {
+    let angle = offset.y.atan2(offset.x).to_degrees();
+    ((-angle - 90.0) / 45.0).round().rem_euclid(8.0) as usize % 8
+}
+
+/// Inverse of [`direction_from_offset`].
+fn offset_from_direction(direction: usize) -> Vector2<f32> {
+    let angle = (-(direction as f32) * 45.0 - 90.0).to_radians();
+    Vector2::new(angle.cos(), angle.sin())
+}
+
+struct Follower {
+    kind: FollowerKind,
+    sprite: Arc<Sprite>,
+    actions: Arc<Actions>,
+    animation_state: AnimationState,
+    position: Vector3<f32>,
+    direction: usize,
+    /// Angle of the falcon around its owner in radians.
+    angle: f32,
+}
+
+impl Follower {
+    fn update(
+        &mut self,
+        owner_position: Vector3<f32>,
+        owner_direction: usize,
+        movement_speed: usize,
+        delta_time: f32,
+        client_tick: ClientTick,
+    ) {
+        let previous_position = self.position;
+
+        match self.kind {
+            FollowerKind::Falcon => {
+                self.angle = (self.angle + delta_time * TAU / FALCON_ROUND_TIME) % TAU;
+                let (sin, cos) = self.angle.sin_cos();
+
+                self.position = owner_position + Vector3::new(cos * FALCON_RADIUS, FALCON_HEIGHT, sin * FALCON_RADIUS);
+                // The falcon always looks where it is flying.
+                self.direction = direction_from_offset(Vector2::new(-sin, cos));
+            }
+            FollowerKind::Cart(_) => {
+                // The cart is dragged along, so it only moves once the owner gets too far away.
+                let offset = Vector2::new(self.position.x - owner_position.x, self.position.z - owner_position.z);
+
+                if offset.magnitude() > CART_DISTANCE {
+                    let offset = offset.normalize() * CART_DISTANCE;
+                    self.position = owner_position + Vector3::new(offset.x, 0.0, offset.y);
+                    self.direction = direction_from_offset(-offset);
+                }
+
+                self.position.y = owner_position.y;
+            }
+            FollowerKind::Warg => {
+                let side = offset_from_direction((owner_direction + 2) % 8) * WARG_DISTANCE;
+                let target = owner_position + Vector3::new(side.x, 0.0, side.y);
+                let step = (target - self.position) * (delta_time * WARG_FOLLOW_SPEED).min(1.0);
+
+                self.position += step;
+                self.direction = match Vector2::new(step.x, step.z).magnitude() > MOVEMENT_THRESHOLD {
+                    true => direction_from_offset(Vector2::new(step.x, step.z)),
+                    false => owner_direction,
+                };
+            }
+        }
+
+        // The falcon flies all the time, everything else only walks while it moves.
+        if self.kind != FollowerKind::Falcon {
+            let moving = (self.position - previous_position).magnitude() > MOVEMENT_THRESHOLD;
+            let walking = self.animation_state.action == 1;
+
+            match (moving, walking) {
+                (true, false) => self.animation_state.walk(movement_speed, client_tick),
+                (false, true) => self.animation_state.idle(client_tick),
+                _ => {}
+            }
+        }
+
+        self.animation_state.update(client_tick, None);
+    }
+
+    fn render<T>(&self, render_target: &mut T::Target, renderer: &T, camera: &dyn Camera, entity_id: EntityId)
+    where
+        T: Renderer + EntityRenderer,
+    {
+        let camera_direction = camera.get_camera_direction();
+        let (texture, palette, origin, mirror) = self
+            .actions
+            .render(&self.sprite, &self.animation_state, camera_direction, self.direction);
+
+        renderer.render_entity(
+            render_target,
+            camera,
+            texture,
+            palette,
+            self.position,
+            Vector3::new(origin.x, origin.y, 0.0),
+            Vector2::from_value(1.0),
+            Vector2::new(1, 1),
+            Vector2::new(0, 0),
+            mirror,
+            entity_id,
+        );
+    }
+}
+
+/// Falcon, cart and warg of a player. They are positioned relative to their
+/// owner but have their own animations.
+pub struct Followers {
+    followers: Vec<Follower>,
+}
+
+impl Followers {
+    pub fn load(
+        game_file_loader: &mut GameFileLoader,
+        sprite_loader: &mut SpriteLoader,
+        action_loader: &mut ActionLoader,
+        effect_state: u32,
+        owner_position: Vector3<f32>,
+        client_tick: ClientTick,
+    ) -> Self {
+        let followers = FollowerKind::from_effect_state(effect_state)
+            .into_iter()
+            .filter_map(|kind| {
+                let file_path = kind.sprite_file_path();
+                let sprite = sprite_loader.get(&format!("{}.spr", file_path), game_file_loader);
+                let actions = action_loader.get(&format!("{}.act", file_path), game_file_loader);
+
+                match (sprite, actions) {
+                    (Ok(sprite), Ok(actions)) => Some(Follower {
+                        kind,
+                        sprite,
+                        actions,
+                        animation_state: AnimationState::new(client_tick),
+                        position: owner_position,
+                        direction: 0,
+                        angle: 0.0,
+                    }),
+                    _ => {
+                        #[cfg(feature = "debug")]
+                        print_debug!("failed to load follower {}{}{}", MAGENTA, file_path, NONE);
+
+                        None
+                    }
+                }
+            })
+            .collect();
+
+        Self { followers }
+    }
+
+    /// Whether the followers would change with the new effect state.
+    pub fn changes_with(previous_effect_state: u32, effect_state: u32) -> bool {
+        FollowerKind::from_effect_state(previous_effect_state) != FollowerKind::from_effect_state(effect_state)
+    }
+
+    pub fn update(
+        &mut self,
+        owner_position: Vector3<f32>,
+        owner_direction: usize,
+        movement_speed: usize,
+        delta_time: f32,
+        client_tick: ClientTick,
+    ) {
+        self.followers
+            .iter_mut()
+            .for_each(|follower| follower.update(owner_position, owner_direction, movement_speed, delta_time, client_tick));
+    }
+
+    pub fn render<T>(&self, render_target: &mut T::Target, renderer: &T, camera: &dyn Camera, entity_id: EntityId)
+    where
+        T: Renderer + EntityRenderer,
+    {
+        self.followers
+            .iter()
+            .for_each(|follower| follower.render(render_target, renderer, camera, entity_id));
+    }
+}
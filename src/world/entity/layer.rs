@@ -0,0 +1,144 @@
+use std::sync::Arc;
+
+use cgmath::{Array, Vector2, Vector3, Zero};
+
+#[cfg(feature = "debug")]
+use crate::debug::*;
+use crate::graphics::{Camera, EntityRenderer, Renderer, Texture};
+use crate::loaders::{ActionLoader, Actions, AnimationState, GameFileLoader, Sprite, SpriteLoader};
+use crate::network::{EntityId, Sex};
+
+/// Distance that layers are moved towards or away from the camera, so that
+/// they don't fight with the body over the same depth.
+pub const LAYER_DEPTH_OFFSET: f32 = 0.05;
+
+pub fn sex_sprite_name(sex: Sex) -> &'static str {
+    match sex {
+        Sex::Male => "³²",
+        _ => "¿©",
+    }
+}
+
+/// Whether the entity looks north from the point of view of the camera. Some
+/// layers switch sides with the body in that case.
+pub fn facing_away(camera: &dyn Camera, head_direction: usize) -> bool {
+    matches!((camera.get_camera_direction() + head_direction) % 8, 3..=5)
+}
+
+/// The slot a headgear is worn in, from the lowest to the highest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HeadgearSlot {
+    Lower,
+    Middle,
+    Upper,
+}
+
+impl HeadgearSlot {
+    pub const ALL: [HeadgearSlot; 3] = [HeadgearSlot::Lower, HeadgearSlot::Middle, HeadgearSlot::Upper];
+
+    /// Headgears are in front of the head, with the highest slot in front.
+    /// Lower and middle headgears are worn on the face, so they move behind
+    /// the head, but stay in front of the body, when the entity looks north.
+    pub fn depth_offset(self, camera: &dyn Camera, head_direction: usize) -> f32 {
+        let order = self as usize as f32;
+
+        match self != HeadgearSlot::Upper && facing_away(camera, head_direction) {
+            true => -LAYER_DEPTH_OFFSET * (0.6 + order * 0.2),
+            false => -LAYER_DEPTH_OFFSET * (1.25 + order * 0.25),
+        }
+    }
+}
+
+/// A sprite that is drawn together with the body of an entity and follows its
+/// animation.
+pub struct SpriteLayer {
+    sprite: Arc<Sprite>,
+    actions: Arc<Actions>,
+    palette: Option<Texture>,
+}
+
+impl SpriteLayer {
+    /// If a palette is given and can be loaded, the layer is rendered with it
+    /// instead of the palette of the sprite.
+    pub fn load(
+        game_file_loader: &mut GameFileLoader,
+        sprite_loader: &mut SpriteLoader,
+        action_loader: &mut ActionLoader,
+        file_path: &str,
+        palette_file_path: Option<&str>,
+    ) -> Option<Self> {
+        let palette = palette_file_path.and_then(|palette_file_path| {
+            let palette = sprite_loader.get_palette(palette_file_path, game_file_loader).ok();
+
+            #[cfg(feature = "debug")]
+            if palette.is_none() {
+                print_debug!("failed to load palette {}{}{}", MAGENTA, palette_file_path, NONE);
+            }
+
+            palette
+        });
+
+        let sprite = match palette.is_some() {
+            true => sprite_loader.get_indexed(&format!("{}.spr", file_path), game_file_loader),
+            false => sprite_loader.get(&format!("{}.spr", file_path), game_file_loader),
+        };
+        let actions = action_loader.get(&format!("{}.act", file_path), game_file_loader);
+
+        match (sprite, actions) {
+            (Ok(sprite), Ok(actions)) => Some(Self { sprite, actions, palette }),
+            _ => {
+                #[cfg(feature = "debug")]
+                print_debug!("failed to load sprite layer {}{}{}", MAGENTA, file_path, NONE);
+
+                None
+            }
+        }
+    }
+
+    pub fn render<T>(
+        &self,
+        render_target: &mut T::Target,
+        renderer: &T,
+        camera: &dyn Camera,
+        entity_id: EntityId,
+        position: Vector3<f32>,
+        body_actions: &Actions,
+        animation_state: &AnimationState,
+        head_direction: usize,
+        depth_offset: f32,
+    ) where
+        T: Renderer + EntityRenderer,
+    {
+        let camera_direction = camera.get_camera_direction();
+        let (texture, palette, origin, mirror) = self
+            .actions
+            .render(&self.sprite, animation_state, camera_direction, head_direction);
+        let palette = palette.map(|sprite_palette| self.palette.clone().unwrap_or(sprite_palette));
+
+        // Layers are moved so that their anchor point lines up with the anchor point
+        // of the body in the current frame.
+        let body_attach_point = body_actions.attach_point(animation_state, camera_direction, head_direction);
+        let layer_attach_point = self.actions.attach_point(animation_state, camera_direction, head_direction);
+        let attach_offset = body_attach_point
+            .zip(layer_attach_point)
+            .map(|(body_attach_point, layer_attach_point)| {
+                let difference = body_attach_point - layer_attach_point;
+                Vector2::new(-difference.x, difference.y) / 10.0
+            })
+            .unwrap_or_else(Vector2::zero);
+
+        renderer.render_entity(
+            render_target,
+            camera,
+            texture,
+            palette,
+            position,
+            Vector3::new(origin.x + attach_offset.x, origin.y + attach_offset.y, depth_offset),
+            Vector2::from_value(1.0),
+            Vector2::new(1, 1),
+            Vector2::new(0, 0),
+            mirror,
+            entity_id,
+        );
+    }
+}
@@ -1,17 +1,33 @@
+mod appearance;
+mod equipment;
+mod follower;
+mod layer;
+mod mount;
+mod status;
+
 use std::sync::Arc;
 
 use cgmath::{Array, Vector2, Vector3, VectorSpace};
 use derive_new::new;
 use procedural::*;
-
+use rand::Rng;
+
+use self::appearance::Appearance;
+use self::equipment::EquipmentSprites;
+use self::follower::Followers;
+use self::layer::{facing_away, HeadgearSlot, SpriteLayer, LAYER_DEPTH_OFFSET};
+use self::mount::Mount;
+pub use self::status::StatusIndicatorSprites;
+use self::status::StatusIndicators;
+use crate::combat::AttackStats;
 #[cfg(feature = "debug")]
 use crate::graphics::MarkerRenderer;
 #[cfg(feature = "debug")]
 use crate::graphics::ModelVertexBuffer;
-use crate::graphics::{Camera, Color, DeferredRenderer, EntityRenderer, Renderer};
+use crate::graphics::{Camera, Color, DeferredRenderer, EntityRenderer, Renderer, Texture};
 use crate::interface::{InterfaceSettings, PrototypeWindow, Size, Window, WindowCache};
-use crate::loaders::{ActionLoader, Actions, AnimationState, GameFileLoader, ScriptLoader, Sprite, SpriteLoader};
-use crate::network::{CharacterInformation, ClientTick, EntityData, EntityId, StatusType};
+use crate::loaders::{ActionLoader, Actions, AnimationState, GameFileLoader, MonsterDatabase, ScriptLoader, Sprite, SpriteLoader};
+use crate::network::{CharacterInformation, ChatRoom, ClientTick, EntityData, EntityId, LookChange, Sex, StatusType};
 use crate::world::Map;
 #[cfg(feature = "debug")]
 use crate::world::MarkerIdentifier;
@@ -42,6 +58,66 @@ pub struct Movement {
     pub steps_vertex_buffer: Option<ModelVertexBuffer>,
 }
 
+/// Time it takes to be knocked back by a single cell, in milliseconds.
+const KNOCKBACK_TIME_PER_CELL: u32 = 40;
+/// Minimum time a knockback takes, so that short ones are still visible.
+const MINIMUM_KNOCKBACK_TIME: u32 = 120;
+/// Time a jump takes, independent of the distance, in milliseconds.
+const JUMP_TIME: u32 = 500;
+/// Height of the highest point of a jump above the straight line between the
+/// start and the end.
+const JUMP_HEIGHT: f32 = 25.0;
+/// Radius of the shadow below entities in world units.
+const BLOB_SHADOW_RADIUS: f32 = 3.0;
+/// Amount that the ground is darkened by in the center of the shadow.
+const BLOB_SHADOW_OPACITY: f32 = 0.6;
+/// Distance between the shadow and the ground, so that they don't fight over
+/// the depth buffer.
+const BLOB_SHADOW_LIFT: f32 = 0.2;
+/// Longest time that the idle animation of a new entity is ahead, in
+/// milliseconds.
+const MAXIMUM_IDLE_OFFSET: u32 = 2000;
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum SlideKind {
+    Knockback,
+    Jump,
+}
+
+/// Movement to a position that is not walked to, like being knocked back or
+/// jumping.
+#[derive(Clone)]
+struct Slide {
+    kind: SlideKind,
+    from: Vector3<f32>,
+    to: Vector3<f32>,
+    starting_timestamp: u32,
+    duration: u32,
+}
+
+impl Slide {
+    fn position_at(&self, client_tick: ClientTick) -> Vector3<f32> {
+        let elapsed = client_tick.0.saturating_sub(self.starting_timestamp);
+        let progress = (elapsed as f32 / self.duration as f32).min(1.0);
+
+        match self.kind {
+            // Start fast and slow down towards the end, like being pushed.
+            SlideKind::Knockback => {
+                let eased = 1.0 - (1.0 - progress).powi(2);
+                self.from.lerp(self.to, eased)
+            }
+            SlideKind::Jump => {
+                let height = JUMP_HEIGHT * 4.0 * progress * (1.0 - progress);
+                self.from.lerp(self.to, progress) + Vector3::new(0.0, height, 0.0)
+            }
+        }
+    }
+
+    fn is_finished(&self, client_tick: ClientTick) -> bool {
+        client_tick.0.saturating_sub(self.starting_timestamp) >= self.duration
+    }
+}
+
 #[derive(Copy, Clone, PartialEq, Eq)]
 pub enum EntityType {
     Warp,
@@ -51,18 +127,119 @@ pub enum EntityType {
     Monster,
 }
 
+impl EntityType {
+    pub fn from_job_id(job_id: usize) -> Self {
+        match job_id {
+            45 => EntityType::Warp,
+            111 => EntityType::Hidden, // TODO: check that this is correct
+            // 111 | 139 => None,
+            0..=44 | 4000..=5999 => EntityType::Player,
+            46..=999 => EntityType::Npc,
+            1000..=3999 => EntityType::Monster,
+            _ => EntityType::Npc,
+        }
+    }
+
+    /// Path of the sprite and action files without the extension.
+    pub fn sprite_file_path(self, script_loader: &ScriptLoader, job_id: usize) -> String {
+        match self {
+            EntityType::Player => format!("¸ó½ºÅÍ\\b_{}", script_loader.get_job_name_from_id(job_id)),
+            EntityType::Npc => format!("npc\\{}", script_loader.get_job_name_from_id(job_id)),
+            EntityType::Monster => format!("¸ó½ºÅÍ\\{}", script_loader.get_job_name_from_id(job_id)),
+            EntityType::Warp | EntityType::Hidden => format!("npc\\{}", script_loader.get_job_name_from_id(job_id)), // TODO: change
+        }
+    }
+
+    /// Action that is played when the entity attacks.
+    fn attack_action(self) -> usize {
+        match self {
+            EntityType::Player => 10,
+            _ => 2,
+        }
+    }
+}
+
+/// Body sprite of an entity, using the mounted sprite if there is one. With
+/// `indexed` set, the sprite is loaded so that it can be rendered with a
+/// different palette.
+fn load_body_sprite(
+    game_file_loader: &mut GameFileLoader,
+    sprite_loader: &mut SpriteLoader,
+    action_loader: &mut ActionLoader,
+    script_loader: &ScriptLoader,
+    entity_type: EntityType,
+    job_id: usize,
+    mount: Option<Mount>,
+    indexed: bool,
+) -> (Arc<Sprite>, Arc<Actions>) {
+    let load_sprite = |sprite_loader: &mut SpriteLoader, game_file_loader: &mut GameFileLoader, file_path: &str| match indexed {
+        true => sprite_loader.get_indexed(&format!("{}.spr", file_path), game_file_loader),
+        false => sprite_loader.get(&format!("{}.spr", file_path), game_file_loader),
+    };
+
+    let file_path = entity_type.sprite_file_path(script_loader, Mount::job_id(mount, job_id));
+    let sprite = load_sprite(sprite_loader, game_file_loader, &file_path);
+    let actions = action_loader.get(&format!("{}.act", file_path), game_file_loader);
+
+    if let (Ok(sprite), Ok(actions)) = (sprite, actions) {
+        return (sprite, actions);
+    }
+
+    // Not every client has all mounted sprites, so fall back to the regular one.
+    let file_path = entity_type.sprite_file_path(script_loader, job_id);
+    let sprite = load_sprite(sprite_loader, game_file_loader, &file_path).unwrap();
+    let actions = action_loader.get(&format!("{}.act", file_path), game_file_loader).unwrap();
+
+    (sprite, actions)
+}
+
 #[derive(PrototypeElement)]
 pub struct Common {
     pub entity_id: EntityId,
     pub job_id: usize,
     pub health_points: usize,
     pub maximum_health_points: usize,
+    pub level: usize,
     pub movement_speed: usize,
     pub head_direction: usize,
 
     #[hidden_element]
     pub entity_type: EntityType,
     pub active_movement: Option<Movement>,
+    #[hidden_element]
+    active_slide: Option<Slide>,
+    #[hidden_element]
+    jump_pending: bool,
+    #[hidden_element]
+    pvp_rank: Option<(usize, usize)>,
+    #[hidden_element]
+    chat_room: Option<ChatRoom>,
+    #[hidden_element]
+    status_indicators: StatusIndicators,
+    #[hidden_element]
+    sex: Sex,
+    #[hidden_element]
+    effect_state: u32,
+    #[hidden_element]
+    mount: Option<Mount>,
+    #[hidden_element]
+    weapon: u32,
+    #[hidden_element]
+    shield: u32,
+    #[hidden_element]
+    appearance: Appearance,
+    #[hidden_element]
+    body_palette: Option<Texture>,
+    #[hidden_element]
+    head: Option<SpriteLayer>,
+    #[hidden_element]
+    headgears: Vec<(HeadgearSlot, SpriteLayer)>,
+    #[hidden_element]
+    garment: Option<SpriteLayer>,
+    #[hidden_element]
+    equipment: EquipmentSprites,
+    #[hidden_element]
+    followers: Followers,
     pub sprite: Arc<Sprite>,
     pub actions: Arc<Actions>,
     pub grid_position: Vector2<usize>,
@@ -92,28 +269,72 @@ impl Common {
         let movement_speed = entity_data.movement_speed as usize;
         let health_points = entity_data.health_points as usize;
         let maximum_health_points = entity_data.maximum_health_points as usize;
+        let level = entity_data.level;
 
         let active_movement = None;
-
-        let entity_type = match job_id {
-            45 => EntityType::Warp,
-            111 => EntityType::Hidden, // TODO: check that this is correct
-            // 111 | 139 => None,
-            0..=44 | 4000..=5999 => EntityType::Player,
-            46..=999 => EntityType::Npc,
-            1000..=3999 => EntityType::Monster,
-            _ => EntityType::Npc,
+        let active_slide = None;
+        let jump_pending = false;
+        let pvp_rank = None;
+        let chat_room = None;
+        let status_indicators = StatusIndicators::from_states(entity_data.body_state, entity_data.health_state);
+        let sex = entity_data.sex;
+        let effect_state = entity_data.effect_state;
+        let mount = Mount::from_effect_state(effect_state);
+        let weapon = entity_data.weapon;
+        let shield = entity_data.shield;
+        let appearance = Appearance {
+            hair_style: entity_data.head as usize,
+            hair_color: entity_data.head_palette as usize,
+            clothes_color: entity_data.body_palette as usize,
+            headgears: [
+                entity_data.head_bottom as usize,
+                entity_data.head_middle as usize,
+                entity_data.head_top as usize,
+            ],
+            garment: entity_data.robe as usize,
         };
 
-        let file_path = match entity_type {
-            EntityType::Player => format!("¸ó½ºÅÍ\\b_{}", script_loader.get_job_name_from_id(job_id)),
-            EntityType::Npc => format!("npc\\{}", script_loader.get_job_name_from_id(job_id)),
-            EntityType::Monster => format!("¸ó½ºÅÍ\\{}", script_loader.get_job_name_from_id(job_id)),
-            EntityType::Warp | EntityType::Hidden => format!("npc\\{}", script_loader.get_job_name_from_id(job_id)), // TODO: change
-        };
-
-        let sprite = sprite_loader.get(&format!("{}.spr", file_path), game_file_loader).unwrap();
-        let actions = action_loader.get(&format!("{}.act", file_path), game_file_loader).unwrap();
+        let entity_type = EntityType::from_job_id(job_id);
+        let body_palette = appearance.load_body_palette(
+            game_file_loader,
+            sprite_loader,
+            script_loader,
+            Mount::job_id(mount, job_id),
+            sex,
+        );
+        let (sprite, actions) = load_body_sprite(
+            game_file_loader,
+            sprite_loader,
+            action_loader,
+            script_loader,
+            entity_type,
+            job_id,
+            mount,
+            body_palette.is_some(),
+        );
+        let head = appearance.load_head(game_file_loader, sprite_loader, action_loader, sex);
+        let headgears = appearance.load_headgears(game_file_loader, sprite_loader, action_loader, script_loader, sex);
+        let garment = appearance.load_garment(
+            game_file_loader,
+            sprite_loader,
+            action_loader,
+            script_loader,
+            Mount::job_id(mount, job_id),
+            sex,
+        );
+        let equipment = Self::load_equipment(
+            game_file_loader,
+            sprite_loader,
+            action_loader,
+            script_loader,
+            entity_type,
+            job_id,
+            mount,
+            sex,
+            weapon,
+            shield,
+        );
+        let followers = Followers::load(game_file_loader, sprite_loader, action_loader, effect_state, position, client_tick);
         let details = ResourceState::Unavailable;
         let animation_state = AnimationState::new(client_tick);
 
@@ -124,10 +345,28 @@ impl Common {
             job_id,
             head_direction,
             active_movement,
+            active_slide,
+            jump_pending,
+            pvp_rank,
+            chat_room,
+            status_indicators,
+            sex,
+            effect_state,
+            mount,
+            weapon,
+            shield,
+            appearance,
+            body_palette,
+            head,
+            headgears,
+            garment,
+            equipment,
+            followers,
             entity_type,
             movement_speed,
             health_points,
             maximum_health_points,
+            level,
             sprite,
             actions,
             details,
@@ -135,14 +374,173 @@ impl Common {
         }
     }
 
+    fn load_equipment(
+        game_file_loader: &mut GameFileLoader,
+        sprite_loader: &mut SpriteLoader,
+        action_loader: &mut ActionLoader,
+        script_loader: &ScriptLoader,
+        entity_type: EntityType,
+        job_id: usize,
+        mount: Option<Mount>,
+        sex: Sex,
+        weapon: u32,
+        shield: u32,
+    ) -> EquipmentSprites {
+        match entity_type {
+            EntityType::Player if !Mount::hides_equipment(mount) => EquipmentSprites::load(
+                game_file_loader,
+                sprite_loader,
+                action_loader,
+                script_loader,
+                Mount::job_id(mount, job_id),
+                sex,
+                weapon as usize,
+                shield as usize,
+            ),
+            _ => EquipmentSprites::default(),
+        }
+    }
+
+    /// Load all sprites again after the mount, the equipment or the look of
+    /// the entity changed.
+    fn reload_sprites(
+        &mut self,
+        game_file_loader: &mut GameFileLoader,
+        sprite_loader: &mut SpriteLoader,
+        action_loader: &mut ActionLoader,
+        script_loader: &ScriptLoader,
+    ) {
+        self.body_palette = self.appearance.load_body_palette(
+            game_file_loader,
+            sprite_loader,
+            script_loader,
+            Mount::job_id(self.mount, self.job_id),
+            self.sex,
+        );
+        (self.sprite, self.actions) = load_body_sprite(
+            game_file_loader,
+            sprite_loader,
+            action_loader,
+            script_loader,
+            self.entity_type,
+            self.job_id,
+            self.mount,
+            self.body_palette.is_some(),
+        );
+        self.head = self.appearance.load_head(game_file_loader, sprite_loader, action_loader, self.sex);
+        self.headgears = self
+            .appearance
+            .load_headgears(game_file_loader, sprite_loader, action_loader, script_loader, self.sex);
+        self.garment = self.appearance.load_garment(
+            game_file_loader,
+            sprite_loader,
+            action_loader,
+            script_loader,
+            Mount::job_id(self.mount, self.job_id),
+            self.sex,
+        );
+        self.equipment = Self::load_equipment(
+            game_file_loader,
+            sprite_loader,
+            action_loader,
+            script_loader,
+            self.entity_type,
+            self.job_id,
+            self.mount,
+            self.sex,
+            self.weapon,
+            self.shield,
+        );
+    }
+
+    /// Entities that appear together, like on map load, would otherwise all
+    /// play their idle animation in sync.
+    pub fn offset_idle_animation(&mut self, random: &mut impl Rng) {
+        self.animation_state.offset_idle(random.gen_range(0..MAXIMUM_IDLE_OFFSET));
+    }
+
+    /// The mounted and the regular sprites don't share their frames, so the
+    /// current animation starts over when the entity mounts or dismounts. An
+    /// entity that is on its way keeps walking.
+    fn restart_animation(&mut self, client_tick: ClientTick) {
+        match self.active_movement.is_some() {
+            true => self.animation_state.walk(self.movement_speed, client_tick),
+            false => self.animation_state.idle(client_tick),
+        }
+    }
+
+    /// Steps that were already planned keep their timing, only the walk
+    /// animation is adjusted right away.
+    pub fn set_movement_speed(&mut self, movement_speed: usize) {
+        self.movement_speed = movement_speed;
+        self.animation_state.update_walk_speed(movement_speed);
+    }
+
     pub fn set_position(&mut self, map: &Map, position: Vector2<usize>, client_tick: ClientTick) {
         self.grid_position = position;
         self.position = map.get_world_position(position);
         self.active_movement = None;
+        self.active_slide = None;
         self.animation_state.idle(client_tick);
     }
 
-    pub fn update(&mut self, map: &Map, _delta_time: f32, client_tick: ClientTick) {
+    /// The next slide of this entity will be a jump rather than a knockback.
+    pub fn prepare_jump(&mut self) {
+        self.jump_pending = true;
+    }
+
+    /// Move to a new position without walking. The grid position is updated
+    /// right away, while the entity itself slides over.
+    pub fn slide_to(&mut self, map: &Map, position: Vector2<usize>, client_tick: ClientTick) {
+        let distance = self
+            .grid_position
+            .x
+            .abs_diff(position.x)
+            .max(self.grid_position.y.abs_diff(position.y)) as u32;
+
+        let (kind, duration) = match std::mem::take(&mut self.jump_pending) {
+            true => (SlideKind::Jump, JUMP_TIME),
+            false => (SlideKind::Knockback, (distance * KNOCKBACK_TIME_PER_CELL).max(MINIMUM_KNOCKBACK_TIME)),
+        };
+
+        self.active_slide = Some(Slide {
+            kind,
+            from: self.position,
+            to: map.get_world_position(position),
+            starting_timestamp: client_tick.0,
+            duration,
+        });
+
+        self.grid_position = position;
+        self.active_movement = None;
+        self.animation_state.idle(client_tick);
+    }
+
+    /// Cut the current walk short so the entity stops on the given cell. If the
+    /// cell is not on the remaining path, the entity is placed there directly.
+    pub fn stop_at(&mut self, map: &Map, position: Vector2<usize>, client_tick: ClientTick) {
+        let remaining_steps = self
+            .active_movement
+            .as_ref()
+            .and_then(|active_movement| active_movement.steps.iter().position(|(step, _)| *step == position))
+            .map(|index| index + 1);
+
+        match (self.active_movement.as_mut(), remaining_steps) {
+            // A single step is not a walk anymore.
+            (Some(active_movement), Some(remaining_steps)) if remaining_steps > 1 => active_movement.steps.truncate(remaining_steps),
+            _ => self.set_position(map, position, client_tick),
+        }
+    }
+
+    pub fn update(&mut self, map: &Map, delta_time: f32, client_tick: ClientTick) {
+        if let Some(active_slide) = self.active_slide.take() {
+            self.position = active_slide.position_at(client_tick);
+
+            if !active_slide.is_finished(client_tick) {
+                self.active_slide = active_slide.into();
+            }
+        }
+
         if let Some(active_movement) = self.active_movement.take() {
             let last_step = active_movement.steps.last().unwrap();
 
@@ -187,7 +585,15 @@ impl Common {
             }
         }
 
-        self.animation_state.update(client_tick);
+        let movement_speed = self.active_movement.as_ref().map(|_| self.movement_speed);
+        self.animation_state.update(client_tick, movement_speed);
+        self.followers.update(
+            self.position,
+            self.head_direction,
+            self.movement_speed,
+            delta_time,
+            client_tick,
+        );
     }
 
     pub fn move_from_to(&mut self, map: &Map, from: Vector2<usize>, to: Vector2<usize>, starting_timestamp: ClientTick) {
@@ -275,6 +681,7 @@ impl Common {
                 .collect();
 
             self.active_movement = Movement::new(steps, starting_timestamp.0).into();
+            self.active_slide = None;
             self.animation_state.walk(self.movement_speed, starting_timestamp);
         }
     }
@@ -464,14 +871,16 @@ impl Common {
         T: Renderer + EntityRenderer,
     {
         let camera_direction = camera.get_camera_direction();
-        let (texture, position, mirror) = self
+        let (texture, palette, position, mirror) = self
             .actions
             .render(&self.sprite, &self.animation_state, camera_direction, self.head_direction);
+        let palette = palette.map(|sprite_palette| self.body_palette.clone().unwrap_or(sprite_palette));
 
         renderer.render_entity(
             render_target,
             camera,
             texture,
+            palette,
             self.position,
             Vector3::new(position.x, position.y, 0.0),
             Vector2::from_value(1.0),
@@ -480,6 +889,198 @@ impl Common {
             mirror,
             self.entity_id,
         );
+
+        if let Some(head) = &self.head {
+            head.render(
+                render_target,
+                renderer,
+                camera,
+                self.entity_id,
+                self.position,
+                &self.actions,
+                &self.animation_state,
+                self.head_direction,
+                -LAYER_DEPTH_OFFSET,
+            );
+        }
+
+        for (slot, headgear) in &self.headgears {
+            headgear.render(
+                render_target,
+                renderer,
+                camera,
+                self.entity_id,
+                self.position,
+                &self.actions,
+                &self.animation_state,
+                self.head_direction,
+                slot.depth_offset(camera, self.head_direction),
+            );
+        }
+
+        if let Some(garment) = &self.garment {
+            // Garments hang behind the body and cover it when the entity looks north.
+            let depth_offset = match facing_away(camera, self.head_direction) {
+                true => -LAYER_DEPTH_OFFSET * 0.5,
+                false => LAYER_DEPTH_OFFSET * 2.0,
+            };
+
+            garment.render(
+                render_target,
+                renderer,
+                camera,
+                self.entity_id,
+                self.position,
+                &self.actions,
+                &self.animation_state,
+                self.head_direction,
+                depth_offset,
+            );
+        }
+
+        self.equipment.render(
+            render_target,
+            renderer,
+            camera,
+            self.entity_id,
+            self.position,
+            &self.actions,
+            &self.animation_state,
+            self.head_direction,
+        );
+
+        self.followers.render(render_target, renderer, camera, self.entity_id);
+    }
+
+    /// Render a short text centered above the head of the entity.
+    fn render_overhead_text(
+        &self,
+        render_target: &mut <DeferredRenderer as Renderer>::Target,
+        renderer: &DeferredRenderer,
+        camera: &dyn Camera,
+        window_size: Vector2<f32>,
+        text: &str,
+        color: Color,
+        height: f32,
+    ) {
+        const FONT_SIZE: f32 = 14.0;
+
+        let (view_matrix, projection_matrix) = camera.view_projection_matrices();
+        let clip_space_position = (projection_matrix * view_matrix) * (self.position + Vector3::new(0.0, height, 0.0)).extend(1.0);
+        let screen_position = Vector2::new(
+            clip_space_position.x / clip_space_position.w + 1.0,
+            clip_space_position.y / clip_space_position.w + 1.0,
+        );
+        let screen_position = screen_position / 2.0;
+        let final_position = Vector2::new(screen_position.x * window_size.x, screen_position.y * window_size.y);
+
+        let offset = Vector2::new(text.len() as f32 * FONT_SIZE / 4.0, FONT_SIZE);
+        renderer.render_text(render_target, text, final_position - offset, color, FONT_SIZE);
+    }
+
+    pub fn render_status_indicators<T>(
+        &self,
+        render_target: &mut T::Target,
+        renderer: &T,
+        camera: &dyn Camera,
+        status_indicator_sprites: &StatusIndicatorSprites,
+    ) where
+        T: Renderer + EntityRenderer,
+    {
+        status_indicator_sprites.render(
+            render_target,
+            renderer,
+            camera,
+            self.entity_id,
+            self.position,
+            self.status_indicators,
+        );
+    }
+
+    /// Render a soft shadow on the ground below the entity. The shadow stays on
+    /// the ground, lies flat on slopes and fades while the entity is in the
+    /// air.
+    pub fn render_blob_shadow(
+        &self,
+        render_target: &mut <DeferredRenderer as Renderer>::Target,
+        renderer: &DeferredRenderer,
+        camera: &dyn Camera,
+        map: &Map,
+    ) {
+        let ground_height = map.ground_height(self.position.x, self.position.z).unwrap_or(self.position.y);
+        let height_above_ground = (self.position.y - ground_height).max(0.0);
+        let opacity = BLOB_SHADOW_OPACITY * (1.0 - height_above_ground / (JUMP_HEIGHT * 2.0)).max(0.0);
+        let normal = map.ground_normal(self.position.x, self.position.z).unwrap_or_else(Vector3::unit_y);
+        let position = Vector3::new(self.position.x, ground_height, self.position.z) + normal * BLOB_SHADOW_LIFT;
+
+        renderer.render_blob_shadow(render_target, camera, position, normal, BLOB_SHADOW_RADIUS, opacity);
+    }
+
+    /// Show the rank of the entity on PvP maps above its head.
+    pub fn render_pvp_rank(
+        &self,
+        render_target: &mut <DeferredRenderer as Renderer>::Target,
+        renderer: &DeferredRenderer,
+        camera: &dyn Camera,
+        window_size: Vector2<f32>,
+    ) {
+        let Some((ranking, total)) = self.pvp_rank else {
+            return;
+        };
+
+        let text = format!("#{}/{}", ranking, total);
+        self.render_overhead_text(
+            render_target,
+            renderer,
+            camera,
+            window_size,
+            &text,
+            Color::rgb(255, 200, 50),
+            30.0,
+        );
+    }
+
+    /// Show the sign of the chat room the entity opened above its head.
+    pub fn render_chat_room(
+        &self,
+        render_target: &mut <DeferredRenderer as Renderer>::Target,
+        renderer: &DeferredRenderer,
+        camera: &dyn Camera,
+        window_size: Vector2<f32>,
+    ) {
+        let Some(chat_room) = &self.chat_room else {
+            return;
+        };
+
+        self.render_overhead_text(
+            render_target,
+            renderer,
+            camera,
+            window_size,
+            &chat_room.sign_text(),
+            Color::rgb(255, 255, 220),
+            46.0,
+        );
+    }
+
+    /// Show that the player is away from the keyboard. Placed above the PvP
+    /// rank, so both can be seen at the same time.
+    pub fn render_away_badge(
+        &self,
+        render_target: &mut <DeferredRenderer as Renderer>::Target,
+        renderer: &DeferredRenderer,
+        camera: &dyn Camera,
+        window_size: Vector2<f32>,
+    ) {
+        self.render_overhead_text(
+            render_target,
+            renderer,
+            camera,
+            window_size,
+            "AFK",
+            Color::rgb(180, 180, 180),
+            38.0,
+        );
     }
 
     #[cfg(feature = "debug")]
@@ -504,6 +1105,7 @@ pub struct Player {
     pub activity_points: usize,
     pub maximum_spell_points: usize,
     pub maximum_activity_points: usize,
+    pub attack_stats: AttackStats,
 }
 
 impl Player {
@@ -537,9 +1139,18 @@ impl Player {
             activity_points,
             maximum_spell_points,
             maximum_activity_points,
+            attack_stats: AttackStats::default(),
         }
     }
 
+    pub fn get_health_points(&self) -> (usize, usize) {
+        (self.common.health_points, self.common.maximum_health_points)
+    }
+
+    pub fn get_spell_points(&self) -> (usize, usize) {
+        (self.spell_points, self.maximum_spell_points)
+    }
+
     pub fn get_common(&self) -> &Common {
         &self.common
     }
@@ -556,6 +1167,11 @@ impl Player {
             StatusType::SpellPoints(value) => self.spell_points = value as usize,
             StatusType::ActivityPoints(value) => self.activity_points = value as usize,
             StatusType::MaximumActivityPoints(value) => self.maximum_activity_points = value as usize,
+            StatusType::Attack1(value) => self.attack_stats.attack = value as usize,
+            StatusType::Attack2(value) => self.attack_stats.weapon_attack = value as usize,
+            StatusType::MagicAttack1(value) => self.attack_stats.magic_attack = value as usize,
+            StatusType::MagicAttack2(value) => self.attack_stats.weapon_magic_attack = value as usize,
+            StatusType::MovementSpeed(value) => self.common.set_movement_speed(value as usize),
             _ => {}
         }
     }
@@ -696,6 +1312,10 @@ impl Entity {
         self.get_common().job_id
     }
 
+    pub fn get_head_direction(&self) -> usize {
+        self.get_common().head_direction
+    }
+
     pub fn get_entity_type(&self) -> EntityType {
         self.get_common().entity_type
     }
@@ -731,12 +1351,168 @@ impl Entity {
         self.get_common_mut().set_position(map, position, client_tick);
     }
 
+    pub fn prepare_jump(&mut self) {
+        self.get_common_mut().prepare_jump();
+    }
+
+    pub fn set_pvp_rank(&mut self, ranking: usize, total: usize) {
+        self.get_common_mut().pvp_rank = Some((ranking, total));
+    }
+
+    pub fn set_chat_room(&mut self, chat_room: Option<ChatRoom>) {
+        self.get_common_mut().chat_room = chat_room;
+    }
+
+    pub fn get_chat_room(&self) -> Option<&ChatRoom> {
+        self.get_common().chat_room.as_ref()
+    }
+
+    pub fn slide_to(&mut self, map: &Map, position: Vector2<usize>, client_tick: ClientTick) {
+        self.get_common_mut().slide_to(map, position, client_tick);
+    }
+
+    pub fn stop_at(&mut self, map: &Map, position: Vector2<usize>, client_tick: ClientTick) {
+        self.get_common_mut().stop_at(map, position, client_tick);
+    }
+
+    pub fn get_level(&self) -> usize {
+        self.get_common().level
+    }
+
+    pub fn get_health_points(&self) -> (usize, usize) {
+        let common = self.get_common();
+        (common.health_points, common.maximum_health_points)
+    }
+
+    /// Remaining health in percent, if the maximum is known.
+    pub fn get_health_percentage(&self) -> Option<usize> {
+        let common = self.get_common();
+
+        (common.maximum_health_points > 0).then(|| common.health_points * 100 / common.maximum_health_points)
+    }
+
+    /// Lines shown on the info card when hovering the entity.
+    pub fn info_lines(&self, monster_database: &MonsterDatabase) -> Vec<String> {
+        let mut lines = Vec::new();
+
+        if let Some(name) = self.get_details() {
+            lines.push(name.split('#').next().unwrap().to_owned());
+        }
+
+        let monster_info = match self.get_entity_type() {
+            EntityType::Monster => monster_database.get(self.get_job()),
+            _ => None,
+        };
+
+        // the server doesn't always send the level of monsters
+        let level = Some(self.get_level())
+            .filter(|level| *level > 0)
+            .or(monster_info.map(|monster_info| monster_info.level));
+
+        if let Some(level) = level
+            && matches!(self.get_entity_type(), EntityType::Player | EntityType::Monster)
+        {
+            lines.push(format!("level {}", level));
+        }
+
+        if let Some(monster_info) = monster_info {
+            lines.push(format!("{:?} {:?}", monster_info.size, monster_info.race));
+            lines.push(format!("{:?} {}", monster_info.element, monster_info.element_level));
+        }
+
+        if let Some(health_percentage) = self.get_health_percentage() {
+            lines.push(format!("health {}%", health_percentage));
+        }
+
+        lines
+    }
+
     pub fn update_health(&mut self, health_points: usize, maximum_health_points: usize) {
         let common = self.get_common_mut();
         common.health_points = health_points;
         common.maximum_health_points = maximum_health_points;
     }
 
+    /// Only the player receives its own movement speed, other entities tell
+    /// theirs when they appear.
+    pub fn update_movement_speed(&mut self, movement_speed: usize) {
+        self.get_common_mut().set_movement_speed(movement_speed);
+    }
+
+    pub fn update_states(&mut self, body_state: u16, health_state: u16) {
+        self.get_common_mut().status_indicators = StatusIndicators::from_states(body_state, health_state);
+    }
+
+    /// Switch to the mounted sprites or back when the mount of the entity
+    /// changed, and add or remove followers.
+    pub fn update_effect_state(
+        &mut self,
+        game_file_loader: &mut GameFileLoader,
+        sprite_loader: &mut SpriteLoader,
+        action_loader: &mut ActionLoader,
+        script_loader: &ScriptLoader,
+        effect_state: u32,
+        client_tick: ClientTick,
+    ) {
+        let common = self.get_common_mut();
+
+        if Followers::changes_with(common.effect_state, effect_state) {
+            common.followers = Followers::load(
+                game_file_loader,
+                sprite_loader,
+                action_loader,
+                effect_state,
+                common.position,
+                client_tick,
+            );
+        }
+
+        common.effect_state = effect_state;
+
+        let mount = Mount::from_effect_state(effect_state);
+
+        if mount != common.mount {
+            common.mount = mount;
+            common.reload_sprites(game_file_loader, sprite_loader, action_loader, script_loader);
+            common.restart_animation(client_tick);
+        }
+    }
+
+    pub fn update_look(
+        &mut self,
+        game_file_loader: &mut GameFileLoader,
+        sprite_loader: &mut SpriteLoader,
+        action_loader: &mut ActionLoader,
+        script_loader: &ScriptLoader,
+        look_change: LookChange,
+    ) {
+        let common = self.get_common_mut();
+
+        match look_change {
+            LookChange::HairStyle(hair_style) => common.appearance.hair_style = hair_style,
+            LookChange::HairColor(hair_color) => common.appearance.hair_color = hair_color,
+            LookChange::ClothesColor(clothes_color) => common.appearance.clothes_color = clothes_color,
+            LookChange::Weapon { weapon, shield } => {
+                common.weapon = weapon;
+                common.shield = shield;
+            }
+            LookChange::Shield(shield) => common.shield = shield,
+            LookChange::HeadBottom(headgear) => common.appearance.headgears[0] = headgear,
+            LookChange::HeadMiddle(headgear) => common.appearance.headgears[1] = headgear,
+            LookChange::HeadTop(headgear) => common.appearance.headgears[2] = headgear,
+            LookChange::Garment(garment) => common.appearance.garment = garment,
+        }
+
+        common.reload_sprites(game_file_loader, sprite_loader, action_loader, script_loader);
+    }
+
+    pub fn attack(&mut self, attack_duration: u32, client_tick: ClientTick) {
+        let common = self.get_common_mut();
+        let action = common.entity_type.attack_action();
+
+        common.animation_state.attack(action, attack_duration, client_tick);
+    }
+
     pub fn update(&mut self, map: &Map, delta_time: f32, client_tick: ClientTick) {
         self.get_common_mut().update(map, delta_time, client_tick);
     }
@@ -772,6 +1548,59 @@ impl Entity {
             .render_marker(render_target, renderer, camera, marker_identifier, hovered);
     }
 
+    pub fn render_blob_shadow(
+        &self,
+        render_target: &mut <DeferredRenderer as Renderer>::Target,
+        renderer: &DeferredRenderer,
+        camera: &dyn Camera,
+        map: &Map,
+    ) {
+        self.get_common().render_blob_shadow(render_target, renderer, camera, map);
+    }
+
+    pub fn render_status_indicators<T>(
+        &self,
+        render_target: &mut T::Target,
+        renderer: &T,
+        camera: &dyn Camera,
+        status_indicator_sprites: &StatusIndicatorSprites,
+    ) where
+        T: Renderer + EntityRenderer,
+    {
+        self.get_common()
+            .render_status_indicators(render_target, renderer, camera, status_indicator_sprites);
+    }
+
+    pub fn render_pvp_rank(
+        &self,
+        render_target: &mut <DeferredRenderer as Renderer>::Target,
+        renderer: &DeferredRenderer,
+        camera: &dyn Camera,
+        window_size: Vector2<f32>,
+    ) {
+        self.get_common().render_pvp_rank(render_target, renderer, camera, window_size);
+    }
+
+    pub fn render_chat_room(
+        &self,
+        render_target: &mut <DeferredRenderer as Renderer>::Target,
+        renderer: &DeferredRenderer,
+        camera: &dyn Camera,
+        window_size: Vector2<f32>,
+    ) {
+        self.get_common().render_chat_room(render_target, renderer, camera, window_size);
+    }
+
+    pub fn render_away_badge(
+        &self,
+        render_target: &mut <DeferredRenderer as Renderer>::Target,
+        renderer: &DeferredRenderer,
+        camera: &dyn Camera,
+        window_size: Vector2<f32>,
+    ) {
+        self.get_common().render_away_badge(render_target, renderer, camera, window_size);
+    }
+
     pub fn render_status(
         &self,
         render_target: &mut <DeferredRenderer as Renderer>::Target,
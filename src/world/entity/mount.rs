@@ -0,0 +1,62 @@
+const OPTION_RIDING: u32 = 0x20;
+const OPTION_DRAGON: u32 = 0x80000 | 0x800000 | 0x1000000 | 0x2000000 | 0x4000000;
+const OPTION_WUG_RIDER: u32 = 0x200000;
+const OPTION_MADO_GEAR: u32 = 0x400000;
+
+/// What an entity is riding, taken from its effect state.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mount {
+    Peco,
+    Dragon,
+    Wug,
+    MadoGear,
+}
+
+/// Jobs that have a separate body sprite while mounted, together with the
+/// mount and the job that is shown instead.
+const MOUNTED_JOBS: [(usize, Mount, usize); 14] = [
+    (7, Mount::Peco, 13),
+    (14, Mount::Peco, 21),
+    (4008, Mount::Peco, 4014),
+    (4015, Mount::Peco, 4022),
+    (4030, Mount::Peco, 4036),
+    (4037, Mount::Peco, 4044),
+    (4054, Mount::Dragon, 4080),
+    (4060, Mount::Dragon, 4081),
+    (4066, Mount::Peco, 4082),
+    (4073, Mount::Peco, 4083),
+    (4056, Mount::Wug, 4084),
+    (4062, Mount::Wug, 4085),
+    (4058, Mount::MadoGear, 4086),
+    (4064, Mount::MadoGear, 4087),
+];
+
+impl Mount {
+    pub fn from_effect_state(effect_state: u32) -> Option<Self> {
+        [
+            (OPTION_RIDING, Mount::Peco),
+            (OPTION_DRAGON, Mount::Dragon),
+            (OPTION_WUG_RIDER, Mount::Wug),
+            (OPTION_MADO_GEAR, Mount::MadoGear),
+        ]
+        .into_iter()
+        .find(|(option, _)| effect_state & option != 0)
+        .map(|(_, mount)| mount)
+    }
+
+    /// Mado gears have arms of their own, so weapons and shields are not shown
+    /// on them.
+    pub fn hides_equipment(mount: Option<Self>) -> bool {
+        mount == Some(Mount::MadoGear)
+    }
+
+    /// The job whose sprites are used for an entity. Only differs from the
+    /// actual job while the entity is mounted.
+    pub fn job_id(mount: Option<Self>, job_id: usize) -> usize {
+        MOUNTED_JOBS
+            .iter()
+            .find(|(base_job_id, job_mount, _)| *base_job_id == job_id && Some(*job_mount) == mount)
+            .map(|(.., mounted_job_id)| *mounted_job_id)
+            .unwrap_or(job_id)
+    }
+}
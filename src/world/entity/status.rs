@@ -0,0 +1,166 @@
+use std::sync::Arc;
+
+use cgmath::{Array, Vector2, Vector3};
+
+#[cfg(feature = "debug")]
+use crate::debug::*;
+use crate::graphics::{Camera, EntityRenderer, Renderer};
+use crate::loaders::{ActionLoader, Actions, AnimationState, GameFileLoader, Sprite, SpriteLoader};
+use crate::network::{ClientTick, EntityId};
+
+/// Height above the position of the entity where the first indicator is
+/// shown.
+const INDICATOR_HEIGHT: f32 = 24.0;
+/// Vertical distance between two indicators on the same entity.
+const INDICATOR_SPACING: f32 = 6.0;
+
+/// Conditions that are shown as an animation above the head of an entity.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StatusIndicator {
+    Stun,
+    Sleep,
+    Freeze,
+    Silence,
+}
+
+impl StatusIndicator {
+    const ALL: [StatusIndicator; 4] = [
+        StatusIndicator::Stun,
+        StatusIndicator::Sleep,
+        StatusIndicator::Freeze,
+        StatusIndicator::Silence,
+    ];
+
+    fn bit(self) -> u8 {
+        1 << self as u8
+    }
+
+    fn sprite_file_path(self) -> &'static str {
+        match self {
+            StatusIndicator::Stun => "ÀÌÆÑÆ®\\status-stun",
+            StatusIndicator::Sleep => "ÀÌÆÑÆ®\\status-sleep",
+            StatusIndicator::Freeze => "ÀÌÆÑÆ®\\status-freeze",
+            StatusIndicator::Silence => "ÀÌÆÑÆ®\\status-silence",
+        }
+    }
+}
+
+/// The indicators that are active on an entity. Stored as bits, so changing
+/// the state of an entity never allocates.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StatusIndicators(u8);
+
+impl StatusIndicators {
+    /// Body state and health state as sent by the server with the entity or in
+    /// a state change.
+    pub fn from_states(body_state: u16, health_state: u16) -> Self {
+        const BODY_STATE_FREEZE: u16 = 2;
+        const BODY_STATE_STUN: u16 = 3;
+        const BODY_STATE_SLEEP: u16 = 4;
+        const HEALTH_STATE_SILENCE: u16 = 0x04;
+
+        let body_indicator = match body_state {
+            BODY_STATE_FREEZE => Some(StatusIndicator::Freeze),
+            BODY_STATE_STUN => Some(StatusIndicator::Stun),
+            BODY_STATE_SLEEP => Some(StatusIndicator::Sleep),
+            _ => None,
+        };
+
+        let mut indicators = Self::default();
+
+        if let Some(indicator) = body_indicator {
+            indicators.0 |= indicator.bit();
+        }
+
+        if health_state & HEALTH_STATE_SILENCE != 0 {
+            indicators.0 |= StatusIndicator::Silence.bit();
+        }
+
+        indicators
+    }
+
+    pub fn iter(self) -> impl Iterator<Item = StatusIndicator> {
+        StatusIndicator::ALL
+            .into_iter()
+            .filter(move |indicator| self.0 & indicator.bit() != 0)
+    }
+}
+
+/// Sprites of all indicators. They are loaded once and shared by all entities,
+/// which also means that all indicators of the same kind animate in sync.
+pub struct StatusIndicatorSprites {
+    sprites: Vec<(StatusIndicator, Arc<Sprite>, Arc<Actions>, AnimationState)>,
+}
+
+impl StatusIndicatorSprites {
+    pub fn new(
+        game_file_loader: &mut GameFileLoader,
+        sprite_loader: &mut SpriteLoader,
+        action_loader: &mut ActionLoader,
+        client_tick: ClientTick,
+    ) -> Self {
+        let sprites = StatusIndicator::ALL
+            .into_iter()
+            .filter_map(|indicator| {
+                let file_path = indicator.sprite_file_path();
+                let sprite = sprite_loader.get(&format!("{}.spr", file_path), game_file_loader);
+                let actions = action_loader.get(&format!("{}.act", file_path), game_file_loader);
+
+                match (sprite, actions) {
+                    (Ok(sprite), Ok(actions)) => Some((indicator, sprite, actions, AnimationState::new(client_tick))),
+                    _ => {
+                        #[cfg(feature = "debug")]
+                        print_debug!("failed to load status indicator {}{}{}", MAGENTA, file_path, NONE);
+
+                        None
+                    }
+                }
+            })
+            .collect();
+
+        Self { sprites }
+    }
+
+    pub fn update(&mut self, client_tick: ClientTick) {
+        self.sprites
+            .iter_mut()
+            .for_each(|(_, _, _, animation_state)| animation_state.update(client_tick, None));
+    }
+
+    pub fn render<T>(
+        &self,
+        render_target: &mut T::Target,
+        renderer: &T,
+        camera: &dyn Camera,
+        entity_id: EntityId,
+        position: Vector3<f32>,
+        indicators: StatusIndicators,
+    ) where
+        T: Renderer + EntityRenderer,
+    {
+        let camera_direction = camera.get_camera_direction();
+
+        indicators
+            .iter()
+            .filter_map(|indicator| self.sprites.iter().find(|(sprite_indicator, ..)| *sprite_indicator == indicator))
+            .enumerate()
+            .for_each(|(index, (_, sprite, actions, animation_state))| {
+                let (texture, palette, origin, mirror) = actions.render(sprite, animation_state, camera_direction, 0);
+                let height = INDICATOR_HEIGHT + index as f32 * INDICATOR_SPACING;
+
+                renderer.render_entity(
+                    render_target,
+                    camera,
+                    texture,
+                    palette,
+                    position + Vector3::new(0.0, height, 0.0),
+                    Vector3::new(origin.x, origin.y, 0.0),
+                    Vector2::from_value(1.0),
+                    Vector2::new(1, 1),
+                    Vector2::new(0, 0),
+                    mirror,
+                    entity_id,
+                );
+            });
+    }
+}
@@ -0,0 +1,59 @@
+use std::collections::HashSet;
+
+use cgmath::{EuclideanSpace, Vector3};
+
+use super::Map;
+use crate::graphics::PlayerCamera;
+
+/// How fast objects fade in and out, in opacity per second.
+const FADE_SPEED: f32 = 3.0;
+/// Height above the feet of the player that has to stay visible, so objects
+/// the player is standing on don't fade.
+const PLAYER_CENTER_HEIGHT: f32 = 10.0;
+/// How far above the player objects are considered to be a roof.
+const ROOF_SEARCH_HEIGHT: f32 = 300.0;
+
+/// Opacity of every object of the current map. Objects that are between the
+/// camera and the player fade out, so the player is never hidden behind trees
+/// or roofs. Objects above the player can also be hidden completely, which
+/// opens up the roofs of buildings the player is in.
+#[derive(Default)]
+pub struct ObjectFading {
+    opacities: Vec<f32>,
+}
+
+impl ObjectFading {
+    pub fn update(&mut self, map: &Map, player_camera: &PlayerCamera, fade_opacity: f32, hide_roofs: bool, delta_time: f32) {
+        // The map changed, so the previous opacities don't mean anything anymore.
+        if self.opacities.len() != map.object_count() {
+            self.opacities = vec![1.0; map.object_count()];
+        }
+
+        let player_center = player_camera.focus_point() + Vector3::new(0.0, PLAYER_CENTER_HEIGHT, 0.0);
+        let occluding = map.objects_between(player_center, player_camera.camera_position().to_vec());
+        // Maps don't mark which objects are roofs, so anything straight above the player
+        // is treated as one.
+        let covering = match hide_roofs {
+            true => map.objects_between(player_center, player_center + Vector3::new(0.0, ROOF_SEARCH_HEIGHT, 0.0)),
+            false => HashSet::new(),
+        };
+        let step = FADE_SPEED * delta_time;
+
+        for (index, opacity) in self.opacities.iter_mut().enumerate() {
+            let target_opacity = match (covering.contains(&index), occluding.contains(&index)) {
+                (true, _) => 0.0,
+                (false, true) => fade_opacity,
+                (false, false) => 1.0,
+            };
+
+            *opacity = match *opacity > target_opacity {
+                true => (*opacity - step).max(target_opacity),
+                false => (*opacity + step).min(target_opacity),
+            };
+        }
+    }
+
+    pub fn get_opacity(&self, index: usize) -> f32 {
+        self.opacities.get(index).copied().unwrap_or(1.0)
+    }
+}
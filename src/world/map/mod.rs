@@ -1,10 +1,15 @@
+mod fading;
 mod tile;
 
-use cgmath::{Array, EuclideanSpace, Matrix4, Point3, SquareMatrix, Vector2, Vector3};
+use std::collections::HashSet;
+
+use cgmath::{Array, EuclideanSpace, InnerSpace, Matrix4, Point3, Vector2, Vector3};
 use collision::{Aabb3, Frustum, Relation};
 use derive_new::new;
+use image::{Rgba, RgbaImage};
 use procedural::*;
 
+pub use self::fading::ObjectFading;
 pub use self::tile::{Tile, TileType};
 use crate::graphics::*;
 #[cfg(feature = "debug")]
@@ -130,8 +135,12 @@ pub struct Map {
     #[hidden_element]
     water_vertex_buffer: Option<WaterVertexBuffer>,
     #[hidden_element]
-    ground_textures: Vec<Texture>,
+    ground_texture_array: Texture,
     objects: Vec<Object>,
+    /// Inverse of the bounding box matrix of every object, used to check what
+    /// a line crosses. `None` if the bounding box has no volume.
+    #[hidden_element]
+    object_inverse_matrices: Vec<Option<Matrix4<f32>>>,
     light_sources: Vec<LightSource>,
     sound_sources: Vec<SoundSource>,
     effect_sources: Vec<EffectSource>,
@@ -139,6 +148,8 @@ pub struct Map {
     tile_picker_vertex_buffer: TileVertexBuffer,
     #[hidden_element]
     tile_vertex_buffer: ModelVertexBuffer, // make debug only
+    #[hidden_element]
+    _geometry_allocation: TrackedAllocation,
 }
 
 impl Map {
@@ -150,6 +161,36 @@ impl Map {
         y <= self.height
     }
 
+    pub fn get_size(&self) -> Vector2<usize> {
+        Vector2::new(self.width, self.height)
+    }
+
+    /// Top-down image of the walkable and water tiles with one pixel per tile
+    /// and north facing up. Used for the minimap of maps that don't come with
+    /// their own minimap image, so it is square with the map in the center just
+    /// like those.
+    pub fn minimap_image(&self) -> RgbaImage {
+        let side = self.width.max(self.height);
+        let offset = Vector2::new((side - self.width) / 2, (side - self.height) / 2);
+
+        RgbaImage::from_fn(side as u32, side as u32, |x, y| {
+            let x = (x as usize).checked_sub(offset.x).filter(|x| *x < self.width);
+            let y = (y as usize).checked_sub(offset.y).filter(|y| *y < self.height);
+
+            let Some((x, y)) = x.zip(y) else {
+                return Rgba([0; 4]);
+            };
+
+            let tile = self.get_tile(Vector2::new(x, self.height - 1 - y));
+
+            match (tile.is_water(), tile.is_walkable()) {
+                (true, _) => Rgba([70, 110, 170, 255]),
+                (false, true) => Rgba([200, 190, 160, 255]),
+                (false, false) => Rgba([60, 60, 60, 255]),
+            }
+        })
+    }
+
     pub fn get_world_position(&self, position: Vector2<usize>) -> Vector3<f32> {
         let height = self.get_tile(position).average_height();
         Vector3::new(position.x as f32 * 5.0 + 2.5, height, position.y as f32 * 5.0 + 2.5)
@@ -159,16 +200,66 @@ impl Map {
         &self.tiles[position.x + position.y * self.width]
     }
 
+    pub fn get_tile_checked(&self, position: Vector2<usize>) -> Option<&Tile> {
+        (position.x < self.width && position.y < self.height).then(|| self.get_tile(position))
+    }
+
+    /// Tile at a world position, if it is inside of the map.
+    fn tile_at(&self, x: f32, z: f32) -> Option<&Tile> {
+        if x < 0.0 || z < 0.0 {
+            return None;
+        }
+
+        let position = Vector2::new((x / 5.0) as usize, (z / 5.0) as usize);
+        self.get_tile_checked(position)
+    }
+
+    /// Height of the ground at a world position, if it is inside of the map.
+    pub fn ground_height(&self, x: f32, z: f32) -> Option<f32> {
+        self.tile_at(x, z).map(Tile::average_height)
+    }
+
+    /// Direction the ground is facing at a world position, if it is inside of
+    /// the map.
+    pub fn ground_normal(&self, x: f32, z: f32) -> Option<Vector3<f32>> {
+        self.tile_at(x, z).map(Tile::normal)
+    }
+
+    /// Part of the line between the two points, from 0 to 1, that can be
+    /// traveled before getting too close to the ground.
+    pub fn unobstructed_fraction(&self, from: Vector3<f32>, to: Vector3<f32>) -> f32 {
+        const SAMPLE_DISTANCE: f32 = 2.5;
+        const CLEARANCE: f32 = 3.0;
+
+        let offset = to - from;
+        let sample_count = (offset.magnitude() / SAMPLE_DISTANCE).ceil().max(1.0) as usize;
+
+        for sample in 1..=sample_count {
+            let point = from + offset * (sample as f32 / sample_count as f32);
+
+            if let Some(height) = self.ground_height(point.x, point.z)
+                && point.y < height + CLEARANCE
+            {
+                return (sample - 1) as f32 / sample_count as f32;
+            }
+        }
+
+        1.0
+    }
+
+    pub fn sound_sources(&self) -> &[SoundSource] {
+        &self.sound_sources
+    }
+
     pub fn render_ground<T>(&self, render_target: &mut T::Target, renderer: &T, camera: &dyn Camera, time: f32)
     where
-        T: Renderer + GeometryRenderer,
+        T: Renderer + GroundRenderer,
     {
-        renderer.render_geometry(
+        renderer.render_ground(
             render_target,
             camera,
             self.ground_vertex_buffer.clone(),
-            &self.ground_textures,
-            Matrix4::identity(),
+            self.ground_texture_array.clone(),
             time,
         );
     }
@@ -179,6 +270,7 @@ impl Map {
         renderer: &T,
         camera: &dyn Camera,
         client_tick: ClientTick,
+        object_fading: &ObjectFading,
         time: f32,
         #[cfg(feature = "debug")] frustum_culling: bool,
     ) where
@@ -188,10 +280,12 @@ impl Map {
         let frustum = Frustum::from_matrix4(projection_matrix * view_matrix).unwrap();
         let standard_box = OrientedBox::default();
 
-        for object in &self.objects {
+        for (index, object) in self.objects.iter().enumerate() {
+            let opacity = object_fading.get_opacity(index);
+
             #[cfg(feature = "debug")]
             if !frustum_culling {
-                object.render_geometry(render_target, renderer, camera, client_tick, time);
+                object.render_geometry(render_target, renderer, camera, client_tick, opacity, time);
                 continue;
             }
 
@@ -205,11 +299,57 @@ impl Map {
             let culled = matches!(frustum.contains(&collision_bounding_box), Relation::Out);
 
             if !culled {
-                object.render_geometry(render_target, renderer, camera, client_tick, time);
+                object.render_geometry(render_target, renderer, camera, client_tick, opacity, time);
             };
         }
     }
 
+    pub fn object_count(&self) -> usize {
+        self.objects.len()
+    }
+
+    /// Indices of all objects whose bounding box is crossed by the line between
+    /// the two points.
+    pub fn objects_between(&self, from: Vector3<f32>, to: Vector3<f32>) -> HashSet<usize> {
+        self.object_inverse_matrices
+            .iter()
+            .enumerate()
+            .filter(|(_, inverse_matrix)| {
+                // The bounding box matrix maps a cube from -1 to 1 onto the bounding box, so the
+                // line is moved into that space to compare it against the cube.
+                let Some(inverse_matrix) = **inverse_matrix else {
+                    return false;
+                };
+
+                let start = (inverse_matrix * from.extend(1.0)).truncate();
+                let end = (inverse_matrix * to.extend(1.0)).truncate();
+                let direction = end - start;
+
+                let mut entry: f32 = 0.0;
+                let mut exit: f32 = 1.0;
+
+                for axis in 0..3 {
+                    if direction[axis].abs() < f32::EPSILON {
+                        if start[axis].abs() > 1.0 {
+                            return false;
+                        }
+
+                        continue;
+                    }
+
+                    let first = (-1.0 - start[axis]) / direction[axis];
+                    let second = (1.0 - start[axis]) / direction[axis];
+
+                    entry = entry.max(first.min(second));
+                    exit = exit.min(first.max(second));
+                }
+
+                entry <= exit
+            })
+            .map(|(index, _)| index)
+            .collect()
+    }
+
     #[cfg(feature = "debug")]
     pub fn render_bounding(
         &self,
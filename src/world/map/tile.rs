@@ -1,3 +1,4 @@
+use cgmath::{InnerSpace, Vector3};
 use derive_new::new;
 
 const NONE: u8 = 0b00000000;
@@ -30,6 +31,10 @@ impl TileType {
     pub fn is_walkable(&self) -> bool {
         self.0 & WALKABLE != 0
     }
+
+    pub fn is_water(&self) -> bool {
+        self.0 & WATER != 0
+    }
 }
 
 #[derive(Clone, new)]
@@ -46,7 +51,20 @@ impl Tile {
         self.tile_type.is_walkable()
     }
 
+    pub fn is_water(&self) -> bool {
+        self.tile_type.is_water()
+    }
+
     pub fn average_height(&self) -> f32 {
         (self.upper_left_height + self.upper_right_height + self.lower_left_height + self.lower_right_height) / 4.0
     }
+
+    /// Normal of the plane that fits the corners of the tile best. Tiles are 5
+    /// units wide, with the upper left corner at the lowest x and z.
+    pub fn normal(&self) -> Vector3<f32> {
+        let rise_x = (self.upper_right_height + self.lower_right_height - self.upper_left_height - self.lower_left_height) / 2.0;
+        let rise_z = (self.lower_left_height + self.lower_right_height - self.upper_left_height - self.upper_right_height) / 2.0;
+
+        Vector3::new(-rise_x, 5.0, -rise_z).normalize()
+    }
 }
@@ -0,0 +1,72 @@
+use cgmath::{Vector2, Zero};
+
+use crate::graphics::Texture;
+use crate::loaders::{GameFileLoader, TextureLoader};
+use crate::world::Map;
+
+/// Kind of marker shown on the minimap, each with its own color.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MinimapMarker {
+    PartyMember,
+    Npc,
+    Warp,
+}
+
+/// Everything the minimap shows about the current map. All positions are in
+/// tiles.
+#[derive(Clone)]
+pub struct MinimapState {
+    /// Top-down image of the map with north facing up.
+    pub texture: Option<Texture>,
+    pub map_name: String,
+    pub map_size: Vector2<usize>,
+    pub player_position: Vector2<f32>,
+    /// Direction that the camera is looking in, with north being positive y.
+    pub view_direction: Vector2<f32>,
+    pub markers: Vec<(MinimapMarker, Vector2<usize>)>,
+}
+
+impl Default for MinimapState {
+    fn default() -> Self {
+        Self {
+            texture: None,
+            map_name: String::new(),
+            map_size: Vector2::zero(),
+            player_position: Vector2::zero(),
+            view_direction: Vector2::unit_y(),
+            markers: Vec::new(),
+        }
+    }
+}
+
+impl MinimapState {
+    /// Uses the minimap image that comes with the map, or one generated from
+    /// its tiles for maps without an image.
+    pub fn load_map(&mut self, map: &Map, map_name: &str, game_file_loader: &mut GameFileLoader, texture_loader: &mut TextureLoader) {
+        let path = format!("À¯ÀúÀÎÅÍÆäÀÌ½º\\map\\{}.bmp", map_name);
+        let texture = texture_loader
+            .get(&path, game_file_loader)
+            .unwrap_or_else(|_| texture_loader.get_generated(&format!("minimap of {}", map_name), || map.minimap_image()));
+
+        self.texture = Some(texture);
+        self.map_name = map_name.to_string();
+        self.map_size = map.get_size();
+    }
+
+    /// Returns true if anything changed, so the minimap only needs to be
+    /// rendered again when the player moves or turns the camera.
+    pub fn update(
+        &mut self,
+        player_position: Vector2<f32>,
+        view_direction: Vector2<f32>,
+        markers: Vec<(MinimapMarker, Vector2<usize>)>,
+    ) -> bool {
+        let changed = self.player_position != player_position || self.view_direction != view_direction || self.markers != markers;
+
+        self.player_position = player_position;
+        self.view_direction = view_direction;
+        self.markers = markers;
+
+        changed
+    }
+}
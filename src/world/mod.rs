@@ -2,6 +2,7 @@ mod effect;
 mod entity;
 mod light;
 mod map;
+mod minimap;
 mod model;
 mod object;
 mod sound;
@@ -10,6 +11,7 @@ pub use self::effect::*;
 pub use self::entity::*;
 pub use self::light::*;
 pub use self::map::*;
+pub use self::minimap::{MinimapMarker, MinimapState};
 pub use self::model::*;
 pub use self::object::*;
 pub use self::sound::*;
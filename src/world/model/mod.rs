@@ -30,12 +30,13 @@ impl Model {
         camera: &dyn Camera,
         root_transform: &Transform,
         client_tick: ClientTick,
+        opacity: f32,
         time: f32,
     ) where
         T: Renderer + GeometryRenderer,
     {
         self.root_node
-            .render_geometry(render_target, renderer, camera, root_transform, client_tick, time);
+            .render_geometry(render_target, renderer, camera, root_transform, client_tick, opacity, time);
     }
 
     pub fn bounding_box_matrix(bounding_box: &BoundingBox, transform: &Transform) -> Matrix4<f32> {
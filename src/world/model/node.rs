@@ -2,7 +2,7 @@ use cgmath::{Array, Matrix4, SquareMatrix, Vector3, Vector4};
 use derive_new::new;
 use procedural::*;
 
-use crate::graphics::{Camera, GeometryRenderer, ModelVertexBuffer, Renderer, Texture, Transform};
+use crate::graphics::{Camera, GeometryRenderer, ModelVertexBuffer, Renderer, Texture, TrackedAllocation, Transform};
 use crate::loaders::RotationKeyframeData;
 use crate::network::ClientTick;
 use crate::system::multiply_matrix4_and_vector3;
@@ -105,6 +105,8 @@ pub struct Node {
     #[hidden_element]
     pub vertex_buffer: ModelVertexBuffer,
     #[hidden_element]
+    _vertex_allocation: TrackedAllocation,
+    #[hidden_element]
     pub textures: Vec<Texture>,
     pub child_nodes: Vec<Node>,
     pub rotation_keyframes: Vec<RotationKeyframeData>,
@@ -162,6 +164,7 @@ impl Node {
         camera: &dyn Camera,
         transform: &Transform,
         client_tick: ClientTick,
+        opacity: f32,
         time: f32,
     ) where
         T: Renderer + GeometryRenderer,
@@ -172,11 +175,12 @@ impl Node {
             self.vertex_buffer.clone(),
             &self.textures,
             self.world_matrix(transform, client_tick),
+            opacity,
             time,
         );
 
-        self.child_nodes
-            .iter()
-            .for_each(|node| node.render_geometry(render_target, renderer, camera, transform, client_tick, time));
+        self.child_nodes.iter().for_each(|node| {
+            node.render_geometry(render_target, renderer, camera, transform, client_tick, opacity, time)
+        });
     }
 }
@@ -21,12 +21,19 @@ impl Object {
         self.transform.position += offset;
     }
 
-    pub fn render_geometry<T>(&self, render_target: &mut T::Target, renderer: &T, camera: &dyn Camera, client_tick: ClientTick, time: f32)
-    where
+    pub fn render_geometry<T>(
+        &self,
+        render_target: &mut T::Target,
+        renderer: &T,
+        camera: &dyn Camera,
+        client_tick: ClientTick,
+        opacity: f32,
+        time: f32,
+    ) where
         T: Renderer + GeometryRenderer,
     {
         self.model
-            .render_geometry(render_target, renderer, camera, &self.transform, client_tick, time);
+            .render_geometry(render_target, renderer, camera, &self.transform, client_tick, opacity, time);
     }
 
     pub fn get_bounding_box_matrix(&self) -> Matrix4<f32> {